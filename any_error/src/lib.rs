@@ -11,7 +11,7 @@ use std::{
     future::Future,
     ops,
     pin::Pin,
-    sync::Arc,
+    sync::{Arc, RwLock},
     task::{Context, Poll},
 };
 
@@ -109,9 +109,15 @@ pub fn set_error_hook(hook: Arc<dyn ErrorHook>) -> ResetErrorHookOnDrop {
 }
 
 /// Invokes the error hook set by [`set_error_hook`] with the given error.
+///
+/// The error is also passed to the reporter set with [`set_reporter`], if any, whether or not
+/// there is an error hook to handle it.
 pub fn throw(error: impl Into<Error>) -> ErrorId {
+    let error = error.into();
+    let caught = ERROR_HOOK.with_borrow(Option::is_some);
+    report(ErrorSource::Thrown { caught }, || error.clone());
     ERROR_HOOK
-        .with_borrow(|hook| hook.as_ref().map(|hook| hook.throw(error.into())))
+        .with_borrow(|hook| hook.as_ref().map(|hook| hook.throw(error)))
         .unwrap_or_default()
 }
 
@@ -122,6 +128,71 @@ pub fn clear(id: &ErrorId) {
         .unwrap_or_default()
 }
 
+/* Reporting */
+
+/// Where an error passed to the reporter set with [`set_reporter`] comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorSource {
+    /// The error was [thrown](throw), usually because an `Err` was rendered. `caught` is whether
+    /// an error hook, like that of an error boundary, was set to handle it.
+    Thrown {
+        /// Whether an error hook was set to handle the error.
+        caught: bool,
+    },
+    /// A server function, at the given path, returned an error to the client.
+    ServerFn(&'static str),
+    /// The code panicked.
+    Panic,
+}
+
+impl Display for ErrorSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorSource::Thrown { caught: true } => f.write_str("caught error"),
+            ErrorSource::Thrown { caught: false } => {
+                f.write_str("uncaught error")
+            }
+            ErrorSource::ServerFn(path) => write!(f, "server function {path}"),
+            ErrorSource::Panic => f.write_str("panic"),
+        }
+    }
+}
+
+type Reporter = Arc<dyn Fn(&Error, ErrorSource) + Send + Sync>;
+
+static REPORTER: RwLock<Option<Reporter>> = RwLock::new(None);
+
+/// Sets the global reporter, which is called with every error that is thrown or otherwise
+/// [reported](report), on every thread, replacing the previous one.
+///
+/// Unlike an error hook, the reporter does not handle errors: it is meant to log them, or to send
+/// them to an error tracking service.
+pub fn set_reporter(
+    reporter: impl Fn(&Error, ErrorSource) + Send + Sync + 'static,
+) {
+    *REPORTER.write().unwrap_or_else(|e| e.into_inner()) =
+        Some(Arc::new(reporter));
+}
+
+/// Removes the reporter set with [`set_reporter`].
+pub fn clear_reporter() {
+    *REPORTER.write().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// Passes an error to the reporter set with [`set_reporter`], if any. The error is only created
+/// if there is a reporter.
+pub fn report(source: ErrorSource, error: impl FnOnce() -> Error) {
+    let reporter = REPORTER
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+        .map(Arc::clone);
+    if let Some(reporter) = reporter {
+        reporter(&error(), source);
+    }
+}
+
 pin_project_lite::pin_project! {
     /// A [`Future`] that reads the error hook that is set when it is created, and sets this as the
     /// current error hook whenever it is polled.
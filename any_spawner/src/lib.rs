@@ -30,6 +30,10 @@
 use std::{future::Future, pin::Pin, sync::OnceLock};
 use thiserror::Error;
 
+mod local_tasks;
+pub use local_tasks::{Driven, LocalTasks};
+pub mod time;
+
 /// A future that has been pinned.
 pub type PinnedFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
 /// A future that has been pinned.
@@ -340,6 +344,48 @@ impl Executor {
             .map_err(|_| ExecutorError::AlreadySet)
     }
 
+    /// Globally sets an executor that runs every task inside a future that drives it, for
+    /// single-threaded runtimes that cannot spawn tasks of their own, like some edge and
+    /// serverless environments.
+    ///
+    /// Tasks are added to the [`LocalTasks`] that is being polled when they are spawned, and run
+    /// as part of it. Tasks spawned while no [`LocalTasks`] is being polled belong to the thread
+    /// instead: they are never dropped along with a [`LocalTasks`], and are polled whenever any
+    /// [`LocalTasks`] is polled on the same thread, or by [`Executor::poll_local`].
+    ///
+    /// Returns `Err(_)` if a global executor has already been set.
+    pub fn init_local_tasks() -> Result<(), ExecutorError> {
+        let executor_impl = ExecutorFns {
+            spawn: |fut| local_tasks::spawn(fut),
+            spawn_local: local_tasks::spawn,
+            poll_local: local_tasks::poll_detached,
+        };
+        EXECUTOR_FNS
+            .set(executor_impl)
+            .map_err(|_| ExecutorError::AlreadySet)?;
+        local_tasks::ENABLED.store(true, std::sync::atomic::Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Globally sets a deterministic executor for tests, in which tasks only run when
+    /// [`time::run_until_idle`], [`time::advance_time`] or [`time::block_on`] is called on the
+    /// thread that spawned them, and the timers in [`time`] follow a virtual clock that only moves
+    /// when it is advanced.
+    ///
+    /// Returns `Err(_)` if a global executor has already been set.
+    pub fn init_virtual_time() -> Result<(), ExecutorError> {
+        let executor_impl = ExecutorFns {
+            spawn: |fut| time::spawn_virtual(fut),
+            spawn_local: time::spawn_virtual,
+            poll_local: time::run_until_idle,
+        };
+        EXECUTOR_FNS
+            .set(executor_impl)
+            .map_err(|_| ExecutorError::AlreadySet)?;
+        time::ENABLED.store(true, std::sync::atomic::Ordering::Relaxed);
+        Ok(())
+    }
+
     /// Globally sets a custom executor as the executor used to spawn tasks.
     ///
     /// Requires the custom executor to be `Send + Sync` as it will be stored statically.
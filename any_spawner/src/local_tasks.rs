@@ -0,0 +1,215 @@
+use crate::PinnedLocalFuture;
+use futures::{
+    stream::FuturesUnordered,
+    task::{noop_waker_ref, Context},
+    Stream, StreamExt,
+};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    future::Future,
+    mem,
+    pin::Pin,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
+    task::Poll,
+};
+
+/// Whether [`Executor::init_local_tasks`](crate::Executor::init_local_tasks) has been used.
+pub(crate) static ENABLED: AtomicBool = AtomicBool::new(false);
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+    // the tasks of every scope that has been driven on this thread
+    static SCOPES: RefCell<HashMap<u64, Rc<TaskSet>>> = RefCell::new(HashMap::new());
+    // the scopes that are currently being polled, innermost last
+    static CURRENT: RefCell<Vec<Rc<TaskSet>>> = const { RefCell::new(Vec::new()) };
+    // tasks that were spawned while no scope was being polled, which live as long as the thread
+    static DETACHED: Rc<TaskSet> = Rc::new(TaskSet::default());
+}
+
+#[derive(Default)]
+struct TaskSet {
+    spawned: RefCell<Vec<PinnedLocalFuture<()>>>,
+    running: RefCell<FuturesUnordered<PinnedLocalFuture<()>>>,
+}
+
+impl TaskSet {
+    fn enter<T>(self: &Rc<Self>, f: impl FnOnce() -> T) -> T {
+        CURRENT.with(|current| current.borrow_mut().push(Rc::clone(self)));
+        let value = f();
+        CURRENT.with(|current| current.borrow_mut().pop());
+        value
+    }
+
+    /// Polls the tasks until all of them are waiting, including those they spawn in turn.
+    fn poll_tasks(self: &Rc<Self>, cx: &mut Context<'_>) {
+        self.enter(|| loop {
+            let spawned = mem::take(&mut *self.spawned.borrow_mut());
+            let Ok(mut running) = self.running.try_borrow_mut() else {
+                // re-entrant poll of the same scope
+                self.spawned.borrow_mut().extend(spawned);
+                return;
+            };
+            running.extend(spawned);
+            while let Poll::Ready(Some(())) = running.poll_next_unpin(cx) {}
+            drop(running);
+            if self.spawned.borrow().is_empty() {
+                return;
+            }
+        })
+    }
+}
+
+/// Queues a task on the scope that is being polled on this thread.
+pub(crate) fn spawn(fut: PinnedLocalFuture<()>) {
+    let scope = CURRENT.with(|current| current.borrow().last().cloned());
+    match scope {
+        Some(scope) => scope.spawned.borrow_mut().push(fut),
+        None => {
+            DETACHED.with(|detached| detached.spawned.borrow_mut().push(fut))
+        }
+    }
+}
+
+/// Runs the tasks that were spawned outside of any scope, until they are all waiting.
+pub(crate) fn poll_detached() {
+    let detached = DETACHED.with(Rc::clone);
+    detached.poll_tasks(&mut Context::from_waker(noop_waker_ref()));
+}
+
+/// A set of tasks that run inside the futures and streams that drive it, for runtimes that cannot
+/// spawn tasks of their own.
+///
+/// Once the global executor has been set with
+/// [`Executor::init_local_tasks`](crate::Executor::init_local_tasks), tasks that are spawned
+/// while a future returned by [`drive`](LocalTasks::drive) is being polled are added to its set,
+/// and are polled along with it from then on. The tasks are dropped once every clone of the
+/// `LocalTasks` has been dropped.
+///
+/// This lets a server in a single-threaded environment without a multithreaded runtime drive
+/// the tasks of each request, such as loading its resources, from within the request's own
+/// future and response body:
+///
+/// ```rust
+/// use any_spawner::{Executor, LocalTasks};
+/// use futures::channel::oneshot;
+///
+/// Executor::init_local_tasks().unwrap();
+///
+/// let tasks = LocalTasks::new();
+/// let value = futures::executor::block_on(tasks.drive(async {
+///     let (tx, rx) = oneshot::channel();
+///     Executor::spawn(async move {
+///         _ = tx.send(42);
+///     });
+///     rx.await.unwrap()
+/// }));
+/// assert_eq!(value, 42);
+/// ```
+///
+/// Tasks are kept on the thread they were spawned on, so a future that is driven on several
+/// threads only drives the tasks of the thread it is currently being polled on. With any other
+/// executor, [`drive`](LocalTasks::drive) does nothing but poll the future or stream it wraps.
+#[derive(Debug, Clone)]
+pub struct LocalTasks(Arc<ScopeId>);
+
+#[derive(Debug)]
+struct ScopeId(u64);
+
+impl Drop for ScopeId {
+    fn drop(&mut self) {
+        // the thread-local may already have been destroyed if the thread is exiting
+        _ = SCOPES.try_with(|scopes| scopes.borrow_mut().remove(&self.0));
+    }
+}
+
+impl Default for LocalTasks {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LocalTasks {
+    /// Creates an empty set of tasks.
+    pub fn new() -> Self {
+        Self(Arc::new(ScopeId(NEXT_ID.fetch_add(1, Ordering::Relaxed))))
+    }
+
+    /// Wraps a [`Future`] or [`Stream`], so that polling it also polls these tasks.
+    pub fn drive<T>(&self, inner: T) -> Driven<T> {
+        Driven {
+            tasks: self.clone(),
+            inner: Box::pin(inner),
+        }
+    }
+
+    fn poll_with<T>(
+        &self,
+        cx: &mut Context<'_>,
+        poll: impl FnOnce(&mut Context<'_>) -> Poll<T>,
+    ) -> Poll<T> {
+        if !ENABLED.load(Ordering::Relaxed) {
+            return poll(cx);
+        }
+
+        let id = (self.0).0;
+        let set = SCOPES.with(|scopes| {
+            Rc::clone(scopes.borrow_mut().entry(id).or_default())
+        });
+        let value = set.enter(|| poll(cx));
+        set.poll_tasks(cx);
+        // tasks spawned outside of a scope outlive this one, so they are only polled alongside it
+        let detached = DETACHED.with(Rc::clone);
+        detached.poll_tasks(cx);
+        value
+    }
+}
+
+/// A [`Future`] or [`Stream`] that drives a set of [`LocalTasks`] whenever it is polled.
+#[must_use = "futures and streams do nothing unless polled"]
+pub struct Driven<T> {
+    tasks: LocalTasks,
+    inner: Pin<Box<T>>,
+}
+
+impl<T> std::fmt::Debug for Driven<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Driven")
+            .field("tasks", &self.tasks)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Future for Driven<T>
+where
+    T: Future,
+{
+    type Output = T::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let inner = &mut this.inner;
+        this.tasks.poll_with(cx, |cx| inner.as_mut().poll(cx))
+    }
+}
+
+impl<T> Stream for Driven<T>
+where
+    T: Stream,
+{
+    type Item = T::Item;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let inner = &mut this.inner;
+        this.tasks.poll_with(cx, |cx| inner.as_mut().poll_next(cx))
+    }
+}
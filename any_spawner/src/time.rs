@@ -0,0 +1,592 @@
+//! Timers that work with any executor, and that can be driven by a virtual clock in tests.
+//!
+//! [`sleep`], [`interval`] and [`timeout`] use real time by default: tokio's timer inside a tokio
+//! runtime, `setTimeout` in the browser, and otherwise a single timer thread that all of them
+//! share. Once the global executor has been set with [`Executor::init_virtual_time`], tasks only
+//! run when a test asks them to, and time only passes when it is advanced, so that time-based
+//! behavior can be tested quickly and deterministically:
+//!
+//! ```rust
+//! use any_spawner::{time, Executor};
+//! use std::{
+//!     sync::{
+//!         atomic::{AtomicBool, Ordering},
+//!         Arc,
+//!     },
+//!     time::Duration,
+//! };
+//!
+//! Executor::init_virtual_time().unwrap();
+//!
+//! let done = Arc::new(AtomicBool::new(false));
+//! Executor::spawn({
+//!     let done = Arc::clone(&done);
+//!     async move {
+//!         time::sleep(Duration::from_secs(60)).await;
+//!         done.store(true, Ordering::Relaxed);
+//!     }
+//! });
+//!
+//! time::advance_time(Duration::from_secs(59));
+//! assert!(!done.load(Ordering::Relaxed));
+//! time::advance_time(Duration::from_secs(1));
+//! assert!(done.load(Ordering::Relaxed));
+//! ```
+//!
+//! The virtual clock and the tasks belong to the thread that spawned them, so tests that run in
+//! parallel don't affect each other.
+
+use crate::Executor;
+use futures::{
+    executor::{LocalPool, LocalSpawner},
+    task::LocalSpawnExt,
+    Stream,
+};
+use std::{
+    cell::{Cell, RefCell},
+    collections::BTreeMap,
+    future::Future,
+    pin::Pin,
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+    task::{Context, Poll, Waker},
+    time::Duration,
+};
+use thiserror::Error;
+
+/// Whether [`Executor::init_virtual_time`] has been used.
+pub(crate) static ENABLED: AtomicBool = AtomicBool::new(false);
+
+static NEXT_TIMER: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+    static POOL: RefCell<LocalPool> = RefCell::new(LocalPool::new());
+    // kept apart from the pool, so that running tasks can spawn more
+    static SPAWNER: LocalSpawner = POOL.with(|pool| pool.borrow().spawner());
+    static NOW: Cell<Duration> = const { Cell::new(Duration::ZERO) };
+    // wakers of the pending timers, by deadline
+    static TIMERS: RefCell<BTreeMap<(Duration, u64), Waker>> = const { RefCell::new(BTreeMap::new()) };
+}
+
+pub(crate) fn spawn_virtual(fut: crate::PinnedLocalFuture<()>) {
+    SPAWNER.with(|spawner| {
+        spawner
+            .spawn_local(fut)
+            .expect("failed to spawn task on the virtual-time executor")
+    });
+}
+
+/// Whether the global executor was set with [`Executor::init_virtual_time`], so that timers
+/// follow the virtual clock.
+pub fn is_virtual() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// How much virtual time has passed on this thread. Always zero with a real-time executor.
+pub fn now() -> Duration {
+    NOW.with(Cell::get)
+}
+
+/// Runs the tasks spawned on this thread until all of them are waiting, without moving the
+/// clock. With a real-time executor, this is [`Executor::poll_local`].
+pub fn run_until_idle() {
+    if !is_virtual() {
+        Executor::poll_local();
+        return;
+    }
+    POOL.with(|pool| {
+        // a task that runs this would otherwise poll itself
+        if let Ok(mut pool) = pool.try_borrow_mut() {
+            pool.run_until_stalled();
+        }
+    });
+}
+
+/// Moves the virtual clock forward by `duration`, firing the timers that are due on the way in
+/// order, and running the tasks they wake before moving on to the next one.
+///
+/// # Panics
+/// Panics if the global executor was not set with [`Executor::init_virtual_time`].
+#[track_caller]
+pub fn advance_time(duration: Duration) {
+    assert!(
+        is_virtual(),
+        "advance_time() requires Executor::init_virtual_time()"
+    );
+    let target = now() + duration;
+    run_until_idle();
+    while let Some(deadline) = next_deadline().filter(|d| *d <= target) {
+        fire_until(deadline);
+        run_until_idle();
+    }
+    NOW.with(|now| now.set(target));
+    run_until_idle();
+}
+
+/// Runs `future` to completion on this thread, along with the tasks it spawns. With a
+/// virtual-time executor, the clock jumps to the next timer whenever every task is waiting.
+///
+/// # Panics
+/// Panics with virtual time if every task is waiting and no timer is pending, because the future
+/// could then never complete.
+#[track_caller]
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    if !is_virtual() {
+        return futures::executor::block_on(future);
+    }
+    let mut future = std::pin::pin!(future);
+    let mut cx = Context::from_waker(futures::task::noop_waker_ref());
+    loop {
+        if let Poll::Ready(value) = future.as_mut().poll(&mut cx) {
+            return value;
+        }
+        run_until_idle();
+        if let Poll::Ready(value) = future.as_mut().poll(&mut cx) {
+            return value;
+        }
+        match next_deadline() {
+            Some(deadline) => fire_until(deadline),
+            None => panic!(
+                "block_on(): the future is waiting, but no task can run and \
+                 no timer is pending"
+            ),
+        }
+    }
+}
+
+fn next_deadline() -> Option<Duration> {
+    TIMERS.with(|timers| timers.borrow().keys().next().map(|(d, _)| *d))
+}
+
+/// Sets the clock to `deadline`, and wakes the timers that are due by then.
+fn fire_until(deadline: Duration) {
+    if deadline > now() {
+        NOW.with(|now| now.set(deadline));
+    }
+    let due = TIMERS.with(|timers| {
+        let mut timers = timers.borrow_mut();
+        let later = timers.split_off(&(deadline, u64::MAX));
+        std::mem::replace(&mut *timers, later)
+    });
+    for waker in due.into_values() {
+        waker.wake();
+    }
+}
+
+/// Waits until `duration` has passed.
+pub fn sleep(duration: Duration) -> Sleep {
+    Sleep::new(duration)
+}
+
+/// A future that completes once its duration has passed. Created by [`sleep`].
+#[must_use = "futures do nothing unless polled"]
+#[derive(Debug)]
+pub struct Sleep(SleepInner);
+
+#[derive(Debug)]
+enum SleepInner {
+    Virtual { deadline: Duration, id: u64 },
+    Real(real::Alarm),
+}
+
+impl Sleep {
+    fn new(duration: Duration) -> Self {
+        Self(if is_virtual() {
+            SleepInner::Virtual {
+                deadline: now() + duration,
+                id: NEXT_TIMER.fetch_add(1, Ordering::Relaxed),
+            }
+        } else {
+            SleepInner::Real(real::Alarm::new(duration))
+        })
+    }
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        match &self.0 {
+            SleepInner::Virtual { deadline, id } => {
+                let key = (*deadline, *id);
+                if now() >= *deadline {
+                    TIMERS.with(|timers| timers.borrow_mut().remove(&key));
+                    Poll::Ready(())
+                } else {
+                    TIMERS.with(|timers| {
+                        timers.borrow_mut().insert(key, cx.waker().clone())
+                    });
+                    Poll::Pending
+                }
+            }
+            SleepInner::Real(alarm) => alarm.poll(cx),
+        }
+    }
+}
+
+// real timers are cancelled when their `Alarm` is dropped
+impl Drop for Sleep {
+    fn drop(&mut self) {
+        if let SleepInner::Virtual { deadline, id } = self.0 {
+            // the thread-local may already have been destroyed if the thread is exiting
+            _ = TIMERS
+                .try_with(|timers| timers.borrow_mut().remove(&(deadline, id)));
+        }
+    }
+}
+
+/// Yields every `period`, starting one `period` from now.
+pub fn interval(period: Duration) -> Interval {
+    Interval {
+        period,
+        sleep: sleep(period),
+    }
+}
+
+/// A stream that yields at a fixed period. Created by [`interval`].
+#[must_use = "streams do nothing unless polled"]
+#[derive(Debug)]
+pub struct Interval {
+    period: Duration,
+    sleep: Sleep,
+}
+
+impl Stream for Interval {
+    type Item = ();
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<()>> {
+        if Pin::new(&mut self.sleep).poll(cx).is_pending() {
+            return Poll::Pending;
+        }
+        // virtual ticks keep to the period exactly
+        let next = match self.sleep.0 {
+            SleepInner::Virtual { deadline, .. } => {
+                Sleep(SleepInner::Virtual {
+                    deadline: deadline + self.period,
+                    id: NEXT_TIMER.fetch_add(1, Ordering::Relaxed),
+                })
+            }
+            SleepInner::Real(_) => sleep(self.period),
+        };
+        self.sleep = next;
+        Poll::Ready(Some(()))
+    }
+}
+
+/// The error returned by [`timeout`] when the future did not complete in time.
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("deadline has elapsed")]
+pub struct Elapsed;
+
+/// Waits for `future`, but for no longer than `duration`.
+pub async fn timeout<F: Future>(
+    duration: Duration,
+    future: F,
+) -> Result<F::Output, Elapsed> {
+    use futures::future::{select, Either};
+
+    let future = std::pin::pin!(future);
+    match select(future, sleep(duration)).await {
+        Either::Left((value, _)) => Ok(value),
+        Either::Right(_) => Err(Elapsed),
+    }
+}
+
+mod real {
+    use std::{
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc, Mutex,
+        },
+        task::{Context, Poll, Waker},
+        time::Duration,
+    };
+
+    /// A flag that is set once a real timer fires. The timer is cancelled if the alarm is
+    /// dropped before that.
+    #[derive(Debug)]
+    pub(super) struct Alarm {
+        state: Arc<State>,
+        // `None` for a zero duration, which needs no timer
+        timer: Option<Timer>,
+    }
+
+    #[derive(Debug, Default)]
+    struct State {
+        fired: AtomicBool,
+        waker: Mutex<Option<Waker>>,
+    }
+
+    impl State {
+        fn fire(&self) {
+            self.fired.store(true, Ordering::Release);
+            let waker =
+                self.waker.lock().unwrap_or_else(|e| e.into_inner()).take();
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+        }
+    }
+
+    impl Alarm {
+        pub(super) fn new(duration: Duration) -> Self {
+            let state = Arc::new(State::default());
+            let timer = if duration.is_zero() {
+                state.fired.store(true, Ordering::Release);
+                None
+            } else {
+                Some(start(duration, Arc::clone(&state)))
+            };
+            Self { state, timer }
+        }
+
+        pub(super) fn poll(&self, cx: &mut Context<'_>) -> Poll<()> {
+            if self.state.fired.load(Ordering::Acquire) {
+                return Poll::Ready(());
+            }
+            *self.state.waker.lock().unwrap_or_else(|e| e.into_inner()) =
+                Some(cx.waker().clone());
+            // it may have fired before the waker was stored
+            if self.state.fired.load(Ordering::Acquire) {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        }
+    }
+
+    impl Drop for Alarm {
+        fn drop(&mut self) {
+            if let Some(timer) = self.timer.take() {
+                if !self.state.fired.load(Ordering::Acquire) {
+                    timer.cancel();
+                }
+            }
+        }
+    }
+
+    /// A running timer, which can be cancelled.
+    #[cfg(not(target_family = "wasm"))]
+    #[derive(Debug)]
+    enum Timer {
+        #[cfg(feature = "tokio")]
+        Tokio(tokio::task::AbortHandle),
+        Thread(timer_thread::Key),
+    }
+
+    #[cfg(not(target_family = "wasm"))]
+    impl Timer {
+        fn cancel(self) {
+            match self {
+                #[cfg(feature = "tokio")]
+                Timer::Tokio(task) => task.abort(),
+                Timer::Thread(key) => timer_thread::cancel(key),
+            }
+        }
+    }
+
+    #[cfg(not(target_family = "wasm"))]
+    fn start(duration: Duration, state: Arc<State>) -> Timer {
+        // tokio's own timer, when the sleep is created inside a runtime
+        #[cfg(feature = "tokio")]
+        if let Ok(handle) = tokio::runtime::Handle::try_current() {
+            let task = handle.spawn(async move {
+                tokio::time::sleep(duration).await;
+                state.fire();
+            });
+            return Timer::Tokio(task.abort_handle());
+        }
+        Timer::Thread(timer_thread::schedule(
+            std::time::Instant::now() + duration,
+            state,
+        ))
+    }
+
+    /// A single thread that fires the alarms of every executor without a timer of its own.
+    #[cfg(not(target_family = "wasm"))]
+    mod timer_thread {
+        use super::State;
+        use std::{
+            collections::BTreeMap,
+            sync::{
+                atomic::{AtomicU64, Ordering},
+                Arc, Condvar, Mutex, OnceLock,
+            },
+            time::Instant,
+        };
+
+        /// The deadline and id of a scheduled alarm.
+        pub(super) type Key = (Instant, u64);
+
+        type Alarms = BTreeMap<Key, Arc<State>>;
+
+        static NEXT_ALARM: AtomicU64 = AtomicU64::new(0);
+
+        pub(super) fn schedule(deadline: Instant, state: Arc<State>) -> Key {
+            let (alarms, changed) = alarms();
+            let key = (deadline, NEXT_ALARM.fetch_add(1, Ordering::Relaxed));
+            alarms
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .insert(key, state);
+            changed.notify_one();
+            key
+        }
+
+        pub(super) fn cancel(key: Key) {
+            let (alarms, _) = alarms();
+            let state = alarms
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .remove(&key);
+            // dropped without the lock
+            drop(state);
+        }
+
+        fn alarms() -> &'static (Mutex<Alarms>, Condvar) {
+            static ALARMS: OnceLock<(Mutex<Alarms>, Condvar)> = OnceLock::new();
+            ALARMS.get_or_init(|| {
+                std::thread::Builder::new()
+                    .name("any_spawner-timer".into())
+                    .spawn(run)
+                    .expect("could not start the timer thread");
+                Default::default()
+            })
+        }
+
+        fn run() {
+            let (alarms, changed) = alarms();
+            let mut pending = alarms.lock().unwrap_or_else(|e| e.into_inner());
+            loop {
+                let now = Instant::now();
+                let later = pending.split_off(&(now, u64::MAX));
+                let due = std::mem::replace(&mut *pending, later);
+                if !due.is_empty() {
+                    // fired without the lock, as waking a task may schedule another alarm
+                    drop(pending);
+                    due.into_values().for_each(|state| state.fire());
+                    pending = alarms.lock().unwrap_or_else(|e| e.into_inner());
+                    continue;
+                }
+                pending = match pending.keys().next() {
+                    Some(&(deadline, _)) => {
+                        changed
+                            .wait_timeout(pending, deadline - now)
+                            .unwrap_or_else(|e| e.into_inner())
+                            .0
+                    }
+                    None => {
+                        changed.wait(pending).unwrap_or_else(|e| e.into_inner())
+                    }
+                };
+            }
+        }
+    }
+
+    /// A `setTimeout` that has been scheduled, and the id of its alarm.
+    #[cfg(all(target_family = "wasm", feature = "wasm-bindgen"))]
+    #[derive(Debug)]
+    struct Timer {
+        id: u64,
+        handle: f64,
+    }
+
+    #[cfg(all(target_family = "wasm", feature = "wasm-bindgen"))]
+    impl Timer {
+        fn cancel(self) {
+            timeouts::cancel(self);
+        }
+    }
+
+    #[cfg(all(target_family = "wasm", feature = "wasm-bindgen"))]
+    fn start(duration: Duration, state: Arc<State>) -> Timer {
+        timeouts::schedule(duration, state)
+    }
+
+    /// The alarms waiting for a `setTimeout`, which all call the same function with the id of
+    /// their alarm, so that cancelling one leaves nothing behind.
+    #[cfg(all(target_family = "wasm", feature = "wasm-bindgen"))]
+    mod timeouts {
+        use super::{State, Timer};
+        use std::{
+            cell::{Cell, RefCell},
+            collections::HashMap,
+            sync::Arc,
+            time::Duration,
+        };
+        use wasm_bindgen_futures::{
+            js_sys::{self, Function},
+            wasm_bindgen::{closure::Closure, JsCast, JsValue},
+        };
+
+        thread_local! {
+            static PENDING: RefCell<HashMap<u64, Arc<State>>> =
+                RefCell::new(HashMap::new());
+            static NEXT_ID: Cell<u64> = const { Cell::new(0) };
+            // lives as long as the page
+            static FIRE: Function = Closure::<dyn Fn(f64)>::new(|id: f64| {
+                let state = PENDING.with(|pending| {
+                    pending.borrow_mut().remove(&(id as u64))
+                });
+                if let Some(state) = state {
+                    state.fire();
+                }
+            })
+            .into_js_value()
+            .unchecked_into();
+        }
+
+        fn global(name: &str) -> Function {
+            js_sys::Reflect::get(&js_sys::global(), &name.into())
+                .ok()
+                .and_then(|f| f.dyn_into::<Function>().ok())
+                .unwrap_or_else(|| panic!("{name} is not available"))
+        }
+
+        pub(super) fn schedule(duration: Duration, state: Arc<State>) -> Timer {
+            let id = NEXT_ID.with(|next| next.replace(next.get() + 1));
+            PENDING.with(|pending| pending.borrow_mut().insert(id, state));
+            let handle = FIRE.with(|fire| {
+                global("setTimeout").call3(
+                    &JsValue::NULL,
+                    fire,
+                    &JsValue::from_f64(duration.as_millis() as f64),
+                    &JsValue::from_f64(id as f64),
+                )
+            });
+            Timer {
+                id,
+                handle: handle
+                    .ok()
+                    .and_then(|h| h.as_f64())
+                    .unwrap_or_default(),
+            }
+        }
+
+        pub(super) fn cancel(timer: Timer) {
+            _ = global("clearTimeout")
+                .call1(&JsValue::NULL, &JsValue::from_f64(timer.handle));
+            let state =
+                PENDING.with(|pending| pending.borrow_mut().remove(&timer.id));
+            drop(state);
+        }
+    }
+
+    #[cfg(all(target_family = "wasm", not(feature = "wasm-bindgen")))]
+    #[derive(Debug)]
+    enum Timer {}
+
+    #[cfg(all(target_family = "wasm", not(feature = "wasm-bindgen")))]
+    impl Timer {
+        fn cancel(self) {
+            match self {}
+        }
+    }
+
+    #[cfg(all(target_family = "wasm", not(feature = "wasm-bindgen")))]
+    fn start(_duration: Duration, _state: Arc<State>) -> Timer {
+        panic!("any_spawner timers on wasm require the `wasm-bindgen` feature");
+    }
+}
@@ -0,0 +1,78 @@
+use any_spawner::{Executor, LocalTasks};
+use futures::{channel::oneshot, executor::block_on, stream, StreamExt};
+use std::{cell::Cell, rc::Rc};
+// All tests in this file use the same executor.
+
+#[test]
+fn tasks_run_inside_the_future_that_drives_them() {
+    _ = Executor::init_local_tasks();
+
+    let value = block_on(LocalTasks::new().drive(async {
+        let (tx, rx) = oneshot::channel();
+        Executor::spawn(async move {
+            // tasks can spawn tasks of their own
+            let (inner_tx, inner_rx) = oneshot::channel();
+            Executor::spawn_local(async move {
+                _ = inner_tx.send(20);
+            });
+            _ = tx.send(inner_rx.await.unwrap() + 22);
+        });
+        rx.await.unwrap()
+    }));
+    assert_eq!(value, 42);
+}
+
+#[test]
+fn tasks_are_shared_by_a_future_and_a_stream() {
+    _ = Executor::init_local_tasks();
+
+    let tasks = LocalTasks::new();
+    let ran = Rc::new(Cell::new(false));
+    let (tx, rx) = oneshot::channel::<()>();
+    block_on(tasks.drive({
+        let ran = Rc::clone(&ran);
+        async move {
+            Executor::spawn_local(async move {
+                _ = rx.await;
+                ran.set(true);
+            });
+        }
+    }));
+    assert!(!ran.get());
+
+    // the task is still running while the response body is streamed
+    let chunks = block_on(
+        tasks
+            .drive(stream::once(async move {
+                tx.send(()).unwrap();
+                Executor::tick().await;
+                "done"
+            }))
+            .collect::<Vec<_>>(),
+    );
+    assert_eq!(chunks, ["done"]);
+    assert!(ran.get());
+}
+
+#[test]
+fn tasks_spawned_outside_a_scope_outlive_the_scopes_that_poll_them() {
+    _ = Executor::init_local_tasks();
+
+    let ran = Rc::new(Cell::new(false));
+    let (tx, rx) = oneshot::channel::<()>();
+    Executor::spawn_local({
+        let ran = Rc::clone(&ran);
+        async move {
+            _ = rx.await;
+            ran.set(true);
+        }
+    });
+
+    block_on(LocalTasks::new().drive(async {}));
+    assert!(!ran.get());
+
+    // the task was not dropped along with the first scope
+    tx.send(()).unwrap();
+    block_on(LocalTasks::new().drive(async {}));
+    assert!(ran.get());
+}
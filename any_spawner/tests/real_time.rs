@@ -0,0 +1,50 @@
+use any_spawner::time;
+use futures::{executor::block_on, future::join_all};
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+#[test]
+fn timers_without_a_runtime_share_a_thread() {
+    let fired = Arc::new(Mutex::new(Vec::new()));
+    let start = Instant::now();
+    block_on(join_all([30, 10, 20].map(|ms| {
+        let fired = Arc::clone(&fired);
+        async move {
+            time::sleep(Duration::from_millis(ms)).await;
+            fired.lock().unwrap().push(ms);
+        }
+    })));
+
+    assert_eq!(*fired.lock().unwrap(), [10, 20, 30]);
+    assert!(start.elapsed() >= Duration::from_millis(30));
+    assert_eq!(
+        block_on(time::timeout(
+            Duration::from_millis(10),
+            futures::future::pending::<()>()
+        )),
+        Err(time::Elapsed)
+    );
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test(start_paused = true)]
+async fn timers_in_a_tokio_runtime_use_its_clock() {
+    // with the clock paused, tokio skips ahead instead of waiting for an hour
+    let start = tokio::time::Instant::now();
+    time::sleep(Duration::from_secs(60 * 60)).await;
+    assert!(start.elapsed() >= Duration::from_secs(60 * 60));
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn dropping_a_sleep_cancels_its_timer() {
+    let metrics = tokio::runtime::Handle::current().metrics();
+    let sleep = time::sleep(Duration::from_secs(60 * 60));
+    assert_eq!(metrics.num_alive_tasks(), 1);
+
+    drop(sleep);
+    tokio::task::yield_now().await;
+    assert_eq!(metrics.num_alive_tasks(), 0);
+}
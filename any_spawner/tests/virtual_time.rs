@@ -0,0 +1,68 @@
+use any_spawner::{time, Executor};
+use futures::StreamExt;
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+// All tests in this file use the same executor.
+
+#[test]
+fn timers_fire_in_order_as_time_advances() {
+    _ = Executor::init_virtual_time();
+    let start = time::now();
+
+    let log = Rc::new(RefCell::new(Vec::new()));
+    for secs in [30, 10, 20] {
+        let log = Rc::clone(&log);
+        Executor::spawn_local(async move {
+            time::sleep(Duration::from_secs(secs)).await;
+            log.borrow_mut().push((secs, time::now() - start));
+        });
+    }
+
+    time::run_until_idle();
+    assert!(log.borrow().is_empty());
+
+    time::advance_time(Duration::from_secs(25));
+    assert_eq!(
+        *log.borrow(),
+        [(10, Duration::from_secs(10)), (20, Duration::from_secs(20))]
+    );
+    assert_eq!(time::now() - start, Duration::from_secs(25));
+
+    time::advance_time(Duration::from_secs(5));
+    assert_eq!(log.borrow().len(), 3);
+}
+
+#[test]
+fn intervals_and_timeouts_use_virtual_time() {
+    _ = Executor::init_virtual_time();
+    let real_start = Instant::now();
+
+    let ticks = Rc::new(RefCell::new(0));
+    Executor::spawn_local({
+        let ticks = Rc::clone(&ticks);
+        async move {
+            let mut interval = time::interval(Duration::from_secs(60));
+            while interval.next().await.is_some() {
+                *ticks.borrow_mut() += 1;
+            }
+        }
+    });
+    time::advance_time(Duration::from_secs(60 * 60));
+    assert_eq!(*ticks.borrow(), 60);
+
+    let slow = time::timeout(
+        Duration::from_secs(5),
+        time::sleep(Duration::from_secs(10)),
+    );
+    assert_eq!(time::block_on(slow), Err(time::Elapsed));
+    let fast = time::timeout(Duration::from_secs(5), async {
+        time::sleep(Duration::from_secs(1)).await;
+        42
+    });
+    assert_eq!(time::block_on(fast), Ok(42));
+
+    assert!(real_start.elapsed() < Duration::from_secs(5));
+}
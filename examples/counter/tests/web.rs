@@ -175,3 +175,20 @@ async fn inc() {
         .outer_html()
     );
 }
+
+// leptos::testing::browser takes care of the setup and teardown above, and dispatches events
+#[wasm_bindgen_test]
+async fn inc_with_test_helpers() {
+    use leptos::testing::browser::*;
+
+    let root = mount_to_body_for_test(
+        || view! { <SimpleCounter initial_value=0 step=1/> },
+    );
+    let inc = root.get("button:last-of-type");
+
+    click(&inc);
+    click(&inc);
+    next_animation_frame().await;
+
+    assert_eq!(root.get("span").text_content().unwrap(), "Value: 2!");
+}
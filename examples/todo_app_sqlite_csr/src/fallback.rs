@@ -1,7 +1,7 @@
 use axum::{
     body::Body,
     extract::State,
-    http::{Request, Response, StatusCode, Uri},
+    http::Request,
     response::{Html, IntoResponse, Response as AxumResponse},
 };
 use leptos::{
@@ -9,18 +9,13 @@ use leptos::{
     hydration::{AutoReload, HydrationScripts},
     prelude::*,
 };
-use tower::util::ServiceExt;
-use tower_http::services::ServeDir;
 
 pub async fn file_or_index_handler(
-    uri: Uri,
     State(options): State<LeptosOptions>,
+    req: Request<Body>,
 ) -> AxumResponse {
-    let root = options.site_root.clone();
-    let res = get_static_file(uri.clone(), &root).await.unwrap();
-
-    if res.status() == StatusCode::OK {
-        res.into_response()
+    if let Some(res) = leptos_axum::serve_static_file(&options, &req).await {
+        res
     } else {
         Html(view! {
             <!DOCTYPE html>
@@ -38,22 +33,3 @@ pub async fn file_or_index_handler(
         }.to_html()).into_response()
     }
 }
-
-async fn get_static_file(
-    uri: Uri,
-    root: &str,
-) -> Result<Response<Body>, (StatusCode, String)> {
-    let req = Request::builder()
-        .uri(uri.clone())
-        .body(Body::empty())
-        .unwrap();
-    // `ServeDir` implements `tower::Service` so we can call it with `tower::ServiceExt::oneshot`
-    // This path is relative to the cargo root
-    match ServeDir::new(root).oneshot(req).await {
-        Ok(res) => Ok(res.into_response()),
-        Err(err) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
-            format!("Something went wrong: {err}"),
-        )),
-    }
-}
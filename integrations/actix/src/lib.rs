@@ -25,13 +25,17 @@ use leptos::{
     context::{provide_context, use_context},
     hydration::IslandsRouterNavigation,
     prelude::expect_context,
-    reactive::{computed::ScopedFuture, owner::Owner},
+    reactive::{
+        computed::ScopedFuture,
+        owner::{with_context, Owner, TaskCancellation},
+    },
+    request::{self, RequestParts, ResponseHeaders},
     IntoView,
 };
 use leptos_integration_utils::{
-    BoxedFnOnce, ExtendResponse, PinnedFuture, PinnedStream,
+    BoxedFnOnce, DisconnectGuard, ExtendResponse, PinnedFuture, PinnedStream,
 };
-use leptos_meta::ServerMetaContext;
+use leptos_meta::{AppManifest, ServerMetaContext};
 use leptos_router::{
     components::provide_server_redirect,
     location::RequestUrl,
@@ -39,7 +43,7 @@ use leptos_router::{
     ExpandOptionals, Method, PathSegment, RouteList, RouteListing, SsrMode,
 };
 use once_cell::sync::Lazy;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use send_wrapper::SendWrapper;
 use server_fn::{
     error::ServerFnErrorErr, redirect::REDIRECT_HEADER,
@@ -51,16 +55,33 @@ use std::{
     future::Future,
     ops::{Deref, DerefMut},
     path::Path,
-    sync::Arc,
+    pin::pin,
+    ptr,
+    sync::{Arc, Weak},
 };
 
+mod ws;
+#[doc(inline)]
+pub use leptos_integration_utils::cache;
+#[doc(inline)]
+pub use leptos_integration_utils::health;
+#[cfg(feature = "session")]
+#[doc(inline)]
+pub use leptos_integration_utils::session;
+#[doc(inline)]
+pub use leptos_integration_utils::shutdown;
+#[doc(inline)]
+pub use leptos_integration_utils::sitemap;
+pub use ws::*;
+
 /// This struct lets you define headers and override the status of the Response from an Element or a Server Function
 /// Typically contained inside of a ResponseOptions. Setting this is useful for cookies and custom responses.
 #[derive(Debug, Clone, Default)]
 pub struct ResponseParts {
     /// If provided, this will overwrite any other status code for this response.
     pub status: Option<StatusCode>,
-    /// The map of headers that should be added to the response.
+    /// The map of headers that should be added to the response, replacing any headers it
+    /// already has with the same name.
     pub headers: header::HeaderMap,
 }
 
@@ -115,21 +136,97 @@ impl DerefMut for Request {
     }
 }
 
+fn request_parts(req: &HttpRequest) -> RequestParts {
+    let mut headers = request::HeaderMap::with_capacity(req.headers().len());
+    for (name, value) in req.headers() {
+        if let (Ok(name), Ok(value)) = (
+            request::HeaderName::from_bytes(name.as_str().as_bytes()),
+            request::HeaderValue::from_bytes(value.as_bytes()),
+        ) {
+            headers.append(name, value);
+        }
+    }
+    let version = match req.version() {
+        http::Version::HTTP_09 => request::Version::HTTP_09,
+        http::Version::HTTP_10 => request::Version::HTTP_10,
+        http::Version::HTTP_2 => request::Version::HTTP_2,
+        http::Version::HTTP_3 => request::Version::HTTP_3,
+        _ => request::Version::HTTP_11,
+    };
+    RequestParts {
+        method: request::Method::from_bytes(req.method().as_str().as_bytes())
+            .unwrap_or_default(),
+        uri: req.uri().to_string().parse().unwrap_or_default(),
+        version,
+        headers,
+        remote_addr: req.peer_addr(),
+        extensions: Default::default(),
+    }
+}
+
+fn response_headers(res_options: &ResponseOptions) -> ResponseHeaders {
+    let res_options = res_options.clone();
+    ResponseHeaders::new(move |name, value| {
+        let (Ok(name), Ok(value)) = (
+            header::HeaderName::from_bytes(name.as_str().as_bytes()),
+            header::HeaderValue::from_bytes(value.as_bytes()),
+        ) else {
+            return false;
+        };
+        let sent = res_options.is_sent();
+        res_options.append_header(name, value);
+        !sent
+    })
+}
+
 /// Allows you to override details of the HTTP response like the status code and add Headers/Cookies.
+///
+/// ## Streaming
+///
+/// When a page is streamed, the status and headers are sent along with the first chunk of HTML.
+/// Anything set while the shell renders, i.e., outside `<Suspense/>`, is always applied, so a
+/// `NotFound` component used as the router's fallback can set a 404 in every [`SsrMode`]:
+/// ```
+/// use actix_web::http::StatusCode;
+/// use leptos::prelude::*;
+///
+/// #[component]
+/// fn NotFound() -> impl IntoView {
+///     if let Some(res) = use_context::<leptos_actix::ResponseOptions>() {
+///         res.set_status(StatusCode::NOT_FOUND);
+///     }
+///     view! { <h1>"Not Found"</h1> }
+/// }
+/// ```
+///
+/// Inside `<Suspense/>`, whether changes are applied depends on the mode:
+/// - [`SsrMode::OutOfOrder`], [`SsrMode::PartiallyBlocked`], and [`SsrMode::InOrder`] send the
+///   first chunk before suspended content has loaded. Load data that decides the status with a
+///   [blocking resource](leptos::server::Resource::new_blocking), which holds back the first
+///   chunk until it resolves.
+/// - [`SsrMode::Async`] renders the whole page before sending anything, so changes from anywhere
+///   in the tree are applied.
+///
+/// Changes made after the status and headers have been sent are ignored, and a warning is
+/// logged. [`is_sent`](ResponseOptions::is_sent) tells whether this has happened yet.
+///
+/// Changes made directly through the `ResponseParts` in this struct are not checked.
 #[derive(Debug, Clone, Default)]
 pub struct ResponseOptions(pub Arc<RwLock<ResponseParts>>);
 
+// The parts of the responses whose status and headers have been sent, which are added while
+// they are locked for writing. They are held weakly, so that they don't outlive their responses,
+// but that keeps their allocations, and so their addresses, from being reused while listed.
+static SENT: Mutex<Vec<Weak<RwLock<ResponseParts>>>> = Mutex::new(Vec::new());
+
 impl ResponseOptions {
     /// A simpler way to overwrite the contents of `ResponseOptions` with a new `ResponseParts`.
     pub fn overwrite(&self, parts: ResponseParts) {
-        let mut writable = self.0.write();
-        *writable = parts
+        self.modify("overwrite", |res_parts| *res_parts = parts);
     }
     /// Set the status of the returned Response.
     pub fn set_status(&self, status: StatusCode) {
-        let mut writeable = self.0.write();
-        let res_parts = &mut *writeable;
-        res_parts.status = Some(status);
+        self.modify("set_status", |res_parts| res_parts.status = Some(status));
     }
     /// Insert a header, overwriting any previous value with the same key.
     pub fn insert_header(
@@ -137,9 +234,9 @@ impl ResponseOptions {
         key: header::HeaderName,
         value: header::HeaderValue,
     ) {
-        let mut writeable = self.0.write();
-        let res_parts = &mut *writeable;
-        res_parts.headers.insert(key, value);
+        self.modify("insert_header", |res_parts| {
+            res_parts.headers.insert(key, value);
+        });
     }
     /// Append a header, leaving any header with the same key intact.
     pub fn append_header(
@@ -147,9 +244,49 @@ impl ResponseOptions {
         key: header::HeaderName,
         value: header::HeaderValue,
     ) {
-        let mut writeable = self.0.write();
-        let res_parts = &mut *writeable;
-        res_parts.headers.append(key, value);
+        self.modify("append_header", |res_parts| {
+            res_parts.headers.append(key, value);
+        });
+    }
+    /// Add a `Set-Cookie` header, leaving any other cookies intact.
+    pub fn set_cookie(&self, cookie: header::HeaderValue) {
+        self.modify("set_cookie", |res_parts| {
+            res_parts.headers.append(header::SET_COOKIE, cookie);
+        });
+    }
+    /// Whether the status and headers have already been sent to the client.
+    ///
+    /// Once they have been sent, any further changes are ignored.
+    pub fn is_sent(&self) -> bool {
+        let parts = Arc::as_ptr(&self.0);
+        SENT.lock().iter().any(|sent| ptr::eq(sent.as_ptr(), parts))
+    }
+
+    /// The status that has been set for the response, if any.
+    pub fn status(&self) -> Option<StatusCode> {
+        self.0.read().status
+    }
+
+    fn mark_sent(&self) {
+        if !self.is_sent() {
+            let mut sent = SENT.lock();
+            sent.retain(|parts| parts.strong_count() > 0);
+            sent.push(Arc::downgrade(&self.0));
+        }
+    }
+
+    fn modify(&self, method: &str, fun: impl FnOnce(&mut ResponseParts)) {
+        let mut parts = self.0.write();
+        if self.is_sent() {
+            leptos::logging::warn!(
+                "ResponseOptions::{method} was called after the response \
+                 status and headers were sent, so it has no effect. Set them \
+                 before the first chunk of HTML is streamed, for example from \
+                 a blocking resource or with SsrMode::Async."
+            );
+        } else {
+            fun(&mut parts);
+        }
     }
 }
 
@@ -162,26 +299,29 @@ impl ExtendResponse for ActixResponse {
         stream: impl Stream<Item = String> + Send + 'static,
     ) -> Self {
         ActixResponse(
-            HttpResponse::Ok()
-                .content_type("text/html")
-                .streaming(stream.map(|chunk| {
-                    Ok(web::Bytes::from(chunk)) as Result<web::Bytes>
-                })),
+            HttpResponse::Ok().streaming(stream.map(|chunk| {
+                Ok(web::Bytes::from(chunk)) as Result<web::Bytes>
+            })),
         )
     }
 
     fn extend_response(&mut self, res_options: &Self::ResponseOptions) {
-        let mut res_options = res_options.0.write();
+        let mut parts = res_options.0.write();
+        res_options.mark_sent();
+        let res_options = &mut *parts;
+        if let Some(status) = res_options.status {
+            *self.0.status_mut() = status;
+        }
 
+        // headers that were set replace those of the response with the same name, as in
+        // `leptos_axum`
         let headers = self.0.headers_mut();
+        for key in res_options.headers.keys() {
+            headers.remove(key);
+        }
         for (key, value) in std::mem::take(&mut res_options.headers) {
             headers.append(key, value);
         }
-
-        // Set status to what is returned in the function
-        if let Some(status) = res_options.status {
-            *self.0.status_mut() = status;
-        }
     }
 
     fn set_default_content_type(&mut self, content_type: &str) {
@@ -229,9 +369,10 @@ impl ExtendResponse for ActixResponse {
     tracing::instrument(level = "trace", fields(error), skip_all)
 )]
 pub fn redirect(path: &str) {
-    if let (Some(req), Some(res)) =
-        (use_context::<Request>(), use_context::<ResponseOptions>())
-    {
+    if let (Some(req), Some(res)) = (
+        with_context(Request::clone),
+        with_context(ResponseOptions::clone),
+    ) {
         // insert the Location header in any case
         res.insert_header(
             header::LOCATION,
@@ -304,6 +445,10 @@ pub fn redirect(path: &str) {
 /// This function always provides context values including the following types:
 /// - [ResponseOptions]
 /// - [Request]
+/// - [`RequestParts`](leptos::request::RequestParts)
+/// - [`ResponseHeaders`](leptos::request::ResponseHeaders)
+/// - [`TaskCancellation`](leptos::reactive::owner::TaskCancellation), which is cancelled if the
+///   client disconnects before the response is complete
 #[cfg_attr(
     feature = "tracing",
     tracing::instrument(level = "trace", fields(error), skip_all)
@@ -331,6 +476,10 @@ pub fn handle_server_fns() -> Route {
 /// This function always provides context values including the following types:
 /// - [ResponseOptions]
 /// - [Request]
+/// - [`RequestParts`](leptos::request::RequestParts)
+/// - [`ResponseHeaders`](leptos::request::ResponseHeaders)
+/// - [`TaskCancellation`](leptos::reactive::owner::TaskCancellation), which is cancelled if the
+///   client disconnects before the response is complete
 #[cfg_attr(
     feature = "tracing",
     tracing::instrument(level = "trace", fields(error), skip_all)
@@ -341,6 +490,10 @@ pub fn handle_server_fns_with_context(
     web::to(move |req: HttpRequest, payload: Payload| {
         let additional_context = additional_context.clone();
         async move {
+            if shutdown::is_shutting_down() {
+                return shutting_down_response();
+            }
+
             let additional_context = additional_context.clone();
 
             let path = req.path();
@@ -348,66 +501,67 @@ pub fn handle_server_fns_with_context(
             if let Some(mut service) =
                 server_fn::actix::get_server_fn_service(path, method)
             {
+                #[cfg(feature = "tracing")]
+                let span = leptos_integration_utils::request_span(
+                    "server_fn",
+                    method.as_str(),
+                    path,
+                    trace_context(&req).as_ref(),
+                );
                 let owner = Owner::new();
-                owner
-                    .with(|| {
-                        ScopedFuture::new(async move {
-                            provide_context(Request::new(&req));
-                            let res_options = ResponseOptions::default();
-                            provide_context(res_options.clone());
-                            additional_context();
-
-                            // store Accepts and Referer in case we need them for redirect (below)
-                            let accepts_html = req
-                                .headers()
-                                .get(ACCEPT)
-                                .and_then(|v| v.to_str().ok())
-                                .map(|v| v.contains("text/html"))
-                                .unwrap_or(false);
-                            let referrer = req.headers().get(REFERER).cloned();
-
-                            // actually run the server fn
-                            let mut res = ActixResponse(
-                                service
-                                    .run(ActixRequest::from((req, payload)))
-                                    .await
-                                    .take(),
-                            );
-
-                            // if it accepts text/html (i.e., is a plain form post) and doesn't already have a
-                            // Location set, then redirect to the Referer
-                            if accepts_html {
-                                if let Some(referrer) = referrer {
-                                    let has_location =
-                                        res.0.headers().get(LOCATION).is_some();
-                                    if !has_location {
-                                        *res.0.status_mut() = StatusCode::FOUND;
-                                        res.0
-                                            .headers_mut()
-                                            .insert(LOCATION, referrer);
-                                    }
-                                }
-                            }
-
-                            // the Location header may have been set to Referer, so any redirection by the
-                            // user must overwrite it
-                            {
-                                let mut res_options = res_options.0.write();
-                                let headers = res.0.headers_mut();
-
-                                for location in
-                                    res_options.headers.remove(header::LOCATION)
-                                {
-                                    headers.insert(header::LOCATION, location);
+                let res = owner.with(|| {
+                    ScopedFuture::new(async move {
+                        provide_context(request_parts(&req));
+                        provide_context(Request::new(&req));
+                        let res_options = ResponseOptions::default();
+                        provide_context(response_headers(&res_options));
+                        provide_context(res_options.clone());
+                        let cancellation = TaskCancellation::new();
+                        provide_context(cancellation.clone());
+                        let disconnect = DisconnectGuard::new(cancellation);
+                        additional_context();
+
+                        // store Accepts and Referer in case we need them for redirect (below)
+                        let accepts_html = req
+                            .headers()
+                            .get(ACCEPT)
+                            .and_then(|v| v.to_str().ok())
+                            .map(|v| v.contains("text/html"))
+                            .unwrap_or(false);
+                        let referrer = req.headers().get(REFERER).cloned();
+
+                        // actually run the server fn
+                        let mut res = ActixResponse(
+                            service
+                                .run(ActixRequest::from((req, payload)))
+                                .await
+                                .take(),
+                        );
+                        disconnect.disarm();
+
+                        // if it accepts text/html (i.e., is a plain form post) and doesn't already have a
+                        // Location set, then redirect to the Referer
+                        if accepts_html {
+                            if let Some(referrer) = referrer {
+                                let has_location =
+                                    res.0.headers().get(LOCATION).is_some();
+                                if !has_location {
+                                    *res.0.status_mut() = StatusCode::FOUND;
+                                    res.0
+                                        .headers_mut()
+                                        .insert(LOCATION, referrer);
                                 }
                             }
+                        }
 
-                            // apply status code and headers if user changed them
-                            res.extend_response(&res_options);
-                            res.0
-                        })
+                        // apply status code and headers if user changed them
+                        res.extend_response(&res_options);
+                        res.0
                     })
-                    .await
+                });
+                #[cfg(feature = "tracing")]
+                let res = tracing::Instrument::instrument(res, span);
+                res.await
             } else {
                 HttpResponse::BadRequest().body(format!(
                     "Could not find a server function at the route {:?}. \
@@ -426,6 +580,273 @@ pub fn handle_server_fns_with_context(
     })
 }
 
+/// Waits until the process receives `SIGTERM` or Ctrl+C, and then starts a graceful [`shutdown`]
+/// of server-rendered responses.
+///
+/// From then on, new requests to Leptos routes and server functions are answered with
+/// `503 Service Unavailable`. Responses that are still streaming after `deadline` are ended with
+/// a valid HTML tail, instead of being cut off mid-chunk when the process exits.
+///
+/// Actix handles these signals itself by default, so they should be disabled, and the server
+/// stopped once this resolves. Its shutdown timeout should be longer than `deadline`:
+///
+/// ```no_run
+/// use actix_web::{App, HttpServer};
+/// use std::time::Duration;
+///
+/// #[actix_web::main]
+/// async fn main() -> std::io::Result<()> {
+///     let deadline = Duration::from_secs(10);
+///     let server = HttpServer::new(App::new)
+///         .disable_signals()
+///         .shutdown_timeout(deadline.as_secs() + 5)
+///         .bind(("127.0.0.1", 8080))?
+///         .run();
+///
+///     let handle = server.handle();
+///     actix_web::rt::spawn(async move {
+///         leptos_actix::shutdown_signal(deadline).await;
+///         handle.stop(true).await;
+///     });
+///     server.await
+/// }
+/// ```
+pub async fn shutdown_signal(deadline: std::time::Duration) {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("failed to listen for Ctrl+C");
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(
+            tokio::signal::unix::SignalKind::terminate(),
+        )
+        .expect("failed to listen for SIGTERM")
+        .recv()
+        .await;
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    futures::future::select(pin!(ctrl_c), pin!(terminate)).await;
+    rt::spawn(shutdown::drain(tokio::time::sleep(deadline)));
+}
+
+#[cfg(feature = "tracing")]
+fn trace_context(req: &HttpRequest) -> Option<request::TraceContext> {
+    req.headers()
+        .get("traceparent")
+        .and_then(|value| value.to_str().ok())
+        .and_then(request::TraceContext::parse)
+}
+
+/// Actix middleware that serves pages from a [`PageCache`](cache::PageCache), and stores the
+/// pages it renders once they have been streamed to the client.
+///
+/// The cache is read from the app data. Requests that cannot use the cache, like `POST` requests
+/// to server functions, are passed through. Pages served from the cache have an `Age` header.
+///
+/// ```no_run
+/// use actix_web::{middleware::from_fn, web::Data, App};
+/// use leptos_actix::cache::PageCache;
+///
+/// let cache = PageCache::new();
+/// let app = App::new()
+///     .app_data(Data::new(cache))
+///     .wrap(from_fn(leptos_actix::cache_pages));
+///     // .leptos_routes(...)
+/// ```
+pub async fn cache_pages(
+    cache: Data<cache::PageCache>,
+    req: ServiceRequest,
+    next: middleware::Next<impl body::MessageBody + 'static>,
+) -> Result<dev::ServiceResponse<body::BoxBody>, Error> {
+    let parts = request_parts(req.request());
+    let Some(key) = cache.key(&parts.method, &parts.uri, &parts.headers) else {
+        return next.call(req).await.map(|res| res.map_into_boxed_body());
+    };
+    if let Some(page) = cache.get(&key) {
+        let mut res = HttpResponse::build(
+            StatusCode::from_u16(page.status.as_u16()).unwrap_or_default(),
+        );
+        for (name, value) in &page.headers {
+            res.append_header((name.as_str(), value.as_bytes()));
+        }
+        res.insert_header((header::AGE, page.age().as_bytes()));
+        return Ok(req.into_response(res.body(page.body)));
+    }
+
+    let res = next.call(req).await?;
+    let status = request::StatusCode::from_u16(res.status().as_u16())
+        .unwrap_or_default();
+    let mut headers = request::HeaderMap::with_capacity(res.headers().len());
+    for (name, value) in res.headers() {
+        if let (Ok(name), Ok(value)) = (
+            request::HeaderName::from_bytes(name.as_str().as_bytes()),
+            request::HeaderValue::from_bytes(value.as_bytes()),
+        ) {
+            headers.append(name, value);
+        }
+    }
+    Ok(res
+        .map_body(|_, body| {
+            let mut body = Box::pin(body);
+            let body =
+                futures::stream::poll_fn(move |cx| body.as_mut().poll_next(cx));
+            body::BodyStream::new(cache.record(key, status, &headers, body))
+        })
+        .map_into_boxed_body())
+}
+
+/// Returns a configuration that serves `/robots.txt` and `/sitemap.xml` for the `GET` routes of
+/// the app, built with the crawler options in `options`. See [`sitemap`] for what they contain.
+///
+/// ```no_run
+/// use actix_web::App;
+/// use leptos::{config::get_configuration, prelude::*};
+/// use leptos_actix::generate_route_list;
+///
+/// # fn App() -> impl IntoView { () }
+/// let leptos_options = get_configuration(None).unwrap().leptos_options;
+/// let routes = generate_route_list(App);
+///
+/// let app = App::new()
+///     .configure(leptos_actix::crawler_routes(&leptos_options, &routes));
+///     // .leptos_routes(routes, ...)
+/// ```
+pub fn crawler_routes(
+    options: &LeptosOptions,
+    routes: &[ActixRouteListing],
+) -> impl FnOnce(&mut ServiceConfig) {
+    let robots = sitemap::robots_txt(options);
+    let sitemap = sitemap::sitemap_xml(
+        options,
+        routes
+            .iter()
+            .filter(|route| {
+                route
+                    .methods()
+                    .any(|method| method == leptos_router::Method::Get)
+            })
+            .map(ActixRouteListing::path),
+    );
+    move |config| {
+        config
+            .route(
+                "/robots.txt",
+                web::get().to(move || {
+                    let robots = robots.clone();
+                    async move {
+                        HttpResponse::Ok()
+                            .content_type("text/plain; charset=utf-8")
+                            .body(robots)
+                    }
+                }),
+            )
+            .route(
+                "/sitemap.xml",
+                web::get().to(move || {
+                    let sitemap = sitemap.clone();
+                    async move {
+                        HttpResponse::Ok()
+                            .content_type("application/xml")
+                            .body(sitemap)
+                    }
+                }),
+            );
+    }
+}
+
+/// Returns a route that serves `manifest` as JSON, with the `application/manifest+json` content
+/// type. Mount it at the `href` of the [`WebAppManifest`](leptos_meta::WebAppManifest) component,
+/// which is [`AppManifest::DEFAULT_PATH`] by default.
+///
+/// ```no_run
+/// use actix_web::App;
+/// use leptos_meta::AppManifest;
+///
+/// let manifest = AppManifest::new("Todo List");
+///
+/// let app = App::new().route(
+///     AppManifest::DEFAULT_PATH,
+///     leptos_actix::web_app_manifest(&manifest),
+/// );
+/// ```
+pub fn web_app_manifest(manifest: &AppManifest) -> Route {
+    let json = manifest.to_json();
+    web::get().to(move || {
+        let json = json.clone();
+        async move {
+            HttpResponse::Ok()
+                .content_type(AppManifest::CONTENT_TYPE)
+                .body(json)
+        }
+    })
+}
+
+/// Returns a configuration that serves the `/livez`, `/readyz` and `/build-info` probes of
+/// `health`. See [`health`] for what they check.
+///
+/// ```no_run
+/// use actix_web::App;
+/// use leptos::config::get_configuration;
+/// use leptos_actix::health::{BuildInfo, Health};
+///
+/// let leptos_options = get_configuration(None).unwrap().leptos_options;
+/// let health = Health::new(
+///     &leptos_options,
+///     BuildInfo::new(env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION")),
+/// );
+///
+/// let app = App::new().configure(leptos_actix::health_routes(health));
+///     // .leptos_routes(routes, ...)
+/// ```
+pub fn health_routes(
+    health: health::Health,
+) -> impl FnOnce(&mut ServiceConfig) {
+    fn respond(res: health::HealthResponse) -> HttpResponse {
+        HttpResponse::build(
+            StatusCode::from_u16(res.status.as_u16()).unwrap_or_default(),
+        )
+        .content_type("application/json")
+        .body(res.body)
+    }
+
+    let live = health.clone();
+    let build = health.clone();
+    move |config| {
+        config
+            .route(
+                "/livez",
+                web::get().to(move || {
+                    let res = live.live();
+                    async move { respond(res) }
+                }),
+            )
+            .route(
+                "/readyz",
+                web::get().to(move || {
+                    let health = health.clone();
+                    async move { respond(health.ready().await) }
+                }),
+            )
+            .route(
+                "/build-info",
+                web::get().to(move || {
+                    let res = build.build_info();
+                    async move { respond(res) }
+                }),
+            );
+    }
+}
+
+fn shutting_down_response() -> HttpResponse {
+    HttpResponse::ServiceUnavailable()
+        .force_close()
+        .body("The server is shutting down.")
+}
+
 /// Returns an Actix [struct@Route](actix_web::Route) that listens for a `GET` request and tries
 /// to route it using [leptos_router], serving an HTML stream of your application. The stream
 /// will include fallback content for any `<Suspense/>` nodes, and be immediately interactive,
@@ -468,6 +889,10 @@ pub fn handle_server_fns_with_context(
 /// This function always provides context values including the following types:
 /// - [ResponseOptions]
 /// - [Request]
+/// - [`RequestParts`](leptos::request::RequestParts)
+/// - [`ResponseHeaders`](leptos::request::ResponseHeaders)
+/// - [`TaskCancellation`](leptos::reactive::owner::TaskCancellation), which is cancelled if the
+///   client disconnects before the response is complete
 /// - [MetaContext](leptos_meta::MetaContext)
 #[cfg_attr(
     feature = "tracing",
@@ -528,6 +953,10 @@ where
 /// This function always provides context values including the following types:
 /// - [ResponseOptions]
 /// - [Request]
+/// - [`RequestParts`](leptos::request::RequestParts)
+/// - [`ResponseHeaders`](leptos::request::ResponseHeaders)
+/// - [`TaskCancellation`](leptos::reactive::owner::TaskCancellation), which is cancelled if the
+///   client disconnects before the response is complete
 #[cfg_attr(
     feature = "tracing",
     tracing::instrument(level = "trace", fields(error), skip_all)
@@ -583,6 +1012,10 @@ where
 /// This function always provides context values including the following types:
 /// - [ResponseOptions]
 /// - [Request]
+/// - [`RequestParts`](leptos::request::RequestParts)
+/// - [`ResponseHeaders`](leptos::request::ResponseHeaders)
+/// - [`TaskCancellation`](leptos::reactive::owner::TaskCancellation), which is cancelled if the
+///   client disconnects before the response is complete
 #[cfg_attr(
     feature = "tracing",
     tracing::instrument(level = "trace", fields(error), skip_all)
@@ -607,6 +1040,10 @@ where
 /// This function always provides context values including the following types:
 /// - [ResponseOptions]
 /// - [Request]
+/// - [`RequestParts`](leptos::request::RequestParts)
+/// - [`ResponseHeaders`](leptos::request::ResponseHeaders)
+/// - [`TaskCancellation`](leptos::reactive::owner::TaskCancellation), which is cancelled if the
+///   client disconnects before the response is complete
 #[cfg_attr(
     feature = "tracing",
     tracing::instrument(level = "trace", fields(error), skip_all)
@@ -642,6 +1079,10 @@ where
 /// This function always provides context values including the following types:
 /// - [ResponseOptions]
 /// - [Request]
+/// - [`RequestParts`](leptos::request::RequestParts)
+/// - [`ResponseHeaders`](leptos::request::ResponseHeaders)
+/// - [`TaskCancellation`](leptos::reactive::owner::TaskCancellation), which is cancelled if the
+///   client disconnects before the response is complete
 #[cfg_attr(
     feature = "tracing",
     tracing::instrument(level = "trace", fields(error), skip_all)
@@ -689,6 +1130,10 @@ where
 /// This function always provides context values including the following types:
 /// - [ResponseOptions]
 /// - [Request]
+/// - [`RequestParts`](leptos::request::RequestParts)
+/// - [`ResponseHeaders`](leptos::request::ResponseHeaders)
+/// - [`TaskCancellation`](leptos::reactive::owner::TaskCancellation), which is cancelled if the
+///   client disconnects before the response is complete
 /// - [MetaContext](leptos_meta::MetaContext)
 #[cfg_attr(
     feature = "tracing",
@@ -730,6 +1175,10 @@ where
 /// This function always provides context values including the following types:
 /// - [ResponseOptions]
 /// - [Request]
+/// - [`RequestParts`](leptos::request::RequestParts)
+/// - [`ResponseHeaders`](leptos::request::ResponseHeaders)
+/// - [`TaskCancellation`](leptos::reactive::owner::TaskCancellation), which is cancelled if the
+///   client disconnects before the response is complete
 #[cfg_attr(
     feature = "tracing",
     tracing::instrument(level = "trace", fields(error), skip_all)
@@ -779,6 +1228,8 @@ fn provide_contexts(
     provide_context(RequestUrl::new(&path));
     provide_context(meta_context.clone());
     provide_context(res_options.clone());
+    provide_context(response_headers(res_options));
+    provide_context(request_parts(&req));
     provide_context(req);
     provide_server_redirect(redirect);
     leptos::nonce::provide_nonce();
@@ -812,7 +1263,18 @@ where
         let app_fn = app_fn.clone();
         let add_context = additional_context.clone();
 
-        async move {
+        #[cfg(feature = "tracing")]
+        let span = leptos_integration_utils::request_span(
+            "ssr",
+            req.method().as_str(),
+            req.path(),
+            trace_context(&req).as_ref(),
+        );
+        let res = async move {
+            if shutdown::is_shutting_down() {
+                return shutting_down_response();
+            }
+
             let is_island_router_navigation = cfg!(feature = "islands-router")
                 && req.headers().get("Islands-Router").is_some();
 
@@ -844,7 +1306,10 @@ where
             .await;
 
             res.0
-        }
+        };
+        #[cfg(feature = "tracing")]
+        let res = tracing::Instrument::instrument(res, span);
+        res
     };
     match method {
         Method::Get => web::get().to(handler),
@@ -1215,7 +1680,7 @@ static STATIC_HEADERS: Lazy<DashMap<String, ResponseOptions>> =
 
 fn was_404(owner: &Owner) -> bool {
     let resp = owner.with(|| expect_context::<ResponseOptions>());
-    let status = resp.0.read().status;
+    let status = resp.status();
 
     if let Some(status) = status {
         return status == StatusCode::NOT_FOUND;
@@ -1348,6 +1813,11 @@ pub trait LeptosRoutes {
     /// Adds routes to the Axum router that have either
     /// 1) been generated by `leptos_router`, or
     /// 2) handle a server function.
+    ///
+    /// To serve the app below its [`base_path`](leptos::config::LeptosOptions::base_path), and
+    /// server functions at the
+    /// [`server_fn_mount`](leptos::config::LeptosOptions::server_fn_mount) of the app, call
+    /// [`mount_server_fns`](leptos::hydration::mount_server_fns) before this.
     fn leptos_routes<IV>(
         self,
         paths: Vec<ActixRouteListing>,
@@ -1425,13 +1895,16 @@ where
                 let additional_context = additional_context.clone();
                 let handler =
                     handle_server_fns_with_context(additional_context);
-                router = router.route(path, handler);
+                router = router
+                    .route(&server_fn::prefix::mounted_path(path), handler);
             }
         }
 
         // register routes defined in Leptos's Router
         for listing in paths.iter().filter(|p| !p.exclude) {
-            let path = listing.path();
+            // routes that were listed before the base path was set are moved below it
+            let path = leptos::base_path::ensure_base_path(listing.path());
+            let path = path.as_ref();
             let mode = listing.mode();
 
             for method in listing.methods() {
@@ -1538,13 +2011,16 @@ impl LeptosRoutes for &mut ServiceConfig {
                 let additional_context = additional_context.clone();
                 let handler =
                     handle_server_fns_with_context(additional_context);
-                router = router.route(path, handler);
+                router = router
+                    .route(&server_fn::prefix::mounted_path(path), handler);
             }
         }
 
         // register routes defined in Leptos's Router
         for listing in paths.iter().filter(|p| !p.exclude) {
-            let path = listing.path();
+            // routes that were listed before the base path was set are moved below it
+            let path = leptos::base_path::ensure_base_path(listing.path());
+            let path = path.as_ref();
             let mode = listing.mode();
 
             for method in listing.methods() {
@@ -1606,6 +2082,9 @@ impl LeptosRoutes for &mut ServiceConfig {
 ///
 /// Any error that occurs during extraction is converted to a [`ServerFnError`].
 ///
+/// To extract headers, cookies, or connection information in code that is shared with other
+/// server integrations, use [`use_request_parts`](leptos::request::use_request_parts) instead.
+///
 /// ```rust
 /// use leptos::prelude::*;
 ///
@@ -0,0 +1,300 @@
+use actix_web::{web, HttpRequest, Route};
+use actix_ws::{
+    AggregatedMessage, AggregatedMessageStream, Closed, ProtocolError, Session,
+};
+use futures::StreamExt;
+use leptos::server_signal::ServerValue;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{fmt, future::Future, marker::PhantomData, pin::pin};
+
+/// Creates a route that upgrades requests to a WebSocket connection and passes it to `handler`.
+///
+/// Messages are exchanged as JSON text frames, which matches the client handle returned by
+/// `leptos::websocket::use_websocket`. `In` is the type of messages received from the client,
+/// and `Out` the type of messages sent to it, so sharing both types between the server and the
+/// client keeps the two ends in sync.
+///
+/// The handler runs on the current worker thread, and the connection is closed when it returns.
+///
+/// ```rust,no_run
+/// use actix_web::{App, HttpServer};
+/// use leptos_actix::{websocket, WebSocketConnection};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// pub struct ChatMessage {
+///     pub author: String,
+///     pub text: String,
+/// }
+///
+/// async fn echo(mut conn: WebSocketConnection<ChatMessage, ChatMessage>) {
+///     while let Some(Ok(msg)) = conn.recv().await {
+///         if conn.send(&msg).await.is_err() {
+///             break;
+///         }
+///     }
+/// }
+///
+/// # async fn run() -> std::io::Result<()> {
+/// HttpServer::new(|| App::new().route("/ws/chat", websocket(echo)))
+///     .bind(("127.0.0.1", 3000))?
+///     .run()
+///     .await
+/// # }
+/// ```
+pub fn websocket<In, Out, F, Fut>(handler: F) -> Route
+where
+    In: DeserializeOwned + 'static,
+    Out: Serialize + 'static,
+    F: Fn(WebSocketConnection<In, Out>) -> Fut + Clone + 'static,
+    Fut: Future<Output = ()> + 'static,
+{
+    web::get().to(move |req: HttpRequest, body: web::Payload| {
+        let handler = handler.clone();
+        async move {
+            let (response, session, stream) = actix_ws::handle(&req, body)?;
+            let conn = WebSocketConnection::new(session, stream);
+            actix_web::rt::spawn(handler(conn));
+            Ok::<_, actix_web::Error>(response)
+        }
+    })
+}
+
+/// Creates a route that serves a [`ServerValue`] over a WebSocket, for a
+/// `leptos::server_signal::ServerSignal` to connect to.
+///
+/// Each client is sent the current value as soon as it connects, and every new value after that.
+/// Values sent by clients are applied if the [`ServerValue`] is writable, and ignored otherwise.
+///
+/// ```rust,no_run
+/// use actix_web::{App, HttpServer};
+/// use leptos::server_signal::ServerValue;
+/// use leptos_actix::server_signal;
+///
+/// # async fn run() -> std::io::Result<()> {
+/// let visitors = ServerValue::new(0u64);
+/// HttpServer::new({
+///     let visitors = visitors.clone();
+///     move || {
+///         App::new().route("/ws/visitors", server_signal(visitors.clone()))
+///     }
+/// })
+/// .bind(("127.0.0.1", 3000))?
+/// .run()
+/// .await
+/// # }
+/// ```
+pub fn server_signal<T>(value: ServerValue<T>) -> Route
+where
+    T: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    websocket(move |conn: WebSocketConnection<T, T>| {
+        let value = value.clone();
+        async move {
+            let (mut sender, mut receiver) = conn.split();
+            let mut updates = value.subscribe();
+            let send = async {
+                while let Some(update) = updates.next().await {
+                    if sender.send(&update).await.is_err() {
+                        break;
+                    }
+                }
+            };
+            let receive = async {
+                while let Some(message) = receiver.recv().await {
+                    match message {
+                        Ok(message) => {
+                            value.set_from_client(message);
+                        }
+                        Err(WebSocketError::Json(_)) => {}
+                        Err(_) => break,
+                    }
+                }
+            };
+            // the connection is closed once either side stops
+            futures::future::select(pin!(send), pin!(receive)).await;
+        }
+    })
+}
+
+/// An error that occurred while exchanging messages over a [`WebSocketConnection`].
+#[derive(Debug)]
+pub enum WebSocketError {
+    /// The connection has already been closed.
+    Closed,
+    /// The client sent an invalid WebSocket frame.
+    Protocol(ProtocolError),
+    /// A message could not be serialized or deserialized.
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for WebSocketError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WebSocketError::Closed => {
+                f.write_str("WebSocket connection closed")
+            }
+            WebSocketError::Protocol(e) => write!(f, "WebSocket error: {e}"),
+            WebSocketError::Json(e) => {
+                write!(f, "could not encode WebSocket message: {e}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for WebSocketError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            WebSocketError::Closed => None,
+            WebSocketError::Protocol(e) => Some(e),
+            WebSocketError::Json(e) => Some(e),
+        }
+    }
+}
+
+impl From<Closed> for WebSocketError {
+    fn from(_: Closed) -> Self {
+        WebSocketError::Closed
+    }
+}
+
+/// A WebSocket connection that exchanges JSON-encoded messages, passed to the handler of a
+/// [`websocket`] route.
+///
+/// `In` is the type of messages received from the client, and `Out` the type of messages sent to
+/// it. Use [`split`](Self::split) to receive and send messages from separate tasks.
+#[derive(Debug)]
+pub struct WebSocketConnection<In, Out> {
+    sender: WebSocketSender<Out>,
+    receiver: WebSocketReceiver<In>,
+}
+
+impl<In, Out> WebSocketConnection<In, Out>
+where
+    In: DeserializeOwned,
+    Out: Serialize,
+{
+    /// Wraps the session and message stream returned by [`actix_ws::handle`].
+    pub fn new(session: Session, stream: actix_ws::MessageStream) -> Self {
+        Self {
+            sender: WebSocketSender {
+                session: session.clone(),
+                ty: PhantomData,
+            },
+            receiver: WebSocketReceiver {
+                session,
+                stream: stream.aggregate_continuations(),
+                ty: PhantomData,
+            },
+        }
+    }
+
+    /// Waits for the next message from the client.
+    ///
+    /// Returns `None` once the client has closed the connection.
+    pub async fn recv(&mut self) -> Option<Result<In, WebSocketError>> {
+        self.receiver.recv().await
+    }
+
+    /// Sends a message to the client.
+    pub async fn send(&mut self, message: &Out) -> Result<(), WebSocketError> {
+        self.sender.send(message).await
+    }
+
+    /// Closes the connection.
+    pub async fn close(self) -> Result<(), WebSocketError> {
+        self.sender.close().await
+    }
+
+    /// Splits the connection into halves that send and receive messages independently.
+    pub fn split(self) -> (WebSocketSender<Out>, WebSocketReceiver<In>) {
+        (self.sender, self.receiver)
+    }
+}
+
+/// The sending half of a [`WebSocketConnection`].
+pub struct WebSocketSender<Out> {
+    session: Session,
+    ty: PhantomData<fn(Out)>,
+}
+
+impl<Out> Clone for WebSocketSender<Out> {
+    fn clone(&self) -> Self {
+        Self {
+            session: self.session.clone(),
+            ty: PhantomData,
+        }
+    }
+}
+
+impl<Out> fmt::Debug for WebSocketSender<Out> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WebSocketSender").finish_non_exhaustive()
+    }
+}
+
+impl<Out> WebSocketSender<Out>
+where
+    Out: Serialize,
+{
+    /// Sends a message to the client.
+    pub async fn send(&mut self, message: &Out) -> Result<(), WebSocketError> {
+        let text =
+            serde_json::to_string(message).map_err(WebSocketError::Json)?;
+        Ok(self.session.text(text).await?)
+    }
+
+    /// Closes the connection.
+    pub async fn close(self) -> Result<(), WebSocketError> {
+        Ok(self.session.close(None).await?)
+    }
+}
+
+/// The receiving half of a [`WebSocketConnection`].
+pub struct WebSocketReceiver<In> {
+    session: Session,
+    stream: AggregatedMessageStream,
+    ty: PhantomData<fn() -> In>,
+}
+
+impl<In> fmt::Debug for WebSocketReceiver<In> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WebSocketReceiver").finish_non_exhaustive()
+    }
+}
+
+impl<In> WebSocketReceiver<In>
+where
+    In: DeserializeOwned,
+{
+    /// Waits for the next message from the client.
+    ///
+    /// Returns `None` once the client has closed the connection. Pings are answered
+    /// automatically.
+    pub async fn recv(&mut self) -> Option<Result<In, WebSocketError>> {
+        loop {
+            let message = match self.stream.next().await? {
+                Ok(message) => message,
+                Err(e) => return Some(Err(WebSocketError::Protocol(e))),
+            };
+            let message = match message {
+                AggregatedMessage::Text(text) => serde_json::from_str(&text),
+                AggregatedMessage::Binary(bytes) => {
+                    serde_json::from_slice(&bytes)
+                }
+                AggregatedMessage::Ping(bytes) => {
+                    if self.session.pong(&bytes).await.is_err() {
+                        return None;
+                    }
+                    continue;
+                }
+                AggregatedMessage::Pong(_) => continue,
+                AggregatedMessage::Close(reason) => {
+                    _ = self.session.clone().close(reason).await;
+                    return None;
+                }
+            };
+            return Some(message.map_err(WebSocketError::Json));
+        }
+    }
+}
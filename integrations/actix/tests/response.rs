@@ -0,0 +1,253 @@
+use actix_web::{
+    body::MessageBody,
+    http::{
+        header::{self, HeaderValue},
+        StatusCode,
+    },
+    test, App,
+};
+use futures::{channel::oneshot, future::poll_fn, stream};
+use leptos::{
+    prelude::*,
+    server_fn::codec::{StreamingText, TextStream},
+};
+use leptos_actix::{
+    handle_server_fns, render_app_to_stream, render_app_to_stream_with_context,
+    ResponseOptions,
+};
+use leptos_router::Method;
+use std::{
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+};
+
+#[component]
+fn Feed() -> impl IntoView {
+    let res = expect_context::<ResponseOptions>();
+    res.insert_header(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/xhtml+xml"),
+    );
+    res.set_cookie(HeaderValue::from_static("a=1"));
+    res.set_cookie(HeaderValue::from_static("b=2"));
+    view! { <p>"Hello"</p> }
+}
+
+// the backend is named, as `leptos_axum` enables its own when the workspace is built together
+#[server(
+    prefix = "/api",
+    endpoint = "login",
+    server = leptos::server_fn::actix::ActixServerFnBackend
+)]
+async fn login() -> Result<(), ServerFnError> {
+    leptos_actix::redirect("/account");
+    Ok(())
+}
+
+#[actix_web::test]
+async fn headers_from_response_options_replace_those_of_the_response() {
+    _ = any_spawner::Executor::init_tokio();
+    let app = test::init_service(
+        App::new()
+            .route("/feed", render_app_to_stream(Feed, Method::Get))
+            .route("/api/{tail:.*}", handle_server_fns()),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/feed").to_request();
+    let res = test::call_service(&app, req).await;
+    let content_types = res
+        .headers()
+        .get_all(header::CONTENT_TYPE)
+        .collect::<Vec<_>>();
+    assert_eq!(content_types, ["application/xhtml+xml"]);
+    let cookies = res
+        .headers()
+        .get_all(header::SET_COOKIE)
+        .collect::<Vec<_>>();
+    assert_eq!(cookies, ["a=1", "b=2"]);
+
+    // a plain form post is redirected to the Referer, unless the server fn redirects elsewhere
+    let req = test::TestRequest::post()
+        .uri("/api/login")
+        .insert_header((header::ACCEPT, "text/html"))
+        .insert_header((header::REFERER, "/login"))
+        .insert_header((
+            header::CONTENT_TYPE,
+            "application/x-www-form-urlencoded",
+        ))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    let locations = res.headers().get_all(header::LOCATION).collect::<Vec<_>>();
+    assert_eq!(locations, ["/account"]);
+}
+
+// holds back the resource of `Slow` until the test has read the shell
+#[derive(Clone, Default)]
+struct Gate {
+    release: Arc<Mutex<Option<oneshot::Receiver<()>>>>,
+    sent_when_resolved: Arc<AtomicBool>,
+}
+
+impl Gate {
+    fn new() -> (Self, oneshot::Sender<()>) {
+        let (release, receiver) = oneshot::channel();
+        let gate = Gate {
+            release: Arc::new(Mutex::new(Some(receiver))),
+            ..Default::default()
+        };
+        (gate, release)
+    }
+}
+
+#[component]
+fn Slow() -> impl IntoView {
+    let res = expect_context::<ResponseOptions>();
+    let gate = expect_context::<Gate>();
+    let release = Arc::clone(&gate.release);
+    let data = Resource::new(
+        || (),
+        move |_| {
+            let release = release.lock().unwrap().take();
+            async move {
+                if let Some(release) = release {
+                    _ = release.await;
+                }
+                "Loaded".to_string()
+            }
+        },
+    );
+    view! {
+        <Suspense fallback=|| "Loading">
+            {move || {
+                let res = res.clone();
+                let gate = gate.clone();
+                Suspend::new(async move {
+                    let data = data.await;
+                    gate.sent_when_resolved.store(res.is_sent(), Ordering::SeqCst);
+                    res.set_status(StatusCode::NOT_FOUND);
+                    res.insert_header(
+                        header::CACHE_CONTROL,
+                        HeaderValue::from_static("no-store"),
+                    );
+                    res.set_cookie(HeaderValue::from_static("late=1"));
+                    data
+                })
+            }}
+        </Suspense>
+    }
+}
+
+#[server(
+    prefix = "/api",
+    endpoint = "count",
+    output = StreamingText,
+    server = leptos::server_fn::actix::ActixServerFnBackend
+)]
+async fn count() -> Result<TextStream, ServerFnError> {
+    expect_context::<ResponseOptions>().insert_header(
+        header::CACHE_CONTROL,
+        HeaderValue::from_static("no-store"),
+    );
+    Ok(TextStream::new(stream::iter(
+        ["1\n", "2\n"].map(|line| Ok(line.to_string())),
+    )))
+}
+
+async fn next_chunk(body: &mut Pin<Box<impl MessageBody>>) -> Option<String> {
+    let chunk = poll_fn(|cx| body.as_mut().poll_next(cx)).await?;
+    Some(String::from_utf8(chunk.ok()?.to_vec()).unwrap())
+}
+
+#[actix_web::test]
+async fn out_of_order_streaming_sends_the_shell_before_suspense_resolves() {
+    _ = any_spawner::Executor::init_tokio();
+    let (gate, release) = Gate::new();
+    let app = test::init_service(App::new().route(
+        "/",
+        render_app_to_stream_with_context(
+            move || provide_context(gate.clone()),
+            Slow,
+            Method::Get,
+        ),
+    ))
+    .await;
+
+    let req = test::TestRequest::get().uri("/").to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let mut body = Box::pin(res.into_body());
+
+    // the shell is flushed while the resource is still pending
+    let mut html = String::new();
+    while !html.contains("Loading") {
+        html.push_str(&next_chunk(&mut body).await.expect("no shell"));
+    }
+    assert!(!html.contains("Loaded"));
+
+    release.send(()).unwrap();
+    while let Some(chunk) = next_chunk(&mut body).await {
+        html.push_str(&chunk);
+    }
+    assert!(html.contains("Loaded"), "{html}");
+}
+
+#[actix_web::test]
+async fn response_options_set_after_the_first_flush_are_ignored() {
+    _ = any_spawner::Executor::init_tokio();
+    let (gate, release) = Gate::new();
+    let sent_when_resolved = Arc::clone(&gate.sent_when_resolved);
+    let app = test::init_service(App::new().route(
+        "/",
+        render_app_to_stream_with_context(
+            move || provide_context(gate.clone()),
+            Slow,
+            Method::Get,
+        ),
+    ))
+    .await;
+
+    let req = test::TestRequest::get().uri("/").to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert!(res.headers().get(header::CACHE_CONTROL).is_none());
+    assert!(res.headers().get(header::SET_COOKIE).is_none());
+
+    // `Slow` sets a 404, a header and a cookie once its resource resolves, after the headers were sent
+    release.send(()).unwrap();
+    let html = String::from_utf8(test::read_body(res).await.to_vec()).unwrap();
+    assert!(html.contains("Loaded"), "{html}");
+    assert!(sent_when_resolved.load(Ordering::SeqCst));
+}
+
+#[actix_web::test]
+async fn streaming_server_fns_apply_response_options() {
+    _ = any_spawner::Executor::init_tokio();
+    let app = test::init_service(
+        App::new().route("/api/{tail:.*}", handle_server_fns()),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/count")
+        .insert_header((
+            header::CONTENT_TYPE,
+            "application/x-www-form-urlencoded",
+        ))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(
+        res.headers().get(header::CACHE_CONTROL).unwrap(),
+        "no-store"
+    );
+    let mut body = Box::pin(res.into_body());
+    let mut text = String::new();
+    while let Some(chunk) = next_chunk(&mut body).await {
+        text.push_str(&chunk);
+    }
+    assert_eq!(text, "1\n2\n");
+}
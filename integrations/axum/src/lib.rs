@@ -17,6 +17,7 @@
 //! - `default`: supports running in a typical native Tokio/Axum environment
 //! - `wasm`: with `default-features = false`, supports running in a JS Fetch-based
 //!   environment
+//! - neither: supports running on a single-threaded runtime that cannot spawn tasks
 //!
 //! ### Important Note
 //! Prior to 0.5, using `default-features = false` on `leptos_axum` simply did nothing. Now, it actively
@@ -25,8 +26,20 @@
 //! `
 //! `spawn_local` called from outside of a `task::LocalSet`
 //! `
-//! If you are not using the `wasm` feature, do not set `default-features = false` on this package.
+//! If you are running on a Tokio runtime, do not set `default-features = false` on this package.
 //!
+//! ## Single-Threaded Runtimes
+//! With `default-features = false` and without the `wasm` feature, `leptos_axum` does not depend
+//! on a Tokio runtime to spawn tasks. Instead, the tasks of each request, like loading its
+//! resources, are run inside the request's own future and response body, using
+//! [`LocalTasks`](any_spawner::LocalTasks). This supports single-threaded runtimes that cannot
+//! spawn tasks of their own, such as WASI-based edge and serverless environments.
+//!
+//!
+//! ## Other Frameworks
+//! The handlers for server functions and rendering are implemented in `leptos_tower`, on top of
+//! `tower` and `http`. Its `LeptosService` can host a Leptos app on any other framework that
+//! accepts a `tower::Service`.
 //!
 //! ## More information
 //!
@@ -35,243 +48,92 @@
 //! directory in the Leptos repository.
 
 #[cfg(feature = "default")]
-use axum::http::Uri;
+mod static_files;
+#[cfg(feature = "default")]
+mod ws;
+
 use axum::{
     body::{Body, Bytes},
     extract::{FromRef, FromRequestParts, MatchedPath, State},
-    http::{
-        header::{self, HeaderName, HeaderValue, ACCEPT, LOCATION, REFERER},
-        request::Parts,
-        HeaderMap, Method, Request, Response, StatusCode,
-    },
+    http::{header, request::Parts, Method, Request, Response},
     response::IntoResponse,
-    routing::{delete, get, patch, post, put},
+    routing::{delete, get, patch, post, put, MethodRouter},
+};
+#[cfg(feature = "default")]
+use axum::{
+    extract::ConnectInfo,
+    http::{StatusCode, Uri},
 };
 #[cfg(feature = "default")]
 use dashmap::DashMap;
 use futures::{stream::once, Future, Stream, StreamExt};
 use hydration_context::SsrSharedContext;
+#[cfg(feature = "default")]
+use leptos::reactive::computed::ScopedFuture;
 use leptos::{
     config::LeptosOptions,
     context::{provide_context, use_context},
     prelude::*,
-    reactive::{computed::ScopedFuture, owner::Owner},
+    reactive::owner::Owner,
     IntoView,
 };
-use leptos_integration_utils::{
-    BoxedFnOnce, ExtendResponse, PinnedFuture, PinnedStream,
-};
-use leptos_meta::ServerMetaContext;
+#[doc(inline)]
+pub use leptos_integration_utils::cache;
+#[doc(inline)]
+pub use leptos_integration_utils::health;
+#[cfg(feature = "session")]
+#[doc(inline)]
+pub use leptos_integration_utils::session;
+#[doc(inline)]
+pub use leptos_integration_utils::shutdown;
+#[doc(inline)]
+pub use leptos_integration_utils::sitemap;
+use leptos_integration_utils::{BoxedFnOnce, PinnedFuture, PinnedStream};
+use leptos_meta::{AppManifest, ServerMetaContext};
 #[cfg(feature = "default")]
 use leptos_router::static_routes::ResolvedStaticPath;
 use leptos_router::{
-    components::provide_server_redirect, location::RequestUrl,
-    static_routes::RegenerationFn, ExpandOptionals, PathSegment, RouteList,
-    RouteListing, SsrMode,
+    location::RequestUrl, static_routes::RegenerationFn, ExpandOptionals,
+    PathSegment, RouteList, RouteListing, SsrMode,
 };
 #[cfg(feature = "default")]
 use once_cell::sync::Lazy;
-use parking_lot::RwLock;
-use server_fn::{error::ServerFnErrorErr, redirect::REDIRECT_HEADER};
+use server_fn::error::ServerFnErrorErr;
+#[cfg(feature = "default")]
+pub use static_files::serve_static_file;
 #[cfg(feature = "default")]
 use std::path::Path;
 use std::{collections::HashSet, fmt::Debug, io, pin::Pin, sync::Arc};
 #[cfg(feature = "default")]
 use tower::util::ServiceExt;
 #[cfg(feature = "default")]
-use tower_http::services::ServeDir;
+pub use ws::*;
 // use tracing::Instrument; // TODO check tracing span -- was this used in 0.6 for a missing link?
 
-/// This struct lets you define headers and override the status of the Response from an Element or a Server Function
-/// Typically contained inside of a ResponseOptions. Setting this is useful for cookies and custom responses.
-#[derive(Debug, Clone, Default)]
-pub struct ResponseParts {
-    /// If provided, this will overwrite any other status code for this response.
-    pub status: Option<StatusCode>,
-    /// The map of headers that should be added to the response.
-    pub headers: HeaderMap,
-}
-
-impl ResponseParts {
-    /// Insert a header, overwriting any previous value with the same key
-    pub fn insert_header(&mut self, key: HeaderName, value: HeaderValue) {
-        self.headers.insert(key, value);
-    }
-    /// Append a header, leaving any header with the same key intact
-    pub fn append_header(&mut self, key: HeaderName, value: HeaderValue) {
-        self.headers.append(key, value);
-    }
-}
-
-/// Allows you to override details of the HTTP response like the status code and add Headers/Cookies.
-///
-/// `ResponseOptions` is provided via context when you use most of the handlers provided in this
-/// crate, including [`.leptos_routes`](LeptosRoutes::leptos_routes),
-/// [`.leptos_routes_with_context`](LeptosRoutes::leptos_routes_with_context), [`handle_server_fns`], etc.
-/// You can find the full set of provided context types in each handler function.
-///
-/// If you provide your own handler, you will need to provide `ResponseOptions` via context
-/// yourself if you want to access it via context.
-/// ```
-/// use leptos::prelude::*;
-///
-/// #[server]
-/// pub async fn get_opts() -> Result<(), ServerFnError> {
-///     let opts = expect_context::<leptos_axum::ResponseOptions>();
-///     Ok(())
-/// }
-#[derive(Debug, Clone, Default)]
-pub struct ResponseOptions(pub Arc<RwLock<ResponseParts>>);
-
-impl ResponseOptions {
-    /// A simpler way to overwrite the contents of `ResponseOptions` with a new `ResponseParts`.
-    pub fn overwrite(&self, parts: ResponseParts) {
-        let mut writable = self.0.write();
-        *writable = parts
-    }
-    /// Set the status of the returned Response.
-    pub fn set_status(&self, status: StatusCode) {
-        let mut writeable = self.0.write();
-        let res_parts = &mut *writeable;
-        res_parts.status = Some(status);
-    }
-    /// Insert a header, overwriting any previous value with the same key.
-    pub fn insert_header(&self, key: HeaderName, value: HeaderValue) {
-        let mut writeable = self.0.write();
-        let res_parts = &mut *writeable;
-        res_parts.headers.insert(key, value);
-    }
-    /// Append a header, leaving any header with the same key intact.
-    pub fn append_header(&self, key: HeaderName, value: HeaderValue) {
-        let mut writeable = self.0.write();
-        let res_parts = &mut *writeable;
-        res_parts.headers.append(key, value);
-    }
-}
-
-struct AxumResponse(Response<Body>);
-
-impl ExtendResponse for AxumResponse {
-    type ResponseOptions = ResponseOptions;
-
-    fn from_stream(
-        stream: impl Stream<Item = String> + Send + 'static,
-    ) -> Self {
-        AxumResponse(
-            Body::from_stream(
-                stream.map(|chunk| Ok(chunk) as Result<String, std::io::Error>),
-            )
-            .into_response(),
-        )
-    }
-
-    fn extend_response(&mut self, res_options: &Self::ResponseOptions) {
-        let mut res_options = res_options.0.write();
-        if let Some(status) = res_options.status {
-            *self.0.status_mut() = status;
-        }
-        self.0
-            .headers_mut()
-            .extend(std::mem::take(&mut res_options.headers));
-    }
-
-    fn set_default_content_type(&mut self, content_type: &str) {
-        let headers = self.0.headers_mut();
-        if !headers.contains_key(header::CONTENT_TYPE) {
-            // Set the Content Type headers on all responses. This makes Firefox show the page source
-            // without complaining
-            headers.insert(
-                header::CONTENT_TYPE,
-                HeaderValue::from_str(content_type).unwrap(),
-            );
-        }
-    }
-}
+#[doc(inline)]
+pub use leptos_tower::{
+    generate_request_and_parts, redirect, ResponseOptions, ResponseParts,
+};
 
-/// Provides an easy way to redirect the user from within a server function.
-///
-/// Calling `redirect` in a server function will redirect the browser in three
-/// situations:
-/// 1. A server function that is calling in a [blocking
-///    resource](leptos::server::Resource::new_blocking).
-/// 2. A server function that is called from WASM running in the client (e.g., a dispatched action
-///    or a spawned `Future`).
-/// 3. A `<form>` submitted to the server function endpoint using default browser APIs (often due
-///    to using [`ActionForm`] without JS/WASM present.)
+/// Adds the address of the client from [`ConnectInfo`] to the request extensions, where the
+/// handlers of `leptos_tower` look for it.
 ///
-/// Using it with a non-blocking [`Resource`] will not work if you are using streaming rendering,
-/// as the response's headers will already have been sent by the time the server function calls `redirect()`.
-///
-/// ### Implementation
-///
-/// This sets the `Location` header to the URL given.
-///
-/// If the route or server function in which this is called is being accessed
-/// by an ordinary `GET` request or an HTML `<form>` without any enhancement, it also sets a
-/// status code of `302` for a temporary redirect. (This is determined by whether the `Accept`
-/// header contains `text/html` as it does for an ordinary navigation.)
-///
-/// Otherwise, it sets a custom header that indicates to the client that it should redirect,
-/// without actually setting the status code. This means that the client will not follow the
-/// redirect, and can therefore return the value of the server function and then handle
-/// the redirect with client-side routing.
-pub fn redirect(path: &str) {
-    if let (Some(req), Some(res)) =
-        (use_context::<Parts>(), use_context::<ResponseOptions>())
+/// `ConnectInfo` is only available on the Tokio runtime.
+#[cfg(feature = "default")]
+fn with_remote_addr(mut req: Request<Body>) -> Request<Body> {
+    if let Some(ConnectInfo(addr)) = req
+        .extensions()
+        .get::<ConnectInfo<std::net::SocketAddr>>()
+        .copied()
     {
-        // insert the Location header in any case
-        res.insert_header(
-            header::LOCATION,
-            header::HeaderValue::from_str(path)
-                .expect("Failed to create HeaderValue"),
-        );
-
-        let accepts_html = req
-            .headers
-            .get(ACCEPT)
-            .and_then(|v| v.to_str().ok())
-            .map(|v| v.contains("text/html"))
-            .unwrap_or(false);
-        if accepts_html {
-            // if the request accepts text/html, it's a plain form request and needs
-            // to have the 302 code set
-            res.set_status(StatusCode::FOUND);
-        } else {
-            // otherwise, we sent it from the server fn client and actually don't want
-            // to set a real redirect, as this will break the ability to return data
-            // instead, set the REDIRECT_HEADER to indicate that the client should redirect
-            res.insert_header(
-                HeaderName::from_static(REDIRECT_HEADER),
-                HeaderValue::from_str("").unwrap(),
-            );
-        }
-    } else {
-        #[cfg(feature = "tracing")]
-        {
-            tracing::warn!(
-                "Couldn't retrieve either Parts or ResponseOptions while \
-                 trying to redirect()."
-            );
-        }
-        #[cfg(not(feature = "tracing"))]
-        {
-            eprintln!(
-                "Couldn't retrieve either Parts or ResponseOptions while \
-                 trying to redirect()."
-            );
-        }
+        req.extensions_mut().insert(addr);
     }
+    req
 }
 
-/// Decomposes an HTTP request into its parts, allowing you to read its headers
-/// and other data without consuming the body. Creates a new Request from the
-/// original parts for further processing
-pub fn generate_request_and_parts(
-    req: Request<Body>,
-) -> (Request<Body>, Parts) {
-    let (parts, body) = req.into_parts();
-    let parts2 = parts.clone();
-    (Request::from_parts(parts, body), parts2)
+#[cfg(not(feature = "default"))]
+fn with_remote_addr(req: Request<Body>) -> Request<Body> {
+    req
 }
 
 /// An Axum handlers to listens for a request with Leptos server function arguments in the body,
@@ -309,6 +171,10 @@ pub fn generate_request_and_parts(
 /// ## Provided Context Types
 /// This function always provides context values including the following types:
 /// - [`Parts`]
+/// - [`RequestParts`](leptos::request::RequestParts)
+/// - [`ResponseHeaders`](leptos::request::ResponseHeaders)
+/// - [`TaskCancellation`](leptos::reactive::owner::TaskCancellation), which is cancelled if the
+///   client disconnects before the response is complete
 /// - [`ResponseOptions`]
 #[cfg_attr(
     feature = "tracing",
@@ -318,20 +184,113 @@ pub async fn handle_server_fns(req: Request<Body>) -> impl IntoResponse {
     handle_server_fns_inner(|| {}, req).await
 }
 
+/// Waits until the process receives `SIGTERM` or Ctrl+C, and then starts a graceful [`shutdown`]
+/// of server-rendered responses.
+///
+/// From then on, new requests to Leptos routes and server functions are answered with
+/// `503 Service Unavailable`. Responses that are still streaming after `deadline` are ended with
+/// a valid HTML tail, instead of being cut off mid-chunk when the process exits.
+///
+/// This is meant to be passed to axum's graceful shutdown, which stops accepting connections
+/// once the signal has been received and waits for open ones to finish:
+///
+/// ```no_run
+/// use axum::Router;
+/// use std::{net::SocketAddr, time::Duration};
+///
+/// #[cfg(feature = "default")]
+/// #[tokio::main]
+/// async fn main() {
+///     let addr = SocketAddr::from(([127, 0, 0, 1], 3000));
+///     let app = Router::new();
+///
+///     let listener = tokio::net::TcpListener::bind(&addr).await.unwrap();
+///     axum::serve(listener, app.into_make_service())
+///         .with_graceful_shutdown(leptos_axum::shutdown_signal(
+///             Duration::from_secs(10),
+///         ))
+///         .await
+///         .unwrap();
+/// }
+///
+/// # #[cfg(not(feature = "default"))]
+/// # fn main() { }
+/// ```
+#[cfg(feature = "default")]
+pub async fn shutdown_signal(deadline: std::time::Duration) {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("failed to listen for Ctrl+C");
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(
+            tokio::signal::unix::SignalKind::terminate(),
+        )
+        .expect("failed to listen for SIGTERM")
+        .recv()
+        .await;
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    futures::future::select(std::pin::pin!(ctrl_c), std::pin::pin!(terminate))
+        .await;
+    tokio::spawn(shutdown::drain(tokio::time::sleep(deadline)));
+}
+
+/// Axum middleware that serves pages from a [`PageCache`](cache::PageCache), and stores the pages
+/// it renders once they have been streamed to the client.
+///
+/// Requests that cannot use the cache, like `POST` requests to server functions, are passed
+/// through. Pages served from the cache have an `Age` header.
+///
+/// ```no_run
+/// use axum::{middleware, Router};
+/// use leptos_axum::cache::PageCache;
+///
+/// # fn app() -> Router {
+/// let cache = PageCache::new();
+/// let app: Router = Router::new()
+///     // .leptos_routes(...)
+///     .layer(middleware::from_fn_with_state(cache, leptos_axum::cache_pages));
+/// # app
+/// # }
+/// ```
+pub async fn cache_pages(
+    State(cache): State<cache::PageCache>,
+    req: Request<Body>,
+    next: axum::middleware::Next,
+) -> Response<Body> {
+    let Some(key) = cache.key(req.method(), req.uri(), req.headers()) else {
+        return next.run(req).await;
+    };
+    if let Some(page) = cache.get(&key) {
+        let mut res = Response::new(Body::from(page.body.clone()));
+        *res.status_mut() = page.status;
+        *res.headers_mut() = page.headers.clone();
+        res.headers_mut().insert(header::AGE, page.age());
+        return res;
+    }
+
+    let (parts, body) = next.run(req).await.into_parts();
+    let body = cache.record(
+        key,
+        parts.status,
+        &parts.headers,
+        body.into_data_stream(),
+    );
+    Response::from_parts(parts, Body::from_stream(body))
+}
+
 fn init_executor() {
     #[cfg(feature = "wasm")]
     let _ = any_spawner::Executor::init_wasm_bindgen();
     #[cfg(all(not(feature = "wasm"), feature = "default"))]
     let _ = any_spawner::Executor::init_tokio();
     #[cfg(all(not(feature = "wasm"), not(feature = "default")))]
-    {
-        eprintln!(
-            "It appears you have set 'default-features = false' on \
-             'leptos_axum', but are not using the 'wasm' feature. Either \
-             remove 'default-features = false' or, if you are running in a \
-             JS-hosted WASM server environment, add the 'wasm' feature."
-        );
-    }
+    let _ = any_spawner::Executor::init_local_tasks();
 }
 
 /// An Axum handlers to listens for a request with Leptos server function arguments in the body,
@@ -354,6 +313,10 @@ fn init_executor() {
 /// ## Provided Context Types
 /// This function always provides context values including the following types:
 /// - [`Parts`]
+/// - [`RequestParts`](leptos::request::RequestParts)
+/// - [`ResponseHeaders`](leptos::request::ResponseHeaders)
+/// - [`TaskCancellation`](leptos::reactive::owner::TaskCancellation), which is cancelled if the
+///   client disconnects before the response is complete
 /// - [`ResponseOptions`]
 #[cfg_attr(
     feature = "tracing",
@@ -370,68 +333,12 @@ async fn handle_server_fns_inner(
     additional_context: impl Fn() + 'static + Clone + Send,
     req: Request<Body>,
 ) -> impl IntoResponse {
-    let method = req.method().clone();
-    let path = req.uri().path().to_string();
-    let (req, parts) = generate_request_and_parts(req);
-
-    if let Some(mut service) =
-        server_fn::axum::get_server_fn_service(&path, method)
-    {
-        let owner = Owner::new();
-        owner
-            .with(|| {
-                ScopedFuture::new(async move {
-                    provide_context(parts);
-                    let res_options = ResponseOptions::default();
-                    provide_context(res_options.clone());
-                    additional_context();
-
-                    // store Accepts and Referer in case we need them for redirect (below)
-                    let accepts_html = req
-                        .headers()
-                        .get(ACCEPT)
-                        .and_then(|v| v.to_str().ok())
-                        .map(|v| v.contains("text/html"))
-                        .unwrap_or(false);
-                    let referrer = req.headers().get(REFERER).cloned();
-
-                    // actually run the server fn
-                    let mut res = AxumResponse(service.run(req).await);
-
-                    // if it accepts text/html (i.e., is a plain form post) and doesn't already have a
-                    // Location set, then redirect to the Referer
-                    if accepts_html {
-                        if let Some(referrer) = referrer {
-                            let has_location =
-                                res.0.headers().get(LOCATION).is_some();
-                            if !has_location {
-                                *res.0.status_mut() = StatusCode::FOUND;
-                                res.0.headers_mut().insert(LOCATION, referrer);
-                            }
-                        }
-                    }
-
-                    // apply status code and headers if user changed them
-                    res.extend_response(&res_options);
-                    Ok(res.0)
-                })
-            })
-            .await
-    } else {
-        Response::builder()
-            .status(StatusCode::BAD_REQUEST)
-            .body(Body::from(format!(
-                "Could not find a server function at the route {path}. \
-                 \n\nIt's likely that either
-                         1. The API prefix you specify in the `#[server]` \
-                 macro doesn't match the prefix at which your server function \
-                 handler is mounted, or \n2. You are on a platform that \
-                 doesn't support automatic server function registration and \
-                 you need to call ServerFn::register_explicit() on the server \
-                 function type, somewhere in your `main` function.",
-            )))
-    }
-    .expect("could not build Response")
+    let req = with_remote_addr(req);
+    let service = server_fn::axum::get_server_fn_service(
+        req.uri().path(),
+        req.method().clone(),
+    );
+    leptos_tower::run_server_fn(additional_context, req, service).await
 }
 
 /// A stream of bytes of HTML.
@@ -478,6 +385,10 @@ pub type PinnedHtmlStream =
 /// ## Provided Context Types
 /// This function always provides context values including the following types:
 /// - [`Parts`]
+/// - [`RequestParts`](leptos::request::RequestParts)
+/// - [`ResponseHeaders`](leptos::request::ResponseHeaders)
+/// - [`TaskCancellation`](leptos::reactive::owner::TaskCancellation), which is cancelled if the
+///   client disconnects before the response is complete
 /// - [`ResponseOptions`]
 /// - [`ServerMetaContext`]
 #[cfg_attr(
@@ -567,6 +478,10 @@ where
 /// ## Provided Context Types
 /// This function always provides context values including the following types:
 /// - [`Parts`]
+/// - [`RequestParts`](leptos::request::RequestParts)
+/// - [`ResponseHeaders`](leptos::request::ResponseHeaders)
+/// - [`TaskCancellation`](leptos::reactive::owner::TaskCancellation), which is cancelled if the
+///   client disconnects before the response is complete
 /// - [`ResponseOptions`]
 /// - [`ServerMetaContext`]
 #[cfg_attr(
@@ -620,6 +535,10 @@ where
 /// ## Provided Context Types
 /// This function always provides context values including the following types:
 /// - [`Parts`]
+/// - [`RequestParts`](leptos::request::RequestParts)
+/// - [`ResponseHeaders`](leptos::request::ResponseHeaders)
+/// - [`TaskCancellation`](leptos::reactive::owner::TaskCancellation), which is cancelled if the
+///   client disconnects before the response is complete
 /// - [`ResponseOptions`]
 /// - [`ServerMetaContext`]
 #[cfg_attr(
@@ -752,6 +671,10 @@ where
 /// ## Provided Context Types
 /// This function always provides context values including the following types:
 /// - [`Parts`]
+/// - [`RequestParts`](leptos::request::RequestParts)
+/// - [`ResponseHeaders`](leptos::request::ResponseHeaders)
+/// - [`TaskCancellation`](leptos::reactive::owner::TaskCancellation), which is cancelled if the
+///   client disconnects before the response is complete
 /// - [`ResponseOptions`]
 /// - [`ServerMetaContext`]
 #[cfg_attr(
@@ -826,6 +749,10 @@ where
 /// ## Provided Context Types
 /// This function always provides context values including the following types:
 /// - [`Parts`]
+/// - [`RequestParts`](leptos::request::RequestParts)
+/// - [`ResponseHeaders`](leptos::request::ResponseHeaders)
+/// - [`TaskCancellation`](leptos::reactive::owner::TaskCancellation), which is cancelled if the
+///   client disconnects before the response is complete
 /// - [`ResponseOptions`]
 /// - [`ServerMetaContext`]
 #[cfg_attr(
@@ -892,68 +819,13 @@ fn handle_response_inner<IV>(
 where
     IV: IntoView + 'static,
 {
-    Box::pin(async move {
-        let is_island_router_navigation = cfg!(feature = "islands-router")
-            && req.headers().get("Islands-Router").is_some();
-
-        let add_context = additional_context.clone();
-        let res_options = ResponseOptions::default();
-        let (meta_context, meta_output) = ServerMetaContext::new();
-
-        let additional_context = {
-            let meta_context = meta_context.clone();
-            let res_options = res_options.clone();
-            move || {
-                // Need to get the path and query string of the Request
-                // For reasons that escape me, if the incoming URI protocol is https, it provides the absolute URI
-                let path = req.uri().path_and_query().unwrap().as_str();
-
-                let full_path = format!("http://leptos.dev{path}");
-                let (_, req_parts) = generate_request_and_parts(req);
-                provide_contexts(
-                    &full_path,
-                    &meta_context,
-                    req_parts,
-                    res_options.clone(),
-                );
-                add_context();
-
-                if is_island_router_navigation {
-                    provide_context(IslandsRouterNavigation);
-                }
-            }
-        };
-
-        let res = AxumResponse::from_app(
-            app_fn,
-            meta_output,
-            additional_context,
-            res_options,
-            stream_builder,
-            !is_island_router_navigation,
-        )
-        .await;
-
-        res.0
-    })
-}
-
-#[cfg_attr(
-    feature = "tracing",
-    tracing::instrument(level = "trace", fields(error), skip_all)
-)]
-fn provide_contexts(
-    path: &str,
-    meta_context: &ServerMetaContext,
-    parts: Parts,
-    default_res_options: ResponseOptions,
-) {
-    provide_context(RequestUrl::new(path));
-    provide_context(meta_context.clone());
-    provide_context(parts);
-    provide_context(default_res_options);
-    provide_server_redirect(redirect);
-    leptos::nonce::provide_nonce();
+    let res = leptos_tower::render_response(
+        additional_context,
+        app_fn,
+        with_remote_addr(req),
+        stream_builder,
+    );
+    Box::pin(async move { res.await.map(Body::new) })
 }
 
 /// Returns an Axum [Handler](axum::handler::Handler) that listens for a `GET` request and tries
@@ -998,6 +870,10 @@ fn provide_contexts(
 /// ## Provided Context Types
 /// This function always provides context values including the following types:
 /// - [`Parts`]
+/// - [`RequestParts`](leptos::request::RequestParts)
+/// - [`ResponseHeaders`](leptos::request::ResponseHeaders)
+/// - [`TaskCancellation`](leptos::reactive::owner::TaskCancellation), which is cancelled if the
+///   client disconnects before the response is complete
 /// - [`ResponseOptions`]
 /// - [`ServerMetaContext`]
 #[cfg_attr(
@@ -1052,6 +928,10 @@ where
 /// ## Provided Context Types
 /// This function always provides context values including the following types:
 /// - [`Parts`]
+/// - [`RequestParts`](leptos::request::RequestParts)
+/// - [`ResponseHeaders`](leptos::request::ResponseHeaders)
+/// - [`TaskCancellation`](leptos::reactive::owner::TaskCancellation), which is cancelled if the
+///   client disconnects before the response is complete
 /// - [`ResponseOptions`]
 /// - [`ServerMetaContext`]
 #[cfg_attr(
@@ -1119,6 +999,10 @@ where
 /// ## Provided Context Types
 /// This function always provides context values including the following types:
 /// - [`Parts`]
+/// - [`RequestParts`](leptos::request::RequestParts)
+/// - [`ResponseHeaders`](leptos::request::ResponseHeaders)
+/// - [`TaskCancellation`](leptos::reactive::owner::TaskCancellation), which is cancelled if the
+///   client disconnects before the response is complete
 /// - [`ResponseOptions`]
 /// - [`ServerMetaContext`]
 #[cfg_attr(
@@ -1160,6 +1044,57 @@ where
     })
 }
 
+/// Returns a router that serves the `/livez`, `/readyz` and `/build-info` probes of `health`.
+/// See [`health`] for what they check.
+///
+/// ```no_run
+/// use axum::Router;
+/// use leptos::config::get_configuration;
+/// use leptos_axum::health::{BuildInfo, Health};
+///
+/// # fn app() -> Router {
+/// let leptos_options = get_configuration(None).unwrap().leptos_options;
+/// let health = Health::new(
+///     &leptos_options,
+///     BuildInfo::new(env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION")),
+/// );
+///
+/// Router::new()
+///     .merge(leptos_axum::health_routes(health))
+///     // .leptos_routes(...)
+///     .with_state(leptos_options)
+/// # }
+/// ```
+pub fn health_routes<S>(health: health::Health) -> axum::Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    fn respond(res: health::HealthResponse) -> Response<Body> {
+        (
+            res.status,
+            [(header::CONTENT_TYPE, "application/json")],
+            res.body,
+        )
+            .into_response()
+    }
+
+    let live = health.clone();
+    let build = health.clone();
+    axum::Router::new()
+        .route("/livez", get(move || async move { respond(live.live()) }))
+        .route(
+            "/readyz",
+            get(move || {
+                let health = health.clone();
+                async move { respond(health.ready().await) }
+            }),
+        )
+        .route(
+            "/build-info",
+            get(move || async move { respond(build.build_info()) }),
+        )
+}
+
 /// Generates a list of all routes defined in Leptos's Router in your app. We can then use this to automatically
 /// create routes in Axum's Router without having to use wildcard matching or fallbacks. Takes in your root app Element
 /// as an argument so it can walk you app tree. This version is tailored to generate Axum compatible paths.
@@ -1308,6 +1243,89 @@ impl AxumRouteListing {
     }
 }
 
+/// Returns a router that serves `/robots.txt` and `/sitemap.xml` for the `GET` routes of the app,
+/// built with the crawler options in `options`. See [`sitemap`] for what they contain.
+///
+/// ```no_run
+/// use axum::Router;
+/// use leptos::{config::get_configuration, prelude::*};
+/// use leptos_axum::{generate_route_list, LeptosRoutes};
+///
+/// # fn App() -> impl IntoView { () }
+/// # fn shell(_: LeptosOptions) -> impl IntoView { () }
+/// # fn app() -> Router {
+/// let leptos_options = get_configuration(None).unwrap().leptos_options;
+/// let routes = generate_route_list(App);
+///
+/// Router::new()
+///     .merge(leptos_axum::crawler_routes(&leptos_options, &routes))
+///     .leptos_routes(&leptos_options, routes, {
+///         let leptos_options = leptos_options.clone();
+///         move || shell(leptos_options.clone())
+///     })
+///     .with_state(leptos_options)
+/// # }
+/// ```
+pub fn crawler_routes<S>(
+    options: &LeptosOptions,
+    routes: &[AxumRouteListing],
+) -> axum::Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let robots = sitemap::robots_txt(options);
+    let sitemap = sitemap::sitemap_xml(
+        options,
+        routes
+            .iter()
+            .filter(|route| route.methods.contains(&leptos_router::Method::Get))
+            .map(AxumRouteListing::path),
+    );
+    axum::Router::new()
+        .route(
+            "/robots.txt",
+            get(move || async move {
+                (
+                    [(header::CONTENT_TYPE, "text/plain; charset=utf-8")],
+                    robots,
+                )
+            }),
+        )
+        .route(
+            "/sitemap.xml",
+            get(move || async move {
+                ([(header::CONTENT_TYPE, "application/xml")], sitemap)
+            }),
+        )
+}
+
+/// Returns a handler that serves `manifest` as JSON, with the `application/manifest+json` content
+/// type. Mount it at the `href` of the [`WebAppManifest`](leptos_meta::WebAppManifest) component,
+/// which is [`AppManifest::DEFAULT_PATH`] by default.
+///
+/// ```no_run
+/// use axum::Router;
+/// use leptos_meta::AppManifest;
+///
+/// # fn app() -> Router {
+/// let manifest = AppManifest::new("Todo List");
+///
+/// Router::new().route(
+///     AppManifest::DEFAULT_PATH,
+///     leptos_axum::web_app_manifest(&manifest),
+/// )
+/// # }
+/// ```
+pub fn web_app_manifest<S>(manifest: &AppManifest) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let json = manifest.to_json();
+    get(move || async move {
+        ([(header::CONTENT_TYPE, AppManifest::CONTENT_TYPE)], json)
+    })
+}
+
 /// Generates a list of all routes defined in Leptos's Router in your app. We can then use this to automatically
 /// create routes in Axum's Router without having to use wildcard matching or fallbacks. Takes in your root app Element
 /// as an argument so it can walk you app tree. This version is tailored to generate Axum compatible paths. Adding excluded_routes
@@ -1335,7 +1353,12 @@ where
             provide_context(RequestUrl::new(""));
             let (mock_parts, _) = Request::new(Body::from("")).into_parts();
             let (mock_meta, _) = ServerMetaContext::new();
-            provide_contexts("", &mock_meta, mock_parts, Default::default());
+            leptos_tower::provide_contexts(
+                "",
+                &mock_meta,
+                mock_parts,
+                Default::default(),
+            );
             additional_context();
             RouteList::generate(&app_fn)
         })
@@ -1412,7 +1435,7 @@ impl StaticRouteGenerator {
                     .unwrap();
                 let (mock_parts, _) = mock_req.into_parts();
                 let res_options = ResponseOptions::default();
-                provide_contexts(
+                leptos_tower::provide_contexts(
                     &full_path,
                     &meta_context,
                     mock_parts,
@@ -1527,7 +1550,7 @@ static STATIC_HEADERS: Lazy<DashMap<String, ResponseOptions>> =
 #[cfg(feature = "default")]
 fn was_404(owner: &Owner) -> bool {
     let resp = owner.with(|| expect_context::<ResponseOptions>());
-    let status = resp.0.read().status;
+    let status = resp.status();
 
     if let Some(status) = status {
         return status == StatusCode::NOT_FOUND;
@@ -1643,7 +1666,7 @@ where
             //
             // this if for thing like 404s, where we do not want to cache an endless series of
             // typos (or malicious requests)
-            let mut res = AxumResponse(match html {
+            let mut res = match html {
                 Some(html) => axum::response::Html(html).into_response(),
                 None => match ServeFile::new(path).oneshot(req).await {
                     Ok(res) => res.into_response(),
@@ -1653,13 +1676,13 @@ where
                     )
                         .into_response(),
                 },
-            });
+            };
 
             if let Some(options) = response_options {
-                res.extend_response(&options);
+                options.apply(&mut res);
             }
 
-            res.0
+            res
         })
     }
 }
@@ -1790,6 +1813,7 @@ where
         IV: IntoView + 'static,
     {
         init_executor();
+        leptos::hydration::mount_server_fns(&LeptosOptions::from_ref(state));
 
         // S represents the router's finished state allowing us to provide
         // it to the user's server functions.
@@ -1816,7 +1840,7 @@ where
 
             if !excluded.contains(path) {
                 router = router.route(
-                    path,
+                    &server_fn::prefix::mounted_path(path),
                     match method {
                         Method::GET => get(handler),
                         Method::POST => post(handler),
@@ -1836,7 +1860,9 @@ where
 
         // register router paths
         for listing in paths.iter().filter(|p| !p.exclude) {
-            let path = listing.path();
+            // routes that were listed before the base path was set are moved below it
+            let path = leptos::base_path::ensure_base_path(listing.path());
+            let path = path.as_ref();
 
             for method in listing.methods() {
                 let cx_with_state = cx_with_state.clone();
@@ -1945,9 +1971,10 @@ where
     {
         let mut router = self;
         for listing in paths.iter().filter(|p| !p.exclude) {
+            let path = leptos::base_path::ensure_base_path(listing.path());
             for method in listing.methods() {
                 router = router.route(
-                    listing.path(),
+                    &path,
                     match method {
                         leptos_router::Method::Get => get(handler.clone()),
                         leptos_router::Method::Post => post(handler.clone()),
@@ -1971,6 +1998,9 @@ where
 ///
 /// Any error that occurs during extraction is converted to a [`ServerFnError`].
 ///
+/// To extract headers, cookies, or connection information in code that is shared with other
+/// server integrations, use [`use_request_parts`](leptos::request::use_request_parts) instead.
+///
 /// ```rust
 /// use leptos::prelude::*;
 ///
@@ -2019,8 +2049,9 @@ where
 
 /// A reasonable handler for serving static files (like JS/WASM/CSS) and 404 errors.
 ///
-/// This is provided as a convenience, but is a fairly simple function. If you need to adapt it,
-/// simply reuse the source code of this function in your own application.
+/// Files are served with [`serve_static_file`], which handles precompressed variants, caching
+/// headers and conditional requests. Any other request renders the app with a `404 Not Found`
+/// status.
 #[cfg(feature = "default")]
 pub fn file_and_error_handler_with_context<S, IV>(
     additional_context: impl Fn() + 'static + Clone + Send,
@@ -2038,18 +2069,14 @@ where
     S: Send + Sync + Clone + 'static,
     LeptosOptions: FromRef<S>,
 {
-    move |uri: Uri, State(state): State<S>, req: Request<Body>| {
+    move |_: Uri, State(state): State<S>, req: Request<Body>| {
         Box::pin({
             let additional_context = additional_context.clone();
             let shell = shell.clone();
             async move {
                 let options = LeptosOptions::from_ref(&state);
-                let res =
-                    get_static_file(uri, &options.site_root, req.headers());
-                let res = res.await.unwrap();
-
-                if res.status() == StatusCode::OK {
-                    res.into_response()
+                if let Some(res) = serve_static_file(&options, &req).await {
+                    res
                 } else {
                     let mut res = handle_response_inner(
                         move || {
@@ -2089,8 +2116,9 @@ where
 
 /// A reasonable handler for serving static files (like JS/WASM/CSS) and 404 errors.
 ///
-/// This is provided as a convenience, but is a fairly simple function. If you need to adapt it,
-/// simply reuse the source code of this function in your own application.
+/// Files are served with [`serve_static_file`], which handles precompressed variants, caching
+/// headers and conditional requests. Any other request renders the app with a `404 Not Found`
+/// status.
 #[cfg(feature = "default")]
 pub fn file_and_error_handler<S, IV>(
     shell: impl Fn(LeptosOptions) -> IV + 'static + Clone + Send,
@@ -2109,35 +2137,3 @@ where
 {
     file_and_error_handler_with_context(move || (), shell)
 }
-
-#[cfg(feature = "default")]
-async fn get_static_file(
-    uri: Uri,
-    root: &str,
-    headers: &HeaderMap<HeaderValue>,
-) -> Result<Response<Body>, (StatusCode, String)> {
-    use axum::http::header::ACCEPT_ENCODING;
-
-    let req = Request::builder().uri(uri);
-
-    let req = match headers.get(ACCEPT_ENCODING) {
-        Some(value) => req.header(ACCEPT_ENCODING, value),
-        None => req,
-    };
-
-    let req = req.body(Body::empty()).unwrap();
-    // `ServeDir` implements `tower::Service` so we can call it with `tower::ServiceExt::oneshot`
-    // This path is relative to the cargo root
-    match ServeDir::new(root)
-        .precompressed_gzip()
-        .precompressed_br()
-        .oneshot(req)
-        .await
-    {
-        Ok(res) => Ok(res.into_response()),
-        Err(err) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
-            format!("Something went wrong: {err}"),
-        )),
-    }
-}
@@ -0,0 +1,303 @@
+use axum::{
+    body::Body,
+    http::{
+        header::{
+            ACCEPT_ENCODING, CACHE_CONTROL, CONTENT_ENCODING, CONTENT_LENGTH,
+            CONTENT_RANGE, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, IF_RANGE,
+            LAST_MODIFIED, RANGE, VARY,
+        },
+        HeaderMap, HeaderValue, Method, Request, Response, StatusCode,
+    },
+    response::IntoResponse,
+};
+use leptos::config::LeptosOptions;
+use std::future::Future;
+use tower::util::ServiceExt;
+use tower_http::services::ServeDir;
+
+/// `Cache-Control` for files whose name changes with their content.
+const IMMUTABLE: &str = "public, max-age=31536000, immutable";
+
+/// `Cache-Control` for every other file, which browsers may store but must revalidate.
+const REVALIDATE: &str = "no-cache";
+
+/// Serves a `GET` or `HEAD` request for a file in the [`site_root`](LeptosOptions::site_root),
+/// returning `None` if there is no such file. If the app has a
+/// [`base_path`](LeptosOptions::base_path), files are served below it.
+///
+/// This is the asset handling used by [`file_and_error_handler`](crate::file_and_error_handler),
+/// for fallbacks that need to render something else for missing files:
+///
+/// - If the client accepts them, the precompressed `.br` and `.gz` variants that sit next to a
+///   file are served in its place.
+/// - Responses carry an `ETag` and a `Last-Modified` date, and conditional requests are answered
+///   with `304 Not Modified`. `Range` requests are supported, and `If-Range` is honored by
+///   serving the whole file.
+/// - When [`hash_files`](LeptosOptions::hash_files) is set, files in the
+///   [`site_pkg_dir`](LeptosOptions::site_pkg_dir) are cached as immutable for a year, as their
+///   name changes whenever their content does. Every other file is revalidated on each use.
+///
+/// ```rust,no_run
+/// use axum::{
+///     body::Body,
+///     extract::State,
+///     http::{Request, StatusCode},
+///     response::{IntoResponse, Response},
+/// };
+/// use leptos::config::LeptosOptions;
+///
+/// async fn fallback(
+///     State(options): State<LeptosOptions>,
+///     req: Request<Body>,
+/// ) -> Response {
+///     match leptos_axum::serve_static_file(&options, &req).await {
+///         Some(res) => res,
+///         None => (StatusCode::NOT_FOUND, "Not found.").into_response(),
+///     }
+/// }
+/// ```
+pub fn serve_static_file<B>(
+    options: &LeptosOptions,
+    req: &Request<B>,
+) -> impl Future<Output = Option<Response<Body>>> + Send + 'static {
+    let is_read = req.method() == Method::GET || req.method() == Method::HEAD;
+    let headers = req.headers();
+
+    // files are served below the base path, but stored at the root of the site
+    let path = match options
+        .base_path
+        .as_deref()
+        .and_then(leptos::base_path::normalize)
+    {
+        Some(base) => req
+            .uri()
+            .path()
+            .strip_prefix(&*base)
+            .filter(|path| path.starts_with('/'))
+            .map(str::to_string),
+        None => Some(req.uri().path().to_string()),
+    };
+    let is_read = is_read && path.is_some();
+    let path = path.unwrap_or_else(|| "/".to_string());
+
+    let mut inner = Request::builder()
+        .method(req.method().clone())
+        .uri(path.as_str());
+    for name in [ACCEPT_ENCODING, RANGE] {
+        // `ServeDir` does not support `If-Range`, so only the full file can be known to match
+        if name == RANGE && headers.contains_key(IF_RANGE) {
+            continue;
+        }
+        if let Some(value) = headers.get(&name) {
+            inner = inner.header(name, value);
+        }
+    }
+    // `If-None-Match` takes precedence over `If-Modified-Since`, and is checked below
+    let if_none_match = headers.get(IF_NONE_MATCH).cloned();
+    if if_none_match.is_none() {
+        if let Some(value) = headers.get(IF_MODIFIED_SINCE) {
+            inner = inner.header(IF_MODIFIED_SINCE, value);
+        }
+    }
+    let inner = inner.body(Body::empty()).unwrap();
+
+    let root = options.site_root.clone();
+    let cache_control = if is_hashed(options, &path) {
+        IMMUTABLE
+    } else {
+        REVALIDATE
+    };
+
+    async move {
+        if !is_read {
+            return None;
+        }
+
+        // `ServeDir` is infallible, and the path is relative to the current directory
+        let Ok(res) = ServeDir::new(&*root)
+            .precompressed_br()
+            .precompressed_gzip()
+            .oneshot(inner)
+            .await;
+        if res.status() == StatusCode::NOT_FOUND {
+            return None;
+        }
+        let mut res = res.into_response();
+
+        let res_headers = res.headers_mut();
+        res_headers
+            .insert(CACHE_CONTROL, HeaderValue::from_static(cache_control));
+        res_headers.insert(VARY, HeaderValue::from_static("accept-encoding"));
+
+        if let Some(etag) = etag(res.status(), res.headers()) {
+            if if_none_match.is_some_and(|tags| matches_etag(&tags, &etag)) {
+                let mut not_modified = Response::new(Body::empty());
+                *not_modified.status_mut() = StatusCode::NOT_MODIFIED;
+                let not_modified_headers = not_modified.headers_mut();
+                for name in [CACHE_CONTROL, VARY, LAST_MODIFIED] {
+                    if let Some(value) = res.headers_mut().remove(&name) {
+                        not_modified_headers.insert(name, value);
+                    }
+                }
+                not_modified_headers.insert(ETAG, etag);
+                return Some(not_modified);
+            }
+            res.headers_mut().insert(ETAG, etag);
+        }
+
+        Some(res)
+    }
+}
+
+/// Whether the file at `path` has a content hash in its name.
+fn is_hashed(options: &LeptosOptions, path: &str) -> bool {
+    options.hash_files
+        && path
+            .strip_prefix('/')
+            .and_then(|path| path.strip_prefix(&*options.site_pkg_dir))
+            .is_some_and(|rest| rest.starts_with('/'))
+}
+
+/// Builds a weak validator from the size, encoding and modification date of the file that was
+/// served. Range responses use the size of the whole file, so they share the validator of the
+/// full response.
+fn etag(status: StatusCode, headers: &HeaderMap) -> Option<HeaderValue> {
+    let size = match status {
+        StatusCode::OK => headers.get(CONTENT_LENGTH)?.to_str().ok()?,
+        StatusCode::PARTIAL_CONTENT => headers
+            .get(CONTENT_RANGE)?
+            .to_str()
+            .ok()?
+            .rsplit('/')
+            .next()?,
+        _ => return None,
+    };
+    let size = size.parse::<u64>().ok()?;
+    let last_modified = headers.get(LAST_MODIFIED)?.as_bytes();
+    let encoding = headers
+        .get(CONTENT_ENCODING)
+        .map(HeaderValue::as_bytes)
+        .unwrap_or_default();
+
+    // FNV-1a, so that the validator stays the same across restarts and builds
+    let mut hash = 0xcbf29ce484222325_u64;
+    for byte in last_modified.iter().chain(b";").chain(encoding) {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    HeaderValue::from_str(&format!("W/\"{size:x}-{hash:x}\"")).ok()
+}
+
+/// Weak comparison of an `If-None-Match` header with the validator of the file.
+fn matches_etag(if_none_match: &HeaderValue, etag: &HeaderValue) -> bool {
+    let Ok(if_none_match) = if_none_match.to_str() else {
+        return false;
+    };
+    let opaque = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+    let etag = opaque(etag.to_str().unwrap_or_default());
+    if_none_match
+        .split(',')
+        .any(|tag| tag.trim() == "*" || opaque(tag) == etag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{fs, path::PathBuf};
+
+    fn site(name: &str) -> (LeptosOptions, PathBuf) {
+        let root = std::env::temp_dir()
+            .join(format!("leptos_axum_{name}_{}", std::process::id()));
+        fs::create_dir_all(root.join("pkg")).unwrap();
+        fs::write(root.join("pkg/app.0123abcd.js"), "console.log(1);").unwrap();
+        fs::write(root.join("pkg/app.0123abcd.js.br"), "brotli").unwrap();
+        fs::write(root.join("favicon.ico"), "icon").unwrap();
+        let options = LeptosOptions::builder()
+            .output_name("app")
+            .site_root(root.to_string_lossy().to_string())
+            .hash_files(true)
+            .build();
+        (options, root)
+    }
+
+    fn get(uri: &str, headers: &[(&str, &str)]) -> Request<Body> {
+        let mut req = Request::get(uri);
+        for (name, value) in headers {
+            req = req.header(*name, *value);
+        }
+        req.body(Body::empty()).unwrap()
+    }
+
+    #[test]
+    fn serves_precompressed_and_conditional_responses() {
+        let (options, root) = site("conditional");
+        tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(check_responses(&options));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn serves_files_below_the_base_path() {
+        let (mut options, root) = site("base_path");
+        options.base_path = Some("/tools/myapp/".to_string());
+        tokio::runtime::Runtime::new().unwrap().block_on(async {
+            let res = serve_static_file(
+                &options,
+                &get("/tools/myapp/favicon.ico", &[]),
+            )
+            .await
+            .unwrap();
+            assert_eq!(res.status(), StatusCode::OK);
+            assert!(serve_static_file(&options, &get("/favicon.ico", &[]))
+                .await
+                .is_none());
+        });
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    async fn check_responses(options: &LeptosOptions) {
+        let res = serve_static_file(
+            options,
+            &get("/pkg/app.0123abcd.js", &[("accept-encoding", "gzip, br")]),
+        )
+        .await
+        .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.headers()[CONTENT_ENCODING], "br");
+        assert_eq!(res.headers()[CACHE_CONTROL], IMMUTABLE);
+        assert_eq!(res.headers()[VARY], "accept-encoding");
+        let etag = res.headers()[ETAG].to_str().unwrap().to_string();
+
+        let res = serve_static_file(
+            options,
+            &get(
+                "/pkg/app.0123abcd.js",
+                &[("accept-encoding", "br"), ("if-none-match", &etag)],
+            ),
+        )
+        .await
+        .unwrap();
+        assert_eq!(res.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(res.headers()[ETAG], *etag);
+
+        // the uncompressed file is a different representation
+        let res = serve_static_file(
+            options,
+            &get("/pkg/app.0123abcd.js", &[("if-none-match", &etag)]),
+        )
+        .await
+        .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert!(res.headers().get(CONTENT_ENCODING).is_none());
+
+        let res = serve_static_file(options, &get("/favicon.ico", &[]))
+            .await
+            .unwrap();
+        assert_eq!(res.headers()[CACHE_CONTROL], REVALIDATE);
+
+        assert!(serve_static_file(options, &get("/missing.js", &[]))
+            .await
+            .is_none());
+    }
+}
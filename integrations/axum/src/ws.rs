@@ -0,0 +1,258 @@
+use axum::{
+    extract::ws::{Message, WebSocket, WebSocketUpgrade},
+    routing::{get, MethodRouter},
+};
+use futures::{
+    stream::{SplitSink, SplitStream},
+    Future, SinkExt, StreamExt,
+};
+use leptos::server_signal::ServerValue;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{fmt, marker::PhantomData, pin::pin};
+
+/// Creates a route that upgrades requests to a WebSocket connection and passes it to `handler`.
+///
+/// Messages are exchanged as JSON text frames, which matches the client handle returned by
+/// `leptos::websocket::use_websocket`. `In` is the type of messages received from the client,
+/// and `Out` the type of messages sent to it, so sharing both types between the server and the
+/// client keeps the two ends in sync.
+///
+/// The connection is closed when `handler` returns.
+///
+/// ```rust,no_run
+/// use axum::Router;
+/// use leptos_axum::{websocket, WebSocketConnection};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// pub struct ChatMessage {
+///     pub author: String,
+///     pub text: String,
+/// }
+///
+/// async fn echo(mut conn: WebSocketConnection<ChatMessage, ChatMessage>) {
+///     while let Some(Ok(msg)) = conn.recv().await {
+///         if conn.send(&msg).await.is_err() {
+///             break;
+///         }
+///     }
+/// }
+///
+/// let app: Router = Router::new().route("/ws/chat", websocket(echo));
+/// ```
+pub fn websocket<In, Out, F, Fut, S>(handler: F) -> MethodRouter<S>
+where
+    In: DeserializeOwned + Send + 'static,
+    Out: Serialize + Send + 'static,
+    F: Fn(WebSocketConnection<In, Out>) -> Fut + Clone + Send + Sync + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+    S: Clone + Send + Sync + 'static,
+{
+    get(move |upgrade: WebSocketUpgrade| async move {
+        upgrade
+            .on_upgrade(move |socket| handler(WebSocketConnection::new(socket)))
+    })
+}
+
+/// Creates a route that serves a [`ServerValue`] over a WebSocket, for a
+/// `leptos::server_signal::ServerSignal` to connect to.
+///
+/// Each client is sent the current value as soon as it connects, and every new value after that.
+/// Values sent by clients are applied if the [`ServerValue`] is writable, and ignored otherwise.
+///
+/// ```rust,no_run
+/// use axum::Router;
+/// use leptos::server_signal::ServerValue;
+/// use leptos_axum::server_signal;
+///
+/// let visitors = ServerValue::new(0u64);
+/// let app: Router =
+///     Router::new().route("/ws/visitors", server_signal(visitors.clone()));
+/// // anywhere else on the server
+/// visitors.update(|count| *count += 1);
+/// ```
+pub fn server_signal<T, S>(value: ServerValue<T>) -> MethodRouter<S>
+where
+    T: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+    S: Clone + Send + Sync + 'static,
+{
+    websocket(move |conn: WebSocketConnection<T, T>| {
+        let value = value.clone();
+        async move {
+            let (mut sender, mut receiver) = conn.split();
+            let mut updates = value.subscribe();
+            let send = async {
+                while let Some(update) = updates.next().await {
+                    if sender.send(&update).await.is_err() {
+                        break;
+                    }
+                }
+            };
+            let receive = async {
+                while let Some(message) = receiver.recv().await {
+                    match message {
+                        Ok(message) => {
+                            value.set_from_client(message);
+                        }
+                        Err(WebSocketError::Json(_)) => {}
+                        Err(WebSocketError::Socket(_)) => break,
+                    }
+                }
+            };
+            // the connection is closed once either side stops
+            futures::future::select(pin!(send), pin!(receive)).await;
+        }
+    })
+}
+
+/// An error that occurred while exchanging messages over a [`WebSocketConnection`].
+#[derive(Debug)]
+pub enum WebSocketError {
+    /// The underlying connection failed.
+    Socket(axum::Error),
+    /// A message could not be serialized or deserialized.
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for WebSocketError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WebSocketError::Socket(e) => write!(f, "WebSocket error: {e}"),
+            WebSocketError::Json(e) => {
+                write!(f, "could not encode WebSocket message: {e}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for WebSocketError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            WebSocketError::Socket(e) => Some(e),
+            WebSocketError::Json(e) => Some(e),
+        }
+    }
+}
+
+/// A WebSocket connection that exchanges JSON-encoded messages, passed to the handler of a
+/// [`websocket`] route.
+///
+/// `In` is the type of messages received from the client, and `Out` the type of messages sent to
+/// it. Use [`split`](Self::split) to receive and send messages from separate tasks.
+#[derive(Debug)]
+pub struct WebSocketConnection<In, Out> {
+    sender: WebSocketSender<Out>,
+    receiver: WebSocketReceiver<In>,
+}
+
+impl<In, Out> WebSocketConnection<In, Out>
+where
+    In: DeserializeOwned,
+    Out: Serialize,
+{
+    /// Wraps an upgraded [`WebSocket`].
+    pub fn new(socket: WebSocket) -> Self {
+        let (sink, stream) = socket.split();
+        Self {
+            sender: WebSocketSender {
+                sink,
+                ty: PhantomData,
+            },
+            receiver: WebSocketReceiver {
+                stream,
+                ty: PhantomData,
+            },
+        }
+    }
+
+    /// Waits for the next message from the client.
+    ///
+    /// Returns `None` once the client has closed the connection.
+    pub async fn recv(&mut self) -> Option<Result<In, WebSocketError>> {
+        self.receiver.recv().await
+    }
+
+    /// Sends a message to the client.
+    pub async fn send(&mut self, message: &Out) -> Result<(), WebSocketError> {
+        self.sender.send(message).await
+    }
+
+    /// Closes the connection.
+    pub async fn close(self) -> Result<(), WebSocketError> {
+        self.sender.close().await
+    }
+
+    /// Splits the connection into halves that send and receive messages independently.
+    pub fn split(self) -> (WebSocketSender<Out>, WebSocketReceiver<In>) {
+        (self.sender, self.receiver)
+    }
+}
+
+/// The sending half of a [`WebSocketConnection`].
+pub struct WebSocketSender<Out> {
+    sink: SplitSink<WebSocket, Message>,
+    ty: PhantomData<fn(Out)>,
+}
+
+impl<Out> fmt::Debug for WebSocketSender<Out> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WebSocketSender").finish_non_exhaustive()
+    }
+}
+
+impl<Out> WebSocketSender<Out>
+where
+    Out: Serialize,
+{
+    /// Sends a message to the client.
+    pub async fn send(&mut self, message: &Out) -> Result<(), WebSocketError> {
+        let text =
+            serde_json::to_string(message).map_err(WebSocketError::Json)?;
+        self.sink
+            .send(Message::Text(text.into()))
+            .await
+            .map_err(WebSocketError::Socket)
+    }
+
+    /// Closes the connection.
+    pub async fn close(mut self) -> Result<(), WebSocketError> {
+        self.sink.close().await.map_err(WebSocketError::Socket)
+    }
+}
+
+/// The receiving half of a [`WebSocketConnection`].
+pub struct WebSocketReceiver<In> {
+    stream: SplitStream<WebSocket>,
+    ty: PhantomData<fn() -> In>,
+}
+
+impl<In> fmt::Debug for WebSocketReceiver<In> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WebSocketReceiver").finish_non_exhaustive()
+    }
+}
+
+impl<In> WebSocketReceiver<In>
+where
+    In: DeserializeOwned,
+{
+    /// Waits for the next message from the client.
+    ///
+    /// Returns `None` once the client has closed the connection. Pings are answered
+    /// automatically.
+    pub async fn recv(&mut self) -> Option<Result<In, WebSocketError>> {
+        loop {
+            let message = match self.stream.next().await? {
+                Ok(message) => message,
+                Err(e) => return Some(Err(WebSocketError::Socket(e))),
+            };
+            let message = match message {
+                Message::Text(text) => serde_json::from_str(text.as_str()),
+                Message::Binary(bytes) => serde_json::from_slice(&bytes),
+                Message::Ping(_) | Message::Pong(_) => continue,
+                Message::Close(_) => return None,
+            };
+            return Some(message.map_err(WebSocketError::Json));
+        }
+    }
+}
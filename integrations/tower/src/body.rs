@@ -0,0 +1,218 @@
+use bytes::Bytes;
+use futures::{future::poll_fn, Stream, TryStreamExt};
+use http_body::{Frame, SizeHint};
+use std::{
+    fmt,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// An error from the body of a request or a response.
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+type BoxBody =
+    Pin<Box<dyn http_body::Body<Data = Bytes, Error = BoxError> + Send>>;
+
+/// The body of the requests and responses handled by Leptos.
+///
+/// Any [`http_body::Body`] can be turned into a `Body` with [`Body::new`], and a `Body` can be
+/// passed to any server that accepts an `http_body::Body`.
+pub struct Body(BoxBody);
+
+impl Body {
+    /// Wraps any body.
+    pub fn new<B>(body: B) -> Self
+    where
+        B: http_body::Body<Data = Bytes> + Send + 'static,
+        B::Error: Into<BoxError>,
+    {
+        Self(Box::pin(MapErr(Box::pin(body))))
+    }
+
+    /// An empty body.
+    pub fn empty() -> Self {
+        Self::from(Bytes::new())
+    }
+
+    /// A body that streams the chunks of `stream`.
+    pub fn from_stream<S, D, E>(stream: S) -> Self
+    where
+        S: Stream<Item = Result<D, E>> + Send + 'static,
+        D: Into<Bytes> + 'static,
+        E: Into<BoxError> + 'static,
+    {
+        Self(Box::pin(StreamBody(Box::pin(
+            stream.map_ok(Into::into).map_err(Into::into),
+        ))))
+    }
+
+    /// Turns the body into a stream of its chunks of data.
+    pub fn into_data_stream(
+        mut self,
+    ) -> impl Stream<Item = Result<Bytes, BoxError>> + Send {
+        futures::stream::poll_fn(move |cx| loop {
+            match futures::ready!(self.0.as_mut().poll_frame(cx)) {
+                Some(Ok(frame)) => {
+                    // trailers are skipped
+                    if let Ok(data) = frame.into_data() {
+                        return Poll::Ready(Some(Ok(data)));
+                    }
+                }
+                Some(Err(error)) => return Poll::Ready(Some(Err(error))),
+                None => return Poll::Ready(None),
+            }
+        })
+    }
+
+    // reads the whole body
+    pub(crate) async fn collect(mut self) -> Result<Bytes, BoxError> {
+        let mut data = Vec::new();
+        while let Some(frame) =
+            poll_fn(|cx| self.0.as_mut().poll_frame(cx)).await
+        {
+            if let Ok(chunk) = frame?.into_data() {
+                data.extend_from_slice(&chunk);
+            }
+        }
+        Ok(data.into())
+    }
+}
+
+impl Default for Body {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl fmt::Debug for Body {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Body").finish_non_exhaustive()
+    }
+}
+
+impl From<Bytes> for Body {
+    fn from(data: Bytes) -> Self {
+        Self(Box::pin(Full(Some(data))))
+    }
+}
+
+impl From<String> for Body {
+    fn from(data: String) -> Self {
+        Self::from(Bytes::from(data))
+    }
+}
+
+impl From<&'static str> for Body {
+    fn from(data: &'static str) -> Self {
+        Self::from(Bytes::from_static(data.as_bytes()))
+    }
+}
+
+impl From<server_fn::response::generic::Body> for Body {
+    fn from(body: server_fn::response::generic::Body) -> Self {
+        match body {
+            server_fn::response::generic::Body::Sync(data) => Self::from(data),
+            server_fn::response::generic::Body::Async(stream) => {
+                Self::from_stream(stream.map_err(|error| error.to_string()))
+            }
+        }
+    }
+}
+
+impl http_body::Body for Body {
+    type Data = Bytes;
+    type Error = BoxError;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Bytes>, BoxError>>> {
+        self.0.as_mut().poll_frame(cx)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.0.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.0.size_hint()
+    }
+}
+
+struct Full(Option<Bytes>);
+
+impl http_body::Body for Full {
+    type Data = Bytes;
+    type Error = BoxError;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Bytes>, BoxError>>> {
+        Poll::Ready(
+            self.0
+                .take()
+                .filter(|data| !data.is_empty())
+                .map(|data| Ok(Frame::data(data))),
+        )
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.0.as_ref().map_or(true, Bytes::is_empty)
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::with_exact(
+            self.0.as_ref().map_or(0, |data| data.len() as u64),
+        )
+    }
+}
+
+struct StreamBody<S>(Pin<Box<S>>);
+
+impl<S> http_body::Body for StreamBody<S>
+where
+    S: Stream<Item = Result<Bytes, BoxError>>,
+{
+    type Data = Bytes;
+    type Error = BoxError;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Bytes>, BoxError>>> {
+        self.0
+            .as_mut()
+            .poll_next(cx)
+            .map(|chunk| chunk.map(|chunk| chunk.map(Frame::data)))
+    }
+}
+
+struct MapErr<B>(Pin<Box<B>>);
+
+impl<B> http_body::Body for MapErr<B>
+where
+    B: http_body::Body<Data = Bytes>,
+    B::Error: Into<BoxError>,
+{
+    type Data = Bytes;
+    type Error = BoxError;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Bytes>, BoxError>>> {
+        self.0
+            .as_mut()
+            .poll_frame(cx)
+            .map(|frame| frame.map(|frame| frame.map_err(Into::into)))
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.0.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.0.size_hint()
+    }
+}
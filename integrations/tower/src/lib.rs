@@ -0,0 +1,814 @@
+#![forbid(unsafe_code)]
+#![deny(missing_docs)]
+#![allow(clippy::type_complexity)]
+
+//! A [`tower::Service`] that serves a Leptos app, for hosting it on any server built on
+//! `tower` and `http`.
+//!
+//! [`LeptosService`] runs server functions and renders the app for every other request, so that
+//! frameworks like viz, salvo, or plain hyper only need a thin adapter to host Leptos SSR. The
+//! handlers it is built from, [`run_server_fn`] and [`render_response`], are also used by
+//! `leptos_axum`, which adds routing, static files and extractors on top of them.
+//!
+//! ```no_run
+//! use leptos::prelude::*;
+//! use leptos_tower::LeptosService;
+//!
+//! #[component]
+//! fn App() -> impl IntoView {
+//!     view! { <main>"Hello, world!"</main> }
+//! }
+//!
+//! // tasks are spawned with an executor that suits the host's runtime, e.g.,
+//! // `Executor::init_tokio()` on Tokio
+//! any_spawner::Executor::init_futures_executor().unwrap();
+//! let service = LeptosService::new(App);
+//! // hand `service` to any server that accepts a `tower::Service`
+//! ```
+//!
+//! Only `tower` and `http` are needed to host an app: server functions are registered with the
+//! [`generic`](server_fn::generic) server function backend, whose requests carry their whole
+//! body, so [`handle_server_fns`] reads the body before running one. Requests and responses carry
+//! their body as [`Body`], and [`LeptosService`] converts the body of any other request. If the
+//! server knows the address of the client, it should add it to the request extensions as a
+//! [`SocketAddr`](std::net::SocketAddr), which is then available as the
+//! [`remote_addr`](leptos::request::RequestParts::remote_addr) of the request.
+
+use futures::{stream::once, Stream, StreamExt};
+use http::{
+    header::{self, HeaderName, HeaderValue, ACCEPT, LOCATION, REFERER},
+    request::Parts,
+    HeaderMap, Request, Response, StatusCode,
+};
+#[cfg(feature = "tracing")]
+use leptos::request::TraceContext;
+use leptos::{
+    context::provide_context,
+    hydration::IslandsRouterNavigation,
+    reactive::{
+        computed::ScopedFuture,
+        owner::{with_context, Owner, TaskCancellation},
+    },
+    request::{RequestParts, ResponseHeaders},
+    IntoView,
+};
+use leptos_integration_utils::{
+    shutdown, BoxedFnOnce, DisconnectGuard, ExtendResponse, PinnedFuture,
+    PinnedStream,
+};
+use leptos_meta::ServerMetaContext;
+use leptos_router::{
+    components::provide_server_redirect, location::RequestUrl, SsrMode,
+};
+use parking_lot::{Mutex, RwLock};
+use server_fn::{middleware::BoxedService, redirect::REDIRECT_HEADER};
+use std::{
+    convert::Infallible,
+    net::SocketAddr,
+    ptr,
+    sync::{Arc, Weak},
+    task::{Context, Poll},
+};
+
+mod body;
+pub use body::{Body, BoxError};
+
+/// This struct lets you define headers and override the status of the Response from an Element or a Server Function
+/// Typically contained inside of a ResponseOptions. Setting this is useful for cookies and custom responses.
+#[derive(Debug, Clone, Default)]
+pub struct ResponseParts {
+    /// If provided, this will overwrite any other status code for this response.
+    pub status: Option<StatusCode>,
+    /// The map of headers that should be added to the response, replacing any headers it
+    /// already has with the same name.
+    pub headers: HeaderMap,
+}
+
+impl ResponseParts {
+    /// Insert a header, overwriting any previous value with the same key
+    pub fn insert_header(&mut self, key: HeaderName, value: HeaderValue) {
+        self.headers.insert(key, value);
+    }
+    /// Append a header, leaving any header with the same key intact
+    pub fn append_header(&mut self, key: HeaderName, value: HeaderValue) {
+        self.headers.append(key, value);
+    }
+}
+
+/// Allows you to override details of the HTTP response like the status code and add Headers/Cookies.
+///
+/// `ResponseOptions` is provided via context by [`handle_server_fns`], [`render_response`], and
+/// the integrations that are built on them. If you provide your own handler, you will need to
+/// provide `ResponseOptions` via context yourself if you want to access it via context.
+/// ```
+/// use leptos::prelude::*;
+///
+/// #[server]
+/// pub async fn get_opts() -> Result<(), ServerFnError> {
+///     let opts = expect_context::<leptos_tower::ResponseOptions>();
+///     Ok(())
+/// }
+/// ```
+///
+/// ## Streaming
+///
+/// When a page is streamed, the status and headers are sent along with the first chunk of HTML.
+/// Anything set while the shell renders, i.e., outside `<Suspense/>`, is always applied, so a
+/// `NotFound` component used as the router's fallback can set a 404 in every [`SsrMode`]:
+/// ```
+/// use http::StatusCode;
+/// use leptos::prelude::*;
+///
+/// #[component]
+/// fn NotFound() -> impl IntoView {
+///     if let Some(res) = use_context::<leptos_tower::ResponseOptions>() {
+///         res.set_status(StatusCode::NOT_FOUND);
+///     }
+///     view! { <h1>"Not Found"</h1> }
+/// }
+/// ```
+///
+/// Inside `<Suspense/>`, whether changes are applied depends on the mode:
+/// - [`SsrMode::OutOfOrder`], [`SsrMode::PartiallyBlocked`], and [`SsrMode::InOrder`] send the
+///   first chunk before suspended content has loaded. Load data that decides the status with a
+///   [blocking resource](leptos::server::Resource::new_blocking), which holds back the first
+///   chunk until it resolves.
+/// - [`SsrMode::Async`] renders the whole page before sending anything, so changes from anywhere
+///   in the tree are applied.
+///
+/// Changes made after the status and headers have been sent are ignored, and a warning is
+/// logged. [`is_sent`](ResponseOptions::is_sent) tells whether this has happened yet.
+///
+/// Changes made directly through the `ResponseParts` in this struct are not checked.
+#[derive(Debug, Clone, Default)]
+pub struct ResponseOptions(pub Arc<RwLock<ResponseParts>>);
+
+// The parts of the responses whose status and headers have been sent, which are added while
+// they are locked for writing. They are held weakly, so that they don't outlive their responses,
+// but that keeps their allocations, and so their addresses, from being reused while listed.
+static SENT: Mutex<Vec<Weak<RwLock<ResponseParts>>>> = Mutex::new(Vec::new());
+
+impl ResponseOptions {
+    /// A simpler way to overwrite the contents of `ResponseOptions` with a new `ResponseParts`.
+    pub fn overwrite(&self, parts: ResponseParts) {
+        self.modify("overwrite", |res_parts| *res_parts = parts);
+    }
+    /// Set the status of the returned Response.
+    pub fn set_status(&self, status: StatusCode) {
+        self.modify("set_status", |res_parts| res_parts.status = Some(status));
+    }
+    /// Insert a header, overwriting any previous value with the same key.
+    pub fn insert_header(&self, key: HeaderName, value: HeaderValue) {
+        self.modify("insert_header", |res_parts| {
+            res_parts.headers.insert(key, value);
+        });
+    }
+    /// Append a header, leaving any header with the same key intact.
+    pub fn append_header(&self, key: HeaderName, value: HeaderValue) {
+        self.modify("append_header", |res_parts| {
+            res_parts.headers.append(key, value);
+        });
+    }
+    /// Add a `Set-Cookie` header, leaving any other cookies intact.
+    pub fn set_cookie(&self, cookie: HeaderValue) {
+        self.modify("set_cookie", |res_parts| {
+            res_parts.headers.append(header::SET_COOKIE, cookie);
+        });
+    }
+    /// Whether the status and headers have already been sent to the client.
+    ///
+    /// Once they have been sent, any further changes are ignored.
+    pub fn is_sent(&self) -> bool {
+        let parts = Arc::as_ptr(&self.0);
+        SENT.lock().iter().any(|sent| ptr::eq(sent.as_ptr(), parts))
+    }
+
+    /// The status that has been set for the response, if any.
+    pub fn status(&self) -> Option<StatusCode> {
+        self.0.read().status
+    }
+
+    /// Sets the status and headers on a response that is about to be sent, after which any
+    /// further changes are ignored.
+    pub fn apply<B>(&self, res: &mut Response<B>) {
+        let mut parts = self.0.write();
+        self.mark_sent();
+        if let Some(status) = parts.status {
+            *res.status_mut() = status;
+        }
+        res.headers_mut().extend(std::mem::take(&mut parts.headers));
+    }
+
+    fn mark_sent(&self) {
+        if !self.is_sent() {
+            let mut sent = SENT.lock();
+            sent.retain(|parts| parts.strong_count() > 0);
+            sent.push(Arc::downgrade(&self.0));
+        }
+    }
+
+    fn modify(&self, method: &str, fun: impl FnOnce(&mut ResponseParts)) {
+        let mut parts = self.0.write();
+        if self.is_sent() {
+            leptos::logging::warn!(
+                "ResponseOptions::{method} was called after the response \
+                 status and headers were sent, so it has no effect. Set them \
+                 before the first chunk of HTML is streamed, for example from \
+                 a blocking resource or with SsrMode::Async."
+            );
+        } else {
+            fun(&mut parts);
+        }
+    }
+}
+
+struct TowerResponse(Response<Body>);
+
+impl ExtendResponse for TowerResponse {
+    type ResponseOptions = ResponseOptions;
+
+    fn from_stream(
+        stream: impl Stream<Item = String> + Send + 'static,
+    ) -> Self {
+        TowerResponse(Response::new(Body::from_stream(
+            stream.map(|chunk| Ok(chunk) as Result<String, std::io::Error>),
+        )))
+    }
+
+    fn extend_response(&mut self, res_options: &Self::ResponseOptions) {
+        res_options.apply(&mut self.0);
+    }
+
+    fn set_default_content_type(&mut self, content_type: &str) {
+        let headers = self.0.headers_mut();
+        if !headers.contains_key(header::CONTENT_TYPE) {
+            // Set the Content Type headers on all responses. This makes Firefox show the page source
+            // without complaining
+            headers.insert(
+                header::CONTENT_TYPE,
+                HeaderValue::from_str(content_type).unwrap(),
+            );
+        }
+    }
+}
+
+/// Provides an easy way to redirect the user from within a server function.
+///
+/// Calling `redirect` in a server function will redirect the browser in three
+/// situations:
+/// 1. A server function that is calling in a [blocking
+///    resource](leptos::server::Resource::new_blocking).
+/// 2. A server function that is called from WASM running in the client (e.g., a dispatched action
+///    or a spawned `Future`).
+/// 3. A `<form>` submitted to the server function endpoint using default browser APIs (often due
+///    to using [`ActionForm`](leptos::form::ActionForm) without JS/WASM present.)
+///
+/// Using it with a non-blocking [`Resource`](leptos::server::Resource) will not work if you are using streaming rendering,
+/// as the response's headers will already have been sent by the time the server function calls `redirect()`.
+///
+/// ### Implementation
+///
+/// This sets the `Location` header to the URL given.
+///
+/// If the route or server function in which this is called is being accessed
+/// by an ordinary `GET` request or an HTML `<form>` without any enhancement, it also sets a
+/// status code of `302` for a temporary redirect. (This is determined by whether the `Accept`
+/// header contains `text/html` as it does for an ordinary navigation.)
+///
+/// Otherwise, it sets a custom header that indicates to the client that it should redirect,
+/// without actually setting the status code. This means that the client will not follow the
+/// redirect, and can therefore return the value of the server function and then handle
+/// the redirect with client-side routing.
+pub fn redirect(path: &str) {
+    if let (Some(req), Some(res)) = (
+        with_context(Parts::clone),
+        with_context(ResponseOptions::clone),
+    ) {
+        // insert the Location header in any case
+        res.insert_header(
+            header::LOCATION,
+            header::HeaderValue::from_str(path)
+                .expect("Failed to create HeaderValue"),
+        );
+
+        let accepts_html = req
+            .headers
+            .get(ACCEPT)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.contains("text/html"))
+            .unwrap_or(false);
+        if accepts_html {
+            // if the request accepts text/html, it's a plain form request and needs
+            // to have the 302 code set
+            res.set_status(StatusCode::FOUND);
+        } else {
+            // otherwise, we sent it from the server fn client and actually don't want
+            // to set a real redirect, as this will break the ability to return data
+            // instead, set the REDIRECT_HEADER to indicate that the client should redirect
+            res.insert_header(
+                HeaderName::from_static(REDIRECT_HEADER),
+                HeaderValue::from_str("").unwrap(),
+            );
+        }
+    } else {
+        #[cfg(feature = "tracing")]
+        {
+            tracing::warn!(
+                "Couldn't retrieve either Parts or ResponseOptions while \
+                 trying to redirect()."
+            );
+        }
+        #[cfg(not(feature = "tracing"))]
+        {
+            eprintln!(
+                "Couldn't retrieve either Parts or ResponseOptions while \
+                 trying to redirect()."
+            );
+        }
+    }
+}
+
+/// Decomposes an HTTP request into its parts, allowing you to read its headers
+/// and other data without consuming the body. Creates a new Request from the
+/// original parts for further processing
+pub fn generate_request_and_parts<B>(req: Request<B>) -> (Request<B>, Parts) {
+    let (parts, body) = req.into_parts();
+    let parts2 = parts.clone();
+    (Request::from_parts(parts, body), parts2)
+}
+
+fn request_parts(parts: &Parts) -> RequestParts {
+    RequestParts {
+        method: parts.method.clone(),
+        uri: parts.uri.clone(),
+        version: parts.version,
+        headers: parts.headers.clone(),
+        remote_addr: parts.extensions.get::<SocketAddr>().copied(),
+        extensions: parts.extensions.clone(),
+    }
+}
+
+fn response_headers(res_options: &ResponseOptions) -> ResponseHeaders {
+    let res_options = res_options.clone();
+    ResponseHeaders::new(move |name, value| {
+        let sent = res_options.is_sent();
+        res_options.append_header(name, value);
+        !sent
+    })
+}
+
+fn shutting_down_response<B: From<String>>() -> Response<B> {
+    Response::builder()
+        .status(StatusCode::SERVICE_UNAVAILABLE)
+        .header(header::CONNECTION, "close")
+        .body(B::from("The server is shutting down.".to_string()))
+        .unwrap()
+}
+
+/// Runs the server function registered for the path and method of the request, and returns its
+/// response. Requests for which no server function is registered are answered with
+/// `400 Bad Request`.
+///
+/// Server functions are looked up among those registered with the
+/// [`generic`](server_fn::generic) server function backend, whose requests carry their whole
+/// body, so the body of the request is read before the server function runs. Integrations that
+/// register server functions with another backend look them up themselves, and run them with
+/// [`run_server_fn`].
+///
+/// `additional_context` is run in the reactive scope of the server function, so that it can
+/// provide context to it.
+///
+/// ## Provided Context Types
+/// This function always provides context values including the following types:
+/// - [`Parts`]
+/// - [`RequestParts`](leptos::request::RequestParts)
+/// - [`ResponseHeaders`](leptos::request::ResponseHeaders)
+/// - [`TaskCancellation`](leptos::reactive::owner::TaskCancellation), which is cancelled if the
+///   client disconnects before the response is complete
+/// - [`ResponseOptions`]
+pub async fn handle_server_fns(
+    additional_context: impl Fn() + 'static + Clone + Send,
+    req: Request<Body>,
+) -> Response<Body> {
+    let service = server_fn::generic::get_server_fn_service(
+        req.uri().path(),
+        req.method().clone(),
+    );
+    serve_server_fn(additional_context, req, service).await
+}
+
+/// Reads the body of `req` and runs `service`, which was looked up for it.
+async fn serve_server_fn(
+    additional_context: impl Fn() + 'static + Clone + Send,
+    req: Request<Body>,
+    service: Option<
+        BoxedService<
+            Request<bytes::Bytes>,
+            Response<server_fn::response::generic::Body>,
+        >,
+    >,
+) -> Response<Body> {
+    if shutdown::is_shutting_down() {
+        return shutting_down_response();
+    }
+
+    let (parts, body) = req.into_parts();
+    let body = match service {
+        Some(_) => match body.collect().await {
+            Ok(body) => body,
+            Err(error) => {
+                return Response::builder()
+                    .status(StatusCode::BAD_REQUEST)
+                    .body(Body::from(format!(
+                        "Could not read the body of the request: {error}"
+                    )))
+                    .expect("could not build Response")
+            }
+        },
+        None => Default::default(),
+    };
+    run_server_fn(
+        additional_context,
+        Request::from_parts(parts, body),
+        service,
+    )
+    .await
+    .map(Body::from)
+}
+
+/// Runs `service`, the server function registered for the path and method of `req`, and
+/// returns its response, like [`handle_server_fns`]. If `service` is `None`, the request is
+/// answered with `400 Bad Request`.
+///
+/// This is for integrations that register server functions with their own server function
+/// backend, and look them up in its registry.
+pub async fn run_server_fn<ReqBody, ResBody>(
+    additional_context: impl Fn() + 'static + Clone + Send,
+    req: Request<ReqBody>,
+    service: Option<BoxedService<Request<ReqBody>, Response<ResBody>>>,
+) -> Response<ResBody>
+where
+    ReqBody: Send + 'static,
+    ResBody: From<String> + 'static,
+{
+    if shutdown::is_shutting_down() {
+        return shutting_down_response();
+    }
+
+    let path = req.uri().path().to_string();
+    let (req, parts) = generate_request_and_parts(req);
+
+    if let Some(mut service) = service {
+        #[cfg(feature = "tracing")]
+        let span = leptos_integration_utils::request_span(
+            "server_fn",
+            req.method().as_str(),
+            &path,
+            TraceContext::from_headers(req.headers()).as_ref(),
+        );
+        let owner = Owner::new();
+        let res = owner.with(|| {
+            ScopedFuture::new(async move {
+                provide_context(request_parts(&parts));
+                provide_context(parts);
+                let res_options = ResponseOptions::default();
+                provide_context(response_headers(&res_options));
+                provide_context(res_options.clone());
+                let cancellation = TaskCancellation::new();
+                provide_context(cancellation.clone());
+                let disconnect = DisconnectGuard::new(cancellation);
+                additional_context();
+
+                // store Accepts and Referer in case we need them for redirect (below)
+                let accepts_html = req
+                    .headers()
+                    .get(ACCEPT)
+                    .and_then(|v| v.to_str().ok())
+                    .map(|v| v.contains("text/html"))
+                    .unwrap_or(false);
+                let referrer = req.headers().get(REFERER).cloned();
+
+                // actually run the server fn
+                let mut res = service.run(req).await;
+                disconnect.disarm();
+
+                // if it accepts text/html (i.e., is a plain form post) and doesn't already have a
+                // Location set, then redirect to the Referer
+                if accepts_html {
+                    if let Some(referrer) = referrer {
+                        let has_location =
+                            res.headers().get(LOCATION).is_some();
+                        if !has_location {
+                            *res.status_mut() = StatusCode::FOUND;
+                            res.headers_mut().insert(LOCATION, referrer);
+                        }
+                    }
+                }
+
+                // apply status code and headers if user changed them
+                res_options.apply(&mut res);
+                res
+            })
+        });
+        #[cfg(feature = "tracing")]
+        let res = tracing::Instrument::instrument(res, span);
+        any_spawner::LocalTasks::new().drive(res).await
+    } else {
+        Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(ResBody::from(format!(
+                "Could not find a server function at the route {path}. \
+                 \n\nIt's likely that either
+                         1. The API prefix you specify in the `#[server]` \
+                 macro doesn't match the prefix at which your server function \
+                 handler is mounted, or \n2. You are on a platform that \
+                 doesn't support automatic server function registration and \
+                 you need to call ServerFn::register_explicit() on the server \
+                 function type, somewhere in your `main` function.",
+            )))
+            .expect("could not build Response")
+    }
+}
+
+/// Renders the app for a request, and returns a response that streams its HTML.
+///
+/// `stream_builder` turns the app into a stream of HTML, followed by the `chunks` of any data
+/// that is serialized for hydration. Whether the client supports out-of-order streaming is
+/// passed as its last argument. [`stream_builder`] returns the one used for each [`SsrMode`].
+///
+/// ## Provided Context Types
+/// This function always provides context values including the following types:
+/// - [`Parts`]
+/// - [`RequestParts`](leptos::request::RequestParts)
+/// - [`ResponseHeaders`](leptos::request::ResponseHeaders)
+/// - [`TaskCancellation`](leptos::reactive::owner::TaskCancellation), which is cancelled if the
+///   client disconnects before the response is complete
+/// - [`ResponseOptions`]
+/// - [`ServerMetaContext`]
+pub fn render_response<IV, B>(
+    additional_context: impl Fn() + 'static + Clone + Send,
+    app_fn: impl FnOnce() -> IV + Send + 'static,
+    req: Request<B>,
+    stream_builder: fn(
+        IV,
+        BoxedFnOnce<PinnedStream<String>>,
+        bool,
+    ) -> PinnedFuture<PinnedStream<String>>,
+) -> PinnedFuture<Response<Body>>
+where
+    IV: IntoView + 'static,
+    B: Send + 'static,
+{
+    #[cfg(feature = "tracing")]
+    let span = leptos_integration_utils::request_span(
+        "ssr",
+        req.method().as_str(),
+        req.uri().path(),
+        TraceContext::from_headers(req.headers()).as_ref(),
+    );
+    let res = async move {
+        if shutdown::is_shutting_down() {
+            return shutting_down_response();
+        }
+
+        let is_island_router_navigation = cfg!(feature = "islands-router")
+            && req.headers().get("Islands-Router").is_some();
+
+        let add_context = additional_context.clone();
+        let res_options = ResponseOptions::default();
+        let (meta_context, meta_output) = ServerMetaContext::new();
+
+        let additional_context = {
+            let meta_context = meta_context.clone();
+            let res_options = res_options.clone();
+            move || {
+                // Need to get the path and query string of the Request
+                // For reasons that escape me, if the incoming URI protocol is https, it provides the absolute URI
+                let path = req.uri().path_and_query().unwrap().as_str();
+
+                let full_path = format!("http://leptos.dev{path}");
+                let (_, req_parts) = generate_request_and_parts(req);
+                provide_contexts(
+                    &full_path,
+                    &meta_context,
+                    req_parts,
+                    res_options.clone(),
+                );
+                add_context();
+
+                if is_island_router_navigation {
+                    provide_context(IslandsRouterNavigation);
+                }
+            }
+        };
+
+        let res = TowerResponse::from_app(
+            app_fn,
+            meta_output,
+            additional_context,
+            res_options,
+            stream_builder,
+            !is_island_router_navigation,
+        )
+        .await;
+
+        res.0
+    };
+    #[cfg(feature = "tracing")]
+    let res = tracing::Instrument::instrument(res, span);
+    Box::pin(res)
+}
+
+/// Provides the context values that [`render_response`] provides to the app, for rendering it
+/// outside of a request, e.g., to generate its route list or its static pages.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "trace", fields(error), skip_all)
+)]
+pub fn provide_contexts(
+    path: &str,
+    meta_context: &ServerMetaContext,
+    parts: Parts,
+    default_res_options: ResponseOptions,
+) {
+    provide_context(RequestUrl::new(path));
+    provide_context(meta_context.clone());
+    provide_context(request_parts(&parts));
+    provide_context(parts);
+    provide_context(response_headers(&default_res_options));
+    provide_context(default_res_options);
+    provide_server_redirect(redirect);
+    leptos::nonce::provide_nonce();
+}
+
+/// Returns the function that [`render_response`] uses to stream the HTML of the app in the given
+/// mode.
+///
+/// [`SsrMode::Static`] pages are rendered on each request like [`SsrMode::Async`] ones, as there
+/// are no files to serve them from.
+pub fn stream_builder<IV>(
+    mode: &SsrMode,
+) -> fn(
+    IV,
+    BoxedFnOnce<PinnedStream<String>>,
+    bool,
+) -> PinnedFuture<PinnedStream<String>>
+where
+    IV: IntoView + 'static,
+{
+    match mode {
+        SsrMode::OutOfOrder | SsrMode::PartiallyBlocked => {
+            |app, chunks, supports_ooo| {
+                Box::pin(async move {
+                    let app = if cfg!(feature = "islands-router") {
+                        if supports_ooo {
+                            app.to_html_stream_out_of_order_branching()
+                        } else {
+                            app.to_html_stream_in_order_branching()
+                        }
+                    } else if supports_ooo {
+                        app.to_html_stream_out_of_order()
+                    } else {
+                        app.to_html_stream_in_order()
+                    };
+                    Box::pin(app.chain(chunks())) as PinnedStream<String>
+                })
+            }
+        }
+        SsrMode::InOrder => |app, chunks, _supports_ooo| {
+            let app = if cfg!(feature = "islands-router") {
+                app.to_html_stream_in_order_branching()
+            } else {
+                app.to_html_stream_in_order()
+            };
+            Box::pin(async move {
+                Box::pin(app.chain(chunks())) as PinnedStream<String>
+            })
+        },
+        SsrMode::Async | SsrMode::Static(_) => |app, chunks, _supports_ooo| {
+            Box::pin(async move {
+                let app = if cfg!(feature = "islands-router") {
+                    app.to_html_stream_in_order_branching()
+                } else {
+                    app.to_html_stream_in_order()
+                };
+                let app = app.collect::<String>().await;
+                let chunks = chunks();
+                Box::pin(once(async move { app }).chain(chunks))
+                    as PinnedStream<String>
+            })
+        },
+    }
+}
+
+/// A [`tower::Service`] that runs the server functions of the app, and renders the app for any
+/// other request.
+///
+/// Responses are never errors: the app decides the status of the pages it renders, e.g., a
+/// `404` from the fallback of its router.
+pub struct LeptosService<F> {
+    app_fn: F,
+    additional_context: Arc<dyn Fn() + Send + Sync>,
+    mode: SsrMode,
+}
+
+impl<F> Clone for LeptosService<F>
+where
+    F: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            app_fn: self.app_fn.clone(),
+            additional_context: Arc::clone(&self.additional_context),
+            mode: self.mode.clone(),
+        }
+    }
+}
+
+impl<F> std::fmt::Debug for LeptosService<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LeptosService")
+            .field("mode", &self.mode)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<F, IV> LeptosService<F>
+where
+    F: Fn() -> IV + Clone + Send + 'static,
+    IV: IntoView + 'static,
+{
+    /// Creates a service that renders the app returned by `app_fn`, streaming it out of order.
+    ///
+    /// To serve the app below its [`base_path`](leptos::config::LeptosOptions::base_path), or
+    /// server functions at its
+    /// [`server_fn_mount`](leptos::config::LeptosOptions::server_fn_mount), call
+    /// [`mount_server_fns`](leptos::hydration::mount_server_fns) first.
+    pub fn new(app_fn: F) -> Self {
+        Self {
+            app_fn,
+            additional_context: Arc::new(|| {}),
+            mode: SsrMode::OutOfOrder,
+        }
+    }
+
+    /// Runs `additional_context` for each request, both when rendering the app and when running
+    /// a server function, so that it can provide context to them.
+    pub fn with_context(
+        mut self,
+        additional_context: impl Fn() + Send + Sync + 'static,
+    ) -> Self {
+        self.additional_context = Arc::new(additional_context);
+        self
+    }
+
+    /// Sets how the app is rendered. See [`stream_builder`] for the modes that are supported.
+    pub fn ssr_mode(mut self, mode: SsrMode) -> Self {
+        self.mode = mode;
+        self
+    }
+}
+
+impl<F, IV, B> tower::Service<Request<B>> for LeptosService<F>
+where
+    F: Fn() -> IV + Clone + Send + 'static,
+    IV: IntoView + 'static,
+    B: http_body::Body<Data = bytes::Bytes> + Send + 'static,
+    B::Error: Into<BoxError>,
+{
+    type Response = Response<Body>;
+    type Error = Infallible;
+    type Future = PinnedFuture<Result<Response<Body>, Infallible>>;
+
+    fn poll_ready(
+        &mut self,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        let req = req.map(Body::new);
+        let additional_context = {
+            let additional_context = Arc::clone(&self.additional_context);
+            move || additional_context()
+        };
+        let service = server_fn::generic::get_server_fn_service(
+            req.uri().path(),
+            req.method().clone(),
+        );
+        if service.is_some() {
+            Box::pin(async move {
+                Ok(serve_server_fn(additional_context, req, service).await)
+            })
+        } else {
+            let res = render_response(
+                additional_context,
+                self.app_fn.clone(),
+                req,
+                stream_builder(&self.mode),
+            );
+            Box::pin(async move { Ok(res.await) })
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use http::{header, HeaderValue, Response, StatusCode};
+use leptos_tower::{ResponseOptions, ResponseParts};
+use parking_lot::RwLock;
+use std::{env, process::Command, sync::Arc};
+
+#[test]
+fn apply_copies_status_headers_and_cookies() {
+    let res_options = ResponseOptions::default();
+    res_options.set_status(StatusCode::CREATED);
+    res_options.insert_header(
+        header::CACHE_CONTROL,
+        HeaderValue::from_static("no-store"),
+    );
+    res_options.set_cookie(HeaderValue::from_static("a=1"));
+    res_options.set_cookie(HeaderValue::from_static("b=2"));
+    assert!(!res_options.is_sent());
+
+    let mut res = Response::new(());
+    res_options.apply(&mut res);
+
+    assert!(res_options.is_sent());
+    assert_eq!(res.status(), StatusCode::CREATED);
+    assert_eq!(res.headers()[header::CACHE_CONTROL], "no-store");
+    let cookies = res.headers().get_all(header::SET_COOKIE);
+    assert_eq!(cookies.iter().collect::<Vec<_>>(), ["a=1", "b=2"]);
+}
+
+#[test]
+fn options_built_from_parts_are_sent_separately() {
+    let parts = ResponseParts {
+        status: Some(StatusCode::ACCEPTED),
+        ..Default::default()
+    };
+    let first = ResponseOptions(Arc::new(RwLock::new(parts)));
+    let second = ResponseOptions::default();
+    let copy = first.clone();
+
+    first.apply(&mut Response::new(()));
+    assert!(first.is_sent());
+    assert!(copy.is_sent());
+    assert!(!second.is_sent());
+
+    let ResponseOptions(parts) = copy;
+    assert_eq!(parts.read().status, Some(StatusCode::ACCEPTED));
+    drop((first, parts));
+    // responses created afterwards start out unsent, even if they reuse the memory
+    for _ in 0..100 {
+        assert!(!ResponseOptions::default().is_sent());
+    }
+}
+
+// the warning is printed to stderr, which the test harness captures, so the
+// changes are made again in a child process whose output can be read
+#[test]
+fn changes_after_the_response_is_sent_warn_and_are_ignored() {
+    const CHILD: &str = "LEPTOS_TOWER_SENT_RESPONSE_CHILD";
+
+    if env::var_os(CHILD).is_some() {
+        let res_options = ResponseOptions::default();
+        res_options.set_status(StatusCode::CREATED);
+        let mut res = Response::new(());
+        res_options.apply(&mut res);
+
+        res_options.set_status(StatusCode::NOT_FOUND);
+        res_options.set_cookie(HeaderValue::from_static("late=1"));
+
+        assert_eq!(res_options.status(), Some(StatusCode::CREATED));
+        assert!(res_options.0.read().headers.is_empty());
+        // applying again, as a second response would, only sees the changes made in time
+        let mut again = Response::new(());
+        res_options.apply(&mut again);
+        assert_eq!(again.status(), StatusCode::CREATED);
+        assert!(!again.headers().contains_key(header::SET_COOKIE));
+        return;
+    }
+
+    let output = Command::new(env::current_exe().unwrap())
+        .args([
+            "changes_after_the_response_is_sent_warn_and_are_ignored",
+            "--exact",
+            "--nocapture",
+        ])
+        .env(CHILD, "1")
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(output.status.success(), "{stderr}");
+    assert!(
+        stderr.contains("ResponseOptions::set_status was called after"),
+        "{stderr}"
+    );
+    assert!(
+        stderr.contains("ResponseOptions::set_cookie was called after"),
+        "{stderr}"
+    );
+}
@@ -0,0 +1,85 @@
+use futures::{executor::block_on, StreamExt};
+use http::{header, HeaderValue, Request, StatusCode};
+use leptos::{prelude::*, request::use_request_parts};
+use leptos_tower::{Body, LeptosService, ResponseOptions};
+use std::net::SocketAddr;
+use tower::ServiceExt;
+
+#[component]
+fn App() -> impl IntoView {
+    expect_context::<ResponseOptions>().insert_header(
+        header::CACHE_CONTROL,
+        HeaderValue::from_static("no-store"),
+    );
+    view! { <p>"Hello from " {use_context::<&'static str>()}</p> }
+}
+
+// the backend is named, as `leptos_axum` enables its own when the workspace is built together
+#[server(
+    prefix = "/api",
+    endpoint = "client_addr",
+    server = leptos::server_fn::generic::GenericServerFnBackend
+)]
+async fn client_addr() -> Result<String, ServerFnError> {
+    let conn: leptos::request::ConnectionInfo = use_request_parts()?;
+    Ok(format!("{:?}", conn.remote_addr))
+}
+
+#[server(
+    prefix = "/api",
+    endpoint = "greet",
+    server = leptos::server_fn::generic::GenericServerFnBackend
+)]
+async fn greet(name: String) -> Result<String, ServerFnError> {
+    Ok(format!("Hello, {name}!"))
+}
+
+async fn body_text(body: Body) -> String {
+    let chunks = body.into_data_stream().collect::<Vec<_>>().await;
+    chunks
+        .into_iter()
+        .map(|chunk| String::from_utf8(chunk.unwrap().to_vec()).unwrap())
+        .collect()
+}
+
+#[test]
+fn service_renders_the_app_and_runs_server_fns() {
+    _ = any_spawner::Executor::init_futures_executor();
+    let service =
+        LeptosService::new(App).with_context(|| provide_context("tower"));
+
+    block_on(async {
+        let req = Request::get("/").body(String::new()).unwrap();
+        let res = service.clone().oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.headers()[header::CACHE_CONTROL], "no-store");
+        assert_eq!(
+            res.headers()[header::CONTENT_TYPE],
+            "text/html; charset=utf-8"
+        );
+        let html = body_text(res.into_body()).await;
+        assert!(html.starts_with("<p>Hello from <!>tower</p>"), "{html}");
+
+        let addr = SocketAddr::from(([127, 0, 0, 1], 4000));
+        let mut req = Request::post("/api/client_addr")
+            .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(String::new())
+            .unwrap();
+        req.extensions_mut().insert(addr);
+        let res = service.clone().oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(
+            body_text(res.into_body()).await,
+            "\"Some(127.0.0.1:4000)\""
+        );
+
+        // the body of the request is read before the server function runs
+        let req = Request::post("/api/greet")
+            .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(String::from("name=Ana"))
+            .unwrap();
+        let res = service.oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(body_text(res.into_body()).await, "\"Hello, Ana!\"");
+    });
+}
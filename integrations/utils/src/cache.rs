@@ -0,0 +1,588 @@
+//! A cache of complete server-rendered pages.
+//!
+//! A [`PageCache`] stores the responses to `GET` requests once they have been streamed to the
+//! client, and serves later requests for the same URL from memory, without rendering the page
+//! again. The first request for a page is still streamed as it renders.
+//!
+//! Responses are only stored if they can be shared between users: they must have a `200 OK`
+//! status, no `Set-Cookie` header, no `Cache-Control` directive like `private` or `no-store`, no
+//! `Vary: *`, and no CSP nonce. A page can opt out of caching by setting such a header with
+//! `ResponseOptions`. A stored page is only served to requests that match the original one in
+//! every header named by its `Vary` header.
+//!
+//! Requests that carry an `Authorization` or `Cookie` header are always rendered, unless the
+//! cache is told with [`PageCache::allow_authorization`] or [`PageCache::allow_cookies`] that the
+//! pages don't depend on them. Requests that carry one of the cookies marked with
+//! [`PageCache::bypass_cookie`], like the session cookie, are then still rendered.
+//!
+//! After a mutation, server functions can remove the pages it affects with [`invalidate`], which
+//! applies to every cache in the process:
+//!
+//! ```rust,ignore
+//! #[server]
+//! pub async fn add_post(title: String) -> Result<(), ServerFnError> {
+//!     db::insert_post(&title).await?;
+//!     leptos_axum::cache::invalidate("/posts");
+//!     Ok(())
+//! }
+//! ```
+//!
+//! The server integrations provide a `cache_pages` middleware that uses a [`PageCache`].
+
+use bytes::Bytes;
+use futures::{stream, Stream, StreamExt};
+use http::{
+    header, HeaderMap, HeaderName, HeaderValue, Method, StatusCode, Uri,
+};
+use or_poisoned::OrPoisoned;
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, OnceLock, Weak,
+    },
+    time::{Duration, Instant},
+};
+
+/// Every cache in the process, so that pages can be invalidated without a handle to the cache.
+fn registry() -> &'static Mutex<Vec<Weak<Pages>>> {
+    static REGISTRY: OnceLock<Mutex<Vec<Weak<Pages>>>> = OnceLock::new();
+    REGISTRY.get_or_init(Default::default)
+}
+
+/// Removes the cached pages for `path` from every [`PageCache`], whatever their query string.
+pub fn invalidate(path: &str) {
+    for pages in live_caches() {
+        pages.invalidate(|key| key.path == path);
+    }
+}
+
+/// Removes every page from every [`PageCache`].
+pub fn invalidate_all() {
+    for pages in live_caches() {
+        pages.invalidate(|_| true);
+    }
+}
+
+fn live_caches() -> Vec<Arc<Pages>> {
+    let mut registry = registry().lock().or_poisoned();
+    registry.retain(|pages| pages.strong_count() > 0);
+    registry.iter().filter_map(Weak::upgrade).collect()
+}
+
+/// Identifies the cached version of a page: its URL, along with the values of the headers and
+/// cookies that the cache varies on.
+#[derive(Debug, Clone)]
+pub struct CacheKey {
+    page: PageKey,
+    // checked against the `Vary` header of the stored response
+    headers: HeaderMap,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct PageKey {
+    path: String,
+    query: Option<String>,
+    vary: Vec<Option<String>>,
+}
+
+/// A complete response that has been stored in a [`PageCache`].
+#[derive(Debug, Clone)]
+pub struct CachedPage {
+    /// The status of the response.
+    pub status: StatusCode,
+    /// The headers of the response.
+    pub headers: HeaderMap,
+    /// The body of the response.
+    pub body: Bytes,
+    // the request headers named by the `Vary` header of the response, with their values
+    vary: Vec<(HeaderName, Option<HeaderValue>)>,
+    stored_at: Instant,
+}
+
+impl CachedPage {
+    /// How long ago the page was stored, as the value of an `Age` header.
+    pub fn age(&self) -> HeaderValue {
+        HeaderValue::from(self.stored_at.elapsed().as_secs())
+    }
+}
+
+#[derive(Debug, Default)]
+struct Pages {
+    entries: Mutex<HashMap<PageKey, CachedPage>>,
+    // incremented by every invalidation, so that pages which were rendering at the time are
+    // not stored
+    generation: AtomicU64,
+}
+
+impl Pages {
+    fn invalidate(&self, matches: impl Fn(&PageKey) -> bool) {
+        self.generation.fetch_add(1, Ordering::AcqRel);
+        self.entries
+            .lock()
+            .or_poisoned()
+            .retain(|key, _| !matches(key));
+    }
+}
+
+/// An in-memory cache of server-rendered pages. See the [module docs](self).
+///
+/// Clones of a `PageCache` share the same pages.
+#[derive(Debug, Clone)]
+pub struct PageCache {
+    ttl: Option<Duration>,
+    max_pages: usize,
+    vary_headers: Vec<HeaderName>,
+    vary_cookies: Vec<String>,
+    bypass_cookies: Vec<String>,
+    allow_cookies: bool,
+    allow_authorization: bool,
+    pages: Arc<Pages>,
+}
+
+impl Default for PageCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PageCache {
+    /// Creates an empty cache, which keeps pages until they are invalidated and holds at most
+    /// 1,000 of them.
+    pub fn new() -> Self {
+        let pages = Arc::new(Pages::default());
+        registry().lock().or_poisoned().push(Arc::downgrade(&pages));
+        Self {
+            ttl: None,
+            max_pages: 1_000,
+            // the islands router requests a different response for client-side navigations
+            vary_headers: vec![HeaderName::from_static("islands-router")],
+            vary_cookies: Vec::new(),
+            bypass_cookies: Vec::new(),
+            allow_cookies: false,
+            allow_authorization: false,
+            pages,
+        }
+    }
+
+    /// Sets how long a page is served from the cache before it is rendered again.
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Sets the largest number of pages to keep. The oldest page is removed to make room for a
+    /// new one.
+    pub fn max_pages(mut self, max_pages: usize) -> Self {
+        self.max_pages = max_pages;
+        self
+    }
+
+    /// Caches a separate version of each page for each value of the request header `name`, like
+    /// `Accept-Language`.
+    pub fn vary_header(mut self, name: HeaderName) -> Self {
+        self.vary_headers.push(name);
+        self
+    }
+
+    /// Caches a separate version of each page for each value of the cookie `name`, like a
+    /// theme or locale preference. This only has an effect along with
+    /// [`allow_cookies`](Self::allow_cookies).
+    pub fn vary_cookie(mut self, name: impl Into<String>) -> Self {
+        self.vary_cookies.push(name.into());
+        self
+    }
+
+    /// Renders every request that carries the cookie `name`, like a session cookie, instead of
+    /// using the cache, when [`allow_cookies`](Self::allow_cookies) is set.
+    pub fn bypass_cookie(mut self, name: impl Into<String>) -> Self {
+        self.bypass_cookies.push(name.into());
+        self
+    }
+
+    /// Uses the cache for requests that carry a `Cookie` header, which are otherwise always
+    /// rendered.
+    ///
+    /// Only set this if pages depend on no cookies other than those marked with
+    /// [`vary_cookie`](Self::vary_cookie) or [`bypass_cookie`](Self::bypass_cookie): any other
+    /// page is served to every user.
+    pub fn allow_cookies(mut self) -> Self {
+        self.allow_cookies = true;
+        self
+    }
+
+    /// Uses the cache for requests that carry an `Authorization` header, which are otherwise
+    /// always rendered.
+    ///
+    /// Only set this if pages do not depend on who is signed in, or if they name
+    /// `Authorization` in their `Vary` header.
+    pub fn allow_authorization(mut self) -> Self {
+        self.allow_authorization = true;
+        self
+    }
+
+    /// Returns the key of the page requested with `method`, `uri` and `headers`, or `None` if the
+    /// request should not use the cache.
+    pub fn key(
+        &self,
+        method: &Method,
+        uri: &Uri,
+        headers: &HeaderMap,
+    ) -> Option<CacheKey> {
+        if method != Method::GET
+            || (!self.allow_authorization
+                && headers.contains_key(header::AUTHORIZATION))
+            || (!self.allow_cookies && headers.contains_key(header::COOKIE))
+        {
+            return None;
+        }
+        let cookies = request_cookies(headers);
+        if self
+            .bypass_cookies
+            .iter()
+            .any(|name| cookies.contains_key(name.as_str()))
+        {
+            return None;
+        }
+
+        let vary = self
+            .vary_headers
+            .iter()
+            .map(|name| {
+                headers
+                    .get(name)
+                    .and_then(|value| value.to_str().ok())
+                    .map(str::to_string)
+            })
+            .chain(self.vary_cookies.iter().map(|name| {
+                cookies.get(name.as_str()).map(|value| value.to_string())
+            }))
+            .collect();
+        Some(CacheKey {
+            page: PageKey {
+                path: uri.path().to_string(),
+                query: uri.query().map(str::to_string),
+                vary,
+            },
+            headers: headers.clone(),
+        })
+    }
+
+    /// Returns the cached page for `key`, if it has not expired and the request matches the
+    /// original one in the headers named by its `Vary` header.
+    pub fn get(&self, key: &CacheKey) -> Option<CachedPage> {
+        let mut entries = self.pages.entries.lock().or_poisoned();
+        let page = entries.get(&key.page)?;
+        if self.ttl.is_some_and(|ttl| page.stored_at.elapsed() >= ttl) {
+            entries.remove(&key.page);
+            return None;
+        }
+        page.vary
+            .iter()
+            .all(|(name, value)| key.headers.get(name) == value.as_ref())
+            .then(|| page.clone())
+    }
+
+    /// Passes the body of a response through, and stores the response under `key` once the
+    /// whole body has been streamed, if it can be shared between users.
+    ///
+    /// Nothing is stored if the body fails or is dropped before it ends, if it contains a CSP
+    /// nonce, or if the cache is invalidated in the meantime. The returned stream is `Send` if `body` is.
+    pub fn record<E>(
+        &self,
+        key: CacheKey,
+        status: StatusCode,
+        headers: &HeaderMap,
+        body: impl Stream<Item = Result<Bytes, E>> + 'static,
+    ) -> impl Stream<Item = Result<Bytes, E>> + 'static
+    where
+        E: 'static,
+    {
+        let pending = is_storable(status, headers).then(|| Pending {
+            cache: self.clone(),
+            vary: response_vary(headers)
+                .map(|name| {
+                    let value = key.headers.get(&name).cloned();
+                    (name, value)
+                })
+                .collect(),
+            key: key.page,
+            status,
+            headers: headers.clone(),
+            body: Vec::new(),
+            generation: self.pages.generation.load(Ordering::Acquire),
+        });
+        stream::unfold(
+            (Box::pin(body), pending),
+            |(mut body, mut pending)| async move {
+                match body.next().await {
+                    Some(Ok(chunk)) => {
+                        if let Some(pending) = &mut pending {
+                            pending.body.extend_from_slice(&chunk);
+                        }
+                        Some((Ok(chunk), (body, pending)))
+                    }
+                    Some(Err(e)) => Some((Err(e), (body, None))),
+                    None => {
+                        if let Some(pending) = pending {
+                            pending.store();
+                        }
+                        None
+                    }
+                }
+            },
+        )
+    }
+
+    /// Removes the cached pages for `path`, whatever their query string.
+    pub fn invalidate(&self, path: &str) {
+        self.pages.invalidate(|key| key.path == path);
+    }
+
+    /// Removes every page from the cache.
+    pub fn invalidate_all(&self) {
+        self.pages.invalidate(|_| true);
+    }
+
+    /// The number of pages in the cache.
+    pub fn len(&self) -> usize {
+        self.pages.entries.lock().or_poisoned().len()
+    }
+
+    /// Whether the cache holds no pages.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// A response that is being streamed, and will be stored once it is complete.
+struct Pending {
+    cache: PageCache,
+    key: PageKey,
+    vary: Vec<(HeaderName, Option<HeaderValue>)>,
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Vec<u8>,
+    generation: u64,
+}
+
+impl Pending {
+    fn store(self) {
+        // a nonce must be different for every response
+        if contains_nonce(&self.body) {
+            return;
+        }
+        let pages = &self.cache.pages;
+        let mut entries = pages.entries.lock().or_poisoned();
+        if pages.generation.load(Ordering::Acquire) != self.generation {
+            return;
+        }
+        if !entries.contains_key(&self.key)
+            && entries.len() >= self.cache.max_pages
+        {
+            let oldest = entries
+                .iter()
+                .min_by_key(|(_, page)| page.stored_at)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                entries.remove(&oldest);
+            }
+        }
+        if self.cache.max_pages > 0 {
+            entries.insert(
+                self.key,
+                CachedPage {
+                    status: self.status,
+                    headers: self.headers,
+                    body: self.body.into(),
+                    vary: self.vary,
+                    stored_at: Instant::now(),
+                },
+            );
+        }
+    }
+}
+
+/// Whether a response with this status and headers can be served to other users.
+fn is_storable(status: StatusCode, headers: &HeaderMap) -> bool {
+    const PRIVATE: [&str; 3] = ["private", "no-store", "no-cache"];
+
+    status == StatusCode::OK
+        && !headers.contains_key(header::SET_COOKIE)
+        && !response_vary(headers).any(|name| name.as_str() == "*")
+        && !headers
+            .get_all(header::CONTENT_SECURITY_POLICY)
+            .iter()
+            .any(|value| contains_nonce(value.as_bytes()))
+        && !headers.get_all(header::CACHE_CONTROL).iter().any(|value| {
+            value
+                .to_str()
+                .unwrap_or_default()
+                .split(',')
+                .any(|directive| {
+                    PRIVATE.contains(
+                        &directive.trim().to_ascii_lowercase().as_str(),
+                    )
+                })
+        })
+}
+
+/// The request headers named by the `Vary` header of a response, including `*`.
+fn response_vary(headers: &HeaderMap) -> impl Iterator<Item = HeaderName> + '_ {
+    headers
+        .get_all(header::VARY)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .filter_map(|name| HeaderName::from_bytes(name.trim().as_bytes()).ok())
+}
+
+/// Whether a body or `Content-Security-Policy` header mentions a nonce, either as a `nonce`
+/// attribute or as a `'nonce-…'` source.
+fn contains_nonce(bytes: &[u8]) -> bool {
+    bytes
+        .windows(6)
+        .any(|window| window == b"nonce=" || window == b"'nonce")
+}
+
+fn request_cookies(headers: &HeaderMap) -> HashMap<&str, &str> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .filter_map(|cookie| cookie.trim().split_once('='))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+    use std::convert::Infallible;
+
+    fn render(
+        cache: &PageCache,
+        key: CacheKey,
+        headers: &HeaderMap,
+    ) -> Vec<Result<Bytes, Infallible>> {
+        let body = stream::iter(["<html>", "</html>"])
+            .map(|chunk| Ok(Bytes::from_static(chunk.as_bytes())));
+        block_on(
+            cache
+                .record(key, StatusCode::OK, headers, body)
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    #[test]
+    fn pages_are_stored_once_streamed_and_invalidated_by_path() {
+        let cache = PageCache::new().allow_cookies().bypass_cookie("id");
+        let uri: Uri = "/posts?page=2".parse().unwrap();
+        let mut headers = HeaderMap::new();
+        let key = cache.key(&Method::GET, &uri, &headers).unwrap();
+        assert!(cache.get(&key).is_none());
+
+        assert_eq!(render(&cache, key.clone(), &HeaderMap::new()).len(), 2);
+        assert_eq!(cache.get(&key).unwrap().body, "<html></html>");
+
+        headers.insert(header::COOKIE, HeaderValue::from_static("id=abc"));
+        assert!(cache.key(&Method::GET, &uri, &headers).is_none());
+        assert!(cache.key(&Method::POST, &uri, &HeaderMap::new()).is_none());
+
+        invalidate("/posts");
+        assert!(cache.get(&key).is_none());
+    }
+
+    #[test]
+    fn credentialed_requests_are_rendered_unless_allowed() {
+        let uri: Uri = "/".parse().unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert(header::COOKIE, HeaderValue::from_static("theme=dark"));
+        assert!(PageCache::new().key(&Method::GET, &uri, &headers).is_none());
+        assert!(PageCache::new()
+            .allow_cookies()
+            .key(&Method::GET, &uri, &headers)
+            .is_some());
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_static("Bearer x"),
+        );
+        assert!(PageCache::new().key(&Method::GET, &uri, &headers).is_none());
+        assert!(PageCache::new()
+            .allow_authorization()
+            .key(&Method::GET, &uri, &headers)
+            .is_some());
+    }
+
+    #[test]
+    fn pages_are_only_served_to_requests_matching_their_vary_header() {
+        let cache = PageCache::new();
+        let uri: Uri = "/".parse().unwrap();
+        let mut english = HeaderMap::new();
+        english.insert(header::ACCEPT_LANGUAGE, HeaderValue::from_static("en"));
+        let mut french = HeaderMap::new();
+        french.insert(header::ACCEPT_LANGUAGE, HeaderValue::from_static("fr"));
+
+        let mut response = HeaderMap::new();
+        response
+            .insert(header::VARY, HeaderValue::from_static("Accept-Language"));
+        let key = cache.key(&Method::GET, &uri, &english).unwrap();
+        render(&cache, key.clone(), &response);
+        assert!(cache.get(&key).is_some());
+        let key = cache.key(&Method::GET, &uri, &french).unwrap();
+        assert!(cache.get(&key).is_none());
+
+        let mut response = HeaderMap::new();
+        response.insert(header::VARY, HeaderValue::from_static("*"));
+        let cache = PageCache::new();
+        render(&cache, key, &response);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn pages_with_a_nonce_are_not_stored() {
+        let cache = PageCache::new();
+        let uri: Uri = "/".parse().unwrap();
+        let key = cache.key(&Method::GET, &uri, &HeaderMap::new()).unwrap();
+        let body = stream::iter(["<script nonce=\"abc\">", "</script>"]).map(
+            |chunk| Ok::<_, Infallible>(Bytes::from_static(chunk.as_bytes())),
+        );
+        block_on(
+            cache
+                .record(key.clone(), StatusCode::OK, &HeaderMap::new(), body)
+                .collect::<Vec<_>>(),
+        );
+        assert!(cache.is_empty());
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::CONTENT_SECURITY_POLICY,
+            HeaderValue::from_static("script-src 'nonce-abc'"),
+        );
+        render(&cache, key, &headers);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn personalized_responses_are_not_stored() {
+        let cache = PageCache::new();
+        let uri: Uri = "/account".parse().unwrap();
+        let key = cache.key(&Method::GET, &uri, &HeaderMap::new()).unwrap();
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::SET_COOKIE, HeaderValue::from_static("id=abc"));
+        render(&cache, key.clone(), &headers);
+        assert!(cache.is_empty());
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::CACHE_CONTROL,
+            HeaderValue::from_static("max-age=0, Private"),
+        );
+        render(&cache, key, &headers);
+        assert!(cache.is_empty());
+    }
+}
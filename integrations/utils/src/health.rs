@@ -0,0 +1,295 @@
+//! Liveness, readiness and build information for orchestrators and load balancers.
+//!
+//! The server integrations provide routes that answer with the JSON bodies built here:
+//!
+//! - `/livez` always succeeds while the server is able to answer requests.
+//! - `/readyz` runs the readiness checks, and answers with `503 Service Unavailable` if any of
+//!   them fails. The built-in checks fail while the server is [shutting down](crate::shutdown),
+//!   if the JS and Wasm files of the app are missing from the
+//!   [`site_pkg_dir`](LeptosOptions::site_pkg_dir), or if
+//!   [`hash_files`](LeptosOptions::hash_files) is set but the hash file that names them was not
+//!   deployed with the server binary. Apps can add their own checks with [`Health::check`].
+//! - `/build-info` describes the running build.
+
+use futures::future::join_all;
+use http::StatusCode;
+use leptos::hydration::{hash_file_path, hydration_file_names};
+use leptos_config::LeptosOptions;
+use serde::Serialize;
+use std::{borrow::Cow, fmt, future::Future, path::Path, pin::Pin, sync::Arc};
+
+type CheckFn = Arc<
+    dyn Fn() -> Pin<Box<dyn Future<Output = Result<(), String>> + Send>>
+        + Send
+        + Sync,
+>;
+
+/// Describes the running build of the app.
+#[derive(Clone, Debug, Serialize)]
+pub struct BuildInfo {
+    /// The name of the app.
+    pub name: &'static str,
+    /// The version of the app.
+    pub version: &'static str,
+    /// The commit the app was built from, if known.
+    pub git_commit: Option<&'static str>,
+    /// The version of Leptos the app was built with.
+    pub leptos_version: &'static str,
+    /// `"debug"` or `"release"`.
+    pub profile: &'static str,
+}
+
+impl BuildInfo {
+    /// Creates the build information of an app, usually from
+    /// `env!("CARGO_PKG_NAME")` and `env!("CARGO_PKG_VERSION")`.
+    pub fn new(name: &'static str, version: &'static str) -> Self {
+        Self {
+            name,
+            version,
+            git_commit: None,
+            leptos_version: env!("CARGO_PKG_VERSION"),
+            profile: if cfg!(debug_assertions) {
+                "debug"
+            } else {
+                "release"
+            },
+        }
+    }
+
+    /// Sets the commit the app was built from, for example from `option_env!("GIT_COMMIT")`.
+    pub fn git_commit(mut self, commit: Option<&'static str>) -> Self {
+        self.git_commit = commit;
+        self
+    }
+}
+
+/// A response of one of the health routes, with a JSON body.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HealthResponse {
+    /// The status code of the response.
+    pub status: StatusCode,
+    /// The JSON body of the response.
+    pub body: String,
+}
+
+#[derive(Debug, Serialize)]
+struct CheckResult {
+    name: Cow<'static, str>,
+    ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// The health checks of an app.
+#[derive(Clone)]
+pub struct Health {
+    options: LeptosOptions,
+    build: BuildInfo,
+    check_assets: bool,
+    checks: Vec<(Cow<'static, str>, CheckFn)>,
+}
+
+impl fmt::Debug for Health {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Health")
+            .field("build", &self.build)
+            .field("check_assets", &self.check_assets)
+            .field(
+                "checks",
+                &self.checks.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl Health {
+    /// Creates the health checks of the app described by `options` and `build`, with the
+    /// built-in readiness checks.
+    pub fn new(options: &LeptosOptions, build: BuildInfo) -> Self {
+        Self {
+            options: options.clone(),
+            build,
+            check_assets: true,
+            checks: Vec::new(),
+        }
+    }
+
+    /// Skips the checks for the JS and Wasm files of the app, for apps that are not hydrated.
+    pub fn without_asset_checks(mut self) -> Self {
+        self.check_assets = false;
+        self
+    }
+
+    /// Adds a readiness check, like a query that makes sure the database can be reached. The
+    /// server is not ready while it returns an error.
+    pub fn check<F, Fut>(
+        mut self,
+        name: impl Into<Cow<'static, str>>,
+        check: F,
+    ) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), String>> + Send + 'static,
+    {
+        self.checks
+            .push((name.into(), Arc::new(move || Box::pin(check()))));
+        self
+    }
+
+    /// Answers a liveness probe.
+    pub fn live(&self) -> HealthResponse {
+        json(StatusCode::OK, &serde_json::json!({ "status": "live" }))
+    }
+
+    /// Runs the readiness checks, and answers with `503 Service Unavailable` if any of them
+    /// fails.
+    pub async fn ready(&self) -> HealthResponse {
+        let mut results = vec![CheckResult::new(
+            "shutdown",
+            if crate::shutdown::is_shutting_down() {
+                Err("the server is shutting down".to_string())
+            } else {
+                Ok(())
+            },
+        )];
+        if self.check_assets {
+            results.push(CheckResult::new(
+                "hash_file",
+                check_hash_file(&self.options),
+            ));
+            results.push(CheckResult::new(
+                "wasm_assets",
+                check_assets(&self.options),
+            ));
+        }
+        let custom = join_all(self.checks.iter().map(|(name, check)| {
+            let check = check();
+            async move { CheckResult::new(name.clone(), check.await) }
+        }))
+        .await;
+        results.extend(custom);
+
+        let ready = results.iter().all(|result| result.ok);
+        let (status, label) = if ready {
+            (StatusCode::OK, "ready")
+        } else {
+            (StatusCode::SERVICE_UNAVAILABLE, "unavailable")
+        };
+        json(
+            status,
+            &serde_json::json!({ "status": label, "checks": results }),
+        )
+    }
+
+    /// Answers with the build information of the app.
+    pub fn build_info(&self) -> HealthResponse {
+        json(StatusCode::OK, &self.build)
+    }
+}
+
+impl CheckResult {
+    fn new(
+        name: impl Into<Cow<'static, str>>,
+        result: Result<(), String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            ok: result.is_ok(),
+            error: result.err(),
+        }
+    }
+}
+
+fn json(status: StatusCode, body: &impl Serialize) -> HealthResponse {
+    HealthResponse {
+        status,
+        body: serde_json::to_string(body)
+            .expect("health responses can be serialized"),
+    }
+}
+
+/// The hash file lists the hashed names of the JS and Wasm files that the server binary links to.
+fn check_hash_file(options: &LeptosOptions) -> Result<(), String> {
+    if !options.hash_files {
+        return Ok(());
+    }
+    let path = hash_file_path(options);
+    let hashes = std::fs::read_to_string(&path)
+        .map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+    for file in ["js", "wasm"] {
+        let listed = hashes.lines().any(|line| {
+            line.split_once(':')
+                .is_some_and(|(name, _)| name.trim() == file)
+        });
+        if !listed {
+            return Err(format!(
+                "{} has no hash for the {file} file",
+                path.display()
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// The JS and Wasm files that the hydration scripts load exist in the site root.
+fn check_assets(options: &LeptosOptions) -> Result<(), String> {
+    let (js, wasm) = hydration_file_names(options);
+    let pkg = Path::new(&*options.site_root).join(&*options.site_pkg_dir);
+    let missing = [format!("{js}.js"), format!("{wasm}.wasm")]
+        .into_iter()
+        .map(|file| pkg.join(file))
+        .filter(|path| !path.is_file())
+        .map(|path| path.display().to_string())
+        .collect::<Vec<_>>();
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("missing {}", missing.join(", ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+    use std::fs;
+
+    #[test]
+    fn readiness_requires_the_assets_and_every_check() {
+        let root = std::env::temp_dir()
+            .join(format!("leptos_health_{}", std::process::id()));
+        fs::create_dir_all(root.join("pkg")).unwrap();
+        let options = LeptosOptions::builder()
+            .output_name("app")
+            .site_root(root.to_string_lossy().to_string())
+            .build();
+        let health = Health::new(&options, BuildInfo::new("app", "1.0.0"));
+
+        let res = block_on(health.ready());
+        assert_eq!(res.status, StatusCode::SERVICE_UNAVAILABLE);
+        let body: serde_json::Value = serde_json::from_str(&res.body).unwrap();
+        assert_eq!(body["checks"][2]["name"], "wasm_assets");
+        assert_eq!(body["checks"][2]["ok"], false);
+
+        fs::write(root.join("pkg/app.js"), "").unwrap();
+        fs::write(root.join("pkg/app_bg.wasm"), "").unwrap();
+        assert_eq!(block_on(health.ready()).status, StatusCode::OK);
+
+        let health = health.check("database", || async {
+            Err("connection refused".to_string())
+        });
+        let res = block_on(health.ready());
+        assert_eq!(res.status, StatusCode::SERVICE_UNAVAILABLE);
+        let body: serde_json::Value = serde_json::from_str(&res.body).unwrap();
+        assert_eq!(body["status"], "unavailable");
+        assert_eq!(body["checks"][3]["name"], "database");
+        assert_eq!(body["checks"][3]["error"], "connection refused");
+        fs::remove_dir_all(root).unwrap();
+
+        assert_eq!(health.live().status, StatusCode::OK);
+        assert!(health
+            .build_info()
+            .body
+            .starts_with("{\"name\":\"app\",\"version\":\"1.0.0\""));
+    }
+}
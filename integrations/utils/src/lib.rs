@@ -1,10 +1,22 @@
 #![allow(clippy::type_complexity)]
 
+pub mod cache;
+#[cfg(feature = "health")]
+pub mod health;
+#[cfg(feature = "session")]
+pub mod session;
+pub mod shutdown;
+pub mod sitemap;
+
+use any_spawner::LocalTasks;
 use futures::{stream::once, Stream, StreamExt};
 use hydration_context::{SharedContext, SsrSharedContext};
+#[cfg(feature = "tracing")]
+use leptos::request::TraceContext;
 use leptos::{
+    context::provide_context,
     nonce::use_nonce,
-    reactive::owner::{Owner, Sandboxed},
+    reactive::owner::{Owner, Sandboxed, TaskCancellation},
     IntoView,
 };
 use leptos_config::LeptosOptions;
@@ -40,7 +52,16 @@ pub trait ExtendResponse: Sized {
     where
         IV: IntoView + 'static,
     {
-        async move {
+        // drives the tasks of the response on single-threaded runtimes, until the body is done
+        let tasks = LocalTasks::new();
+        tasks.clone().drive(async move {
+            let in_flight = shutdown::InFlight::new();
+            let cancellation = TaskCancellation::new();
+            let disconnect = DisconnectGuard::new(cancellation.clone());
+            let additional_context = move || {
+                provide_context(cancellation);
+                additional_context();
+            };
             let (owner, stream) = build_response(
                 app_fn,
                 additional_context,
@@ -73,17 +94,32 @@ pub trait ExtendResponse: Sized {
 
             // wait for the first chunk of the stream, then set the status and headers
             let first_chunk = stream.next().await.unwrap_or_default();
+            let stream = shutdown::track(in_flight, &first_chunk, stream);
+
+            let stream = once(async move { first_chunk })
+                .chain(stream)
+                // drop the owner, cleaning up the reactive runtime,
+                // once the stream is over
+                .chain(once(async move {
+                    disconnect.disarm();
+                    owner.unset();
+                    Default::default()
+                }));
+
+            // the body is polled by the server, outside of the request's span
+            #[cfg(feature = "tracing")]
+            let stream = {
+                let span = tracing::Span::current();
+                let mut stream = Box::pin(stream);
+                futures::stream::poll_fn(move |cx| {
+                    let _guard = span.enter();
+                    stream.as_mut().poll_next(cx)
+                })
+            };
 
-            let mut res = Self::from_stream(Sandboxed::new(
-                once(async move { first_chunk })
-                    .chain(stream)
-                    // drop the owner, cleaning up the reactive runtime,
-                    // once the stream is over
-                    .chain(once(async move {
-                        owner.unset();
-                        Default::default()
-                    })),
-            ));
+            let stream = tasks.drive(stream);
+
+            let mut res = Self::from_stream(Sandboxed::new(stream));
 
             res.extend_response(&res_options);
 
@@ -92,6 +128,56 @@ pub trait ExtendResponse: Sized {
             res.set_default_content_type("text/html; charset=utf-8");
 
             res
+        })
+    }
+}
+
+/// Creates the root tracing span of a request, under which the spans of the components,
+/// resources, and server functions it runs are nested.
+///
+/// `handler` describes what handles the request, like `"ssr"` or `"server_fn"`. The trace and
+/// parent IDs of the caller's [`TraceContext`] are recorded on the span, if there is one.
+#[cfg(feature = "tracing")]
+pub fn request_span(
+    handler: &'static str,
+    method: &str,
+    path: &str,
+    trace: Option<&TraceContext>,
+) -> tracing::Span {
+    tracing::info_span!(
+        "request",
+        handler,
+        method,
+        path,
+        trace_id = trace.map(|trace| trace.trace_id.as_str()),
+        parent_id = trace.map(|trace| trace.parent_id.as_str()),
+    )
+}
+
+/// Cancels the tasks of a request if it is dropped before [`disarm`](DisconnectGuard::disarm) is
+/// called, which happens when the client disconnects before the response is complete.
+///
+/// The [`TaskCancellation`] should be provided via context while handling the request, so that the
+/// resources and other tasks spawned for it are cancelled along with the response.
+#[derive(Debug)]
+pub struct DisconnectGuard(Option<TaskCancellation>);
+
+impl DisconnectGuard {
+    /// Creates a guard that cancels `cancellation` when it is dropped.
+    pub fn new(cancellation: TaskCancellation) -> Self {
+        Self(Some(cancellation))
+    }
+
+    /// Marks the response as complete, so that its tasks are not cancelled.
+    pub fn disarm(mut self) {
+        self.0.take();
+    }
+}
+
+impl Drop for DisconnectGuard {
+    fn drop(&mut self) {
+        if let Some(cancellation) = self.0.take() {
+            cancellation.cancel();
         }
     }
 }
@@ -0,0 +1,765 @@
+//! Server-side sessions with pluggable storage.
+//!
+//! This module is only available with the `session` feature of the server integration.
+//!
+//! A [`SessionConfig`] describes where sessions are stored and how the session cookie is
+//! protected. Calling [`SessionConfig::provide`] while handling a request, usually from the
+//! additional context passed to the server integration, provides a [`Session`] via context, so
+//! server functions and server-rendered components can read and write session data:
+//!
+//! ```rust,ignore
+//! let sessions = SessionConfig::new(MemoryStore::default(), SessionKey::from_secret(&secret))
+//!     .public_key("user_name");
+//!
+//! let app = Router::new()
+//!     .leptos_routes_with_context(&leptos_options, routes, {
+//!         let sessions = sessions.clone();
+//!         move || sessions.provide()
+//!     }, {
+//!         let leptos_options = leptos_options.clone();
+//!         move || shell(leptos_options.clone())
+//!     });
+//! ```
+//!
+//! ```rust,ignore
+//! #[server]
+//! pub async fn log_in(name: String) -> Result<(), ServerFnError> {
+//!     let session = expect_context::<Session>();
+//!     // prevent session fixation by changing the ID when privileges change
+//!     session.cycle_id().await?;
+//!     session.insert("user_name", &name).await?;
+//!     Ok(())
+//! }
+//! ```
+//!
+//! Only the session ID is stored in the cookie. It is either signed, so it cannot be forged, or
+//! encrypted, so it cannot be read either. Values stored under keys marked with
+//! [`SessionConfig::public_key`] are also sent to the client, where they can be read with
+//! [`use_public_session`](leptos::session::use_public_session).
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use futures::lock::{Mutex, MutexGuard};
+use leptos::{
+    auth::AuthSource,
+    prelude::provide_context,
+    reactive::owner::with_context,
+    request::{
+        use_request_parts, Cookies, HeaderName, HeaderValue, ResponseHeaders,
+    },
+    session::PublicSessionSource,
+};
+use or_poisoned::OrPoisoned;
+use ring::{
+    aead::{self, Aad, LessSafeKey, Nonce, UnboundKey, CHACHA20_POLY1305},
+    hkdf, hmac,
+    rand::{SecureRandom, SystemRandom},
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex as SyncMutex},
+    time::{Duration, SystemTime},
+};
+
+/// The data of one session, as saved in a [`SessionStore`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionRecord {
+    /// The values stored in the session, encoded as JSON.
+    pub data: HashMap<String, serde_json::Value>,
+    /// When the session expires. Expired sessions are treated as if they did not exist.
+    pub expires_at: SystemTime,
+}
+
+/// The future returned by the methods of a [`SessionStore`].
+pub type StoreFuture<'a, T> =
+    Pin<Box<dyn Future<Output = Result<T, SessionError>> + Send + 'a>>;
+
+/// Storage for session data, like a database or a cache.
+pub trait SessionStore: Send + Sync + 'static {
+    /// Loads the session with the given ID, if it exists.
+    fn load<'a>(
+        &'a self,
+        id: &'a str,
+    ) -> StoreFuture<'a, Option<SessionRecord>>;
+
+    /// Saves the session with the given ID, replacing any existing data.
+    fn save<'a>(
+        &'a self,
+        id: &'a str,
+        record: &'a SessionRecord,
+    ) -> StoreFuture<'a, ()>;
+
+    /// Deletes the session with the given ID.
+    fn delete<'a>(&'a self, id: &'a str) -> StoreFuture<'a, ()>;
+}
+
+/// A [`SessionStore`] that keeps sessions in memory.
+///
+/// Sessions are lost when the server restarts, and are not shared between multiple servers, so
+/// this is mostly useful during development and in tests.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryStore(Arc<SyncMutex<HashMap<String, SessionRecord>>>);
+
+impl SessionStore for MemoryStore {
+    fn load<'a>(
+        &'a self,
+        id: &'a str,
+    ) -> StoreFuture<'a, Option<SessionRecord>> {
+        let mut sessions = self.0.lock().or_poisoned();
+        let now = SystemTime::now();
+        sessions.retain(|_, record| record.expires_at > now);
+        let record = sessions.get(id).cloned();
+        Box::pin(async move { Ok(record) })
+    }
+
+    fn save<'a>(
+        &'a self,
+        id: &'a str,
+        record: &'a SessionRecord,
+    ) -> StoreFuture<'a, ()> {
+        self.0
+            .lock()
+            .or_poisoned()
+            .insert(id.to_owned(), record.clone());
+        Box::pin(async { Ok(()) })
+    }
+
+    fn delete<'a>(&'a self, id: &'a str) -> StoreFuture<'a, ()> {
+        self.0.lock().or_poisoned().remove(id);
+        Box::pin(async { Ok(()) })
+    }
+}
+
+/// An error that occurred while reading or writing a [`Session`].
+#[derive(Debug)]
+pub enum SessionError {
+    /// The [`SessionStore`] failed.
+    Store(String),
+    /// A value could not be serialized or deserialized.
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for SessionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SessionError::Store(e) => write!(f, "session store error: {e}"),
+            SessionError::Json(e) => {
+                write!(f, "could not encode session value: {e}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SessionError {}
+
+/// The secret used to sign or encrypt session cookies.
+#[derive(Clone)]
+pub struct SessionKey {
+    signing: hmac::Key,
+    encryption: Arc<LessSafeKey>,
+}
+
+impl fmt::Debug for SessionKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SessionKey").finish_non_exhaustive()
+    }
+}
+
+impl SessionKey {
+    /// Derives a key from a secret, which should be at least 32 random bytes and kept out of
+    /// source control.
+    ///
+    /// # Panics
+    /// Panics if `secret` is shorter than 32 bytes.
+    pub fn from_secret(secret: &[u8]) -> Self {
+        assert!(
+            secret.len() >= 32,
+            "a session secret must be at least 32 bytes long"
+        );
+        let prk = hkdf::Salt::new(hkdf::HKDF_SHA256, b"leptos-session")
+            .extract(secret);
+        let signing = prk
+            .expand(&[b"signing"], hmac::HMAC_SHA256)
+            .expect("HMAC key length is valid")
+            .into();
+        let encryption: UnboundKey = prk
+            .expand(&[b"encryption"], &CHACHA20_POLY1305)
+            .expect("ChaCha20-Poly1305 key length is valid")
+            .into();
+        Self {
+            signing,
+            encryption: Arc::new(LessSafeKey::new(encryption)),
+        }
+    }
+
+    /// Generates a random key.
+    ///
+    /// Cookies protected with a generated key become invalid when the server restarts, which
+    /// logs everyone out, so this is mostly useful during development.
+    pub fn generate() -> Self {
+        let mut secret = [0; 64];
+        SystemRandom::new()
+            .fill(&mut secret)
+            .expect("system randomness is available");
+        Self::from_secret(&secret)
+    }
+
+    fn sign(&self, id: &str) -> String {
+        let tag = hmac::sign(&self.signing, id.as_bytes());
+        format!("{id}.{}", URL_SAFE_NO_PAD.encode(tag.as_ref()))
+    }
+
+    fn verify(&self, cookie: &str) -> Option<String> {
+        let (id, tag) = cookie.rsplit_once('.')?;
+        let tag = URL_SAFE_NO_PAD.decode(tag).ok()?;
+        hmac::verify(&self.signing, id.as_bytes(), &tag).ok()?;
+        Some(id.to_owned())
+    }
+
+    fn encrypt(&self, id: &str, cookie_name: &str) -> String {
+        let mut nonce = [0; aead::NONCE_LEN];
+        SystemRandom::new()
+            .fill(&mut nonce)
+            .expect("system randomness is available");
+        let mut sealed = id.as_bytes().to_vec();
+        self.encryption
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(cookie_name.as_bytes()),
+                &mut sealed,
+            )
+            .expect("session IDs are short enough to encrypt");
+        let mut cookie = nonce.to_vec();
+        cookie.extend(sealed);
+        URL_SAFE_NO_PAD.encode(cookie)
+    }
+
+    fn decrypt(&self, cookie: &str, cookie_name: &str) -> Option<String> {
+        let mut cookie = URL_SAFE_NO_PAD.decode(cookie).ok()?;
+        if cookie.len() < aead::NONCE_LEN {
+            return None;
+        }
+        let mut sealed = cookie.split_off(aead::NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(&cookie).ok()?;
+        let id = self
+            .encryption
+            .open_in_place(
+                nonce,
+                Aad::from(cookie_name.as_bytes()),
+                &mut sealed,
+            )
+            .ok()?;
+        String::from_utf8(id.to_vec()).ok()
+    }
+}
+
+/// How the session ID is protected in the session cookie.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CookieProtection {
+    /// The ID is signed, so it cannot be forged, but can be read.
+    #[default]
+    Signed,
+    /// The ID is encrypted, so it can be neither forged nor read.
+    Encrypted,
+}
+
+/// Describes how sessions are stored and identified. See the [module documentation](self).
+#[derive(Clone)]
+pub struct SessionConfig {
+    store: Arc<dyn SessionStore>,
+    key: SessionKey,
+    protection: CookieProtection,
+    cookie_name: Arc<str>,
+    cookie_path: Arc<str>,
+    secure: bool,
+    ttl: Duration,
+    public_keys: Arc<[String]>,
+}
+
+impl fmt::Debug for SessionConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SessionConfig")
+            .field("protection", &self.protection)
+            .field("cookie_name", &self.cookie_name)
+            .field("cookie_path", &self.cookie_path)
+            .field("secure", &self.secure)
+            .field("ttl", &self.ttl)
+            .field("public_keys", &self.public_keys)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SessionConfig {
+    /// Creates a configuration that stores sessions in `store` and signs session cookies with
+    /// `key`.
+    ///
+    /// By default, the cookie is called `id`, applies to the whole site, is only sent over
+    /// HTTPS, and sessions expire after seven days.
+    pub fn new(store: impl SessionStore, key: SessionKey) -> Self {
+        Self {
+            store: Arc::new(store),
+            key,
+            protection: CookieProtection::default(),
+            cookie_name: "id".into(),
+            cookie_path: "/".into(),
+            secure: true,
+            ttl: Duration::from_secs(7 * 24 * 60 * 60),
+            public_keys: Arc::new([]),
+        }
+    }
+
+    /// Sets how the session ID is protected in the cookie.
+    pub fn protection(mut self, protection: CookieProtection) -> Self {
+        self.protection = protection;
+        self
+    }
+
+    /// Sets the name of the session cookie.
+    pub fn cookie_name(mut self, name: impl Into<String>) -> Self {
+        self.cookie_name = name.into().into();
+        self
+    }
+
+    /// Sets the path the session cookie applies to.
+    pub fn cookie_path(mut self, path: impl Into<String>) -> Self {
+        self.cookie_path = path.into().into();
+        self
+    }
+
+    /// Sets whether the session cookie is only sent over HTTPS. Disable this to use sessions
+    /// over plain HTTP during local development.
+    pub fn secure(mut self, secure: bool) -> Self {
+        self.secure = secure;
+        self
+    }
+
+    /// Sets how long a session lasts after it was last written.
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Marks the value stored under `key` as visible to the client.
+    pub fn public_key(mut self, key: impl Into<String>) -> Self {
+        let mut keys = self.public_keys.to_vec();
+        keys.push(key.into());
+        self.public_keys = keys.into();
+        self
+    }
+
+    /// Provides the [`Session`] of the current request via context, along with a
+    /// [`PublicSessionSource`] for its public values if any keys are public.
+    ///
+    /// This should be called while handling each request, for example from the additional
+    /// context passed to the server integration's handlers.
+    pub fn provide(&self) {
+        let cookie = use_request_parts::<Cookies>().ok().and_then(|cookies| {
+            cookies.get(&self.cookie_name).map(str::to_owned)
+        });
+        let session = Session(Arc::new(SessionInner {
+            config: self.clone(),
+            cookie,
+            response: with_context(ResponseHeaders::clone),
+            state: Mutex::new(None),
+        }));
+        if !self.public_keys.is_empty() {
+            provide_context(PublicSessionSource::new({
+                let session = session.clone();
+                move || {
+                    let session = session.clone();
+                    async move { session.public_values().await }
+                }
+            }));
+        }
+        provide_context(session);
+    }
+
+    fn encode_id(&self, id: &str) -> String {
+        match self.protection {
+            CookieProtection::Signed => self.key.sign(id),
+            CookieProtection::Encrypted => {
+                self.key.encrypt(id, &self.cookie_name)
+            }
+        }
+    }
+
+    fn decode_id(&self, cookie: &str) -> Option<String> {
+        match self.protection {
+            CookieProtection::Signed => self.key.verify(cookie),
+            CookieProtection::Encrypted => {
+                self.key.decrypt(cookie, &self.cookie_name)
+            }
+        }
+    }
+
+    fn set_cookie(&self, value: Option<&str>) -> String {
+        let name = &self.cookie_name;
+        let path = &self.cookie_path;
+        let mut cookie = match value {
+            Some(value) => format!(
+                "{name}={value}; Path={path}; Max-Age={}",
+                self.ttl.as_secs()
+            ),
+            None => format!("{name}=; Path={path}; Max-Age=0"),
+        };
+        cookie.push_str("; HttpOnly; SameSite=Lax");
+        if self.secure {
+            cookie.push_str("; Secure");
+        }
+        cookie
+    }
+}
+
+/// The session of the current request, provided via context by [`SessionConfig::provide`].
+///
+/// The session is loaded from the store the first time it is read, and every change is saved
+/// immediately. A session ID is only assigned, and the session cookie only set, once a value is
+/// first inserted. When a page is streamed, the cookie can only be set before the first chunk of
+/// HTML has been sent.
+#[derive(Clone)]
+pub struct Session(Arc<SessionInner>);
+
+struct SessionInner {
+    config: SessionConfig,
+    cookie: Option<String>,
+    response: Option<ResponseHeaders>,
+    state: Mutex<Option<SessionState>>,
+}
+
+struct SessionState {
+    id: Option<String>,
+    data: HashMap<String, serde_json::Value>,
+}
+
+impl fmt::Debug for Session {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Session").finish_non_exhaustive()
+    }
+}
+
+impl Session {
+    /// Returns the value stored under `key`, if any.
+    pub async fn get<T>(&self, key: &str) -> Result<Option<T>, SessionError>
+    where
+        T: DeserializeOwned,
+    {
+        self.with_state(|state| {
+            state
+                .data
+                .get(key)
+                .map(|value| T::deserialize(value).map_err(SessionError::Json))
+                .transpose()
+        })
+        .await
+    }
+
+    /// Stores `value` under `key`, replacing any previous value.
+    pub async fn insert<T>(
+        &self,
+        key: impl Into<String>,
+        value: &T,
+    ) -> Result<(), SessionError>
+    where
+        T: Serialize,
+    {
+        let value = serde_json::to_value(value).map_err(SessionError::Json)?;
+        let mut guard = self.load().await?;
+        let state = guard.as_mut().expect("session state was loaded");
+        state.data.insert(key.into(), value);
+        if state.id.is_none() {
+            let id = new_id();
+            self.set_cookie(Some(&id));
+            state.id = Some(id);
+        }
+        self.save(state).await
+    }
+
+    /// Removes the value stored under `key`, and returns whether there was one.
+    pub async fn remove(&self, key: &str) -> Result<bool, SessionError> {
+        let mut guard = self.load().await?;
+        let state = guard.as_mut().expect("session state was loaded");
+        if state.data.remove(key).is_none() {
+            return Ok(false);
+        }
+        self.save(state).await?;
+        Ok(true)
+    }
+
+    /// Deletes the session from the store and removes the session cookie.
+    pub async fn destroy(&self) -> Result<(), SessionError> {
+        let mut guard = self.load().await?;
+        let state = guard.as_mut().expect("session state was loaded");
+        state.data.clear();
+        if let Some(id) = state.id.take() {
+            self.0.config.store.delete(&id).await?;
+            self.set_cookie(None);
+        }
+        Ok(())
+    }
+
+    /// Moves the session's data to a new ID.
+    ///
+    /// This should be called whenever the privileges of a session change, for example when a
+    /// user logs in, to prevent session fixation attacks.
+    pub async fn cycle_id(&self) -> Result<(), SessionError> {
+        let mut guard = self.load().await?;
+        let state = guard.as_mut().expect("session state was loaded");
+        let Some(old_id) = state.id.take() else {
+            return Ok(());
+        };
+        self.0.config.store.delete(&old_id).await?;
+        let id = new_id();
+        self.set_cookie(Some(&id));
+        state.id = Some(id);
+        self.save(state).await
+    }
+
+    /// Returns the ID of the session, if one has been assigned.
+    pub async fn id(&self) -> Result<Option<String>, SessionError> {
+        self.with_state(|state| Ok(state.id.clone())).await
+    }
+
+    /// Provides an [`AuthSource`] via context that reads the current user from the value stored
+    /// under `key`, making it available to [`use_auth`](leptos::auth::use_auth) and
+    /// [`require_user`](leptos::auth::require_user).
+    ///
+    /// ```rust,ignore
+    /// move || {
+    ///     sessions.provide();
+    ///     expect_context::<Session>().provide_user::<User>("user");
+    /// }
+    /// ```
+    pub fn provide_user<User>(&self, key: impl Into<String>)
+    where
+        User: DeserializeOwned + Send + Sync + 'static,
+    {
+        let session = self.clone();
+        let key: Arc<str> = key.into().into();
+        provide_context(AuthSource::new(move || {
+            let session = session.clone();
+            let key = Arc::clone(&key);
+            async move {
+                session.get::<User>(&key).await.unwrap_or_else(|e| {
+                    leptos::logging::error!(
+                        "Failed to load the user from the session: {e}"
+                    );
+                    None
+                })
+            }
+        }));
+    }
+
+    async fn public_values(&self) -> HashMap<String, String> {
+        let public_keys = Arc::clone(&self.0.config.public_keys);
+        self.with_state(|state| {
+            Ok(public_keys
+                .iter()
+                .filter_map(|key| {
+                    let value = state.data.get(key)?;
+                    Some((key.clone(), value.to_string()))
+                })
+                .collect())
+        })
+        .await
+        .unwrap_or_default()
+    }
+
+    async fn with_state<U>(
+        &self,
+        fun: impl FnOnce(&SessionState) -> Result<U, SessionError>,
+    ) -> Result<U, SessionError> {
+        let guard = self.load().await?;
+        fun(guard.as_ref().expect("session state was loaded"))
+    }
+
+    async fn load(
+        &self,
+    ) -> Result<MutexGuard<'_, Option<SessionState>>, SessionError> {
+        let mut guard = self.0.state.lock().await;
+        if guard.is_none() {
+            let config = &self.0.config;
+            let id = self
+                .0
+                .cookie
+                .as_deref()
+                .and_then(|cookie| config.decode_id(cookie));
+            let record = match &id {
+                Some(id) => config.store.load(id).await?,
+                None => None,
+            };
+            let state = match record {
+                Some(record) if record.expires_at > SystemTime::now() => {
+                    SessionState {
+                        id,
+                        data: record.data,
+                    }
+                }
+                _ => SessionState {
+                    id: None,
+                    data: HashMap::new(),
+                },
+            };
+            *guard = Some(state);
+        }
+        Ok(guard)
+    }
+
+    async fn save(&self, state: &SessionState) -> Result<(), SessionError> {
+        let Some(id) = &state.id else {
+            return Ok(());
+        };
+        let record = SessionRecord {
+            data: state.data.clone(),
+            expires_at: SystemTime::now() + self.0.config.ttl,
+        };
+        self.0.config.store.save(id, &record).await
+    }
+
+    fn set_cookie(&self, id: Option<&str>) {
+        let config = &self.0.config;
+        let value = id.map(|id| config.encode_id(id));
+        let cookie = config.set_cookie(value.as_deref());
+        let sent = match (&self.0.response, HeaderValue::from_str(&cookie)) {
+            (Some(response), Ok(cookie)) => {
+                response.append(HeaderName::from_static("set-cookie"), cookie)
+            }
+            _ => false,
+        };
+        if !sent {
+            leptos::logging::warn!(
+                "The session cookie could not be set, because the response \
+                 headers were already sent or are not available."
+            );
+        }
+    }
+}
+
+fn new_id() -> String {
+    let mut id = [0; 16];
+    SystemRandom::new()
+        .fill(&mut id)
+        .expect("system randomness is available");
+    URL_SAFE_NO_PAD.encode(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+    use leptos::{
+        prelude::{expect_context, Owner},
+        request::{HeaderMap, RequestParts},
+    };
+
+    // runs `fun` in a request that sent `cookie`, and returns the `Set-Cookie` headers
+    fn request<Fut>(
+        config: &SessionConfig,
+        cookie: Option<&str>,
+        fun: impl FnOnce(Session) -> Fut,
+    ) -> Vec<String>
+    where
+        Fut: Future<Output = ()>,
+    {
+        let set_cookies = Arc::new(SyncMutex::new(Vec::new()));
+        let owner = Owner::new();
+        owner.with(|| {
+            let mut headers = HeaderMap::new();
+            if let Some(cookie) = cookie {
+                headers.insert("cookie", cookie.parse().unwrap());
+            }
+            provide_context(RequestParts {
+                headers,
+                ..Default::default()
+            });
+            provide_context(ResponseHeaders::new({
+                let set_cookies = Arc::clone(&set_cookies);
+                move |_, value| {
+                    set_cookies
+                        .lock()
+                        .or_poisoned()
+                        .push(value.to_str().unwrap().to_owned());
+                    true
+                }
+            }));
+            config.provide();
+            block_on(fun(expect_context::<Session>()));
+        });
+        let set_cookies = set_cookies.lock().or_poisoned().clone();
+        set_cookies
+    }
+
+    fn cookie_pair(set_cookie: &str) -> &str {
+        set_cookie.split(';').next().unwrap()
+    }
+
+    #[test]
+    fn sessions_persist_between_requests() {
+        let store = MemoryStore::default();
+        let config = SessionConfig::new(
+            store.clone(),
+            SessionKey::from_secret(&[1; 32]),
+        )
+        .protection(CookieProtection::Encrypted)
+        .cookie_name("sid");
+
+        // reading an empty session does not set a cookie
+        let set = request(&config, None, |session| async move {
+            assert_eq!(session.get::<u32>("visits").await.unwrap(), None);
+        });
+        assert!(set.is_empty());
+
+        let set = request(&config, None, |session| async move {
+            session.insert("visits", &1).await.unwrap();
+        });
+        assert_eq!(set.len(), 1);
+        assert!(set[0].starts_with("sid="));
+        assert!(set[0].contains("; HttpOnly; SameSite=Lax; Secure"));
+        let cookie = cookie_pair(&set[0]).to_owned();
+
+        let set = request(&config, Some(&cookie), |session| async move {
+            assert_eq!(session.get::<u32>("visits").await.unwrap(), Some(1));
+            session.cycle_id().await.unwrap();
+            assert_eq!(session.get::<u32>("visits").await.unwrap(), Some(1));
+        });
+        assert_eq!(set.len(), 1);
+        let cycled = cookie_pair(&set[0]).to_owned();
+
+        // the old ID no longer works after cycling
+        request(&config, Some(&cookie), |session| async move {
+            assert_eq!(session.get::<u32>("visits").await.unwrap(), None);
+        });
+        // and neither does a tampered cookie
+        request(&config, Some("sid=abc"), |session| async move {
+            assert_eq!(session.id().await.unwrap(), None);
+        });
+
+        let set = request(&config, Some(&cycled), |session| async move {
+            session.destroy().await.unwrap();
+        });
+        assert!(set[0].starts_with("sid=; Path=/; Max-Age=0"));
+        assert!(store.0.lock().or_poisoned().is_empty());
+    }
+
+    #[test]
+    fn session_ids_are_signed_and_encrypted() {
+        let key = SessionKey::from_secret(&[7; 32]);
+        let other = SessionKey::from_secret(&[8; 32]);
+
+        let signed = key.sign("abc");
+        assert!(signed.starts_with("abc."));
+        assert_eq!(key.verify(&signed).as_deref(), Some("abc"));
+        assert_eq!(other.verify(&signed), None);
+        assert_eq!(key.verify(&signed.replace("abc", "abd")), None);
+
+        let encrypted = key.encrypt("abc", "id");
+        assert!(!encrypted.contains("abc"));
+        assert_ne!(encrypted, key.encrypt("abc", "id"));
+        assert_eq!(key.decrypt(&encrypted, "id").as_deref(), Some("abc"));
+        assert_eq!(key.decrypt(&encrypted, "other"), None);
+        assert_eq!(other.decrypt(&encrypted, "id"), None);
+        assert_eq!(key.decrypt("short", "id"), None);
+    }
+}
@@ -0,0 +1,190 @@
+//! Graceful shutdown of in-flight server-rendered responses.
+//!
+//! When a server is asked to shut down, for example during a rolling deploy, pages that are still
+//! being streamed would otherwise be cut off wherever the process happens to stop. Calling
+//! [`drain`] instead marks the server as shutting down, so that the integrations answer new
+//! requests with `503 Service Unavailable`, and then waits for every in-flight response to finish.
+//! Responses that are still streaming when the deadline passes are ended at the next chunk
+//! boundary, followed by the closing tags of the document.
+//!
+//! The server integrations provide a `shutdown_signal` function that calls [`drain`] when the
+//! process receives `SIGTERM` or Ctrl+C.
+
+use futures::{
+    channel::oneshot,
+    future::{self, Either, FutureExt, Shared},
+    stream, Stream, StreamExt,
+};
+use std::{
+    future::Future,
+    pin::pin,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Mutex, OnceLock,
+    },
+    task::{Poll, Waker},
+};
+
+/// Closes the document of a response that was ended early.
+const HTML_TAIL: &str = "</body></html>";
+
+struct State {
+    shutting_down: AtomicBool,
+    in_flight: AtomicUsize,
+    drained: Mutex<Vec<Waker>>,
+    terminate_tx: Mutex<Option<oneshot::Sender<()>>>,
+    terminate: Shared<oneshot::Receiver<()>>,
+}
+
+fn state() -> &'static State {
+    static STATE: OnceLock<State> = OnceLock::new();
+    STATE.get_or_init(|| {
+        let (tx, rx) = oneshot::channel();
+        State {
+            shutting_down: AtomicBool::new(false),
+            in_flight: AtomicUsize::new(0),
+            drained: Mutex::new(Vec::new()),
+            terminate_tx: Mutex::new(Some(tx)),
+            terminate: rx.shared(),
+        }
+    })
+}
+
+/// Whether the server has started to shut down, in which case new requests should be rejected.
+pub fn is_shutting_down() -> bool {
+    state().shutting_down.load(Ordering::Acquire)
+}
+
+/// The number of server-rendered responses that are currently being streamed.
+pub fn in_flight() -> usize {
+    state().in_flight.load(Ordering::Acquire)
+}
+
+/// Starts shutting down, and waits until every in-flight response has finished.
+///
+/// Responses that are still streaming when `deadline` resolves are ended early with a valid HTML
+/// tail. This resolves once no responses are in flight anymore, at which point the server can
+/// exit without truncating any of them.
+pub async fn drain(deadline: impl Future<Output = ()>) {
+    let state = state();
+    state.shutting_down.store(true, Ordering::Release);
+
+    if let Either::Right(_) =
+        future::select(pin!(drained()), pin!(deadline)).await
+    {
+        let remaining = in_flight();
+        if remaining > 0 {
+            leptos::logging::warn!(
+                "Shutdown deadline passed with {remaining} response(s) still \
+                 streaming. Ending them early."
+            );
+        }
+        if let Some(terminate) = state.terminate_tx.lock().unwrap().take() {
+            _ = terminate.send(());
+        }
+        drained().await;
+    }
+}
+
+fn drained() -> impl Future<Output = ()> {
+    future::poll_fn(|cx| {
+        let state = state();
+        if in_flight() == 0 {
+            return Poll::Ready(());
+        }
+        state.drained.lock().unwrap().push(cx.waker().clone());
+        // a response may have finished while the waker was registered
+        if in_flight() == 0 {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    })
+}
+
+/// Counts a response as in flight until it is dropped.
+pub(crate) struct InFlight;
+
+impl InFlight {
+    pub(crate) fn new() -> Self {
+        state().in_flight.fetch_add(1, Ordering::AcqRel);
+        InFlight
+    }
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        let state = state();
+        if state.in_flight.fetch_sub(1, Ordering::AcqRel) == 1 {
+            for waker in state.drained.lock().unwrap().drain(..) {
+                waker.wake();
+            }
+        }
+    }
+}
+
+/// Keeps a response in flight while the rest of its body is streamed, and ends the body early
+/// with a valid HTML tail if the shutdown deadline passes. `sent` is the part of the body that
+/// has already been sent.
+pub(crate) fn track(
+    in_flight: InFlight,
+    sent: &str,
+    rest: impl Stream<Item = String> + Send + 'static,
+) -> impl Stream<Item = String> + Send + 'static {
+    let closed = sent.contains("</html>");
+    let rest = Box::pin(rest.take_until(state().terminate.clone()));
+    stream::unfold(Some((rest, closed, in_flight)), |tracked| async move {
+        let (mut rest, closed, in_flight) = tracked?;
+        match rest.next().await {
+            Some(chunk) => {
+                let closed = closed || chunk.contains("</html>");
+                Some((chunk, Some((rest, closed, in_flight))))
+            }
+            // keep the response in flight until the tail has been sent
+            None if rest.is_stopped() && !closed => {
+                Some((HTML_TAIL.to_string(), Some((rest, true, in_flight))))
+            }
+            None => None,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::{channel::mpsc, executor::block_on, join};
+
+    #[test]
+    fn draining_ends_streams_that_outlive_the_deadline() {
+        block_on(async {
+            let finished = track(
+                InFlight::new(),
+                "<html><body>",
+                stream::iter(["</body></html>".to_string()]),
+            );
+            let (chunks, rx) = mpsc::unbounded::<String>();
+            let mut stuck =
+                Box::pin(track(InFlight::new(), "<html><body>", rx));
+            assert_eq!(in_flight(), 2);
+
+            assert_eq!(finished.collect::<Vec<_>>().await, ["</body></html>"]);
+            assert_eq!(in_flight(), 1);
+            chunks.unbounded_send("<p>".into()).unwrap();
+            assert_eq!(stuck.next().await.as_deref(), Some("<p>"));
+
+            let (deadline_tx, deadline_rx) = oneshot::channel::<()>();
+            let ((), rest) = join!(
+                drain(async move {
+                    _ = deadline_rx.await;
+                }),
+                async {
+                    assert!(is_shutting_down());
+                    deadline_tx.send(()).unwrap();
+                    stuck.collect::<Vec<_>>().await
+                }
+            );
+            assert_eq!(rest, [HTML_TAIL]);
+            assert_eq!(in_flight(), 0);
+        });
+    }
+}
@@ -0,0 +1,125 @@
+//! `robots.txt` and `sitemap.xml` for the routes of an app.
+//!
+//! The server integrations provide routes that serve both files, built from the route list of the
+//! app and the crawler options in [`LeptosOptions`]:
+//!
+//! - [`site_url`](LeptosOptions::site_url) is the public URL used for the absolute URLs of the
+//!   sitemap,
+//! - [`robots_disallow`](LeptosOptions::robots_disallow) lists the path prefixes that crawlers
+//!   should not visit.
+//!
+//! The sitemap only lists routes without parameters or wildcards, as their other URLs cannot be
+//! known from the route tree alone.
+
+use leptos_config::LeptosOptions;
+use std::collections::BTreeSet;
+
+/// Returns the content of a `robots.txt` that allows every crawler to visit every path except
+/// the [`robots_disallow`](LeptosOptions::robots_disallow) prefixes, and points to the sitemap.
+pub fn robots_txt(options: &LeptosOptions) -> String {
+    let mut robots = String::from("User-agent: *\n");
+    if options.robots_disallow.is_empty() {
+        robots.push_str("Disallow:\n");
+    }
+    for path in &options.robots_disallow {
+        robots.push_str(&format!("Disallow: {path}\n"));
+    }
+    robots.push_str(&format!("\nSitemap: {}/sitemap.xml\n", site_url(options)));
+    robots
+}
+
+/// Returns the content of a `sitemap.xml` that lists the given route paths, skipping those with
+/// parameters or wildcards and those that crawlers are asked not to visit.
+pub fn sitemap_xml<'a>(
+    options: &LeptosOptions,
+    paths: impl IntoIterator<Item = &'a str>,
+) -> String {
+    let site_url = site_url(options);
+    let paths = paths
+        .into_iter()
+        .filter(|path| is_static(path))
+        .filter(|path| *path != &*options.not_found_path)
+        .filter(|path| {
+            !options
+                .robots_disallow
+                .iter()
+                .any(|prefix| path.starts_with(prefix.as_str()))
+        })
+        .collect::<BTreeSet<_>>();
+
+    let mut sitemap = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<urlset \
+         xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+    );
+    for path in paths {
+        sitemap.push_str("  <url><loc>");
+        escape_into(&mut sitemap, &site_url);
+        escape_into(&mut sitemap, path);
+        sitemap.push_str("</loc></url>\n");
+    }
+    sitemap.push_str("</urlset>\n");
+    sitemap
+}
+
+fn site_url(options: &LeptosOptions) -> String {
+    match &options.site_url {
+        Some(url) => url.trim_end_matches('/').to_string(),
+        None => format!("http://{}", options.site_addr),
+    }
+}
+
+/// Whether the path matches a single URL. Covers both the Axum (`{id}`, `{*rest}`) and the Actix
+/// (`{id}`, `{rest:.*}`) path syntax.
+fn is_static(path: &str) -> bool {
+    !path.contains(['{', ':', '*'])
+}
+
+fn escape_into(buf: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => buf.push_str("&amp;"),
+            '<' => buf.push_str("&lt;"),
+            '>' => buf.push_str("&gt;"),
+            '"' => buf.push_str("&quot;"),
+            '\'' => buf.push_str("&apos;"),
+            c => buf.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sitemap_lists_static_routes_that_crawlers_may_visit() {
+        let options = LeptosOptions::builder()
+            .output_name("app")
+            .site_url("https://example.com/")
+            .robots_disallow(vec!["/admin".to_string()])
+            .build();
+
+        assert_eq!(
+            robots_txt(&options),
+            "User-agent: *\nDisallow: /admin\n\nSitemap: \
+             https://example.com/sitemap.xml\n"
+        );
+        assert_eq!(
+            sitemap_xml(
+                &options,
+                [
+                    "/about",
+                    "/",
+                    "/posts/{id}",
+                    "/admin/users",
+                    "/404",
+                    "/about"
+                ]
+            ),
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<urlset \
+             xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n  \
+             <url><loc>https://example.com/</loc></url>\n  \
+             <url><loc>https://example.com/about</loc></url>\n</urlset>\n"
+        );
+    }
+}
@@ -0,0 +1,180 @@
+//! Canvas drawing, physics and other animations that can't be expressed with CSS need a callback
+//! that runs before every repaint. [`use_raf`](crate::animation_frame::use_raf) runs one until the
+//! reactive owner that started it is cleaned up, and can be paused and resumed in between.
+//!
+//! ```rust
+//! use leptos::{animation_frame::use_raf, prelude::*};
+//!
+//! #[component]
+//! pub fn Spinner() -> impl IntoView {
+//!     let (angle, set_angle) = signal(0.0);
+//!     let raf = use_raf(move |_timestamp, delta| {
+//!         // a full turn every two seconds
+//!         set_angle.update(|angle| *angle = (*angle + delta * 0.18) % 360.0);
+//!     });
+//!
+//!     view! {
+//!         <div style:transform=move || format!("rotate({}deg)", angle.get())>"↻"</div>
+//!         <button on:click=move |_| {
+//!             if raf.is_running() { raf.pause() } else { raf.resume() }
+//!         }>
+//!             {move || if raf.is_running() { "Pause" } else { "Resume" }}
+//!         </button>
+//!         <p>"Running for " {move || (raf.elapsed() / 1000.0).floor()} "s"</p>
+//!     }
+//! }
+//! ```
+//!
+//! Animation frames only exist in the browser; on the server, the callback is never called.
+
+use leptos_dom::helpers::{queue_microtask, window};
+use reactive_graph::{
+    effect::Effect,
+    owner::on_cleanup,
+    signal::RwSignal,
+    traits::{Get, GetUntracked, Set, Update},
+};
+use send_wrapper::SendWrapper;
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen::{closure::Closure, JsCast};
+
+/// A handle to an animation frame loop, created with [`use_raf`].
+#[derive(Debug, Clone, Copy)]
+pub struct UseRaf {
+    running: RwSignal<bool>,
+    elapsed: RwSignal<f64>,
+}
+
+impl UseRaf {
+    /// Stops calling the callback until [`resume`](Self::resume) is called.
+    pub fn pause(&self) {
+        if self.running.get_untracked() {
+            self.running.set(false);
+        }
+    }
+
+    /// Starts calling the callback again after [`pause`](Self::pause).
+    ///
+    /// The first frame after resuming has a delta of `0.0`, so that the time spent paused is not
+    /// counted.
+    pub fn resume(&self) {
+        if !self.running.get_untracked() {
+            self.running.set(true);
+        }
+    }
+
+    /// Whether the loop is running. Reactive.
+    pub fn is_running(&self) -> bool {
+        self.running.get()
+    }
+
+    /// The total time that the loop has been running, in milliseconds, not counting the time it
+    /// spent paused. Reactive, and updated on every frame.
+    pub fn elapsed(&self) -> f64 {
+        self.elapsed.get()
+    }
+}
+
+/// Calls `callback` on every animation frame, with the timestamp of the frame and the time since
+/// the previous frame, both in milliseconds.
+///
+/// The delta is `0.0` on the first frame. The loop stops when the reactive owner that called this
+/// is cleaned up.
+pub fn use_raf(callback: impl Fn(f64, f64) + 'static) -> UseRaf {
+    let raf = UseRaf {
+        running: RwSignal::new(true),
+        elapsed: RwSignal::new(0.0),
+    };
+    let callback: Rc<dyn Fn(f64, f64)> = Rc::new(callback);
+
+    // effects only run in the browser, so no frame is requested on the server
+    Effect::new(move |_| {
+        if !raf.running.get() {
+            return;
+        }
+        let frame = Rc::new(RefCell::new(Frame {
+            request: None,
+            last: None,
+            on_frame: None,
+        }));
+        let on_frame = Closure::<dyn FnMut(f64)>::new({
+            let frame = Rc::downgrade(&frame);
+            let callback = Rc::clone(&callback);
+            move |timestamp: f64| {
+                let Some(frame) = frame.upgrade() else {
+                    return;
+                };
+                let delta = {
+                    let mut frame = frame.borrow_mut();
+                    let delta =
+                        frame.last.map(|last| timestamp - last).unwrap_or(0.0);
+                    frame.last = Some(timestamp);
+                    delta
+                };
+                raf.elapsed.update(|elapsed| *elapsed += delta);
+                callback(timestamp, delta);
+                frame.borrow_mut().request_next();
+            }
+        });
+        frame.borrow_mut().on_frame = Some(on_frame);
+        frame.borrow_mut().request_next();
+
+        let frame = SendWrapper::new(frame);
+        on_cleanup(move || {
+            let frame = frame.take();
+            let on_frame = {
+                let mut frame = frame.borrow_mut();
+                if let Some(request) = frame.request.take() {
+                    _ = window().cancel_animation_frame(request);
+                }
+                frame.on_frame.take()
+            };
+            // the loop can be stopped from inside the callback, which must not be dropped while
+            // it is running
+            queue_microtask(move || drop(on_frame));
+        });
+    });
+
+    raf
+}
+
+struct Frame {
+    request: Option<i32>,
+    last: Option<f64>,
+    on_frame: Option<Closure<dyn FnMut(f64)>>,
+}
+
+impl Frame {
+    fn request_next(&mut self) {
+        self.request = self.on_frame.as_ref().and_then(|on_frame| {
+            window()
+                .request_animation_frame(on_frame.as_ref().unchecked_ref())
+                .ok()
+        });
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use super::*;
+    use crate::testing::with_test_owner;
+
+    #[test]
+    fn the_loop_can_be_paused_and_resumed_on_the_server() {
+        with_test_owner(|| {
+            let raf =
+                use_raf(|_, _| panic!("there are no frames on the server"));
+            assert!(raf.is_running());
+            assert_eq!(raf.elapsed(), 0.0);
+
+            raf.pause();
+            assert!(!raf.is_running());
+            raf.pause();
+            assert!(!raf.is_running());
+
+            raf.resume();
+            assert!(raf.is_running());
+            assert_eq!(raf.elapsed(), 0.0);
+        });
+    }
+}
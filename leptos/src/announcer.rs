@@ -0,0 +1,199 @@
+//! Changes that sighted users notice on their own, like a toast appearing or a “Saving…” status
+//! turning into “Saved”, go unnoticed by screen reader users unless they are announced.
+//! [`use_announcer`](crate::announcer::use_announcer) returns an
+//! [`Announcer`](crate::announcer::Announcer) that posts messages to a visually hidden
+//! [live region](https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/ARIA_Live_Regions)
+//! that Leptos adds to the page the first time it is needed:
+//!
+//! ```rust
+//! use leptos::{announcer::use_announcer, prelude::*};
+//!
+//! #[component]
+//! pub fn SaveButton(save: ServerAction<Save>) -> impl IntoView {
+//!     let announcer = use_announcer();
+//!     Effect::new(move |_| match save.value().get() {
+//!         Some(Ok(_)) => announcer.polite("Saved"),
+//!         Some(Err(_)) => announcer.assertive("Could not save your changes"),
+//!         None => {}
+//!     });
+//!
+//!     view! { <button on:click=move |_| { save.dispatch(Save {}); }>"Save"</button> }
+//! }
+//! # #[server] async fn save() -> Result<(), ServerFnError> { Ok(()) }
+//! ```
+//!
+//! A message that is posted again while it is still being announced is only announced once. Each
+//! message is removed from the live region a few seconds later, so that users who browse the
+//! page don't come across stale messages. A new message replaces the previous one with the same
+//! politeness.
+//!
+//! Announcements are only made in the browser; on the server, they do nothing.
+
+use std::time::Duration;
+
+/// How urgently a message is announced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Politeness {
+    /// Announced once the screen reader is done with what it is reading, for status updates.
+    #[default]
+    Polite,
+    /// Announced right away, interrupting what the screen reader is reading, for errors and other
+    /// messages that need immediate attention.
+    Assertive,
+}
+
+/// Posts messages to the live regions of the page. Returned by [`use_announcer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Announcer;
+
+/// Returns an [`Announcer`] to announce messages to screen reader users.
+pub fn use_announcer() -> Announcer {
+    Announcer
+}
+
+impl Announcer {
+    /// Announces `message` with the given politeness.
+    pub fn announce(&self, message: impl Into<String>, politeness: Politeness) {
+        let message = message.into();
+        if message.is_empty() || !is_browser() {
+            return;
+        }
+        region::announce(message, politeness);
+    }
+
+    /// Announces `message` once the screen reader is done with what it is reading.
+    pub fn polite(&self, message: impl Into<String>) {
+        self.announce(message, Politeness::Polite);
+    }
+
+    /// Announces `message` right away, interrupting what the screen reader is reading.
+    pub fn assertive(&self, message: impl Into<String>) {
+        self.announce(message, Politeness::Assertive);
+    }
+}
+
+// screen readers announce changes to the content of a live region, so it is emptied first and
+// the message is only added a moment later, to announce a message that was already there again
+const ANNOUNCE_AFTER: Duration = Duration::from_millis(100);
+const CLEAR_AFTER: Duration = Duration::from_secs(5);
+
+fn is_browser() -> bool {
+    cfg!(target_arch = "wasm32")
+        && reactive_graph::owner::Owner::current_shared_context()
+            .map(|sc| sc.is_browser())
+            .unwrap_or(true)
+}
+
+mod region {
+    use super::{Politeness, ANNOUNCE_AFTER, CLEAR_AFTER};
+    use leptos_dom::helpers::{document, set_timeout};
+    use std::cell::RefCell;
+    use wasm_bindgen::JsCast;
+    use web_sys::HtmlElement;
+
+    const VISUALLY_HIDDEN: &str = "position:absolute;width:1px;height:1px;\
+        margin:-1px;padding:0;border:0;overflow:hidden;clip:rect(0 0 0 0);\
+        white-space:nowrap;";
+
+    thread_local! {
+        static REGIONS: RefCell<[Option<Region>; 2]> = const { RefCell::new([None, None]) };
+    }
+
+    struct Region {
+        element: HtmlElement,
+        // the message being announced, if any
+        message: Option<String>,
+        // bumped for each message, so that the timers of earlier messages do nothing
+        generation: u64,
+    }
+
+    pub(super) fn announce(message: String, politeness: Politeness) {
+        let index = politeness as usize;
+        let generation = REGIONS.with(|regions| {
+            let mut regions = regions.borrow_mut();
+            let region = regions[index].get_or_insert_with(|| Region {
+                element: create(politeness),
+                message: None,
+                generation: 0,
+            });
+            if region.message.as_deref() == Some(message.as_str()) {
+                return None;
+            }
+            region.element.set_text_content(None);
+            region.message = Some(message);
+            region.generation += 1;
+            Some(region.generation)
+        });
+        let Some(generation) = generation else {
+            return;
+        };
+
+        set_timeout(
+            move || {
+                with_current(index, generation, |region| {
+                    region.element.set_text_content(region.message.as_deref());
+                });
+                set_timeout(
+                    move || {
+                        with_current(index, generation, |region| {
+                            region.element.set_text_content(None);
+                            region.message = None;
+                        })
+                    },
+                    CLEAR_AFTER,
+                );
+            },
+            ANNOUNCE_AFTER,
+        );
+    }
+
+    /// Runs `fun` with the region, unless a later message has replaced the one of `generation`.
+    fn with_current(
+        index: usize,
+        generation: u64,
+        fun: impl FnOnce(&mut Region),
+    ) {
+        REGIONS.with(|regions| {
+            if let Some(region) = regions.borrow_mut()[index]
+                .as_mut()
+                .filter(|region| region.generation == generation)
+            {
+                fun(region);
+            }
+        });
+    }
+
+    fn create(politeness: Politeness) -> HtmlElement {
+        let element = document()
+            .create_element("div")
+            .expect("could not create the live region")
+            .unchecked_into::<HtmlElement>();
+        let (live, role) = match politeness {
+            Politeness::Polite => ("polite", "status"),
+            Politeness::Assertive => ("assertive", "alert"),
+        };
+        _ = element.set_attribute("aria-live", live);
+        _ = element.set_attribute("role", role);
+        _ = element.set_attribute("aria-atomic", "true");
+        _ = element.set_attribute("data-leptos-announcer", live);
+        _ = element.set_attribute("style", VISUALLY_HIDDEN);
+        if let Some(body) = document().body() {
+            _ = body.append_child(&element);
+        }
+        element
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn announcing_outside_the_browser_does_nothing() {
+        // there is no document to add a live region to, so this would panic if it tried
+        let announcer = use_announcer();
+        announcer.polite("Saved");
+        announcer.assertive("Could not save your changes");
+        announcer.announce("", Politeness::Polite);
+    }
+}
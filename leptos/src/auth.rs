@@ -0,0 +1,248 @@
+//! A single source of truth for the current user.
+//!
+//! The server loads the user of each request, usually from its session, and provides an
+//! [`AuthSource`] via context. The same user is then available everywhere:
+//!
+//! - in components, reactively, with [`use_auth`], including in the browser after hydration,
+//! - as the condition of a `<ProtectedRoute/>`, with [`AuthContext::condition`],
+//! - in server functions, with [`require_user`] and [`require_user_where`].
+//!
+//! ```rust
+//! use leptos::{
+//!     auth::{require_user_where, use_auth},
+//!     prelude::*,
+//! };
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Clone, Debug, Serialize, Deserialize)]
+//! pub struct User {
+//!     name: String,
+//!     is_admin: bool,
+//! }
+//!
+//! #[server]
+//! pub async fn delete_post(id: u32) -> Result<(), ServerFnError> {
+//!     let admin = require_user_where::<User>(|user| user.is_admin).await?;
+//!     // ...
+//!     # _ = (id, admin);
+//!     Ok(())
+//! }
+//!
+//! #[component]
+//! pub fn Header() -> impl IntoView {
+//!     let auth = use_auth::<User>();
+//!
+//!     view! {
+//!         <Transition>
+//!             {move || auth.user().map(|user| view! { <span>{user.name}</span> })}
+//!         </Transition>
+//!     }
+//! }
+//! ```
+//!
+//! With `leptos_axum` or `leptos_actix`, `Session::provide_user` provides an [`AuthSource`] that
+//! reads the user from the session.
+
+use leptos_server::OnceResource;
+use reactive_graph::{
+    owner::{provide_context, use_context, with_context},
+    signal::RwSignal,
+    traits::{Get, Set},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use server_fn::error::ServerFnErrorErr;
+use std::{fmt, future::Future, pin::Pin, sync::Arc};
+
+type LoadUser<User> = Pin<Box<dyn Future<Output = Option<User>> + Send>>;
+
+/// Loads the user of the current request on the server.
+///
+/// This is provided via context by the server, for example with the session layer of the
+/// server integrations.
+pub struct AuthSource<User>(Arc<dyn Fn() -> LoadUser<User> + Send + Sync>);
+
+impl<User> AuthSource<User> {
+    /// Creates a source that loads the current user with `load`, which returns `None` if no user
+    /// is signed in.
+    pub fn new<Fut>(load: impl Fn() -> Fut + Send + Sync + 'static) -> Self
+    where
+        Fut: Future<Output = Option<User>> + Send + 'static,
+    {
+        Self(Arc::new(move || Box::pin(load())))
+    }
+
+    /// Loads the current user.
+    pub async fn load(&self) -> Option<User> {
+        (self.0)().await
+    }
+}
+
+impl<User> Clone for AuthSource<User> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<User> fmt::Debug for AuthSource<User> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AuthSource").finish_non_exhaustive()
+    }
+}
+
+/// The user that is signed in, shared between the server and the client. Created with
+/// [`provide_auth`] or [`use_auth`].
+pub struct AuthContext<User>
+where
+    User: Send + Sync + 'static,
+{
+    user: OnceResource<Option<User>>,
+    local: RwSignal<Option<Option<User>>>,
+}
+
+impl<User> Clone for AuthContext<User>
+where
+    User: Send + Sync + 'static,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<User> Copy for AuthContext<User> where User: Send + Sync + 'static {}
+
+impl<User> fmt::Debug for AuthContext<User>
+where
+    User: Send + Sync + 'static,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AuthContext").finish_non_exhaustive()
+    }
+}
+
+/// Creates the [`AuthContext`] for the current user and provides it via context.
+///
+/// The user is loaded from the [`AuthSource`] while rendering on the server, and sent to the
+/// client with the HTML, like any other resource. Without a source, for example in a client-side
+/// rendered app, no user is signed in until one is set with [`AuthContext::set_user`].
+///
+/// This is usually called once, near the root of the app, so that every component shares the
+/// same context.
+#[track_caller]
+pub fn provide_auth<User>() -> AuthContext<User>
+where
+    User: Clone + Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+    let source = with_context(AuthSource::<User>::clone);
+    let auth = AuthContext {
+        user: OnceResource::new(async move {
+            match source {
+                Some(source) => source.load().await,
+                None => None,
+            }
+        }),
+        local: RwSignal::new(None),
+    };
+    provide_context(auth);
+    auth
+}
+
+/// Returns the [`AuthContext`] provided by [`provide_auth`], or calls it if no context has been
+/// provided yet.
+#[track_caller]
+pub fn use_auth<User>() -> AuthContext<User>
+where
+    User: Clone + Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+    with_context(AuthContext::<User>::clone).unwrap_or_else(provide_auth)
+}
+
+impl<User> AuthContext<User>
+where
+    User: Clone + Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+    fn state(&self) -> Option<Option<User>> {
+        self.local.get().or_else(|| self.user.get())
+    }
+
+    /// Returns the current user, or `None` if no user is signed in or the user is still loading.
+    ///
+    /// Reactive. Reading the user while it loads suspends, so this should be read under
+    /// `<Suspense/>` or `<Transition/>`.
+    pub fn user(&self) -> Option<User> {
+        self.state().flatten()
+    }
+
+    /// Returns whether a user is signed in, or `None` while the user is loading. Reactive.
+    pub fn is_authenticated(&self) -> Option<bool> {
+        self.state().map(|user| user.is_some())
+    }
+
+    /// Returns whether a user is signed in and `allowed` returns `true` for them, or `None` while
+    /// the user is loading. Reactive.
+    pub fn is_allowed(
+        &self,
+        allowed: impl FnOnce(&User) -> bool,
+    ) -> Option<bool> {
+        self.state()
+            .map(|user| user.as_ref().map(allowed).unwrap_or(false))
+    }
+
+    /// Returns a condition for `<ProtectedRoute/>` that allows signed-in users.
+    pub fn condition(
+        self,
+    ) -> impl Fn() -> Option<bool> + Send + Clone + 'static {
+        move || self.is_authenticated()
+    }
+
+    /// Returns a condition for `<ProtectedRoute/>` that allows signed-in users for whom `allowed`
+    /// returns `true`.
+    pub fn condition_with(
+        self,
+        allowed: impl Fn(&User) -> bool + Send + Clone + 'static,
+    ) -> impl Fn() -> Option<bool> + Send + Clone + 'static {
+        move || self.is_allowed(&allowed)
+    }
+
+    /// Replaces the client's copy of the current user.
+    ///
+    /// This does not sign anyone in or out on the server. It is useful to reflect a change after a
+    /// server function that signed a user in or out has returned.
+    pub fn set_user(&self, user: Option<User>) {
+        self.local.set(Some(user));
+    }
+}
+
+/// Returns the user of the request that is currently being handled, or an error if no user is
+/// signed in.
+///
+/// This is meant to guard server functions, and loads the user directly from the [`AuthSource`].
+pub async fn require_user<User>() -> Result<User, ServerFnErrorErr>
+where
+    User: Send + Sync + 'static,
+{
+    require_user_where(|_| true).await
+}
+
+/// Returns the user of the request that is currently being handled, or an error if no user is
+/// signed in or `allowed` returns `false` for them.
+///
+/// See [`require_user`].
+pub async fn require_user_where<User>(
+    allowed: impl FnOnce(&User) -> bool,
+) -> Result<User, ServerFnErrorErr>
+where
+    User: Send + Sync + 'static,
+{
+    let source = use_context::<AuthSource<User>>().ok_or_else(|| {
+        ServerFnErrorErr::ServerError(
+            "AuthSource should have been provided on the server".to_string(),
+        )
+    })?;
+    match source.load().await {
+        Some(user) if allowed(&user) => Ok(user),
+        Some(_) => Err(ServerFnErrorErr::ServerError("forbidden".to_string())),
+        None => {
+            Err(ServerFnErrorErr::ServerError("unauthenticated".to_string()))
+        }
+    }
+}
@@ -0,0 +1,63 @@
+//! The path is configured once, as the [`base_path`](crate::config::LeptosOptions::base_path) of
+//! the app, and set by [`mount_server_fns`](crate::hydration::mount_server_fns) when the server
+//! starts. The integrations call it when they add the routes of the app, and move the routes that
+//! were listed before it was set below it. Then
+//!
+//! - the `<Router/>` matches routes and resolves links below it,
+//! - [`HydrationScripts`](crate::hydration::HydrationScripts) loads the JS and Wasm files from
+//!   below it, and passes it on to the client when it is hydrated,
+//! - [`mount_server_fns`](crate::hydration::mount_server_fns) serves the server functions below
+//!   it.
+//!
+//! Apps that are rendered on the client only should call
+//! [`set_base_path`](crate::base_path::set_base_path) before they mount.
+
+#[cfg(doc)]
+use leptos_config::LeptosOptions;
+use std::{
+    borrow::Cow,
+    sync::{Arc, RwLock},
+};
+
+static BASE_PATH: RwLock<Option<Arc<str>>> = RwLock::new(None);
+
+/// Sets the path the app is served under. An empty path or `/` serves it at the root of the site.
+pub fn set_base_path(path: Option<&str>) {
+    let path = path.and_then(normalize).map(Arc::from);
+    *BASE_PATH.write().unwrap_or_else(|e| e.into_inner()) = path;
+}
+
+/// Returns the path the app is served under, with a leading and without a trailing slash, or
+/// `None` if it is served at the root of the site.
+pub fn base_path() -> Option<Arc<str>> {
+    BASE_PATH.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Prefixes an absolute `path` with the path the app is served under.
+pub fn with_base_path(path: &str) -> String {
+    match base_path() {
+        Some(base) => format!("{base}{path}"),
+        None => path.to_string(),
+    }
+}
+
+/// Prefixes an absolute `path` with the path the app is served under, unless it is already below
+/// it, like the path of a route that was listed after the base path was set.
+pub fn ensure_base_path(path: &str) -> Cow<'_, str> {
+    match base_path() {
+        Some(base)
+            if !path.strip_prefix(&*base).is_some_and(|rest| {
+                rest.is_empty() || rest.starts_with('/')
+            }) =>
+        {
+            Cow::Owned(format!("{base}{path}"))
+        }
+        _ => Cow::Borrowed(path),
+    }
+}
+
+/// Brings a base path into the `/tools/myapp` form, or returns `None` for the root of the site.
+pub fn normalize(path: &str) -> Option<String> {
+    let path = path.trim_matches('/');
+    (!path.is_empty()).then(|| format!("/{path}"))
+}
@@ -0,0 +1,271 @@
+//! Reading and writing the system clipboard, with the
+//! [Clipboard API](https://developer.mozilla.org/en-US/docs/Web/API/Clipboard_API).
+//!
+//! [`use_clipboard`] returns a [`UseClipboard`] handle that copies text to the clipboard, and
+//! tracks whether something was copied recently, so that a “Copy” button can briefly turn into
+//! “Copied!”:
+//!
+//! ```rust
+//! use leptos::{clipboard::use_clipboard, prelude::*, task::spawn_local};
+//!
+//! #[component]
+//! pub fn CopyButton(text: String) -> impl IntoView {
+//!     let clipboard = use_clipboard();
+//!
+//!     view! {
+//!         <button on:click=move |_| {
+//!             let text = text.clone();
+//!             spawn_local(async move {
+//!                 if let Err(e) = clipboard.copy(text).await {
+//!                     leptos::logging::warn!("{e}");
+//!                 }
+//!             });
+//!         }>
+//!             {move || if clipboard.copied() { "Copied!" } else { "Copy" }}
+//!         </button>
+//!     }
+//! }
+//! ```
+//!
+//! The Clipboard API is only available on pages served over HTTPS or from `localhost`. Elsewhere,
+//! and in browsers that deny the request, copying falls back to the deprecated
+//! `document.execCommand("copy")`, which works in more places as long as it runs in response to
+//! a click or a key press. There is no such fallback for reading the clipboard.
+//!
+//! The clipboard can only be accessed in the browser; on the server, every operation fails with
+//! [`ClipboardError::Unsupported`].
+
+use leptos_dom::helpers::{
+    document, set_timeout_with_handle, window, TimeoutHandle,
+};
+use reactive_graph::{
+    owner::{on_cleanup, StoredValue},
+    signal::RwSignal,
+    traits::{Get, GetValue, Set, SetValue},
+};
+use std::time::Duration;
+use thiserror::Error;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    js_sys::Reflect, Clipboard, HtmlDocument, HtmlElement, HtmlTextAreaElement,
+};
+
+/// An error while accessing the clipboard.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ClipboardError {
+    /// The clipboard can't be accessed here, like on the server, or on a page that is not served
+    /// over HTTPS.
+    #[error("the clipboard is not available")]
+    Unsupported,
+    /// The browser or the user denied access to the clipboard.
+    #[error("access to the clipboard was denied: {0}")]
+    Denied(String),
+    /// Accessing the clipboard failed for another reason.
+    #[error("could not access the clipboard: {0}")]
+    Failed(String),
+}
+
+/// A handle to the clipboard, created with [`use_clipboard`].
+#[derive(Debug, Clone, Copy)]
+pub struct UseClipboard {
+    copied: RwSignal<bool>,
+    reset_after: Duration,
+    reset: StoredValue<Option<TimeoutHandle>>,
+}
+
+/// Returns a handle to the clipboard, whose [`copied`](UseClipboard::copied) state resets two
+/// seconds after each copy.
+pub fn use_clipboard() -> UseClipboard {
+    use_clipboard_with_reset(Duration::from_secs(2))
+}
+
+/// Returns a handle to the clipboard, whose [`copied`](UseClipboard::copied) state resets
+/// `reset_after` after each copy.
+pub fn use_clipboard_with_reset(reset_after: Duration) -> UseClipboard {
+    let clipboard = UseClipboard {
+        copied: RwSignal::new(false),
+        reset_after,
+        reset: StoredValue::new(None),
+    };
+    on_cleanup(move || clipboard.clear_reset());
+    clipboard
+}
+
+impl UseClipboard {
+    /// Whether text was copied to the clipboard recently. Reactive.
+    pub fn copied(&self) -> bool {
+        self.copied.get()
+    }
+
+    /// Copies `text` to the clipboard.
+    pub async fn copy(
+        &self,
+        text: impl Into<String>,
+    ) -> Result<(), ClipboardError> {
+        let text = text.into();
+        if !is_browser() {
+            return Err(ClipboardError::Unsupported);
+        }
+        let result = match clipboard() {
+            Some(clipboard) => {
+                match JsFuture::from(clipboard.write_text(&text)).await {
+                    Ok(_) => Ok(()),
+                    Err(e) => {
+                        let e = ClipboardError::from_js(e);
+                        exec_copy(&text).map_err(|_| e)
+                    }
+                }
+            }
+            None => exec_copy(&text),
+        };
+        if result.is_ok() {
+            self.mark_copied();
+        }
+        result
+    }
+
+    /// Reads the text that is on the clipboard.
+    ///
+    /// Browsers usually ask the user for permission the first time a page does this.
+    pub async fn read(&self) -> Result<String, ClipboardError> {
+        if !is_browser() {
+            return Err(ClipboardError::Unsupported);
+        }
+        let clipboard = clipboard().ok_or(ClipboardError::Unsupported)?;
+        let text = JsFuture::from(clipboard.read_text())
+            .await
+            .map_err(ClipboardError::from_js)?;
+        Ok(text.as_string().unwrap_or_default())
+    }
+
+    fn mark_copied(&self) {
+        self.clear_reset();
+        self.copied.set(true);
+        let copied = self.copied;
+        let handle = set_timeout_with_handle(
+            move || {
+                // the handle may have been disposed of since
+                _ = copied.try_set(false);
+            },
+            self.reset_after,
+        )
+        .ok();
+        _ = self.reset.try_set_value(handle);
+    }
+
+    fn clear_reset(&self) {
+        if let Some(handle) = self.reset.try_get_value().flatten() {
+            handle.clear();
+        }
+    }
+}
+
+impl ClipboardError {
+    fn from_js(e: JsValue) -> Self {
+        let field = |name: &str| {
+            Reflect::get(&e, &JsValue::from_str(name))
+                .ok()
+                .and_then(|value| value.as_string())
+        };
+        let message = field("message").unwrap_or_else(|| format!("{e:?}"));
+        match field("name").as_deref() {
+            Some("NotAllowedError") | Some("SecurityError") => {
+                ClipboardError::Denied(message)
+            }
+            _ => ClipboardError::Failed(message),
+        }
+    }
+}
+
+fn is_browser() -> bool {
+    cfg!(target_arch = "wasm32")
+        && reactive_graph::owner::Owner::current_shared_context()
+            .map(|sc| sc.is_browser())
+            .unwrap_or(true)
+}
+
+/// `navigator.clipboard`, which is missing outside secure contexts.
+fn clipboard() -> Option<Clipboard> {
+    let navigator = window().navigator();
+    Reflect::get(&navigator, &JsValue::from_str("clipboard"))
+        .ok()
+        .filter(|clipboard| !clipboard.is_undefined() && !clipboard.is_null())
+        .map(|_| navigator.clipboard())
+}
+
+/// Copies `text` by selecting it in a hidden `<textarea>` and running the `copy` command.
+fn exec_copy(text: &str) -> Result<(), ClipboardError> {
+    let document = document();
+    let body = document.body().ok_or(ClipboardError::Unsupported)?;
+    let focused = document.active_element();
+    let textarea = document
+        .create_element("textarea")
+        .map_err(ClipboardError::from_js)?
+        .unchecked_into::<HtmlTextAreaElement>();
+    textarea.set_value(text);
+    _ = textarea.set_attribute("readonly", "");
+    _ = textarea
+        .set_attribute("style", "position:fixed;top:0;left:-9999px;opacity:0;");
+    _ = body.append_child(&textarea);
+    textarea.select();
+    let copied = document
+        .unchecked_ref::<HtmlDocument>()
+        .exec_command("copy");
+    textarea.remove();
+    if let Some(focused) =
+        focused.and_then(|el| el.dyn_into::<HtmlElement>().ok())
+    {
+        _ = focused.focus();
+    }
+    match copied {
+        Ok(true) => Ok(()),
+        Ok(false) => Err(ClipboardError::Denied(
+            "the copy command was not allowed".to_string(),
+        )),
+        Err(e) => Err(ClipboardError::from_js(e)),
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use super::*;
+    use crate::testing::with_test_owner;
+    use any_spawner::time;
+    use futures::executor::block_on;
+    use reactive_graph::traits::GetUntracked;
+
+    #[test]
+    fn the_clipboard_is_not_available_on_the_server() {
+        with_test_owner(|| {
+            let clipboard = use_clipboard();
+            assert_eq!(
+                block_on(clipboard.copy("text")),
+                Err(ClipboardError::Unsupported)
+            );
+            assert_eq!(
+                block_on(clipboard.read()),
+                Err(ClipboardError::Unsupported)
+            );
+            assert!(!clipboard.copied());
+        });
+    }
+
+    #[test]
+    fn copied_resets_after_the_last_copy() {
+        with_test_owner(|| {
+            let clipboard = use_clipboard_with_reset(Duration::from_secs(2));
+            clipboard.mark_copied();
+            assert!(clipboard.copied.get_untracked());
+
+            time::advance_time(Duration::from_secs(1));
+            // copying again restarts the delay
+            clipboard.mark_copied();
+            time::advance_time(Duration::from_secs(1));
+            assert!(clipboard.copied.get_untracked());
+
+            time::advance_time(Duration::from_secs(1));
+            assert!(!clipboard.copied.get_untracked());
+        });
+    }
+}
@@ -81,3 +81,31 @@ where
         (self)(props)
     }
 }
+
+/// Implemented for the props of a component with a `#[prop(flatten)]` prop of type `G`, so
+/// that a value of a [`PropGroup`](macro@crate::PropGroup) can be spread into the component
+/// with `<MyComponent {..group}/>`.
+#[rustversion::attr(
+    since(1.78),
+    diagnostic::on_unimplemented(
+        message = "`{Self}` has no `#[prop(flatten)]` prop of type `{G}`",
+        label = "this prop group cannot be spread into the component"
+    )
+)]
+pub trait HasPropGroup<G> {
+    /// Replaces the value of the flattened prop.
+    fn set_prop_group(&mut self, group: G);
+}
+
+/// Used by the `view!` macro for `{..value}` spreads on a component: values of a
+/// [`PropGroup`](macro@crate::PropGroup) have an inherent method with the same name, which
+/// takes precedence over this one and sets the flattened prop instead. Any other value is
+/// returned unchanged, to be spread onto the component as attributes.
+#[doc(hidden)]
+pub trait SpreadAttributes: Sized {
+    fn __spread_onto<P>(self, _props: &mut P) -> Self {
+        self
+    }
+}
+
+impl<T> SpreadAttributes for T {}
@@ -0,0 +1,304 @@
+//! Isomorphic, reactive access to cookies.
+//!
+//! [`use_cookie`] reads a cookie from the request while rendering on the server and from
+//! `document.cookie` in the browser, and returns a signal with its value and a setter. Setting a
+//! new value writes it back: with a `Set-Cookie` header on the server, and to `document.cookie`
+//! in the browser.
+//!
+//! ```rust
+//! use leptos::{cookie::use_cookie, prelude::*};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+//! pub enum Theme {
+//!     Light,
+//!     Dark,
+//! }
+//!
+//! #[component]
+//! pub fn ThemeToggle() -> impl IntoView {
+//!     let (theme, set_theme) = use_cookie::<Theme>("theme");
+//!     let is_dark = move || theme.get() == Some(Theme::Dark);
+//!
+//!     view! {
+//!         <button on:click=move |_| {
+//!             set_theme.set(Some(if is_dark() { Theme::Light } else { Theme::Dark }))
+//!         }>
+//!             {move || if is_dark() { "Light mode" } else { "Dark mode" }}
+//!         </button>
+//!     }
+//! }
+//! ```
+//!
+//! Values are encoded as JSON and percent-encoded, so any type that implements [`Serialize`] and
+//! [`Deserialize`](serde::Deserialize) can be stored. Cookies that were set by other code as
+//! plain text can still be read into types that deserialize from a string, like `String`.
+//!
+//! On the server, reading requires the [`RequestParts`](crate::request::RequestParts) and
+//! writing the [`ResponseHeaders`] provided by the server integrations. When a page is streamed,
+//! cookies can only be set before the first chunk of HTML has been sent.
+
+use crate::request::{
+    use_request_parts, Cookies, HeaderValue, ResponseHeaders,
+};
+use reactive_graph::{
+    owner::{with_context, Owner},
+    signal::RwSignal,
+    traits::Set,
+    wrappers::{read::Signal, write::SignalSetter},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{fmt::Write, sync::Arc, time::Duration};
+
+/// Controls whether a cookie is sent with cross-site requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SameSite {
+    /// The cookie is only sent with requests from the same site.
+    Strict,
+    /// The cookie is also sent when navigating to the site from another one.
+    Lax,
+    /// The cookie is sent with all requests. Requires [`CookieOptions::secure`].
+    None,
+}
+
+/// Attributes of a cookie written by [`use_cookie_with_options`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CookieOptions {
+    /// The path the cookie applies to. Defaults to `/`.
+    pub path: Option<String>,
+    /// The domain the cookie applies to. Defaults to `None`, which is the current host only.
+    pub domain: Option<String>,
+    /// How long the cookie should be kept. Defaults to `None`, which lasts for the browser session.
+    pub max_age: Option<Duration>,
+    /// Whether the cookie should only be sent over HTTPS. Defaults to `false`.
+    pub secure: bool,
+    /// Whether the cookie should be hidden from JavaScript. Defaults to `false`.
+    ///
+    /// `HttpOnly` cookies can only be written from the server. They are still read during
+    /// server rendering, but are not visible in the browser, so the value will be `None` there.
+    pub http_only: bool,
+    /// Whether the cookie is sent with cross-site requests. Defaults to [`SameSite::Lax`].
+    pub same_site: Option<SameSite>,
+}
+
+impl Default for CookieOptions {
+    fn default() -> Self {
+        Self {
+            path: Some("/".into()),
+            domain: None,
+            max_age: None,
+            secure: false,
+            http_only: false,
+            same_site: Some(SameSite::Lax),
+        }
+    }
+}
+
+/// Reads and writes the cookie called `name`, with the default [`CookieOptions`].
+///
+/// See [`use_cookie_with_options`].
+#[track_caller]
+pub fn use_cookie<T>(
+    name: impl Into<String>,
+) -> (Signal<Option<T>>, SignalSetter<Option<T>>)
+where
+    T: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    use_cookie_with_options(name, CookieOptions::default())
+}
+
+/// Reads and writes the cookie called `name`.
+///
+/// Returns a signal with the current value of the cookie, which is `None` if it is not set or
+/// cannot be deserialized, and a setter. Setting `Some(_)` writes the cookie with the given
+/// `options`, and setting `None` removes it.
+#[track_caller]
+pub fn use_cookie_with_options<T>(
+    name: impl Into<String>,
+    options: CookieOptions,
+) -> (Signal<Option<T>>, SignalSetter<Option<T>>)
+where
+    T: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    let name: Arc<str> = name.into().into();
+    let value =
+        RwSignal::new(read_cookie(&name).and_then(|value| decode::<T>(&value)));
+    let setter = SignalSetter::map(move |new_value: Option<T>| {
+        let encoded = new_value.as_ref().and_then(encode);
+        write_cookie(&name, encoded.as_deref(), &options);
+        value.set(new_value);
+    });
+    (value.into(), setter)
+}
+
+fn is_browser() -> bool {
+    cfg!(target_arch = "wasm32")
+        && Owner::current_shared_context()
+            .map(|sc| sc.is_browser())
+            .unwrap_or(true)
+}
+
+fn read_cookie(name: &str) -> Option<String> {
+    let cookies = if is_browser() {
+        Cookies::parse(&document_cookie()?)
+    } else {
+        use_request_parts::<Cookies>().ok()?
+    };
+    cookies.get(name).map(str::to_owned)
+}
+
+fn write_cookie(name: &str, value: Option<&str>, options: &CookieOptions) {
+    let in_browser = is_browser();
+    let cookie = set_cookie_string(name, value, options, !in_browser);
+    if in_browser {
+        set_document_cookie(&cookie);
+    } else if let Some(headers) = with_context(ResponseHeaders::clone) {
+        if let Ok(cookie) = HeaderValue::from_str(&cookie) {
+            headers.append(http::header::SET_COOKIE, cookie);
+        }
+    }
+}
+
+fn set_cookie_string(
+    name: &str,
+    value: Option<&str>,
+    options: &CookieOptions,
+    http_only: bool,
+) -> String {
+    let mut cookie = format!("{name}={}", value.unwrap_or_default());
+    if let Some(path) = &options.path {
+        _ = write!(cookie, "; Path={path}");
+    }
+    if let Some(domain) = &options.domain {
+        _ = write!(cookie, "; Domain={domain}");
+    }
+    if value.is_none() {
+        cookie.push_str("; Max-Age=0; Expires=Thu, 01 Jan 1970 00:00:00 GMT");
+    } else if let Some(max_age) = options.max_age {
+        _ = write!(cookie, "; Max-Age={}", max_age.as_secs());
+    }
+    if options.secure {
+        cookie.push_str("; Secure");
+    }
+    if http_only && options.http_only {
+        cookie.push_str("; HttpOnly");
+    }
+    if let Some(same_site) = options.same_site {
+        cookie.push_str(match same_site {
+            SameSite::Strict => "; SameSite=Strict",
+            SameSite::Lax => "; SameSite=Lax",
+            SameSite::None => "; SameSite=None",
+        });
+    }
+    cookie
+}
+
+fn document_cookie() -> Option<String> {
+    use wasm_bindgen::JsCast;
+
+    leptos_dom::helpers::document()
+        .dyn_into::<web_sys::HtmlDocument>()
+        .ok()?
+        .cookie()
+        .ok()
+}
+
+fn set_document_cookie(cookie: &str) {
+    use wasm_bindgen::JsCast;
+
+    if let Ok(document) =
+        leptos_dom::helpers::document().dyn_into::<web_sys::HtmlDocument>()
+    {
+        _ = document.set_cookie(cookie);
+    }
+}
+
+fn encode<T: Serialize>(value: &T) -> Option<String> {
+    match serde_json::to_string(value) {
+        Ok(json) => Some(percent_encode(&json)),
+        Err(e) => {
+            crate::logging::error!("Failed to serialize cookie value: {e}");
+            None
+        }
+    }
+}
+
+fn decode<T: DeserializeOwned>(value: &str) -> Option<T> {
+    let value = percent_decode(value)?;
+    serde_json::from_str(&value).ok().or_else(|| {
+        // cookies written by other code are usually not JSON
+        T::deserialize(serde::de::value::StrDeserializer::<
+            serde::de::value::Error,
+        >::new(&value))
+        .ok()
+    })
+}
+
+// the characters allowed in a cookie value by RFC 6265, other than `%`
+fn is_cookie_octet(byte: u8) -> bool {
+    matches!(byte, 0x21 | 0x23..=0x24 | 0x26..=0x2B | 0x2D..=0x3A | 0x3C..=0x5B | 0x5D..=0x7E)
+}
+
+fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if is_cookie_octet(byte) {
+            encoded.push(byte as char);
+        } else {
+            _ = write!(encoded, "%{byte:02X}");
+        }
+    }
+    encoded
+}
+
+fn percent_decode(value: &str) -> Option<String> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = value.get(i + 1..i + 3)?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn values_round_trip_through_cookie_encoding() {
+        let value = vec!["a b".to_string(), "c;d,e\"f".to_string(), "ü".into()];
+        let encoded = encode(&value).unwrap();
+        assert!(encoded.bytes().all(|b| b == b'%' || is_cookie_octet(b)));
+        assert_eq!(decode::<Vec<String>>(&encoded), Some(value));
+        assert_eq!(decode::<String>("plain"), Some("plain".to_string()));
+        assert_eq!(decode::<u32>("42"), Some(42));
+        assert_eq!(decode::<u32>("%4"), None);
+    }
+
+    #[test]
+    fn set_cookie_strings_include_attributes() {
+        let options = CookieOptions {
+            max_age: Some(Duration::from_secs(60)),
+            secure: true,
+            http_only: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            set_cookie_string("theme", Some("1"), &options, true),
+            "theme=1; Path=/; Max-Age=60; Secure; HttpOnly; SameSite=Lax"
+        );
+        assert_eq!(
+            set_cookie_string("theme", None, &options, false),
+            "theme=; Path=/; Max-Age=0; Expires=Thu, 01 Jan 1970 00:00:00 \
+             GMT; Secure; SameSite=Lax"
+        );
+    }
+}
@@ -0,0 +1,150 @@
+use either_of::Either;
+use tachys::html::attribute::Attribute;
+
+/// Declares typed bindings for third-party custom elements (web components), such as those
+/// of a web-component design system.
+///
+/// Each element is declared with the tag name it is registered under, and the attributes,
+/// properties, and events it supports. This generates a component with the given name, which
+/// renders the custom element and takes each attribute and property as an optional, typed
+/// prop. Attributes and properties that are not passed are left unset, so that the element's
+/// own defaults apply. Values can be reactive, as each prop accepts anything that converts into
+/// a [`Signal`](crate::prelude::Signal). Any children are rendered inside the element.
+///
+/// An attribute is named after its field, unless another name is given in parentheses (for
+/// example, because it contains a hyphen). Each event generates an event type with
+/// [`define_custom_event!`](crate::define_custom_event), which can be used with `on:typed:` on the
+/// component, and receives a [`TypedCustomEvent`](crate::tachys::html::event::TypedCustomEvent)
+/// with the given detail type.
+///
+/// ```rust
+/// use leptos::{custom_element_bindings, prelude::*};
+///
+/// custom_element_bindings! {
+///     /// A Shoelace button.
+///     pub SlButton("sl-button") {
+///         attributes {
+///             /// The button's theme variant.
+///             variant: String,
+///             disabled: bool,
+///             help_text("help-text"): String,
+///         }
+///         properties {
+///             value: String,
+///         }
+///         events {
+///             /// Emitted when the button gains focus.
+///             SlFocus("sl-focus"): (),
+///         }
+///     }
+/// }
+///
+/// #[component]
+/// fn Toolbar() -> impl IntoView {
+///     let (saving, set_saving) = signal(false);
+///     view! {
+///         <SlButton
+///             variant="primary"
+///             disabled=saving
+///             on:click=move |_| set_saving.set(true)
+///             on:typed:SlFocus=|_| leptos::logging::log!("focused")
+///         >
+///             "Save"
+///         </SlButton>
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! custom_element_bindings {
+    ($(
+        $(#[$meta:meta])*
+        $vis:vis $name:ident($tag:literal) {
+            $(attributes {
+                $(
+                    $(#[$attr_meta:meta])*
+                    $attr:ident $(($attr_name:literal))?: $attr_ty:ty
+                ),* $(,)?
+            })?
+            $(properties {
+                $(
+                    $(#[$prop_meta:meta])*
+                    $prop:ident $(($prop_name:literal))?: $prop_ty:ty
+                ),* $(,)?
+            })?
+            $(events {
+                $(
+                    $(#[$event_meta:meta])*
+                    $event:ident($event_name:literal): $detail:ty
+                ),* $(,)?
+            })?
+        }
+    )*) => {$(
+        $(#[$meta])*
+        #[$crate::component]
+        $vis fn $name(
+            $($(
+                $(#[$attr_meta])*
+                #[prop(optional, into)]
+                $attr: Option<$crate::prelude::Signal<$attr_ty>>,
+            )*)?
+            $($(
+                $(#[$prop_meta])*
+                #[prop(optional, into)]
+                $prop: Option<$crate::prelude::Signal<$prop_ty>>,
+            )*)?
+            #[prop(optional)]
+            children: Option<$crate::children::Children>,
+        ) -> impl $crate::IntoView {
+            use $crate::tachys::html::element::ElementChild;
+            use $crate::tachys::view::add_attr::AddAnyAttr;
+
+            $crate::tachys::html::element::custom($tag)
+                .add_any_attr(($($(
+                    $crate::custom_element::optional($attr.map(|value| {
+                        $crate::tachys::html::attribute::custom::custom_attribute(
+                            $crate::custom_element_bindings!(
+                                @name $attr $($attr_name)?
+                            ),
+                            value,
+                        )
+                    })),
+                )*)?))
+                .add_any_attr(($($(
+                    $crate::custom_element::optional($prop.map(|value| {
+                        $crate::tachys::html::property::prop(
+                            $crate::custom_element_bindings!(
+                                @name $prop $($prop_name)?
+                            ),
+                            value,
+                        )
+                    })),
+                )*)?))
+                .child(children.map(|children| children()))
+        }
+
+        $($(
+            $crate::define_custom_event!(
+                $(#[$event_meta])*
+                $vis $event, $detail, $event_name
+            );
+        )*)?
+    )*};
+    (@name $field:ident) => {
+        ::std::stringify!($field)
+    };
+    (@name $field:ident $name:literal) => {
+        $name
+    };
+}
+
+/// Adds the attribute if it is `Some`, and nothing otherwise.
+#[doc(hidden)]
+pub fn optional<A>(attr: Option<A>) -> Either<A, ()>
+where
+    A: Attribute,
+{
+    match attr {
+        Some(attr) => Either::Left(attr),
+        None => Either::Right(()),
+    }
+}
@@ -0,0 +1,212 @@
+//! The server registers how to get each dependency once, when it starts, with
+//! [`Dependencies::builder`](crate::dependency::Dependencies::builder), and installs them for each
+//! request with [`Dependencies::provide`](crate::dependency::Dependencies::provide) in the context
+//! closure of its integration. Server functions and components rendered on the server then resolve
+//! them by type with [`use_dependency`](crate::dependency::use_dependency):
+//!
+//! ```rust
+//! use leptos::{
+//!     dependency::{use_dependency, Dependencies},
+//!     prelude::*,
+//! };
+//!
+//! #[derive(Clone)]
+//! pub struct Db; // e.g., a connection pool
+//!
+//! #[derive(Clone)]
+//! pub struct Flags {
+//!     pub new_checkout: bool,
+//! }
+//!
+//! #[server]
+//! pub async fn checkout() -> Result<bool, ServerFnError> {
+//!     let _db: Db = use_dependency()?;
+//!     let flags: Flags = use_dependency()?;
+//!     Ok(flags.new_checkout)
+//! }
+//!
+//! let dependencies = Dependencies::builder()
+//!     .singleton(Db)
+//!     .per_request(|| Flags { new_checkout: false })
+//!     .build();
+//! let additional_context = move || dependencies.provide();
+//! // pass `additional_context` to `leptos_routes_with_context` and
+//! // `handle_server_fns_with_context`
+//! # _ = additional_context;
+//! ```
+//!
+//! Registering a type again replaces the earlier registration, so tests can start from the same
+//! builder as the server and swap out the dependencies they need to fake.
+
+use or_poisoned::OrPoisoned;
+use reactive_graph::owner::{provide_context, use_context};
+use server_fn::error::ServerFnErrorErr;
+use std::{
+    any::{type_name, Any, TypeId},
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+type Constructor = Arc<dyn Fn() -> Box<dyn Any + Send + Sync> + Send + Sync>;
+
+#[derive(Clone)]
+enum Provider {
+    /// Cloned on every resolution.
+    Singleton(Constructor),
+    /// Constructed once per request, on first resolution.
+    PerRequest(Constructor),
+}
+
+/// Registers the dependencies of an app. Created with [`Dependencies::builder`].
+#[derive(Default, Clone)]
+pub struct DependenciesBuilder {
+    providers: HashMap<TypeId, Provider>,
+}
+
+impl fmt::Debug for DependenciesBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DependenciesBuilder")
+            .field("len", &self.providers.len())
+            .finish()
+    }
+}
+
+impl DependenciesBuilder {
+    /// Registers a value that is shared by every request, like a connection pool. Each
+    /// resolution returns a clone of it.
+    pub fn singleton<T>(mut self, value: T) -> Self
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        self.providers.insert(
+            TypeId::of::<T>(),
+            Provider::Singleton(Arc::new(move || Box::new(value.clone()))),
+        );
+        self
+    }
+
+    /// Registers a constructor that is run the first time a request resolves the type, after
+    /// which that request gets clones of the same value.
+    ///
+    /// The constructor runs in the reactive scope of the request, so it can itself use
+    /// [`use_dependency`] or [`use_request_parts`](crate::request::use_request_parts).
+    pub fn per_request<T>(
+        mut self,
+        constructor: impl Fn() -> T + Send + Sync + 'static,
+    ) -> Self
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        self.providers.insert(
+            TypeId::of::<T>(),
+            Provider::PerRequest(Arc::new(move || Box::new(constructor()))),
+        );
+        self
+    }
+
+    /// Finishes the registration.
+    pub fn build(self) -> Dependencies {
+        Dependencies(Arc::new(self.providers))
+    }
+}
+
+/// The dependencies of an app, which can be installed for each request with
+/// [`provide`](Dependencies::provide).
+#[derive(Clone)]
+pub struct Dependencies(Arc<HashMap<TypeId, Provider>>);
+
+impl fmt::Debug for Dependencies {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Dependencies")
+            .field("len", &self.0.len())
+            .finish()
+    }
+}
+
+impl Dependencies {
+    /// Starts registering dependencies.
+    pub fn builder() -> DependenciesBuilder {
+        DependenciesBuilder::default()
+    }
+
+    /// Provides the dependencies via context, with an empty set of per-request values.
+    ///
+    /// This should be called once for each request, from the context closure of the server
+    /// integration.
+    pub fn provide(&self) {
+        provide_context(RequestDependencies {
+            dependencies: self.clone(),
+            values: Default::default(),
+        });
+    }
+}
+
+#[derive(Clone)]
+struct RequestDependencies {
+    dependencies: Dependencies,
+    values: Arc<Mutex<RequestValues>>,
+}
+
+#[derive(Default)]
+struct RequestValues {
+    constructed: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+    constructing: Vec<TypeId>,
+}
+
+/// Resolves the dependency of type `T` for the current request.
+///
+/// This fails if no [`Dependencies`] have been provided, if `T` has not been registered, or if
+/// the constructor of `T` depends on `T` itself.
+pub fn use_dependency<T>() -> Result<T, ServerFnErrorErr>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    let scope = use_context::<RequestDependencies>().ok_or_else(|| {
+        ServerFnErrorErr::ServerError(
+            "Dependencies should have been provided for this request with \
+             Dependencies::provide"
+                .to_string(),
+        )
+    })?;
+    let id = TypeId::of::<T>();
+    let downcast = |value: &(dyn Any + Send + Sync)| {
+        value
+            .downcast_ref::<T>()
+            .cloned()
+            .expect("dependencies are stored under their own TypeId")
+    };
+
+    match scope.dependencies.0.get(&id) {
+        None => Err(ServerFnErrorErr::ServerError(format!(
+            "no dependency of type {} has been registered",
+            type_name::<T>()
+        ))),
+        Some(Provider::Singleton(constructor)) => Ok(downcast(&*constructor())),
+        Some(Provider::PerRequest(constructor)) => {
+            {
+                let mut values = scope.values.lock().or_poisoned();
+                if let Some(value) = values.constructed.get(&id) {
+                    return Ok(downcast(&**value));
+                }
+                if values.constructing.contains(&id) {
+                    return Err(ServerFnErrorErr::ServerError(format!(
+                        "the constructor of {} depends on itself",
+                        type_name::<T>()
+                    )));
+                }
+                values.constructing.push(id);
+            }
+
+            // the lock is released, so the constructor can resolve other dependencies
+            let value = constructor();
+            let resolved = downcast(&*value);
+            let mut values = scope.values.lock().or_poisoned();
+            values
+                .constructing
+                .retain(|constructing| *constructing != id);
+            values.constructed.insert(id, value);
+            Ok(resolved)
+        }
+    }
+}
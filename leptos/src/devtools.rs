@@ -0,0 +1,274 @@
+//! An in-app profiler, with the `devtools` feature.
+//!
+//! [`ProfilerOverlay`] shows live statistics from [`reactive_graph::devtools`] in a corner of the
+//! page: how many signals, memos and effects are alive, how often each component has been created
+//! and how long that took, and how often the effects and memos created in it have rerun and how
+//! long that took. It also briefly outlines the parts of the page that DOM updates touch.
+//!
+//! ```rust
+//! # use leptos::{devtools::ProfilerOverlay, prelude::*};
+//! # #[component] fn App() -> impl IntoView {}
+//! # fn app() -> impl IntoView {
+//! view! {
+//!     <App/>
+//!     <ProfilerOverlay/>
+//! }
+//! # }
+//! ```
+//!
+//! Components only run once, so their render count is the number of times they have been created;
+//! everything after that is an update. The effects that update the dynamic parts of a view are
+//! created when the view is mounted, rather than inside the component, so their reruns count
+//! towards the totals but not towards any component.
+
+use crate::{component, IntoView};
+use leptos_dom::helpers::{
+    document, set_interval_with_handle, set_timeout, IntervalHandle,
+};
+pub use reactive_graph::devtools::{
+    components, reset, stats, ComponentStats, Stats,
+};
+use reactive_graph::{effect::Effect, owner::Owner};
+use std::{fmt::Write, time::Duration};
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::{
+    js_sys::Array, Element, HtmlElement, MutationObserver,
+    MutationObserverInit, MutationRecord, Node,
+};
+
+const NAME: &str = "ProfilerOverlay";
+
+const PANEL_STYLE: &str = "position:fixed;right:8px;bottom:8px;\
+    z-index:2147483647;max-height:50vh;overflow:auto;padding:8px;\
+    border-radius:4px;background:rgba(20,20,20,0.9);color:#eee;\
+    font:12px/1.4 monospace;";
+
+const HIGHLIGHT_STYLE: &str = "position:fixed;pointer-events:none;\
+    z-index:2147483646;outline:2px solid #e44;background:rgba(238,68,68,0.1);\
+    transition:opacity 0.6s;";
+
+/// Shows live statistics about components and the reactive graph in a corner of the page, and
+/// outlines the parts of the page that DOM updates touch.
+///
+/// The overlay only exists in the browser, and doesn't count itself in its statistics.
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all))]
+#[component]
+pub fn ProfilerOverlay(
+    /// How often the statistics are refreshed. Defaults to half a second.
+    #[prop(optional)]
+    refresh_interval: Option<Duration>,
+    /// Whether to outline the elements that DOM updates touch. Defaults to `true`.
+    #[prop(default = true)]
+    highlight_updates: bool,
+) -> impl IntoView {
+    if cfg!(target_arch = "wasm32")
+        && Owner::current_shared_context()
+            .map(|sc| sc.is_browser())
+            .unwrap_or(true)
+    {
+        use send_wrapper::SendWrapper;
+
+        let refresh_interval =
+            refresh_interval.unwrap_or(Duration::from_millis(500));
+        reactive_graph::devtools::ignore(|| {
+            Effect::new(move |_| {
+                let overlay = SendWrapper::new(Overlay::mount(
+                    refresh_interval,
+                    highlight_updates,
+                ));
+                Owner::on_cleanup(move || drop(overlay.take()));
+            })
+        });
+    }
+}
+
+struct Overlay {
+    root: HtmlElement,
+    interval: Option<IntervalHandle>,
+    observer: Option<MutationObserver>,
+    // kept alive for as long as the overlay is
+    _on_reset: Closure<dyn FnMut()>,
+    _on_mutation: Option<Closure<dyn FnMut(Array)>>,
+}
+
+impl Overlay {
+    fn mount(refresh_interval: Duration, highlight_updates: bool) -> Self {
+        let root = create("div");
+        _ = root.set_attribute("data-leptos-profiler", "");
+        let panel = create("div");
+        _ = panel.set_attribute("style", PANEL_STYLE);
+        let header = create("div");
+        header.set_inner_html("<strong>Leptos profiler</strong> ");
+        let reset_button = create("button");
+        reset_button.set_text_content(Some("reset"));
+        let body = create("div");
+        _ = header.append_child(&reset_button);
+        _ = panel.append_child(&header);
+        _ = panel.append_child(&body);
+        _ = root.append_child(&panel);
+        if let Some(page) = document().body() {
+            _ = page.append_child(&root);
+        }
+
+        let refresh = move || body.set_inner_html(&summary());
+        refresh();
+        let on_reset = Closure::<dyn FnMut()>::new({
+            let refresh = refresh.clone();
+            move || {
+                reset();
+                refresh();
+            }
+        });
+        _ = reset_button.add_event_listener_with_callback(
+            "click",
+            on_reset.as_ref().unchecked_ref(),
+        );
+        let interval = set_interval_with_handle(refresh, refresh_interval).ok();
+
+        let (observer, on_mutation) = if highlight_updates {
+            let on_mutation = Closure::<dyn FnMut(Array)>::new({
+                let root = root.clone();
+                move |records: Array| highlight(&root, &records)
+            });
+            let observer =
+                MutationObserver::new(on_mutation.as_ref().unchecked_ref())
+                    .ok();
+            if let (Some(observer), Some(page)) = (&observer, document().body())
+            {
+                let options = MutationObserverInit::new();
+                options.set_child_list(true);
+                options.set_subtree(true);
+                options.set_attributes(true);
+                options.set_character_data(true);
+                _ = observer.observe_with_options(&page, &options);
+            }
+            (observer, Some(on_mutation))
+        } else {
+            (None, None)
+        };
+
+        Self {
+            root,
+            interval,
+            observer,
+            _on_reset: on_reset,
+            _on_mutation: on_mutation,
+        }
+    }
+}
+
+impl Drop for Overlay {
+    fn drop(&mut self) {
+        if let Some(interval) = self.interval.take() {
+            interval.clear();
+        }
+        if let Some(observer) = self.observer.take() {
+            observer.disconnect();
+        }
+        self.root.remove();
+    }
+}
+
+fn create(tag: &str) -> HtmlElement {
+    document()
+        .create_element(tag)
+        .expect("could not create an element")
+        .unchecked_into()
+}
+
+/// Briefly outlines the elements that the mutations touched, except for those of the overlay.
+fn highlight(root: &HtmlElement, records: &Array) {
+    let mut touched: Vec<Element> = Vec::new();
+    for record in records.iter() {
+        let Some(target) = record.unchecked_into::<MutationRecord>().target()
+        else {
+            continue;
+        };
+        let element = match target.dyn_into::<Element>() {
+            Ok(element) => element,
+            Err(node) => match node.parent_element() {
+                Some(parent) => parent,
+                None => continue,
+            },
+        };
+        if root.contains(Some(element.unchecked_ref::<Node>()))
+            || touched.contains(&element)
+        {
+            continue;
+        }
+        touched.push(element);
+    }
+
+    for element in touched {
+        let rect = element.get_bounding_client_rect();
+        if rect.width() == 0.0 && rect.height() == 0.0 {
+            continue;
+        }
+        let outline = create("div");
+        _ = outline.set_attribute(
+            "style",
+            &format!(
+                "{HIGHLIGHT_STYLE}left:{}px;top:{}px;width:{}px;height:{}px;",
+                rect.x(),
+                rect.y(),
+                rect.width(),
+                rect.height()
+            ),
+        );
+        _ = root.append_child(&outline);
+        set_timeout(
+            {
+                let outline = outline.clone();
+                move || _ = outline.style().set_property("opacity", "0")
+            },
+            Duration::from_millis(200),
+        );
+        set_timeout(move || outline.remove(), Duration::from_millis(800));
+    }
+}
+
+/// The statistics as HTML, with the components that have spent the most time first.
+fn summary() -> String {
+    let Stats {
+        arena_items,
+        sources,
+        memos,
+        effects,
+        subscriptions,
+        updates,
+        update_time,
+        ..
+    } = stats();
+    let mut components = components();
+    components.retain(|(name, _)| *name != NAME);
+    components.sort_by_key(|(_, stats)| {
+        std::cmp::Reverse(stats.render_time + stats.update_time)
+    });
+
+    let mut html = format!(
+        "<div>{sources} sources, {memos} memos, {effects} effects</div>\
+         <div>{subscriptions} subscriptions, {arena_items} arena items</div>\
+         <div>{updates} updates in {}</div>\
+         <table><tr><th align=left>component</th><th>renders</th>\
+         <th>render time</th><th>updates</th><th>update time</th></tr>",
+        millis(update_time)
+    );
+    for (name, stats) in components {
+        _ = write!(
+            html,
+            "<tr><td>{name}</td><td align=right>{}</td>\
+             <td align=right>{}</td><td align=right>{}</td>\
+             <td align=right>{}</td></tr>",
+            stats.renders,
+            millis(stats.render_time),
+            stats.updates,
+            millis(stats.update_time)
+        );
+    }
+    html.push_str("</table>");
+    html
+}
+
+fn millis(duration: Duration) -> String {
+    format!("{:.1}ms", duration.as_secs_f64() * 1000.0)
+}
@@ -0,0 +1,475 @@
+//! Drag and drop, between the elements of a page and from outside of it.
+//!
+//! The [`draggable`] directive makes an element draggable, and puts [`DragData`] on the drag when
+//! it starts. [`use_drop_zone`] turns an element into a place where things can be dropped: it
+//! tracks whether something is being dragged over the element, and the files that were dropped
+//! on it, like images dragged in from the file manager.
+//!
+//! ```rust
+//! use leptos::{
+//!     drag_and_drop::{
+//!         draggable, use_drop_zone_with_options, DragData, DropZoneOptions,
+//!     },
+//!     html::Ul,
+//!     prelude::*,
+//! };
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Clone, Serialize, Deserialize)]
+//! struct Card {
+//!     id: u32,
+//! }
+//!
+//! #[component]
+//! pub fn Column(cards: RwSignal<Vec<u32>>) -> impl IntoView {
+//!     let column = NodeRef::<Ul>::new();
+//!     let zone = use_drop_zone_with_options(
+//!         column,
+//!         DropZoneOptions::default().accept(DragData::JSON),
+//!         move |dropped| {
+//!             if let Some(card) = dropped.json::<Card>() {
+//!                 cards.update(|cards| cards.push(card.id));
+//!             }
+//!         },
+//!     );
+//!
+//!     view! {
+//!         <ul node_ref=column class:over=move || zone.is_over.get()>
+//!             <For each=move || cards.get() key=|id| *id let:id>
+//!                 <li use:draggable=DragData::new().json(&Card { id })>
+//!                     {id}
+//!                 </li>
+//!             </For>
+//!         </ul>
+//!     }
+//! }
+//! ```
+//!
+//! Data is put on the drag under a MIME type, so that other apps can read it too: text under
+//! `text/plain`, and typed values as JSON under `application/json`. Browsers only let a page read
+//! that data once it is dropped; while dragging, only the MIME types can be checked, which is what
+//! [`DropZoneOptions::accept`] does.
+//!
+//! Listeners are only added in the browser, and removed when the reactive owner that added them is
+//! cleaned up. On the server, nothing is ever dragged over a drop zone.
+
+use reactive_graph::{
+    effect::Effect,
+    owner::{on_cleanup, LocalStorage},
+    signal::RwSignal,
+    traits::{Get, GetUntracked, Set},
+    wrappers::read::Signal,
+};
+use send_wrapper::SendWrapper;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{cell::Cell, rc::Rc};
+use tachys::{
+    html::{
+        element::{ElementExt, ElementType},
+        event::dragstart,
+    },
+    reactive_graph::node_ref::NodeRef,
+};
+use wasm_bindgen::{closure::Closure, JsCast};
+pub use web_sys::File;
+use web_sys::{DataTransfer, DragEvent, Element};
+
+/// What happens to the dragged item when it is dropped, which browsers show with the cursor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DragEffect {
+    /// The item is copied to where it is dropped.
+    #[default]
+    Copy,
+    /// The item is moved to where it is dropped.
+    Move,
+    /// A link to the item is created where it is dropped.
+    Link,
+}
+
+impl DragEffect {
+    fn as_str(self) -> &'static str {
+        match self {
+            DragEffect::Copy => "copy",
+            DragEffect::Move => "move",
+            DragEffect::Link => "link",
+        }
+    }
+}
+
+/// The data that the [`draggable`] directive puts on a drag, under one or more MIME types.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DragData {
+    items: Vec<(String, String)>,
+    effects: Vec<DragEffect>,
+}
+
+impl DragData {
+    /// The MIME type of plain text.
+    pub const TEXT: &'static str = "text/plain";
+
+    /// The MIME type that typed values are serialized under.
+    pub const JSON: &'static str = "application/json";
+
+    /// Creates empty drag data, which can be moved or copied.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `data` under the MIME type `mime`, replacing any data already under that type.
+    pub fn with(
+        mut self,
+        mime: impl Into<String>,
+        data: impl Into<String>,
+    ) -> Self {
+        let mime = mime.into();
+        self.items.retain(|(existing, _)| *existing != mime);
+        self.items.push((mime, data.into()));
+        self
+    }
+
+    /// Adds plain text, which any drop target can read.
+    pub fn text(self, text: impl Into<String>) -> Self {
+        self.with(Self::TEXT, text)
+    }
+
+    /// Adds a value serialized as JSON, to be read with [`Dropped::json`].
+    pub fn json<T>(self, value: &T) -> Self
+    where
+        T: Serialize + ?Sized,
+    {
+        match serde_json::to_string(value) {
+            Ok(json) => self.with(Self::JSON, json),
+            Err(e) => {
+                crate::logging::debug_warn!(
+                    "could not serialize drag data: {e}"
+                );
+                self
+            }
+        }
+    }
+
+    /// Allows the drop target to apply `effect` to the item. If no effect is allowed explicitly,
+    /// the item can be copied or moved.
+    pub fn allow(mut self, effect: DragEffect) -> Self {
+        if !self.effects.contains(&effect) {
+            self.effects.push(effect);
+        }
+        self
+    }
+
+    /// The value of the `effectAllowed` property of the drag.
+    fn effect_allowed(&self) -> &'static str {
+        let allows = |effect| self.effects.contains(&effect);
+        match (
+            allows(DragEffect::Copy),
+            allows(DragEffect::Move),
+            allows(DragEffect::Link),
+        ) {
+            (false, false, false) | (true, true, false) => "copyMove",
+            (true, false, false) => "copy",
+            (false, true, false) => "move",
+            (false, false, true) => "link",
+            (true, false, true) => "copyLink",
+            (false, true, true) => "linkMove",
+            (true, true, true) => "all",
+        }
+    }
+
+    fn set_on(&self, transfer: &DataTransfer) {
+        for (mime, data) in &self.items {
+            _ = transfer.set_data(mime, data);
+        }
+        transfer.set_effect_allowed(self.effect_allowed());
+    }
+}
+
+/// A directive that makes an element draggable, and puts `data` on the drag when it starts.
+///
+/// ```rust
+/// # use leptos::{drag_and_drop::{draggable, DragData}, prelude::*};
+/// # #[component] fn Tag(name: String) -> impl IntoView {
+/// let data = DragData::new().text(name.clone());
+/// view! { <span use:draggable=data>{name}</span> }
+/// # }
+/// ```
+pub fn draggable(el: Element, data: DragData) {
+    _ = el.set_attribute("draggable", "true");
+    let handle = el.on(dragstart, move |ev| {
+        if let Some(transfer) = ev.data_transfer() {
+            data.set_on(&transfer);
+        }
+    });
+    on_cleanup(move || drop(handle));
+}
+
+/// What was dropped on a drop zone, passed to the callback of [`use_drop_zone_with_options`].
+#[derive(Debug, Clone, Default)]
+pub struct Dropped {
+    /// The files that were dropped, if any.
+    pub files: Vec<File>,
+    /// The data that was dropped, with its MIME type.
+    pub data: Vec<(String, String)>,
+}
+
+impl Dropped {
+    fn from_transfer(transfer: &DataTransfer) -> Self {
+        let files = transfer
+            .files()
+            .map(|files| {
+                (0..files.length()).filter_map(|i| files.get(i)).collect()
+            })
+            .unwrap_or_default();
+        let data = types(transfer)
+            .into_iter()
+            .filter(|mime| mime != "Files")
+            .filter_map(|mime| {
+                let data = transfer.get_data(&mime).ok()?;
+                Some((mime, data))
+            })
+            .collect();
+        Self { files, data }
+    }
+
+    /// The data that was dropped under the MIME type `mime`.
+    pub fn get(&self, mime: &str) -> Option<&str> {
+        self.data
+            .iter()
+            .find(|(existing, _)| existing == mime)
+            .map(|(_, data)| data.as_str())
+    }
+
+    /// The plain text that was dropped.
+    pub fn text(&self) -> Option<&str> {
+        self.get(DragData::TEXT)
+    }
+
+    /// The value that was put on the drag with [`DragData::json`], or `None` if there is none or
+    /// it is not a `T`.
+    pub fn json<T>(&self) -> Option<T>
+    where
+        T: DeserializeOwned,
+    {
+        serde_json::from_str(self.get(DragData::JSON)?).ok()
+    }
+}
+
+/// Options that configure [`use_drop_zone_with_options`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DropZoneOptions {
+    /// The MIME types that can be dropped. `Files` stands for files. Drags that carry none of
+    /// them are ignored. Defaults to accepting everything.
+    pub accept: Vec<String>,
+    /// What happens to the dragged item when it is dropped. Defaults to [`DragEffect::Copy`].
+    pub drop_effect: DragEffect,
+}
+
+impl DropZoneOptions {
+    /// Accepts drags that carry data of the MIME type `mime`.
+    pub fn accept(mut self, mime: impl Into<String>) -> Self {
+        self.accept.push(mime.into());
+        self
+    }
+
+    /// Accepts drags that carry files.
+    pub fn accept_files(self) -> Self {
+        self.accept("Files")
+    }
+
+    /// Sets what happens to the dragged item when it is dropped.
+    pub fn drop_effect(mut self, effect: DragEffect) -> Self {
+        self.drop_effect = effect;
+        self
+    }
+
+    fn accepts(&self, transfer: &DataTransfer) -> bool {
+        self.accept.is_empty()
+            || types(transfer)
+                .iter()
+                .any(|mime| self.accept.contains(mime))
+    }
+}
+
+/// The state of a drop zone, created with [`use_drop_zone`].
+#[derive(Debug, Clone, Copy)]
+pub struct UseDropZone {
+    /// Whether something that can be dropped is being dragged over the element. Reactive.
+    pub is_over: Signal<bool>,
+    /// The files of the last drop, or an empty list if it did not include any. Reactive.
+    pub files: Signal<Vec<File>, LocalStorage>,
+}
+
+/// Turns the element that `node_ref` refers to into a place where anything can be dropped.
+pub fn use_drop_zone<E>(node_ref: NodeRef<E>) -> UseDropZone
+where
+    E: ElementType,
+    E::Output: JsCast + Clone + 'static,
+{
+    use_drop_zone_with_options(node_ref, DropZoneOptions::default(), |_| {})
+}
+
+/// Turns the element that `node_ref` refers to into a place where the things allowed by
+/// `options` can be dropped, and calls `on_drop` with each drop.
+///
+/// If `node_ref` is loaded with another element later, that one becomes the drop zone instead.
+pub fn use_drop_zone_with_options<E>(
+    node_ref: NodeRef<E>,
+    options: DropZoneOptions,
+    on_drop: impl Fn(Dropped) + 'static,
+) -> UseDropZone
+where
+    E: ElementType,
+    E::Output: JsCast + Clone + 'static,
+{
+    let is_over = RwSignal::new(false);
+    let files = RwSignal::new_local(Vec::new());
+    let options = Rc::new(options);
+    let on_drop: Rc<dyn Fn(Dropped)> = Rc::new(on_drop);
+
+    // effects only run in the browser, so nothing can be dropped on the server
+    Effect::new(move |_| {
+        let Some(el) = node_ref.get() else {
+            return;
+        };
+        let el = el.unchecked_into::<Element>();
+        // dragenter and dragleave fire for every child the drag passes over, so the drag is only
+        // over the zone while more of them have entered than left
+        let depth = Rc::new(Cell::new(0u32));
+        let set_over = move |over: bool| {
+            if is_over.get_untracked() != over {
+                is_over.set(over);
+            }
+        };
+
+        let on_dragenter = Closure::<dyn Fn(DragEvent)>::new({
+            let options = Rc::clone(&options);
+            let depth = Rc::clone(&depth);
+            move |ev: DragEvent| {
+                if !accepts(&ev, &options) {
+                    return;
+                }
+                ev.prevent_default();
+                depth.set(depth.get() + 1);
+                set_over(true);
+            }
+        });
+        let on_dragover = Closure::<dyn Fn(DragEvent)>::new({
+            let options = Rc::clone(&options);
+            move |ev: DragEvent| {
+                if !accepts(&ev, &options) {
+                    return;
+                }
+                // cancelling dragover is what allows the drop
+                ev.prevent_default();
+                if let Some(transfer) = ev.data_transfer() {
+                    transfer.set_drop_effect(options.drop_effect.as_str());
+                }
+            }
+        });
+        let on_dragleave = Closure::<dyn Fn(DragEvent)>::new({
+            let depth = Rc::clone(&depth);
+            move |_: DragEvent| {
+                depth.set(depth.get().saturating_sub(1));
+                if depth.get() == 0 {
+                    set_over(false);
+                }
+            }
+        });
+        let on_drop = Closure::<dyn Fn(DragEvent)>::new({
+            let options = Rc::clone(&options);
+            let on_drop = Rc::clone(&on_drop);
+            move |ev: DragEvent| {
+                depth.set(0);
+                set_over(false);
+                if !accepts(&ev, &options) {
+                    return;
+                }
+                // keeps the browser from opening dropped files
+                ev.prevent_default();
+                let Some(transfer) = ev.data_transfer() else {
+                    return;
+                };
+                let dropped = Dropped::from_transfer(&transfer);
+                files.set(dropped.files.clone());
+                on_drop(dropped);
+            }
+        });
+
+        let listeners = [
+            ("dragenter", on_dragenter),
+            ("dragover", on_dragover),
+            ("dragleave", on_dragleave),
+            ("drop", on_drop),
+        ];
+        for (event, listener) in &listeners {
+            _ = el.add_event_listener_with_callback(
+                event,
+                listener.as_ref().unchecked_ref(),
+            );
+        }
+
+        let cleanup = SendWrapper::new((el, listeners));
+        on_cleanup(move || {
+            let (el, listeners) = cleanup.take();
+            for (event, listener) in &listeners {
+                _ = el.remove_event_listener_with_callback(
+                    event,
+                    listener.as_ref().unchecked_ref(),
+                );
+            }
+            if is_over.get_untracked() {
+                is_over.set(false);
+            }
+        });
+    });
+
+    UseDropZone {
+        is_over: is_over.into(),
+        files: files.into(),
+    }
+}
+
+fn accepts(ev: &DragEvent, options: &DropZoneOptions) -> bool {
+    ev.data_transfer()
+        .is_some_and(|transfer| options.accepts(&transfer))
+}
+
+fn types(transfer: &DataTransfer) -> Vec<String> {
+    transfer
+        .types()
+        .iter()
+        .filter_map(|mime| mime.as_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DragData, DragEffect};
+
+    #[test]
+    fn effect_allowed_combines_allowed_effects() {
+        assert_eq!(DragData::new().effect_allowed(), "copyMove");
+        assert_eq!(
+            DragData::new().allow(DragEffect::Move).effect_allowed(),
+            "move"
+        );
+        assert_eq!(
+            DragData::new()
+                .allow(DragEffect::Link)
+                .allow(DragEffect::Copy)
+                .effect_allowed(),
+            "copyLink"
+        );
+    }
+
+    #[test]
+    fn later_data_replaces_earlier_data_of_the_same_type() {
+        let data = DragData::new().text("a").json(&1).text("b");
+        assert_eq!(
+            data.items,
+            [
+                ("application/json".to_string(), "1".to_string()),
+                ("text/plain".to_string(), "b".to_string())
+            ]
+        );
+    }
+}
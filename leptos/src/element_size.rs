@@ -0,0 +1,254 @@
+//! Reactive element sizes, with a
+//! [`ResizeObserver`](https://developer.mozilla.org/en-US/docs/Web/API/ResizeObserver).
+//!
+//! [`use_element_size`] tracks the width and height of an element, for charts and layouts that
+//! need to know how much room they have. [`use_resize_observer`] passes the raw
+//! [`ResizeObserverEntry`] of each change to a callback instead, for the border box or device
+//! pixel sizes.
+//!
+//! ```rust
+//! use leptos::{element_size::use_element_size, html::Div, prelude::*};
+//!
+//! #[component]
+//! pub fn Chart() -> impl IntoView {
+//!     let container = NodeRef::<Div>::new();
+//!     let size = use_element_size(container);
+//!
+//!     view! {
+//!         <div node_ref=container class="chart">
+//!             <svg width=size.width height=size.height></svg>
+//!         </div>
+//!     }
+//! }
+//! ```
+//!
+//! Every element is observed by a single `ResizeObserver`, which is shared by all of them, so
+//! that a page with many observed elements still only computes their sizes once per frame.
+//! Elements are only observed in the browser, once they have been mounted, and are no longer
+//! observed when the reactive owner that started observing them is cleaned up. On the server,
+//! sizes are `0.0`.
+
+use reactive_graph::{
+    effect::Effect,
+    owner::on_cleanup,
+    signal::RwSignal,
+    traits::{Get, Set},
+    wrappers::read::Signal,
+};
+use std::rc::Rc;
+use tachys::{html::element::ElementType, reactive_graph::node_ref::NodeRef};
+use wasm_bindgen::JsCast;
+use web_sys::Element;
+pub use web_sys::ResizeObserverEntry;
+
+/// The size of an element's content box, in CSS pixels, created with [`use_element_size`].
+#[derive(Debug, Clone, Copy)]
+pub struct UseElementSize {
+    /// The width of the element. Reactive.
+    pub width: Signal<f64>,
+    /// The height of the element. Reactive.
+    pub height: Signal<f64>,
+}
+
+/// Tracks the size of the content box of the element that `node_ref` refers to.
+///
+/// If `node_ref` is loaded with another element later, that one is observed instead.
+pub fn use_element_size<E>(node_ref: NodeRef<E>) -> UseElementSize
+where
+    E: ElementType,
+    E::Output: JsCast + Clone + 'static,
+{
+    let width = RwSignal::new(0.0);
+    let height = RwSignal::new(0.0);
+    use_resize_observer(node_ref, move |entry| {
+        let rect = entry.content_rect();
+        width.set(rect.width());
+        height.set(rect.height());
+    });
+    UseElementSize {
+        width: width.into(),
+        height: height.into(),
+    }
+}
+
+/// Calls `callback` with each [`ResizeObserverEntry`] for the element that `node_ref` refers to:
+/// once when it starts being observed, and then whenever its size changes.
+///
+/// If `node_ref` is loaded with another element later, that one is observed instead.
+pub fn use_resize_observer<E>(
+    node_ref: NodeRef<E>,
+    callback: impl Fn(&ResizeObserverEntry) + 'static,
+) where
+    E: ElementType,
+    E::Output: JsCast + Clone + 'static,
+{
+    let callback: Rc<dyn Fn(&ResizeObserverEntry)> = Rc::new(callback);
+
+    // effects only run in the browser, so nothing is observed on the server
+    Effect::new(move |_| {
+        let Some(el) = node_ref.get() else {
+            return;
+        };
+        let el = el.unchecked_into::<Element>();
+        if let Some(id) = shared::observe(&el, Rc::clone(&callback)) {
+            on_cleanup(move || shared::unobserve(id));
+        }
+    });
+}
+
+mod shared {
+    use super::ResizeObserverEntry;
+    use std::{cell::RefCell, collections::HashMap, rc::Rc};
+    use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+    use web_sys::{
+        js_sys::{Array, Map},
+        Element, ResizeObserver,
+    };
+
+    type Callback = Rc<dyn Fn(&ResizeObserverEntry)>;
+
+    thread_local! {
+        static OBSERVER: RefCell<Option<Shared>> = const { RefCell::new(None) };
+    }
+
+    /// Identifies one call to [`observe`], so that it can be undone.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub(super) struct ObserveId {
+        target: u32,
+        subscriber: u32,
+    }
+
+    struct Shared {
+        observer: ResizeObserver,
+        _on_resize: Closure<dyn Fn(Array)>,
+        // maps each observed element to the id of its entry in `targets`
+        ids: Map,
+        targets: HashMap<u32, Target>,
+        next_id: u32,
+    }
+
+    struct Target {
+        element: Element,
+        subscribers: Vec<(u32, Callback)>,
+    }
+
+    impl Shared {
+        fn new() -> Option<Self> {
+            let on_resize = Closure::<dyn Fn(Array)>::new(on_resize);
+            let observer =
+                ResizeObserver::new(on_resize.as_ref().unchecked_ref()).ok()?;
+            Some(Self {
+                observer,
+                _on_resize: on_resize,
+                ids: Map::new(),
+                targets: HashMap::new(),
+                next_id: 0,
+            })
+        }
+
+        fn next_id(&mut self) -> u32 {
+            self.next_id = self.next_id.wrapping_add(1);
+            self.next_id
+        }
+    }
+
+    /// Starts calling `callback` for the resizes of `element`.
+    pub(super) fn observe(
+        element: &Element,
+        callback: Callback,
+    ) -> Option<ObserveId> {
+        OBSERVER.with(|observer| {
+            let mut observer = observer.borrow_mut();
+            if observer.is_none() {
+                *observer = Shared::new();
+            }
+            let shared = observer.as_mut()?;
+            let subscriber = shared.next_id();
+            let target = match shared.ids.get(element).as_f64() {
+                Some(target) => target as u32,
+                None => {
+                    let target = shared.next_id();
+                    shared.ids.set(element, &JsValue::from(target));
+                    shared.targets.insert(
+                        target,
+                        Target {
+                            element: element.clone(),
+                            subscribers: Vec::new(),
+                        },
+                    );
+                    shared.observer.observe(element);
+                    target
+                }
+            };
+            let entry = shared.targets.get_mut(&target)?;
+            entry.subscribers.push((subscriber, callback));
+            Some(ObserveId { target, subscriber })
+        })
+    }
+
+    /// Stops calling the callback that was passed to [`observe`], and stops observing the element
+    /// if nothing else needs its size.
+    pub(super) fn unobserve(id: ObserveId) {
+        // the callback is dropped after the borrow ends, in case dropping it disposes of
+        // something that observes another element
+        let _removed = OBSERVER.with(|observer| {
+            let mut observer = observer.borrow_mut();
+            let shared = observer.as_mut()?;
+            let target = shared.targets.get_mut(&id.target)?;
+            let idx = target
+                .subscribers
+                .iter()
+                .position(|(subscriber, _)| *subscriber == id.subscriber)?;
+            let removed = target.subscribers.remove(idx);
+            if target.subscribers.is_empty() {
+                let target = shared.targets.remove(&id.target)?;
+                shared.observer.unobserve(&target.element);
+                shared.ids.delete(&target.element);
+            }
+            Some(removed)
+        });
+    }
+
+    fn on_resize(entries: Array) {
+        for entry in entries.iter() {
+            let Ok(entry) = entry.dyn_into::<ResizeObserverEntry>() else {
+                continue;
+            };
+            // callbacks are collected first, because they can observe or unobserve elements
+            let callbacks = OBSERVER.with(|observer| {
+                let observer = observer.borrow();
+                let shared = observer.as_ref()?;
+                let target = shared.ids.get(&entry.target()).as_f64()? as u32;
+                Some(
+                    shared
+                        .targets
+                        .get(&target)?
+                        .subscribers
+                        .iter()
+                        .map(|(_, callback)| Rc::clone(callback))
+                        .collect::<Vec<_>>(),
+                )
+            });
+            for callback in callbacks.into_iter().flatten() {
+                callback(&entry);
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use super::*;
+    use crate::testing::with_test_owner;
+    use reactive_graph::traits::GetUntracked;
+    use tachys::html::element::Div;
+
+    #[test]
+    fn sizes_are_zero_until_the_element_is_observed() {
+        with_test_owner(|| {
+            let size = use_element_size(NodeRef::<Div>::new());
+            assert_eq!(size.width.get_untracked(), 0.0);
+            assert_eq!(size.height.get_untracked(), 0.0);
+        });
+    }
+}
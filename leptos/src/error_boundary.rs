@@ -5,7 +5,7 @@ use leptos_macro::component;
 use reactive_graph::{
     computed::ArcMemo,
     effect::RenderEffect,
-    owner::{provide_context, ArcStoredValue, Owner},
+    owner::{shadow_context, ArcStoredValue, Owner},
     signal::ArcRwSignal,
     traits::{Get, Update, With, WithUntracked, WriteValue},
 };
@@ -101,8 +101,8 @@ where
 
     let owner = Owner::new();
     let children = owner.with(|| {
-        provide_context(Arc::clone(&hook));
-        provide_context(suspended_children.clone());
+        shadow_context(Arc::clone(&hook));
+        shadow_context(suspended_children.clone());
         children.into_inner()()
     });
 
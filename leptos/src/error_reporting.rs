@@ -0,0 +1,271 @@
+//! [`set_error_hook`](crate::error_reporting::set_error_hook) is called with every error that the
+//! framework sees, along with where it came from and the components it was raised in:
+//! - the errors that are rendered, whether or not an [`ErrorBoundary`](crate::error::ErrorBoundary)
+//!   catches them, like those of resources that have failed,
+//! - the errors that server functions return to the client,
+//! - and panics, like those in effects.
+//!
+//! ```rust
+//! use leptos::error_reporting::set_error_hook;
+//!
+//! set_error_hook(|err, context| {
+//!     // send it to an error tracking service instead
+//!     eprintln!(
+//!         "{} in {}: {err}",
+//!         context.source,
+//!         context.components.join(" < ")
+//!     );
+//! });
+//! ```
+//!
+//! This is unrelated to the thread-local error hook of [`throw_error`], which handles errors
+//! rather than reporting them, and which error boundaries set.
+//!
+//! [`set_panic_hook`](crate::error_reporting::set_panic_hook) logs panics to the console, like
+//! `console_error_panic_hook`, along with the components and the effect or memo that panicked,
+//! which a panic in WebAssembly otherwise gives no clue about:
+//!
+//! ```text
+//! panicked at src/counter.rs:12:9: attempt to subtract with overflow
+//! while running the effect defined at src/counter.rs:10:5
+//! in <Counter/>
+//! in <App/>
+//!
+//! Stack:
+//! ...
+//! ```
+
+use reactive_graph::{diagnostics::RunningNode, owner::Owner};
+use std::{
+    any::Any,
+    fmt::{self, Display, Write},
+    panic::{self, Location},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Once,
+    },
+};
+pub use throw_error::ErrorSource;
+use throw_error::{report, set_reporter, Error};
+use web_sys::js_sys::{self, Reflect};
+
+/// Where an error reported to the hook set with [`set_error_hook`] was raised.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct ErrorContext {
+    /// What kind of error this is.
+    pub source: ErrorSource,
+    /// The reactive owner that was current when the error was raised, if any.
+    pub owner: Option<Owner>,
+    /// The names of the components the error was raised in, from the innermost one to the
+    /// outermost one. See [`Owner::component_stack`]; this is empty in release mode.
+    pub components: Vec<&'static str>,
+    /// The effect or memo that was running when the error was raised, if any. See
+    /// [`running_node`](reactive_graph::diagnostics::running_node); this is `None` in release
+    /// mode.
+    pub running: Option<RunningNode>,
+}
+
+impl ErrorContext {
+    fn current(source: ErrorSource) -> Self {
+        let owner = Owner::current();
+        Self {
+            source,
+            components: owner
+                .as_ref()
+                .map(Owner::component_stack)
+                .unwrap_or_default(),
+            owner,
+            running: reactive_graph::diagnostics::running_node(),
+        }
+    }
+}
+
+/// A panic, as reported to the hook set with [`set_error_hook`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Panicked {
+    /// The panic message.
+    pub message: String,
+    /// The file, line and column the panic happened at.
+    pub location: Option<String>,
+}
+
+impl Display for Panicked {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.location {
+            Some(location) => {
+                write!(f, "panicked at {location}: {}", self.message)
+            }
+            None => write!(f, "panicked: {}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for Panicked {}
+
+impl Panicked {
+    fn new(payload: &(dyn Any + Send), location: Option<&Location>) -> Self {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "Box<dyn Any>".to_string());
+        Self {
+            message,
+            location: location.map(ToString::to_string),
+        }
+    }
+}
+
+/// Sets the global error hook, which is called with every error that is rendered, every error
+/// returned by a server function on the client, and every panic, replacing the previous hook.
+///
+/// The hook runs on the thread where the error was raised. Panics are reported to it after the
+/// panic hook that was set before the first call to this, like that of
+/// `console_error_panic_hook`, or the one set with [`set_panic_hook`], has run.
+pub fn set_error_hook(
+    hook: impl Fn(&Error, &ErrorContext) + Send + Sync + 'static,
+) {
+    set_reporter(move |err, source| hook(err, &ErrorContext::current(source)));
+    install_panic_hook();
+}
+
+/// Sets a panic hook that logs panics to the console in the browser, or to `stderr` on the
+/// server, along with the components that panicked and the effect or memo that was running.
+///
+/// In the browser, the JavaScript stack is logged too, as with `console_error_panic_hook`, which
+/// this replaces. The components and the effect or memo are only known in debug mode.
+pub fn set_panic_hook() {
+    LOG_PANICS.store(true, Ordering::Relaxed);
+    install_panic_hook();
+}
+
+static LOG_PANICS: AtomicBool = AtomicBool::new(false);
+
+/// Wraps the current panic hook once, to report panics to the error hook, and to log them with
+/// their context after [`set_panic_hook`].
+fn install_panic_hook() {
+    static PANIC_HOOK: Once = Once::new();
+
+    PANIC_HOOK.call_once(|| {
+        let prev = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if LOG_PANICS.load(Ordering::Relaxed) {
+                let panicked = Panicked::new(info.payload(), info.location());
+                leptos_dom::logging::console_error(&describe_panic(&panicked));
+            } else {
+                prev(info);
+            }
+            report(ErrorSource::Panic, || {
+                Panicked::new(info.payload(), info.location()).into()
+            });
+        }));
+    });
+}
+
+fn describe_panic(panicked: &Panicked) -> String {
+    let mut message = panicked.to_string();
+    if let Some(running) = reactive_graph::diagnostics::running_node() {
+        _ = write!(message, "\nwhile running {running}");
+    }
+    if let Some(owner) = Owner::current() {
+        for component in owner.component_stack() {
+            _ = write!(message, "\nin <{component}/>");
+        }
+    }
+    if cfg!(all(
+        target_arch = "wasm32",
+        not(any(target_os = "emscripten", target_os = "wasi"))
+    )) {
+        // the stack of the WebAssembly frames, as `console_error_panic_hook` shows it
+        let stack = Reflect::get(&js_sys::Error::new(""), &"stack".into())
+            .ok()
+            .and_then(|stack| stack.as_string())
+            .unwrap_or_default();
+        _ = write!(message, "\n\nStack:\n\n{stack}");
+    }
+    message
+}
+
+/// Removes the hook set with [`set_error_hook`].
+pub fn clear_error_hook() {
+    throw_error::clear_reporter();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reactive_graph::owner::enter_component;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn errors_are_reported_with_their_components() {
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        set_error_hook({
+            let reported = Arc::clone(&reported);
+            move |err, context| {
+                reported.lock().unwrap().push((
+                    err.to_string(),
+                    context.source,
+                    context.components.clone(),
+                ))
+            }
+        });
+
+        let owner = Owner::new();
+        owner.with(|| {
+            let _app = enter_component("App");
+            let child = Owner::new();
+            let _counter = enter_component("Counter");
+            child.with(|| {
+                Owner::new().with(|| {
+                    throw_error::throw(Panicked {
+                        message: "oops".into(),
+                        location: None,
+                    })
+                })
+            });
+        });
+        _ = panic::catch_unwind(|| panic!("boom"));
+        clear_error_hook();
+        throw_error::throw(Panicked {
+            message: "unreported".into(),
+            location: None,
+        });
+
+        // other tests can raise errors while the hook is set
+        let reported = reported.lock().unwrap();
+        assert!(reported.contains(&(
+            "panicked: oops".to_string(),
+            ErrorSource::Thrown { caught: false },
+            vec!["Counter", "App"]
+        )));
+        assert!(reported.iter().any(|(message, source, _)| {
+            message.ends_with("boom") && *source == ErrorSource::Panic
+        }));
+        assert!(!reported
+            .iter()
+            .any(|(message, _, _)| message.contains("unreported")));
+    }
+
+    #[test]
+    fn panics_are_described_with_the_running_node() {
+        use reactive_graph::{computed::Memo, traits::Get};
+
+        let owner = Owner::new();
+        owner.set();
+        let memo = {
+            let _component = enter_component("Counter");
+            Memo::new(|_| {
+                describe_panic(&Panicked {
+                    message: "oops".into(),
+                    location: None,
+                })
+            })
+        };
+        let description = memo.get();
+        assert!(description
+            .starts_with("panicked: oops\nwhile running the memo defined at"));
+        assert!(description.ends_with("\nin <Counter/>"));
+    }
+}
@@ -2,12 +2,14 @@ use crate::{children::Children, component, prelude::*, IntoView};
 use leptos_dom::helpers::window;
 use leptos_server::{ServerAction, ServerMultiAction};
 use serde::de::DeserializeOwned;
+#[cfg(feature = "multipart")]
+use server_fn::codec::{MultipartData, MultipartFormData};
 use server_fn::{
     client::Client,
     codec::PostUrl,
     error::{IntoAppError, ServerFnErrorErr},
     request::ClientReq,
-    Http, ServerFn,
+    ActionInput, Http, ServerFn,
 };
 use tachys::{
     either::Either,
@@ -15,27 +17,123 @@ use tachys::{
         element::{form, Form},
         event::submit,
     },
-    reactive_graph::node_ref::NodeRef,
+    reactive_graph::{node_ref::NodeRef, OwnedView},
 };
 use thiserror::Error;
 use wasm_bindgen::{JsCast, JsValue, UnwrapThrowExt};
 use web_sys::{
-    Event, FormData, HtmlButtonElement, HtmlFormElement, HtmlInputElement,
-    SubmitEvent,
+    Event, File, FormData, HtmlButtonElement, HtmlFormElement,
+    HtmlInputElement, SubmitEvent, UrlSearchParams,
 };
 
+mod group;
+mod input;
+mod model;
+pub use group::*;
+pub use input::*;
+pub use model::*;
+
 /// Automatically turns a server [Action](leptos_server::Action) into an HTML
 /// [`form`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/form)
 /// progressively enhanced to use client-side routing.
 ///
 /// ## Encoding
 /// **Note:** `<ActionForm/>` only works with server functions that use the
-/// default `Url` encoding. This is to ensure that `<ActionForm/>` works correctly
-/// both before and after WASM has loaded.
+/// default `Url` encoding, or the `MultipartFormData` encoding (with the `multipart`
+/// feature). This is to ensure that `<ActionForm/>` works correctly both before and
+/// after WASM has loaded.
+///
+/// ## File Uploads
+/// To upload files with `<input type="file">`, use a server function with the
+/// `MultipartFormData` input encoding. The form is then given
+/// `enctype="multipart/form-data"`, so that the browser uploads the files when it
+/// submits the form before WASM has loaded, and the whole form, including its files, is
+/// sent to the server function after it has loaded.
+///
+/// ```rust,ignore
+/// use leptos::{form::ActionForm, prelude::*};
+/// use server_fn::codec::{MultipartData, MultipartFormData};
+///
+/// #[server(input = MultipartFormData)]
+/// async fn upload_avatar(data: MultipartData) -> Result<(), ServerFnError> {
+///     let mut data = data.into_inner().unwrap();
+///     while let Ok(Some(field)) = data.next_field().await {
+///         // save the file...
+///     }
+///     Ok(())
+/// }
+///
+/// #[component]
+/// fn AvatarForm() -> impl IntoView {
+///     let upload = ServerAction::<UploadAvatar>::new();
+///
+///     view! {
+///       <ActionForm action=upload>
+///         <input type="file" name="avatar" accept="image/*"/>
+///         <input type="submit"/>
+///       </ActionForm>
+///     }
+/// }
+/// ```
+///
+/// Server function actions can't report how much of the upload has been sent; to show the
+/// progress of an upload, use the router's `<Form/>` with its `on_upload_progress` callback.
+///
+/// ## Pending Input and Errors
+/// The children of an `<ActionForm/>`, and any component inside it, can access the state of its
+/// action with [`use_action_form`]: the input of a submission that is in progress, to render
+/// the result optimistically before the server responds, and the error the server returned.
+/// When the server function returns a [`FormError`] (or another error that implements
+/// [`HasFieldErrors`]), each field's error can be shown next to it.
+///
+/// ```rust
+/// # use leptos::prelude::*;
+/// use leptos::form::{use_action_form, ActionForm};
+///
+/// #[server]
+/// async fn add_todo(title: String) -> Result<(), FormError> {
+///     if title.is_empty() {
+///         return Err(FieldErrors::new().with("title", "Enter a title.").into());
+///     }
+///     // save the todo...
+///     Ok(())
+/// }
+///
+/// #[component]
+/// fn NewTodo() -> impl IntoView {
+///     let add_todo = ServerAction::<AddTodo>::new();
+///
+///     view! {
+///       <ActionForm action=add_todo>
+///         <input type="text" name="title"/>
+///         <TitleError/>
+///         <input type="submit"/>
+///         <PendingTodo/>
+///       </ActionForm>
+///     }
+/// }
+///
+/// #[component]
+/// fn TitleError() -> impl IntoView {
+///     let form = use_action_form::<AddTodo>().unwrap();
+///     move || form.field_error("title").map(|error| view! { <p class="error">{error}</p> })
+/// }
+///
+/// #[component]
+/// fn PendingTodo() -> impl IntoView {
+///     let form = use_action_form::<AddTodo>().unwrap();
+///     move || form.pending_input().map(|todo| view! { <li class="pending">{todo.title}</li> })
+/// }
+/// ```
 ///
 /// ## Complex Inputs
-/// Server function arguments that are structs with nested serializable fields
-/// should make use of indexing notation of `serde_qs`.
+/// Server function arguments that are structs with nested serializable fields, sequences, and
+/// options are filled in from bracketed field names, both before and after WASM has loaded
+/// (see [`from_form_str`](server_fn::codec::from_form_str) for the details):
+/// `hefty_arg[first_name]` is a field of a struct, `tags[]` or `tags[0]` is an item of a
+/// sequence, and a name that is repeated, like the checkboxes of a group, is a sequence too.
+/// Since browsers leave unchecked checkboxes out of the form, `bool` and `Vec` fields that they
+/// fill in should have `#[serde(default)]`.
 ///
 /// ```rust
 /// # use leptos::prelude::*;
@@ -45,6 +143,9 @@ use web_sys::{
 /// struct HeftyData {
 ///     first_name: String,
 ///     last_name: String,
+///     nickname: Option<String>,
+///     #[serde(default)]
+///     languages: Vec<String>,
 /// }
 ///
 /// #[component]
@@ -59,6 +160,16 @@ use web_sys::{
 ///           name="hefty_arg[last_name]"
 ///           value="closures-everywhere"
 ///         />
+///         // left empty, this is `None`
+///         <input type="text" name="hefty_arg[nickname]"/>
+///         <label>
+///           <input type="checkbox" name="hefty_arg[languages]" value="rust" checked/>
+///           "Rust"
+///         </label>
+///         <label>
+///           <input type="checkbox" name="hefty_arg[languages]" value="js"/>
+///           "JavaScript"
+///         </label>
 ///         <input type="submit"/>
 ///       </ActionForm>
 ///     }
@@ -85,12 +196,8 @@ pub fn ActionForm<ServFn, OutputProtocol>(
     children: Children,
 ) -> impl IntoView
 where
-    ServFn: DeserializeOwned
-        + ServerFn<Protocol = Http<PostUrl, OutputProtocol>>
-        + Clone
-        + Send
-        + Sync
-        + 'static,
+    ServFn: ServerFn + ActionInput + Send + Sync + 'static,
+    ServFn::Protocol: ActionFormProtocol<ServFn, OutputProtocol>,
     <<ServFn::Client as Client<ServFn::Error>>::Request as ClientReq<
         ServFn::Error,
     >>::FormData: From<FormData>,
@@ -109,6 +216,15 @@ where
     let version = action.version();
     let value = action.value();
 
+    // the state is only provided to the children, so that sibling forms don't shadow each other
+    let owner = Owner::current()
+        .expect("no current reactive Owner found")
+        .child();
+    let children = owner.with(|| {
+        provide_context(ActionFormState { action });
+        children()
+    });
+
     let on_submit = {
         move |ev: SubmitEvent| {
             if ev.default_prevented() {
@@ -117,7 +233,11 @@ where
 
             ev.prevent_default();
 
-            match ServFn::from_event(&ev) {
+            match <ServFn::Protocol as ActionFormProtocol<
+                ServFn,
+                OutputProtocol,
+            >>::Encoding::from_submit_event(&ev)
+            {
                 Ok(new_input) => {
                     action.dispatch(new_input);
                 }
@@ -136,11 +256,16 @@ where
         }
     };
 
+    let enctype = <ServFn::Protocol as ActionFormProtocol<
+        ServFn,
+        OutputProtocol,
+    >>::Encoding::ENCTYPE;
     let action_form = form()
-        .action(ServFn::url())
+        .action(server_fn::prefix::mounted_path(ServFn::url()).into_owned())
         .method("post")
+        .enctype(enctype)
         .on(submit, on_submit)
-        .child(children());
+        .child(OwnedView::new_with_owner(children, owner));
     if let Some(node_ref) = node_ref {
         Either::Left(action_form.node_ref(node_ref))
     } else {
@@ -148,6 +273,95 @@ where
     }
 }
 
+/// The state of the action of the nearest enclosing [`ActionForm`], which can be accessed by its
+/// children with [`use_action_form`].
+pub struct ActionFormState<ServFn>
+where
+    ServFn: ServerFn + 'static,
+{
+    action: ServerAction<ServFn>,
+}
+
+impl<ServFn> Clone for ActionFormState<ServFn>
+where
+    ServFn: ServerFn + 'static,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<ServFn> Copy for ActionFormState<ServFn> where ServFn: ServerFn + 'static {}
+
+impl<ServFn> ActionFormState<ServFn>
+where
+    ServFn: ServerFn + ActionInput + Send + Sync + 'static,
+    ServFn::Output: Send + Sync + 'static,
+    ServFn::Error: Send + Sync + 'static,
+{
+    /// The action the form dispatches.
+    pub fn action(&self) -> ServerAction<ServFn> {
+        self.action
+    }
+
+    /// Whether a submission is in progress.
+    pub fn pending(&self) -> bool {
+        self.action.pending().get()
+    }
+
+    /// The input of the submission that is in progress, which can be used to render its result
+    /// optimistically before the server responds.
+    pub fn pending_input(&self) -> Option<ServFn>
+    where
+        ServFn: Clone,
+    {
+        self.action.input().get()
+    }
+
+    /// The error returned by the latest submission, if it failed.
+    pub fn error(&self) -> Option<ServFn::Error>
+    where
+        ServFn::Error: Clone,
+    {
+        self.action
+            .value()
+            .with(|value| value.as_ref()?.as_ref().err().cloned())
+    }
+
+    /// The error for the field with the given name, if the latest submission failed with
+    /// errors for individual fields.
+    pub fn field_error(&self, name: &str) -> Option<String>
+    where
+        ServFn::Error: HasFieldErrors,
+    {
+        self.action.value().with(|value| {
+            let error = value.as_ref()?.as_ref().err()?;
+            error.field_errors()?.get(name).map(ToOwned::to_owned)
+        })
+    }
+
+    /// The error message for the form as a whole, if the latest submission failed with an error
+    /// that isn't for individual fields.
+    pub fn form_error(&self) -> Option<String>
+    where
+        ServFn::Error: HasFieldErrors,
+    {
+        self.action.value().with(|value| {
+            let error = value.as_ref()?.as_ref().err()?;
+            error.field_errors().is_none().then(|| error.to_string())
+        })
+    }
+}
+
+/// Returns the state of the nearest enclosing [`ActionForm`] for the server function
+/// `ServFn`, or `None` if there is no such form.
+pub fn use_action_form<ServFn>() -> Option<ActionFormState<ServFn>>
+where
+    ServFn: ServerFn + 'static,
+{
+    use_context()
+}
+
 /// Automatically turns a server [MultiAction](leptos_server::MultiAction) into an HTML
 /// [`form`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/form)
 /// progressively enhanced to use client-side routing.
@@ -162,12 +376,8 @@ pub fn MultiActionForm<ServFn, OutputProtocol>(
     children: Children,
 ) -> impl IntoView
 where
-    ServFn: Send
-        + Sync
-        + Clone
-        + DeserializeOwned
-        + ServerFn<Protocol = Http<PostUrl, OutputProtocol>>
-        + 'static,
+    ServFn: Send + Sync + ActionInput + ServerFn + 'static,
+    ServFn::Protocol: ActionFormProtocol<ServFn, OutputProtocol>,
     ServFn::Output: Send + Sync + 'static,
     <<ServFn::Client as Client<ServFn::Error>>::Request as ClientReq<
         ServFn::Error,
@@ -189,7 +399,11 @@ where
 
         ev.prevent_default();
 
-        match ServFn::from_event(&ev) {
+        match <ServFn::Protocol as ActionFormProtocol<
+            ServFn,
+            OutputProtocol,
+        >>::Encoding::from_submit_event(&ev)
+        {
             Ok(new_input) => {
                 action.dispatch(new_input);
             }
@@ -202,10 +416,15 @@ where
         }
     };
 
+    let enctype = <ServFn::Protocol as ActionFormProtocol<
+        ServFn,
+        OutputProtocol,
+    >>::Encoding::ENCTYPE;
     let action_form = form()
-        .action(ServFn::url())
+        .action(server_fn::prefix::mounted_path(ServFn::url()).into_owned())
         .method("post")
         .attr("method", "post")
+        .enctype(enctype)
         .on(submit, on_submit)
         .child(children());
     if let Some(node_ref) = node_ref {
@@ -215,6 +434,57 @@ where
     }
 }
 
+/// An input encoding that can be submitted by an [`ActionForm`] or [`MultiActionForm`], both
+/// natively by the browser before WASM has loaded, and by the form's `submit` handler after it
+/// has loaded.
+pub trait ActionFormEncoding<T>: Sized {
+    /// The `enctype` of the `<form>`, if it differs from the browser's default
+    /// (`application/x-www-form-urlencoded`).
+    const ENCTYPE: Option<&'static str>;
+
+    /// Creates the server function's input from the form that is being submitted.
+    fn from_submit_event(ev: &SubmitEvent) -> Result<T, FromFormDataError>;
+}
+
+/// The protocol of a server function that can be used with an [`ActionForm`] or
+/// [`MultiActionForm`]: [`Http`], with an input encoding that implements
+/// [`ActionFormEncoding`].
+pub trait ActionFormProtocol<T, OutputProtocol> {
+    /// The encoding of the server function's input.
+    type Encoding: ActionFormEncoding<T>;
+}
+
+impl<T, InputProtocol, OutputProtocol> ActionFormProtocol<T, OutputProtocol>
+    for Http<InputProtocol, OutputProtocol>
+where
+    InputProtocol: ActionFormEncoding<T>,
+{
+    type Encoding = InputProtocol;
+}
+
+impl<T> ActionFormEncoding<T> for PostUrl
+where
+    T: DeserializeOwned,
+{
+    const ENCTYPE: Option<&'static str> = None;
+
+    fn from_submit_event(ev: &SubmitEvent) -> Result<T, FromFormDataError> {
+        T::from_event(ev)
+    }
+}
+
+#[cfg(feature = "multipart")]
+impl<T> ActionFormEncoding<T> for MultipartFormData
+where
+    T: From<MultipartData>,
+{
+    const ENCTYPE: Option<&'static str> = Some("multipart/form-data");
+
+    fn from_submit_event(ev: &SubmitEvent) -> Result<T, FromFormDataError> {
+        form_data_from_event(ev).map(|data| MultipartData::from(data).into())
+    }
+}
+
 /// Resolves a redirect location to an (absolute) URL.
 pub(crate) fn resolve_redirect_url(loc: &str) -> Option<web_sys::Url> {
     let origin = match window().location().origin() {
@@ -283,11 +553,9 @@ where
     fn from_form_data(
         form_data: &web_sys::FormData,
     ) -> Result<Self, serde_qs::Error> {
-        let data =
-            web_sys::UrlSearchParams::new_with_str_sequence_sequence(form_data)
-                .unwrap_throw();
+        let data = url_params_from_form_data(form_data);
         let data = data.to_string().as_string().unwrap_or_default();
-        serde_qs::Config::new(5, false).deserialize_str::<Self>(&data)
+        server_fn::codec::from_form_str(&data)
     }
 }
 
@@ -325,3 +593,20 @@ fn form_data_from_event(
         }
     }
 }
+
+/// Converts form data into URL search params, in the same way as the browser does when it
+/// submits a form that is not `multipart/form-data`: each file is replaced by its name.
+pub fn url_params_from_form_data(form_data: &FormData) -> UrlSearchParams {
+    let params = UrlSearchParams::new().unwrap_throw();
+    for entry in form_data.entries() {
+        let entry = web_sys::js_sys::Array::from(&entry.unwrap_throw());
+        let name = entry.get(0).as_string().unwrap_or_default();
+        let value = entry.get(1);
+        let value = match value.dyn_ref::<File>() {
+            Some(file) => file.name(),
+            None => value.as_string().unwrap_or_default(),
+        };
+        params.append(&name, &value);
+    }
+    params
+}
@@ -0,0 +1,236 @@
+use reactive_graph::{
+    signal::RwSignal,
+    traits::{Set, Update, With},
+};
+use std::{
+    borrow::Cow,
+    fmt::{self, Debug},
+};
+use tachys::{
+    dom::event_target_checked,
+    html::{
+        attribute::{custom::custom_attribute, Attribute},
+        event::{change, on},
+        property::prop,
+    },
+};
+
+/// A value that can be chosen with a checkbox or radio input, which is stored in the input's
+/// `value` attribute.
+///
+/// This is implemented for strings, characters, and integers. For other types, such as an enum
+/// of options, implement it by hand:
+///
+/// ```rust
+/// use leptos::form::GroupValue;
+/// use std::borrow::Cow;
+///
+/// #[derive(Clone, PartialEq)]
+/// enum Size {
+///     Small,
+///     Large,
+/// }
+///
+/// impl GroupValue for Size {
+///     fn to_value(&self) -> Cow<'_, str> {
+///         match self {
+///             Size::Small => "small".into(),
+///             Size::Large => "large".into(),
+///         }
+///     }
+/// }
+/// ```
+pub trait GroupValue: Clone + PartialEq + Send + Sync + 'static {
+    /// The text of the input's `value` attribute, which is submitted with the form.
+    fn to_value(&self) -> Cow<'_, str>;
+}
+
+impl GroupValue for String {
+    fn to_value(&self) -> Cow<'_, str> {
+        Cow::Borrowed(self)
+    }
+}
+
+macro_rules! group_value_to_string {
+    ($($ty:ty),*) => {
+        $(
+            impl GroupValue for $ty {
+                fn to_value(&self) -> Cow<'_, str> {
+                    Cow::Owned(self.to_string())
+                }
+            }
+        )*
+    };
+}
+
+group_value_to_string!(
+    char, u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+);
+
+/// Binds a group of checkboxes that share a `name` to a signal holding the values that are
+/// checked.
+///
+/// Each checkbox is given its attributes with [`CheckboxGroup::option`]. They are checked in
+/// the server-rendered HTML as well as in the browser, and checking or unchecking one adds its
+/// value to or removes it from the signal.
+///
+/// ```rust
+/// # use leptos::prelude::*;
+/// use leptos::form::CheckboxGroup;
+///
+/// #[component]
+/// fn Toppings() -> impl IntoView {
+///     let toppings = RwSignal::new(vec!["cheese".to_string()]);
+///     let group = CheckboxGroup::new("toppings", toppings);
+///
+///     view! {
+///       <label><input type="checkbox" {..group.option("cheese".to_string())}/>"Cheese"</label>
+///       <label><input type="checkbox" {..group.option("olives".to_string())}/>"Olives"</label>
+///       <p>{move || toppings.get().join(", ")}</p>
+///     }
+/// }
+/// ```
+pub struct CheckboxGroup<T: 'static> {
+    name: &'static str,
+    values: RwSignal<Vec<T>>,
+}
+
+impl<T> Clone for CheckboxGroup<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for CheckboxGroup<T> {}
+
+impl<T> Debug for CheckboxGroup<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CheckboxGroup")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: GroupValue> CheckboxGroup<T> {
+    /// Creates a group with the given `name`, bound to a signal with the checked values.
+    pub fn new(name: &'static str, values: RwSignal<Vec<T>>) -> Self {
+        Self { name, values }
+    }
+
+    /// The signal that holds the checked values, in the order they were checked.
+    pub fn values(&self) -> RwSignal<Vec<T>> {
+        self.values
+    }
+
+    /// The attributes for the checkbox for `value`: its `name`, `value`, whether it is checked,
+    /// and the listener that updates the signal.
+    ///
+    /// Spread them onto the element with `<input type="checkbox" {..group.option(value)}/>`.
+    pub fn option(self, value: T) -> impl Attribute {
+        let values = self.values;
+        let attr_value = value.to_value().into_owned();
+        let checked = {
+            let value = value.clone();
+            move || values.with(|values| values.contains(&value))
+        };
+        (
+            custom_attribute("name", self.name),
+            custom_attribute("value", attr_value),
+            custom_attribute("checked", checked.clone()),
+            prop("checked", checked),
+            on(change, move |ev| {
+                let checked = event_target_checked(&ev);
+                values.update(|values| {
+                    let position = values.iter().position(|v| *v == value);
+                    match (checked, position) {
+                        (true, None) => values.push(value.clone()),
+                        (false, Some(position)) => {
+                            values.remove(position);
+                        }
+                        _ => {}
+                    }
+                });
+            }),
+        )
+    }
+}
+
+/// Binds a group of radio inputs that share a `name` to a signal holding the value that is
+/// selected.
+///
+/// Each radio input is given its attributes with [`RadioGroup::option`]. The selected one is
+/// checked in the server-rendered HTML as well as in the browser, and selecting one sets the
+/// signal to its value.
+///
+/// ```rust
+/// # use leptos::prelude::*;
+/// use leptos::form::RadioGroup;
+///
+/// #[component]
+/// fn Quantity() -> impl IntoView {
+///     let quantity = RwSignal::new(1u32);
+///     let group = RadioGroup::new("quantity", quantity);
+///
+///     view! {
+///       <label><input type="radio" {..group.option(1)}/>"One"</label>
+///       <label><input type="radio" {..group.option(2)}/>"Two"</label>
+///       <p>{move || quantity.get()}</p>
+///     }
+/// }
+/// ```
+pub struct RadioGroup<T: 'static> {
+    name: &'static str,
+    value: RwSignal<T>,
+}
+
+impl<T> Clone for RadioGroup<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for RadioGroup<T> {}
+
+impl<T> Debug for RadioGroup<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RadioGroup")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: GroupValue> RadioGroup<T> {
+    /// Creates a group with the given `name`, bound to a signal with the selected value.
+    pub fn new(name: &'static str, value: RwSignal<T>) -> Self {
+        Self { name, value }
+    }
+
+    /// The signal that holds the selected value.
+    pub fn value(&self) -> RwSignal<T> {
+        self.value
+    }
+
+    /// The attributes for the radio input for `value`: its `name`, `value`, whether it is
+    /// checked, and the listener that updates the signal.
+    ///
+    /// Spread them onto the element with `<input type="radio" {..group.option(value)}/>`.
+    pub fn option(self, value: T) -> impl Attribute {
+        let selected = self.value;
+        let attr_value = value.to_value().into_owned();
+        let checked = {
+            let value = value.clone();
+            move || selected.with(|selected| *selected == value)
+        };
+        (
+            custom_attribute("name", self.name),
+            custom_attribute("value", attr_value),
+            custom_attribute("checked", checked.clone()),
+            prop("checked", checked),
+            on(change, move |ev| {
+                if event_target_checked(&ev) {
+                    selected.set(value.clone());
+                }
+            }),
+        )
+    }
+}
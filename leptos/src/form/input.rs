@@ -0,0 +1,414 @@
+use reactive_graph::{
+    owner::StoredValue,
+    signal::RwSignal,
+    traits::{Get, GetUntracked, Set, With, WithValue},
+};
+use std::{
+    fmt::{self, Debug, Display},
+    marker::PhantomData,
+    str::FromStr,
+};
+use tachys::{
+    dom::event_target_value,
+    html::{
+        attribute::{custom::custom_attribute, Attribute},
+        event::{input, on},
+        property::prop,
+    },
+};
+
+/// Converts between the text of an `<input>` and a typed value.
+pub trait InputAdapter: Send + Sync + 'static {
+    /// The typed value.
+    type Value: Clone + PartialEq + Send + Sync + 'static;
+
+    /// Parses the text of the input, returning an error message if it is invalid.
+    fn parse(&self, text: &str) -> Result<Self::Value, String>;
+
+    /// Formats a value as the text of the input.
+    fn format(&self, value: &Self::Value) -> String;
+
+    /// Cleans up the text as the user types, before it is parsed. By default, the text is left
+    /// as it is.
+    fn normalize(&self, text: &str) -> String {
+        text.to_owned()
+    }
+}
+
+/// An `<input>` bound to a typed signal through an [`InputAdapter`].
+///
+/// While the user types, the text they have entered is kept as it is, and the signal is updated
+/// whenever the text can be parsed. When it can't, the signal keeps its last valid value and
+/// [`error`](TypedInput::error) returns the reason, so that the input can be marked invalid.
+/// When the signal is set from elsewhere, the input shows the new value.
+///
+/// ```rust
+/// # use leptos::prelude::*;
+/// use leptos::form::{value_as, Mask, TypedInput};
+///
+/// #[component]
+/// fn Payment() -> impl IntoView {
+///     let amount = RwSignal::new(0.0);
+///     let amount_input = value_as::<f64>(amount);
+///     let phone = RwSignal::new(String::new());
+///     let phone_input = TypedInput::new(phone, Mask::new("(###) ###-####"));
+///
+///     view! {
+///       <input type="number" step="0.01" {..amount_input.attrs()}/>
+///       <p class="error">{move || amount_input.error()}</p>
+///       <input type="tel" {..phone_input.attrs()}/>
+///       <p>"Total: " {move || amount.get() * 1.2}</p>
+///     }
+/// }
+/// ```
+pub struct TypedInput<A: InputAdapter> {
+    value: RwSignal<A::Value>,
+    adapter: StoredValue<A>,
+    text: RwSignal<String>,
+    // the value that `text` was last parsed as, so that a value set from elsewhere is noticed
+    parsed: RwSignal<A::Value>,
+    error: RwSignal<Option<String>>,
+}
+
+impl<A: InputAdapter> Clone for TypedInput<A> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<A: InputAdapter> Copy for TypedInput<A> {}
+
+impl<A: InputAdapter> Debug for TypedInput<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TypedInput").finish_non_exhaustive()
+    }
+}
+
+impl<A: InputAdapter> TypedInput<A> {
+    /// Binds `value` to an input with the given adapter.
+    pub fn new(value: RwSignal<A::Value>, adapter: A) -> Self {
+        let current = value.get_untracked();
+        let text = adapter.format(&current);
+        Self {
+            value,
+            adapter: StoredValue::new(adapter),
+            text: RwSignal::new(text),
+            parsed: RwSignal::new(current),
+            error: RwSignal::new(None),
+        }
+    }
+
+    /// The signal holding the typed value.
+    pub fn value(&self) -> RwSignal<A::Value> {
+        self.value
+    }
+
+    /// The text shown in the input, subscribing to changes in it.
+    pub fn text(&self) -> String {
+        let value = self.value.get();
+        if self.parsed.with(|parsed| *parsed == value) {
+            self.text.get()
+        } else {
+            self.adapter.with_value(|adapter| adapter.format(&value))
+        }
+    }
+
+    /// Why the text in the input can't be parsed, if it can't.
+    pub fn error(&self) -> Option<String> {
+        self.error.get()
+    }
+
+    /// Whether the text in the input can't be parsed.
+    pub fn is_invalid(&self) -> bool {
+        self.error.with(Option::is_some)
+    }
+
+    /// Handles the user changing the text of the input.
+    pub fn set_text(&self, text: &str) {
+        let text = self.adapter.with_value(|adapter| adapter.normalize(text));
+        match self.adapter.with_value(|adapter| adapter.parse(&text)) {
+            Ok(value) => {
+                self.parsed.set(value.clone());
+                self.value.set(value);
+                self.error.set(None);
+            }
+            Err(e) => {
+                // the value that is shown is still the one that was typed
+                self.parsed.set(self.value.get_untracked());
+                self.error.set(Some(e));
+            }
+        }
+        self.text.set(text);
+    }
+
+    /// The attributes that bind an `<input>` to the signal: its value, `aria-invalid` while the
+    /// text can't be parsed, and the listener that parses the text as the user types.
+    ///
+    /// Spread them onto the element with `<input {..typed_input.attrs()}/>`.
+    pub fn attrs(self) -> impl Attribute {
+        let text = move || self.text();
+        (
+            custom_attribute("value", text),
+            prop("value", text),
+            custom_attribute("aria-invalid", move || {
+                self.is_invalid().then_some("true")
+            }),
+            on(input, move |ev| self.set_text(&event_target_value(&ev))),
+        )
+    }
+}
+
+/// Binds a signal to an input whose text is parsed with [`FromStr`] and formatted with
+/// [`Display`], such as a number input.
+pub fn value_as<T>(value: RwSignal<T>) -> TypedInput<ValueAs<T>>
+where
+    T: FromStr + Display + Clone + PartialEq + Send + Sync + 'static,
+    T::Err: Display,
+{
+    TypedInput::new(value, ValueAs::default())
+}
+
+/// An [`InputAdapter`] that parses the text with [`FromStr`] and formats the value with
+/// [`Display`].
+pub struct ValueAs<T>(PhantomData<fn() -> T>);
+
+impl<T> Default for ValueAs<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T> Debug for ValueAs<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ValueAs")
+    }
+}
+
+impl<T> InputAdapter for ValueAs<T>
+where
+    T: FromStr + Display + Clone + PartialEq + Send + Sync + 'static,
+    T::Err: Display,
+{
+    type Value = T;
+
+    fn parse(&self, text: &str) -> Result<T, String> {
+        text.trim().parse().map_err(|e: T::Err| e.to_string())
+    }
+
+    fn format(&self, value: &T) -> String {
+        value.to_string()
+    }
+}
+
+/// An [`InputAdapter`] for inputs that may be left empty, which are `None` when they are.
+#[derive(Debug, Default)]
+pub struct Optional<A>(pub A);
+
+impl<A: InputAdapter> InputAdapter for Optional<A> {
+    type Value = Option<A::Value>;
+
+    fn parse(&self, text: &str) -> Result<Self::Value, String> {
+        if text.trim().is_empty() {
+            Ok(None)
+        } else {
+            self.0.parse(text).map(Some)
+        }
+    }
+
+    fn format(&self, value: &Self::Value) -> String {
+        value
+            .as_ref()
+            .map(|value| self.0.format(value))
+            .unwrap_or_default()
+    }
+
+    fn normalize(&self, text: &str) -> String {
+        self.0.normalize(text)
+    }
+}
+
+/// An [`InputAdapter`] that formats text with a pattern as the user types, such as
+/// `"(###) ###-####"` for a phone number.
+///
+/// In the pattern, `#` stands for a digit, `A` for a letter, and `*` for a letter or a digit;
+/// any other character is inserted as it is. The value is the text that was entered in those
+/// places, without the inserted characters, and it is invalid until every place is filled (or
+/// the input is empty).
+#[derive(Debug, Clone)]
+pub struct Mask {
+    pattern: &'static str,
+}
+
+impl Mask {
+    /// Creates a mask with the given pattern.
+    pub fn new(pattern: &'static str) -> Self {
+        Self { pattern }
+    }
+
+    fn accepts(slot: char, c: char) -> Option<bool> {
+        match slot {
+            '#' => Some(c.is_ascii_digit()),
+            'A' => Some(c.is_ascii_alphabetic()),
+            '*' => Some(c.is_ascii_alphanumeric()),
+            _ => None,
+        }
+    }
+
+    // returns the masked text, the characters entered in the slots, and whether all of the
+    // slots were filled
+    fn apply(&self, text: &str) -> (String, String, bool) {
+        let mut masked = String::new();
+        let mut raw = String::new();
+        let mut literals = String::new();
+        let mut chars = text.chars().peekable();
+        for slot in self.pattern.chars() {
+            if Self::accepts(slot, slot).is_none() {
+                literals.push(slot);
+                chars.next_if_eq(&slot);
+                continue;
+            }
+            let Some(c) = chars.find(|&c| Self::accepts(slot, c) == Some(true))
+            else {
+                return (masked, raw, false);
+            };
+            masked.push_str(&literals);
+            literals.clear();
+            masked.push(c);
+            raw.push(c);
+        }
+        masked.push_str(&literals);
+        (masked, raw, true)
+    }
+}
+
+impl InputAdapter for Mask {
+    type Value = String;
+
+    fn parse(&self, text: &str) -> Result<String, String> {
+        match self.apply(text) {
+            (_, raw, true) => Ok(raw),
+            (_, raw, false) if raw.is_empty() => Ok(raw),
+            _ => Err(format!("Enter a value like {}.", self.pattern)),
+        }
+    }
+
+    fn format(&self, value: &String) -> String {
+        self.apply(value).0
+    }
+
+    fn normalize(&self, text: &str) -> String {
+        self.apply(text).0
+    }
+}
+
+#[cfg(feature = "time")]
+mod dates {
+    use super::InputAdapter;
+    use time::{Date, Month, PrimitiveDateTime, Time};
+
+    /// An [`InputAdapter`] for `<input type="date">`, whose text is formatted as `YYYY-MM-DD`.
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct DateInput;
+
+    /// An [`InputAdapter`] for `<input type="time">`, whose text is formatted as `HH:MM`, or
+    /// `HH:MM:SS` when the time has seconds.
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct TimeInput;
+
+    /// An [`InputAdapter`] for `<input type="datetime-local">`, whose text is formatted as
+    /// `YYYY-MM-DDTHH:MM`.
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct DateTimeInput;
+
+    fn parse_date(text: &str) -> Option<Date> {
+        let mut parts = text.splitn(3, '-');
+        let year = parts.next()?.parse().ok()?;
+        let month: u8 = parts.next()?.parse().ok()?;
+        let day = parts.next()?.parse().ok()?;
+        Date::from_calendar_date(year, Month::try_from(month).ok()?, day).ok()
+    }
+
+    fn parse_time(text: &str) -> Option<Time> {
+        let mut parts = text.splitn(3, ':');
+        let hour = parts.next()?.parse().ok()?;
+        let minute = parts.next()?.parse().ok()?;
+        let second = match parts.next() {
+            // browsers may add milliseconds
+            Some(second) => second.split('.').next()?.parse().ok()?,
+            None => 0,
+        };
+        Time::from_hms(hour, minute, second).ok()
+    }
+
+    fn format_date(date: &Date) -> String {
+        format!(
+            "{:04}-{:02}-{:02}",
+            date.year(),
+            u8::from(date.month()),
+            date.day()
+        )
+    }
+
+    fn format_time(time: &Time) -> String {
+        if time.second() == 0 {
+            format!("{:02}:{:02}", time.hour(), time.minute())
+        } else {
+            format!(
+                "{:02}:{:02}:{:02}",
+                time.hour(),
+                time.minute(),
+                time.second()
+            )
+        }
+    }
+
+    impl InputAdapter for DateInput {
+        type Value = Date;
+
+        fn parse(&self, text: &str) -> Result<Date, String> {
+            parse_date(text).ok_or_else(|| "Enter a valid date.".to_string())
+        }
+
+        fn format(&self, value: &Date) -> String {
+            format_date(value)
+        }
+    }
+
+    impl InputAdapter for TimeInput {
+        type Value = Time;
+
+        fn parse(&self, text: &str) -> Result<Time, String> {
+            parse_time(text).ok_or_else(|| "Enter a valid time.".to_string())
+        }
+
+        fn format(&self, value: &Time) -> String {
+            format_time(value)
+        }
+    }
+
+    impl InputAdapter for DateTimeInput {
+        type Value = PrimitiveDateTime;
+
+        fn parse(&self, text: &str) -> Result<PrimitiveDateTime, String> {
+            text.split_once('T')
+                .and_then(|(date, time)| {
+                    Some(PrimitiveDateTime::new(
+                        parse_date(date)?,
+                        parse_time(time)?,
+                    ))
+                })
+                .ok_or_else(|| "Enter a valid date and time.".to_string())
+        }
+
+        fn format(&self, value: &PrimitiveDateTime) -> String {
+            format!(
+                "{}T{}",
+                format_date(&value.date()),
+                format_time(&value.time())
+            )
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+pub use dates::*;
@@ -0,0 +1,973 @@
+use any_spawner::time::sleep;
+use reactive_graph::{
+    actions::Action,
+    effect::Effect,
+    graph::untrack,
+    owner::StoredValue,
+    signal::RwSignal,
+    traits::{
+        Get, GetUntracked, GetValue, Set, SetValue, UpdateValue, With,
+        WithUntracked, WithValue,
+    },
+};
+use serde::{Deserialize, Serialize};
+use server_fn::{
+    codec::JsonEncoding,
+    error::{FromServerFnError, ServerFnError, ServerFnErrorErr},
+};
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Debug, Display},
+    future::Future,
+    pin::Pin,
+    str::FromStr,
+    sync::Arc,
+    time::Duration,
+};
+use tachys::{
+    dom::{event_target_checked, event_target_value},
+    html::{
+        attribute::{custom::custom_attribute, Attribute},
+        event::{blur, change, input, on},
+        property::prop,
+    },
+};
+use thiserror::Error;
+use web_sys::SubmitEvent;
+
+/// A struct that can be edited with a [`FormStore`].
+///
+/// This is usually implemented with `#[derive(FormModel)]`, which generates a struct with a
+/// [`FormField`] for each field of the model, named after the model with a `Fields` suffix.
+/// Validators for a field can be declared with `#[form(validate = ...)]`, which takes any
+/// function of the form `fn(&T) -> Result<(), String>`, and can be repeated. Asynchronous
+/// validators, such as a server function that checks whether a username is available, can be
+/// declared with `#[form(validate_async = ...)]`, which takes a function of the form
+/// `async fn(T) -> Result<(), String>`, and `#[form(debounce_ms = ...)]` sets how long to wait
+/// after the user stops typing before running them (see [`FormField::async_validator`]).
+///
+/// The type of each field must implement `PartialEq`, so that the form can tell whether it has
+/// been changed.
+pub trait FormModel: Clone + Send + Sync + 'static {
+    /// A struct with a [`FormField`] for each field of the model.
+    type Fields: Copy + Send + Sync + 'static;
+
+    /// Creates a field for each field of the model, starting with its current value.
+    fn into_fields(self) -> Self::Fields;
+
+    /// Reads the current value of each field, and subscribes to changes in them.
+    fn from_fields(fields: &Self::Fields) -> Self;
+
+    /// Calls `f` with each of the fields.
+    fn for_each_field(fields: &Self::Fields, f: &mut dyn FnMut(&dyn AnyField));
+
+    /// Checks the model with the validators declared for its fields.
+    ///
+    /// This can be used in a server function to check the submitted data with the same rules
+    /// that were checked in the browser.
+    fn validate(&self) -> Result<(), FieldErrors>;
+}
+
+type Validator<V> = Arc<dyn Fn(&V) -> Result<(), String> + Send + Sync>;
+
+type AsyncFieldValidator<V> = Arc<
+    dyn Fn(V) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send>>
+        + Send
+        + Sync,
+>;
+
+/// How long a field waits after its value stops changing before running its asynchronous
+/// validators, unless it is changed with [`FormField::debounce`].
+pub const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// A single field of a form: its current value, along with its validation state.
+///
+/// Fields are usually created by [`FormModel::into_fields`]. Like a signal, a field is `Copy`,
+/// and lives as long as the reactive owner that created it.
+pub struct FormField<V: 'static> {
+    name: &'static str,
+    value: RwSignal<V>,
+    initial: RwSignal<V>,
+    error: RwSignal<Option<String>>,
+    touched: RwSignal<bool>,
+    validating: RwSignal<bool>,
+    validators: StoredValue<Vec<Validator<V>>>,
+    async_validators: StoredValue<Vec<AsyncFieldValidator<V>>>,
+    debounce: StoredValue<Duration>,
+    // incremented whenever a validation starts, so that results that arrive after a newer
+    // validation has started can be discarded
+    generation: StoredValue<u64>,
+}
+
+impl<V> Clone for FormField<V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<V> Copy for FormField<V> {}
+
+impl<V> Debug for FormField<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FormField")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<V> FormField<V>
+where
+    V: Clone + Send + Sync + 'static,
+{
+    /// Creates a field with the given name (used for the `name` attribute of its input, and to
+    /// match errors to it) and initial value.
+    pub fn new(name: &'static str, value: V) -> Self {
+        Self {
+            name,
+            initial: RwSignal::new(value.clone()),
+            value: RwSignal::new(value),
+            error: RwSignal::new(None),
+            touched: RwSignal::new(false),
+            validating: RwSignal::new(false),
+            validators: StoredValue::new(Vec::new()),
+            async_validators: StoredValue::new(Vec::new()),
+            debounce: StoredValue::new(DEFAULT_DEBOUNCE),
+            generation: StoredValue::new(0),
+        }
+    }
+
+    /// Adds a validator, which returns an error message if the value is invalid.
+    pub fn validator(
+        self,
+        validator: impl Fn(&V) -> Result<(), String> + Send + Sync + 'static,
+    ) -> Self {
+        self.validators
+            .update_value(|validators| validators.push(Arc::new(validator)));
+        self
+    }
+
+    /// Adds an asynchronous validator, such as a server function that checks whether a username
+    /// is available, which returns an error message if the value is invalid.
+    ///
+    /// Asynchronous validators run once all the other validators pass: as the user types, after
+    /// the value has stopped changing for the [`debounce`](FormField::debounce) delay; when the
+    /// user leaves the field; and when the form is submitted. While they run,
+    /// [`is_validating`](FormField::is_validating) is `true`. If the value changes before they
+    /// finish, their result is discarded, so a slow response for an old value never overwrites
+    /// the result for the current one.
+    pub fn async_validator<Fut>(
+        self,
+        validator: impl Fn(V) -> Fut + Send + Sync + 'static,
+    ) -> Self
+    where
+        Fut: Future<Output = Result<(), String>> + Send + 'static,
+    {
+        let validator: AsyncFieldValidator<V> =
+            Arc::new(move |value| Box::pin(validator(value)));
+        self.async_validators
+            .update_value(|validators| validators.push(validator));
+        self
+    }
+
+    /// Sets how long to wait after the value stops changing before running the asynchronous
+    /// validators. Defaults to [`DEFAULT_DEBOUNCE`].
+    pub fn debounce(self, delay: Duration) -> Self {
+        self.debounce.set_value(delay);
+        self
+    }
+
+    /// The name of the field.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The signal that holds the current value of the field.
+    pub fn value(&self) -> RwSignal<V> {
+        self.value
+    }
+
+    /// Returns the current value, and subscribes to changes in it.
+    pub fn get(&self) -> V {
+        self.value.get()
+    }
+
+    /// Sets the value. If the field has been touched, it is validated again.
+    pub fn set(&self, value: V) {
+        self.value.set(value);
+        if self.touched.get_untracked() {
+            self.validate();
+        }
+    }
+
+    /// Returns the value the field started with, or was last marked clean with.
+    pub fn initial(&self) -> V {
+        self.initial.get()
+    }
+
+    /// Whether the value differs from its initial value, subscribing to changes in both.
+    pub fn is_dirty(&self) -> bool
+    where
+        V: PartialEq,
+    {
+        self.initial
+            .with(|initial| self.value.with(|value| value != initial))
+    }
+
+    /// Restores the initial value, and clears the error and touched state, discarding any
+    /// asynchronous validation that is still running.
+    pub fn reset(&self) {
+        self.next_generation();
+        self.validating.set(false);
+        self.value.set(self.initial.get_untracked());
+        self.error.set(None);
+        self.touched.set(false);
+    }
+
+    /// Makes the current value the initial value, so that the field is no longer dirty, for
+    /// example once it has been saved.
+    pub fn mark_clean(&self) {
+        self.initial.set(self.value.get_untracked());
+    }
+
+    /// Returns the current error message, if the field is invalid.
+    pub fn error(&self) -> Option<String> {
+        self.error.get()
+    }
+
+    /// Sets or clears the error message, for example with an error returned by the server.
+    pub fn set_error(&self, error: Option<String>) {
+        self.error.set(error);
+    }
+
+    /// Whether the user has left the field, or it has been validated for a submission.
+    ///
+    /// Errors are usually only shown for touched fields, so that the user isn't told that a field
+    /// is invalid before they have had the chance to fill it in.
+    pub fn is_touched(&self) -> bool {
+        self.touched.get()
+    }
+
+    /// Whether the asynchronous validators are running, or waiting for the value to stop
+    /// changing.
+    pub fn is_validating(&self) -> bool {
+        self.validating.get()
+    }
+
+    /// Runs the validators against the current value, marking the field as touched, and updates
+    /// the error message. Returns whether the value is valid.
+    ///
+    /// This does not run the asynchronous validators, but if the value is invalid, any
+    /// asynchronous validation that is still running is discarded.
+    pub fn validate(&self) -> bool {
+        let error = self.validators.with_value(|validators| {
+            self.value.with_untracked(|value| {
+                validators
+                    .iter()
+                    .find_map(|validator| validator(value).err())
+            })
+        });
+        let valid = error.is_none();
+        if !valid {
+            self.next_generation();
+            self.validating.set(false);
+        }
+        self.touched.set(true);
+        self.error.set(error);
+        valid
+    }
+
+    /// Runs all the validators against the current value, including the asynchronous ones, and
+    /// updates the error message. Returns whether the value is valid.
+    ///
+    /// If another validation starts before this one finishes, this one's result is discarded, and
+    /// it returns `false`.
+    pub async fn validate_async(&self) -> bool {
+        self.validate() && self.run_async_validators(Duration::ZERO).await
+    }
+
+    fn next_generation(&self) -> u64 {
+        self.generation
+            .try_update_value(|generation| {
+                *generation += 1;
+                *generation
+            })
+            .unwrap_or_default()
+    }
+
+    async fn run_async_validators(self, delay: Duration) -> bool {
+        if self.async_validators.with_value(Vec::is_empty) {
+            return true;
+        }
+        let generation = self.next_generation();
+        let is_current =
+            move || self.generation.try_get_value() == Some(generation);
+        self.validating.set(true);
+
+        if !delay.is_zero() {
+            sleep(delay).await;
+            if !is_current() {
+                return false;
+            }
+        }
+
+        let value = self.value.get_untracked();
+        let mut error = None;
+        for validator in self.async_validators.get_value() {
+            if let Err(e) = validator(value.clone()).await {
+                error = Some(e);
+                break;
+            }
+        }
+
+        if !is_current() {
+            return false;
+        }
+        let valid = error.is_none();
+        self.validating.set(false);
+        self.error.set(error);
+        valid
+    }
+
+    // validates the field after the user has changed or left it, running the asynchronous
+    // validators in the background once the value stops changing
+    fn validate_in_background(self, delay: Duration) {
+        if self.validate()
+            && self.async_validators.with_value(|v| !v.is_empty())
+        {
+            crate::task::spawn_local(async move {
+                self.run_async_validators(delay).await;
+            });
+        }
+    }
+
+    /// The attributes that bind an `<input>` or `<textarea>` to this field: its `name`, its
+    /// value, `aria-invalid` while it has an error, and the listeners that update the field as the
+    /// user types and validate it when they leave it.
+    ///
+    /// Spread them onto the element with `<input {..field.attrs()}/>`. If the text that is entered
+    /// can't be parsed, the field keeps its previous value and the parse error is shown.
+    pub fn attrs(self) -> impl Attribute
+    where
+        V: FromStr + Display,
+        V::Err: Display,
+    {
+        let value = move || self.value.with(ToString::to_string);
+        (
+            custom_attribute("name", self.name),
+            custom_attribute("value", value),
+            prop("value", value),
+            self.aria_invalid(),
+            on(input, move |ev| match event_target_value(&ev).parse() {
+                Ok(value) => {
+                    self.value.set(value);
+                    if self.touched.get_untracked() {
+                        self.validate_in_background(self.debounce.get_value());
+                    }
+                }
+                Err(e) => {
+                    self.next_generation();
+                    self.validating.set(false);
+                    self.error.set(Some(e.to_string()));
+                }
+            }),
+            on(blur, move |_| self.validate_in_background(Duration::ZERO)),
+        )
+    }
+
+    fn aria_invalid(self) -> impl Attribute {
+        custom_attribute("aria-invalid", move || {
+            self.error.with(Option::is_some).then_some("true")
+        })
+    }
+}
+
+impl FormField<bool> {
+    /// The attributes that bind an `<input type="checkbox">` to this field.
+    ///
+    /// Spread them onto the element with `<input type="checkbox" {..field.checkbox_attrs()}/>`.
+    pub fn checkbox_attrs(self) -> impl Attribute {
+        (
+            custom_attribute("name", self.name),
+            custom_attribute("checked", move || self.value.get()),
+            prop("checked", move || self.value.get()),
+            self.aria_invalid(),
+            on(change, move |ev| {
+                self.value.set(event_target_checked(&ev));
+                if self.touched.get_untracked() {
+                    self.validate_in_background(Duration::ZERO);
+                }
+            }),
+        )
+    }
+}
+
+/// A [`FormField`] whose value type has been erased, so that all the fields of a form can be
+/// visited at once.
+pub trait AnyField {
+    /// The name of the field.
+    fn name(&self) -> &'static str;
+
+    /// Returns the current error message, and subscribes to changes in it.
+    fn error(&self) -> Option<String>;
+
+    /// Sets or clears the error message.
+    fn set_error(&self, error: Option<String>);
+
+    /// Validates the current value, returning whether it is valid.
+    fn validate(&self) -> bool;
+
+    /// Validates the current value with all the validators, including the asynchronous ones.
+    fn validate_async(&self) -> Pin<Box<dyn Future<Output = bool> + Send>>;
+
+    /// Whether the asynchronous validators are running, subscribing to changes in it.
+    fn is_validating(&self) -> bool;
+
+    /// Whether the value differs from its initial value, subscribing to changes in both.
+    fn is_dirty(&self) -> bool;
+
+    /// Restores the initial value, and clears the error and touched state.
+    fn reset(&self);
+
+    /// Makes the current value the initial value.
+    fn mark_clean(&self);
+}
+
+impl<V> AnyField for FormField<V>
+where
+    V: Clone + PartialEq + Send + Sync + 'static,
+{
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn error(&self) -> Option<String> {
+        FormField::error(self)
+    }
+
+    fn set_error(&self, error: Option<String>) {
+        FormField::set_error(self, error)
+    }
+
+    fn validate(&self) -> bool {
+        FormField::validate(self)
+    }
+
+    fn validate_async(&self) -> Pin<Box<dyn Future<Output = bool> + Send>> {
+        let field = *self;
+        Box::pin(async move { field.validate_async().await })
+    }
+
+    fn is_validating(&self) -> bool {
+        FormField::is_validating(self)
+    }
+
+    fn is_dirty(&self) -> bool {
+        FormField::is_dirty(self)
+    }
+
+    fn reset(&self) {
+        FormField::reset(self)
+    }
+
+    fn mark_clean(&self) {
+        FormField::mark_clean(self)
+    }
+}
+
+/// Error messages for some of the fields of a form, by the name of the field.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldErrors(BTreeMap<String, String>);
+
+impl FieldErrors {
+    /// Creates an empty set of errors.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an error for the given field, replacing any previous error for it.
+    pub fn insert(
+        &mut self,
+        field: impl Into<String>,
+        message: impl Into<String>,
+    ) {
+        self.0.insert(field.into(), message.into());
+    }
+
+    /// Adds an error for the given field, and returns `self`.
+    pub fn with(
+        mut self,
+        field: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        self.insert(field, message);
+        self
+    }
+
+    /// Returns the error for the given field.
+    pub fn get(&self, field: &str) -> Option<&str> {
+        self.0.get(field).map(String::as_str)
+    }
+
+    /// Whether there are no errors.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Iterates over the field names and error messages.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0
+            .iter()
+            .map(|(field, message)| (field.as_str(), message.as_str()))
+    }
+}
+
+impl Display for FieldErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (field, message)) in self.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{field}: {message}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for FieldErrors {}
+
+impl<K, V> FromIterator<(K, V)> for FieldErrors
+where
+    K: Into<String>,
+    V: Into<String>,
+{
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        Self(
+            iter.into_iter()
+                .map(|(field, message)| (field.into(), message.into()))
+                .collect(),
+        )
+    }
+}
+
+/// An error type for server functions that handle a form submission, which sends validation
+/// errors back to the fields of the form they belong to.
+///
+/// ```rust
+/// # use leptos::prelude::*;
+/// #[derive(FormModel, Clone, Debug, serde::Serialize, serde::Deserialize)]
+/// struct NewUser {
+///     #[form(validate = not_empty)]
+///     username: String,
+/// }
+///
+/// fn not_empty(value: &impl AsRef<str>) -> Result<(), String> {
+///     if value.as_ref().is_empty() {
+///         Err("This field is required.".into())
+///     } else {
+///         Ok(())
+///     }
+/// }
+///
+/// #[server]
+/// async fn create_user(user: NewUser) -> Result<(), FormError> {
+///     // check the same rules as the browser
+///     user.validate()?;
+///     if user.username == "admin" {
+///         return Err(FieldErrors::new()
+///             .with("username", "This username is taken.")
+///             .into());
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Error)]
+pub enum FormError {
+    /// Some of the fields are invalid.
+    #[error("{0}")]
+    Fields(FieldErrors),
+    /// Calling the server function failed.
+    #[error(transparent)]
+    ServerFn(ServerFnErrorErr),
+}
+
+impl From<FieldErrors> for FormError {
+    fn from(errors: FieldErrors) -> Self {
+        FormError::Fields(errors)
+    }
+}
+
+impl FromServerFnError for FormError {
+    type Encoder = JsonEncoding;
+
+    fn from_server_fn_error(value: ServerFnErrorErr) -> Self {
+        FormError::ServerFn(value)
+    }
+}
+
+/// An error that may contain errors for individual fields of a form.
+///
+/// [`FormStore::set_result_error`] uses this to show each error next to its field, and any other
+/// error for the form as a whole.
+pub trait HasFieldErrors: Display {
+    /// Returns the errors for individual fields, if this is a validation error.
+    fn field_errors(&self) -> Option<&FieldErrors>;
+}
+
+impl HasFieldErrors for FormError {
+    fn field_errors(&self) -> Option<&FieldErrors> {
+        match self {
+            FormError::Fields(errors) => Some(errors),
+            FormError::ServerFn(_) => None,
+        }
+    }
+}
+
+impl HasFieldErrors for FieldErrors {
+    fn field_errors(&self) -> Option<&FieldErrors> {
+        Some(self)
+    }
+}
+
+impl HasFieldErrors for ServerFnErrorErr {
+    fn field_errors(&self) -> Option<&FieldErrors> {
+        None
+    }
+}
+
+impl<E: Display> HasFieldErrors for ServerFnError<E> {
+    fn field_errors(&self) -> Option<&FieldErrors> {
+        None
+    }
+}
+
+type AsyncValidator<T> = Arc<
+    dyn Fn(T) -> Pin<Box<dyn Future<Output = Result<(), FieldErrors>> + Send>>
+        + Send
+        + Sync,
+>;
+
+/// The reactive state of a form that edits a [`FormModel`]: a [`FormField`] for each of its fields,
+/// and an error for the form as a whole.
+///
+/// ```rust
+/// # use leptos::prelude::*;
+/// #[derive(
+///     FormModel, Clone, Debug, Default, serde::Serialize, serde::Deserialize,
+/// )]
+/// struct NewUser {
+///     #[form(validate = not_empty)]
+///     username: String,
+///     age: u32,
+/// }
+///
+/// fn not_empty(value: &impl AsRef<str>) -> Result<(), String> {
+///     if value.as_ref().is_empty() {
+///         Err("This field is required.".into())
+///     } else {
+///         Ok(())
+///     }
+/// }
+///
+/// #[server]
+/// async fn create_user(user: NewUser) -> Result<(), FormError> {
+///     user.validate()?;
+///     Ok(())
+/// }
+///
+/// #[component]
+/// fn SignUp() -> impl IntoView {
+///     let form = FormStore::new(NewUser::default());
+///     let NewUserFields { username, age } = form.fields();
+///     let on_submit = form.submit_to(
+///         ServerAction::<CreateUser>::new(),
+///         |user| CreateUser { user },
+///     );
+///
+///     view! {
+///         <form on:submit=on_submit>
+///             <input {..username.attrs()}/>
+///             <p class="error">{move || username.error()}</p>
+///             <input type="number" {..age.attrs()}/>
+///             <p class="error">{move || age.error()}</p>
+///             <p class="error">{move || form.error()}</p>
+///             <button type="submit">"Sign up"</button>
+///         </form>
+///     }
+/// }
+/// ```
+pub struct FormStore<T: FormModel> {
+    fields: T::Fields,
+    error: RwSignal<Option<String>>,
+    async_validators: StoredValue<Vec<AsyncValidator<T>>>,
+}
+
+impl<T: FormModel> Clone for FormStore<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: FormModel> Copy for FormStore<T> {}
+
+impl<T: FormModel> Debug for FormStore<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FormStore").finish_non_exhaustive()
+    }
+}
+
+impl<T: FormModel> FormStore<T> {
+    /// Creates a form, starting with the values of `initial`.
+    pub fn new(initial: T) -> Self {
+        Self {
+            fields: initial.into_fields(),
+            error: RwSignal::new(None),
+            async_validators: StoredValue::new(Vec::new()),
+        }
+    }
+
+    /// Adds a validator that checks the whole model asynchronously, for example by calling a
+    /// server function. It is run when the form is submitted, once all the fields are valid.
+    pub fn async_validator<Fut>(
+        self,
+        validator: impl Fn(T) -> Fut + Send + Sync + 'static,
+    ) -> Self
+    where
+        Fut: Future<Output = Result<(), FieldErrors>> + Send + 'static,
+    {
+        let validator: AsyncValidator<T> =
+            Arc::new(move |model| Box::pin(validator(model)));
+        self.async_validators
+            .update_value(|validators| validators.push(validator));
+        self
+    }
+
+    /// The fields of the form.
+    pub fn fields(&self) -> T::Fields {
+        self.fields
+    }
+
+    /// Returns the current values of all the fields, and subscribes to changes in them.
+    pub fn get(&self) -> T {
+        T::from_fields(&self.fields)
+    }
+
+    /// Returns the current values of all the fields, without subscribing to them.
+    pub fn get_untracked(&self) -> T {
+        untrack(|| self.get())
+    }
+
+    /// Returns the error for the form as a whole, such as a failure to reach the server.
+    pub fn error(&self) -> Option<String> {
+        self.error.get()
+    }
+
+    /// Whether none of the fields has an error, subscribing to changes in them.
+    pub fn is_valid(&self) -> bool {
+        let mut valid = true;
+        T::for_each_field(&self.fields, &mut |field| {
+            valid &= field.error().is_none();
+        });
+        valid
+    }
+
+    /// Validates every field, returning whether they are all valid.
+    pub fn validate(&self) -> bool {
+        let mut valid = true;
+        T::for_each_field(&self.fields, &mut |field| {
+            valid &= field.validate();
+        });
+        valid
+    }
+
+    /// Whether any of the fields is running its asynchronous validators, subscribing to changes
+    /// in them.
+    pub fn is_validating(&self) -> bool {
+        let mut validating = false;
+        T::for_each_field(&self.fields, &mut |field| {
+            validating |= field.is_validating();
+        });
+        validating
+    }
+
+    /// Validates every field, and then, if they are all valid, runs the asynchronous validators of
+    /// the fields and of the form in turn. Returns whether the form is valid.
+    pub async fn validate_async(&self) -> bool {
+        if !self.validate() {
+            return false;
+        }
+        let mut fields = Vec::new();
+        T::for_each_field(&self.fields, &mut |field| {
+            fields.push(field.validate_async());
+        });
+        let mut valid = true;
+        for field in fields {
+            valid &= field.await;
+        }
+        if !valid {
+            return false;
+        }
+        let validators = self.async_validators.get_value();
+        for validator in validators {
+            if let Err(errors) = validator(self.get_untracked()).await {
+                self.set_field_errors(&errors);
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Shows the given errors next to their fields, clearing the errors of every other field.
+    ///
+    /// Errors for fields that the form does not have are shown as the error for the whole form.
+    pub fn set_field_errors(&self, errors: &FieldErrors) {
+        let mut unknown = errors.clone();
+        T::for_each_field(&self.fields, &mut |field| {
+            let error = unknown.0.remove(field.name());
+            field.set_error(error);
+        });
+        self.error
+            .set((!unknown.is_empty()).then(|| unknown.to_string()));
+    }
+
+    /// Shows an error returned when submitting the form: validation errors are shown next to
+    /// their fields, and any other error is shown as the error for the whole form.
+    pub fn set_result_error<E: HasFieldErrors>(&self, error: &E) {
+        match error.field_errors() {
+            Some(errors) => self.set_field_errors(errors),
+            None => self.error.set(Some(error.to_string())),
+        }
+    }
+
+    /// Clears the errors of the form and all of its fields.
+    pub fn clear_errors(&self) {
+        T::for_each_field(&self.fields, &mut |field| field.set_error(None));
+        self.error.set(None);
+    }
+
+    /// Whether any of the fields differs from its initial value, subscribing to changes in them.
+    ///
+    /// This can be used to warn the user before they leave a page with unsaved changes.
+    pub fn is_dirty(&self) -> bool {
+        let mut dirty = false;
+        T::for_each_field(&self.fields, &mut |field| {
+            dirty |= field.is_dirty();
+        });
+        dirty
+    }
+
+    /// The names of the fields that differ from their initial values, subscribing to changes in
+    /// them.
+    pub fn dirty_fields(&self) -> Vec<&'static str> {
+        let mut dirty = Vec::new();
+        T::for_each_field(&self.fields, &mut |field| {
+            if field.is_dirty() {
+                dirty.push(field.name());
+            }
+        });
+        dirty
+    }
+
+    /// Restores the initial value of every field, and clears all the errors.
+    pub fn reset(&self) {
+        T::for_each_field(&self.fields, &mut |field| field.reset());
+        self.error.set(None);
+    }
+
+    /// Restores the initial value of the field with the given name. Returns `false` if the form
+    /// has no such field.
+    pub fn reset_field(&self, name: &str) -> bool {
+        let mut found = false;
+        T::for_each_field(&self.fields, &mut |field| {
+            if field.name() == name {
+                field.reset();
+                found = true;
+            }
+        });
+        found
+    }
+
+    /// Makes the current values of the fields their initial values, so that the form is no
+    /// longer dirty.
+    pub fn mark_clean(&self) {
+        T::for_each_field(&self.fields, &mut |field| field.mark_clean());
+    }
+
+    /// Returns a `submit` event handler that validates the form and, if it is valid, dispatches
+    /// `action` with the input created from the model by `to_input`.
+    ///
+    /// When the action returns an error, validation errors are shown next to their fields and
+    /// any other error as the error for the whole form (see [`FormStore::set_result_error`]).
+    /// When it succeeds, the form is marked clean (see [`FormStore::mark_clean`]).
+    pub fn submit_to<I, O, E>(
+        &self,
+        action: impl Into<Action<I, Result<O, E>>>,
+        to_input: impl Fn(T) -> I + Send + Sync + 'static,
+    ) -> impl FnMut(SubmitEvent) + Clone + 'static
+    where
+        I: Send + Sync + 'static,
+        O: Send + Sync + 'static,
+        E: HasFieldErrors + Send + Sync + 'static,
+    {
+        let action = action.into();
+        let form = *self;
+        let to_input = Arc::new(to_input);
+
+        let value = action.value();
+        Effect::new(move |_| {
+            value.with(|result| match result {
+                Some(Ok(_)) => {
+                    form.clear_errors();
+                    form.mark_clean();
+                }
+                Some(Err(error)) => form.set_result_error(error),
+                None => {}
+            })
+        });
+
+        move |ev: SubmitEvent| {
+            ev.prevent_default();
+            let to_input = Arc::clone(&to_input);
+            crate::task::spawn_local(async move {
+                if form.validate_async().await {
+                    form.error.set(None);
+                    action.dispatch(to_input(form.get_untracked()));
+                }
+            });
+        }
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use super::*;
+    use crate::testing::with_test_owner;
+    use any_spawner::time;
+
+    #[test]
+    fn debounced_validation_follows_virtual_time() {
+        with_test_owner(|| {
+            let username = FormField::new("username", "admin".to_string())
+                .async_validator(|username: String| async move {
+                    if username == "admin" {
+                        Err("Taken.".to_string())
+                    } else {
+                        Ok(())
+                    }
+                })
+                .debounce(Duration::from_millis(500));
+
+            username.validate_in_background(username.debounce.get_value());
+            time::run_until_idle();
+            assert!(username.is_validating());
+            assert_eq!(username.error(), None);
+
+            time::advance_time(Duration::from_millis(499));
+            assert!(username.is_validating());
+            assert_eq!(username.error(), None);
+
+            time::advance_time(Duration::from_millis(1));
+            assert!(!username.is_validating());
+            assert_eq!(username.error().as_deref(), Some("Taken."));
+        });
+    }
+}
@@ -0,0 +1,352 @@
+//! The position of the device, with the
+//! [Geolocation API](https://developer.mozilla.org/en-US/docs/Web/API/Geolocation_API).
+//!
+//! [`use_geolocation`] watches the position of the device and returns a [`UseGeolocation`] handle
+//! with the latest position, the latest error, and whether the user has allowed the page to
+//! access their location.
+//!
+//! ```rust
+//! use leptos::{
+//!     geolocation::{use_geolocation, GeolocationOptions},
+//!     prelude::*,
+//! };
+//!
+//! #[component]
+//! pub fn WhereAmI() -> impl IntoView {
+//!     let geo = use_geolocation(GeolocationOptions::default());
+//!
+//!     view! {
+//!         {move || match (geo.position(), geo.error()) {
+//!             (Some(pos), _) => format!("{:.4}, {:.4}", pos.latitude, pos.longitude),
+//!             (None, Some(e)) => e.to_string(),
+//!             (None, None) => "Locating…".to_string(),
+//!         }}
+//!     }
+//! }
+//! ```
+//!
+//! The browser asks the user for permission the first time the position is watched. The position
+//! can only be known in the browser: on the server, and until the first position arrives,
+//! [`UseGeolocation::position`] is `None` and [`UseGeolocation::permission`] is
+//! [`PermissionState::Unknown`].
+
+use crate::task::spawn_local;
+use leptos_dom::helpers::window;
+use reactive_graph::{
+    effect::Effect,
+    owner::on_cleanup,
+    signal::RwSignal,
+    traits::{Get, GetUntracked, Set},
+};
+use send_wrapper::SendWrapper;
+use std::{cell::RefCell, rc::Rc, time::Duration};
+use thiserror::Error;
+use wasm_bindgen::{closure::Closure, JsCast};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    PermissionDescriptor, PermissionName, PermissionStatus, Position,
+    PositionError, PositionOptions,
+};
+
+/// Options that configure [`use_geolocation`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct GeolocationOptions {
+    /// Whether to ask for the most accurate position the device can provide, which can be slower
+    /// and use more power. Defaults to `false`.
+    pub enable_high_accuracy: bool,
+    /// How old a cached position can be. Defaults to `None`, which always asks for a new one.
+    pub maximum_age: Option<Duration>,
+    /// How long to wait for each position before reporting [`GeolocationError::Timeout`].
+    /// Defaults to `None`, which waits as long as it takes.
+    pub timeout: Option<Duration>,
+}
+
+impl GeolocationOptions {
+    fn to_position_options(self) -> PositionOptions {
+        let options = PositionOptions::new();
+        options.set_enable_high_accuracy(self.enable_high_accuracy);
+        options
+            .set_maximum_age(self.maximum_age.map(millis).unwrap_or_default());
+        if let Some(timeout) = self.timeout {
+            options.set_timeout(millis(timeout));
+        }
+        options
+    }
+}
+
+fn millis(duration: Duration) -> u32 {
+    duration.as_millis().try_into().unwrap_or(u32::MAX)
+}
+
+/// A position of the device.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeolocationPosition {
+    /// The latitude, in decimal degrees.
+    pub latitude: f64,
+    /// The longitude, in decimal degrees.
+    pub longitude: f64,
+    /// The accuracy of the latitude and longitude, in meters.
+    pub accuracy: f64,
+    /// The altitude above sea level, in meters, if the device can tell.
+    pub altitude: Option<f64>,
+    /// The accuracy of the altitude, in meters, if the device can tell.
+    pub altitude_accuracy: Option<f64>,
+    /// The direction of travel, in degrees clockwise from true north, if the device is moving.
+    pub heading: Option<f64>,
+    /// The speed, in meters per second, if the device can tell.
+    pub speed: Option<f64>,
+    /// When the position was determined, in milliseconds since the Unix epoch.
+    pub timestamp: f64,
+}
+
+impl From<Position> for GeolocationPosition {
+    fn from(position: Position) -> Self {
+        let coords = position.coords();
+        Self {
+            latitude: coords.latitude(),
+            longitude: coords.longitude(),
+            accuracy: coords.accuracy(),
+            altitude: coords.altitude(),
+            altitude_accuracy: coords.altitude_accuracy(),
+            heading: coords.heading().filter(|heading| !heading.is_nan()),
+            speed: coords.speed(),
+            timestamp: position.timestamp(),
+        }
+    }
+}
+
+/// An error while determining the position of the device.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum GeolocationError {
+    /// The browser does not support geolocation.
+    #[error("geolocation is not supported")]
+    Unsupported,
+    /// The user or the browser did not allow the page to access the location.
+    #[error("access to the location was denied: {0}")]
+    PermissionDenied(String),
+    /// The position could not be determined, for example because the device is offline.
+    #[error("the position is unavailable: {0}")]
+    PositionUnavailable(String),
+    /// The position could not be determined within [`GeolocationOptions::timeout`].
+    #[error("timed out while determining the position: {0}")]
+    Timeout(String),
+}
+
+impl From<PositionError> for GeolocationError {
+    fn from(e: PositionError) -> Self {
+        match e.code() {
+            PositionError::PERMISSION_DENIED => {
+                GeolocationError::PermissionDenied(e.message())
+            }
+            PositionError::TIMEOUT => GeolocationError::Timeout(e.message()),
+            _ => GeolocationError::PositionUnavailable(e.message()),
+        }
+    }
+}
+
+/// Whether the page is allowed to access the location of the device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PermissionState {
+    /// Not known yet, like on the server, or in a browser without the Permissions API before
+    /// the first position or error.
+    #[default]
+    Unknown,
+    /// The browser will ask the user.
+    Prompt,
+    /// The page may access the location.
+    Granted,
+    /// The page may not access the location.
+    Denied,
+}
+
+impl From<web_sys::PermissionState> for PermissionState {
+    fn from(state: web_sys::PermissionState) -> Self {
+        match state {
+            web_sys::PermissionState::Granted => PermissionState::Granted,
+            web_sys::PermissionState::Denied => PermissionState::Denied,
+            web_sys::PermissionState::Prompt => PermissionState::Prompt,
+            _ => PermissionState::Unknown,
+        }
+    }
+}
+
+/// A handle to the position of the device, created with [`use_geolocation`].
+#[derive(Debug, Clone, Copy)]
+pub struct UseGeolocation {
+    position: RwSignal<Option<GeolocationPosition>>,
+    error: RwSignal<Option<GeolocationError>>,
+    permission: RwSignal<PermissionState>,
+    watching: RwSignal<bool>,
+}
+
+impl UseGeolocation {
+    /// The latest position, if any. Reactive.
+    pub fn position(&self) -> Option<GeolocationPosition> {
+        self.position.get()
+    }
+
+    /// The error from the latest attempt to determine the position, if it failed. Reactive.
+    pub fn error(&self) -> Option<GeolocationError> {
+        self.error.get()
+    }
+
+    /// Whether the page is allowed to access the location. Reactive.
+    pub fn permission(&self) -> PermissionState {
+        self.permission.get()
+    }
+
+    /// Stops watching the position until [`resume`](Self::resume) is called. The latest
+    /// position is kept.
+    pub fn pause(&self) {
+        if self.watching.get_untracked() {
+            self.watching.set(false);
+        }
+    }
+
+    /// Starts watching the position again after [`pause`](Self::pause).
+    pub fn resume(&self) {
+        if !self.watching.get_untracked() {
+            self.watching.set(true);
+        }
+    }
+
+    /// Whether the position is being watched. Reactive.
+    pub fn is_watching(&self) -> bool {
+        self.watching.get()
+    }
+}
+
+/// Watches the position of the device, until the reactive owner that called this is cleaned up.
+pub fn use_geolocation(options: GeolocationOptions) -> UseGeolocation {
+    let geo = UseGeolocation {
+        position: RwSignal::new(None),
+        error: RwSignal::new(None),
+        permission: RwSignal::new(PermissionState::Unknown),
+        watching: RwSignal::new(true),
+    };
+
+    // effects only run in the browser, so nothing is watched on the server
+    Effect::new(move |_| {
+        if !geo.watching.get() {
+            return;
+        }
+        let Ok(geolocation) = window().navigator().geolocation() else {
+            geo.error.set(Some(GeolocationError::Unsupported));
+            return;
+        };
+        let on_position =
+            Closure::<dyn Fn(Position)>::new(move |position: Position| {
+                geo.position.set(Some(position.into()));
+                geo.error.set(None);
+                if geo.permission.get_untracked() != PermissionState::Granted {
+                    geo.permission.set(PermissionState::Granted);
+                }
+            });
+        let on_error =
+            Closure::<dyn Fn(PositionError)>::new(move |e: PositionError| {
+                let e = GeolocationError::from(e);
+                if matches!(e, GeolocationError::PermissionDenied(_)) {
+                    geo.permission.set(PermissionState::Denied);
+                }
+                geo.error.set(Some(e));
+            });
+        let Ok(watch) = geolocation
+            .watch_position_with_error_callback_and_options(
+                on_position.as_ref().unchecked_ref(),
+                Some(on_error.as_ref().unchecked_ref()),
+                &options.to_position_options(),
+            )
+        else {
+            return;
+        };
+
+        let cleanup = SendWrapper::new((geolocation, on_position, on_error));
+        on_cleanup(move || {
+            let (geolocation, _on_position, _on_error) = cleanup.take();
+            geolocation.clear_watch(watch);
+        });
+    });
+
+    Effect::new(move |_| watch_permission(geo.permission));
+
+    geo
+}
+
+enum PermissionWatch {
+    Pending,
+    Watching(PermissionStatus, Closure<dyn Fn()>),
+    Disposed,
+}
+
+/// Keeps `permission` up to date with the Permissions API, where it is supported.
+fn watch_permission(permission: RwSignal<PermissionState>) {
+    let Ok(permissions) = window().navigator().permissions() else {
+        return;
+    };
+    let Ok(query) = permissions
+        .query(&PermissionDescriptor::new(PermissionName::Geolocation))
+    else {
+        return;
+    };
+    let watch = Rc::new(RefCell::new(PermissionWatch::Pending));
+
+    spawn_local({
+        let watch = Rc::clone(&watch);
+        async move {
+            let Ok(status) = JsFuture::from(query).await else {
+                return;
+            };
+            let status = status.unchecked_into::<PermissionStatus>();
+            let mut watch = watch.borrow_mut();
+            if matches!(*watch, PermissionWatch::Disposed) {
+                return;
+            }
+            permission.set(status.state().into());
+            let on_change = Closure::<dyn Fn()>::new({
+                let status = status.clone();
+                move || {
+                    permission.set(status.state().into());
+                }
+            });
+            status.set_onchange(Some(on_change.as_ref().unchecked_ref()));
+            *watch = PermissionWatch::Watching(status, on_change);
+        }
+    });
+
+    let watch = SendWrapper::new(watch);
+    on_cleanup(move || {
+        let watch = watch.take();
+        let previous = watch.replace(PermissionWatch::Disposed);
+        if let PermissionWatch::Watching(status, _on_change) = previous {
+            status.set_onchange(None);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn durations_saturate_at_the_largest_number_of_milliseconds() {
+        assert_eq!(millis(Duration::from_millis(1500)), 1500);
+        assert_eq!(millis(Duration::from_secs(u64::MAX)), u32::MAX);
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn nothing_is_known_about_the_position_on_the_server() {
+        crate::testing::with_test_owner(|| {
+            let geo = use_geolocation(GeolocationOptions::default());
+            assert_eq!(geo.position(), None);
+            assert_eq!(geo.error(), None);
+            assert_eq!(geo.permission(), PermissionState::Unknown);
+
+            assert!(geo.is_watching());
+            geo.pause();
+            assert!(!geo.is_watching());
+            geo.resume();
+            assert!(geo.is_watching());
+        });
+    }
+}
@@ -0,0 +1,812 @@
+//! Taps, long presses, swipes, drags and pinches, recognized from
+//! [pointer events](https://developer.mozilla.org/en-US/docs/Web/API/Pointer_events).
+//!
+//! [`use_gesture`] listens to the pointer events of an element, and turns them into typed
+//! gestures, the latest of each kind being available as a signal. This works the same for the
+//! mouse, pens and touch, without any bookkeeping of pointer ids in the app:
+//!
+//! ```rust
+//! use leptos::{
+//!     gesture::{use_gesture, DragPhase, SwipeDirection},
+//!     html::Div,
+//!     prelude::*,
+//! };
+//!
+//! #[component]
+//! pub fn Card() -> impl IntoView {
+//!     let card = NodeRef::<Div>::new();
+//!     let gesture = use_gesture(card);
+//!
+//!     let offset = move || match gesture.drag.get() {
+//!         Some(drag) if drag.phase == DragPhase::Move => drag.offset.x,
+//!         _ => 0.0,
+//!     };
+//!     let dismissed = move || {
+//!         gesture
+//!             .swipe
+//!             .get()
+//!             .is_some_and(|swipe| swipe.direction == SwipeDirection::Left)
+//!     };
+//!
+//!     view! {
+//!         <div
+//!             node_ref=card
+//!             class="card"
+//!             class:dismissed=dismissed
+//!             style:touch-action="pan-y"
+//!             style:transform=move || format!("translateX({}px)", offset())
+//!         >
+//!             "Swipe left to dismiss"
+//!         </div>
+//!     }
+//! }
+//! ```
+//!
+//! [`use_gesture_with_options`] changes the thresholds that tell the gestures apart, and calls a
+//! callback with every gesture as it is recognized, which is where anything that must happen once
+//! per gesture belongs.
+//!
+//! The pointer that starts a gesture is captured by the element, so that the gesture goes on when
+//! it leaves the element. Browsers take over touches to scroll or zoom the page, and cancel the
+//! pointers when they do, which cancels the drag or pinch in progress: elements that handle drags
+//! or swipes in a direction the page scrolls in should be given a `touch-action` that leaves that
+//! direction to them, like `none` or `pan-y`.
+//!
+//! Listeners are only added in the browser, and removed when the reactive owner that added them is
+//! cleaned up. On the server, no gesture is ever recognized.
+
+use leptos_dom::helpers::{set_timeout_with_handle, TimeoutHandle};
+use reactive_graph::{
+    effect::Effect,
+    owner::on_cleanup,
+    signal::RwSignal,
+    traits::{Get, Set},
+    wrappers::read::Signal,
+};
+use send_wrapper::SendWrapper;
+use std::{cell::RefCell, rc::Rc, time::Duration};
+use tachys::{html::element::ElementType, reactive_graph::node_ref::NodeRef};
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::{Element, PointerEvent};
+
+/// A position on the page, or a distance between two positions, in CSS pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Point {
+    /// The horizontal coordinate, growing to the right.
+    pub x: f64,
+    /// The vertical coordinate, growing downwards.
+    pub y: f64,
+}
+
+impl Point {
+    /// Creates a point.
+    pub fn new(x: f64, y: f64) -> Self {
+        Self { x, y }
+    }
+
+    /// The distance from the origin to the point.
+    pub fn length(self) -> f64 {
+        self.x.hypot(self.y)
+    }
+
+    fn minus(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+
+    fn midpoint(self, other: Point) -> Point {
+        Point::new((self.x + other.x) / 2.0, (self.y + other.y) / 2.0)
+    }
+}
+
+/// A pointer that was pressed and released without moving.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tap {
+    /// Where the pointer was released, relative to the viewport.
+    pub position: Point,
+}
+
+/// A pointer that was held down without moving for [`GestureOptions::long_press`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LongPress {
+    /// Where the pointer is held down, relative to the viewport.
+    pub position: Point,
+}
+
+/// The direction of a [`Swipe`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SwipeDirection {
+    /// Towards the left of the screen.
+    Left,
+    /// Towards the right of the screen.
+    Right,
+    /// Towards the top of the screen.
+    Up,
+    /// Towards the bottom of the screen.
+    Down,
+}
+
+/// A quick drag that ended while the pointer was still moving fast, like flicking a card away.
+///
+/// A swipe is recognized when a drag ends, after its [`DragPhase::End`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Swipe {
+    /// The direction the pointer moved in the most.
+    pub direction: SwipeDirection,
+    /// How far the pointer moved, from where it was pressed to where it was released.
+    pub offset: Point,
+    /// How fast the pointer moved, in CSS pixels per millisecond.
+    pub velocity: f64,
+}
+
+/// How far along a [`Drag`] or a [`Pinch`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DragPhase {
+    /// The gesture has just been recognized.
+    Start,
+    /// The pointers have moved.
+    Move,
+    /// The pointers were released.
+    End,
+    /// The gesture was interrupted, because the browser took over the pointers, or because
+    /// another pointer turned a drag into a pinch. Whatever the gesture changed should be undone.
+    Cancel,
+}
+
+/// A single pointer that moved farther than [`GestureOptions::tap_tolerance`] while pressed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Drag {
+    /// How far along the drag is.
+    pub phase: DragPhase,
+    /// Where the pointer was pressed, relative to the viewport.
+    pub start: Point,
+    /// Where the pointer is now, relative to the viewport.
+    pub position: Point,
+    /// How far the pointer has moved since it was pressed.
+    pub offset: Point,
+}
+
+/// Two pointers moving closer together or farther apart.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pinch {
+    /// How far along the pinch is.
+    pub phase: DragPhase,
+    /// The distance between the pointers, relative to their distance when the pinch started.
+    pub scale: f64,
+    /// The point halfway between the pointers, relative to the viewport.
+    pub center: Point,
+}
+
+/// Any gesture, as passed to the callback of [`use_gesture_with_options`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Gesture {
+    /// See [`Tap`].
+    Tap(Tap),
+    /// See [`LongPress`].
+    LongPress(LongPress),
+    /// See [`Swipe`].
+    Swipe(Swipe),
+    /// See [`Drag`].
+    Drag(Drag),
+    /// See [`Pinch`].
+    Pinch(Pinch),
+}
+
+/// Options that configure [`use_gesture_with_options`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct GestureOptions {
+    /// How far, in CSS pixels, a pointer can move before a press becomes a drag. Defaults to `10`.
+    pub tap_tolerance: f64,
+    /// How long a pointer has to be held down to make a long press. Defaults to 500ms.
+    pub long_press: Duration,
+    /// How far, in CSS pixels, a drag has to move to be a swipe. Defaults to `30`.
+    pub swipe_distance: f64,
+    /// How fast, in CSS pixels per millisecond, a drag has to move to be a swipe. Defaults to
+    /// `0.3`.
+    pub swipe_velocity: f64,
+}
+
+impl Default for GestureOptions {
+    fn default() -> Self {
+        Self {
+            tap_tolerance: 10.0,
+            long_press: Duration::from_millis(500),
+            swipe_distance: 30.0,
+            swipe_velocity: 0.3,
+        }
+    }
+}
+
+impl GestureOptions {
+    /// Sets how far a pointer can move before a press becomes a drag.
+    pub fn tap_tolerance(mut self, pixels: f64) -> Self {
+        self.tap_tolerance = pixels;
+        self
+    }
+
+    /// Sets how long a pointer has to be held down to make a long press.
+    pub fn long_press(mut self, duration: Duration) -> Self {
+        self.long_press = duration;
+        self
+    }
+
+    /// Sets how far and how fast a drag has to move to be a swipe.
+    pub fn swipe(mut self, distance: f64, velocity: f64) -> Self {
+        self.swipe_distance = distance;
+        self.swipe_velocity = velocity;
+        self
+    }
+}
+
+/// The latest gesture of each kind recognized on an element, created with [`use_gesture`].
+#[derive(Debug, Clone, Copy)]
+pub struct UseGesture {
+    /// The latest tap, if any. Reactive.
+    pub tap: Signal<Option<Tap>>,
+    /// The latest long press, if any. Reactive.
+    pub long_press: Signal<Option<LongPress>>,
+    /// The latest swipe, if any. Reactive.
+    pub swipe: Signal<Option<Swipe>>,
+    /// The latest step of the latest drag, if any. Reactive.
+    pub drag: Signal<Option<Drag>>,
+    /// The latest step of the latest pinch, if any. Reactive.
+    pub pinch: Signal<Option<Pinch>>,
+}
+
+/// Recognizes gestures on the element that `node_ref` refers to.
+pub fn use_gesture<E>(node_ref: NodeRef<E>) -> UseGesture
+where
+    E: ElementType,
+    E::Output: JsCast + Clone + 'static,
+{
+    use_gesture_with_options(node_ref, GestureOptions::default(), |_| {})
+}
+
+/// Recognizes gestures on the element that `node_ref` refers to, with the thresholds set in
+/// `options`, and calls `on_gesture` with each of them.
+///
+/// If `node_ref` is loaded with another element later, gestures are recognized on that one
+/// instead.
+pub fn use_gesture_with_options<E>(
+    node_ref: NodeRef<E>,
+    options: GestureOptions,
+    on_gesture: impl Fn(Gesture) + 'static,
+) -> UseGesture
+where
+    E: ElementType,
+    E::Output: JsCast + Clone + 'static,
+{
+    let tap = RwSignal::new(None);
+    let long_press = RwSignal::new(None);
+    let swipe = RwSignal::new(None);
+    let drag = RwSignal::new(None);
+    let pinch = RwSignal::new(None);
+    let on_gesture: Rc<dyn Fn(Gesture)> = Rc::new(move |gesture| {
+        match gesture {
+            Gesture::Tap(gesture) => tap.set(Some(gesture)),
+            Gesture::LongPress(gesture) => long_press.set(Some(gesture)),
+            Gesture::Swipe(gesture) => swipe.set(Some(gesture)),
+            Gesture::Drag(gesture) => drag.set(Some(gesture)),
+            Gesture::Pinch(gesture) => pinch.set(Some(gesture)),
+        }
+        on_gesture(gesture);
+    });
+
+    // effects only run in the browser, so no gesture is recognized on the server
+    Effect::new(move |_| {
+        let Some(el) = node_ref.get() else {
+            return;
+        };
+        let el = el.unchecked_into::<Element>();
+        let recognizer =
+            Rc::new(RefCell::new(Recognizer::new(options.clone())));
+        let timer = Rc::new(RefCell::new(None::<TimeoutHandle>));
+
+        // the gestures are emitted once the recognizer is no longer borrowed, because the
+        // callback can do anything, including cleaning up this element
+        let emit = {
+            let on_gesture = Rc::clone(&on_gesture);
+            move |gestures: Vec<Gesture>| {
+                for gesture in gestures {
+                    on_gesture(gesture);
+                }
+            }
+        };
+        let arm_long_press = {
+            let recognizer = Rc::clone(&recognizer);
+            let timer = Rc::clone(&timer);
+            let emit = emit.clone();
+            let duration = options.long_press;
+            move || {
+                if let Some(handle) = timer.borrow_mut().take() {
+                    handle.clear();
+                }
+                let Some(press) = recognizer.borrow().pending_press() else {
+                    return;
+                };
+                let recognizer = Rc::clone(&recognizer);
+                let emit = emit.clone();
+                *timer.borrow_mut() = set_timeout_with_handle(
+                    move || {
+                        let gesture = recognizer.borrow_mut().long_press(press);
+                        emit(gesture.into_iter().collect());
+                    },
+                    duration,
+                )
+                .ok();
+            }
+        };
+
+        let on_pointerdown = Closure::<dyn Fn(PointerEvent)>::new({
+            let recognizer = Rc::clone(&recognizer);
+            let emit = emit.clone();
+            let el = el.clone();
+            move |ev: PointerEvent| {
+                // only the main button of a mouse starts a gesture
+                if ev.pointer_type() == "mouse" && ev.button() != 0 {
+                    return;
+                }
+                let gestures = recognizer.borrow_mut().down(
+                    ev.pointer_id(),
+                    position(&ev),
+                    ev.time_stamp(),
+                );
+                if recognizer.borrow().is_tracking(ev.pointer_id()) {
+                    // keeps the moves coming once the pointer leaves the element
+                    _ = el.set_pointer_capture(ev.pointer_id());
+                }
+                arm_long_press();
+                emit(gestures);
+            }
+        });
+        let on_pointermove = Closure::<dyn Fn(PointerEvent)>::new({
+            let recognizer = Rc::clone(&recognizer);
+            let emit = emit.clone();
+            move |ev: PointerEvent| {
+                let gestures = recognizer.borrow_mut().moved(
+                    ev.pointer_id(),
+                    position(&ev),
+                    ev.time_stamp(),
+                );
+                emit(gestures);
+            }
+        });
+        let on_pointerup = Closure::<dyn Fn(PointerEvent)>::new({
+            let recognizer = Rc::clone(&recognizer);
+            let emit = emit.clone();
+            move |ev: PointerEvent| {
+                let gestures = recognizer.borrow_mut().up(
+                    ev.pointer_id(),
+                    position(&ev),
+                    ev.time_stamp(),
+                );
+                emit(gestures);
+            }
+        });
+        // the browser cancels pointers it takes over, and capture is lost when the element is
+        // removed, neither of which is followed by a pointerup
+        let on_pointercancel = Closure::<dyn Fn(PointerEvent)>::new({
+            let recognizer = Rc::clone(&recognizer);
+            move |ev: PointerEvent| {
+                let gestures = recognizer.borrow_mut().cancel(ev.pointer_id());
+                emit(gestures);
+            }
+        });
+
+        let listeners = [
+            ("pointerdown", on_pointerdown),
+            ("pointermove", on_pointermove),
+            ("pointerup", on_pointerup),
+            ("pointercancel", on_pointercancel),
+        ];
+        for (event, listener) in &listeners {
+            _ = el.add_event_listener_with_callback(
+                event,
+                listener.as_ref().unchecked_ref(),
+            );
+        }
+        _ = el.add_event_listener_with_callback(
+            "lostpointercapture",
+            listeners[3].1.as_ref().unchecked_ref(),
+        );
+
+        let cleanup = SendWrapper::new((el, listeners, timer));
+        on_cleanup(move || {
+            let (el, listeners, timer) = cleanup.take();
+            for (event, listener) in &listeners {
+                _ = el.remove_event_listener_with_callback(
+                    event,
+                    listener.as_ref().unchecked_ref(),
+                );
+            }
+            _ = el.remove_event_listener_with_callback(
+                "lostpointercapture",
+                listeners[3].1.as_ref().unchecked_ref(),
+            );
+            let handle = timer.borrow_mut().take();
+            if let Some(handle) = handle {
+                handle.clear();
+            }
+        });
+    });
+
+    UseGesture {
+        tap: tap.into(),
+        long_press: long_press.into(),
+        swipe: swipe.into(),
+        drag: drag.into(),
+        pinch: pinch.into(),
+    }
+}
+
+fn position(ev: &PointerEvent) -> Point {
+    Point::new(ev.client_x() as f64, ev.client_y() as f64)
+}
+
+/// A pointer that is pressed.
+#[derive(Debug, Clone, Copy)]
+struct Pointer {
+    id: i32,
+    start: Point,
+    start_time: f64,
+    position: Point,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum State {
+    /// No pointer is pressed.
+    Idle,
+    /// A single pointer is pressed, and has not moved yet. The number tells presses apart, so
+    /// that the long press timer of an earlier one is ignored.
+    Pressed(u64),
+    /// A single pointer was held down long enough to make a long press.
+    LongPressed,
+    /// A single pointer is being dragged.
+    Dragging,
+    /// Two pointers are pinching, which were this far apart when the pinch started.
+    Pinching(f64),
+    /// The gesture has ended or was cancelled, and nothing more is recognized until every pointer
+    /// has been released.
+    Settling,
+}
+
+/// Turns pointer events into gestures, without touching the DOM.
+#[derive(Debug)]
+struct Recognizer {
+    options: GestureOptions,
+    pointers: Vec<Pointer>,
+    state: State,
+    presses: u64,
+}
+
+impl Recognizer {
+    fn new(options: GestureOptions) -> Self {
+        Self {
+            options,
+            pointers: Vec::new(),
+            state: State::Idle,
+            presses: 0,
+        }
+    }
+
+    fn is_tracking(&self, id: i32) -> bool {
+        self.pointers.iter().any(|pointer| pointer.id == id)
+    }
+
+    /// The press that a long press timer should be started for, if any.
+    fn pending_press(&self) -> Option<u64> {
+        match self.state {
+            State::Pressed(press) => Some(press),
+            _ => None,
+        }
+    }
+
+    fn pointer_mut(&mut self, id: i32) -> Option<&mut Pointer> {
+        self.pointers.iter_mut().find(|pointer| pointer.id == id)
+    }
+
+    fn drag(&self, phase: DragPhase) -> Option<Gesture> {
+        let pointer = self.pointers.first()?;
+        Some(Gesture::Drag(Drag {
+            phase,
+            start: pointer.start,
+            position: pointer.position,
+            offset: pointer.position.minus(pointer.start),
+        }))
+    }
+
+    fn pinch(&self, phase: DragPhase) -> Option<Gesture> {
+        let [a, b] = self.pointers.as_slice() else {
+            return None;
+        };
+        let distance = a.position.minus(b.position).length();
+        let scale = match self.state {
+            State::Pinching(initial) if initial > 0.0 => distance / initial,
+            _ => 1.0,
+        };
+        Some(Gesture::Pinch(Pinch {
+            phase,
+            scale,
+            center: a.position.midpoint(b.position),
+        }))
+    }
+
+    fn down(&mut self, id: i32, position: Point, time: f64) -> Vec<Gesture> {
+        if self.is_tracking(id) || self.pointers.len() >= 2 {
+            return Vec::new();
+        }
+        let mut gestures = Vec::new();
+        self.pointers.push(Pointer {
+            id,
+            start: position,
+            start_time: time,
+            position,
+        });
+        match self.state {
+            State::Idle => {
+                self.presses += 1;
+                self.state = State::Pressed(self.presses);
+            }
+            // a second pointer turns a press or a drag into a pinch
+            State::Pressed(_) | State::LongPressed | State::Dragging => {
+                if self.state == State::Dragging {
+                    gestures.extend(self.drag(DragPhase::Cancel));
+                }
+                let [a, b] = self.pointers.as_slice() else {
+                    unreachable!()
+                };
+                self.state =
+                    State::Pinching(a.position.minus(b.position).length());
+                gestures.extend(self.pinch(DragPhase::Start));
+            }
+            State::Pinching(_) | State::Settling => {}
+        }
+        gestures
+    }
+
+    fn moved(&mut self, id: i32, position: Point, _time: f64) -> Vec<Gesture> {
+        let Some(pointer) = self.pointer_mut(id) else {
+            return Vec::new();
+        };
+        pointer.position = position;
+        let moved = position.minus(pointer.start).length();
+        match self.state {
+            State::Pressed(_) | State::LongPressed
+                if moved > self.options.tap_tolerance =>
+            {
+                self.state = State::Dragging;
+                [DragPhase::Start, DragPhase::Move]
+                    .into_iter()
+                    .filter_map(|phase| self.drag(phase))
+                    .collect()
+            }
+            State::Dragging => self.drag(DragPhase::Move).into_iter().collect(),
+            State::Pinching(_) => {
+                self.pinch(DragPhase::Move).into_iter().collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn up(&mut self, id: i32, position: Point, time: f64) -> Vec<Gesture> {
+        let Some(pointer) = self.pointer_mut(id) else {
+            return Vec::new();
+        };
+        pointer.position = position;
+        let pointer = *pointer;
+        let gestures = match self.state {
+            State::Pressed(_) => vec![Gesture::Tap(Tap { position })],
+            State::Dragging => {
+                let mut gestures: Vec<_> =
+                    self.drag(DragPhase::End).into_iter().collect();
+                gestures.extend(self.swipe(&pointer, time));
+                gestures
+            }
+            State::Pinching(_) => {
+                self.pinch(DragPhase::End).into_iter().collect()
+            }
+            _ => Vec::new(),
+        };
+        self.release(id);
+        gestures
+    }
+
+    fn cancel(&mut self, id: i32) -> Vec<Gesture> {
+        if !self.is_tracking(id) {
+            return Vec::new();
+        }
+        let gestures = match self.state {
+            State::Dragging => self.drag(DragPhase::Cancel),
+            State::Pinching(_) => self.pinch(DragPhase::Cancel),
+            _ => None,
+        };
+        self.release(id);
+        gestures.into_iter().collect()
+    }
+
+    /// Called once the long press timer of `press` has run out.
+    fn long_press(&mut self, press: u64) -> Option<Gesture> {
+        if self.state != State::Pressed(press) {
+            return None;
+        }
+        self.state = State::LongPressed;
+        Some(Gesture::LongPress(LongPress {
+            position: self.pointers.first()?.position,
+        }))
+    }
+
+    fn swipe(&self, pointer: &Pointer, time: f64) -> Option<Gesture> {
+        let offset = pointer.position.minus(pointer.start);
+        let distance = offset.length();
+        let duration = (time - pointer.start_time).max(1.0);
+        let velocity = distance / duration;
+        if distance < self.options.swipe_distance
+            || velocity < self.options.swipe_velocity
+        {
+            return None;
+        }
+        let direction = if offset.x.abs() >= offset.y.abs() {
+            if offset.x < 0.0 {
+                SwipeDirection::Left
+            } else {
+                SwipeDirection::Right
+            }
+        } else if offset.y < 0.0 {
+            SwipeDirection::Up
+        } else {
+            SwipeDirection::Down
+        };
+        Some(Gesture::Swipe(Swipe {
+            direction,
+            offset,
+            velocity,
+        }))
+    }
+
+    fn release(&mut self, id: i32) {
+        self.pointers.retain(|pointer| pointer.id != id);
+        self.state = if self.pointers.is_empty() {
+            State::Idle
+        } else {
+            State::Settling
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(x: f64, y: f64) -> Point {
+        Point::new(x, y)
+    }
+
+    #[test]
+    fn presses_without_moving_are_taps_or_long_presses() {
+        let mut recognizer = Recognizer::new(GestureOptions::default());
+        assert!(recognizer.down(1, at(10.0, 10.0), 0.0).is_empty());
+        assert!(recognizer.moved(1, at(14.0, 12.0), 50.0).is_empty());
+        assert_eq!(
+            recognizer.up(1, at(14.0, 12.0), 100.0),
+            [Gesture::Tap(Tap {
+                position: at(14.0, 12.0)
+            })]
+        );
+
+        recognizer.down(1, at(10.0, 10.0), 200.0);
+        let press = recognizer.pending_press().unwrap();
+        assert_eq!(
+            recognizer.long_press(press),
+            Some(Gesture::LongPress(LongPress {
+                position: at(10.0, 10.0)
+            }))
+        );
+        assert!(recognizer.up(1, at(10.0, 10.0), 800.0).is_empty());
+
+        // the timer of an earlier press is ignored
+        recognizer.down(1, at(10.0, 10.0), 900.0);
+        assert_eq!(recognizer.long_press(press), None);
+    }
+
+    #[test]
+    fn fast_drags_are_swipes() {
+        let mut recognizer = Recognizer::new(GestureOptions::default());
+        recognizer.down(1, at(100.0, 0.0), 0.0);
+        let gestures = recognizer.moved(1, at(80.0, 2.0), 20.0);
+        assert!(matches!(
+            gestures.as_slice(),
+            [
+                Gesture::Drag(Drag {
+                    phase: DragPhase::Start,
+                    ..
+                }),
+                Gesture::Drag(Drag {
+                    phase: DragPhase::Move,
+                    ..
+                })
+            ]
+        ));
+        let gestures = recognizer.up(1, at(40.0, 5.0), 100.0);
+        assert!(matches!(
+            gestures.as_slice(),
+            [
+                Gesture::Drag(Drag {
+                    phase: DragPhase::End,
+                    offset: Point { x: -60.0, y: 5.0 },
+                    ..
+                }),
+                Gesture::Swipe(Swipe {
+                    direction: SwipeDirection::Left,
+                    ..
+                })
+            ]
+        ));
+
+        // a slow drag is only a drag
+        recognizer.down(1, at(0.0, 0.0), 1000.0);
+        recognizer.moved(1, at(0.0, 50.0), 1500.0);
+        assert_eq!(recognizer.up(1, at(0.0, 100.0), 2000.0).len(), 1);
+    }
+
+    #[test]
+    fn second_pointers_turn_drags_into_pinches() {
+        let mut recognizer = Recognizer::new(GestureOptions::default());
+        recognizer.down(1, at(0.0, 0.0), 0.0);
+        recognizer.moved(1, at(20.0, 0.0), 10.0);
+        let gestures = recognizer.down(2, at(120.0, 0.0), 20.0);
+        assert!(matches!(
+            gestures.as_slice(),
+            [
+                Gesture::Drag(Drag {
+                    phase: DragPhase::Cancel,
+                    ..
+                }),
+                Gesture::Pinch(Pinch {
+                    phase: DragPhase::Start,
+                    scale: 1.0,
+                    ..
+                })
+            ]
+        ));
+        assert_eq!(
+            recognizer.moved(2, at(220.0, 0.0), 30.0),
+            [Gesture::Pinch(Pinch {
+                phase: DragPhase::Move,
+                scale: 2.0,
+                center: at(120.0, 0.0)
+            })]
+        );
+
+        // the other pointer does nothing until it is released too
+        assert!(matches!(
+            recognizer.up(1, at(20.0, 0.0), 40.0).as_slice(),
+            [Gesture::Pinch(Pinch {
+                phase: DragPhase::End,
+                ..
+            })]
+        ));
+        assert!(recognizer.moved(2, at(300.0, 0.0), 50.0).is_empty());
+        assert!(recognizer.up(2, at(300.0, 0.0), 60.0).is_empty());
+        assert_eq!(recognizer.state, State::Idle);
+    }
+
+    #[test]
+    fn cancelled_pointers_cancel_the_gesture() {
+        let mut recognizer = Recognizer::new(GestureOptions::default());
+        recognizer.down(1, at(0.0, 0.0), 0.0);
+        recognizer.moved(1, at(0.0, 40.0), 10.0);
+        assert!(matches!(
+            recognizer.cancel(1).as_slice(),
+            [Gesture::Drag(Drag {
+                phase: DragPhase::Cancel,
+                ..
+            })]
+        ));
+        // the pointerup that can follow is ignored, and so is the capture being lost
+        assert!(recognizer.up(1, at(0.0, 80.0), 20.0).is_empty());
+        assert!(recognizer.cancel(1).is_empty());
+
+        recognizer.down(1, at(0.0, 0.0), 100.0);
+        assert!(recognizer.cancel(1).is_empty());
+        assert_eq!(recognizer.pending_press(), None);
+    }
+}
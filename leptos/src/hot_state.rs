@@ -0,0 +1,394 @@
+//! Signal and store values that survive hot reloads during development.
+//!
+//! Changes to the markup of a `view!` are patched into the page without touching the reactive
+//! system, but any other change makes `cargo leptos watch` reload the whole page, which resets
+//! the state of the app. With this feature, the values of the signals that are alive when such a
+//! reload starts are saved, and given back to the signals that are created at the same place in
+//! the code once the page has loaded again, so that the app continues where it left off.
+//!
+//! This happens on its own for the signals created with [`signal`](crate::prelude::signal) or
+//! [`RwSignal::new`](crate::prelude::RwSignal::new) in the crates of the app's workspace, rather
+//! than in its dependencies, whose value is a string, a number, a `bool`, or an `Option` or `Vec`
+//! of one of these, which covers the state of most forms:
+//!
+//! ```rust
+//! use leptos::prelude::*;
+//!
+//! #[component]
+//! pub fn SignUp() -> impl IntoView {
+//!     // keeps what was typed when the component is changed
+//!     let (email, set_email) = signal(String::new());
+//!
+//!     view! {
+//!         <input
+//!             prop:value=email
+//!             on:input:target=move |ev| set_email.set(ev.target().value())
+//!         />
+//!     }
+//! }
+//! ```
+//!
+//! Signals of other types that can be serialized are kept with [`hot_signal`], which creates one,
+//! or [`preserve`], which registers an existing signal or store.
+//!
+//! Signals are matched by the file, line and column they are created at, and by which of the
+//! signals alive there they are, so a component that is rendered several times gets each of its
+//! values back as long as the instances are created in the same order. A signal that a change to
+//! the code has moved to another place starts again from its initial value, as do signals whose
+//! value no longer deserializes into their type because it has changed. Signals whose owner has
+//! been cleaned up are not saved, and give their place to the next one created there.
+//!
+//! Values are only kept in debug builds in the browser, and only across the reloads started by
+//! the hot-reload script. Otherwise, these are plain signals.
+
+use reactive_graph::{
+    signal::{arc_signal, ReadSignal, WriteSignal},
+    traits::{GetUntracked, Set},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use std::panic::Location;
+
+/// Creates a signal like [`signal`](reactive_graph::signal::signal), whose value is kept across
+/// hot reloads.
+#[track_caller]
+pub fn hot_signal<T>(value: T) -> (ReadSignal<T>, WriteSignal<T>)
+where
+    T: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    let location = Location::caller();
+    // an `ArcRwSignal` isn't registered on its own, unlike `signal`
+    let (read, write) = arc_signal(value);
+    let (read, write) = (ReadSignal::from(read), WriteSignal::from(write));
+    register(location, read, write);
+    (read, write)
+}
+
+/// Keeps the value of an existing signal or store across hot reloads. If a value was saved for
+/// it, this sets it right away.
+#[track_caller]
+pub fn preserve<S, T>(signal: S)
+where
+    S: GetUntracked<Value = T> + Set<Value = T> + Clone + 'static,
+    T: Serialize + DeserializeOwned + 'static,
+{
+    register(Location::caller(), signal.clone(), signal);
+}
+
+/// Registers the signals created from now on with the hot-reload state, which mounting or
+/// hydrating the app does.
+pub(crate) fn install() {
+    #[cfg(all(debug_assertions, target_arch = "wasm32"))]
+    reactive_graph::hot_state::on_signal_created(browser::signal_created);
+}
+
+#[cfg(not(all(debug_assertions, target_arch = "wasm32")))]
+fn register<R, W, T>(_location: &'static Location<'static>, _read: R, _write: W)
+where
+    R: GetUntracked<Value = T> + 'static,
+    W: Set<Value = T>,
+    T: Serialize + DeserializeOwned + 'static,
+{
+}
+
+#[cfg(all(debug_assertions, target_arch = "wasm32"))]
+fn register<R, W, T>(location: &'static Location<'static>, read: R, write: W)
+where
+    R: GetUntracked<Value = T> + 'static,
+    W: Set<Value = T>,
+    T: Serialize + DeserializeOwned + 'static,
+{
+    browser::register(location, read, write);
+}
+
+#[cfg(any(test, all(debug_assertions, target_arch = "wasm32")))]
+mod registry {
+    /// A place in the code that creates signals.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub(super) struct Site {
+        pub(super) file: String,
+        pub(super) line: u32,
+        pub(super) column: u32,
+    }
+
+    /// Reads the current value of a signal as JSON, if it is still alive.
+    pub(super) type Snapshot = Box<dyn Fn() -> Option<String>>;
+
+    #[derive(Default)]
+    pub(super) struct Registry {
+        /// The values saved by the last reload, with the site and index of their signals.
+        saved: Vec<(Site, usize, String)>,
+        live: Vec<Instance>,
+        next_id: u64,
+    }
+
+    struct Instance {
+        id: u64,
+        site: Site,
+        index: usize,
+        snapshot: Snapshot,
+    }
+
+    impl Registry {
+        pub(super) fn new(saved: Vec<(Site, usize, String)>) -> Self {
+            Self {
+                saved,
+                ..Default::default()
+            }
+        }
+
+        /// Adds a signal created at `site`, and returns its id along with the value saved for it.
+        pub(super) fn add(
+            &mut self,
+            site: Site,
+            snapshot: Snapshot,
+        ) -> (u64, Option<String>) {
+            // the first index that no live signal at the same site holds
+            let index = (0..)
+                .find(|index| {
+                    !self.live.iter().any(|instance| {
+                        instance.site == site && instance.index == *index
+                    })
+                })
+                .unwrap_or_default();
+            let saved = self.take_saved(&site, index);
+            let id = self.next_id;
+            self.next_id += 1;
+            self.live.push(Instance {
+                id,
+                site,
+                index,
+                snapshot,
+            });
+            (id, saved)
+        }
+
+        /// Removes a signal whose owner has been cleaned up.
+        pub(super) fn remove(&mut self, id: u64) {
+            self.live.retain(|instance| instance.id != id);
+        }
+
+        /// The current values of the live signals.
+        pub(super) fn snapshot(&self) -> Vec<(Site, usize, String)> {
+            self.live
+                .iter()
+                .filter_map(|instance| {
+                    let value = (instance.snapshot)()?;
+                    Some((instance.site.clone(), instance.index, value))
+                })
+                .collect()
+        }
+
+        fn take_saved(&mut self, site: &Site, index: usize) -> Option<String> {
+            let position = self
+                .saved
+                .iter()
+                .position(|(saved, i, _)| saved == site && *i == index)?;
+            Some(self.saved.remove(position).2)
+        }
+    }
+}
+
+#[cfg(all(debug_assertions, target_arch = "wasm32"))]
+mod browser {
+    use super::registry::{Registry, Site};
+    use reactive_graph::{
+        owner::on_cleanup,
+        signal::ArcRwSignal,
+        traits::{GetUntracked, Set},
+    };
+    use serde::{de::DeserializeOwned, Serialize};
+    use std::{any::Any, cell::RefCell, panic::Location, path::Path};
+
+    /// Set by the hot-reload script right before it reloads the page.
+    const RELOADING_KEY: &str = "leptos-hot-reload";
+    /// The values that were saved when the page was unloaded.
+    const STATE_KEY: &str = "leptos-hot-state";
+
+    /// The file, line, column and index of a signal, and its value.
+    type Saved = (String, u32, u32, usize, String);
+
+    thread_local! {
+        static REGISTRY: RefCell<Option<Registry>> = const { RefCell::new(None) };
+    }
+
+    pub(super) fn register<R, W, T>(
+        location: &'static Location<'static>,
+        read: R,
+        write: W,
+    ) where
+        R: GetUntracked<Value = T> + 'static,
+        W: Set<Value = T>,
+        T: Serialize + DeserializeOwned + 'static,
+    {
+        let site = Site {
+            file: location.file().to_string(),
+            line: location.line(),
+            column: location.column(),
+        };
+        let (id, saved) = REGISTRY.with_borrow_mut(|registry| {
+            registry.get_or_insert_with(init).add(
+                site,
+                Box::new(move || {
+                    read.try_get_untracked()
+                        .and_then(|value| serde_json::to_string(&value).ok())
+                }),
+            )
+        });
+        on_cleanup(move || {
+            REGISTRY.with_borrow_mut(|registry| {
+                if let Some(registry) = registry {
+                    registry.remove(id);
+                }
+            })
+        });
+
+        // set outside of the borrow, as effects may create more hot signals
+        if let Some(value) =
+            saved.and_then(|json| serde_json::from_str::<T>(&json).ok())
+        {
+            write.set(value);
+        }
+    }
+
+    /// Registers a signal created with `signal` or `RwSignal::new`, if its value can be saved.
+    pub(super) fn signal_created(
+        location: &'static Location<'static>,
+        signal: &dyn Any,
+    ) {
+        // the files of dependencies outside of the workspace have absolute paths
+        if Path::new(location.file()).is_absolute() {
+            return;
+        }
+        macro_rules! register_types {
+            ($($ty:ty),* $(,)?) => {
+                $(
+                    register_types!(@one $ty);
+                    register_types!(@one Option<$ty>);
+                    register_types!(@one Vec<$ty>);
+                )*
+            };
+            (@one $ty:ty) => {
+                if let Some(signal) = signal.downcast_ref::<ArcRwSignal<$ty>>() {
+                    register(location, signal.clone(), signal.clone());
+                    return;
+                }
+            };
+        }
+        register_types!(
+            String, bool, char, i8, i16, i32, i64, i128, isize, u8, u16, u32,
+            u64, u128, usize, f32, f64,
+        );
+    }
+
+    /// Loads the values saved by the last hot reload, and saves the current ones when the page is
+    /// unloaded.
+    fn init() -> Registry {
+        let storage = session_storage();
+        let mut saved = Vec::new();
+        if let Some(storage) = &storage {
+            let reloading = storage.get_item(RELOADING_KEY).ok().flatten();
+            let state = storage.get_item(STATE_KEY).ok().flatten();
+            _ = storage.remove_item(RELOADING_KEY);
+            _ = storage.remove_item(STATE_KEY);
+            if reloading.is_some() {
+                saved = state
+                    .and_then(|state| {
+                        serde_json::from_str::<Vec<Saved>>(&state).ok()
+                    })
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|(file, line, column, index, value)| {
+                        (Site { file, line, column }, index, value)
+                    })
+                    .collect();
+            }
+        }
+
+        let handle = leptos_dom::helpers::window_event_listener_untyped(
+            "pagehide",
+            |_| save(),
+        );
+        // the listener lives as long as the page
+        std::mem::forget(handle);
+
+        Registry::new(saved)
+    }
+
+    fn save() {
+        let state = REGISTRY.with_borrow(|registry| {
+            let values = registry
+                .as_ref()?
+                .snapshot()
+                .into_iter()
+                .map(|(site, index, value)| {
+                    (site.file, site.line, site.column, index, value)
+                })
+                .collect::<Vec<Saved>>();
+            serde_json::to_string(&values).ok()
+        });
+        if let (Some(storage), Some(state)) = (session_storage(), state) {
+            _ = storage.set_item(STATE_KEY, &state);
+        }
+    }
+
+    fn session_storage() -> Option<web_sys::Storage> {
+        leptos_dom::helpers::window()
+            .session_storage()
+            .ok()
+            .flatten()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::registry::{Registry, Site};
+
+    fn site(line: u32, column: u32) -> Site {
+        Site {
+            file: "src/app.rs".to_string(),
+            line,
+            column,
+        }
+    }
+
+    fn value(value: &'static str) -> super::registry::Snapshot {
+        Box::new(move || Some(value.to_string()))
+    }
+
+    #[test]
+    fn removed_signals_give_their_index_to_the_next_one() {
+        let mut registry = Registry::default();
+        let (first, _) = registry.add(site(10, 5), value("a"));
+        registry.add(site(10, 5), value("b"));
+        assert_eq!(registry.snapshot().len(), 2);
+
+        // the first instance is cleaned up, and a new one takes its place
+        registry.remove(first);
+        registry.add(site(10, 5), value("c"));
+        let mut saved = registry.snapshot();
+        saved.sort_by_key(|(_, index, _)| *index);
+        assert_eq!(
+            saved,
+            [
+                (site(10, 5), 0, "c".to_string()),
+                (site(10, 5), 1, "b".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn values_are_only_given_back_at_the_same_site() {
+        let mut registry = Registry::new(vec![
+            (site(4, 5), 0, "z".to_string()),
+            (site(10, 5), 0, "a".to_string()),
+            (site(10, 5), 1, "b".to_string()),
+        ]);
+
+        // lines were added below line 4, which moved the signals of line 10 down
+        let mut add =
+            |line, column| registry.add(site(line, column), value("")).1;
+        assert_eq!(add(4, 5).as_deref(), Some("z"));
+        assert_eq!(add(14, 5), None);
+        assert_eq!(add(14, 5), None);
+    }
+}
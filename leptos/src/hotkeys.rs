@@ -0,0 +1,620 @@
+//! Keyboard shortcuts, handled by a single registry for the whole page.
+//!
+//! [`use_hotkeys`] calls a handler when a shortcut is pressed, for as long as the reactive owner
+//! that called it is alive:
+//!
+//! ```rust
+//! use leptos::{
+//!     hotkeys::{provide_exclusive_hotkey_scope, use_hotkeys},
+//!     prelude::*,
+//! };
+//!
+//! #[component]
+//! pub fn App() -> impl IntoView {
+//!     let (palette_open, set_palette_open) = signal(false);
+//!     use_hotkeys("mod+k", move |_| set_palette_open.set(true));
+//!     use_hotkeys("g h", move |_| leptos::logging::log!("go home"));
+//!
+//!     view! {
+//!         <Show when=move || palette_open.get()>
+//!             <CommandPalette on_close=move || set_palette_open.set(false)/>
+//!         </Show>
+//!     }
+//! }
+//!
+//! #[component]
+//! fn CommandPalette(on_close: impl Fn() + Send + Sync + 'static) -> impl IntoView {
+//!     // while the palette is open, only its own shortcuts work
+//!     provide_exclusive_hotkey_scope();
+//!     use_hotkeys("escape", move |_| on_close());
+//!
+//!     view! { <input placeholder="Type a command"/> }
+//! }
+//! ```
+//!
+//! A shortcut is made of keys joined with `+`, like `ctrl+shift+p`, where every key but the last
+//! is a modifier: `ctrl`, `alt`, `shift`, `meta`, or `mod`, which is `meta` (<kbd>⌘</kbd>) on
+//! Apple platforms and `ctrl` elsewhere. The last key is the [`key`] of the event, like `k`,
+//! `enter`, `escape`, `arrowup` or `?`, or for letters and digits, the key at that place on the
+//! keyboard. Shortcuts pressed one after the other, within a second, are separated by spaces,
+//! like `g h`, and alternatives by commas, like `mod+k, /`. `space`, `plus` and `comma` stand for
+//! the keys that are used as separators.
+//!
+//! [`key`]: https://developer.mozilla.org/en-US/docs/Web/API/KeyboardEvent/key
+//!
+//! Shortcuts belong to a scope. Each call to [`provide_hotkey_scope`] opens a new scope on top of
+//! the others, for the component that called it and its children, until it is cleaned up. When a
+//! key is pressed, the scopes are searched from the top one, and only the shortcut found in the
+//! highest scope is handled, so that a dialog can take over the shortcuts of the page. An
+//! exclusive scope, opened with [`provide_exclusive_hotkey_scope`], also keeps the shortcuts of
+//! the scopes below it from being handled at all.
+//!
+//! Shortcuts are not handled while an `<input>`, `<textarea>`, `<select>` or editable element has
+//! the focus, unless [`HotkeyOptions::enable_in_inputs`] is set, so that typing is never taken
+//! for a shortcut.
+//!
+//! Shortcuts are only registered in the browser; on the server, these functions do nothing.
+
+use leptos_dom::helpers::{
+    window, window_event_listener, WindowListenerHandle,
+};
+use reactive_graph::{
+    effect::Effect,
+    owner::{on_cleanup, shadow_context, with_context},
+};
+use std::{cell::RefCell, rc::Rc};
+use tachys::html::event::keydown;
+use wasm_bindgen::JsCast;
+use web_sys::{HtmlElement, KeyboardEvent};
+
+/// How long, in milliseconds, the next key of a sequence is waited for.
+const SEQUENCE_TIMEOUT: f64 = 1000.0;
+
+/// Options that configure [`use_hotkeys_with_options`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HotkeyOptions {
+    /// Whether the shortcut is handled while a text field or an editable element has the focus.
+    /// Defaults to `false`.
+    pub enable_in_inputs: bool,
+    /// Whether the default action of the last key, like opening the browser's search for
+    /// `mod+f`, is prevented. Defaults to `true`.
+    pub prevent_default: bool,
+}
+
+impl Default for HotkeyOptions {
+    fn default() -> Self {
+        Self {
+            enable_in_inputs: false,
+            prevent_default: true,
+        }
+    }
+}
+
+impl HotkeyOptions {
+    /// Handles the shortcut while a text field or an editable element has the focus.
+    pub fn enable_in_inputs(mut self) -> Self {
+        self.enable_in_inputs = true;
+        self
+    }
+
+    /// Lets the browser carry out the default action of the last key too.
+    pub fn allow_default(mut self) -> Self {
+        self.prevent_default = false;
+        self
+    }
+}
+
+/// The scope that the shortcuts registered by a component and its children belong to, opened
+/// with [`provide_hotkey_scope`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HotkeyScope(u64);
+
+impl HotkeyScope {
+    /// The scope of the shortcuts registered outside of any other scope, which is below every
+    /// other one.
+    pub const ROOT: HotkeyScope = HotkeyScope(0);
+}
+
+/// Opens a new scope above the current ones, for the shortcuts registered by the current
+/// component and its children. A shortcut in this scope is handled instead of the same shortcut
+/// in the scopes below it, while the others still work.
+///
+/// The scope is closed when the reactive owner that opened it is cleaned up.
+pub fn provide_hotkey_scope() -> HotkeyScope {
+    open_scope(false)
+}
+
+/// Opens a new scope above the current ones, like [`provide_hotkey_scope`], and keeps every
+/// shortcut of the scopes below it from being handled while it is open, as a modal dialog does.
+pub fn provide_exclusive_hotkey_scope() -> HotkeyScope {
+    open_scope(true)
+}
+
+fn open_scope(exclusive: bool) -> HotkeyScope {
+    let scope = HotkeyScope(registry::next_id());
+    shadow_context(scope);
+    // effects only run in the browser, so no scope is opened on the server
+    Effect::new(move |_| {
+        registry::open_scope(scope, exclusive);
+        on_cleanup(move || registry::close_scope(scope));
+    });
+    scope
+}
+
+/// Calls `handler` whenever the shortcut described by `keys` is pressed, with the event of its
+/// last key.
+///
+/// See the [module documentation](self) for how shortcuts are written.
+pub fn use_hotkeys(keys: &str, handler: impl Fn(KeyboardEvent) + 'static) {
+    use_hotkeys_with_options(keys, HotkeyOptions::default(), handler)
+}
+
+/// Calls `handler` whenever the shortcut described by `keys` is pressed, as configured by
+/// `options`.
+pub fn use_hotkeys_with_options(
+    keys: &str,
+    options: HotkeyOptions,
+    handler: impl Fn(KeyboardEvent) + 'static,
+) {
+    let keys = keys.to_string();
+    let scope = with_context(HotkeyScope::clone).unwrap_or(HotkeyScope::ROOT);
+    let handler: Rc<dyn Fn(KeyboardEvent)> = Rc::new(handler);
+
+    // effects only run in the browser, so no shortcut is registered on the server
+    Effect::new(move |_| {
+        let sequences = match parse(&keys, is_apple()) {
+            Ok(sequences) => sequences,
+            Err(e) => {
+                crate::logging::debug_warn!("invalid hotkey {keys:?}: {e}");
+                return;
+            }
+        };
+        let id = registry::register(Binding {
+            id: 0,
+            scope,
+            sequences,
+            options,
+            handler: Rc::clone(&handler),
+        });
+        on_cleanup(move || registry::unregister(id));
+    });
+}
+
+fn is_apple() -> bool {
+    let navigator = window().navigator();
+    navigator
+        .platform()
+        .or_else(|_| navigator.user_agent())
+        .is_ok_and(|platform| {
+            ["Mac", "iPhone", "iPad", "iPod"]
+                .iter()
+                .any(|apple| platform.contains(apple))
+        })
+}
+
+/// One key, along with the modifiers held down with it.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+struct Chord {
+    key: String,
+    ctrl: bool,
+    alt: bool,
+    shift: bool,
+    meta: bool,
+}
+
+impl Chord {
+    fn parse(chord: &str, apple: bool) -> Result<Self, String> {
+        let mut parts = chord.split('+').map(str::trim).collect::<Vec<_>>();
+        let key = parts
+            .pop()
+            .filter(|key| !key.is_empty())
+            .ok_or_else(|| format!("{chord:?} does not end with a key"))?;
+        let mut parsed = Chord {
+            key: normalize_key(key),
+            ..Default::default()
+        };
+        for modifier in parts {
+            match modifier.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => parsed.ctrl = true,
+                "alt" | "option" => parsed.alt = true,
+                "shift" => parsed.shift = true,
+                "meta" | "cmd" | "command" | "super" => parsed.meta = true,
+                "mod" if apple => parsed.meta = true,
+                "mod" => parsed.ctrl = true,
+                other => return Err(format!("{other:?} is not a modifier")),
+            }
+        }
+        Ok(parsed)
+    }
+
+    /// Whether the key pressed in `event` is this one.
+    fn matches(&self, event: &Pressed) -> bool {
+        // symbols like `?` are typed with shift on some layouts and not on others
+        let symbol = self.key.chars().count() == 1
+            && !self.key.chars().all(char::is_alphanumeric);
+        (self.key == event.key || Some(&self.key) == event.code_key.as_ref())
+            && self.ctrl == event.ctrl
+            && self.alt == event.alt
+            && self.meta == event.meta
+            && (symbol || self.shift == event.shift)
+    }
+}
+
+fn normalize_key(key: &str) -> String {
+    let key = key.to_lowercase();
+    match key.as_str() {
+        "esc" => "escape",
+        "space" | "spacebar" => " ",
+        "plus" => "+",
+        "comma" => ",",
+        "return" => "enter",
+        "del" => "delete",
+        "up" => "arrowup",
+        "down" => "arrowdown",
+        "left" => "arrowleft",
+        "right" => "arrowright",
+        _ => return key,
+    }
+    .to_string()
+}
+
+/// Parses the alternatives of a shortcut, each of which is a sequence of chords.
+fn parse(keys: &str, apple: bool) -> Result<Vec<Vec<Chord>>, String> {
+    let sequences = keys
+        .split(',')
+        .map(|sequence| {
+            let sequence = sequence
+                .split_whitespace()
+                .map(|chord| Chord::parse(chord, apple))
+                .collect::<Result<Vec<_>, _>>()?;
+            if sequence.is_empty() {
+                Err("a shortcut is empty".to_string())
+            } else {
+                Ok(sequence)
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(sequences)
+}
+
+/// A key that was pressed.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+struct Pressed {
+    key: String,
+    /// The letter or digit at the place of the key on a US keyboard, if it is one.
+    code_key: Option<String>,
+    ctrl: bool,
+    alt: bool,
+    shift: bool,
+    meta: bool,
+}
+
+impl Pressed {
+    fn from_event(ev: &KeyboardEvent) -> Self {
+        let code = ev.code();
+        Self {
+            key: ev.key().to_lowercase(),
+            code_key: code
+                .strip_prefix("Key")
+                .or_else(|| code.strip_prefix("Digit"))
+                .map(str::to_lowercase),
+            ctrl: ev.ctrl_key(),
+            alt: ev.alt_key(),
+            shift: ev.shift_key(),
+            meta: ev.meta_key(),
+        }
+    }
+
+    fn is_modifier(&self) -> bool {
+        matches!(
+            self.key.as_str(),
+            "control" | "alt" | "shift" | "meta" | "altgraph" | "capslock"
+        )
+    }
+}
+
+/// Whether typing in the target of the event should not trigger shortcuts.
+fn is_editable(ev: &KeyboardEvent) -> bool {
+    ev.target()
+        .and_then(|target| target.dyn_into::<HtmlElement>().ok())
+        .is_some_and(|el| {
+            el.is_content_editable()
+                || matches!(
+                    el.tag_name().as_str(),
+                    "INPUT" | "TEXTAREA" | "SELECT"
+                )
+        })
+}
+
+struct Binding {
+    id: u64,
+    scope: HotkeyScope,
+    sequences: Vec<Vec<Chord>>,
+    options: HotkeyOptions,
+    handler: Rc<dyn Fn(KeyboardEvent)>,
+}
+
+mod registry {
+    use super::*;
+
+    thread_local! {
+        static REGISTRY: RefCell<Registry> = RefCell::new(Registry::default());
+    }
+
+    #[derive(Default)]
+    pub(super) struct Registry {
+        /// The scopes that are open, from the bottom one to the top one, and whether they are
+        /// exclusive. The root scope is always below them.
+        pub(super) scopes: Vec<(HotkeyScope, bool)>,
+        pub(super) bindings: Vec<Binding>,
+        /// The keys pressed lately, for sequences.
+        pressed: Vec<Pressed>,
+        last_pressed: f64,
+        next_id: u64,
+        listener: Option<WindowListenerHandle>,
+    }
+
+    impl Registry {
+        pub(super) fn next_id(&mut self) -> u64 {
+            self.next_id += 1;
+            self.next_id
+        }
+
+        /// Finds the binding that handles `pressed`, at `time` in milliseconds.
+        pub(super) fn dispatch(
+            &mut self,
+            pressed: Pressed,
+            time: f64,
+            in_input: bool,
+        ) -> Option<&Binding> {
+            if pressed.is_modifier() {
+                return None;
+            }
+            if time - self.last_pressed > SEQUENCE_TIMEOUT {
+                self.pressed.clear();
+            }
+            self.last_pressed = time;
+            self.pressed.push(pressed);
+            let longest = self
+                .bindings
+                .iter()
+                .flat_map(|binding| &binding.sequences)
+                .map(Vec::len)
+                .max()
+                .unwrap_or(1);
+            let excess = self.pressed.len().saturating_sub(longest);
+            self.pressed.drain(..excess);
+
+            let scopes = self
+                .scopes
+                .iter()
+                .rev()
+                .copied()
+                .chain([(HotkeyScope::ROOT, false)]);
+            let mut found = None;
+            for (scope, exclusive) in scopes {
+                // later bindings override earlier ones in the same scope
+                found = self.bindings.iter().rposition(|binding| {
+                    binding.scope == scope
+                        && (!in_input || binding.options.enable_in_inputs)
+                        && binding.sequences.iter().any(|sequence| {
+                            self.pressed.len() >= sequence.len()
+                                && self.pressed
+                                    [self.pressed.len() - sequence.len()..]
+                                    .iter()
+                                    .zip(sequence)
+                                    .all(|(pressed, chord)| {
+                                        chord.matches(pressed)
+                                    })
+                        })
+                });
+                if found.is_some() || exclusive {
+                    break;
+                }
+            }
+            let found = found?;
+            self.pressed.clear();
+            self.bindings.get(found)
+        }
+    }
+
+    fn with<T>(fun: impl FnOnce(&mut Registry) -> T) -> T {
+        REGISTRY.with(|registry| fun(&mut registry.borrow_mut()))
+    }
+
+    pub(super) fn next_id() -> u64 {
+        with(Registry::next_id)
+    }
+
+    pub(super) fn open_scope(scope: HotkeyScope, exclusive: bool) {
+        with(|registry| registry.scopes.push((scope, exclusive)));
+    }
+
+    pub(super) fn close_scope(scope: HotkeyScope) {
+        with(|registry| registry.scopes.retain(|(open, _)| *open != scope));
+    }
+
+    pub(super) fn register(mut binding: Binding) -> u64 {
+        let listen = with(|registry| registry.listener.is_none());
+        // the listener is added outside of the borrow, in case it runs right away
+        let listener =
+            listen.then(|| window_event_listener(keydown, on_keydown));
+        with(|registry| {
+            if registry.listener.is_none() {
+                registry.listener = listener;
+            }
+            binding.id = registry.next_id();
+            let id = binding.id;
+            registry.bindings.push(binding);
+            id
+        })
+    }
+
+    pub(super) fn unregister(id: u64) {
+        // the binding and the listener are dropped outside of the borrow, in case dropping the
+        // handler disposes of something that registers another shortcut
+        let removed = with(|registry| {
+            let idx = registry
+                .bindings
+                .iter()
+                .position(|binding| binding.id == id)?;
+            let binding = registry.bindings.remove(idx);
+            let listener = registry
+                .bindings
+                .is_empty()
+                .then(|| registry.listener.take())
+                .flatten();
+            Some((binding, listener))
+        });
+        if let Some((_, Some(listener))) = removed {
+            listener.remove();
+        }
+    }
+
+    fn on_keydown(ev: KeyboardEvent) {
+        if ev.is_composing() {
+            return;
+        }
+        let handler = with(|registry| {
+            let binding = registry.dispatch(
+                Pressed::from_event(&ev),
+                ev.time_stamp(),
+                is_editable(&ev),
+            )?;
+            Some((Rc::clone(&binding.handler), binding.options.prevent_default))
+        });
+        if let Some((handler, prevent_default)) = handler {
+            if prevent_default {
+                ev.prevent_default();
+            }
+            handler(ev);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{registry::Registry, *};
+
+    fn pressed(key: &str) -> Pressed {
+        let mut parts = key.split('+').collect::<Vec<_>>();
+        let mut pressed = Pressed {
+            key: parts.pop().unwrap().to_string(),
+            ..Default::default()
+        };
+        for modifier in parts {
+            match modifier {
+                "ctrl" => pressed.ctrl = true,
+                "meta" => pressed.meta = true,
+                "shift" => pressed.shift = true,
+                _ => unreachable!(),
+            }
+        }
+        pressed
+    }
+
+    fn bind(registry: &mut Registry, scope: HotkeyScope, keys: &str) -> u64 {
+        let id = registry.next_id();
+        registry.bindings.push(Binding {
+            id,
+            scope,
+            sequences: parse(keys, false).unwrap(),
+            options: HotkeyOptions::default(),
+            handler: Rc::new(|_| {}),
+        });
+        id
+    }
+
+    fn dispatch(registry: &mut Registry, key: &str, time: f64) -> Option<u64> {
+        registry
+            .dispatch(pressed(key), time, false)
+            .map(|binding| binding.id)
+    }
+
+    #[test]
+    fn shortcuts_are_parsed() {
+        assert_eq!(
+            parse("mod+K, shift+/", true).unwrap(),
+            [
+                vec![Chord {
+                    key: "k".into(),
+                    meta: true,
+                    ..Default::default()
+                }],
+                vec![Chord {
+                    key: "/".into(),
+                    shift: true,
+                    ..Default::default()
+                }]
+            ]
+        );
+        assert!(parse("mod+k", false).unwrap()[0][0].ctrl);
+        assert_eq!(parse("g esc", false).unwrap()[0][1].key, "escape");
+        assert!(parse("k+ctrl", false).is_err());
+        assert!(parse("ctrl+", false).is_err());
+    }
+
+    #[test]
+    fn chords_match_modifiers_exactly() {
+        let chord = &parse("ctrl+k", false).unwrap()[0][0];
+        assert!(chord.matches(&pressed("ctrl+k")));
+        assert!(!chord.matches(&pressed("k")));
+        assert!(!chord.matches(&pressed("ctrl+shift+k")));
+
+        // symbols ignore shift, and letters are matched by their place too
+        let chord = &parse("?", false).unwrap()[0][0];
+        assert!(chord.matches(&pressed("shift+?")));
+        let chord = &parse("alt+k", false).unwrap()[0][0];
+        assert!(chord.matches(&Pressed {
+            key: "˚".into(),
+            code_key: Some("k".into()),
+            alt: true,
+            ..Default::default()
+        }));
+    }
+
+    #[test]
+    fn sequences_must_be_typed_in_time() {
+        let mut registry = Registry::default();
+        let go_home = bind(&mut registry, HotkeyScope::ROOT, "g h");
+        assert_eq!(dispatch(&mut registry, "g", 0.0), None);
+        assert_eq!(dispatch(&mut registry, "shift", 100.0), None);
+        assert_eq!(dispatch(&mut registry, "h", 200.0), Some(go_home));
+        assert_eq!(dispatch(&mut registry, "h", 300.0), None);
+
+        assert_eq!(dispatch(&mut registry, "g", 1000.0), None);
+        assert_eq!(dispatch(&mut registry, "h", 2500.0), None);
+    }
+
+    #[test]
+    fn higher_scopes_take_over() {
+        let mut registry = Registry::default();
+        let page_escape = bind(&mut registry, HotkeyScope::ROOT, "escape");
+        let page_search = bind(&mut registry, HotkeyScope::ROOT, "/");
+        let panel = HotkeyScope(100);
+        registry.scopes.push((panel, false));
+        let panel_escape = bind(&mut registry, panel, "escape");
+
+        assert_eq!(dispatch(&mut registry, "escape", 0.0), Some(panel_escape));
+        assert_eq!(dispatch(&mut registry, "/", 0.0), Some(page_search));
+
+        let dialog = HotkeyScope(101);
+        registry.scopes.push((dialog, true));
+        assert_eq!(dispatch(&mut registry, "escape", 0.0), None);
+        assert_eq!(dispatch(&mut registry, "/", 0.0), None);
+
+        // the shortcuts of closed scopes are no longer handled
+        registry.scopes.clear();
+        assert_eq!(dispatch(&mut registry, "escape", 0.0), Some(page_escape));
+    }
+
+    #[test]
+    fn inputs_only_get_the_shortcuts_that_ask_for_them() {
+        let mut registry = Registry::default();
+        bind(&mut registry, HotkeyScope::ROOT, "mod+s");
+        assert!(registry.dispatch(pressed("ctrl+s"), 0.0, true).is_none());
+        registry.bindings[0].options =
+            HotkeyOptions::default().enable_in_inputs();
+        assert!(registry.dispatch(pressed("ctrl+s"), 0.0, true).is_some());
+    }
+}
@@ -3,6 +3,7 @@
 use crate::prelude::*;
 use leptos_config::LeptosOptions;
 use leptos_macro::{component, view};
+use tachys::either::Either;
 
 /// Inserts auto-reloading code used in `cargo-leptos`.
 ///
@@ -54,39 +55,12 @@ pub fn HydrationScripts(
     /// when running in islands mode.
     #[prop(optional)]
     islands_router: bool,
-    /// A base url, not including a trailing slash
+    /// A base url, not including a trailing slash. Defaults to the
+    /// [`base_path`](LeptosOptions::base_path) of the app.
     #[prop(optional, into)]
     root: Option<String>,
 ) -> impl IntoView {
-    let mut js_file_name = options.output_name.to_string();
-    let mut wasm_file_name = options.output_name.to_string();
-    if options.hash_files {
-        let hash_path = std::env::current_exe()
-            .map(|path| {
-                path.parent().map(|p| p.to_path_buf()).unwrap_or_default()
-            })
-            .unwrap_or_default()
-            .join(options.hash_file.as_ref());
-        if hash_path.exists() {
-            let hashes = std::fs::read_to_string(&hash_path)
-                .expect("failed to read hash file");
-            for line in hashes.lines() {
-                let line = line.trim();
-                if !line.is_empty() {
-                    if let Some((file, hash)) = line.split_once(':') {
-                        if file == "js" {
-                            js_file_name.push_str(&format!(".{}", hash.trim()));
-                        } else if file == "wasm" {
-                            wasm_file_name
-                                .push_str(&format!(".{}", hash.trim()));
-                        }
-                    }
-                }
-            }
-        }
-    } else if std::option_env!("LEPTOS_OUTPUT_NAME").is_none() {
-        wasm_file_name.push_str("_bg");
-    }
+    let (js_file_name, wasm_file_name) = hydration_file_names(&options);
 
     let pkg_path = &options.site_pkg_dir;
     #[cfg(feature = "nonce")]
@@ -106,9 +80,61 @@ pub fn HydrationScripts(
         .then_some(include_str!("./islands_routing.js"))
         .unwrap_or_default();
 
-    let root = root.unwrap_or_default();
-    view! {
+    let base_path = options
+        .base_path
+        .as_deref()
+        .and_then(crate::base_path::normalize);
+    let root = root.or_else(|| base_path.clone()).unwrap_or_default();
+    // lets the browser start loading the app before it has parsed the `<head>`
+    #[cfg(feature = "request")]
+    {
+        crate::request::PreloadLink::modulepreload(format!(
+            "{root}/{pkg_path}/{js_file_name}.js"
+        ))
+        .send();
+        crate::request::PreloadLink::preload(
+            format!("{root}/{pkg_path}/{wasm_file_name}.wasm"),
+            "fetch",
+        )
+        .type_("application/wasm")
+        .crossorigin("anonymous")
+        .send();
+    }
+
+    // the metas lead the `<link>` rather than being optional views of their own, which would
+    // leave a placeholder behind when they are not needed
+    let modulepreload = view! {
         <link rel="modulepreload" href=format!("{root}/{pkg_path}/{js_file_name}.js") nonce=nonce.clone()/>
+    };
+    let modulepreload = match server_fn_mount(&options) {
+        Some(at) => {
+            let content =
+                format!("{} {at}", default_server_fn_prefix(&options));
+            Either::Left((
+                view! { <meta name="leptos-server-fn-mount" content=content/> },
+                modulepreload,
+            ))
+        }
+        None => Either::Right(modulepreload),
+    };
+    let modulepreload = match base_path {
+        Some(base) => Either::Left((
+            view! { <meta name="leptos-base-path" content=base/> },
+            modulepreload,
+        )),
+        None => Either::Right(modulepreload),
+    };
+
+    // workers load the app from the same script as the page
+    #[cfg(feature = "worker")]
+    let worker_script = Some(view! {
+        <meta name="leptos-worker-script" content=format!("{root}/{pkg_path}/{js_file_name}.js")/>
+    });
+    #[cfg(not(feature = "worker"))]
+    let worker_script = None::<()>;
+
+    view! {
+        {modulepreload}
         <link
             rel="preload"
             href=format!("{root}/{pkg_path}/{wasm_file_name}.wasm")
@@ -119,7 +145,89 @@ pub fn HydrationScripts(
         <script type="module" nonce=nonce>
             {format!("{script}({root:?}, {pkg_path:?}, {js_file_name:?}, {wasm_file_name:?});{islands_router}")}
         </script>
+        {worker_script}
+    }
+}
+
+/// Sets the [`base_path`](LeptosOptions::base_path) of the app, and mounts the server functions
+/// that use the default prefix at the [`server_fn_mount`](LeptosOptions::server_fn_mount) in
+/// `options`, if there is one, below it.
+///
+/// This should be called once when the server starts, before it adds the server function
+/// routes. The integrations call it when they are given the options. [`HydrationScripts`] passes
+/// both on to the client, which applies them before it hydrates.
+pub fn mount_server_fns(options: &LeptosOptions) {
+    crate::base_path::set_base_path(options.base_path.as_deref());
+    if let Some(at) = server_fn_mount(options) {
+        server_fn::prefix::mount(default_server_fn_prefix(options), &at);
+    }
+}
+
+/// The path that the server functions with the default prefix are served at, below the
+/// [`base_path`](LeptosOptions::base_path), if that differs from the prefix.
+fn server_fn_mount(options: &LeptosOptions) -> Option<String> {
+    let base_path = options
+        .base_path
+        .as_deref()
+        .and_then(crate::base_path::normalize);
+    if base_path.is_none() && options.server_fn_mount.is_none() {
+        return None;
+    }
+    let at = options
+        .server_fn_mount
+        .as_deref()
+        .unwrap_or_else(|| default_server_fn_prefix(options));
+    Some(format!("{}{at}", base_path.unwrap_or_default()))
+}
+
+fn default_server_fn_prefix(options: &LeptosOptions) -> &str {
+    options.server_fn_prefix.as_deref().unwrap_or("/api")
+}
+
+/// The path of the [`hash_file`](LeptosOptions::hash_file) that `cargo-leptos` writes next to
+/// the server binary when [`hash_files`](LeptosOptions::hash_files) is set.
+pub fn hash_file_path(options: &LeptosOptions) -> std::path::PathBuf {
+    std::env::current_exe()
+        .map(|path| path.parent().map(|p| p.to_path_buf()).unwrap_or_default())
+        .unwrap_or_default()
+        .join(options.hash_file.as_ref())
+}
+
+/// The names of the JS and Wasm files in the [`site_pkg_dir`](LeptosOptions::site_pkg_dir) that
+/// [`HydrationScripts`] loads, without their extensions.
+///
+/// When [`hash_files`](LeptosOptions::hash_files) is set, these include the hashes listed in the
+/// [hash file](hash_file_path), if there is one.
+pub fn hydration_file_names(options: &LeptosOptions) -> (String, String) {
+    let mut js_file_name = options.output_name.to_string();
+    let mut wasm_file_name = options.output_name.to_string();
+    if options.hash_files {
+        for (file, hash) in file_hashes(options) {
+            if file == "js" {
+                js_file_name.push_str(&format!(".{hash}"));
+            } else if file == "wasm" {
+                wasm_file_name.push_str(&format!(".{hash}"));
+            }
+        }
+    } else if std::option_env!("LEPTOS_OUTPUT_NAME").is_none() {
+        wasm_file_name.push_str("_bg");
+    }
+    (js_file_name, wasm_file_name)
+}
+
+/// The `kind: hash` pairs in the [hash file](hash_file_path), if there is one.
+pub(crate) fn file_hashes(options: &LeptosOptions) -> Vec<(String, String)> {
+    let hash_path = hash_file_path(options);
+    if !hash_path.exists() {
+        return Vec::new();
     }
+    let hashes =
+        std::fs::read_to_string(&hash_path).expect("failed to read hash file");
+    hashes
+        .lines()
+        .filter_map(|line| line.trim().split_once(':'))
+        .map(|(file, hash)| (file.trim().to_string(), hash.trim().to_string()))
+        .collect()
 }
 
 /// If this is provided via context, it means that you are using the islands router and
@@ -2,7 +2,11 @@ let host = window.location.hostname;
 let ws = new WebSocket(`${protocol}${host}:${reload_port}/live_reload`);
 ws.onmessage = (ev) => {
 	let msg = JSON.parse(ev.data);
-	if (msg.all) window.location.reload();
+	if (msg.all) {
+		// lets `leptos::hot_state` restore the values it saves while unloading
+		sessionStorage.setItem("leptos-hot-reload", "1");
+		window.location.reload();
+	}
 	if (msg.css) {
 		let found = false;
 		document.querySelectorAll("link").forEach((link) => {
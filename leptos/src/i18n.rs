@@ -0,0 +1,320 @@
+//! Messages are loaded at compile time with [`load_locales!`](crate::load_locales), which
+//! generates a `Locale` enum and a `t!` macro for the application. The current locale is stored
+//! in an [`I18n`](crate::i18n::I18n) context, which is provided with
+//! [`provide_i18n`](crate::i18n::provide_i18n) and can be switched at runtime; every message
+//! looked up with `t!` updates when it changes. Messages that include a number choose between
+//! their plural forms with the rules of the current locale's language (see
+//! [`plural_category`](crate::i18n::plural_category)).
+//!
+//! When server rendering, choose the initial locale by negotiating with the user's
+//! `Accept-Language` header using [`Locales::negotiate`](crate::i18n::Locales::negotiate), and
+//! render it as the `lang` attribute of the `<html>` element. When hydrating, read it back with
+//! [`document_locale`](crate::i18n::document_locale), so that the client starts in the same
+//! locale as the server and there is no hydration mismatch.
+//!
+//! ```rust,ignore
+//! leptos::load_locales!(default = "en");
+//!
+//! use crate::i18n::Locale;
+//! use leptos::{i18n::*, prelude::*};
+//!
+//! // on the server, with the request's `Accept-Language` header
+//! provide_i18n(Locale::negotiate(accept_language));
+//!
+//! // in the browser
+//! provide_i18n(document_locale::<Locale>().unwrap_or_default());
+//! ```
+
+use reactive_graph::{
+    owner::{provide_context, use_context},
+    signal::RwSignal,
+    traits::{Get, Set},
+};
+use std::fmt::Debug;
+
+/// A set of locales that an application has been translated into.
+///
+/// This is implemented by the `Locale` enum generated by [`load_locales!`](crate::load_locales).
+pub trait Locales: Copy + Eq + Debug + Send + Sync + 'static {
+    /// All the available locales.
+    const ALL: &'static [Self];
+
+    /// The locale that is used when none of the user's preferred locales is available.
+    const DEFAULT: Self;
+
+    /// The language tag of this locale, such as `en` or `de-AT`.
+    fn as_str(self) -> &'static str;
+
+    /// Returns the locale with the given language tag, ignoring case.
+    fn from_tag(tag: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|locale| locale.as_str().eq_ignore_ascii_case(tag))
+    }
+
+    /// Chooses the available locale that best matches the value of an `Accept-Language` header,
+    /// or [`Locales::DEFAULT`] if none of them match.
+    ///
+    /// Languages are tried in order of preference; each matches a locale with the same tag, or
+    /// else a locale with the same primary language (so `de-CH` matches `de`, and `de` matches
+    /// `de-AT`).
+    fn negotiate(accept_language: &str) -> Self {
+        let mut preferences = accept_language
+            .split(',')
+            .filter_map(|item| {
+                let mut parts = item.split(';');
+                let tag = parts.next()?.trim();
+                let quality = parts
+                    .find_map(|param| param.trim().strip_prefix("q="))
+                    .and_then(|q| q.parse::<f32>().ok())
+                    .unwrap_or(1.0);
+                (!tag.is_empty() && quality > 0.0).then_some((tag, quality))
+            })
+            .collect::<Vec<_>>();
+        // stable sort, so that equally-preferred languages keep their order
+        preferences.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        preferences
+            .iter()
+            .find_map(|(tag, _)| {
+                Self::from_tag(tag).or_else(|| {
+                    let primary = primary_language(tag);
+                    Self::ALL.iter().copied().find(|locale| {
+                        primary_language(locale.as_str())
+                            .eq_ignore_ascii_case(primary)
+                    })
+                })
+            })
+            .unwrap_or(Self::DEFAULT)
+    }
+}
+
+fn primary_language(tag: &str) -> &str {
+    tag.split(['-', '_']).next().unwrap_or(tag)
+}
+
+/// The current locale, which is shared with the rest of the application via context.
+#[derive(Debug)]
+pub struct I18n<L: 'static> {
+    locale: RwSignal<L>,
+}
+
+impl<L> Clone for I18n<L> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<L> Copy for I18n<L> {}
+
+impl<L: Locales> I18n<L> {
+    /// Creates a new context, starting in the given locale.
+    pub fn new(locale: L) -> Self {
+        Self {
+            locale: RwSignal::new(locale),
+        }
+    }
+
+    /// Returns the current locale, and subscribes to changes in it.
+    pub fn locale(&self) -> L {
+        self.locale.get()
+    }
+
+    /// Switches to a different locale, updating every message that has been looked up.
+    pub fn set_locale(&self, locale: L) {
+        self.locale.set(locale);
+    }
+}
+
+/// Provides an [`I18n`] context, starting in the given locale, and returns it.
+pub fn provide_i18n<L: Locales>(locale: L) -> I18n<L> {
+    let i18n = I18n::new(locale);
+    provide_context(i18n);
+    i18n
+}
+
+/// Returns the [`I18n`] context provided by [`provide_i18n`].
+///
+/// # Panics
+/// Panics if no context for this type of locale has been provided.
+#[track_caller]
+pub fn use_i18n<L: Locales>() -> I18n<L> {
+    use_context().expect(
+        "use_i18n() was called without an I18n context. Call provide_i18n() \
+         near the root of your application.",
+    )
+}
+
+/// Returns the locale named by the `lang` attribute of the `<html>` element, if it is one of the
+/// available locales.
+///
+/// This is always `None` outside the browser.
+pub fn document_locale<L: Locales>() -> Option<L> {
+    if cfg!(target_arch = "wasm32") {
+        let lang = tachys::dom::document()
+            .document_element()?
+            .get_attribute("lang")?;
+        L::from_tag(&lang)
+    } else {
+        None
+    }
+}
+
+/// The [CLDR plural categories](https://cldr.unicode.org/index/cldr-spec/plural-rules), which
+/// each language uses to choose between the forms of a word like "item" and "items".
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PluralCategory {
+    /// Used for zero in some languages, like Arabic and Latvian.
+    Zero,
+    /// Usually used for one.
+    One,
+    /// Used for two in some languages, like Arabic and Hebrew.
+    Two,
+    /// Used for small numbers in some languages, like 2–4 in Czech.
+    Few,
+    /// Used for large numbers in some languages, like 5–20 in Russian.
+    Many,
+    /// Used for every number that is not in another category.
+    Other,
+}
+
+/// Returns the plural category of the integer `n` in the language of the locale `tag`.
+///
+/// This is used by the `t!` macro to choose the case of a plural message. It covers the CLDR
+/// rules for integers in most widely-used languages; languages that are not listed use the
+/// English rules, in which only 1 is [`PluralCategory::One`].
+pub fn plural_category(tag: &str, n: u64) -> PluralCategory {
+    use PluralCategory::*;
+
+    let language = primary_language(tag).to_ascii_lowercase();
+    let (n10, n100) = (n % 10, n % 100);
+    match language.as_str() {
+        "ja" | "zh" | "ko" | "vi" | "th" | "id" | "ms" | "my" | "lo" | "km" => {
+            Other
+        }
+        "pt" if tag.eq_ignore_ascii_case("pt-PT") => match n {
+            1 => One,
+            _ => Other,
+        },
+        "fr" | "pt" | "hi" | "bn" | "fa" | "am" | "zu" => match n {
+            0 | 1 => One,
+            _ => Other,
+        },
+        "ru" | "uk" | "be" => match (n10, n100) {
+            (1, n100) if n100 != 11 => One,
+            (2..=4, n100) if !(12..=14).contains(&n100) => Few,
+            _ => Many,
+        },
+        "hr" | "sr" | "bs" => match (n10, n100) {
+            (1, n100) if n100 != 11 => One,
+            (2..=4, n100) if !(12..=14).contains(&n100) => Few,
+            _ => Other,
+        },
+        "pl" => match (n, n10, n100) {
+            (1, _, _) => One,
+            (_, 2..=4, n100) if !(12..=14).contains(&n100) => Few,
+            _ => Many,
+        },
+        "cs" | "sk" => match n {
+            1 => One,
+            2..=4 => Few,
+            _ => Other,
+        },
+        "lt" => match (n10, n100) {
+            (1, n100) if !(11..=19).contains(&n100) => One,
+            (2..=9, n100) if !(11..=19).contains(&n100) => Few,
+            _ => Other,
+        },
+        "lv" => match (n10, n100) {
+            (0, _) | (_, 11..=19) => Zero,
+            (1, _) => One,
+            _ => Other,
+        },
+        "ro" => match (n, n100) {
+            (1, _) => One,
+            (0, _) | (_, 2..=19) => Few,
+            _ => Other,
+        },
+        "sl" => match n100 {
+            1 => One,
+            2 => Two,
+            3 | 4 => Few,
+            _ => Other,
+        },
+        "ar" => match (n, n100) {
+            (0, _) => Zero,
+            (1, _) => One,
+            (2, _) => Two,
+            (_, 3..=10) => Few,
+            (_, 11..=99) => Many,
+            _ => Other,
+        },
+        "he" => match n {
+            1 => One,
+            2 => Two,
+            _ => Other,
+        },
+        "ga" => match n {
+            1 => One,
+            2 => Two,
+            3..=6 => Few,
+            7..=10 => Many,
+            _ => Other,
+        },
+        "cy" => match n {
+            0 => Zero,
+            1 => One,
+            2 => Two,
+            3 => Few,
+            6 => Many,
+            _ => Other,
+        },
+        _ => match n {
+            1 => One,
+            _ => Other,
+        },
+    }
+}
+
+/// A number that can choose the case of a plural message.
+#[rustversion::attr(
+    since(1.78),
+    diagnostic::on_unimplemented(
+        message = "`{Self}` cannot be used to choose a plural",
+        note = "plurals can only be chosen by integers"
+    )
+)]
+pub trait PluralCount {
+    /// The absolute value of the number, which is used to find its plural category.
+    fn plural_count(&self) -> u64;
+}
+
+macro_rules! plural_count_unsigned {
+    ($($ty:ty),*) => {
+        $(impl PluralCount for $ty {
+            fn plural_count(&self) -> u64 {
+                u64::try_from(*self).unwrap_or(u64::MAX)
+            }
+        })*
+    };
+}
+
+macro_rules! plural_count_signed {
+    ($($ty:ty),*) => {
+        $(impl PluralCount for $ty {
+            fn plural_count(&self) -> u64 {
+                u64::try_from(self.unsigned_abs()).unwrap_or(u64::MAX)
+            }
+        })*
+    };
+}
+
+plural_count_unsigned!(u8, u16, u32, u64, u128, usize);
+plural_count_signed!(i8, i16, i32, i64, i128, isize);
+
+impl<T: PluralCount + ?Sized> PluralCount for &T {
+    fn plural_count(&self) -> u64 {
+        T::plural_count(self)
+    }
+}
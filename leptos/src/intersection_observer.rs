@@ -0,0 +1,208 @@
+//! Reactive visibility of elements, with an
+//! [`IntersectionObserver`](https://developer.mozilla.org/en-US/docs/Web/API/IntersectionObserver).
+//!
+//! [`use_intersection_observer`] tracks whether an element is visible in the viewport, and how
+//! much of it is, which is the building block for lazy loading, infinite scrolling and animations
+//! that start once an element scrolls into view.
+//!
+//! ```rust
+//! use leptos::{
+//!     html::Div,
+//!     intersection_observer::{
+//!         use_intersection_observer, IntersectionObserverOptions,
+//!     },
+//!     prelude::*,
+//! };
+//!
+//! #[component]
+//! pub fn LazyImage(src: String) -> impl IntoView {
+//!     let container = NodeRef::<Div>::new();
+//!     let visibility = use_intersection_observer(
+//!         container,
+//!         IntersectionObserverOptions {
+//!             root_margin: "200px".into(),
+//!             once: true,
+//!             ..Default::default()
+//!         },
+//!     );
+//!
+//!     view! {
+//!         <div node_ref=container class="placeholder">
+//!             <Show when=move || visibility.is_intersecting()>
+//!                 <img src=src.clone()/>
+//!             </Show>
+//!         </div>
+//!     }
+//! }
+//! ```
+//!
+//! The observer is only created in the browser, once the element has been mounted; on the server,
+//! and until the first observation, the element counts as not visible. The observer is
+//! disconnected when the reactive owner that created it is cleaned up.
+
+use reactive_graph::{
+    effect::Effect,
+    owner::on_cleanup,
+    signal::RwSignal,
+    traits::{Get, GetUntracked, Set},
+};
+use send_wrapper::SendWrapper;
+use std::borrow::Cow;
+use tachys::{html::element::ElementType, reactive_graph::node_ref::NodeRef};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use web_sys::{
+    js_sys::Array, Element, IntersectionObserver, IntersectionObserverEntry,
+    IntersectionObserverInit,
+};
+
+/// Options that configure [`use_intersection_observer`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct IntersectionObserverOptions {
+    /// Grows or shrinks the viewport before computing intersections, with the syntax of the CSS
+    /// `margin` property, like `"100px 0px"`. Defaults to `"0px"`.
+    pub root_margin: Cow<'static, str>,
+    /// The visible ratios, between `0.0` and `1.0`, at which the signals are updated. Defaults to
+    /// `[0.0]`, which updates them when the element starts or stops being visible at all.
+    pub thresholds: Vec<f64>,
+    /// Whether to stop observing the element the first time it becomes visible, which is what
+    /// lazy loading needs. Defaults to `false`.
+    pub once: bool,
+}
+
+impl Default for IntersectionObserverOptions {
+    fn default() -> Self {
+        Self {
+            root_margin: Cow::Borrowed("0px"),
+            thresholds: vec![0.0],
+            once: false,
+        }
+    }
+}
+
+/// A handle to the visibility of an element, created with [`use_intersection_observer`].
+#[derive(Debug, Clone, Copy)]
+pub struct UseIntersectionObserver {
+    is_intersecting: RwSignal<bool>,
+    ratio: RwSignal<f64>,
+    stopped: RwSignal<bool>,
+}
+
+impl UseIntersectionObserver {
+    /// Whether any part of the element is visible. Reactive.
+    pub fn is_intersecting(&self) -> bool {
+        self.is_intersecting.get()
+    }
+
+    /// How much of the element is visible, from `0.0` to `1.0`, as of the last threshold that was
+    /// crossed. Reactive.
+    pub fn ratio(&self) -> f64 {
+        self.ratio.get()
+    }
+
+    /// Stops observing the element. The signals keep their last values.
+    pub fn stop(&self) {
+        self.stopped.set(true);
+    }
+
+    /// Whether the element is no longer observed, because of [`stop`](Self::stop) or
+    /// [`IntersectionObserverOptions::once`]. Reactive.
+    pub fn is_stopped(&self) -> bool {
+        self.stopped.get()
+    }
+}
+
+/// Observes how much of the element that `node_ref` refers to is visible in the viewport.
+///
+/// If `node_ref` is loaded with another element later, that one is observed instead.
+pub fn use_intersection_observer<E>(
+    node_ref: NodeRef<E>,
+    options: IntersectionObserverOptions,
+) -> UseIntersectionObserver
+where
+    E: ElementType,
+    E::Output: JsCast + Clone + 'static,
+{
+    let handle = UseIntersectionObserver {
+        is_intersecting: RwSignal::new(false),
+        ratio: RwSignal::new(0.0),
+        stopped: RwSignal::new(false),
+    };
+    let once = options.once;
+
+    // effects only run in the browser, so nothing is observed on the server
+    Effect::new(move |_| {
+        if handle.stopped.get() {
+            return;
+        }
+        let Some(el) = node_ref.get() else {
+            return;
+        };
+        let el = el.unchecked_into::<Element>();
+
+        let on_intersection =
+            Closure::<dyn Fn(Array)>::new(move |entries: Array| {
+                // entries are in chronological order, so the last one is the current state
+                let Some(entry) = entries.iter().last().and_then(|entry| {
+                    entry.dyn_into::<IntersectionObserverEntry>().ok()
+                }) else {
+                    return;
+                };
+                handle.is_intersecting.set(entry.is_intersecting());
+                handle.ratio.set(entry.intersection_ratio());
+                if once
+                    && entry.is_intersecting()
+                    && !handle.stopped.get_untracked()
+                {
+                    handle.stopped.set(true);
+                }
+            });
+        let init = IntersectionObserverInit::new();
+        init.set_root_margin(&options.root_margin);
+        init.set_threshold(&JsValue::from(
+            options
+                .thresholds
+                .iter()
+                .map(|threshold| JsValue::from_f64(*threshold))
+                .collect::<Array>(),
+        ));
+        let Ok(observer) = IntersectionObserver::new_with_options(
+            on_intersection.as_ref().unchecked_ref(),
+            &init,
+        ) else {
+            return;
+        };
+        observer.observe(&el);
+
+        let cleanup = SendWrapper::new((observer, on_intersection));
+        on_cleanup(move || {
+            let (observer, _on_intersection) = cleanup.take();
+            observer.disconnect();
+        });
+    });
+
+    handle
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use super::*;
+    use crate::testing::with_test_owner;
+    use tachys::html::element::Div;
+
+    #[test]
+    fn elements_are_not_visible_until_they_are_observed() {
+        with_test_owner(|| {
+            let visibility = use_intersection_observer(
+                NodeRef::<Div>::new(),
+                IntersectionObserverOptions::default(),
+            );
+            assert!(!visibility.is_intersecting());
+            assert_eq!(visibility.ratio(), 0.0);
+            assert!(!visibility.is_stopped());
+
+            visibility.stop();
+            assert!(visibility.is_stopped());
+            assert!(!visibility.is_intersecting());
+        });
+    }
+}
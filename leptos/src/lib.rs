@@ -91,6 +91,40 @@
 //! - **`rkyv`** In SSR/hydrate mode, uses [`rkyv`](https://docs.rs/rkyv/latest/rkyv/) to serialize resources and send them
 //!   from the server to the client.
 //! - **`tracing`** Adds support for [`tracing`](https://docs.rs/tracing/latest/tracing/).
+//! - **`islands`** Enables the [`island`] macro.
+//! - **`delegation`** Delegates bubbling events to a single listener on the window for each event
+//!   type, rather than attaching a listener to each element. Without it, the delegation code is
+//!   not compiled into the WebAssembly binary.
+//! - **`multipart`** Enables multipart form data as an encoding for server functions.
+//! - **`html_validation`** Checks [`view`] templates at compile time for common HTML mistakes that
+//!   would otherwise only show up as hydration errors.
+//!
+//! These features each enable one of the optional modules, and the `web-sys` APIs it uses, so
+//! that apps that don't use it don't compile it:
+//! - **`request`** [`request`], which `ssr`, `cookie` and `media-query` also enable
+//! - **`cookie`** [`cookie`]
+//! - **`session`** [`session`]
+//! - **`auth`** [`auth`]
+//! - **`hot-state`** [`hot_state`]
+//! - **`synced-signal`** [`synced_signal`]
+//! - **`devtools`** [`devtools`]
+//! - **`websocket`** [`websocket`] and [`server_signal`]
+//! - **`sse`** [`sse`]
+//! - **`service-worker`** [`service_worker`]
+//! - **`drag-and-drop`** [`drag_and_drop`]
+//! - **`worker`** [`worker`]
+//! - **`shared-worker`** [`shared_worker`]
+//! - **`clipboard`** [`clipboard`]
+//! - **`geolocation`** [`geolocation`]
+//! - **`gesture`** [`gesture`]
+//! - **`hotkeys`** [`hotkeys`]
+//! - **`animation-frame`** [`animation_frame`]
+//! - **`announcer`** [`announcer`]
+//! - **`observers`** [`element_size`], [`intersection_observer`] and [`mutation_observer`]
+//! - **`media-query`** [`media_query`]
+//! - **`roving-tabindex`** [`roving_tabindex`]
+//! - **`scoped-style`** [`scoped_style`], which the [`style!`](crate::style) macro needs
+//! - **`testing`** [`testing`]
 //!
 //! **Important Note:** You must enable one of `csr`, `hydrate`, or `ssr` to tell Leptos
 //! which mode your app is operating in. You should only enable one of these per build target,
@@ -183,6 +217,8 @@ pub mod prelude {
             error::{FromServerFnError, ServerFnError, ServerFnErrorErr},
         };
         pub use tachys::{
+            class_list,
+            html::class_list::ClassSegment,
             reactive_graph::{bind::BindAttribute, node_ref::*, Suspend},
             view::{fragment::Fragment, template::ViewTemplate},
         };
@@ -190,7 +226,7 @@ pub mod prelude {
     pub use export_types::*;
 }
 
-/// Components used for working with HTML forms, like `<ActionForm>`.
+/// Components and types used for working with HTML forms, like `<ActionForm>` and `FormStore`.
 pub mod form;
 
 /// A standard way to wrap functions and closures to pass them to components.
@@ -202,11 +238,20 @@ pub mod children;
 /// Wrapper for intercepting component attributes.
 pub mod attribute_interceptor;
 
+/// Conversions used when passing slots to a component.
+pub mod slot;
+
+#[doc(hidden)]
+pub mod custom_element;
+
 #[doc(hidden)]
 /// Traits used to implement component constructors.
 pub mod component;
 mod error_boundary;
 
+/// Translating an application into several languages.
+pub mod i18n;
+
 /// Tools for handling errors.
 pub mod error {
     pub use crate::error_boundary::*;
@@ -242,7 +287,99 @@ mod suspense_component;
 
 /// Types for reactive string properties for components.
 pub mod text_prop;
+
 mod transition;
+#[cfg(feature = "websocket")]
+pub mod websocket;
+
+#[cfg(feature = "websocket")]
+pub mod server_signal;
+
+#[cfg(feature = "sse")]
+pub mod sse;
+
+#[cfg(feature = "service-worker")]
+pub mod service_worker;
+
+#[cfg(feature = "cookie")]
+pub mod cookie;
+
+#[cfg(feature = "session")]
+pub mod session;
+
+#[cfg(feature = "auth")]
+pub mod auth;
+
+#[cfg(feature = "hot-state")]
+pub mod hot_state;
+
+#[cfg(feature = "synced-signal")]
+pub mod synced_signal;
+
+#[cfg(feature = "devtools")]
+pub mod devtools;
+
+#[cfg(feature = "drag-and-drop")]
+pub mod drag_and_drop;
+
+#[cfg(feature = "worker")]
+pub mod worker;
+
+#[cfg(feature = "shared-worker")]
+pub mod shared_worker;
+
+#[cfg(feature = "clipboard")]
+pub mod clipboard;
+
+#[cfg(feature = "geolocation")]
+pub mod geolocation;
+
+#[cfg(feature = "gesture")]
+pub mod gesture;
+
+#[cfg(feature = "hotkeys")]
+pub mod hotkeys;
+
+/// A loop that runs on every animation frame, with
+/// [`requestAnimationFrame`](https://developer.mozilla.org/en-US/docs/Web/API/Window/requestAnimationFrame).
+#[cfg(feature = "animation-frame")]
+pub mod animation_frame;
+
+/// Announcements for screen reader users.
+#[cfg(feature = "announcer")]
+pub mod announcer;
+
+#[cfg(feature = "observers")]
+pub mod element_size;
+#[cfg(feature = "observers")]
+pub mod intersection_observer;
+#[cfg(feature = "observers")]
+pub mod mutation_observer;
+
+#[cfg(feature = "media-query")]
+pub mod media_query;
+
+#[cfg(feature = "roving-tabindex")]
+pub mod roving_tabindex;
+
+#[cfg(feature = "scoped-style")]
+pub mod scoped_style;
+
+#[cfg(feature = "testing")]
+pub mod testing;
+
+/// Serving an app under a sub-path, like `https://example.com/tools/myapp/`.
+pub mod base_path;
+
+/// Typed dependencies, like database pools, API clients, or feature flags, that are resolved per
+/// request on the server.
+pub mod dependency;
+
+/// A global hook for reporting errors to a logging or error tracking service.
+pub mod error_reporting;
+
+#[cfg(feature = "request")]
+pub mod request;
 pub use leptos_macro::*;
 #[doc(inline)]
 pub use server_fn;
@@ -272,14 +409,27 @@ pub use reactive_graph as reactive;
 /// Provide and access data along the reactive graph, sharing data without directly passing arguments.
 pub mod context {
     pub use crate::provider::*;
-    pub use reactive_graph::owner::{provide_context, use_context};
+    pub use reactive_graph::owner::{
+        expect_context_keyed, provide_context, provide_context_keyed,
+        shadow_context, use_context, use_context_keyed, with_context_keyed,
+    };
 }
 
 #[doc(inline)]
 pub use leptos_server as server;
+/// Merges conditional classes, keeping the last of any conflicting utility classes.
+#[doc(inline)]
+pub use tachys::class_list;
+/// Declares a custom event type with a strongly-typed payload.
+#[doc(inline)]
+pub use tachys::define_custom_event;
 /// HTML attribute types.
 #[doc(inline)]
 pub use tachys::html::attribute as attr;
+/// Typed values for CSS properties, like `px(10)` or `rgb(255, 0, 0)`, for use in `style:`
+/// bindings.
+#[doc(inline)]
+pub use tachys::html::css;
 /// HTML element types.
 #[doc(inline)]
 pub use tachys::html::element as html;
@@ -0,0 +1,188 @@
+//! Reactive [media queries](https://developer.mozilla.org/en-US/docs/Web/CSS/CSS_media_queries).
+//!
+//! [`use_media_query`] returns a signal that tracks whether a media query matches, so responsive
+//! logic can be written in Rust without setting up a `MediaQueryList` listener by hand.
+//! [`use_prefers_reduced_motion`] and [`use_color_scheme`] track the user preferences that are
+//! most often needed.
+//!
+//! ```rust
+//! use leptos::{
+//!     media_query::{use_color_scheme, use_media_query, ColorScheme},
+//!     prelude::*,
+//! };
+//!
+//! #[component]
+//! pub fn Layout(children: ChildrenFn) -> impl IntoView {
+//!     let is_mobile = use_media_query("(max-width: 600px)");
+//!     let scheme = use_color_scheme();
+//!
+//!     view! {
+//!         <div
+//!             class:mobile=is_mobile
+//!             class:dark=move || scheme.get() == ColorScheme::Dark
+//!         >
+//!             {children()}
+//!         </div>
+//!     }
+//! }
+//! ```
+//!
+//! Media queries can only be evaluated in the browser. Every signal starts out with a default
+//! value, which is what is rendered on the server and during hydration, and is updated to the
+//! actual value once the app has been mounted. For `prefers-*` preferences, the default is taken
+//! from the matching [client hint](https://developer.mozilla.org/en-US/docs/Web/HTTP/Client_hints)
+//! header on the server, which browsers only send once they have been asked to with
+//! [`request_client_hints`].
+
+use crate::request::{
+    use_request_parts, HeaderMap, HeaderName, HeaderValue, ResponseHeaders,
+};
+use leptos_dom::helpers::window;
+use reactive_graph::{
+    effect::Effect,
+    owner::{on_cleanup, with_context},
+    signal::RwSignal,
+    traits::{Get, Set},
+    wrappers::read::Signal,
+};
+use send_wrapper::SendWrapper;
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::MediaQueryListEvent;
+
+/// The client hint header for the `prefers-color-scheme` preference.
+pub const PREFERS_COLOR_SCHEME: &str = "sec-ch-prefers-color-scheme";
+
+/// The client hint header for the `prefers-reduced-motion` preference.
+pub const PREFERS_REDUCED_MOTION: &str = "sec-ch-prefers-reduced-motion";
+
+/// The color scheme that the user prefers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ColorScheme {
+    /// Dark text on a light background.
+    #[default]
+    Light,
+    /// Light text on a dark background.
+    Dark,
+}
+
+/// Returns a signal that is `true` while `query` matches, like `"(max-width: 600px)"`.
+///
+/// The signal is `false` on the server and during hydration. See [`use_media_query_with_default`]
+/// to start from another value.
+pub fn use_media_query(query: impl Into<String>) -> Signal<bool> {
+    use_media_query_with_default(query, false)
+}
+
+/// Returns a signal that is `true` while `query` matches, starting out as `default` on the server
+/// and during hydration.
+///
+/// The listener is removed when the reactive owner that called this is cleaned up.
+pub fn use_media_query_with_default(
+    query: impl Into<String>,
+    default: bool,
+) -> Signal<bool> {
+    let query = query.into();
+    let matches = RwSignal::new(default);
+
+    // effects only run in the browser, so the query is never evaluated on the server
+    Effect::new(move |_| {
+        let Ok(Some(list)) = window().match_media(&query) else {
+            return;
+        };
+        matches.set(list.matches());
+
+        let on_change = Closure::<dyn Fn(MediaQueryListEvent)>::new(
+            move |ev: MediaQueryListEvent| matches.set(ev.matches()),
+        );
+        _ = list.add_event_listener_with_callback(
+            "change",
+            on_change.as_ref().unchecked_ref(),
+        );
+        let cleanup = SendWrapper::new((list, on_change));
+        on_cleanup(move || {
+            let (list, on_change) = cleanup.take();
+            _ = list.remove_event_listener_with_callback(
+                "change",
+                on_change.as_ref().unchecked_ref(),
+            );
+        });
+    });
+
+    matches.into()
+}
+
+/// Returns a signal that is `true` while the user prefers reduced motion.
+///
+/// On the server, this starts out from the `Sec-CH-Prefers-Reduced-Motion` client hint, if the
+/// browser sent it, and is `false` otherwise.
+pub fn use_prefers_reduced_motion() -> Signal<bool> {
+    let default =
+        client_hint(PREFERS_REDUCED_MOTION).as_deref() == Some("reduce");
+    use_media_query_with_default("(prefers-reduced-motion: reduce)", default)
+}
+
+/// Returns a signal with the color scheme that the user prefers.
+///
+/// On the server, this starts out from the `Sec-CH-Prefers-Color-Scheme` client hint, if the
+/// browser sent it, and is [`ColorScheme::Light`] otherwise.
+pub fn use_color_scheme() -> Signal<ColorScheme> {
+    let default = client_hint(PREFERS_COLOR_SCHEME).as_deref() == Some("dark");
+    let dark =
+        use_media_query_with_default("(prefers-color-scheme: dark)", default);
+    Signal::derive(move || {
+        if dark.get() {
+            ColorScheme::Dark
+        } else {
+            ColorScheme::Light
+        }
+    })
+}
+
+/// Asks the browser to send the `prefers-*` client hints with its next requests, by adding an
+/// `Accept-CH` header to the response of the request that is currently being rendered.
+///
+/// This should be called once per page, for example in the component that renders the shell of
+/// the app. Browsers only send client hints over HTTPS, and only for requests after the one that
+/// asked for them, so the first page that a user visits still renders with the defaults.
+///
+/// Returns `false` if there is no such response, which is the case on the client, or if its
+/// headers have already been sent.
+pub fn request_client_hints() -> bool {
+    let hints = HeaderValue::from_static(
+        "Sec-CH-Prefers-Color-Scheme, Sec-CH-Prefers-Reduced-Motion",
+    );
+    with_context::<ResponseHeaders, _>(|headers| {
+        headers.append(HeaderName::from_static("accept-ch"), hints.clone())
+            && headers.append(http::header::VARY, hints)
+    })
+    .unwrap_or(false)
+}
+
+/// The value of a client hint header of the current request, if any.
+fn client_hint(name: &str) -> Option<String> {
+    let headers = use_request_parts::<HeaderMap>().ok()?;
+    parse_hint(headers.get(name)?.to_str().ok()?)
+}
+
+/// Client hints are structured header strings, which are quoted.
+fn parse_hint(value: &str) -> Option<String> {
+    let value = value.trim();
+    let value = value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .unwrap_or(value);
+    (!value.is_empty()).then(|| value.to_ascii_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_hint;
+
+    #[test]
+    fn parses_quoted_and_bare_client_hints() {
+        assert_eq!(parse_hint("\"dark\"").as_deref(), Some("dark"));
+        assert_eq!(parse_hint(" reduce ").as_deref(), Some("reduce"));
+        assert_eq!(parse_hint("\"Light\"").as_deref(), Some("light"));
+        assert_eq!(parse_hint("\"\""), None);
+    }
+}
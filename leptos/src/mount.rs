@@ -48,6 +48,9 @@ where
     // we ignore the return value because an Err here just means the wasm-bindgen executor is
     // already initialized, which is not an issue
     _ = Executor::init_wasm_bindgen();
+    #[cfg(feature = "hot-state")]
+    crate::hot_state::install();
+    mount_server_fns();
 
     #[cfg(debug_assertions)]
     {
@@ -103,6 +106,8 @@ where
     // we ignore the return value because an Err here just means the wasm-bindgen executor is
     // already initialized, which is not an issue
     _ = Executor::init_wasm_bindgen();
+    #[cfg(feature = "hot-state")]
+    crate::hot_state::install();
 
     #[cfg(debug_assertions)]
     {
@@ -147,6 +152,8 @@ where
     // we ignore the return value because an Err here just means the wasm-bindgen executor is
     // already initialized, which is not an issue
     _ = Executor::init_wasm_bindgen();
+    #[cfg(feature = "hot-state")]
+    crate::hot_state::install();
 
     // create a new reactive owner and use it as the root node to run the app
     let owner = Owner::new();
@@ -172,6 +179,9 @@ pub fn hydrate_islands() {
     // we ignore the return value because an Err here just means the wasm-bindgen executor is
     // already initialized, which is not an issue
     _ = Executor::init_wasm_bindgen();
+    #[cfg(feature = "hot-state")]
+    crate::hot_state::install();
+    mount_server_fns();
 
     #[cfg(debug_assertions)]
     FIRST_CALL.set(false);
@@ -184,6 +194,27 @@ pub fn hydrate_islands() {
     std::mem::forget(owner);
 }
 
+/// Applies the base path and server function mount that `HydrationScripts` passed on from the
+/// server.
+#[cfg(feature = "hydrate")]
+fn mount_server_fns() {
+    let meta = |name: &str| {
+        tachys::dom::document()
+            .query_selector(&format!("meta[name={name}]"))
+            .ok()
+            .flatten()
+            .and_then(|meta| meta.get_attribute("content"))
+    };
+    if let Some(base_path) = meta("leptos-base-path") {
+        crate::base_path::set_base_path(Some(&base_path));
+    }
+    let mount = meta("leptos-server-fn-mount");
+    if let Some((prefix, at)) = mount.as_deref().and_then(|m| m.split_once(' '))
+    {
+        server_fn::prefix::mount(prefix, at);
+    }
+}
+
 /// On drop, this will clean up the reactive [`Owner`] and unmount the view created by
 /// [`mount_to`].
 ///
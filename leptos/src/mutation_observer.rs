@@ -0,0 +1,212 @@
+//! Notifications of changes to the DOM, with a
+//! [`MutationObserver`](https://developer.mozilla.org/en-US/docs/Web/API/MutationObserver).
+//!
+//! Third-party scripts, like embedded widgets, editors or analytics tools, often change parts of
+//! the DOM that Leptos doesn't own. [`use_mutation_observer`] is notified of these changes, so the
+//! app can react to them, and disconnects the observer when the component that started it is
+//! cleaned up.
+//!
+//! ```rust
+//! use leptos::{
+//!     html::Div,
+//!     mutation_observer::{use_mutation_observer, MutationObserverOptions},
+//!     prelude::*,
+//! };
+//!
+//! #[component]
+//! pub fn Embed() -> impl IntoView {
+//!     let container = NodeRef::<Div>::new();
+//!     let (loaded, set_loaded) = signal(false);
+//!     use_mutation_observer(
+//!         container,
+//!         MutationObserverOptions {
+//!             subtree: true,
+//!             ..Default::default()
+//!         },
+//!         move |records| {
+//!             if records.iter().any(|record| record.added_nodes().length() > 0) {
+//!                 set_loaded.set(true);
+//!             }
+//!         },
+//!     );
+//!
+//!     view! {
+//!         <div node_ref=container class="embed"></div>
+//!         <Show when=move || !loaded.get()>"Loading the widget…"</Show>
+//!     }
+//! }
+//! ```
+//!
+//! The observer is only created in the browser, once the element has been mounted, so the
+//! changes that hydration makes to the element are not reported.
+
+use reactive_graph::{
+    effect::Effect,
+    owner::on_cleanup,
+    signal::RwSignal,
+    traits::{Get, Set},
+};
+use send_wrapper::SendWrapper;
+use std::rc::Rc;
+use tachys::{html::element::ElementType, reactive_graph::node_ref::NodeRef};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+pub use web_sys::MutationRecord;
+use web_sys::{js_sys::Array, Element, MutationObserver, MutationObserverInit};
+
+/// Options that configure [`use_mutation_observer`], and which changes it reports.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MutationObserverOptions {
+    /// Whether to report children being added or removed. Defaults to `true`.
+    pub child_list: bool,
+    /// Whether to report changes to attributes. Defaults to `false`, unless
+    /// [`attribute_filter`](Self::attribute_filter) is not empty.
+    pub attributes: bool,
+    /// Only report changes to these attributes. Defaults to reporting all of them.
+    pub attribute_filter: Vec<String>,
+    /// Whether to record the previous value of changed attributes. Defaults to `false`.
+    pub attribute_old_value: bool,
+    /// Whether to report changes to the text of text nodes. Defaults to `false`.
+    pub character_data: bool,
+    /// Whether to record the previous text of changed text nodes. Defaults to `false`.
+    pub character_data_old_value: bool,
+    /// Whether to report changes to all descendants, rather than only to the element and its
+    /// children. Defaults to `false`.
+    pub subtree: bool,
+}
+
+impl Default for MutationObserverOptions {
+    fn default() -> Self {
+        Self {
+            child_list: true,
+            attributes: false,
+            attribute_filter: Vec::new(),
+            attribute_old_value: false,
+            character_data: false,
+            character_data_old_value: false,
+            subtree: false,
+        }
+    }
+}
+
+impl MutationObserverOptions {
+    fn to_init(&self) -> MutationObserverInit {
+        let init = MutationObserverInit::new();
+        init.set_child_list(self.child_list);
+        init.set_subtree(self.subtree);
+        if self.attributes || !self.attribute_filter.is_empty() {
+            init.set_attributes(true);
+        }
+        if !self.attribute_filter.is_empty() {
+            init.set_attribute_filter(&JsValue::from(
+                self.attribute_filter
+                    .iter()
+                    .map(|name| JsValue::from_str(name))
+                    .collect::<Array>(),
+            ));
+        }
+        if self.attribute_old_value {
+            init.set_attribute_old_value(true);
+        }
+        if self.character_data {
+            init.set_character_data(true);
+        }
+        if self.character_data_old_value {
+            init.set_character_data_old_value(true);
+        }
+        init
+    }
+}
+
+/// A handle to a mutation observer, created with [`use_mutation_observer`].
+#[derive(Debug, Clone, Copy)]
+pub struct UseMutationObserver {
+    stopped: RwSignal<bool>,
+}
+
+impl UseMutationObserver {
+    /// Disconnects the observer before its owner is cleaned up.
+    pub fn stop(&self) {
+        self.stopped.set(true);
+    }
+
+    /// Whether the observer has been disconnected with [`stop`](Self::stop). Reactive.
+    pub fn is_stopped(&self) -> bool {
+        self.stopped.get()
+    }
+}
+
+/// Calls `callback` with the changes to the element that `node_ref` refers to, filtered by
+/// `options`.
+///
+/// If `node_ref` is loaded with another element later, that one is observed instead.
+pub fn use_mutation_observer<E>(
+    node_ref: NodeRef<E>,
+    options: MutationObserverOptions,
+    callback: impl Fn(Vec<MutationRecord>) + 'static,
+) -> UseMutationObserver
+where
+    E: ElementType,
+    E::Output: JsCast + Clone + 'static,
+{
+    let handle = UseMutationObserver {
+        stopped: RwSignal::new(false),
+    };
+    let callback = Rc::new(callback);
+
+    // effects only run in the browser, so nothing is observed on the server
+    Effect::new(move |_| {
+        if handle.stopped.get() {
+            return;
+        }
+        let Some(el) = node_ref.get() else {
+            return;
+        };
+        let el = el.unchecked_into::<Element>();
+        let on_mutation = Closure::<dyn Fn(Array)>::new({
+            let callback = Rc::clone(&callback);
+            move |records: Array| {
+                callback(
+                    records
+                        .iter()
+                        .filter_map(|record| record.dyn_into().ok())
+                        .collect(),
+                )
+            }
+        });
+        let Ok(observer) =
+            MutationObserver::new(on_mutation.as_ref().unchecked_ref())
+        else {
+            return;
+        };
+        _ = observer.observe_with_options(&el, &options.to_init());
+
+        let cleanup = SendWrapper::new((observer, on_mutation));
+        on_cleanup(move || {
+            let (observer, _on_mutation) = cleanup.take();
+            observer.disconnect();
+        });
+    });
+
+    handle
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use super::*;
+    use crate::testing::with_test_owner;
+    use tachys::html::element::Div;
+
+    #[test]
+    fn nothing_is_observed_on_the_server() {
+        with_test_owner(|| {
+            let observer = use_mutation_observer(
+                NodeRef::<Div>::new(),
+                MutationObserverOptions::default(),
+                |_| panic!("there is nothing to observe"),
+            );
+            assert!(!observer.is_stopped());
+            observer.stop();
+            assert!(observer.is_stopped());
+        });
+    }
+}
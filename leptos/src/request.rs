@@ -0,0 +1,469 @@
+//! Framework-agnostic access to the request that is currently being handled on the server.
+//!
+//! The server integrations (`leptos_axum`, `leptos_actix`, and `leptos_tower`) provide the
+//! [`RequestParts`] of each request via context while rendering a page or running a server
+//! function. Components and server functions can then use [`use_request_parts`] to extract
+//! headers, cookies, connection information, and extensions, without depending on a particular
+//! integration.
+//!
+//! ```rust
+//! use leptos::{
+//!     prelude::*,
+//!     request::{use_request_parts, ConnectionInfo, Cookies, HeaderMap},
+//! };
+//!
+//! #[server]
+//! pub async fn whoami() -> Result<String, ServerFnError> {
+//!     let headers: HeaderMap = use_request_parts()?;
+//!     let cookies: Cookies = use_request_parts()?;
+//!     let conn: ConnectionInfo = use_request_parts()?;
+//!
+//!     Ok(format!(
+//!         "{:?} from {:?}, session {:?}",
+//!         headers.get("user-agent"),
+//!         conn.remote_addr,
+//!         cookies.get("session"),
+//!     ))
+//! }
+//! ```
+//!
+//! Extractors return a [`ServerFnErrorErr`] when they fail, so they can be used with `?` in
+//! server functions. On the client, or outside a request handled by an integration, every
+//! extraction fails.
+
+use reactive_graph::owner::with_context;
+use server_fn::error::ServerFnErrorErr;
+use std::{collections::HashMap, fmt, net::SocketAddr, sync::Arc};
+
+pub use http::{
+    Extensions, HeaderMap, HeaderName, HeaderValue, Method, StatusCode, Uri,
+    Version,
+};
+
+/// The head of the request that is currently being handled on the server.
+///
+/// This is provided via context by the server integrations. Use [`use_request_parts`] to access
+/// it, or to extract any other type that implements [`FromRequestParts`].
+#[derive(Debug, Clone, Default)]
+pub struct RequestParts {
+    /// The request method.
+    pub method: Method,
+    /// The request URI.
+    pub uri: Uri,
+    /// The HTTP version of the request.
+    pub version: Version,
+    /// The request headers.
+    pub headers: HeaderMap,
+    /// The address of the client, if the integration has access to it.
+    ///
+    /// With `leptos_axum`, this requires serving the app with
+    /// `into_make_service_with_connect_info::<SocketAddr>()`. With `leptos_tower`, the server
+    /// has to add the address to the request extensions.
+    pub remote_addr: Option<SocketAddr>,
+    /// Request extensions inserted by middleware.
+    ///
+    /// Actix request extensions do not have to be thread-safe, so they cannot be carried over,
+    /// and this is always empty with `leptos_actix`.
+    pub extensions: Extensions,
+}
+
+/// Types that can be extracted from the [`RequestParts`] of the current request with
+/// [`use_request_parts`].
+pub trait FromRequestParts: Sized {
+    /// Extracts the value from the parts of the current request.
+    fn from_request_parts(
+        parts: &RequestParts,
+    ) -> Result<Self, ServerFnErrorErr>;
+}
+
+/// Extracts `T` from the request that is currently being handled on the server.
+///
+/// This works the same way with every server integration, in components rendered on the server
+/// and in server functions. It fails if no request is being handled, for example on the client.
+pub fn use_request_parts<T>() -> Result<T, ServerFnErrorErr>
+where
+    T: FromRequestParts,
+{
+    with_context::<RequestParts, _>(T::from_request_parts).unwrap_or_else(
+        || {
+            Err(ServerFnErrorErr::ServerError(
+                "RequestParts should have been provided by the server \
+                 integration"
+                    .to_string(),
+            ))
+        },
+    )
+}
+
+impl FromRequestParts for RequestParts {
+    fn from_request_parts(
+        parts: &RequestParts,
+    ) -> Result<Self, ServerFnErrorErr> {
+        Ok(parts.clone())
+    }
+}
+
+impl FromRequestParts for Method {
+    fn from_request_parts(
+        parts: &RequestParts,
+    ) -> Result<Self, ServerFnErrorErr> {
+        Ok(parts.method.clone())
+    }
+}
+
+impl FromRequestParts for Uri {
+    fn from_request_parts(
+        parts: &RequestParts,
+    ) -> Result<Self, ServerFnErrorErr> {
+        Ok(parts.uri.clone())
+    }
+}
+
+impl FromRequestParts for Version {
+    fn from_request_parts(
+        parts: &RequestParts,
+    ) -> Result<Self, ServerFnErrorErr> {
+        Ok(parts.version)
+    }
+}
+
+impl FromRequestParts for HeaderMap {
+    fn from_request_parts(
+        parts: &RequestParts,
+    ) -> Result<Self, ServerFnErrorErr> {
+        Ok(parts.headers.clone())
+    }
+}
+
+impl<T> FromRequestParts for Option<T>
+where
+    T: FromRequestParts,
+{
+    fn from_request_parts(
+        parts: &RequestParts,
+    ) -> Result<Self, ServerFnErrorErr> {
+        Ok(T::from_request_parts(parts).ok())
+    }
+}
+
+/// The cookies sent with the current request, parsed from its `Cookie` headers.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Cookies(HashMap<String, String>);
+
+impl Cookies {
+    /// Parses the cookies in the `Cookie` headers of `headers`.
+    ///
+    /// Values are returned as they were sent, without percent-decoding. If a cookie appears more
+    /// than once, the first value wins, which matches how browsers order cookies with more
+    /// specific paths first.
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let mut cookies = Self::default();
+        for header in headers.get_all(http::header::COOKIE) {
+            if let Ok(header) = header.to_str() {
+                cookies.extend_from_str(header);
+            }
+        }
+        cookies
+    }
+
+    /// Parses a list of cookies in the format of a `Cookie` header or `document.cookie`,
+    /// i.e., `name=value` pairs separated by semicolons.
+    pub fn parse(cookies: &str) -> Self {
+        let mut parsed = Self::default();
+        parsed.extend_from_str(cookies);
+        parsed
+    }
+
+    fn extend_from_str(&mut self, cookies: &str) {
+        for (name, value) in
+            cookies.split(';').filter_map(|pair| pair.split_once('='))
+        {
+            let value = value.trim();
+            let value = value
+                .strip_prefix('"')
+                .and_then(|value| value.strip_suffix('"'))
+                .unwrap_or(value);
+            self.0
+                .entry(name.trim().to_string())
+                .or_insert_with(|| value.to_string());
+        }
+    }
+
+    /// Returns the value of the cookie called `name`.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0.get(name).map(String::as_str)
+    }
+
+    /// Iterates over the names and values of all cookies.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    /// Whether the request carried no cookies.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl FromRequestParts for Cookies {
+    fn from_request_parts(
+        parts: &RequestParts,
+    ) -> Result<Self, ServerFnErrorErr> {
+        Ok(Cookies::from_headers(&parts.headers))
+    }
+}
+
+/// Information about the connection the current request was received on.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConnectionInfo {
+    /// The address of the client, if the integration has access to it.
+    pub remote_addr: Option<SocketAddr>,
+    /// The host the request was sent to, from the request URI or its `Host` header.
+    pub host: Option<String>,
+}
+
+impl FromRequestParts for ConnectionInfo {
+    fn from_request_parts(
+        parts: &RequestParts,
+    ) -> Result<Self, ServerFnErrorErr> {
+        let host = parts
+            .uri
+            .authority()
+            .map(|authority| authority.to_string())
+            .or_else(|| {
+                parts
+                    .headers
+                    .get(http::header::HOST)
+                    .and_then(|host| host.to_str().ok())
+                    .map(str::to_string)
+            });
+        Ok(ConnectionInfo {
+            remote_addr: parts.remote_addr,
+            host,
+        })
+    }
+}
+
+/// The [W3C trace context](https://www.w3.org/TR/trace-context/) of the current request, parsed
+/// from its `traceparent` header.
+///
+/// The server integrations record this on the tracing span they create for each request, so that
+/// it can be correlated with the trace of the service that sent the request.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TraceContext {
+    /// The ID of the whole trace, as 32 lowercase hex digits.
+    pub trace_id: String,
+    /// The ID of the span in the calling service that sent the request, as 16 lowercase hex
+    /// digits.
+    pub parent_id: String,
+    /// Whether the caller recorded the trace.
+    pub sampled: bool,
+}
+
+impl TraceContext {
+    /// Parses the value of a `traceparent` header. Returns `None` if it is invalid.
+    pub fn parse(traceparent: &str) -> Option<Self> {
+        let mut parts = traceparent.trim().split('-');
+        let version = parts.next()?;
+        let trace_id = parts.next()?;
+        let parent_id = parts.next()?;
+        let flags = parts.next()?;
+        // later versions may append fields, but version 00 has exactly four
+        if version == "ff" || (version == "00" && parts.next().is_some()) {
+            return None;
+        }
+        let is_hex = |s: &str, len: usize| {
+            s.len() == len
+                && s.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+        };
+        let is_zero = |s: &str| s.bytes().all(|b| b == b'0');
+        if !is_hex(version, 2)
+            || !is_hex(trace_id, 32)
+            || !is_hex(parent_id, 16)
+            || !is_hex(flags, 2)
+            || is_zero(trace_id)
+            || is_zero(parent_id)
+        {
+            return None;
+        }
+        let flags = u8::from_str_radix(flags, 16).ok()?;
+        Some(TraceContext {
+            trace_id: trace_id.to_string(),
+            parent_id: parent_id.to_string(),
+            sampled: flags & 1 == 1,
+        })
+    }
+
+    /// Parses the `traceparent` header of `headers`, if it is present and valid.
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        headers
+            .get("traceparent")
+            .and_then(|value| value.to_str().ok())
+            .and_then(Self::parse)
+    }
+}
+
+impl FromRequestParts for TraceContext {
+    fn from_request_parts(
+        parts: &RequestParts,
+    ) -> Result<Self, ServerFnErrorErr> {
+        Self::from_headers(&parts.headers).ok_or_else(|| {
+            ServerFnErrorErr::ServerError(
+                "the request has no valid traceparent header".to_string(),
+            )
+        })
+    }
+}
+
+/// Extracts a value of type `T` from the request [`Extensions`], which is usually inserted by
+/// middleware.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Extension<T>(pub T);
+
+impl<T> FromRequestParts for Extension<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    fn from_request_parts(
+        parts: &RequestParts,
+    ) -> Result<Self, ServerFnErrorErr> {
+        parts
+            .extensions
+            .get::<T>()
+            .cloned()
+            .map(Extension)
+            .ok_or_else(|| {
+                ServerFnErrorErr::ServerError(format!(
+                    "missing request extension of type {}",
+                    std::any::type_name::<T>()
+                ))
+            })
+    }
+}
+
+/// Adds headers to the response for the request that is currently being handled on the server.
+///
+/// This is provided via context by the server integrations, next to their own
+/// `ResponseOptions`, so that code shared between integrations can set headers like
+/// `Set-Cookie`.
+#[derive(Clone)]
+pub struct ResponseHeaders(
+    Arc<dyn Fn(HeaderName, HeaderValue) -> bool + Send + Sync>,
+);
+
+impl ResponseHeaders {
+    /// Creates a handle that passes appended headers to `append`, which should return `false` if
+    /// the headers of the response have already been sent.
+    pub fn new(
+        append: impl Fn(HeaderName, HeaderValue) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        Self(Arc::new(append))
+    }
+
+    /// Appends a header to the response, leaving any header with the same name intact.
+    ///
+    /// Returns `false` if the status and headers of the response have already been sent, in
+    /// which case the header has no effect.
+    pub fn append(&self, name: HeaderName, value: HeaderValue) -> bool {
+        (self.0)(name, value)
+    }
+}
+
+impl fmt::Debug for ResponseHeaders {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResponseHeaders").finish_non_exhaustive()
+    }
+}
+
+/// A resource that the browser should start to load before it has received the HTML that uses
+/// it, sent as a `Link` header of the server-rendered response.
+///
+/// The headers of a streamed response are sent along with its first chunk, so the browser can
+/// begin to fetch these resources while the rest of the page is still rendering. Proxies and CDNs
+/// that support `103 Early Hints` also use these headers to send early hints for later requests.
+///
+/// [`HydrationScripts`](crate::hydration::HydrationScripts) preloads the JS and WASM of the app this
+/// way, and `leptos_meta` does the same for stylesheets and any preload `<Link/>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreloadLink {
+    href: String,
+    rel: &'static str,
+    as_: Option<String>,
+    type_: Option<String>,
+    crossorigin: Option<String>,
+}
+
+impl PreloadLink {
+    /// Preloads the resource at `href`, which will be used as the given type of destination,
+    /// like `"style"`, `"font"`, or `"fetch"`.
+    pub fn preload(href: impl Into<String>, as_: impl Into<String>) -> Self {
+        Self {
+            href: href.into(),
+            rel: "preload",
+            as_: Some(as_.into()),
+            type_: None,
+            crossorigin: None,
+        }
+    }
+
+    /// Preloads the JavaScript module at `href`.
+    pub fn modulepreload(href: impl Into<String>) -> Self {
+        Self {
+            href: href.into(),
+            rel: "modulepreload",
+            as_: None,
+            type_: None,
+            crossorigin: None,
+        }
+    }
+
+    /// Sets the MIME type of the resource.
+    pub fn type_(mut self, type_: impl Into<String>) -> Self {
+        self.type_ = Some(type_.into());
+        self
+    }
+
+    /// Sets the CORS mode of the request, like `"anonymous"`. This should match the
+    /// `crossorigin` attribute of the element that uses the resource, or the browser will load
+    /// it twice.
+    pub fn crossorigin(mut self, crossorigin: impl Into<String>) -> Self {
+        self.crossorigin = Some(crossorigin.into());
+        self
+    }
+
+    /// Returns the value of the `Link` header for this resource.
+    pub fn header_value(&self) -> String {
+        let mut value = format!("<{}>; rel={}", self.href, self.rel);
+        if let Some(as_) = &self.as_ {
+            value.push_str("; as=");
+            value.push_str(as_);
+        }
+        if let Some(type_) = &self.type_ {
+            value.push_str(&format!("; type=\"{type_}\""));
+        }
+        match self.crossorigin.as_deref() {
+            None => {}
+            Some("") | Some("anonymous") => value.push_str("; crossorigin"),
+            Some(crossorigin) => {
+                value.push_str(&format!("; crossorigin={crossorigin}"))
+            }
+        }
+        value
+    }
+
+    /// Adds the `Link` header to the response of the request that is currently being rendered.
+    ///
+    /// Returns `false` if there is no such response, which is the case on the client, or if its
+    /// headers have already been sent.
+    pub fn send(&self) -> bool {
+        let Ok(value) = HeaderValue::from_str(&self.header_value()) else {
+            return false;
+        };
+        with_context::<ResponseHeaders, _>(|headers| {
+            headers.append(http::header::LINK, value)
+        })
+        .unwrap_or(false)
+    }
+}
@@ -0,0 +1,382 @@
+//! Keyboard navigation for composite widgets, like listboxes, menus, tab lists and toolbars.
+//!
+//! The ARIA authoring practices ask that a composite widget is a single stop in the tab order,
+//! and that the arrow keys move focus between its items. [`use_roving_tabindex`] implements this
+//! with a “roving” `tabindex`: the active item has `tabindex="0"` and every other item has
+//! `tabindex="-1"`, and the arrow keys, <kbd>Home</kbd> and <kbd>End</kbd> move focus and the
+//! `tabindex` together.
+//!
+//! ```rust
+//! use leptos::{
+//!     html::Div,
+//!     prelude::*,
+//!     roving_tabindex::{use_roving_tabindex, Orientation, RovingTabindexOptions},
+//! };
+//!
+//! #[component]
+//! pub fn Toolbar(tools: ReadSignal<Vec<String>>) -> impl IntoView {
+//!     let toolbar = NodeRef::<Div>::new();
+//!     let roving = use_roving_tabindex(
+//!         toolbar,
+//!         RovingTabindexOptions {
+//!             items: "button".into(),
+//!             orientation: Orientation::Horizontal,
+//!             ..Default::default()
+//!         },
+//!     );
+//!
+//!     view! {
+//!         <div node_ref=toolbar role="toolbar">
+//!             <For each=move || tools.get() key=|tool| tool.clone() let:tool>
+//!                 <button>{tool}</button>
+//!             </For>
+//!         </div>
+//!         <p>"Active tool: " {move || roving.active()}</p>
+//!     }
+//! }
+//! ```
+//!
+//! Items are looked up in the DOM whenever they are needed, so items that are added, removed or
+//! moved by [`For`](crate::prelude::For) or other fine-grained updates are handled without any
+//! extra work: the active item keeps its `tabindex="0"` when items are inserted before it, and
+//! the next one takes over when it is removed. Items that are `disabled` or `aria-disabled="true"`
+//! are skipped.
+//!
+//! Keyboard navigation is only set up in the browser; on the server, items are rendered as they
+//! are, so the first item should be given `tabindex="0"` in the view if the widget must be
+//! reachable before hydration.
+
+use reactive_graph::{
+    effect::Effect,
+    owner::{on_cleanup, LocalStorage, StoredValue},
+    signal::RwSignal,
+    traits::{Get, GetUntracked, Set, UpdateValue, WithValue},
+};
+use send_wrapper::SendWrapper;
+use std::borrow::Cow;
+use tachys::{html::element::ElementType, reactive_graph::node_ref::NodeRef};
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::{
+    js_sys::Array, Element, FocusEvent, HtmlElement, KeyboardEvent,
+    MutationObserver, MutationObserverInit,
+};
+
+/// The arrow keys that move between the items of a widget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Orientation {
+    /// <kbd>←</kbd> and <kbd>→</kbd>, for toolbars, tab lists and menu bars.
+    Horizontal,
+    /// <kbd>↑</kbd> and <kbd>↓</kbd>, for listboxes, menus and trees.
+    Vertical,
+    /// All four arrow keys.
+    #[default]
+    Both,
+}
+
+/// Options that configure [`use_roving_tabindex`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RovingTabindexOptions {
+    /// A CSS selector for the items within the container. Defaults to the ARIA roles of the
+    /// items of listboxes, menus, tab lists, radio groups, trees and grids.
+    pub items: Cow<'static, str>,
+    /// The arrow keys that move between items. Defaults to [`Orientation::Both`].
+    pub orientation: Orientation,
+    /// Whether moving past the last item focuses the first one, and the other way around.
+    /// Defaults to `true`.
+    pub wrap: bool,
+}
+
+impl Default for RovingTabindexOptions {
+    fn default() -> Self {
+        Self {
+            items: Cow::Borrowed(
+                "[role=option],[role=menuitem],[role=menuitemcheckbox],\
+                 [role=menuitemradio],[role=tab],[role=radio],\
+                 [role=treeitem],[role=gridcell]",
+            ),
+            orientation: Orientation::default(),
+            wrap: true,
+        }
+    }
+}
+
+/// A handle to the keyboard navigation of a composite widget, created with
+/// [`use_roving_tabindex`].
+#[derive(Debug, Clone, Copy)]
+pub struct UseRovingTabindex {
+    active: RwSignal<usize>,
+    inner: StoredValue<Roving, LocalStorage>,
+}
+
+struct Roving {
+    container: Option<Element>,
+    options: RovingTabindexOptions,
+}
+
+/// Sets up arrow-key navigation between the items of the element that `container` refers to,
+/// once it has been mounted.
+///
+/// The listeners are removed when the reactive owner that called this is cleaned up.
+pub fn use_roving_tabindex<E>(
+    container: NodeRef<E>,
+    options: RovingTabindexOptions,
+) -> UseRovingTabindex
+where
+    E: ElementType,
+    E::Output: JsCast + Clone + 'static,
+{
+    let roving = UseRovingTabindex {
+        active: RwSignal::new(0),
+        inner: StoredValue::new_local(Roving {
+            container: None,
+            options,
+        }),
+    };
+
+    // effects only run in the browser, so nothing is set up on the server
+    Effect::new(move |_| {
+        let Some(container) = container.get() else {
+            return;
+        };
+        let container = container.unchecked_into::<Element>();
+        roving
+            .inner
+            .update_value(|inner| inner.container = Some(container.clone()));
+        roving.sync();
+
+        let on_keydown =
+            Closure::<dyn Fn(KeyboardEvent)>::new(move |ev: KeyboardEvent| {
+                roving.on_keydown(ev)
+            });
+        let on_focusin =
+            Closure::<dyn Fn(FocusEvent)>::new(move |ev: FocusEvent| {
+                if let Some(index) = roving.index_of_target(ev.target()) {
+                    roving.set_active(index);
+                }
+            });
+        let on_mutation =
+            Closure::<dyn Fn(Array)>::new(move |_: Array| roving.sync());
+        _ = container.add_event_listener_with_callback(
+            "keydown",
+            on_keydown.as_ref().unchecked_ref(),
+        );
+        _ = container.add_event_listener_with_callback(
+            "focusin",
+            on_focusin.as_ref().unchecked_ref(),
+        );
+        let observer =
+            MutationObserver::new(on_mutation.as_ref().unchecked_ref()).ok();
+        if let Some(observer) = &observer {
+            let init = MutationObserverInit::new();
+            init.set_child_list(true);
+            init.set_subtree(true);
+            _ = observer.observe_with_options(&container, &init);
+        }
+
+        let cleanup = SendWrapper::new((
+            container,
+            on_keydown,
+            on_focusin,
+            on_mutation,
+            observer,
+        ));
+        on_cleanup(move || {
+            let (container, on_keydown, on_focusin, _on_mutation, observer) =
+                cleanup.take();
+            _ = container.remove_event_listener_with_callback(
+                "keydown",
+                on_keydown.as_ref().unchecked_ref(),
+            );
+            _ = container.remove_event_listener_with_callback(
+                "focusin",
+                on_focusin.as_ref().unchecked_ref(),
+            );
+            if let Some(observer) = observer {
+                observer.disconnect();
+            }
+        });
+    });
+
+    roving
+}
+
+impl UseRovingTabindex {
+    /// The index of the active item, among the items that are not disabled. Reactive.
+    pub fn active(&self) -> usize {
+        self.active.get()
+    }
+
+    /// Makes the item at `index` the one that is reached with <kbd>Tab</kbd>, without focusing it.
+    pub fn set_active(&self, index: usize) {
+        let items = self.items();
+        if index >= items.len() {
+            return;
+        }
+        set_tabindexes(&items, index);
+        if self.active.get_untracked() != index {
+            self.active.set(index);
+        }
+    }
+
+    /// Makes the item at `index` the active one, and focuses it.
+    pub fn focus(&self, index: usize) {
+        self.set_active(index);
+        if let Some(item) = self.items().get(index) {
+            _ = item.unchecked_ref::<HtmlElement>().focus();
+        }
+    }
+
+    fn items(&self) -> Vec<Element> {
+        self.inner
+            .try_with_value(|inner| {
+                let Some(container) = &inner.container else {
+                    return Vec::new();
+                };
+                let Ok(nodes) =
+                    container.query_selector_all(&inner.options.items)
+                else {
+                    return Vec::new();
+                };
+                (0..nodes.length())
+                    .filter_map(|idx| nodes.item(idx))
+                    .filter_map(|node| node.dyn_into::<Element>().ok())
+                    .filter(|item| {
+                        !item.has_attribute("disabled")
+                            && item.get_attribute("aria-disabled").as_deref()
+                                != Some("true")
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn index_of_target(
+        &self,
+        target: Option<web_sys::EventTarget>,
+    ) -> Option<usize> {
+        let target = target?.dyn_into::<Element>().ok()?;
+        self.items().iter().position(|item| *item == target)
+    }
+
+    /// Keeps the `tabindex` on the active item after items have been added, removed or moved.
+    fn sync(&self) {
+        let items = self.items();
+        if items.is_empty() {
+            return;
+        }
+        let index = items
+            .iter()
+            .position(|item| {
+                item.get_attribute("tabindex").as_deref() == Some("0")
+            })
+            .unwrap_or_else(|| self.active.get_untracked())
+            .min(items.len() - 1);
+        self.set_active(index);
+    }
+
+    fn on_keydown(&self, ev: KeyboardEvent) {
+        if ev.alt_key() || ev.ctrl_key() || ev.meta_key() {
+            return;
+        }
+        let Some(current) = self.index_of_target(ev.target()) else {
+            return;
+        };
+        let (orientation, wrap) = self.inner.with_value(|inner| {
+            (inner.options.orientation, inner.options.wrap)
+        });
+        let len = self.items().len();
+        if let Some(next) =
+            next_index(&ev.key(), current, len, orientation, wrap)
+        {
+            ev.prevent_default();
+            self.focus(next);
+        }
+    }
+}
+
+fn set_tabindexes(items: &[Element], active: usize) {
+    for (idx, item) in items.iter().enumerate() {
+        let tabindex = if idx == active { "0" } else { "-1" };
+        if item.get_attribute("tabindex").as_deref() != Some(tabindex) {
+            _ = item.set_attribute("tabindex", tabindex);
+        }
+    }
+}
+
+/// The index of the item that `key` moves to from `current`, if `key` is a navigation key.
+fn next_index(
+    key: &str,
+    current: usize,
+    len: usize,
+    orientation: Orientation,
+    wrap: bool,
+) -> Option<usize> {
+    if len == 0 {
+        return None;
+    }
+    let last = len - 1;
+    let horizontal = orientation != Orientation::Vertical;
+    let vertical = orientation != Orientation::Horizontal;
+    let forward =
+        (key == "ArrowRight" && horizontal) || (key == "ArrowDown" && vertical);
+    let backward =
+        (key == "ArrowLeft" && horizontal) || (key == "ArrowUp" && vertical);
+    match key {
+        "Home" => Some(0),
+        "End" => Some(last),
+        _ if forward => match current.checked_add(1).filter(|i| *i <= last) {
+            Some(next) => Some(next),
+            None if wrap => Some(0),
+            None => Some(last),
+        },
+        _ if backward => match current.checked_sub(1) {
+            Some(prev) => Some(prev),
+            None if wrap => Some(last),
+            None => Some(0),
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{next_index, Orientation};
+
+    #[test]
+    fn arrow_keys_follow_the_orientation() {
+        let next = |key, orientation| next_index(key, 1, 3, orientation, true);
+        assert_eq!(next("ArrowDown", Orientation::Vertical), Some(2));
+        assert_eq!(next("ArrowUp", Orientation::Vertical), Some(0));
+        assert_eq!(next("ArrowRight", Orientation::Vertical), None);
+        assert_eq!(next("ArrowRight", Orientation::Horizontal), Some(2));
+        assert_eq!(next("ArrowDown", Orientation::Horizontal), None);
+        assert_eq!(next("ArrowLeft", Orientation::Both), Some(0));
+        assert_eq!(next("Home", Orientation::Horizontal), Some(0));
+        assert_eq!(next("End", Orientation::Vertical), Some(2));
+        assert_eq!(next("a", Orientation::Both), None);
+    }
+
+    #[test]
+    fn wraps_around_the_ends_only_when_asked() {
+        assert_eq!(
+            next_index("ArrowDown", 2, 3, Orientation::Both, true),
+            Some(0)
+        );
+        assert_eq!(
+            next_index("ArrowUp", 0, 3, Orientation::Both, true),
+            Some(2)
+        );
+        assert_eq!(
+            next_index("ArrowDown", 2, 3, Orientation::Both, false),
+            Some(2)
+        );
+        assert_eq!(
+            next_index("ArrowUp", 0, 3, Orientation::Both, false),
+            Some(0)
+        );
+        assert_eq!(
+            next_index("ArrowDown", 0, 0, Orientation::Both, true),
+            None
+        );
+    }
+}
@@ -0,0 +1,219 @@
+//! Styles scoped to a component, created with the [`style!`](crate::style) macro.
+//!
+//! `style!` rewrites the selectors of its CSS at compile time so that they only match elements
+//! with a class of its own, and returns that class:
+//!
+//! ```rust
+//! use leptos::prelude::*;
+//!
+//! #[component]
+//! pub fn Badge(label: String) -> impl IntoView {
+//!     let class = style!(".badge { padding: 0 0.5em; border-radius: 1em; }");
+//!
+//!     view! { class = class,
+//!         <span class="badge">{label}</span>
+//!     }
+//! }
+//! ```
+//!
+//! The styles are added to the page as they are used:
+//! - On the server, the styles of the components that were rendered are inlined in the `<head>`
+//!   by `leptos_meta`, as long as the app calls `provide_meta_context()`, so that the page is
+//!   styled when it is first painted, without loading the styles of every other component.
+//! - In the browser, the styles of components that were not rendered on the server are added to
+//!   the `<head>` when they are first used.
+//!
+//! ## Extracting a stylesheet
+//!
+//! When the `LEPTOS_SCOPED_CSS_DIR` environment variable is set while compiling, `style!` also
+//! writes the scoped CSS of each of its uses to a file in that directory, along with a manifest
+//! of the styles that each crate uses, which [`bundle`] reads to join the current styles into a
+//! single stylesheet, for example in a step of the deployment. Once it is served, linking it with
+//! `leptos_meta`'s `<ScopedStylesheet href="..."/>` stops the styles from being added in the
+//! browser; the styles of the first page are still inlined by the server.
+
+use reactive_graph::owner::with_context;
+use std::{
+    collections::BTreeSet,
+    fmt,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+};
+
+/// The CSS of a use of the [`style!`](crate::style) macro, scoped to its class.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ScopedStyle {
+    class: &'static str,
+    css: &'static str,
+}
+
+impl ScopedStyle {
+    /// Creates a style from its class and the CSS that has already been scoped to it. This is
+    /// used by [`style!`](crate::style).
+    #[doc(hidden)]
+    pub const fn new(class: &'static str, css: &'static str) -> Self {
+        Self { class, css }
+    }
+
+    /// The class that the rules of the style are scoped to.
+    pub fn class(&self) -> &'static str {
+        self.class
+    }
+
+    /// The scoped CSS.
+    pub fn css(&self) -> &'static str {
+        self.css
+    }
+}
+
+/// Records that the current page uses `style`, and returns its class. This is used by
+/// [`style!`](crate::style).
+#[doc(hidden)]
+pub fn use_scoped_style(style: &'static ScopedStyle) -> &'static str {
+    if let Some(used) = with_context(UsedScopedStyles::clone) {
+        used.insert(style);
+    } else if cfg!(all(
+        target_arch = "wasm32",
+        not(any(target_os = "emscripten", target_os = "wasi"))
+    )) {
+        browser::add(style);
+    }
+    style.class
+}
+
+/// The scoped styles used while rendering a page on the server.
+///
+/// `leptos_meta` provides this through context while rendering, and inlines the styles in the
+/// `<head>` with [`to_html`](Self::to_html).
+#[derive(Clone, Default)]
+pub struct UsedScopedStyles(Arc<Mutex<Vec<&'static ScopedStyle>>>);
+
+impl fmt::Debug for UsedScopedStyles {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.classes()).finish()
+    }
+}
+
+impl UsedScopedStyles {
+    /// Creates an empty set of styles.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn insert(&self, style: &'static ScopedStyle) {
+        let mut styles = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        if !styles.iter().any(|used| used.class == style.class) {
+            styles.push(style);
+        }
+    }
+
+    /// The classes of the styles that were used, in the order they were first used.
+    pub fn classes(&self) -> Vec<&'static str> {
+        self.0
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .map(|style| style.class)
+            .collect()
+    }
+
+    /// A `<style>` element with the CSS of every style that was used, or an empty string if none
+    /// was.
+    ///
+    /// The element lists the classes in its `data-leptos-scoped` attribute, so that they are not
+    /// added again in the browser.
+    pub fn to_html(&self) -> String {
+        let styles = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        if styles.is_empty() {
+            return String::new();
+        }
+        let classes = styles
+            .iter()
+            .map(|style| style.class)
+            .collect::<Vec<_>>()
+            .join(" ");
+        let css = styles.iter().map(|style| style.css).collect::<String>();
+        // the CSS was written in the source code, but must not close the element early
+        let css = css.replace("</", "<\\/");
+        format!("<style data-leptos-scoped=\"{classes}\">{css}</style>")
+    }
+}
+
+static LINKED: AtomicBool = AtomicBool::new(false);
+
+/// Stops scoped styles from being added to the page in the browser, because a stylesheet
+/// extracted with [`bundle`] has been linked. `leptos_meta`'s `<ScopedStylesheet/>` calls this.
+pub fn set_stylesheet_linked(linked: bool) {
+    LINKED.store(linked, Ordering::Relaxed);
+}
+
+/// Joins the CSS files written by [`style!`](crate::style) to `dir`, when
+/// `LEPTOS_SCOPED_CSS_DIR` is set to it, into a single stylesheet.
+///
+/// Only the styles listed in the manifests that `style!` keeps in `dir` for each crate are
+/// included, so that the files of styles that have been changed or removed since they were
+/// written are left out. The styles are joined in the order of their classes, so that the
+/// stylesheet is the same from one build to the next.
+pub fn bundle(dir: impl AsRef<std::path::Path>) -> std::io::Result<String> {
+    let dir = dir.as_ref();
+    let mut classes = BTreeSet::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "manifest") {
+            classes.extend(
+                std::fs::read_to_string(path)?
+                    .lines()
+                    .filter(|class| !class.is_empty())
+                    .map(str::to_string),
+            );
+        }
+    }
+    let mut css = String::new();
+    for class in classes {
+        css.push_str(&std::fs::read_to_string(
+            dir.join(format!("{class}.css")),
+        )?);
+        css.push('\n');
+    }
+    Ok(css)
+}
+
+mod browser {
+    use super::{ScopedStyle, LINKED};
+    use leptos_dom::helpers::{document, queue_microtask};
+    use std::{cell::RefCell, collections::HashSet, sync::atomic::Ordering};
+
+    thread_local! {
+        static ADDED: RefCell<HashSet<&'static str>> = RefCell::new(HashSet::new());
+    }
+
+    /// Adds the `<style>` of `style` to the `<head>`, unless it is already in the document.
+    pub(super) fn add(style: &'static ScopedStyle) {
+        if !ADDED.with(|added| added.borrow_mut().insert(style.class)) {
+            return;
+        }
+        // the `<ScopedStylesheet/>` of the app can be rendered after the components that use
+        // scoped styles, so the styles are only added once they have all been rendered
+        queue_microtask(move || {
+            if LINKED.load(Ordering::Relaxed) {
+                return;
+            }
+            let document = document();
+            let selector =
+                format!("style[data-leptos-scoped~=\"{}\"]", style.class);
+            if document.query_selector(&selector).ok().flatten().is_some() {
+                return;
+            }
+            let Ok(el) = document.create_element("style") else {
+                return;
+            };
+            _ = el.set_attribute("data-leptos-scoped", style.class);
+            el.set_text_content(Some(style.css));
+            if let Some(head) = document.query_selector("head").ok().flatten() {
+                _ = head.append_child(&el);
+            }
+        });
+    }
+}
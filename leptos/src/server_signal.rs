@@ -0,0 +1,317 @@
+//! Values that live on the server and are pushed to every client over a WebSocket.
+//!
+//! A [`ServerValue`] holds a value on the server. Mounting it with `leptos_axum::server_signal`
+//! or `leptos_actix::server_signal` serves it over a WebSocket endpoint, which sends the current
+//! value to each client as soon as it connects, and every new value after that. In the browser, a
+//! [`ServerSignal`] connected to that endpoint can be read like any other signal. This fits
+//! dashboards, presence indicators and other state that the server owns:
+//!
+//! ```rust,no_run
+//! use leptos::{prelude::*, server_signal::ServerSignal};
+//!
+//! #[component]
+//! pub fn VisitorCount() -> impl IntoView {
+//!     let visitors = ServerSignal::new("/ws/visitors", 0u64);
+//!
+//!     view! { <p>{move || visitors.get()} " people are here right now"</p> }
+//! }
+//! ```
+//!
+//! On the server, the same endpoint is served from a [`ServerValue`] that the rest of the server
+//! updates with [`ServerValue::set`] or [`ServerValue::update`].
+//!
+//! By default, only the server can change the value. A [`ServerValue`] created with
+//! [`ServerValue::writable`] also accepts the values sent by clients with
+//! [`ServerSignal::set_on_server`], and pushes them to every client, including the one that sent
+//! it.
+//!
+//! Values are sent as JSON. While rendering on the server, and until the first value arrives in
+//! the browser, a [`ServerSignal`] holds the initial value it was created with.
+
+use crate::websocket::{
+    use_websocket_with_options, ConnectionState, UseWebSocket, WebSocketOptions,
+};
+use futures::{
+    channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender},
+    Stream,
+};
+use or_poisoned::OrPoisoned;
+use reactive_graph::{
+    effect::Effect,
+    signal::{ReadSignal, RwSignal},
+    traits::{DefinedAt, IsDisposed, ReadUntracked, Set, Track},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    fmt::Debug,
+    panic::Location,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+/// A value held by the server, which is pushed to the [`ServerSignal`]s connected to it.
+///
+/// Clones refer to the same value.
+pub struct ServerValue<T> {
+    inner: Arc<Mutex<Shared<T>>>,
+    writable: bool,
+}
+
+struct Shared<T> {
+    value: T,
+    subscribers: Vec<UnboundedSender<T>>,
+}
+
+impl<T> Clone for ServerValue<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+            writable: self.writable,
+        }
+    }
+}
+
+impl<T> Debug for ServerValue<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let inner = self.inner.lock().or_poisoned();
+        f.debug_struct("ServerValue")
+            .field("value", &inner.value)
+            .field("subscribers", &inner.subscribers.len())
+            .field("writable", &self.writable)
+            .finish()
+    }
+}
+
+impl<T> ServerValue<T>
+where
+    T: Clone + Send + 'static,
+{
+    /// Creates a value that only the server can change.
+    pub fn new(value: T) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(Shared {
+                value,
+                subscribers: Vec::new(),
+            })),
+            writable: false,
+        }
+    }
+
+    /// Creates a value that clients can also change, with [`ServerSignal::set_on_server`].
+    pub fn writable(value: T) -> Self {
+        Self {
+            writable: true,
+            ..Self::new(value)
+        }
+    }
+
+    /// Whether clients can change the value.
+    pub fn is_writable(&self) -> bool {
+        self.writable
+    }
+
+    /// Returns a clone of the current value.
+    pub fn get(&self) -> T {
+        self.inner.lock().or_poisoned().value.clone()
+    }
+
+    /// Replaces the value, and pushes it to every connected client.
+    pub fn set(&self, value: T) {
+        self.update(|current| *current = value);
+    }
+
+    /// Updates the value in place, and pushes it to every connected client.
+    pub fn update(&self, fun: impl FnOnce(&mut T)) {
+        let mut inner = self.inner.lock().or_poisoned();
+        fun(&mut inner.value);
+        let value = inner.value.clone();
+        inner.subscribers.retain(|subscriber| {
+            subscriber.unbounded_send(value.clone()).is_ok()
+        });
+    }
+
+    /// Applies a value sent by a client, if the value is [writable](Self::writable). Returns
+    /// whether it was applied.
+    pub fn set_from_client(&self, value: T) -> bool {
+        if self.writable {
+            self.set(value);
+        }
+        self.writable
+    }
+
+    /// Returns a stream that yields the current value, and every new value after that.
+    ///
+    /// This is what the integrations send to each client. The stream ends when every clone of
+    /// the [`ServerValue`] has been dropped.
+    pub fn subscribe(&self) -> ServerValueStream<T> {
+        let (tx, rx) = unbounded();
+        let mut inner = self.inner.lock().or_poisoned();
+        _ = tx.unbounded_send(inner.value.clone());
+        inner.subscribers.push(tx);
+        ServerValueStream { rx }
+    }
+}
+
+/// The stream of values returned by [`ServerValue::subscribe`].
+#[derive(Debug)]
+pub struct ServerValueStream<T> {
+    rx: UnboundedReceiver<T>,
+}
+
+impl<T> Stream for ServerValueStream<T> {
+    type Item = T;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.rx).poll_next(cx)
+    }
+}
+
+/// A signal whose value is pushed by the server over a WebSocket, from a [`ServerValue`].
+///
+/// It can be read like a [`ReadSignal`], and converted into one.
+pub struct ServerSignal<T>
+where
+    T: 'static,
+{
+    value: ReadSignal<T>,
+    ws: UseWebSocket<T, T>,
+}
+
+impl<T> Clone for ServerSignal<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ServerSignal<T> {}
+
+impl<T> Debug for ServerSignal<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ServerSignal")
+            .field("value", &self.value)
+            .field("ws", &self.ws)
+            .finish()
+    }
+}
+
+impl<T> ServerSignal<T>
+where
+    T: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    /// Connects to the [`ServerValue`] served at `url`, with the default [`WebSocketOptions`].
+    ///
+    /// The signal holds `initial` until the first value arrives.
+    #[track_caller]
+    pub fn new(url: impl Into<String>, initial: T) -> Self {
+        Self::new_with_options(url, initial, WebSocketOptions::default())
+    }
+
+    /// Connects to the [`ServerValue`] served at `url`.
+    ///
+    /// The connection is reopened according to `options` when it is lost, and the server sends
+    /// the current value again when it is. The connection is closed when the reactive owner
+    /// that created the signal is cleaned up.
+    #[track_caller]
+    pub fn new_with_options(
+        url: impl Into<String>,
+        initial: T,
+        options: WebSocketOptions,
+    ) -> Self {
+        let value = RwSignal::new(initial);
+        let ws = use_websocket_with_options::<T, T>(url, options);
+        // effects only run in the browser, where the messages are received
+        Effect::new(move |_| {
+            if let Some(message) = ws.message() {
+                value.set(message);
+            }
+        });
+        Self {
+            value: value.read_only(),
+            ws,
+        }
+    }
+
+    /// Asks the server to change the value.
+    ///
+    /// The signal only changes once the server pushes the new value back, which it only does if
+    /// its [`ServerValue`] is [writable](ServerValue::writable).
+    pub fn set_on_server(&self, value: &T) {
+        self.ws.send(value);
+    }
+
+    /// The state of the connection to the server. Reactive.
+    pub fn state(&self) -> ConnectionState {
+        self.ws.state()
+    }
+}
+
+impl<T> DefinedAt for ServerSignal<T>
+where
+    T: Send + Sync,
+{
+    fn defined_at(&self) -> Option<&'static Location<'static>> {
+        self.value.defined_at()
+    }
+}
+
+impl<T> Track for ServerSignal<T>
+where
+    T: Send + Sync,
+{
+    fn track(&self) {
+        self.value.track();
+    }
+}
+
+impl<T> ReadUntracked for ServerSignal<T>
+where
+    T: Send + Sync,
+{
+    type Value = <ReadSignal<T> as ReadUntracked>::Value;
+
+    fn try_read_untracked(&self) -> Option<Self::Value> {
+        self.value.try_read_untracked()
+    }
+}
+
+impl<T> IsDisposed for ServerSignal<T>
+where
+    T: Send + Sync,
+{
+    fn is_disposed(&self) -> bool {
+        self.value.is_disposed()
+    }
+}
+
+impl<T> From<ServerSignal<T>> for ReadSignal<T> {
+    fn from(signal: ServerSignal<T>) -> Self {
+        signal.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ServerValue;
+    use futures::{executor::block_on, StreamExt};
+
+    #[test]
+    fn subscribers_receive_current_and_new_values() {
+        let value = ServerValue::new(1);
+        let mut first = value.subscribe();
+        value.set(2);
+        let mut second = value.subscribe();
+        value.update(|n| *n += 1);
+
+        assert!(!value.set_from_client(10));
+        drop(value);
+        assert_eq!(block_on(first.by_ref().collect::<Vec<_>>()), [1, 2, 3]);
+        assert_eq!(block_on(second.by_ref().collect::<Vec<_>>()), [2, 3]);
+    }
+}
@@ -0,0 +1,465 @@
+//! Registering a [service worker](https://developer.mozilla.org/en-US/docs/Web/API/Service_Worker_API),
+//! and precaching the app's assets so that it can start offline.
+//!
+//! [`use_service_worker`] registers the worker script and returns a [`UseServiceWorker`] handle
+//! that tracks the registration, and whether a new version of the worker is waiting to take over.
+//! The app can then offer to update:
+//!
+//! ```rust
+//! use leptos::{prelude::*, service_worker::use_service_worker};
+//!
+//! #[component]
+//! pub fn UpdateBanner() -> impl IntoView {
+//!     let sw = use_service_worker("/sw.js");
+//!
+//!     view! {
+//!         <Show when=move || sw.update_available()>
+//!             <p>
+//!                 "A new version is available. "
+//!                 <button on:click=move |_| sw.skip_waiting()>"Reload"</button>
+//!             </p>
+//!         </Show>
+//!     }
+//! }
+//! ```
+//!
+//! [`UseServiceWorker::skip_waiting`] sends a `{ type: "SKIP_WAITING" }` message to the waiting
+//! worker, which should answer it by calling `self.skipWaiting()`. The page reloads once the new
+//! worker has taken over.
+//!
+//! ## Precaching
+//!
+//! With [`hash_files`](leptos_config::LeptosOptions::hash_files), the names of the JS, Wasm and CSS
+//! files change with every build, so a worker can't list them itself. On the server,
+//! [`PrecacheManifest::new`] collects their current names, and
+//! [`to_script`](PrecacheManifest::to_script) turns them into a script that the worker can load
+//! with `importScripts()` before it fills its cache:
+//!
+//! ```js
+//! importScripts("/precache-manifest.js");
+//! const { version, urls } = self.__LEPTOS_PRECACHE;
+//!
+//! self.addEventListener("install", (event) => {
+//!   event.waitUntil(caches.open(version).then((cache) => cache.addAll(urls)));
+//! });
+//! self.addEventListener("message", (event) => {
+//!   if (event.data?.type === "SKIP_WAITING") self.skipWaiting();
+//! });
+//! ```
+//!
+//! The worker is only registered in the browser. On the server, the handle stays in the
+//! [`RegistrationState::Pending`] state.
+
+use crate::task::spawn_local;
+use leptos_config::LeptosOptions;
+use leptos_dom::helpers::window;
+use reactive_graph::{
+    effect::Effect,
+    owner::{on_cleanup, LocalStorage, StoredValue},
+    signal::RwSignal,
+    traits::{Get, GetValue, Set},
+};
+use send_wrapper::SendWrapper;
+use serde::Serialize;
+use std::{
+    cell::RefCell,
+    hash::{DefaultHasher, Hash, Hasher},
+    rc::Rc,
+};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    js_sys::{Object, Reflect},
+    RegistrationOptions, ServiceWorker, ServiceWorkerContainer,
+    ServiceWorkerRegistration, ServiceWorkerState,
+};
+
+/// The state of the registration of a service worker.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RegistrationState {
+    /// The worker is being registered. This is also the state on the server.
+    Pending,
+    /// The worker has been registered.
+    Registered,
+    /// The browser does not support service workers, or the page is not served over HTTPS.
+    Unsupported,
+    /// Registering the worker failed, for example because the script could not be loaded.
+    Failed(String),
+}
+
+/// Options that configure [`use_service_worker_with_options`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServiceWorkerOptions {
+    /// The URL of the worker script.
+    pub script_url: String,
+    /// The URLs the worker controls. Defaults to `None`, which is the directory of the script.
+    pub scope: Option<String>,
+    /// Whether the worker script is an ES module. Defaults to `false`.
+    pub module: bool,
+    /// Whether to reload the page once a new worker has taken over after
+    /// [`skip_waiting`](UseServiceWorker::skip_waiting). Defaults to `true`.
+    pub reload_on_update: bool,
+}
+
+impl ServiceWorkerOptions {
+    /// Options for the worker script at `script_url`, with the other options set to their
+    /// defaults.
+    pub fn new(script_url: impl Into<String>) -> Self {
+        Self {
+            script_url: script_url.into(),
+            scope: None,
+            module: false,
+            reload_on_update: true,
+        }
+    }
+}
+
+/// A handle to a service worker registration, created with [`use_service_worker`].
+#[derive(Debug, Clone, Copy)]
+pub struct UseServiceWorker {
+    state: RwSignal<RegistrationState>,
+    update_available: RwSignal<bool>,
+    inner: StoredValue<Rc<RefCell<Registration>>, LocalStorage>,
+}
+
+#[derive(Default)]
+struct Registration {
+    registration: Option<ServiceWorkerRegistration>,
+    container: Option<ServiceWorkerContainer>,
+    on_update_found: Option<Closure<dyn Fn()>>,
+    on_controller_change: Option<Closure<dyn Fn()>>,
+    installing: Vec<(ServiceWorker, Closure<dyn Fn()>)>,
+    skipping: bool,
+    disposed: bool,
+}
+
+/// Registers the service worker script at `script_url`, with the default
+/// [`ServiceWorkerOptions`].
+pub fn use_service_worker(script_url: impl Into<String>) -> UseServiceWorker {
+    use_service_worker_with_options(ServiceWorkerOptions::new(script_url))
+}
+
+/// Registers a service worker, and tracks whether a new version of it is waiting to take over.
+///
+/// The listeners are removed when the reactive owner that called this is cleaned up, but the
+/// worker stays registered.
+pub fn use_service_worker_with_options(
+    options: ServiceWorkerOptions,
+) -> UseServiceWorker {
+    let registration = Rc::new(RefCell::new(Registration::default()));
+    let sw = UseServiceWorker {
+        state: RwSignal::new(RegistrationState::Pending),
+        update_available: RwSignal::new(false),
+        inner: StoredValue::new_local(Rc::clone(&registration)),
+    };
+
+    // effects only run in the browser, so nothing is registered on the server
+    Effect::new(move |_| {
+        let Some(container) = container() else {
+            sw.state.set(RegistrationState::Unsupported);
+            return;
+        };
+        let init = RegistrationOptions::new();
+        if let Some(scope) = &options.scope {
+            init.set_scope(scope);
+        }
+        if options.module {
+            init.set_type("module");
+        }
+        let promise =
+            container.register_with_options(&options.script_url, &init);
+        listen_for_takeover(
+            &registration,
+            &container,
+            options.reload_on_update,
+        );
+
+        spawn_local({
+            let registration = Rc::clone(&registration);
+            async move {
+                let result = JsFuture::from(promise).await;
+                if registration.borrow().disposed {
+                    return;
+                }
+                match result {
+                    Ok(value) => {
+                        let value =
+                            value.unchecked_into::<ServiceWorkerRegistration>();
+                        watch_updates(sw, &registration, &container, value);
+                        sw.state.set(RegistrationState::Registered);
+                    }
+                    Err(e) => {
+                        let message = Reflect::get(&e, &"message".into())
+                            .ok()
+                            .and_then(|message| message.as_string())
+                            .unwrap_or_else(|| format!("{e:?}"));
+                        sw.state.set(RegistrationState::Failed(message));
+                    }
+                }
+            }
+        });
+
+        let registration = SendWrapper::new(Rc::clone(&registration));
+        on_cleanup(move || registration.borrow_mut().dispose());
+    });
+
+    sw
+}
+
+impl UseServiceWorker {
+    /// The state of the registration. Reactive.
+    pub fn state(&self) -> RegistrationState {
+        self.state.get()
+    }
+
+    /// Whether a new version of the worker has been installed, and is waiting for the current
+    /// one to stop controlling the page. Reactive.
+    pub fn update_available(&self) -> bool {
+        self.update_available.get()
+    }
+
+    /// Asks the waiting worker to take over from the current one. Does nothing if there is no
+    /// waiting worker.
+    pub fn skip_waiting(&self) {
+        let Some(registration) = self.inner.try_get_value() else {
+            return;
+        };
+        let mut registration = registration.borrow_mut();
+        let Some(waiting) = registration
+            .registration
+            .as_ref()
+            .and_then(|registration| registration.waiting())
+        else {
+            return;
+        };
+        let message = Object::new();
+        _ = Reflect::set(&message, &"type".into(), &"SKIP_WAITING".into());
+        if waiting.post_message(&message).is_ok() {
+            registration.skipping = true;
+        }
+    }
+
+    /// Asks the browser to check for a new version of the worker script right away, rather than
+    /// when it next navigates.
+    pub fn check_for_update(&self) {
+        let Some(registration) = self.inner.try_get_value() else {
+            return;
+        };
+        let registration = registration.borrow().registration.clone();
+        if let Some(registration) = registration {
+            // the outcome shows up in `update_available`
+            _ = registration.update();
+        }
+    }
+}
+
+impl Registration {
+    fn dispose(&mut self) {
+        self.disposed = true;
+        if let Some(registration) = self.registration.take() {
+            registration.set_onupdatefound(None);
+        }
+        if let Some(container) = self.container.take() {
+            container.set_oncontrollerchange(None);
+        }
+        for (worker, _on_state_change) in self.installing.drain(..) {
+            worker.set_onstatechange(None);
+        }
+        self.on_update_found = None;
+        self.on_controller_change = None;
+    }
+}
+
+/// `navigator.serviceWorker`, which is missing outside secure contexts.
+fn container() -> Option<ServiceWorkerContainer> {
+    let navigator = window().navigator();
+    Reflect::get(&navigator, &JsValue::from_str("serviceWorker"))
+        .ok()
+        .filter(|container| !container.is_undefined() && !container.is_null())
+        .map(|_| navigator.service_worker())
+}
+
+/// Reloads the page when a worker takes over after [`UseServiceWorker::skip_waiting`].
+fn listen_for_takeover(
+    registration: &Rc<RefCell<Registration>>,
+    container: &ServiceWorkerContainer,
+    reload_on_update: bool,
+) {
+    let on_controller_change = Closure::<dyn Fn()>::new({
+        let registration = Rc::downgrade(registration);
+        move || {
+            let Some(registration) = registration.upgrade() else {
+                return;
+            };
+            // a worker that claims the page when it is first installed does not need a reload
+            let skipping =
+                std::mem::take(&mut registration.borrow_mut().skipping);
+            if skipping && reload_on_update {
+                _ = window().location().reload();
+            }
+        }
+    });
+    container.set_oncontrollerchange(Some(
+        on_controller_change.as_ref().unchecked_ref(),
+    ));
+    let mut registration = registration.borrow_mut();
+    registration.container = Some(container.clone());
+    registration.on_controller_change = Some(on_controller_change);
+}
+
+/// Sets `update_available` when a new worker has been installed next to the current one.
+fn watch_updates(
+    sw: UseServiceWorker,
+    registration: &Rc<RefCell<Registration>>,
+    container: &ServiceWorkerContainer,
+    value: ServiceWorkerRegistration,
+) {
+    // the first worker is not an update, because nothing controlled the page before it
+    let is_update = {
+        let container = container.clone();
+        move || container.controller().is_some()
+    };
+    if value.waiting().is_some() && is_update() {
+        sw.update_available.set(true);
+    }
+
+    let on_update_found = Closure::<dyn Fn()>::new({
+        let registration = Rc::downgrade(registration);
+        let value = value.clone();
+        move || {
+            let (Some(registration), Some(installing)) =
+                (registration.upgrade(), value.installing())
+            else {
+                return;
+            };
+            let on_state_change = Closure::<dyn Fn()>::new({
+                let installing = installing.clone();
+                let is_update = is_update.clone();
+                move || {
+                    if installing.state() == ServiceWorkerState::Installed
+                        && is_update()
+                    {
+                        _ = sw.update_available.try_set(true);
+                    }
+                }
+            });
+            installing.set_onstatechange(Some(
+                on_state_change.as_ref().unchecked_ref(),
+            ));
+            registration
+                .borrow_mut()
+                .installing
+                .push((installing, on_state_change));
+        }
+    });
+    value.set_onupdatefound(Some(on_update_found.as_ref().unchecked_ref()));
+    let mut registration = registration.borrow_mut();
+    registration.registration = Some(value);
+    registration.on_update_found = Some(on_update_found);
+}
+
+/// The URLs of the assets that a service worker should cache so that the app can start offline,
+/// built on the server.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PrecacheManifest {
+    /// Changes whenever any of the URLs does, so it can name the cache that holds them.
+    pub version: String,
+    /// The URLs to cache.
+    pub urls: Vec<String>,
+}
+
+impl PrecacheManifest {
+    /// Lists the JS and Wasm files that [`HydrationScripts`](crate::hydration::HydrationScripts)
+    /// loads, and the CSS file in the [`site_pkg_dir`](LeptosOptions::site_pkg_dir) if there is
+    /// one, with the hashes in their names when
+    /// [`hash_files`](LeptosOptions::hash_files) is set.
+    pub fn new(options: &LeptosOptions) -> Self {
+        let (js_file_name, wasm_file_name) =
+            crate::hydration::hydration_file_names(options);
+        let mut css_file_name = options.output_name.to_string();
+        if options.hash_files {
+            if let Some((_, hash)) = crate::hydration::file_hashes(options)
+                .into_iter()
+                .find(|(file, _)| file == "css")
+            {
+                css_file_name.push_str(&format!(".{hash}"));
+            }
+        }
+
+        let root = options
+            .base_path
+            .as_deref()
+            .and_then(crate::base_path::normalize)
+            .unwrap_or_default();
+        let pkg_path = &options.site_pkg_dir;
+        let mut urls = vec![
+            format!("{root}/{pkg_path}/{js_file_name}.js"),
+            format!("{root}/{pkg_path}/{wasm_file_name}.wasm"),
+        ];
+        let css_file = std::path::Path::new(options.site_root.as_ref())
+            .join(pkg_path.as_ref())
+            .join(format!("{css_file_name}.css"));
+        if css_file.exists() {
+            urls.push(format!("{root}/{pkg_path}/{css_file_name}.css"));
+        }
+
+        let mut manifest = Self {
+            version: String::new(),
+            urls,
+        };
+        manifest.update_version();
+        manifest
+    }
+
+    /// Adds more URLs to cache, like the app shell at `/` or an offline page.
+    pub fn with_urls(
+        mut self,
+        urls: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.urls.extend(urls.into_iter().map(Into::into));
+        self.update_version();
+        self
+    }
+
+    /// The manifest as JSON, like `{"version":"…","urls":["…"]}`.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("a manifest is valid JSON")
+    }
+
+    /// A script that sets `self.__LEPTOS_PRECACHE` to the manifest, for the worker to load with
+    /// `importScripts()`. Serve it with the `text/javascript` content type.
+    pub fn to_script(&self) -> String {
+        format!("self.__LEPTOS_PRECACHE = {};", self.to_json())
+    }
+
+    fn update_version(&mut self) {
+        let mut hasher = DefaultHasher::new();
+        self.urls.hash(&mut hasher);
+        self.version = format!("leptos-{:016x}", hasher.finish());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PrecacheManifest;
+    use leptos_config::LeptosOptions;
+
+    #[test]
+    fn manifest_lists_assets_under_the_base_path() {
+        let options = LeptosOptions::builder()
+            .output_name("app")
+            .base_path("/shop/")
+            .build();
+        let manifest = PrecacheManifest::new(&options);
+        let version = manifest.version.clone();
+        assert_eq!(manifest.urls[0], "/shop/pkg/app.js");
+        assert!(manifest.urls[1].starts_with("/shop/pkg/app"));
+
+        let manifest = manifest.with_urls(["/shop/"]);
+        assert_eq!(manifest.urls.last().unwrap(), "/shop/");
+        assert_ne!(manifest.version, version);
+        assert!(manifest
+            .to_script()
+            .starts_with("self.__LEPTOS_PRECACHE = {\"version\":"));
+    }
+}
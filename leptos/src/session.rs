@@ -0,0 +1,155 @@
+//! The part of a server-side session that is visible to the client.
+//!
+//! Sessions themselves live on the server: the server integrations provide a `Session` via
+//! context, which server functions and server-rendered components use to read and write session
+//! data. Some of that data, like the name of the current user, is also useful in the browser.
+//! Keys that the server marks as public are sent to the client along with the server-rendered
+//! HTML, and can be read reactively with [`use_public_session`].
+//!
+//! ```rust
+//! use leptos::{prelude::*, session::use_public_session};
+//!
+//! #[component]
+//! pub fn Greeting() -> impl IntoView {
+//!     let session = use_public_session();
+//!
+//!     view! {
+//!         <Suspense>
+//!             {move || {
+//!                 session
+//!                     .get::<String>("user_name")
+//!                     .map(|name| format!("Hello, {name}!"))
+//!             }}
+//!         </Suspense>
+//!     }
+//! }
+//! ```
+
+use leptos_server::OnceResource;
+use reactive_graph::{
+    owner::{provide_context, with_context},
+    signal::RwSignal,
+    traits::{Get, Update, With},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{collections::HashMap, fmt, future::Future, pin::Pin, sync::Arc};
+
+type PublicValues = HashMap<String, String>;
+
+/// Loads the public values of the current session on the server.
+///
+/// This is provided via context by the server-side session layer. Each value is the JSON
+/// encoding of the value stored under its key.
+#[derive(Clone)]
+pub struct PublicSessionSource(
+    Arc<
+        dyn Fn() -> Pin<Box<dyn Future<Output = PublicValues> + Send>>
+            + Send
+            + Sync,
+    >,
+);
+
+impl PublicSessionSource {
+    /// Creates a source that loads the public values with `load`.
+    pub fn new<Fut>(load: impl Fn() -> Fut + Send + Sync + 'static) -> Self
+    where
+        Fut: Future<Output = PublicValues> + Send + 'static,
+    {
+        Self(Arc::new(move || Box::pin(load())))
+    }
+}
+
+impl fmt::Debug for PublicSessionSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PublicSessionSource")
+            .finish_non_exhaustive()
+    }
+}
+
+/// A reactive, read-only view of the public values of the current session, created with
+/// [`use_public_session`].
+#[derive(Clone, Copy)]
+pub struct PublicSession {
+    values: OnceResource<PublicValues>,
+    local: RwSignal<HashMap<String, Option<String>>>,
+}
+
+impl fmt::Debug for PublicSession {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PublicSession")
+            .field("local", &self.local)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Returns the public values of the current session.
+///
+/// The values are loaded while rendering on the server and sent to the client with the HTML,
+/// like any other resource. Reading them before they have loaded suspends, so they should be read
+/// under `<Suspense/>`. Without a server-side session, for example in a client-side rendered app,
+/// the session is empty.
+///
+/// The handle is provided via context, so every component below the first one that calls this
+/// function shares the same values.
+#[track_caller]
+pub fn use_public_session() -> PublicSession {
+    if let Some(session) = with_context(PublicSession::clone) {
+        return session;
+    }
+    let source = with_context(PublicSessionSource::clone);
+    let session = PublicSession {
+        values: OnceResource::new(async move {
+            match source {
+                Some(source) => (source.0)().await,
+                None => PublicValues::new(),
+            }
+        }),
+        local: RwSignal::new(HashMap::new()),
+    };
+    provide_context(session);
+    session
+}
+
+impl PublicSession {
+    /// Returns the value stored under `key`, if it is public and can be deserialized as `T`.
+    ///
+    /// Returns `None` until the values have loaded. Reactive.
+    pub fn get<T>(&self, key: &str) -> Option<T>
+    where
+        T: DeserializeOwned,
+    {
+        let local = self.local.with(|local| local.get(key).cloned());
+        let json = match local {
+            Some(json) => json,
+            None => self
+                .values
+                .get()
+                .and_then(|values| values.get(key).cloned()),
+        }?;
+        serde_json::from_str(&json).ok()
+    }
+
+    /// Updates the client's copy of the value stored under `key`.
+    ///
+    /// This does not change the session on the server. It is useful to reflect a change after a
+    /// server function that updated the session has returned.
+    pub fn set<T>(&self, key: impl Into<String>, value: &T)
+    where
+        T: Serialize,
+    {
+        if let Ok(json) = serde_json::to_string(value) {
+            self.local.update(|local| {
+                local.insert(key.into(), Some(json));
+            });
+        }
+    }
+
+    /// Removes the client's copy of the value stored under `key`.
+    ///
+    /// Like [`set`](Self::set), this does not change the session on the server.
+    pub fn remove(&self, key: impl Into<String>) {
+        self.local.update(|local| {
+            local.insert(key.into(), None);
+        });
+    }
+}
@@ -0,0 +1,350 @@
+//! Typed, two-way channels to [shared workers](https://developer.mozilla.org/en-US/docs/Web/API/SharedWorker),
+//! worklets and anything else that talks through a [`MessagePort`].
+//!
+//! A shared worker is a single worker for every tab of an origin, which makes it the place to
+//! keep one connection or one cache that all the tabs share. [`use_shared_worker`] connects to
+//! one, and returns a [`TypedPort`] that sends and receives typed messages:
+//!
+//! ```rust,no_run
+//! use leptos::{
+//!     prelude::*,
+//!     shared_worker::{use_shared_worker, SharedWorkerOptions},
+//! };
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize)]
+//! enum Request {
+//!     Subscribe(String),
+//! }
+//!
+//! #[derive(Clone, Deserialize)]
+//! enum Update {
+//!     Price(String, f64),
+//! }
+//!
+//! #[component]
+//! pub fn Ticker() -> impl IntoView {
+//!     let port = use_shared_worker::<Request, Update>(
+//!         "/prices-worker.js",
+//!         SharedWorkerOptions::default(),
+//!     );
+//!     Effect::new(move |_| _ = port.send(&Request::Subscribe("ACME".into())));
+//!
+//!     view! {
+//!         <p>
+//!             {move || match port.message() {
+//!                 Some(Update::Price(symbol, price)) => format!("{symbol}: {price}"),
+//!                 None => "Waiting for prices…".to_string(),
+//!             }}
+//!         </p>
+//!     }
+//! }
+//! ```
+//!
+//! The other side can be written in Rust, with [`on_connect`], or in JavaScript. Each message is
+//! an object with a `json` field, which holds the message as JSON, and a `transfer` field, which
+//! holds the [transferable objects](https://developer.mozilla.org/en-US/docs/Web/API/Web_Workers_API/Transferable_objects)
+//! sent with [`TypedPort::send_with_transfer`], like `ArrayBuffer`s, which are moved to the other
+//! side instead of being copied.
+//!
+//! Other ports, like the `port` of an `AudioWorkletNode`, can be wrapped with [`TypedPort::new`].
+//!
+//! Ports are only opened in the browser, and closed when the reactive owner that opened them is
+//! cleaned up. On the server, a port never receives any message.
+
+use leptos_dom::helpers::queue_microtask;
+use reactive_graph::{
+    effect::Effect,
+    owner::{on_cleanup, LocalStorage, StoredValue},
+    signal::RwSignal,
+    traits::{Get, GetValue, Set, SetValue, WithValue},
+};
+use send_wrapper::SendWrapper;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{cell::RefCell, fmt::Debug, marker::PhantomData, rc::Rc};
+use thiserror::Error;
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+pub use web_sys::MessagePort;
+use web_sys::{
+    js_sys::{Array, Object, Reflect},
+    MessageEvent, SharedWorker, SharedWorkerGlobalScope, WorkerOptions,
+    WorkerType,
+};
+
+/// An error while sending a message through a [`TypedPort`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum PortError {
+    /// The port is not open, because it is used on the server, the worker could not be started,
+    /// or the port was closed.
+    #[error("the port is not open")]
+    Closed,
+    /// The message could not be serialized as JSON.
+    #[error("could not serialize the message: {0}")]
+    Serialization(String),
+    /// The message could not be posted, for example because an object in the transfer list was
+    /// already transferred.
+    #[error("could not post the message: {0}")]
+    Post(String),
+}
+
+/// Options that configure [`use_shared_worker`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SharedWorkerOptions {
+    /// The name of the worker. Pages that use the same script with different names get different
+    /// workers.
+    pub name: Option<String>,
+    /// Whether the script is a JavaScript module. Defaults to `false`.
+    pub module: bool,
+}
+
+/// A [`MessagePort`] that sends messages of type `S` and receives messages of type `R`.
+///
+/// Messages are received as soon as the port is open. The latest one can be read reactively with
+/// [`message`](Self::message), and each one is passed to the callbacks added with
+/// [`on_message`](Self::on_message).
+pub struct TypedPort<S, R>
+where
+    R: 'static,
+{
+    message: RwSignal<Option<R>>,
+    inner: StoredValue<Option<Rc<Port>>, LocalStorage>,
+    handlers: StoredValue<Handlers<R>, LocalStorage>,
+    ty: PhantomData<fn(S)>,
+}
+
+type Handlers<R> = Rc<RefCell<Vec<Rc<dyn Fn(&R, &[JsValue])>>>>;
+
+struct Port {
+    port: MessagePort,
+    _on_message: Closure<dyn Fn(MessageEvent)>,
+    // a shared worker stops once no page holds on to it
+    _worker: Option<SharedWorker>,
+}
+
+impl<S, R> Clone for TypedPort<S, R> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S, R> Copy for TypedPort<S, R> {}
+
+impl<S, R> Debug for TypedPort<S, R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TypedPort")
+            .field("message", &self.message)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Connects to the shared worker that runs the script at `url`, starting it if no other page has.
+///
+/// The connection is made in the browser once the current component has been mounted, so that
+/// messages can be sent from effects created after this, and from event handlers. It is closed
+/// when the reactive owner that called this is cleaned up.
+pub fn use_shared_worker<S, R>(
+    url: impl Into<String>,
+    options: SharedWorkerOptions,
+) -> TypedPort<S, R>
+where
+    S: Serialize + 'static,
+    R: DeserializeOwned + Send + Sync + 'static,
+{
+    let url = url.into();
+    let port = TypedPort::closed();
+
+    // effects only run in the browser, so no worker is started on the server
+    Effect::new(move |_| {
+        let worker_options = WorkerOptions::new();
+        if let Some(name) = &options.name {
+            worker_options.set_name(name);
+        }
+        if options.module {
+            worker_options.set_type(WorkerType::Module);
+        }
+        match SharedWorker::new_with_worker_options(&url, &worker_options) {
+            Ok(worker) => port.open(worker.port(), Some(worker)),
+            Err(e) => crate::logging::error!(
+                "Failed to start the shared worker {url}: {e:?}"
+            ),
+        }
+    });
+
+    port
+}
+
+/// Accepts the connections made to the current shared worker with [`use_shared_worker`], calling
+/// `handler` with a port for each one.
+///
+/// This is meant to be called once, when a shared worker written in Rust starts. The ports stay
+/// open as long as the worker runs, unless they are created under a reactive owner that is
+/// cleaned up.
+pub fn on_connect<S, R>(handler: impl Fn(TypedPort<S, R>) + 'static)
+where
+    S: Serialize + 'static,
+    R: DeserializeOwned + Send + Sync + 'static,
+{
+    let Some(scope) = web_sys::js_sys::global()
+        .dyn_into::<SharedWorkerGlobalScope>()
+        .ok()
+    else {
+        crate::logging::error!(
+            "on_connect() must be called in a shared worker"
+        );
+        return;
+    };
+    let on_connect =
+        Closure::<dyn Fn(MessageEvent)>::new(move |ev: MessageEvent| {
+            if let Ok(port) = ev.ports().get(0).dyn_into::<MessagePort>() {
+                handler(TypedPort::new(port));
+            }
+        });
+    scope.set_onconnect(Some(on_connect.as_ref().unchecked_ref()));
+    // the worker accepts connections for as long as it runs
+    on_connect.forget();
+}
+
+impl<S, R> TypedPort<S, R>
+where
+    S: Serialize + 'static,
+    R: DeserializeOwned + Send + Sync + 'static,
+{
+    /// Wraps a port that is already open, like the `port` of an `AudioWorkletNode` or one end of
+    /// a `MessageChannel`.
+    ///
+    /// The port is closed when the reactive owner that wrapped it is cleaned up.
+    pub fn new(port: MessagePort) -> Self {
+        let typed = Self::closed();
+        typed.open(port, None);
+        typed
+    }
+
+    fn closed() -> Self {
+        Self {
+            message: RwSignal::new(None),
+            inner: StoredValue::new_local(None),
+            handlers: StoredValue::new_local(Rc::default()),
+            ty: PhantomData,
+        }
+    }
+
+    fn open(&self, port: MessagePort, worker: Option<SharedWorker>) {
+        let message = self.message;
+        let handlers = self.handlers.get_value();
+        let on_message =
+            Closure::<dyn Fn(MessageEvent)>::new(move |ev: MessageEvent| {
+                let data = ev.data();
+                let Some(json) = Reflect::get(&data, &"json".into())
+                    .ok()
+                    .and_then(|json| json.as_string())
+                else {
+                    return;
+                };
+                let value = match serde_json::from_str::<R>(&json) {
+                    Ok(value) => value,
+                    Err(e) => {
+                        crate::logging::debug_warn!(
+                            "Could not deserialize a message from a port: {e}"
+                        );
+                        return;
+                    }
+                };
+                let transfer = Reflect::get(&data, &"transfer".into())
+                    .ok()
+                    .and_then(|transfer| transfer.dyn_into::<Array>().ok())
+                    .map(|transfer| transfer.to_vec())
+                    .unwrap_or_default();
+                // the callbacks can add other callbacks, so they are not borrowed while they run
+                let callbacks = handlers.borrow().clone();
+                for callback in callbacks {
+                    callback(&value, &transfer);
+                }
+                message.try_set(Some(value));
+            });
+        port.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+        let inner = Rc::new(Port {
+            port,
+            _on_message: on_message,
+            _worker: worker,
+        });
+        self.inner.set_value(Some(Rc::clone(&inner)));
+
+        let cleanup = SendWrapper::new(inner);
+        on_cleanup(move || {
+            let inner = cleanup.take();
+            inner.port.set_onmessage(None);
+            inner.port.close();
+            // the port can be closed from inside a callback, which must not be dropped while it
+            // is running
+            queue_microtask(move || drop(inner));
+        });
+    }
+
+    /// The latest message received, if any. Reactive.
+    pub fn message(&self) -> Option<R>
+    where
+        R: Clone,
+    {
+        self.message.get()
+    }
+
+    /// Calls `callback` with each message received from now on, along with the objects that
+    /// were transferred with it.
+    pub fn on_message(&self, callback: impl Fn(&R, &[JsValue]) + 'static) {
+        if let Some(handlers) = self.handlers.try_get_value() {
+            handlers.borrow_mut().push(Rc::new(callback));
+        }
+    }
+
+    /// Whether the port is open. Not reactive.
+    pub fn is_open(&self) -> bool {
+        self.inner.try_with_value(Option::is_some).unwrap_or(false)
+    }
+
+    /// Sends a message to the other side.
+    pub fn send(&self, message: &S) -> Result<(), PortError> {
+        self.send_with_transfer(message, &[])
+    }
+
+    /// Sends a message to the other side, and moves the objects in `transfer` to it, which can no
+    /// longer be used on this side.
+    pub fn send_with_transfer(
+        &self,
+        message: &S,
+        transfer: &[JsValue],
+    ) -> Result<(), PortError> {
+        let port = self
+            .inner
+            .try_get_value()
+            .flatten()
+            .ok_or(PortError::Closed)?;
+        let json = serde_json::to_string(message)
+            .map_err(|e| PortError::Serialization(e.to_string()))?;
+        let transfer = transfer.iter().collect::<Array>();
+        let envelope = Object::new();
+        _ = Reflect::set(&envelope, &"json".into(), &json.into());
+        _ = Reflect::set(&envelope, &"transfer".into(), &transfer);
+        port.port
+            .post_message_with_transferable(&envelope, &transfer)
+            .map_err(|e| PortError::Post(format!("{e:?}")))
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use super::*;
+    use crate::testing::with_test_owner;
+
+    #[test]
+    fn ports_are_closed_on_the_server() {
+        with_test_owner(|| {
+            let port = use_shared_worker::<String, String>(
+                "/worker.js",
+                SharedWorkerOptions::default(),
+            );
+            assert!(!port.is_open());
+            assert_eq!(port.message(), None);
+            assert_eq!(port.send(&"hello".to_string()), Err(PortError::Closed));
+        });
+    }
+}
@@ -0,0 +1,58 @@
+//! How many times a slot can be passed to a component is determined by the type of the
+//! component's prop for that slot:
+//! - `S`: the slot must be passed exactly once.
+//! - `#[prop(optional)] Option<S>`: the slot may be passed at most once.
+//! - `#[prop(optional)] Vec<S>`: the slot may be passed any number of times.
+//!
+//! Leaving out a required slot, or passing a slot more times than its prop allows, is a
+//! compile-time error.
+
+/// Converts the list of values created when the same slot is passed to a component more than
+/// once into the type of the component's prop for that slot.
+///
+/// This is used internally by the `view!` macro. It is implemented for any prop type that
+/// implements [`FromSlots`].
+pub trait IntoSlots<T> {
+    /// Converts the slot values into the type of the prop.
+    fn into_slots(self) -> T;
+}
+
+impl<S, T> IntoSlots<T> for Vec<S>
+where
+    T: FromSlots<S>,
+{
+    fn into_slots(self) -> T {
+        T::from_slots(self)
+    }
+}
+
+/// A slot prop type that can hold more than one value, so that a slot of type `S` can be
+/// passed to it several times.
+///
+/// This is only implemented for collections, so that passing a single-value slot several
+/// times fails with a clear error.
+#[rustversion::attr(
+    since(1.78),
+    diagnostic::on_unimplemented(
+        message = "the slot prop of type `{Self}` cannot be passed more \
+                   than one `{S}`",
+        label = "this slot is passed more than once, but the component \
+                 accepts at most one",
+        note = "a slot prop of type `S` takes exactly one `<S slot>`, \
+                `Option<S>` takes at most one, and `Vec<S>` takes any \
+                number"
+    )
+)]
+pub trait FromSlots<S> {
+    /// Collects the slot values.
+    fn from_slots(slots: Vec<S>) -> Self;
+}
+
+impl<S, T> FromSlots<S> for Vec<T>
+where
+    T: From<S>,
+{
+    fn from_slots(slots: Vec<S>) -> Self {
+        slots.into_iter().map(T::from).collect()
+    }
+}
@@ -0,0 +1,435 @@
+//! A reactive client for [server-sent events](https://developer.mozilla.org/en-US/docs/Web/API/Server-sent_events).
+//!
+//! [`SseResource`] opens an [`EventSource`] to an endpoint that streams `text/event-stream`
+//! responses, decodes each event as JSON, and exposes the latest one reactively:
+//!
+//! ```rust,no_run
+//! use leptos::{prelude::*, sse::SseResource};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Clone, Serialize, Deserialize)]
+//! pub struct Price {
+//!     pub symbol: String,
+//!     pub cents: u64,
+//! }
+//!
+//! #[component]
+//! pub fn Ticker() -> impl IntoView {
+//!     let prices = SseResource::<Price>::new("/api/prices");
+//!
+//!     view! {
+//!         <p>
+//!             {move || match prices.value() {
+//!                 Some(price) => format!("{}: {}", price.symbol, price.cents as f64 / 100.0),
+//!                 None => "Waiting for prices…".to_string(),
+//!             }}
+//!         </p>
+//!     }
+//! }
+//! ```
+//!
+//! Unlike a WebSocket, the connection only carries messages from the server to the client, over
+//! plain HTTP, so it works through most proxies and needs no special support on the server.
+//!
+//! The connection is only opened in the browser. While rendering on the server, the resource
+//! stays in the [`SseState::Connecting`] state and has no value.
+
+use leptos_dom::helpers::{
+    queue_microtask, set_timeout_with_handle, TimeoutHandle,
+};
+use reactive_graph::{
+    effect::Effect,
+    owner::{on_cleanup, LocalStorage, StoredValue},
+    signal::RwSignal,
+    traits::{Get, GetValue, Set},
+};
+use send_wrapper::SendWrapper;
+use serde::de::DeserializeOwned;
+use server_fn::ServerFn;
+use std::{cell::RefCell, fmt::Debug, rc::Rc, time::Duration};
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::{Event, EventSource, EventSourceInit, MessageEvent};
+
+/// The state of the connection managed by an [`SseResource`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SseState {
+    /// The connection is being opened for the first time.
+    Connecting,
+    /// The connection is open, and events are being received.
+    Open,
+    /// The connection was lost, and a new one will be opened after a delay.
+    Reconnecting {
+        /// The number of reconnection attempts since the connection was last open, including
+        /// the upcoming one.
+        attempt: u32,
+    },
+    /// The connection has been closed and will not be reopened automatically.
+    Closed,
+}
+
+/// Options that configure [`SseResource::new_with_options`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SseOptions {
+    /// The type of the events to listen to, as set by the `event:` field of the stream. Defaults
+    /// to `"message"`, the type of events without that field.
+    pub event: String,
+    /// Whether to send cookies with a request to another origin. Defaults to `false`.
+    pub with_credentials: bool,
+    /// Whether to open a new connection when the current one is lost. Defaults to `true`.
+    pub reconnect: bool,
+    /// How long to wait before the first reconnection attempt. Defaults to one second.
+    ///
+    /// The delay doubles after each failed attempt, up to [`max_reconnect_delay`](Self::max_reconnect_delay).
+    pub reconnect_delay: Duration,
+    /// The longest delay between two reconnection attempts. Defaults to thirty seconds.
+    pub max_reconnect_delay: Duration,
+    /// The number of reconnection attempts after which the resource gives up and moves to
+    /// [`SseState::Closed`]. Defaults to `None`, which retries forever.
+    pub max_reconnect_attempts: Option<u32>,
+}
+
+impl Default for SseOptions {
+    fn default() -> Self {
+        Self {
+            event: "message".to_string(),
+            with_credentials: false,
+            reconnect: true,
+            reconnect_delay: Duration::from_secs(1),
+            max_reconnect_delay: Duration::from_secs(30),
+            max_reconnect_attempts: None,
+        }
+    }
+}
+
+impl SseOptions {
+    fn delay_for(&self, attempt: u32) -> Duration {
+        let factor = 1u32
+            .checked_shl(attempt.saturating_sub(1))
+            .unwrap_or(u32::MAX);
+        self.reconnect_delay
+            .checked_mul(factor)
+            .unwrap_or(self.max_reconnect_delay)
+            .min(self.max_reconnect_delay)
+    }
+}
+
+/// A reactive handle to a stream of server-sent events, each decoded from JSON into a `T`.
+pub struct SseResource<T>
+where
+    T: 'static,
+{
+    state: RwSignal<SseState>,
+    value: RwSignal<Option<T>>,
+    inner: StoredValue<Rc<RefCell<Connection>>, LocalStorage>,
+}
+
+impl<T> Clone for SseResource<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for SseResource<T> {}
+
+impl<T> Debug for SseResource<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SseResource")
+            .field("state", &self.state)
+            .finish_non_exhaustive()
+    }
+}
+
+struct Connection {
+    url: String,
+    options: SseOptions,
+    source: Option<EventSource>,
+    listeners: Option<Listeners>,
+    attempt: u32,
+    closed: bool,
+    timeout: Option<TimeoutHandle>,
+}
+
+/// The callbacks of the current [`EventSource`], which must live as long as it does.
+struct Listeners {
+    _on_open: Closure<dyn Fn()>,
+    on_message: Closure<dyn Fn(MessageEvent)>,
+    _on_error: Closure<dyn Fn(Event)>,
+}
+
+impl<T> SseResource<T>
+where
+    T: DeserializeOwned + Send + Sync + 'static,
+{
+    /// Listens to the events sent by `url`, with the default [`SseOptions`].
+    pub fn new(url: impl Into<String>) -> Self {
+        Self::new_with_options(url, SseOptions::default())
+    }
+
+    /// Listens to the events sent by the server function `F`, whose handler returns a
+    /// `text/event-stream` response.
+    pub fn from_server_fn<F>() -> Self
+    where
+        F: ServerFn,
+    {
+        Self::new(F::url())
+    }
+
+    /// Listens to the events sent by `url`, which can be absolute or relative to the current page.
+    ///
+    /// The connection is opened in the browser once the current component has been mounted. When
+    /// it fails, a new one is opened after [`SseOptions::reconnect_delay`], backing off
+    /// exponentially until a connection succeeds. The connection is closed when the reactive
+    /// owner that created the resource is cleaned up.
+    pub fn new_with_options(
+        url: impl Into<String>,
+        options: SseOptions,
+    ) -> Self {
+        let connection = Rc::new(RefCell::new(Connection {
+            url: url.into(),
+            options,
+            source: None,
+            listeners: None,
+            attempt: 0,
+            closed: false,
+            timeout: None,
+        }));
+        let sse = SseResource {
+            state: RwSignal::new(SseState::Connecting),
+            value: RwSignal::new(None),
+            inner: StoredValue::new_local(Rc::clone(&connection)),
+        };
+
+        // effects only run in the browser, so the connection is never opened on the server
+        Effect::new(move |_| {
+            let cleanup = SendWrapper::new(Rc::clone(&connection));
+            on_cleanup(move || shut_down(&cleanup));
+            connect(sse.state, sse.value, Rc::clone(&connection));
+        });
+
+        sse
+    }
+
+    /// The most recent event, if any. Reactive.
+    pub fn value(&self) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.value.get()
+    }
+
+    /// The current state of the connection. Reactive.
+    pub fn state(&self) -> SseState {
+        self.state.get()
+    }
+
+    /// Whether the connection is currently open. Reactive.
+    pub fn is_open(&self) -> bool {
+        self.state() == SseState::Open
+    }
+
+    /// Closes the connection and stops reconnecting. The latest value is kept.
+    pub fn close(&self) {
+        if let Some(connection) = self.inner.try_get_value() {
+            shut_down(&connection);
+            self.state.set(SseState::Closed);
+        }
+    }
+
+    /// Opens a new connection after [`close`](Self::close), or after the resource gave up
+    /// reconnecting. Does nothing while a connection is open or being opened.
+    pub fn open(&self) {
+        let Some(connection) = self.inner.try_get_value() else {
+            return;
+        };
+        {
+            let mut connection = connection.borrow_mut();
+            if !connection.closed && connection.source.is_some() {
+                return;
+            }
+            if let Some(timeout) = connection.timeout.take() {
+                timeout.clear();
+            }
+            connection.closed = false;
+            connection.attempt = 0;
+        }
+        self.state.set(SseState::Connecting);
+        connect(self.state, self.value, connection);
+    }
+}
+
+impl Connection {
+    fn close_source(&mut self) {
+        if let Some(source) = self.source.take() {
+            source.close();
+            source.set_onopen(None);
+            source.set_onerror(None);
+            if let Some(listeners) = self.listeners.take() {
+                _ = source.remove_event_listener_with_callback(
+                    &self.options.event,
+                    listeners.on_message.as_ref().unchecked_ref(),
+                );
+                // the connection can be closed from inside a listener, which must not be
+                // dropped while it is running
+                queue_microtask(move || drop(listeners));
+            }
+        }
+    }
+}
+
+fn shut_down(connection: &RefCell<Connection>) {
+    let mut connection = connection.borrow_mut();
+    connection.closed = true;
+    if let Some(timeout) = connection.timeout.take() {
+        timeout.clear();
+    }
+    connection.close_source();
+}
+
+fn connect<T>(
+    state: RwSignal<SseState>,
+    value: RwSignal<Option<T>>,
+    connection: Rc<RefCell<Connection>>,
+) where
+    T: DeserializeOwned + Send + Sync + 'static,
+{
+    let (url, event, with_credentials) = {
+        let connection = connection.borrow();
+        (
+            connection.url.clone(),
+            connection.options.event.clone(),
+            connection.options.with_credentials,
+        )
+    };
+    let init = EventSourceInit::new();
+    init.set_with_credentials(with_credentials);
+    let source = match EventSource::new_with_event_source_init_dict(&url, &init)
+    {
+        Ok(source) => source,
+        Err(e) => {
+            crate::logging::error!(
+                "Failed to open event stream from {url}: {e:?}"
+            );
+            schedule_reconnect(state, value, connection);
+            return;
+        }
+    };
+
+    let on_open = Closure::<dyn Fn()>::new({
+        let connection = Rc::downgrade(&connection);
+        move || {
+            if let Some(connection) = connection.upgrade() {
+                connection.borrow_mut().attempt = 0;
+            }
+            state.try_set(SseState::Open);
+        }
+    });
+    source.set_onopen(Some(on_open.as_ref().unchecked_ref()));
+
+    let on_message =
+        Closure::<dyn Fn(MessageEvent)>::new(move |event: MessageEvent| {
+            let Some(data) = event.data().as_string() else {
+                return;
+            };
+            match serde_json::from_str(&data) {
+                Ok(data) => {
+                    value.try_set(Some(data));
+                }
+                Err(e) => crate::logging::error!(
+                    "Failed to deserialize server-sent event: {e}"
+                ),
+            }
+        });
+    _ = source.add_event_listener_with_callback(
+        &event,
+        on_message.as_ref().unchecked_ref(),
+    );
+
+    // the browser retries on its own after some errors, but gives up after others, so the
+    // connection is always replaced to apply the same backoff in both cases
+    let on_error = Closure::<dyn Fn(Event)>::new({
+        let connection = Rc::downgrade(&connection);
+        let source = source.clone();
+        move |_: Event| {
+            let Some(connection) = connection.upgrade() else {
+                return;
+            };
+            {
+                let mut current = connection.borrow_mut();
+                // a newer connection has already replaced this one
+                if current.source.as_ref() != Some(&source) {
+                    return;
+                }
+                current.close_source();
+            }
+            schedule_reconnect(state, value, connection);
+        }
+    });
+    source.set_onerror(Some(on_error.as_ref().unchecked_ref()));
+
+    let mut connection = connection.borrow_mut();
+    connection.source = Some(source);
+    connection.listeners = Some(Listeners {
+        _on_open: on_open,
+        on_message,
+        _on_error: on_error,
+    });
+}
+
+fn schedule_reconnect<T>(
+    state: RwSignal<SseState>,
+    value: RwSignal<Option<T>>,
+    connection: Rc<RefCell<Connection>>,
+) where
+    T: DeserializeOwned + Send + Sync + 'static,
+{
+    let delay = {
+        let mut current = connection.borrow_mut();
+        let exhausted = current
+            .options
+            .max_reconnect_attempts
+            .is_some_and(|max| current.attempt >= max);
+        if current.closed || !current.options.reconnect || exhausted {
+            current.closed = true;
+            drop(current);
+            state.try_set(SseState::Closed);
+            return;
+        }
+        current.attempt += 1;
+        state.try_set(SseState::Reconnecting {
+            attempt: current.attempt,
+        });
+        current.options.delay_for(current.attempt)
+    };
+    let handle = set_timeout_with_handle(
+        {
+            let connection = Rc::clone(&connection);
+            move || {
+                connection.borrow_mut().timeout = None;
+                if !connection.borrow().closed {
+                    connect(state, value, connection);
+                }
+            }
+        },
+        delay,
+    );
+    connection.borrow_mut().timeout = handle.ok();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SseOptions;
+    use std::time::Duration;
+
+    #[test]
+    fn reconnect_delay_is_capped() {
+        let options = SseOptions {
+            reconnect_delay: Duration::from_secs(2),
+            max_reconnect_delay: Duration::from_secs(10),
+            ..Default::default()
+        };
+        let delays = (1..=5)
+            .map(|attempt| options.delay_for(attempt).as_secs())
+            .collect::<Vec<_>>();
+        assert_eq!(delays, [2, 4, 8, 10, 10]);
+    }
+}
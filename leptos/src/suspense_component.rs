@@ -12,7 +12,7 @@ use reactive_graph::{
         ArcMemo, ScopedFuture,
     },
     effect::RenderEffect,
-    owner::{provide_context, use_context, Owner},
+    owner::{shadow_context, use_context, with_context, Owner},
     signal::ArcRwSignal,
     traits::{Dispose, Get, Read, Track, With, WriteValue},
 };
@@ -100,7 +100,8 @@ pub fn Suspense<Chil>(
 where
     Chil: IntoView + Send + 'static,
 {
-    let error_boundary_parent = use_context::<ErrorBoundarySuspendedChildren>();
+    let error_boundary_parent =
+        with_context(ErrorBoundarySuspendedChildren::clone);
 
     let owner = Owner::new();
     owner.with(|| {
@@ -115,7 +116,7 @@ where
         let fallback = fallback.run();
         let children = children.into_inner()();
         let tasks = ArcRwSignal::new(SlotMap::<DefaultKey, ()>::new());
-        provide_context(SuspenseContext {
+        shadow_context(SuspenseContext {
             tasks: tasks.clone(),
         });
         let none_pending = ArcMemo::new(move |prev: Option<&bool>| {
@@ -141,7 +142,7 @@ fn nonce_or_not() -> Option<Arc<str>> {
     #[cfg(feature = "nonce")]
     {
         use crate::nonce::Nonce;
-        use_context::<Nonce>().map(|n| n.0)
+        with_context(|n: &Nonce| n.0.clone())
     }
     #[cfg(not(feature = "nonce"))]
     {
@@ -316,7 +317,7 @@ where
         // now, create listener for local resources
         let (local_tx, mut local_rx) =
             futures::channel::oneshot::channel::<()>();
-        provide_context(LocalResourceNotifier::from(local_tx));
+        shadow_context(LocalResourceNotifier::from(local_tx));
 
         // walk over the tree of children once to make sure that all resource loads are registered
         self.children.dry_resolve();
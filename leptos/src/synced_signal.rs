@@ -0,0 +1,608 @@
+//! Signals whose value is kept in sync between all the tabs of the app that are open in the same
+//! browser.
+//!
+//! A [`SyncedSignal`] works like an [`RwSignal`], but every change to its value is sent to the
+//! other tabs over a [`BroadcastChannel`](https://developer.mozilla.org/en-US/docs/Web/API/BroadcastChannel),
+//! where it updates the synced signals that were created with the same key. This keeps things like
+//! the signed-in user or the color theme consistent when the user switches between tabs:
+//!
+//! ```rust
+//! use leptos::{prelude::*, synced_signal::SyncedSignal};
+//!
+//! #[component]
+//! pub fn ThemeToggle() -> impl IntoView {
+//!     let dark = SyncedSignal::new("dark-mode", false);
+//!
+//!     view! {
+//!         <button on:click=move |_| dark.update(|dark| *dark = !*dark)>
+//!             {move || if dark.get() { "Light mode" } else { "Dark mode" }}
+//!         </button>
+//!     }
+//! }
+//! ```
+//!
+//! Values are sent as JSON. A tab that opens later asks the others for the current value when it
+//! starts. In browsers without `BroadcastChannel`, values are passed through `localStorage`
+//! instead, which also keeps the latest value after every tab has been closed.
+//!
+//! When two tabs change the value at nearly the same time, the [`ConflictPolicy`] in
+//! [`SyncedSignalOptions`] decides which value wins.
+//!
+//! Values are only synced in the browser. On the server, a synced signal is a plain signal that
+//! holds its initial value.
+
+use leptos_dom::helpers::{
+    window, window_event_listener_untyped, WindowListenerHandle,
+};
+use reactive_graph::{
+    effect::Effect,
+    owner::on_cleanup,
+    signal::RwSignal,
+    traits::{
+        DefinedAt, IsDisposed, Notify, ReadUntracked, Set, Track,
+        UntrackableGuard, With, WithUntracked, Write,
+    },
+};
+use send_wrapper::SendWrapper;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    cell::RefCell, fmt::Debug, ops::DerefMut, panic::Location, rc::Rc,
+    sync::Arc,
+};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use web_sys::{
+    js_sys::{Date, Math},
+    BroadcastChannel, MessageEvent, Storage, StorageEvent,
+};
+
+/// Prefixes the name of the channel, and of the `localStorage` item, of every synced signal.
+const PREFIX: &str = "leptos-sync:";
+
+/// Combines a local and a remote value, for [`ConflictPolicy::Merge`].
+type MergeFn<T> = Arc<dyn Fn(&T, &T) -> T + Send + Sync>;
+
+/// Decides what happens when another tab sends a value to a [`SyncedSignal`].
+#[derive(Default)]
+pub enum ConflictPolicy<T> {
+    /// Keeps the value that was changed most recently, by the clock of each tab. This is the
+    /// default.
+    #[default]
+    LastWriteWins,
+    /// Always takes the value from the other tab.
+    AcceptRemote,
+    /// Never takes values from other tabs, but still sends this tab's changes to them.
+    KeepLocal,
+    /// Combines the local value (first argument) and the value from the other tab (second
+    /// argument) into a new value.
+    ///
+    /// If the combined value is different from the one that was received, it is sent back to the
+    /// other tabs, so the function should give the same result when it is applied again, like a
+    /// union of two sets.
+    Merge(MergeFn<T>),
+}
+
+impl<T> ConflictPolicy<T> {
+    /// Creates a [`ConflictPolicy::Merge`] policy from a function.
+    pub fn merge(merge: impl Fn(&T, &T) -> T + Send + Sync + 'static) -> Self {
+        Self::Merge(Arc::new(merge))
+    }
+}
+
+impl<T> Clone for ConflictPolicy<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::LastWriteWins => Self::LastWriteWins,
+            Self::AcceptRemote => Self::AcceptRemote,
+            Self::KeepLocal => Self::KeepLocal,
+            Self::Merge(merge) => Self::Merge(Arc::clone(merge)),
+        }
+    }
+}
+
+impl<T> Debug for ConflictPolicy<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::LastWriteWins => f.write_str("LastWriteWins"),
+            Self::AcceptRemote => f.write_str("AcceptRemote"),
+            Self::KeepLocal => f.write_str("KeepLocal"),
+            Self::Merge(_) => f.write_str("Merge(..)"),
+        }
+    }
+}
+
+/// Options that configure [`SyncedSignal::new_with_options`].
+#[derive(Debug, Clone)]
+pub struct SyncedSignalOptions<T> {
+    /// What to do with values from other tabs. Defaults to [`ConflictPolicy::LastWriteWins`].
+    pub policy: ConflictPolicy<T>,
+    /// Whether to pass values through `localStorage` in browsers without `BroadcastChannel`.
+    /// Defaults to `true`; otherwise, values are not synced in these browsers.
+    pub storage_fallback: bool,
+}
+
+impl<T> Default for SyncedSignalOptions<T> {
+    fn default() -> Self {
+        Self {
+            policy: ConflictPolicy::default(),
+            storage_fallback: true,
+        }
+    }
+}
+
+/// A signal whose value is kept in sync with the synced signals that have the same key in other
+/// tabs.
+///
+/// Reading and writing it works like an [`RwSignal`].
+pub struct SyncedSignal<T>
+where
+    T: 'static,
+{
+    value: RwSignal<T>,
+}
+
+impl<T> Clone for SyncedSignal<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for SyncedSignal<T> {}
+
+impl<T> Debug for SyncedSignal<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SyncedSignal")
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+impl<T> SyncedSignal<T>
+where
+    T: Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+    /// Creates a signal that is synced with the other tabs under `key`, with the default
+    /// [`SyncedSignalOptions`].
+    ///
+    /// The signal starts with `initial`, and takes the current value of the other tabs once it
+    /// has been received.
+    #[track_caller]
+    pub fn new(key: impl Into<String>, initial: T) -> Self {
+        Self::new_with_options(key, initial, SyncedSignalOptions::default())
+    }
+
+    /// Creates a signal that is synced with the other tabs under `key`.
+    ///
+    /// Syncing stops when the reactive owner that created the signal is cleaned up.
+    #[track_caller]
+    pub fn new_with_options(
+        key: impl Into<String>,
+        initial: T,
+        options: SyncedSignalOptions<T>,
+    ) -> Self {
+        let signal = Self {
+            value: RwSignal::new(initial),
+        };
+        let channel = Rc::new(RefCell::new(Channel {
+            name: format!("{PREFIX}{}", key.into()),
+            tab: 0.0,
+            modified: 0.0,
+            last_json: None,
+            transport: Transport::Closed,
+        }));
+        let policy = options.policy;
+        let storage_fallback = options.storage_fallback;
+
+        // effects only run in the browser, so nothing is synced on the server
+        Effect::new({
+            let channel = Rc::clone(&channel);
+            move |_| {
+                open(signal.value, &channel, policy.clone(), storage_fallback);
+                let channel = SendWrapper::new(Rc::clone(&channel));
+                on_cleanup(move || channel.borrow_mut().close());
+            }
+        });
+
+        Effect::new(move |prev: Option<()>| {
+            let Some(json) =
+                signal.value.with(|value| serde_json::to_string(value).ok())
+            else {
+                return;
+            };
+            let mut channel = channel.borrow_mut();
+            if channel.last_json.as_deref() == Some(json.as_str()) {
+                return;
+            }
+            channel.last_json = Some(json);
+            // the initial value is not a change
+            if prev.is_some() {
+                channel.modified = Date::now();
+                channel.send_value();
+            }
+        });
+
+        signal
+    }
+}
+
+impl<T> DefinedAt for SyncedSignal<T>
+where
+    T: Send + Sync,
+{
+    fn defined_at(&self) -> Option<&'static Location<'static>> {
+        self.value.defined_at()
+    }
+}
+
+impl<T> Track for SyncedSignal<T>
+where
+    T: Send + Sync,
+{
+    fn track(&self) {
+        self.value.track();
+    }
+}
+
+impl<T> ReadUntracked for SyncedSignal<T>
+where
+    T: Send + Sync,
+{
+    type Value = <RwSignal<T> as ReadUntracked>::Value;
+
+    fn try_read_untracked(&self) -> Option<Self::Value> {
+        self.value.try_read_untracked()
+    }
+}
+
+impl<T> Notify for SyncedSignal<T>
+where
+    T: Send + Sync,
+{
+    fn notify(&self) {
+        self.value.notify();
+    }
+}
+
+impl<T> Write for SyncedSignal<T>
+where
+    T: Send + Sync,
+{
+    type Value = T;
+
+    fn try_write(&self) -> Option<impl UntrackableGuard<Target = Self::Value>> {
+        self.value.try_write()
+    }
+
+    fn try_write_untracked(
+        &self,
+    ) -> Option<impl DerefMut<Target = Self::Value>> {
+        self.value.try_write_untracked()
+    }
+}
+
+impl<T> IsDisposed for SyncedSignal<T>
+where
+    T: Send + Sync,
+{
+    fn is_disposed(&self) -> bool {
+        self.value.is_disposed()
+    }
+}
+
+enum Transport {
+    Closed,
+    Broadcast(BroadcastChannel, Closure<dyn Fn(MessageEvent)>),
+    Storage(Storage, WindowListenerHandle),
+}
+
+struct Channel {
+    name: String,
+    tab: f64,
+    /// When the current value was set, by the clock of the tab that set it.
+    modified: f64,
+    /// The current value, as it was last sent or received.
+    last_json: Option<String>,
+    transport: Transport,
+}
+
+/// A message between the synced signals of different tabs.
+#[derive(Serialize, Deserialize)]
+enum Message {
+    /// Asks the other tabs for their value.
+    Request { tab: f64 },
+    /// The value of a tab, and when it was set.
+    Value {
+        tab: f64,
+        modified: f64,
+        value: serde_json::Value,
+    },
+}
+
+impl Channel {
+    fn send(&self, message: &Message) {
+        let Ok(json) = serde_json::to_string(message) else {
+            return;
+        };
+        match &self.transport {
+            Transport::Closed => {}
+            Transport::Broadcast(channel, _) => {
+                _ = channel.post_message(&JsValue::from_str(&json));
+            }
+            Transport::Storage(storage, _) => {
+                _ = storage.set_item(&self.name, &json);
+            }
+        }
+    }
+
+    fn send_value(&self) {
+        let Some(value) = self
+            .last_json
+            .as_deref()
+            .and_then(|json| serde_json::from_str(json).ok())
+        else {
+            return;
+        };
+        self.send(&Message::Value {
+            tab: self.tab,
+            modified: self.modified,
+            value,
+        });
+    }
+
+    fn close(&mut self) {
+        match std::mem::replace(&mut self.transport, Transport::Closed) {
+            Transport::Closed => {}
+            Transport::Broadcast(channel, _on_message) => {
+                channel.set_onmessage(None);
+                channel.close();
+            }
+            Transport::Storage(_, listener) => listener.remove(),
+        }
+    }
+}
+
+fn open<T>(
+    value: RwSignal<T>,
+    channel: &Rc<RefCell<Channel>>,
+    policy: ConflictPolicy<T>,
+    storage_fallback: bool,
+) where
+    T: Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+    let receive = {
+        let channel = Rc::downgrade(channel);
+        move |json: &str| {
+            if let (Some(channel), Ok(message)) =
+                (channel.upgrade(), serde_json::from_str(json))
+            {
+                receive(value, &channel, &policy, message);
+            }
+        }
+    };
+
+    let name = {
+        let mut channel = channel.borrow_mut();
+        channel.tab = Math::random();
+        channel.name.clone()
+    };
+    if let Ok(broadcast) = BroadcastChannel::new(&name) {
+        let on_message =
+            Closure::<dyn Fn(MessageEvent)>::new(move |ev: MessageEvent| {
+                if let Some(json) = ev.data().as_string() {
+                    receive(&json);
+                }
+            });
+        broadcast.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+        let mut channel = channel.borrow_mut();
+        channel.transport = Transport::Broadcast(broadcast, on_message);
+        // asks the tabs that are already open for the current value
+        channel.send(&Message::Request { tab: channel.tab });
+        return;
+    }
+
+    if !storage_fallback {
+        return;
+    }
+    let Some(storage) = window().local_storage().ok().flatten() else {
+        return;
+    };
+    let stored = storage.get_item(&name).ok().flatten();
+    let receive = Rc::new(receive);
+    // `storage` events are only fired in the other tabs
+    let listener = window_event_listener_untyped("storage", {
+        let receive = Rc::clone(&receive);
+        move |ev| {
+            let ev = ev.unchecked_into::<StorageEvent>();
+            if ev.key().as_deref() == Some(name.as_str()) {
+                if let Some(json) = ev.new_value() {
+                    receive(&json);
+                }
+            }
+        }
+    });
+    channel.borrow_mut().transport = Transport::Storage(storage, listener);
+    // the value left behind by the last tab that changed it
+    if let Some(json) = stored {
+        receive(&json);
+    }
+}
+
+fn receive<T>(
+    value: RwSignal<T>,
+    channel: &RefCell<Channel>,
+    policy: &ConflictPolicy<T>,
+    message: Message,
+) where
+    T: Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+    let mut current = channel.borrow_mut();
+    let (tab, modified, remote) = match message {
+        Message::Request { tab } => {
+            // the tab that has just opened only needs the value if it has ever changed
+            if tab != current.tab && current.modified > 0.0 {
+                current.send_value();
+            }
+            return;
+        }
+        Message::Value {
+            tab,
+            modified,
+            value,
+        } => (tab, modified, value),
+    };
+    if tab == current.tab {
+        return;
+    }
+    let Ok(remote) = serde_json::from_value::<T>(remote) else {
+        return;
+    };
+    // JSON is compared as this tab serializes it, so that the effect that sends changes to the
+    // other tabs does not mistake a received value for a local change
+    let Ok(remote_json) = serde_json::to_string(&remote) else {
+        return;
+    };
+    let remote_is_newer = (modified, tab) > (current.modified, current.tab);
+    let next = match policy {
+        ConflictPolicy::LastWriteWins if remote_is_newer => remote,
+        ConflictPolicy::LastWriteWins | ConflictPolicy::KeepLocal => return,
+        ConflictPolicy::AcceptRemote => remote,
+        ConflictPolicy::Merge(merge) => {
+            match value.try_with_untracked(|local| merge(local, &remote)) {
+                Some(merged) => merged,
+                None => return,
+            }
+        }
+    };
+    let next_json = serde_json::to_string(&next).ok();
+    current.modified = modified;
+    if next_json == current.last_json {
+        return;
+    }
+    // a merged value that differs from the one received does not match this, so it is sent back
+    current.last_json = Some(remote_json);
+    drop(current);
+    value.set(next);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reactive_graph::{owner::Owner, traits::GetUntracked};
+    use serde_json::json;
+
+    /// This tab's channel, with a value that was set at `modified`.
+    fn channel(value: &impl Serialize, modified: f64) -> RefCell<Channel> {
+        RefCell::new(Channel {
+            name: format!("{PREFIX}test"),
+            tab: 1.0,
+            modified,
+            last_json: serde_json::to_string(value).ok(),
+            transport: Transport::Closed,
+        })
+    }
+
+    fn from_tab(tab: f64, modified: f64, value: serde_json::Value) -> Message {
+        Message::Value {
+            tab,
+            modified,
+            value,
+        }
+    }
+
+    #[test]
+    fn the_last_write_wins_by_default() {
+        let owner = Owner::new();
+        owner.set();
+
+        let value = RwSignal::new(1);
+        let channel = channel(&1, 10.0);
+        let policy = ConflictPolicy::default();
+
+        receive(value, &channel, &policy, from_tab(2.0, 5.0, json!(2)));
+        assert_eq!(value.get_untracked(), 1);
+
+        receive(value, &channel, &policy, from_tab(2.0, 20.0, json!(3)));
+        assert_eq!(value.get_untracked(), 3);
+        assert_eq!(channel.borrow().modified, 20.0);
+        // so that the received value is not sent back as a local change
+        assert_eq!(channel.borrow().last_json.as_deref(), Some("3"));
+    }
+
+    #[test]
+    fn values_sent_by_the_same_tab_are_ignored() {
+        let owner = Owner::new();
+        owner.set();
+
+        let value = RwSignal::new(1);
+        let channel = channel(&1, 10.0);
+
+        receive(
+            value,
+            &channel,
+            &ConflictPolicy::AcceptRemote,
+            from_tab(1.0, 20.0, json!(2)),
+        );
+        assert_eq!(value.get_untracked(), 1);
+    }
+
+    #[test]
+    fn policies_decide_whether_older_values_are_taken() {
+        let owner = Owner::new();
+        owner.set();
+
+        let value = RwSignal::new(1);
+        let channel = channel(&1, 10.0);
+
+        receive(
+            value,
+            &channel,
+            &ConflictPolicy::KeepLocal,
+            from_tab(2.0, 20.0, json!(2)),
+        );
+        assert_eq!(value.get_untracked(), 1);
+
+        receive(
+            value,
+            &channel,
+            &ConflictPolicy::AcceptRemote,
+            from_tab(2.0, 5.0, json!(3)),
+        );
+        assert_eq!(value.get_untracked(), 3);
+    }
+
+    #[test]
+    fn merged_values_that_differ_from_the_remote_one_are_sent_back() {
+        let owner = Owner::new();
+        owner.set();
+
+        let value = RwSignal::new(vec![1, 2]);
+        let channel = channel(&[1, 2], 10.0);
+        let policy = ConflictPolicy::merge(|local: &Vec<i32>, remote| {
+            let mut merged = local.clone();
+            merged.extend(remote.iter().filter(|n| !local.contains(n)));
+            merged.sort();
+            merged
+        });
+
+        receive(value, &channel, &policy, from_tab(2.0, 5.0, json!([2, 3])));
+        assert_eq!(value.get_untracked(), [1, 2, 3]);
+        // the effect that sends changes sees `[1,2,3]`, which differs from this, and sends it
+        assert_eq!(channel.borrow().last_json.as_deref(), Some("[2,3]"));
+    }
+
+    #[test]
+    fn values_that_cannot_be_deserialized_are_ignored() {
+        let owner = Owner::new();
+        owner.set();
+
+        let value = RwSignal::new(1);
+        let channel = channel(&1, 10.0);
+
+        receive(
+            value,
+            &channel,
+            &ConflictPolicy::AcceptRemote,
+            from_tab(2.0, 20.0, json!("not a number")),
+        );
+        assert_eq!(value.get_untracked(), 1);
+    }
+}
@@ -0,0 +1,809 @@
+//! Testing components under `cargo test`, without a browser, or in a browser with the helpers in
+//! [`browser`].
+//!
+//! With the `ssr` feature, [`Screen::render`] renders a view on the server, waiting for any `<Suspense/>` and the
+//! resources it reads, like a server-rendered page would, and parses the result so that it can
+//! be queried by role, text or test id. Resources are loaded by the global [`Executor`], which
+//! the test sets up, like with [`Executor::init_futures_executor`]:
+//!
+//! ```rust
+//! # #[cfg(feature = "ssr")] {
+//! use leptos::{prelude::*, testing::Screen};
+//!
+//! #[component]
+//! fn Greeting(name: Signal<String>) -> impl IntoView {
+//!     view! {
+//!         <h1>"Hello, " {name} "!"</h1>
+//!         <button data-testid="wave">"Wave"</button>
+//!     }
+//! }
+//!
+//! # futures::executor::block_on(async {
+//! let name = RwSignal::new("Ada".to_string());
+//! let mut screen = Screen::render(move || view! { <Greeting name=name.into() /> }).await;
+//! assert_eq!(screen.get_by_role("heading").text(), "Hello, Ada!");
+//! assert_eq!(screen.get_by_test_id("wave").text(), "Wave");
+//!
+//! name.set("Grace".to_string());
+//! screen.rerender().await;
+//! assert!(screen.query_by_text("Hello, Grace!").is_some());
+//! # });
+//! # }
+//! ```
+//!
+//! The listeners added with `on:` are kept by each render, and [`Screen::fire`] runs them like
+//! the browser would for an event fired at an element. The view is created again by each render,
+//! so state that should survive it, like the signals that handlers change, belongs outside of it:
+//!
+//! ```rust
+//! # #[cfg(feature = "ssr")] {
+//! use leptos::{ev, prelude::*, testing::Screen};
+//!
+//! # futures::executor::block_on(async {
+//! let count = RwSignal::new(0);
+//! let mut screen = Screen::render(move || {
+//!     view! { <button on:click=move |_| *count.write() += 1>{count}</button> }
+//! })
+//! .await;
+//! screen.fire(screen.get_by_role("button"), ev::click);
+//! screen.rerender().await;
+//! assert_eq!(screen.get_by_role("button").text(), "1");
+//! # });
+//! # }
+//! ```
+//!
+//! Tests that need the browser's own behavior, like default actions or reading an input's value
+//! from an event, run in a browser and fire events with the helpers in [`browser`].
+//!
+//! For snapshot tests, [`Element::snapshot`] turns a rendered view into HTML that only changes
+//! when the view does, and [`normalize_html`] does the same for HTML rendered some other way, like
+//! the response of a server integration for a route:
+//!
+//! ```rust,ignore
+//! let screen = Screen::render(|| view! { <App/> }).await;
+//! insta::assert_snapshot!(screen.snapshot());
+//! ```
+
+pub mod browser;
+
+#[cfg(feature = "ssr")]
+use crate::IntoView;
+use any_spawner::{time, Executor};
+#[cfg(feature = "ssr")]
+use futures::StreamExt;
+#[cfg(feature = "ssr")]
+use hydration_context::{SharedContext, SsrSharedContext};
+use reactive_graph::owner::Owner;
+#[cfg(feature = "ssr")]
+use std::{
+    cell::RefCell,
+    ops::Deref,
+    panic::{self, AssertUnwindSafe},
+    sync::Arc,
+};
+#[cfg(feature = "ssr")]
+use tachys::{
+    html::event::{
+        recorder::{self, Listener, Recording},
+        EventDescriptor,
+    },
+    view::RenderHtml,
+};
+#[cfg(feature = "ssr")]
+use wasm_bindgen::JsValue;
+
+#[cfg(feature = "ssr")]
+type ViewFn = Arc<dyn Fn() -> crate::prelude::AnyView + Send + Sync>;
+
+#[cfg(feature = "ssr")]
+/// A rendered view that can be queried. Derefs to the root [`Element`], which holds the
+/// top-level nodes of the view.
+pub struct Screen {
+    view: ViewFn,
+    owner: Owner,
+    html: String,
+    root: Element,
+    listeners: RefCell<Vec<Listener>>,
+}
+
+#[cfg(feature = "ssr")]
+impl std::fmt::Debug for Screen {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Screen")
+            .field("html", &self.html)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "ssr")]
+impl Screen {
+    /// Renders the view returned by `view`, once everything it is waiting for has loaded.
+    pub async fn render<F, V>(view: F) -> Self
+    where
+        F: Fn() -> V + Send + Sync + 'static,
+        V: IntoView + 'static,
+    {
+        let view: ViewFn = Arc::new(move || {
+            use crate::prelude::IntoAny;
+            view().into_view().into_any()
+        });
+        let (owner, html, listeners) = render_html(&view).await;
+        Self {
+            root: Element::parse(&html),
+            view,
+            owner,
+            html: strip_listeners(&html),
+            listeners: RefCell::new(listeners),
+        }
+    }
+
+    /// Renders the view again, for example after changing a signal that it reads.
+    pub async fn rerender(&mut self) {
+        let (owner, html, listeners) = render_html(&self.view).await;
+        self.root = Element::parse(&html);
+        self.owner = owner;
+        self.html = strip_listeners(&html);
+        self.listeners = RefCell::new(listeners);
+    }
+
+    /// Fires `event` at `target`, an element of this screen: the listeners added to it with
+    /// `on:` run, followed by those of its ancestors if the event bubbles. The view is not
+    /// rendered again until [`rerender`](Screen::rerender) or [`settle`](Screen::settle).
+    ///
+    /// The listeners receive an event that does not exist outside of a browser, so they can
+    /// change signals, but panic if they call one of its methods, like `prevent_default`. Tests
+    /// for these listeners run in a browser, with the helpers in [`browser`].
+    ///
+    /// # Panics
+    /// Panics if `target` is not an element of this screen, if no listener for the event would
+    /// run, or if a listener panics, naming the event and the element.
+    #[track_caller]
+    pub fn fire<E: EventDescriptor>(&self, target: &Element, event: E) {
+        let path = self.root.path_to(target).unwrap_or_else(|| {
+            panic!("{} is not an element of this screen", target.html())
+        });
+        let name = event.name();
+        let mut listeners = self.listeners.borrow_mut();
+        let ids = path
+            .iter()
+            .rev()
+            .take(if E::BUBBLES { path.len() } else { 1 })
+            .flat_map(|el| el.listeners.iter().copied())
+            .filter(|&id| listeners[id].name() == name)
+            .collect::<Vec<_>>();
+        if ids.is_empty() {
+            panic!("no listener for `{name}` on {}", target.html());
+        }
+        self.owner.with(|| {
+            for id in ids {
+                let call =
+                    AssertUnwindSafe(|| listeners[id].call(JsValue::UNDEFINED));
+                if let Err(e) = panic::catch_unwind(call) {
+                    let message = e
+                        .downcast_ref::<&str>()
+                        .copied()
+                        .or_else(|| {
+                            e.downcast_ref::<String>().map(String::as_str)
+                        })
+                        .unwrap_or("Box<dyn Any>");
+                    // calling into JavaScript is what panics outside of a browser
+                    let hint = if message.contains("non-wasm targets") {
+                        "\n\nThe event passed to listeners by Screen::fire() \
+                         only exists in a browser, so listeners that read it \
+                         need to be tested in one, with \
+                         leptos::testing::browser."
+                    } else {
+                        ""
+                    };
+                    panic!(
+                        "the `{name}` listener of {} panicked: {message}{hint}",
+                        target.html()
+                    );
+                }
+            }
+        });
+    }
+
+    /// Lets spawned tasks run, like an action that was dispatched, and renders the view again,
+    /// until running them no longer changes it.
+    ///
+    /// Timers are not waited for, as they would make the test wait in real time; tests whose
+    /// timers come from `any_spawner::time` can set up [`Executor::init_virtual_time`] and
+    /// advance the clock instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the view is still changing after 100 rounds.
+    pub async fn settle(&mut self) {
+        for _ in 0..100 {
+            Executor::tick().await;
+            let html = std::mem::take(&mut self.html);
+            self.rerender().await;
+            if self.html == html {
+                return;
+            }
+        }
+        panic!("the view did not settle: {}", self.html);
+    }
+
+    /// The rendered HTML.
+    pub fn html(&self) -> &str {
+        &self.html
+    }
+
+    /// The reactive owner of the current render, for providing or reading context.
+    pub fn owner(&self) -> &Owner {
+        &self.owner
+    }
+}
+
+#[cfg(feature = "ssr")]
+impl Deref for Screen {
+    type Target = Element;
+
+    fn deref(&self) -> &Self::Target {
+        &self.root
+    }
+}
+
+#[cfg(feature = "ssr")]
+async fn render_html(view: &ViewFn) -> (Owner, String, Vec<Listener>) {
+    let shared_context = Arc::new(SsrSharedContext::new())
+        as Arc<dyn SharedContext + Send + Sync>;
+    let owner = Owner::new_root(Some(shared_context));
+    let recording = Recording::start();
+    let stream = owner.with(|| view().to_html_stream_in_order());
+    let html = stream.collect::<String>().await;
+    (owner, html, recording.finish())
+}
+
+/// Removes the attributes that mark the elements of recorded listeners.
+#[cfg(feature = "ssr")]
+fn strip_listeners(html: &str) -> String {
+    let marker = format!(" {}=\"", recorder::ATTR);
+    let mut stripped = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(start) = rest.find(&marker) {
+        stripped.push_str(&rest[..start]);
+        let value = &rest[start + marker.len()..];
+        rest = value.find('"').map_or("", |end| &value[end + 1..]);
+    }
+    stripped.push_str(rest);
+    stripped
+}
+
+/// An element of a rendered [`Screen`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Element {
+    name: String,
+    attrs: Vec<(String, String)>,
+    children: Vec<Node>,
+    // indices of the listeners recorded for this element
+    listeners: Vec<usize>,
+}
+
+/// A node of a rendered [`Screen`]. Comments are left out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Node {
+    /// An element.
+    Element(Element),
+    /// Text, with entities decoded.
+    Text(String),
+}
+
+impl Element {
+    /// The tag name, in lowercase. Empty for the root of a [`Screen`].
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The value of an attribute; empty for boolean attributes.
+    pub fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The child nodes.
+    pub fn children(&self) -> &[Node] {
+        &self.children
+    }
+
+    /// The text content, with whitespace collapsed.
+    pub fn text(&self) -> String {
+        let mut text = String::new();
+        self.collect_text(&mut text);
+        text.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    fn collect_text(&self, buf: &mut String) {
+        for child in &self.children {
+            match child {
+                Node::Text(text) => buf.push_str(text),
+                Node::Element(el) => el.collect_text(buf),
+            }
+        }
+    }
+
+    /// The ARIA role: the `role` attribute, or the implicit role of common elements.
+    pub fn role(&self) -> Option<&str> {
+        if let Some(role) = self.attr("role") {
+            return Some(role);
+        }
+        Some(match self.name.as_str() {
+            "button" => "button",
+            "a" if self.attr("href").is_some() => "link",
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => "heading",
+            "input" => match self.attr("type").unwrap_or("text") {
+                "checkbox" => "checkbox",
+                "radio" => "radio",
+                "button" | "submit" | "reset" => "button",
+                "range" => "slider",
+                "number" => "spinbutton",
+                "search" => "searchbox",
+                "hidden" => return None,
+                _ => "textbox",
+            },
+            "textarea" => "textbox",
+            "select" => "combobox",
+            "option" => "option",
+            "ul" | "ol" => "list",
+            "li" => "listitem",
+            "img" => "img",
+            "nav" => "navigation",
+            "main" => "main",
+            "form" => "form",
+            "table" => "table",
+            "tr" => "row",
+            "td" => "cell",
+            "th" => "columnheader",
+            "dialog" => "dialog",
+            _ => return None,
+        })
+    }
+
+    /// All descendant elements, in document order.
+    pub fn descendants(&self) -> Vec<&Element> {
+        let mut all = Vec::new();
+        self.collect_descendants(&mut all);
+        all
+    }
+
+    fn collect_descendants<'a>(&'a self, all: &mut Vec<&'a Element>) {
+        for child in &self.children {
+            if let Node::Element(el) = child {
+                all.push(el);
+                el.collect_descendants(all);
+            }
+        }
+    }
+
+    /// The elements from this one down to `target`, which must be the same element, not an
+    /// equal one.
+    #[cfg(feature = "ssr")]
+    fn path_to<'a>(&'a self, target: &Element) -> Option<Vec<&'a Element>> {
+        if std::ptr::eq(self, target) {
+            return Some(vec![self]);
+        }
+        self.children.iter().find_map(|child| match child {
+            Node::Element(el) => el.path_to(target).map(|mut path| {
+                path.insert(0, self);
+                path
+            }),
+            Node::Text(_) => None,
+        })
+    }
+
+    /// All descendants with this role.
+    pub fn all_by_role(&self, role: &str) -> Vec<&Element> {
+        self.descendants()
+            .into_iter()
+            .filter(|el| el.role() == Some(role))
+            .collect()
+    }
+
+    /// The first descendant with this role.
+    pub fn query_by_role(&self, role: &str) -> Option<&Element> {
+        self.all_by_role(role).into_iter().next()
+    }
+
+    /// The first descendant with this role.
+    ///
+    /// # Panics
+    /// Panics if there is none.
+    #[track_caller]
+    pub fn get_by_role(&self, role: &str) -> &Element {
+        self.query_by_role(role).unwrap_or_else(|| {
+            panic!("no element with role {role:?} in {}", self.html())
+        })
+    }
+
+    /// All descendants whose text is `text`, not counting the elements that contain them.
+    pub fn all_by_text(&self, text: &str) -> Vec<&Element> {
+        self.descendants()
+            .into_iter()
+            .filter(|el| {
+                el.text() == text
+                    && !el
+                        .descendants()
+                        .iter()
+                        .any(|child| child.text() == text)
+            })
+            .collect()
+    }
+
+    /// The first descendant whose text is `text`.
+    pub fn query_by_text(&self, text: &str) -> Option<&Element> {
+        self.all_by_text(text).into_iter().next()
+    }
+
+    /// The first descendant whose text is `text`.
+    ///
+    /// # Panics
+    /// Panics if there is none.
+    #[track_caller]
+    pub fn get_by_text(&self, text: &str) -> &Element {
+        self.query_by_text(text).unwrap_or_else(|| {
+            panic!("no element with text {text:?} in {}", self.html())
+        })
+    }
+
+    /// The descendant whose `data-testid` attribute is `id`.
+    pub fn query_by_test_id(&self, id: &str) -> Option<&Element> {
+        self.descendants()
+            .into_iter()
+            .find(|el| el.attr("data-testid") == Some(id))
+    }
+
+    /// The descendant whose `data-testid` attribute is `id`.
+    ///
+    /// # Panics
+    /// Panics if there is none.
+    #[track_caller]
+    pub fn get_by_test_id(&self, id: &str) -> &Element {
+        self.query_by_test_id(id).unwrap_or_else(|| {
+            panic!("no element with test id {id:?} in {}", self.html())
+        })
+    }
+
+    /// Renders the element back to HTML, without comments, for error messages.
+    pub fn html(&self) -> String {
+        let mut html = String::new();
+        self.write_html(&mut html);
+        html
+    }
+
+    fn write_html(&self, buf: &mut String) {
+        if !self.name.is_empty() {
+            buf.push('<');
+            buf.push_str(&self.name);
+            for (name, value) in &self.attrs {
+                buf.push_str(&format!(" {name}=\"{value}\""));
+            }
+            buf.push('>');
+        }
+        for child in &self.children {
+            match child {
+                Node::Text(text) => buf.push_str(text),
+                Node::Element(el) => el.write_html(buf),
+            }
+        }
+        if !self.name.is_empty() && !is_void(&self.name) {
+            buf.push_str(&format!("</{}>", self.name));
+        }
+    }
+
+    /// Renders the element as normalized HTML, for snapshot tests.
+    ///
+    /// The result is the same from one build to the next: hydration markers, scripts and hydration
+    /// IDs are left out, attributes are sorted by name, whitespace in text is collapsed, and each
+    /// element starts on its own line, indented by its depth.
+    pub fn snapshot(&self) -> String {
+        let mut buf = String::new();
+        self.write_snapshot(0, &mut buf);
+        buf
+    }
+
+    fn write_snapshot(&self, depth: usize, buf: &mut String) {
+        let children = self.snapshot_children();
+        if self.name.is_empty() {
+            for child in children {
+                write_snapshot_child(child, depth, buf);
+            }
+            return;
+        }
+
+        let indent = "  ".repeat(depth);
+        buf.push_str(&indent);
+        buf.push('<');
+        buf.push_str(&self.name);
+        let mut attrs = self
+            .attrs
+            .iter()
+            .filter(|(name, value)| !self.is_hydration_attr(name, value))
+            .collect::<Vec<_>>();
+        attrs.sort();
+        for (name, value) in attrs {
+            buf.push(' ');
+            buf.push_str(name);
+            if !value.is_empty() {
+                buf.push_str(&format!("=\"{}\"", escape(value, true)));
+            }
+        }
+        buf.push('>');
+        if is_void(&self.name) {
+            buf.push('\n');
+            return;
+        }
+        match children.as_slice() {
+            [] => {}
+            [Snapshot::Text(text)] => buf.push_str(&escape(text, false)),
+            _ => {
+                buf.push('\n');
+                for child in children {
+                    write_snapshot_child(child, depth + 1, buf);
+                }
+                buf.push_str(&indent);
+            }
+        }
+        buf.push_str(&format!("</{}>\n", self.name));
+    }
+
+    /// The children, with adjacent text joined and whitespace-only text left out.
+    fn snapshot_children(&self) -> Vec<Snapshot<'_>> {
+        fn flush<'a>(text: &mut String, children: &mut Vec<Snapshot<'a>>) {
+            let collapsed =
+                text.split_whitespace().collect::<Vec<_>>().join(" ");
+            if !collapsed.is_empty() {
+                children.push(Snapshot::Text(collapsed));
+            }
+            text.clear();
+        }
+
+        let mut children = Vec::new();
+        let mut text = String::new();
+        for child in &self.children {
+            match child {
+                Node::Text(t) => text.push_str(t),
+                Node::Element(el) => {
+                    flush(&mut text, &mut children);
+                    children.push(Snapshot::Element(el));
+                }
+            }
+        }
+        flush(&mut text, &mut children);
+        children
+    }
+
+    /// Whether an attribute only exists for hydration, and may change between builds.
+    fn is_hydration_attr(&self, name: &str, value: &str) -> bool {
+        name == "nonce"
+            // out-of-order streaming renders resolved `<Suspense/>` into `<template id="1-f">`
+            || (self.name == "template"
+                && name == "id"
+                && value.strip_suffix('f').is_some_and(|id| {
+                    id.chars().all(|c| c.is_ascii_digit() || c == '-')
+                }))
+    }
+
+    /// Parses HTML into a root element holding its top-level nodes. This only needs to handle
+    /// the HTML rendered by Leptos itself.
+    fn parse(html: &str) -> Element {
+        let mut stack = vec![Element::default()];
+        let mut rest = html;
+        while !rest.is_empty() {
+            if let Some(comment) = rest.strip_prefix("<!") {
+                // comments, hydration markers and doctypes
+                rest = match comment.strip_prefix("--") {
+                    Some(comment) => comment
+                        .find("-->")
+                        .map_or("", |end| &comment[end + 3..]),
+                    None => {
+                        comment.find('>').map_or("", |end| &comment[end + 1..])
+                    }
+                };
+            } else if let Some(close) = rest.strip_prefix("</") {
+                let end = close.find('>').unwrap_or(close.len());
+                let name = close[..end].trim().to_ascii_lowercase();
+                rest = close.get(end + 1..).unwrap_or("");
+                if stack.iter().skip(1).any(|el| el.name == name) {
+                    while let Some(el) = stack.pop() {
+                        let done = el.name == name;
+                        push_child(&mut stack, Node::Element(el));
+                        if done {
+                            break;
+                        }
+                    }
+                }
+            } else if rest.starts_with('<')
+                && rest[1..].starts_with(|c: char| c.is_ascii_alphabetic())
+            {
+                let (el, self_closing, after) = parse_open_tag(&rest[1..]);
+                rest = after;
+                if el.name == "script" || el.name == "style" {
+                    // raw text that isn't shown
+                    let close = format!("</{}>", el.name);
+                    rest = rest
+                        .find(&close)
+                        .map_or("", |end| &rest[end + close.len()..]);
+                } else if self_closing || is_void(&el.name) {
+                    push_child(&mut stack, Node::Element(el));
+                } else {
+                    stack.push(el);
+                }
+            } else {
+                let first = rest.chars().next().map_or(1, char::len_utf8);
+                let end = rest[first..]
+                    .find('<')
+                    .map_or(rest.len(), |end| end + first);
+                push_child(
+                    &mut stack,
+                    Node::Text(decode_entities(&rest[..end])),
+                );
+                rest = &rest[end..];
+            }
+        }
+        while stack.len() > 1 {
+            let el = stack.pop().expect("checked length");
+            push_child(&mut stack, Node::Element(el));
+        }
+        stack.pop().unwrap_or_default()
+    }
+}
+
+enum Snapshot<'a> {
+    Element(&'a Element),
+    Text(String),
+}
+
+fn write_snapshot_child(child: Snapshot<'_>, depth: usize, buf: &mut String) {
+    match child {
+        Snapshot::Element(el) => el.write_snapshot(depth, buf),
+        Snapshot::Text(text) => {
+            buf.push_str(&"  ".repeat(depth));
+            buf.push_str(&escape(&text, false));
+            buf.push('\n');
+        }
+    }
+}
+
+/// Runs `f` in a new reactive [`Owner`], which is cleaned up once it returns, and returns what
+/// `f` returns. For testing hooks outside of a view, like on the server.
+///
+/// The global [`Executor`] is set with [`Executor::init_virtual_time`], so that the effects `f`
+/// creates are queued but don't run, like with the `ssr` feature, even in a build in which
+/// effects are enabled. They run if `f` moves the virtual clock with [`time::advance_time`] or
+/// runs the queued tasks with [`time::run_until_idle`].
+///
+/// # Panics
+/// Panics if another global executor has already been set.
+pub fn with_test_owner<T>(f: impl FnOnce() -> T) -> T {
+    if Executor::init_virtual_time().is_err() && !time::is_virtual() {
+        panic!(
+            "`with_test_owner` needs the virtual-time executor, but another \
+             global executor has already been set"
+        );
+    }
+    let owner = Owner::new();
+    let value = owner.with(f);
+    owner.cleanup();
+    value
+}
+
+/// Normalizes rendered HTML for snapshot tests, like [`Element::snapshot`].
+pub fn normalize_html(html: &str) -> String {
+    Element::parse(html).snapshot()
+}
+
+fn push_child(stack: &mut [Element], node: Node) {
+    if let Some(parent) = stack.last_mut() {
+        parent.children.push(node);
+    }
+}
+
+/// Parses a tag after its `<`, returning the element, whether it closed itself, and the rest of
+/// the input.
+fn parse_open_tag(input: &str) -> (Element, bool, &str) {
+    let name_end = input
+        .find(|c: char| c.is_whitespace() || c == '>' || c == '/')
+        .unwrap_or(input.len());
+    let mut el = Element {
+        name: input[..name_end].to_ascii_lowercase(),
+        ..Default::default()
+    };
+    let mut rest = &input[name_end..];
+    loop {
+        rest = rest.trim_start();
+        if let Some(after) = rest.strip_prefix("/>") {
+            return (el, true, after);
+        } else if let Some(after) = rest.strip_prefix('>') {
+            return (el, false, after);
+        } else if let Some(after) = rest.strip_prefix('/') {
+            rest = after;
+            continue;
+        } else if rest.is_empty() {
+            return (el, false, rest);
+        }
+
+        let name_end = rest
+            .find(|c: char| {
+                c.is_whitespace() || c == '=' || c == '>' || c == '/'
+            })
+            .unwrap_or(rest.len());
+        let name = rest[..name_end].to_ascii_lowercase();
+        rest = rest[name_end..].trim_start();
+        let value = if let Some(after) = rest.strip_prefix('=') {
+            let after = after.trim_start();
+            let (value, after) = match after.chars().next() {
+                Some(quote @ ('"' | '\'')) => {
+                    let value = &after[1..];
+                    let end = value.find(quote).unwrap_or(value.len());
+                    (&value[..end], value.get(end + 1..).unwrap_or(""))
+                }
+                _ => {
+                    let end = after
+                        .find(|c: char| c.is_whitespace() || c == '>')
+                        .unwrap_or(after.len());
+                    (&after[..end], &after[end..])
+                }
+            };
+            rest = after;
+            decode_entities(value)
+        } else {
+            String::new()
+        };
+        #[cfg(feature = "ssr")]
+        if name == recorder::ATTR {
+            if let Ok(id) = value.parse() {
+                el.listeners.push(id);
+                continue;
+            }
+        }
+        el.attrs.push((name, value));
+    }
+}
+
+fn is_void(name: &str) -> bool {
+    matches!(
+        name,
+        "area"
+            | "base"
+            | "br"
+            | "col"
+            | "embed"
+            | "hr"
+            | "img"
+            | "input"
+            | "link"
+            | "meta"
+            | "source"
+            | "track"
+            | "wbr"
+    )
+}
+
+fn escape(text: &str, attr: bool) -> String {
+    let text = text
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;");
+    if attr {
+        text.replace('"', "&quot;")
+    } else {
+        text
+    }
+}
+
+fn decode_entities(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&nbsp;", "\u{a0}")
+        .replace("&amp;", "&")
+}
@@ -0,0 +1,246 @@
+//! Helpers for component tests that run in a browser with `wasm-bindgen-test`.
+//!
+//! [`mount_to_body_for_test`] mounts a view into a fresh container, and unmounts the view of the
+//! previous test and disposes of its reactive owner first, so that tests don't see each other's
+//! DOM or effects. The helpers for dispatching events create events that bubble, like the
+//! browser's own, so that they also reach delegated event listeners:
+//!
+//! ```rust,ignore
+//! use leptos::{prelude::*, testing::browser::*};
+//! use wasm_bindgen_test::*;
+//!
+//! wasm_bindgen_test_configure!(run_in_browser);
+//!
+//! #[wasm_bindgen_test]
+//! async fn increments() {
+//!     let root = mount_to_body_for_test(|| view! { <Counter/> });
+//!     click(&root.get("button"));
+//!     next_animation_frame().await;
+//!     assert_eq!(root.get("span").text_content().unwrap(), "1");
+//! }
+//! ```
+
+use crate::{mount::mount_to, IntoView};
+use std::{any::Any, cell::RefCell};
+use tachys::dom::document;
+use wasm_bindgen::JsCast;
+use web_sys::{
+    Element, Event, EventInit, HtmlElement, HtmlInputElement,
+    HtmlSelectElement, HtmlTextAreaElement, KeyboardEvent, KeyboardEventInit,
+    MouseEvent, MouseEventInit,
+};
+
+thread_local! {
+    static MOUNTED: RefCell<Option<Mounted>> = const { RefCell::new(None) };
+}
+
+struct Mounted {
+    container: HtmlElement,
+    // the `UnmountHandle`
+    handle: Option<Box<dyn Any>>,
+}
+
+impl Drop for Mounted {
+    fn drop(&mut self) {
+        // unmounts the view and cleans up its owner before the container goes
+        drop(self.handle.take());
+        self.container.remove();
+    }
+}
+
+/// Mounts the view returned by `f` into a new container at the end of the `<body>`, after
+/// [cleaning up](cleanup) the view mounted by the previous call.
+///
+/// The view stays mounted until the next call or [`cleanup`], even once the returned
+/// [`TestRoot`] is dropped.
+pub fn mount_to_body_for_test<F, N>(f: F) -> TestRoot
+where
+    F: FnOnce() -> N + 'static,
+    N: IntoView,
+    N::State: 'static,
+{
+    cleanup();
+
+    let container = document()
+        .create_element("div")
+        .expect("could not create an element")
+        .unchecked_into::<HtmlElement>();
+    _ = container.set_attribute("data-leptos-test", "");
+    document()
+        .body()
+        .expect("there is no <body>")
+        .append_child(&container)
+        .expect("could not append the test container to the <body>");
+
+    let handle = mount_to(container.clone(), f);
+    MOUNTED.with(|mounted| {
+        *mounted.borrow_mut() = Some(Mounted {
+            container: container.clone(),
+            handle: Some(Box::new(handle)),
+        })
+    });
+    TestRoot { container }
+}
+
+/// Unmounts the view mounted by [`mount_to_body_for_test`], disposes of its reactive owner, and
+/// removes its container. Does nothing if no view is mounted.
+pub fn cleanup() {
+    // taken out first, so that cleanups that mount again don't find it borrowed
+    let mounted = MOUNTED.with(|mounted| mounted.borrow_mut().take());
+    drop(mounted);
+}
+
+/// The container of a view mounted by [`mount_to_body_for_test`].
+#[derive(Debug, Clone)]
+pub struct TestRoot {
+    container: HtmlElement,
+}
+
+impl TestRoot {
+    /// The element the view is mounted into.
+    pub fn container(&self) -> &HtmlElement {
+        &self.container
+    }
+
+    /// The first element that matches a CSS selector.
+    pub fn query(&self, selector: &str) -> Option<Element> {
+        self.container.query_selector(selector).ok().flatten()
+    }
+
+    /// All elements that match a CSS selector, in document order.
+    pub fn query_all(&self, selector: &str) -> Vec<Element> {
+        let Ok(nodes) = self.container.query_selector_all(selector) else {
+            return Vec::new();
+        };
+        (0..nodes.length())
+            .filter_map(|i| nodes.item(i))
+            .filter_map(|node| node.dyn_into::<Element>().ok())
+            .collect()
+    }
+
+    /// The first element that matches a CSS selector.
+    ///
+    /// # Panics
+    /// Panics if there is none.
+    #[track_caller]
+    pub fn get(&self, selector: &str) -> Element {
+        self.query(selector).unwrap_or_else(|| {
+            panic!(
+                "no element matches {selector:?} in {}",
+                self.container.inner_html()
+            )
+        })
+    }
+
+    /// The HTML of the mounted view.
+    pub fn inner_html(&self) -> String {
+        self.container.inner_html()
+    }
+}
+
+/// Waits for the browser's next animation frame, by which time effects triggered by earlier
+/// changes have run and the DOM has been updated.
+pub async fn next_animation_frame() {
+    let (tx, rx) = futures::channel::oneshot::channel();
+    leptos_dom::helpers::request_animation_frame(move || {
+        _ = tx.send(());
+    });
+    _ = rx.await;
+}
+
+/// Waits until `done` returns `true`, checking it again after each animation frame, for things
+/// the browser reports asynchronously, like observers and messages from other contexts.
+///
+/// Gives up after about a second's worth of frames, and leaves it to the test's assertions to
+/// report what did not happen.
+pub async fn wait_until(done: impl Fn() -> bool) {
+    for _ in 0..60 {
+        if done() {
+            return;
+        }
+        next_animation_frame().await;
+    }
+}
+
+/// Dispatches a bubbling event of type `event` to `target`.
+pub fn dispatch(target: &Element, event: &str) {
+    let init = EventInit::new();
+    init.set_bubbles(true);
+    init.set_composed(true);
+    let event = Event::new_with_event_init_dict(event, &init)
+        .expect("could not create the event");
+    _ = target.dispatch_event(&event);
+}
+
+/// Clicks `target`, with a bubbling `click` event.
+pub fn click(target: &Element) {
+    let init = MouseEventInit::new();
+    init.set_bubbles(true);
+    init.set_cancelable(true);
+    init.set_composed(true);
+    let event = MouseEvent::new_with_mouse_event_init_dict("click", &init)
+        .expect("could not create the event");
+    _ = target.dispatch_event(&event);
+}
+
+/// Sets the value of an `<input>`, `<textarea>` or `<select>`, and dispatches an `input` event,
+/// as if the user had typed it.
+#[track_caller]
+pub fn input(target: &Element, value: &str) {
+    set_value(target, value);
+    dispatch(target, "input");
+}
+
+/// Sets the value of an `<input>`, `<textarea>` or `<select>`, and dispatches an `input` and a
+/// `change` event, as if the user had changed it and moved on.
+#[track_caller]
+pub fn change(target: &Element, value: &str) {
+    set_value(target, value);
+    dispatch(target, "input");
+    dispatch(target, "change");
+}
+
+/// Checks or unchecks a checkbox or radio button, and dispatches an `input` and a `change`
+/// event.
+///
+/// # Panics
+/// Panics if `target` is not an `<input>`.
+#[track_caller]
+pub fn check(target: &Element, checked: bool) {
+    target
+        .dyn_ref::<HtmlInputElement>()
+        .expect("check() requires an <input>")
+        .set_checked(checked);
+    dispatch(target, "input");
+    dispatch(target, "change");
+}
+
+/// Presses a key on `target`, with bubbling `keydown` and `keyup` events. `key` is the value of
+/// [`KeyboardEvent.key`](https://developer.mozilla.org/en-US/docs/Web/API/KeyboardEvent/key),
+/// like `"Enter"` or `"a"`.
+pub fn key_press(target: &Element, key: &str) {
+    for kind in ["keydown", "keyup"] {
+        let init = KeyboardEventInit::new();
+        init.set_bubbles(true);
+        init.set_cancelable(true);
+        init.set_composed(true);
+        init.set_key(key);
+        let event =
+            KeyboardEvent::new_with_keyboard_event_init_dict(kind, &init)
+                .expect("could not create the event");
+        _ = target.dispatch_event(&event);
+    }
+}
+
+#[track_caller]
+fn set_value(target: &Element, value: &str) {
+    if let Some(input) = target.dyn_ref::<HtmlInputElement>() {
+        input.set_value(value);
+    } else if let Some(textarea) = target.dyn_ref::<HtmlTextAreaElement>() {
+        textarea.set_value(value);
+    } else if let Some(select) = target.dyn_ref::<HtmlSelectElement>() {
+        select.set_value(value);
+    } else {
+        panic!("{} has no value to set", target.tag_name());
+    }
+}
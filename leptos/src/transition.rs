@@ -8,7 +8,7 @@ use leptos_macro::component;
 use reactive_graph::{
     computed::{suspense::SuspenseContext, ArcMemo},
     effect::Effect,
-    owner::{provide_context, use_context, Owner},
+    owner::{shadow_context, with_context, Owner},
     signal::ArcRwSignal,
     traits::{Get, Set, Track, With},
     wrappers::write::SignalSetter,
@@ -86,7 +86,8 @@ pub fn Transition<Chil>(
 where
     Chil: IntoView + Send + 'static,
 {
-    let error_boundary_parent = use_context::<ErrorBoundarySuspendedChildren>();
+    let error_boundary_parent =
+        with_context(ErrorBoundarySuspendedChildren::clone);
 
     let owner = Owner::new();
     owner.with(|| {
@@ -101,7 +102,7 @@ where
         let fallback = fallback.run();
         let children = children.into_inner()();
         let tasks = ArcRwSignal::new(SlotMap::<DefaultKey, ()>::new());
-        provide_context(SuspenseContext {
+        shadow_context(SuspenseContext {
             tasks: tasks.clone(),
         });
         let none_pending = ArcMemo::new(move |prev: Option<&bool>| {
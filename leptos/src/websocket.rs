@@ -0,0 +1,458 @@
+//! A reactive client for WebSocket endpoints.
+//!
+//! [`use_websocket`] opens a connection to a WebSocket endpoint, like one mounted with
+//! `leptos_axum::websocket` or `leptos_actix::websocket`, and returns a [`UseWebSocket`] handle.
+//! Messages are sent and received as JSON text frames, so the same types can be shared between
+//! the server handler and the client.
+//!
+//! ```rust,no_run
+//! use leptos::{prelude::*, websocket::use_websocket};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Clone, Serialize, Deserialize)]
+//! pub struct ChatMessage {
+//!     pub author: String,
+//!     pub text: String,
+//! }
+//!
+//! #[component]
+//! pub fn Chat() -> impl IntoView {
+//!     let chat = use_websocket::<ChatMessage, ChatMessage>("/ws/chat");
+//!
+//!     view! {
+//!         <p>"Status: " {move || format!("{:?}", chat.state())}</p>
+//!         <p>{move || chat.message().map(|msg| format!("{}: {}", msg.author, msg.text))}</p>
+//!         <button on:click=move |_| {
+//!             chat.send(&ChatMessage {
+//!                 author: "me".into(),
+//!                 text: "Hello!".into(),
+//!             })
+//!         }>
+//!             "Say hello"
+//!         </button>
+//!     }
+//! }
+//! ```
+//!
+//! The connection is only opened in the browser. While rendering on the server, the handle stays
+//! in the [`ConnectionState::Connecting`] state and messages passed to [`UseWebSocket::send`] are
+//! queued, which keeps the server-rendered HTML consistent with the first render during
+//! hydration.
+
+use leptos_dom::helpers::{set_timeout_with_handle, window, TimeoutHandle};
+use reactive_graph::{
+    effect::Effect,
+    owner::{on_cleanup, LocalStorage, StoredValue},
+    signal::RwSignal,
+    traits::{Get, GetUntracked, GetValue, Set},
+};
+use send_wrapper::SendWrapper;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    cell::RefCell, fmt::Debug, marker::PhantomData, rc::Rc, time::Duration,
+};
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::{CloseEvent, MessageEvent};
+
+/// The state of the connection managed by a [`UseWebSocket`] handle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConnectionState {
+    /// The connection is being opened for the first time.
+    Connecting,
+    /// The connection is open, and messages can be sent and received.
+    Open,
+    /// The connection was lost, and a new one will be opened after a delay.
+    Reconnecting {
+        /// The number of reconnection attempts since the connection was last open,
+        /// including the upcoming one.
+        attempt: u32,
+    },
+    /// The connection has been closed and will not be reopened automatically.
+    Closed,
+}
+
+/// Options that configure [`use_websocket_with_options`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebSocketOptions {
+    /// Whether to open a new connection when the current one is lost. Defaults to `true`.
+    pub reconnect: bool,
+    /// How long to wait before the first reconnection attempt. Defaults to one second.
+    ///
+    /// The delay doubles after each failed attempt, up to [`max_reconnect_delay`](Self::max_reconnect_delay).
+    pub reconnect_delay: Duration,
+    /// The longest delay between two reconnection attempts. Defaults to thirty seconds.
+    pub max_reconnect_delay: Duration,
+    /// The number of reconnection attempts after which the handle gives up and moves to
+    /// [`ConnectionState::Closed`]. Defaults to `None`, which retries forever.
+    pub max_reconnect_attempts: Option<u32>,
+}
+
+impl Default for WebSocketOptions {
+    fn default() -> Self {
+        Self {
+            reconnect: true,
+            reconnect_delay: Duration::from_secs(1),
+            max_reconnect_delay: Duration::from_secs(30),
+            max_reconnect_attempts: None,
+        }
+    }
+}
+
+impl WebSocketOptions {
+    fn delay_for(&self, attempt: u32) -> Duration {
+        let factor = 1u32
+            .checked_shl(attempt.saturating_sub(1))
+            .unwrap_or(u32::MAX);
+        self.reconnect_delay
+            .checked_mul(factor)
+            .unwrap_or(self.max_reconnect_delay)
+            .min(self.max_reconnect_delay)
+    }
+}
+
+/// A reactive handle to a WebSocket connection, created with [`use_websocket`].
+///
+/// `Send` is the type of messages sent to the server, and `Recv` the type of messages received
+/// from it. Both are encoded as JSON.
+pub struct UseWebSocket<Send, Recv>
+where
+    Recv: 'static,
+{
+    state: RwSignal<ConnectionState>,
+    message: RwSignal<Option<Recv>>,
+    inner: StoredValue<Rc<RefCell<Connection>>, LocalStorage>,
+    ty: PhantomData<fn(Send)>,
+}
+
+impl<Send, Recv> Clone for UseWebSocket<Send, Recv> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Send, Recv> Copy for UseWebSocket<Send, Recv> {}
+
+impl<Send, Recv> Debug for UseWebSocket<Send, Recv> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UseWebSocket")
+            .field("state", &self.state)
+            .finish_non_exhaustive()
+    }
+}
+
+struct Connection {
+    url: String,
+    options: WebSocketOptions,
+    socket: Option<web_sys::WebSocket>,
+    queue: Vec<String>,
+    attempt: u32,
+    closed: bool,
+    timeout: Option<TimeoutHandle>,
+}
+
+/// Opens a WebSocket connection to `url` with the default [`WebSocketOptions`].
+///
+/// See [`use_websocket_with_options`].
+pub fn use_websocket<Send, Recv>(
+    url: impl Into<String>,
+) -> UseWebSocket<Send, Recv>
+where
+    Send: Serialize + 'static,
+    Recv: DeserializeOwned + std::marker::Send + Sync + 'static,
+{
+    use_websocket_with_options(url, WebSocketOptions::default())
+}
+
+/// Opens a WebSocket connection to `url` and returns a reactive handle to it.
+///
+/// `url` can be absolute (`wss://example.com/ws`) or relative to the current page (`/ws`), in
+/// which case the scheme is derived from the page's: `https` pages connect with `wss` and `http`
+/// pages with `ws`.
+///
+/// The connection is opened in the browser once the current component has been mounted. When it
+/// is lost, a new one is opened after [`WebSocketOptions::reconnect_delay`], backing off
+/// exponentially until a connection succeeds. The connection is closed when the reactive owner
+/// that created the handle is cleaned up.
+pub fn use_websocket_with_options<Send, Recv>(
+    url: impl Into<String>,
+    options: WebSocketOptions,
+) -> UseWebSocket<Send, Recv>
+where
+    Send: Serialize + 'static,
+    Recv: DeserializeOwned + std::marker::Send + Sync + 'static,
+{
+    let connection = Rc::new(RefCell::new(Connection {
+        url: url.into(),
+        options,
+        socket: None,
+        queue: Vec::new(),
+        attempt: 0,
+        closed: false,
+        timeout: None,
+    }));
+    let ws = UseWebSocket {
+        state: RwSignal::new(ConnectionState::Connecting),
+        message: RwSignal::new(None),
+        inner: StoredValue::new_local(Rc::clone(&connection)),
+        ty: PhantomData,
+    };
+
+    // effects only run in the browser, so the connection is never opened on the server
+    Effect::new(move |_| {
+        let cleanup = SendWrapper::new(Rc::clone(&connection));
+        on_cleanup(move || shut_down(&cleanup));
+        connect(ws.state, ws.message, Rc::clone(&connection));
+    });
+
+    ws
+}
+
+impl<Send, Recv> UseWebSocket<Send, Recv>
+where
+    Send: Serialize + 'static,
+    Recv: DeserializeOwned + std::marker::Send + Sync + 'static,
+{
+    /// The current state of the connection. Reactive.
+    pub fn state(&self) -> ConnectionState {
+        self.state.get()
+    }
+
+    /// Whether the connection is currently open. Reactive.
+    pub fn is_open(&self) -> bool {
+        self.state() == ConnectionState::Open
+    }
+
+    /// The most recent message received from the server, if any. Reactive.
+    pub fn message(&self) -> Option<Recv>
+    where
+        Recv: Clone,
+    {
+        self.message.get()
+    }
+
+    /// Sends a message to the server.
+    ///
+    /// If the connection is not open yet, or is being reestablished, the message is queued and
+    /// sent as soon as a connection opens. Messages passed after [`close`](Self::close) are
+    /// dropped.
+    pub fn send(&self, message: &Send) {
+        let message = match serde_json::to_string(message) {
+            Ok(message) => message,
+            Err(e) => {
+                crate::logging::error!(
+                    "Failed to serialize WebSocket message: {e}"
+                );
+                return;
+            }
+        };
+        let Some(connection) = self.inner.try_get_value() else {
+            return;
+        };
+        let mut connection = connection.borrow_mut();
+        if connection.closed {
+            return;
+        }
+        let sent = self.state.get_untracked() == ConnectionState::Open
+            && connection
+                .socket
+                .as_ref()
+                .is_some_and(|socket| socket.send_with_str(&message).is_ok());
+        if !sent {
+            connection.queue.push(message);
+        }
+    }
+
+    /// Closes the connection and stops reconnecting.
+    pub fn close(&self) {
+        if let Some(connection) = self.inner.try_get_value() {
+            shut_down(&connection);
+            self.state.set(ConnectionState::Closed);
+        }
+    }
+
+    /// Opens a new connection after [`close`](Self::close), or after the handle gave up
+    /// reconnecting. Does nothing while a connection is open or being opened.
+    pub fn open(&self) {
+        let Some(connection) = self.inner.try_get_value() else {
+            return;
+        };
+        {
+            let mut connection = connection.borrow_mut();
+            if !connection.closed && connection.socket.is_some() {
+                return;
+            }
+            if let Some(timeout) = connection.timeout.take() {
+                timeout.clear();
+            }
+            connection.closed = false;
+            connection.attempt = 0;
+        }
+        self.state.set(ConnectionState::Connecting);
+        connect(self.state, self.message, connection);
+    }
+}
+
+fn shut_down(connection: &RefCell<Connection>) {
+    let mut connection = connection.borrow_mut();
+    connection.closed = true;
+    connection.queue.clear();
+    if let Some(timeout) = connection.timeout.take() {
+        timeout.clear();
+    }
+    if let Some(socket) = connection.socket.take() {
+        _ = socket.close();
+    }
+}
+
+fn resolve_url(url: &str) -> Result<String, String> {
+    if url.starts_with("ws://") || url.starts_with("wss://") {
+        return Ok(url.to_owned());
+    }
+    let base = window().location().href().map_err(|e| format!("{e:?}"))?;
+    let url = web_sys::Url::new_with_base(url, &base)
+        .map_err(|e| format!("{e:?}"))?;
+    match url.protocol().as_str() {
+        "https:" => url.set_protocol("wss:"),
+        "http:" => url.set_protocol("ws:"),
+        _ => {}
+    }
+    Ok(url.href())
+}
+
+fn connect<Recv>(
+    state: RwSignal<ConnectionState>,
+    message: RwSignal<Option<Recv>>,
+    connection: Rc<RefCell<Connection>>,
+) where
+    Recv: DeserializeOwned + Send + Sync + 'static,
+{
+    let url = connection.borrow().url.clone();
+    let socket = match resolve_url(&url).and_then(|url| {
+        web_sys::WebSocket::new(&url).map_err(|e| format!("{e:?}"))
+    }) {
+        Ok(socket) => socket,
+        Err(e) => {
+            crate::logging::error!(
+                "Failed to open WebSocket connection to {url}: {e}"
+            );
+            schedule_reconnect(state, message, connection);
+            return;
+        }
+    };
+
+    let onopen = Closure::<dyn Fn()>::new({
+        let connection = Rc::clone(&connection);
+        let socket = socket.clone();
+        move || {
+            let queue = {
+                let mut connection = connection.borrow_mut();
+                connection.attempt = 0;
+                std::mem::take(&mut connection.queue)
+            };
+            for message in queue {
+                _ = socket.send_with_str(&message);
+            }
+            state.try_set(ConnectionState::Open);
+        }
+    });
+    socket.set_onopen(Some(onopen.into_js_value().unchecked_ref()));
+
+    let onmessage =
+        Closure::<dyn Fn(MessageEvent)>::new(move |event: MessageEvent| {
+            let Some(data) = event.data().as_string() else {
+                crate::logging::warn!(
+                    "Ignoring non-text WebSocket message from {url}"
+                );
+                return;
+            };
+            match serde_json::from_str(&data) {
+                Ok(data) => {
+                    message.try_set(Some(data));
+                }
+                Err(e) => crate::logging::error!(
+                    "Failed to deserialize WebSocket message: {e}"
+                ),
+            }
+        });
+    socket.set_onmessage(Some(onmessage.into_js_value().unchecked_ref()));
+
+    let onclose = Closure::<dyn Fn(CloseEvent)>::new({
+        let connection = Rc::clone(&connection);
+        let socket = socket.clone();
+        move |_: CloseEvent| {
+            {
+                let mut current = connection.borrow_mut();
+                // a newer connection has already replaced this one
+                if current.socket.as_ref() != Some(&socket) {
+                    return;
+                }
+                current.socket = None;
+            }
+            schedule_reconnect(state, message, Rc::clone(&connection));
+        }
+    });
+    socket.set_onclose(Some(onclose.into_js_value().unchecked_ref()));
+
+    connection.borrow_mut().socket = Some(socket);
+}
+
+fn schedule_reconnect<Recv>(
+    state: RwSignal<ConnectionState>,
+    message: RwSignal<Option<Recv>>,
+    connection: Rc<RefCell<Connection>>,
+) where
+    Recv: DeserializeOwned + Send + Sync + 'static,
+{
+    let delay = {
+        let mut current = connection.borrow_mut();
+        let exhausted = current
+            .options
+            .max_reconnect_attempts
+            .is_some_and(|max| current.attempt >= max);
+        if current.closed || !current.options.reconnect || exhausted {
+            current.closed = true;
+            current.queue.clear();
+            drop(current);
+            state.try_set(ConnectionState::Closed);
+            return;
+        }
+        current.attempt += 1;
+        state.try_set(ConnectionState::Reconnecting {
+            attempt: current.attempt,
+        });
+        current.options.delay_for(current.attempt)
+    };
+    let handle = set_timeout_with_handle(
+        {
+            let connection = Rc::clone(&connection);
+            move || {
+                connection.borrow_mut().timeout = None;
+                if !connection.borrow().closed {
+                    connect(state, message, connection);
+                }
+            }
+        },
+        delay,
+    );
+    connection.borrow_mut().timeout = handle.ok();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WebSocketOptions;
+    use std::time::Duration;
+
+    #[test]
+    fn reconnect_delay_backs_off_exponentially() {
+        let options = WebSocketOptions {
+            reconnect_delay: Duration::from_millis(500),
+            max_reconnect_delay: Duration::from_secs(5),
+            ..Default::default()
+        };
+        let delays = (1..=6)
+            .map(|attempt| options.delay_for(attempt).as_millis())
+            .collect::<Vec<_>>();
+        assert_eq!(delays, [500, 1000, 2000, 4000, 5000, 5000]);
+        assert_eq!(options.delay_for(u32::MAX), Duration::from_secs(5));
+    }
+}
@@ -0,0 +1,13 @@
+let glue;
+self.onmessage = ({ data }) => {
+	if (data.glue) {
+		glue = import(data.glue).then(async (mod) => {
+			await mod.default({ module_or_path: data.module });
+			return mod;
+		});
+		return;
+	}
+	glue
+		.then((mod) => self.postMessage({ output: mod[data.entry](data.input) }))
+		.catch((e) => self.postMessage({ error: String(e) }));
+};
@@ -0,0 +1,542 @@
+//! Heavy computations that run in a [Web Worker](https://developer.mozilla.org/en-US/docs/Web/API/Web_Workers_API),
+//! off the main thread.
+//!
+//! [`spawn_worker!`](crate::spawn_worker) declares a function that can run in a worker. A
+//! [`WorkerResource`] runs it whenever its input changes, and exposes the result reactively, like
+//! a resource, while the page stays responsive:
+//!
+//! ```rust
+//! use leptos::{prelude::*, spawn_worker, worker::WorkerResource};
+//!
+//! spawn_worker! {
+//!     /// Counts the primes below `limit`, the slow way.
+//!     fn count_primes(limit: u64) -> usize {
+//!         (2..limit)
+//!             .filter(|n| (2..*n).take_while(|d| d * d <= *n).all(|d| n % d != 0))
+//!             .count()
+//!     }
+//! }
+//!
+//! #[component]
+//! pub fn Primes() -> impl IntoView {
+//!     let (limit, set_limit) = signal(1_000_000u64);
+//!     let primes = WorkerResource::new(count_primes, move || limit.get());
+//!
+//!     view! {
+//!         <input
+//!             type="number"
+//!             prop:value=move || limit.get()
+//!             on:change=move |ev| set_limit.set(event_target_value(&ev).parse().unwrap_or(0))
+//!         />
+//!         <p class:stale=move || primes.loading()>
+//!             {move || primes.get().map(|count| format!("{count} primes"))}
+//!         </p>
+//!     }
+//! }
+//! ```
+//!
+//! The worker runs the same WebAssembly binary as the page, which it loads from the JavaScript
+//! module that [`HydrationScripts`](crate::hydration::HydrationScripts) loads; apps that are not
+//! hydrated can set it with [`set_script_url`]. Inputs and outputs are sent to and from the
+//! worker as JSON, so they must implement [`Serialize`] and [`DeserializeOwned`].
+//!
+//! The worker is created from a `blob:` URL, which a `Content-Security-Policy` must allow with
+//! `worker-src blob:`.
+//!
+//! Workers are only started in the browser. While rendering on the server, a [`WorkerResource`]
+//! has no value; [`WorkerFn::run`] runs the function on the current thread instead.
+
+use futures::channel::oneshot;
+use reactive_graph::{
+    effect::Effect,
+    owner::{on_cleanup, StoredValue},
+    signal::RwSignal,
+    traits::{
+        DefinedAt, Get, GetValue, IsDisposed, ReadUntracked, Set, SetValue,
+        Track,
+    },
+};
+use send_wrapper::SendWrapper;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    cell::{Cell, RefCell},
+    fmt::Debug,
+    panic::Location,
+    rc::Rc,
+};
+use thiserror::Error;
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use web_sys::{
+    js_sys::{Array, Object, Reflect},
+    Blob, BlobPropertyBag, MessageEvent, Url, Worker, WorkerOptions,
+    WorkerType,
+};
+
+/// Declares a function that can run in a Web Worker, as a [`WorkerFn`] constant of the same name.
+///
+/// The function takes a single input, and both the input and the output must implement
+/// [`Serialize`](serde::Serialize) and [`DeserializeOwned`](serde::de::DeserializeOwned). It
+/// cannot capture anything, since it runs in another instance of the app.
+///
+/// The function is exported from the app's WebAssembly binary under its own name, which must
+/// not be used by any other export of the app.
+///
+/// ```rust
+/// use leptos::spawn_worker;
+///
+/// spawn_worker! {
+///     pub fn word_count(text: String) -> usize {
+///         text.split_whitespace().count()
+///     }
+/// }
+///
+/// assert_eq!(word_count.run("runs on this thread".into()), 4);
+/// ```
+#[macro_export]
+macro_rules! spawn_worker {
+    (
+        $(#[$meta:meta])*
+        $vis:vis fn $name:ident($input:ident: $in:ty) -> $out:ty $body:block
+    ) => {
+        $(#[$meta])*
+        #[allow(non_upper_case_globals)]
+        $vis const $name: $crate::worker::WorkerFn<$in, $out> = {
+            fn run($input: $in) -> $out $body
+
+            // the worker calls this export of the app, by the name of the function
+            #[$crate::wasm_bindgen::prelude::wasm_bindgen(
+                wasm_bindgen = $crate::wasm_bindgen,
+                js_name = $name
+            )]
+            pub fn entry(
+                input: ::std::string::String,
+            ) -> ::std::result::Result<
+                ::std::string::String,
+                $crate::wasm_bindgen::JsValue,
+            > {
+                $crate::worker::__entry(input, run)
+                    .map_err(::std::convert::Into::into)
+            }
+
+            $crate::worker::WorkerFn::__new(::std::stringify!($name), run)
+        };
+    };
+}
+
+/// A function that can run in a Web Worker, declared with [`spawn_worker!`](crate::spawn_worker).
+pub struct WorkerFn<I, O> {
+    name: &'static str,
+    run: fn(I) -> O,
+}
+
+impl<I, O> Clone for WorkerFn<I, O> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<I, O> Copy for WorkerFn<I, O> {}
+
+impl<I, O> Debug for WorkerFn<I, O> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WorkerFn")
+            .field("name", &self.name)
+            .field("input", &std::any::type_name::<I>())
+            .field("output", &std::any::type_name::<O>())
+            .finish()
+    }
+}
+
+impl<I, O> WorkerFn<I, O> {
+    #[doc(hidden)]
+    pub const fn __new(name: &'static str, run: fn(I) -> O) -> Self {
+        Self { name, run }
+    }
+
+    /// Runs the function on the current thread.
+    pub fn run(&self, input: I) -> O {
+        (self.run)(input)
+    }
+}
+
+impl<I, O> WorkerFn<I, O>
+where
+    I: Serialize,
+    O: DeserializeOwned,
+{
+    /// Runs the function in a new worker, which is stopped once it returns.
+    pub async fn call(&self, input: &I) -> Result<O, WorkerError> {
+        let input = serde_json::to_string(input)
+            .map_err(|e| WorkerError::Serialization(e.to_string()))?;
+        let thread = Thread::spawn()?;
+        let output =
+            thread.run(self.name, input).await.unwrap_or_else(|| {
+                Err(WorkerError::Failed("the worker was stopped".to_string()))
+            })?;
+        serde_json::from_str(&output)
+            .map_err(|e| WorkerError::Serialization(e.to_string()))
+    }
+}
+
+#[doc(hidden)]
+pub fn __entry<I, O>(input: String, run: fn(I) -> O) -> Result<String, String>
+where
+    I: DeserializeOwned,
+    O: Serialize,
+{
+    let input = serde_json::from_str(&input).map_err(|e| e.to_string())?;
+    serde_json::to_string(&run(input)).map_err(|e| e.to_string())
+}
+
+/// An error while running a [`WorkerFn`] in a worker.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum WorkerError {
+    /// The worker could not be started, because the browser does not support module workers or
+    /// the URL of the app's script is unknown.
+    #[error("the worker could not be started: {0}")]
+    Unsupported(String),
+    /// The input or the output could not be sent as JSON.
+    #[error("could not serialize the worker's input or output: {0}")]
+    Serialization(String),
+    /// The function panicked, or the app could not be loaded in the worker.
+    #[error("the worker failed: {0}")]
+    Failed(String),
+}
+
+thread_local! {
+    static SCRIPT_URL: RefCell<Option<String>> = const { RefCell::new(None) };
+    static BOOTSTRAP_URL: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Sets the URL of the JavaScript module generated by `wasm-bindgen` for the app, which workers
+/// load to run the app's WebAssembly binary.
+///
+/// This is only needed when the app is not hydrated with
+/// [`HydrationScripts`](crate::hydration::HydrationScripts), which passes it on from the server.
+pub fn set_script_url(url: impl Into<String>) {
+    SCRIPT_URL.with_borrow_mut(|script_url| *script_url = Some(url.into()));
+}
+
+fn script_url() -> Option<String> {
+    let url = SCRIPT_URL.with_borrow(Clone::clone).or_else(|| {
+        tachys::dom::document()
+            .query_selector("meta[name=leptos-worker-script]")
+            .ok()
+            .flatten()
+            .and_then(|meta| meta.get_attribute("content"))
+    })?;
+    // the worker is created from a `blob:` URL, which relative URLs cannot be resolved against
+    let base = tachys::dom::window().location().href().ok()?;
+    Url::new_with_base(&url, &base).ok().map(|url| url.href())
+}
+
+/// The `blob:` URL of the script that loads the app in a worker, created once per page.
+fn bootstrap_url() -> Result<String, JsValue> {
+    BOOTSTRAP_URL.with_borrow_mut(|bootstrap_url| {
+        if let Some(url) = bootstrap_url {
+            return Ok(url.clone());
+        }
+        let options = BlobPropertyBag::new();
+        options.set_type("text/javascript");
+        let blob = Blob::new_with_str_sequence_and_options(
+            &Array::of1(&JsValue::from_str(include_str!("./worker.js"))),
+            &options,
+        )?;
+        let url = Url::create_object_url_with_blob(&blob)?;
+        *bootstrap_url = Some(url.clone());
+        Ok(url)
+    })
+}
+
+type Reply = oneshot::Sender<Result<String, WorkerError>>;
+
+/// A worker with the app loaded, which runs one function at a time. It is stopped when dropped.
+struct Thread {
+    worker: Worker,
+    pending: Rc<RefCell<Option<Reply>>>,
+    stopped: Cell<bool>,
+    _on_message: Closure<dyn Fn(MessageEvent)>,
+}
+
+impl Thread {
+    fn spawn() -> Result<Self, WorkerError> {
+        let script_url = script_url().ok_or_else(|| {
+            WorkerError::Unsupported(
+                "the URL of the app's script is unknown".to_string(),
+            )
+        })?;
+        let unsupported =
+            |e: JsValue| WorkerError::Unsupported(format!("{e:?}"));
+        let options = WorkerOptions::new();
+        options.set_type(WorkerType::Module);
+        let worker = Worker::new_with_options(
+            &bootstrap_url().map_err(unsupported)?,
+            &options,
+        )
+        .map_err(unsupported)?;
+
+        let pending = Rc::new(RefCell::new(None::<Reply>));
+        let on_message = Closure::<dyn Fn(MessageEvent)>::new({
+            let pending = Rc::clone(&pending);
+            move |ev: MessageEvent| {
+                let Some(reply) = pending.borrow_mut().take() else {
+                    return;
+                };
+                let data = ev.data();
+                let field = |name: &str| {
+                    Reflect::get(&data, &JsValue::from_str(name))
+                        .ok()
+                        .and_then(|value| value.as_string())
+                };
+                _ = reply.send(match field("output") {
+                    Some(output) => Ok(output),
+                    None => Err(WorkerError::Failed(
+                        field("error").unwrap_or_default(),
+                    )),
+                });
+            }
+        });
+        worker.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+
+        let init = Object::new();
+        _ = Reflect::set(&init, &"glue".into(), &script_url.into());
+        _ = Reflect::set(&init, &"module".into(), &wasm_bindgen::module());
+        worker.post_message(&init).map_err(unsupported)?;
+
+        Ok(Self {
+            worker,
+            pending,
+            stopped: Cell::new(false),
+            _on_message: on_message,
+        })
+    }
+
+    fn is_busy(&self) -> bool {
+        self.pending.borrow().is_some()
+    }
+
+    fn is_stopped(&self) -> bool {
+        self.stopped.get()
+    }
+
+    /// Stops the worker, cancelling the function that is running.
+    fn stop(&self) {
+        if !self.stopped.replace(true) {
+            self.worker.set_onmessage(None);
+            self.worker.terminate();
+            self.pending.borrow_mut().take();
+        }
+    }
+
+    /// Runs the function exported as `name` with `input`, or returns `None` if the worker is
+    /// stopped before it returns.
+    async fn run(
+        &self,
+        name: &str,
+        input: String,
+    ) -> Option<Result<String, WorkerError>> {
+        let (tx, rx) = oneshot::channel();
+        *self.pending.borrow_mut() = Some(tx);
+        let message = Object::new();
+        _ = Reflect::set(&message, &"entry".into(), &name.into());
+        _ = Reflect::set(&message, &"input".into(), &input.into());
+        if let Err(e) = self.worker.post_message(&message) {
+            return Some(Err(WorkerError::Failed(format!("{e:?}"))));
+        }
+        rx.await.ok()
+    }
+}
+
+impl Drop for Thread {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// The result of a [`WorkerFn`] that runs in a worker whenever its input changes.
+///
+/// It can be read like a signal of `Option<T>`, which is `None` until the first result arrives.
+/// While a new result is being computed, the previous one is kept.
+pub struct WorkerResource<T>
+where
+    T: 'static,
+{
+    value: RwSignal<Option<T>>,
+    loading: RwSignal<bool>,
+    error: RwSignal<Option<WorkerError>>,
+}
+
+impl<T> Clone for WorkerResource<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for WorkerResource<T> {}
+
+impl<T> Debug for WorkerResource<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WorkerResource")
+            .field("value", &self.value)
+            .field("loading", &self.loading)
+            .field("error", &self.error)
+            .finish()
+    }
+}
+
+impl<T> WorkerResource<T>
+where
+    T: DeserializeOwned + Send + Sync + 'static,
+{
+    /// Runs `worker_fn` in a worker with the value returned by `input`, which is tracked: when it
+    /// changes, the function runs again with the new input.
+    ///
+    /// The worker is started once, and reused for every input. If the input changes while the
+    /// function is still running, that run is cancelled by stopping the worker, and a new one is
+    /// started. The worker is stopped when the reactive owner that created the resource is
+    /// cleaned up.
+    #[track_caller]
+    pub fn new<I>(
+        worker_fn: WorkerFn<I, T>,
+        input: impl Fn() -> I + 'static,
+    ) -> Self
+    where
+        I: Serialize,
+    {
+        let resource = Self {
+            value: RwSignal::new(None),
+            loading: RwSignal::new(false),
+            error: RwSignal::new(None),
+        };
+        let thread = StoredValue::new_local(None::<Rc<Thread>>);
+
+        // effects only run in the browser, so no worker is started on the server
+        Effect::new(move |_| {
+            let input = match serde_json::to_string(&input()) {
+                Ok(input) => input,
+                Err(e) => {
+                    resource
+                        .error
+                        .set(Some(WorkerError::Serialization(e.to_string())));
+                    return;
+                }
+            };
+            let current = match thread.get_value() {
+                Some(current) if !current.is_stopped() => current,
+                _ => match Thread::spawn() {
+                    Ok(spawned) => {
+                        let spawned = Rc::new(spawned);
+                        thread.set_value(Some(Rc::clone(&spawned)));
+                        spawned
+                    }
+                    Err(e) => {
+                        resource.error.set(Some(e));
+                        return;
+                    }
+                },
+            };
+            // a run that is still going on when the input changes, or when the resource is
+            // disposed of, is cancelled by stopping its worker
+            let cleanup = SendWrapper::new(Rc::clone(&current));
+            on_cleanup(move || {
+                if cleanup.is_busy() {
+                    cleanup.stop();
+                }
+            });
+
+            resource.loading.set(true);
+            crate::task::spawn_local(async move {
+                let Some(result) = current.run(worker_fn.name, input).await
+                else {
+                    return;
+                };
+                match result.and_then(|output| {
+                    serde_json::from_str(&output)
+                        .map_err(|e| WorkerError::Serialization(e.to_string()))
+                }) {
+                    Ok(output) => {
+                        resource.value.set(Some(output));
+                        resource.error.set(None);
+                    }
+                    Err(e) => {
+                        // the app may be broken after a panic, so the next run gets a new worker
+                        current.stop();
+                        resource.error.set(Some(e));
+                    }
+                }
+                resource.loading.set(false);
+            });
+        });
+
+        resource
+    }
+
+    /// Whether the function is running. Reactive.
+    pub fn loading(&self) -> bool {
+        self.loading.get()
+    }
+
+    /// The error of the latest run, if it failed. Reactive.
+    pub fn error(&self) -> Option<WorkerError> {
+        self.error.get()
+    }
+}
+
+impl<T> DefinedAt for WorkerResource<T>
+where
+    T: Send + Sync,
+{
+    fn defined_at(&self) -> Option<&'static Location<'static>> {
+        self.value.defined_at()
+    }
+}
+
+impl<T> Track for WorkerResource<T>
+where
+    T: Send + Sync,
+{
+    fn track(&self) {
+        self.value.track();
+    }
+}
+
+impl<T> ReadUntracked for WorkerResource<T>
+where
+    T: Send + Sync,
+{
+    type Value = <RwSignal<Option<T>> as ReadUntracked>::Value;
+
+    fn try_read_untracked(&self) -> Option<Self::Value> {
+        self.value.try_read_untracked()
+    }
+}
+
+impl<T> IsDisposed for WorkerResource<T>
+where
+    T: Send + Sync,
+{
+    fn is_disposed(&self) -> bool {
+        self.value.is_disposed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::__entry;
+
+    spawn_worker! {
+        fn double(n: i32) -> i32 {
+            n * 2
+        }
+    }
+
+    #[test]
+    fn entry_passes_json_through_the_function() {
+        assert_eq!(double.run(21), 42);
+        assert_eq!(
+            __entry("21".to_string(), |n: i32| n * 2),
+            Ok("42".to_string())
+        );
+        assert!(__entry("\"21\"".to_string(), |n: i32| n * 2).is_err());
+    }
+}
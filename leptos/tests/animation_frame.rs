@@ -0,0 +1,82 @@
+#![cfg(all(
+    target_family = "wasm",
+    feature = "csr",
+    feature = "animation-frame",
+    feature = "testing"
+))]
+
+use leptos::{
+    animation_frame::{use_raf, UseRaf},
+    testing::browser::*,
+};
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// Starts a loop that records the delta of each frame.
+fn record_frames() -> (UseRaf, Rc<RefCell<Vec<f64>>>) {
+    let deltas = Rc::new(RefCell::new(Vec::new()));
+    let raf = Rc::new(RefCell::new(None));
+    mount_to_body_for_test({
+        let deltas = Rc::clone(&deltas);
+        let raf = Rc::clone(&raf);
+        move || {
+            *raf.borrow_mut() = Some(use_raf(move |_timestamp, delta| {
+                deltas.borrow_mut().push(delta)
+            }));
+        }
+    });
+    let raf = raf.borrow_mut().take().unwrap();
+    (raf, deltas)
+}
+
+async fn frames(count: usize) {
+    for _ in 0..count {
+        next_animation_frame().await;
+    }
+}
+
+#[wasm_bindgen_test]
+async fn the_callback_runs_on_every_frame_with_the_time_since_the_last_one() {
+    let (raf, deltas) = record_frames();
+    frames(5).await;
+
+    let deltas = deltas.borrow().clone();
+    assert!(deltas.len() >= 3, "only {} frames", deltas.len());
+    assert_eq!(deltas[0], 0.0);
+    assert!(deltas[1..].iter().all(|delta| *delta > 0.0));
+    assert_eq!(raf.elapsed(), deltas.iter().sum::<f64>());
+}
+
+#[wasm_bindgen_test]
+async fn pausing_stops_the_loop_and_resuming_does_not_count_the_pause() {
+    let (raf, deltas) = record_frames();
+    frames(3).await;
+
+    raf.pause();
+    frames(1).await;
+    let paused_at = deltas.borrow().len();
+    let elapsed = raf.elapsed();
+    frames(3).await;
+    assert!(!raf.is_running());
+    assert_eq!(deltas.borrow().len(), paused_at);
+    assert_eq!(raf.elapsed(), elapsed);
+
+    raf.resume();
+    frames(3).await;
+    assert!(raf.is_running());
+    assert!(deltas.borrow().len() > paused_at);
+    assert_eq!(deltas.borrow()[paused_at], 0.0);
+}
+
+#[wasm_bindgen_test]
+async fn the_loop_stops_when_its_owner_is_cleaned_up() {
+    let (_raf, deltas) = record_frames();
+    frames(3).await;
+
+    cleanup();
+    let stopped_at = deltas.borrow().len();
+    frames(3).await;
+    assert_eq!(deltas.borrow().len(), stopped_at);
+}
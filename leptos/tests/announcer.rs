@@ -0,0 +1,67 @@
+#![cfg(all(target_family = "wasm", feature = "announcer"))]
+
+use any_spawner::time::sleep;
+use leptos::{announcer::use_announcer, prelude::*};
+use std::time::Duration;
+use wasm_bindgen_test::*;
+use web_sys::Element;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn region(live: &str) -> Element {
+    document()
+        .query_selector(&format!("[data-leptos-announcer={live}]"))
+        .unwrap()
+        .expect("the live region was not added")
+}
+
+#[wasm_bindgen_test]
+async fn messages_are_added_to_the_live_region_after_it_is_emptied() {
+    let announcer = use_announcer();
+    announcer.polite("Saved");
+
+    let polite = region("polite");
+    assert_eq!(polite.get_attribute("role").as_deref(), Some("status"));
+    assert_eq!(polite.get_attribute("aria-atomic").as_deref(), Some("true"));
+    // the region is emptied first, so that the same message is announced again
+    assert_eq!(polite.text_content().as_deref(), Some(""));
+
+    sleep(Duration::from_millis(200)).await;
+    assert_eq!(polite.text_content().as_deref(), Some("Saved"));
+}
+
+#[wasm_bindgen_test]
+async fn each_politeness_has_its_own_live_region() {
+    let announcer = use_announcer();
+    announcer.polite("Saving…");
+    announcer.assertive("Could not save your changes");
+    sleep(Duration::from_millis(200)).await;
+
+    let assertive = region("assertive");
+    assert_eq!(assertive.get_attribute("role").as_deref(), Some("alert"));
+    assert_eq!(
+        assertive.text_content().as_deref(),
+        Some("Could not save your changes")
+    );
+    assert_eq!(region("polite").text_content().as_deref(), Some("Saving…"));
+    assert_eq!(
+        document()
+            .query_selector_all("[data-leptos-announcer]")
+            .unwrap()
+            .length(),
+        2
+    );
+}
+
+#[wasm_bindgen_test]
+async fn a_message_that_is_still_announced_is_not_posted_again() {
+    let announcer = use_announcer();
+    announcer.polite("Copied");
+    sleep(Duration::from_millis(200)).await;
+
+    let polite = region("polite");
+    assert_eq!(polite.text_content().as_deref(), Some("Copied"));
+    // posting it again does not empty the region
+    announcer.polite("Copied");
+    assert_eq!(polite.text_content().as_deref(), Some("Copied"));
+}
@@ -0,0 +1,64 @@
+#![cfg(feature = "auth")]
+
+use futures::executor::block_on;
+use leptos::{
+    auth::{require_user, require_user_where, use_auth, AuthSource},
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct User {
+    name: String,
+    is_admin: bool,
+}
+
+fn provide_user(user: Option<User>) {
+    provide_context(AuthSource::new(move || {
+        let user = user.clone();
+        async move { user }
+    }));
+}
+
+#[test]
+fn server_fns_are_guarded_by_the_auth_source() {
+    let owner = Owner::new();
+    owner.with(|| {
+        assert!(block_on(require_user::<User>()).is_err());
+
+        provide_user(Some(User {
+            name: "ada".into(),
+            is_admin: false,
+        }));
+        assert_eq!(block_on(require_user::<User>()).unwrap().name, "ada");
+        assert!(
+            block_on(require_user_where::<User>(|user| user.is_admin)).is_err()
+        );
+
+        provide_user(None);
+        assert!(block_on(require_user::<User>()).is_err());
+    });
+}
+
+#[test]
+fn client_copy_of_the_user_can_be_replaced() {
+    _ = any_spawner::Executor::init_futures_executor();
+    let owner = Owner::new();
+    owner.with(|| {
+        let auth = use_auth::<User>();
+        let admin = auth.condition_with(|user: &User| user.is_admin);
+
+        auth.set_user(Some(User {
+            name: "ada".into(),
+            is_admin: true,
+        }));
+        assert_eq!(auth.is_authenticated(), Some(true));
+        assert_eq!(admin(), Some(true));
+        assert_eq!(use_auth::<User>().user().unwrap().name, "ada");
+
+        auth.set_user(None);
+        assert_eq!(auth.user(), None);
+        assert_eq!(auth.condition()(), Some(false));
+        assert_eq!(admin(), Some(false));
+    });
+}
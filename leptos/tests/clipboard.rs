@@ -0,0 +1,32 @@
+#![cfg(all(
+    target_family = "wasm",
+    feature = "clipboard",
+    feature = "testing"
+))]
+
+use leptos::{clipboard::use_clipboard, prelude::*, testing::browser::*};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+use web_sys::HtmlElement;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+async fn copying_marks_copied_only_when_it_succeeds() {
+    let root = mount_to_body_for_test(|| view! { <input id="focused"/> });
+    let input = root.get("#focused").unchecked_into::<HtmlElement>();
+    input.focus().unwrap();
+
+    // whether the test browser lets the page write to the clipboard depends on its permissions,
+    // so either outcome is fine as long as the state matches it
+    let clipboard = use_clipboard();
+    let result = clipboard.copy("copied text").await;
+    assert_eq!(clipboard.copied(), result.is_ok(), "{result:?}");
+
+    // the fallback leaves nothing behind, and gives the focus back
+    assert!(document().query_selector("textarea").unwrap().is_none());
+    assert_eq!(
+        document().active_element().map(|el| el.id()).as_deref(),
+        Some("focused")
+    );
+}
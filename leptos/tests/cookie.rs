@@ -0,0 +1,95 @@
+#![cfg(feature = "cookie")]
+
+use leptos::{
+    cookie::{use_cookie, use_cookie_with_options, CookieOptions},
+    prelude::*,
+    request::{HeaderMap, HeaderValue, RequestParts, ResponseHeaders},
+};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct Prefs {
+    theme: String,
+    font_size: u8,
+}
+
+fn provide_request(cookie: &'static str) -> Arc<Mutex<Vec<String>>> {
+    let mut headers = HeaderMap::new();
+    headers.insert("cookie", HeaderValue::from_static(cookie));
+    provide_context(RequestParts {
+        headers,
+        ..Default::default()
+    });
+
+    let set_cookies = Arc::new(Mutex::new(Vec::new()));
+    provide_context(ResponseHeaders::new({
+        let set_cookies = Arc::clone(&set_cookies);
+        move |name, value| {
+            assert_eq!(name, "set-cookie");
+            set_cookies
+                .lock()
+                .unwrap()
+                .push(value.to_str().unwrap().to_string());
+            true
+        }
+    }));
+    set_cookies
+}
+
+#[test]
+fn cookies_are_read_from_the_request() {
+    let owner = Owner::new();
+    owner.with(|| {
+        provide_request(
+            "prefs=%7B%22theme%22%3A%22dark%22%2C%22font_size%22%3A14%7D; \
+             lang=en; count=oops",
+        );
+
+        let (prefs, _) = use_cookie::<Prefs>("prefs");
+        assert_eq!(
+            prefs.get_untracked(),
+            Some(Prefs {
+                theme: "dark".into(),
+                font_size: 14
+            })
+        );
+        let (lang, _) = use_cookie::<String>("lang");
+        assert_eq!(lang.get_untracked().as_deref(), Some("en"));
+        let (count, _) = use_cookie::<u32>("count");
+        assert_eq!(count.get_untracked(), None);
+        let (missing, _) = use_cookie::<String>("missing");
+        assert_eq!(missing.get_untracked(), None);
+    });
+}
+
+#[test]
+fn cookies_are_written_to_the_response() {
+    let owner = Owner::new();
+    owner.with(|| {
+        let set_cookies = provide_request("count=1");
+
+        let (count, set_count) = use_cookie_with_options::<u32>(
+            "count",
+            CookieOptions {
+                http_only: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(count.get_untracked(), Some(1));
+
+        set_count.set(Some(2));
+        assert_eq!(count.get_untracked(), Some(2));
+        set_count.set(None);
+        assert_eq!(count.get_untracked(), None);
+
+        assert_eq!(
+            *set_cookies.lock().unwrap(),
+            [
+                "count=2; Path=/; HttpOnly; SameSite=Lax",
+                "count=; Path=/; Max-Age=0; Expires=Thu, 01 Jan 1970 \
+                 00:00:00 GMT; HttpOnly; SameSite=Lax",
+            ]
+        );
+    });
+}
@@ -0,0 +1,61 @@
+#![cfg(all(target_family = "wasm", feature = "testing"))]
+
+use leptos::{prelude::*, testing::browser::*};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct SavePayload {
+    id: u32,
+    name: String,
+}
+
+leptos::define_custom_event!(SaveRequested, SavePayload);
+
+#[wasm_bindgen_test]
+fn typed_custom_events_round_trip_their_detail() {
+    let received = RwSignal::new(None);
+    let root = mount_to_body_for_test(move || {
+        view! {
+            <div on:typed:SaveRequested=move |ev| received.set(Some(ev.detail()))>
+                <button/>
+            </div>
+        }
+    });
+
+    let payload = SavePayload {
+        id: 42,
+        name: "draft".into(),
+    };
+    // dispatched on a child, as the event bubbles up to the listener
+    assert!(SaveRequested.dispatch(&root.get("button"), &payload));
+    assert_eq!(received.get_untracked(), Some(Some(payload)));
+}
+
+#[wasm_bindgen_test]
+fn typed_custom_events_without_a_detail_have_no_payload() {
+    let received = RwSignal::new(None);
+    let root = mount_to_body_for_test(move || {
+        view! {
+            <div on:typed:SaveRequested=move |ev| received.set(Some(ev.detail()))/>
+        }
+    });
+
+    dispatch(&root.get("div"), "SaveRequested");
+    assert_eq!(received.get_untracked(), Some(None));
+}
+
+#[wasm_bindgen_test]
+fn cancelling_a_typed_custom_event_is_reported_to_the_dispatcher() {
+    let root = mount_to_body_for_test(move || {
+        view! { <div on:typed:SaveRequested=|ev| ev.prevent_default()/> }
+    });
+
+    let payload = SavePayload {
+        id: 1,
+        name: "draft".into(),
+    };
+    assert!(!SaveRequested.dispatch(&root.get("div"), &payload));
+}
@@ -0,0 +1,77 @@
+use futures::executor::block_on;
+use leptos::{
+    dependency::{use_dependency, Dependencies},
+    prelude::*,
+};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+struct Pool(&'static str);
+
+#[derive(Clone, Debug, PartialEq)]
+struct Connection(usize, Pool);
+
+#[derive(Clone, Debug, PartialEq)]
+struct Cyclic;
+
+fn dependencies(opened: Arc<AtomicUsize>) -> Dependencies {
+    Dependencies::builder()
+        .singleton(Pool("postgres"))
+        .per_request(move || {
+            let pool = use_dependency::<Pool>().unwrap();
+            Connection(opened.fetch_add(1, Ordering::Relaxed), pool)
+        })
+        .per_request(|| use_dependency::<Cyclic>().unwrap_or(Cyclic))
+        .build()
+}
+
+#[test]
+fn per_request_dependencies_are_constructed_once_per_request() {
+    let opened = Arc::new(AtomicUsize::new(0));
+    let dependencies = dependencies(Arc::clone(&opened));
+
+    Owner::new().with(|| {
+        assert!(use_dependency::<Pool>().is_err());
+
+        dependencies.provide();
+        assert_eq!(use_dependency::<Pool>().unwrap(), Pool("postgres"));
+        let conn = use_dependency::<Connection>().unwrap();
+        assert_eq!(conn, Connection(0, Pool("postgres")));
+        // resolved inside a server function
+        assert_eq!(
+            block_on(async { use_dependency::<Connection>() }).unwrap(),
+            conn
+        );
+        assert!(use_dependency::<String>().is_err());
+    });
+
+    Owner::new().with(|| {
+        dependencies.provide();
+        assert_eq!(use_dependency::<Connection>().unwrap().0, 1);
+    });
+    assert_eq!(opened.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn constructors_that_depend_on_themselves_fail_to_resolve() {
+    Owner::new().with(|| {
+        dependencies(Default::default()).provide();
+        // the inner resolution fails, and the constructor falls back
+        assert_eq!(use_dependency::<Cyclic>().unwrap(), Cyclic);
+    });
+}
+
+#[test]
+fn later_registrations_replace_earlier_ones() {
+    let dependencies = Dependencies::builder()
+        .singleton(Pool("postgres"))
+        .singleton(Pool("in-memory"))
+        .build();
+    Owner::new().with(|| {
+        dependencies.provide();
+        assert_eq!(use_dependency::<Pool>().unwrap(), Pool("in-memory"));
+    });
+}
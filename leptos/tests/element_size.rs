@@ -0,0 +1,82 @@
+#![cfg(all(
+    target_family = "wasm",
+    feature = "csr",
+    feature = "observers",
+    feature = "testing"
+))]
+
+use leptos::{
+    element_size::{use_element_size, use_resize_observer, UseElementSize},
+    html::Div,
+    prelude::*,
+    testing::browser::*,
+};
+use std::{cell::Cell, rc::Rc};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+async fn sizes_follow_the_content_box_of_the_element() {
+    let size = Rc::new(Cell::new(None::<UseElementSize>));
+    let width = RwSignal::new("120px");
+    mount_to_body_for_test({
+        let size = Rc::clone(&size);
+        move || {
+            let node_ref = NodeRef::<Div>::new();
+            size.set(Some(use_element_size(node_ref)));
+            view! {
+                <div
+                    node_ref=node_ref
+                    style:width=move || width.get()
+                    style:height="40px"
+                    style:padding="5px"
+                />
+            }
+        }
+    });
+    let size = size.get().unwrap();
+
+    wait_until(|| size.width.get_untracked() > 0.0).await;
+    // the padding is not part of the content box
+    assert_eq!(size.width.get_untracked(), 120.0);
+    assert_eq!(size.height.get_untracked(), 40.0);
+
+    width.set("80px");
+    wait_until(|| size.width.get_untracked() != 120.0).await;
+    assert_eq!(size.width.get_untracked(), 80.0);
+}
+
+#[component]
+fn CountResizes(node_ref: NodeRef<Div>, count: RwSignal<u32>) -> impl IntoView {
+    use_resize_observer(node_ref, move |_| *count.write() += 1);
+}
+
+#[wasm_bindgen_test]
+async fn every_observer_of_an_element_is_called_until_it_is_cleaned_up() {
+    let first = RwSignal::new(0);
+    let second = RwSignal::new(0);
+    let show_second = RwSignal::new(true);
+    let width = RwSignal::new("100px");
+    mount_to_body_for_test(move || {
+        let node_ref = NodeRef::<Div>::new();
+        view! {
+            <div node_ref=node_ref style:width=move || width.get()/>
+            <CountResizes node_ref count=first/>
+            <Show when=move || show_second.get()>
+                <CountResizes node_ref count=second/>
+            </Show>
+        }
+    });
+    let counts = move || (first.get_untracked(), second.get_untracked());
+
+    wait_until(|| counts() == (1, 1)).await;
+    assert_eq!(counts(), (1, 1));
+
+    // the second observer is cleaned up along with the <Show/>
+    show_second.set(false);
+    next_animation_frame().await;
+    width.set("50px");
+    wait_until(|| counts().0 > 1).await;
+    assert_eq!(counts(), (2, 1));
+}
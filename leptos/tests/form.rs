@@ -0,0 +1,514 @@
+use leptos::{
+    form::{
+        value_as, FieldErrors, FormError, FormField, FormModel, FormStore,
+        InputAdapter, Mask, Optional, TypedInput,
+    },
+    prelude::*,
+    server_fn::error::ServerFnErrorErr,
+};
+
+#[derive(FormModel, Clone, Debug, Default, PartialEq)]
+struct NewUser {
+    #[form(validate = not_empty)]
+    #[form(validate = |name: &String| {
+        if name.len() < 3 {
+            Err("Too short.".to_string())
+        } else {
+            Ok(())
+        }
+    })]
+    username: String,
+    age: u32,
+    subscribe: bool,
+}
+
+fn not_empty(value: &impl AsRef<str>) -> Result<(), String> {
+    if value.as_ref().is_empty() {
+        Err("Required.".into())
+    } else {
+        Ok(())
+    }
+}
+
+#[test]
+fn fields_are_validated() {
+    let owner = Owner::new();
+    owner.set();
+
+    let form = FormStore::new(NewUser::default());
+    let NewUserFields { username, age, .. } = form.fields();
+    assert_eq!(username.name(), "username");
+    assert_eq!(username.error(), None);
+    assert!(!username.is_touched());
+
+    assert!(!form.validate());
+    assert_eq!(username.error().as_deref(), Some("Required."));
+    assert_eq!(age.error(), None);
+    assert!(!form.is_valid());
+
+    // touched fields are validated again when they change
+    username.set("al".to_string());
+    assert_eq!(username.error().as_deref(), Some("Too short."));
+    username.set("alice".to_string());
+    assert_eq!(username.error(), None);
+    age.set(30);
+    assert!(form.is_valid());
+
+    assert_eq!(
+        form.get(),
+        NewUser {
+            username: "alice".to_string(),
+            age: 30,
+            subscribe: false,
+        }
+    );
+}
+
+#[test]
+fn model_is_validated_without_a_form() {
+    assert_eq!(
+        NewUser::default().validate(),
+        Err(FieldErrors::new().with("username", "Required."))
+    );
+    let user = NewUser {
+        username: "alice".to_string(),
+        ..Default::default()
+    };
+    assert_eq!(user.validate(), Ok(()));
+}
+
+#[test]
+fn server_errors_are_mapped_to_fields() {
+    let owner = Owner::new();
+    owner.set();
+
+    let form = FormStore::new(NewUser::default());
+    let NewUserFields { username, age, .. } = form.fields();
+
+    form.set_result_error(&FormError::from(
+        FieldErrors::new()
+            .with("username", "This username is taken.")
+            .with("team", "Unknown team."),
+    ));
+    assert_eq!(username.error().as_deref(), Some("This username is taken."));
+    assert_eq!(age.error(), None);
+    assert_eq!(form.error().as_deref(), Some("team: Unknown team."));
+
+    form.set_result_error(&FormError::ServerFn(ServerFnErrorErr::Request(
+        "offline".to_string(),
+    )));
+    assert!(form.error().unwrap().contains("offline"));
+    // field errors are kept until they are replaced
+    assert!(username.error().is_some());
+
+    form.clear_errors();
+    assert_eq!(username.error(), None);
+    assert_eq!(form.error(), None);
+}
+
+#[test]
+fn async_validators_run_after_fields_are_valid() {
+    let owner = Owner::new();
+    owner.set();
+
+    let form = FormStore::new(NewUser::default()).async_validator(
+        |user: NewUser| async move {
+            if user.username == "admin" {
+                Err(FieldErrors::new().with("username", "Taken."))
+            } else {
+                Ok(())
+            }
+        },
+    );
+    let username = form.fields().username;
+
+    assert!(!futures::executor::block_on(form.validate_async()));
+    assert_eq!(username.error().as_deref(), Some("Required."));
+
+    username.set("admin".to_string());
+    assert!(!futures::executor::block_on(form.validate_async()));
+    assert_eq!(username.error().as_deref(), Some("Taken."));
+
+    username.set("alice".to_string());
+    assert!(futures::executor::block_on(form.validate_async()));
+    assert_eq!(username.error(), None);
+}
+
+#[derive(FormModel, Clone, Default)]
+struct Account {
+    #[form(validate = not_empty)]
+    #[form(validate_async = username_available, debounce_ms = 500)]
+    username: String,
+}
+
+async fn username_available(username: String) -> Result<(), String> {
+    if username == "admin" {
+        Err("Taken.".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+#[test]
+fn fields_are_validated_asynchronously() {
+    let owner = Owner::new();
+    owner.set();
+
+    let form = FormStore::new(Account::default());
+    let username = form.fields().username;
+
+    // the asynchronous validators only run once the others pass
+    assert!(!futures::executor::block_on(form.validate_async()));
+    assert_eq!(username.error().as_deref(), Some("Required."));
+
+    username.set("admin".to_string());
+    assert!(!futures::executor::block_on(form.validate_async()));
+    assert_eq!(username.error().as_deref(), Some("Taken."));
+    assert!(!form.is_validating());
+
+    username.set("alice".to_string());
+    assert!(futures::executor::block_on(username.validate_async()));
+    assert_eq!(username.error(), None);
+}
+
+#[test]
+fn stale_async_validation_is_discarded() {
+    use futures::{channel::oneshot, pin_mut, poll};
+    use std::sync::{Arc, Mutex};
+
+    let owner = Owner::new();
+    owner.set();
+
+    // the first check is held until `respond` is sent
+    let (respond, held) = oneshot::channel::<()>();
+    let held = Arc::new(Mutex::new(Some(held)));
+    let username = FormField::new("username", "admin".to_string())
+        .async_validator(move |username: String| {
+            let held = held.lock().unwrap().take();
+            async move {
+                if let Some(held) = held {
+                    _ = held.await;
+                }
+                username_available(username).await
+            }
+        });
+
+    futures::executor::block_on(async {
+        let first = username.validate_async();
+        pin_mut!(first);
+        assert!(poll!(&mut first).is_pending());
+        assert!(username.is_validating());
+
+        username.set("alice".to_string());
+        assert!(username.validate_async().await);
+        assert!(!username.is_validating());
+
+        // the result for "admin" arrives after the one for "alice"
+        respond.send(()).unwrap();
+        assert!(!first.await);
+        assert_eq!(username.error(), None);
+    });
+}
+
+#[test]
+fn changes_are_tracked_and_reset() {
+    let owner = Owner::new();
+    owner.set();
+
+    let form = FormStore::new(NewUser {
+        username: "alice".to_string(),
+        ..Default::default()
+    });
+    let NewUserFields { username, age, .. } = form.fields();
+    assert!(!form.is_dirty());
+
+    username.set("bob".to_string());
+    age.set(30);
+    assert!(username.is_dirty());
+    assert_eq!(form.dirty_fields(), ["username", "age"]);
+
+    // changing a field back to its initial value makes it clean again
+    age.set(0);
+    assert_eq!(form.dirty_fields(), ["username"]);
+
+    username.validate();
+    assert!(form.reset_field("username"));
+    assert!(!form.reset_field("team"));
+    assert_eq!(username.get(), "alice");
+    assert!(!username.is_touched());
+    assert!(!form.is_dirty());
+
+    username.set("bob".to_string());
+    form.mark_clean();
+    assert!(!form.is_dirty());
+    assert_eq!(username.initial(), "bob");
+
+    username.set(String::new());
+    form.validate();
+    form.reset();
+    assert_eq!(username.get(), "bob");
+    assert_eq!(username.error(), None);
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn fields_render_as_inputs() {
+    let owner = Owner::new();
+    owner.set();
+
+    let form = FormStore::new(NewUser {
+        username: "alice".to_string(),
+        age: 30,
+        subscribe: true,
+    });
+    let NewUserFields {
+        username,
+        age,
+        subscribe,
+    } = form.fields();
+    age.set_error(Some("Too young.".to_string()));
+
+    let rendered = view! {
+        <input {..username.attrs()}/>
+        <input type="number" {..age.attrs()}/>
+        <input type="checkbox" {..subscribe.checkbox_attrs()}/>
+    };
+
+    assert_eq!(
+        rendered.to_html(),
+        "<input name=\"username\" value=\"alice\"><input type=\"number\" \
+         name=\"age\" value=\"30\" aria-invalid=\"true\"><input \
+         type=\"checkbox\" name=\"subscribe\" checked>"
+    );
+}
+
+#[cfg(feature = "ssr")]
+#[server(endpoint = "rename")]
+async fn rename(name: String) -> Result<(), ServerFnError> {
+    _ = name;
+    Ok(())
+}
+
+#[cfg(all(feature = "ssr", feature = "multipart"))]
+#[server(input = server_fn::codec::MultipartFormData, endpoint = "upload")]
+async fn upload(
+    data: server_fn::codec::MultipartData,
+) -> Result<(), ServerFnError> {
+    _ = data;
+    Ok(())
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn action_form_is_url_encoded() {
+    let owner = Owner::new();
+    owner.set();
+
+    let action = ServerAction::<Rename>::new();
+    let rendered = view! {
+        <ActionForm action>
+            <input name="name"/>
+        </ActionForm>
+    };
+
+    assert_eq!(
+        rendered.to_html(),
+        "<form action=\"/api/rename\" method=\"post\"><input \
+         name=\"name\"></form>"
+    );
+}
+
+#[cfg(all(feature = "ssr", feature = "multipart"))]
+#[test]
+fn action_form_uploads_files() {
+    let owner = Owner::new();
+    owner.set();
+
+    let action = ServerAction::<Upload>::new();
+    let rendered = view! {
+        <ActionForm action>
+            <input type="file" name="file"/>
+        </ActionForm>
+    };
+
+    assert_eq!(
+        rendered.to_html(),
+        "<form action=\"/api/upload\" method=\"post\" \
+         enctype=\"multipart/form-data\"><input type=\"file\" \
+         name=\"file\"></form>"
+    );
+}
+
+#[cfg(feature = "ssr")]
+#[server(prefix = "/forms", endpoint = "archive")]
+async fn archive(id: u32) -> Result<(), ServerFnError> {
+    _ = id;
+    Ok(())
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn action_forms_post_to_the_mounted_path() {
+    let owner = Owner::new();
+    owner.set();
+
+    // a prefix of its own, as the mounts are shared by every test
+    server_fn::prefix::mount("/forms", "/v2/forms");
+    let action = ServerAction::<Archive>::new();
+    let multi_action = ServerMultiAction::<Archive>::new();
+    let rendered = view! {
+        <ActionForm action>
+            <input name="id"/>
+        </ActionForm>
+        <MultiActionForm action=multi_action>
+            <input name="id"/>
+        </MultiActionForm>
+    }
+    .to_html();
+
+    assert_eq!(rendered.matches("action=\"/v2/forms/archive\"").count(), 2);
+    assert!(!rendered.contains("action=\"/forms/archive\""));
+}
+
+#[cfg(feature = "ssr")]
+#[server(endpoint = "register")]
+async fn register(username: String) -> Result<(), FormError> {
+    _ = username;
+    Ok(())
+}
+
+#[cfg(feature = "ssr")]
+#[component]
+fn RegisterState() -> impl IntoView {
+    let form = use_action_form::<Register>().unwrap();
+    view! {
+        <p>{move || form.field_error("username")}</p>
+        <p>{move || form.form_error()}</p>
+        <p>{move || form.pending_input().map(|input| input.username)}</p>
+    }
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn action_form_exposes_its_state() {
+    let owner = Owner::new();
+    owner.set();
+
+    let action = ServerAction::<Register>::new();
+    let rendered = move || {
+        view! {
+            <ActionForm action>
+                <RegisterState/>
+            </ActionForm>
+        }
+        .to_html()
+    };
+    assert!(rendered().contains("<p><!></p><p><!></p><p><!></p>"));
+    assert!(use_action_form::<Register>().is_none());
+
+    action.value().set(Some(Err(FieldErrors::new()
+        .with("username", "This username is taken.")
+        .into())));
+    assert!(rendered()
+        .contains("<p>This username is taken.</p><p><!></p><p><!></p>"));
+
+    action.value().set(Some(Err(FormError::ServerFn(
+        ServerFnErrorErr::Request("offline".to_string()),
+    ))));
+    assert!(rendered().contains(
+        "<p><!></p><p>error reaching server to call server function: \
+         offline</p><p><!></p>"
+    ));
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn groups_render_checked_states() {
+    let owner = Owner::new();
+    owner.set();
+
+    let toppings = CheckboxGroup::new("toppings", RwSignal::new(vec![3, 1]));
+    let size = RadioGroup::new("size", RwSignal::new("large".to_string()));
+    let rendered = view! {
+        <input type="checkbox" {..toppings.option(1)}/>
+        <input type="checkbox" {..toppings.option(2)}/>
+        <input type="radio" {..size.option("small".to_string())}/>
+        <input type="radio" {..size.option("large".to_string())}/>
+    };
+
+    assert_eq!(
+        rendered.to_html(),
+        "<input type=\"checkbox\" name=\"toppings\" value=\"1\" \
+         checked><input type=\"checkbox\" name=\"toppings\" \
+         value=\"2\"><input type=\"radio\" name=\"size\" \
+         value=\"small\"><input type=\"radio\" name=\"size\" \
+         value=\"large\" checked>"
+    );
+}
+
+#[test]
+fn typed_inputs_keep_invalid_text() {
+    let owner = Owner::new();
+    owner.set();
+
+    let amount = RwSignal::new(1.5);
+    let input = value_as::<f64>(amount);
+    assert_eq!(input.text(), "1.5");
+
+    input.set_text("2.");
+    assert_eq!(amount.get(), 2.0);
+    // the text is kept as it was typed while it parses to the same value
+    assert_eq!(input.text(), "2.");
+
+    input.set_text("2.x");
+    assert_eq!(amount.get(), 2.0);
+    assert_eq!(input.text(), "2.x");
+    assert_eq!(input.error().as_deref(), Some("invalid float literal"));
+
+    // setting the signal from elsewhere replaces the text
+    amount.set(10.0);
+    assert_eq!(input.text(), "10");
+}
+
+#[test]
+fn masks_format_as_the_user_types() {
+    let owner = Owner::new();
+    owner.set();
+
+    let phone = RwSignal::new(String::new());
+    let input = TypedInput::new(phone, Mask::new("(###) ###-####"));
+
+    input.set_text("5551");
+    assert_eq!(input.text(), "(555) 1");
+    assert!(input.is_invalid());
+
+    input.set_text("(555) 123-45678");
+    assert_eq!(input.text(), "(555) 123-4567");
+    assert_eq!(phone.get(), "5551234567");
+    assert!(!input.is_invalid());
+
+    phone.set("2125550000".to_string());
+    assert_eq!(input.text(), "(212) 555-0000");
+
+    let optional = Optional(Mask::new("##-##"));
+    assert_eq!(optional.parse(""), Ok(None));
+    assert_eq!(optional.parse("1234"), Ok(Some("1234".to_string())));
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn dates_are_parsed_and_formatted() {
+    use leptos::form::{DateInput, DateTimeInput, TimeInput};
+
+    let date = DateInput.parse("2024-02-29").unwrap();
+    assert_eq!(DateInput.format(&date), "2024-02-29");
+    assert!(DateInput.parse("2023-02-29").is_err());
+
+    let time = TimeInput.parse("09:30").unwrap();
+    assert_eq!(TimeInput.format(&time), "09:30");
+    let time = TimeInput.parse("09:30:15.250").unwrap();
+    assert_eq!(TimeInput.format(&time), "09:30:15");
+
+    let datetime = DateTimeInput.parse("2024-12-31T23:59").unwrap();
+    assert_eq!(DateTimeInput.format(&datetime), "2024-12-31T23:59");
+}
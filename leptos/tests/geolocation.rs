@@ -0,0 +1,38 @@
+#![cfg(all(
+    target_family = "wasm",
+    feature = "csr",
+    feature = "geolocation",
+    feature = "testing"
+))]
+
+use any_spawner::time::sleep;
+use leptos::{
+    geolocation::{
+        use_geolocation, GeolocationOptions, PermissionState, UseGeolocation,
+    },
+    testing::browser::*,
+};
+use std::{cell::Cell, rc::Rc, time::Duration};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+async fn the_permission_is_read_from_the_permissions_api() {
+    let geo = Rc::new(Cell::new(None::<UseGeolocation>));
+    mount_to_body_for_test({
+        let geo = Rc::clone(&geo);
+        move || geo.set(Some(use_geolocation(GeolocationOptions::default())))
+    });
+    let geo = geo.get().unwrap();
+    assert_eq!(geo.permission(), PermissionState::Unknown);
+
+    // the test browser may or may not have been granted the permission, but it answers
+    for _ in 0..20 {
+        if geo.permission() != PermissionState::Unknown {
+            break;
+        }
+        sleep(Duration::from_millis(50)).await;
+    }
+    assert_ne!(geo.permission(), PermissionState::Unknown);
+}
@@ -0,0 +1,70 @@
+#![cfg(all(target_family = "wasm", feature = "csr", feature = "testing"))]
+
+use leptos::{
+    ev,
+    leptos_dom::helpers::{
+        use_document_event_listener, use_window_event_listener,
+    },
+    prelude::*,
+    testing::browser::*,
+};
+use wasm_bindgen_test::*;
+use web_sys::Event;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn window_listeners_are_removed_when_their_owner_is_cleaned_up() {
+    let resizes = RwSignal::new(0);
+    mount_to_body_for_test(move || {
+        use_window_event_listener(ev::resize, move |_| *resizes.write() += 1);
+    });
+    let resize = || {
+        _ = window().dispatch_event(&Event::new("resize").unwrap());
+    };
+
+    resize();
+    assert_eq!(resizes.get_untracked(), 1);
+
+    cleanup();
+    resize();
+    assert_eq!(resizes.get_untracked(), 1);
+}
+
+#[wasm_bindgen_test]
+fn capturing_document_listeners_run_first_and_are_removed() {
+    let order = RwSignal::new(Vec::new());
+    let root = mount_to_body_for_test(move || {
+        use_document_event_listener(ev::capture(ev::click), move |_| {
+            order.write().push("document")
+        });
+        view! { <button on:click=move |_| order.write().push("button")/> }
+    });
+
+    click(&root.get("button"));
+    assert_eq!(order.get_untracked(), ["document", "button"]);
+
+    // a capturing listener is only removed if `capture` is passed again
+    cleanup();
+    order.write().clear();
+    let outside = document().create_element("button").unwrap();
+    document().body().unwrap().append_child(&outside).unwrap();
+    click(&outside);
+    outside.remove();
+    assert!(order.get_untracked().is_empty());
+}
+
+#[wasm_bindgen_test]
+fn once_document_listeners_only_run_once() {
+    let keys = RwSignal::new(0);
+    let root = mount_to_body_for_test(move || {
+        use_document_event_listener(ev::once(ev::keydown), move |_| {
+            *keys.write() += 1
+        });
+        view! { <input/> }
+    });
+
+    key_press(&root.get("input"), "a");
+    key_press(&root.get("input"), "b");
+    assert_eq!(keys.get_untracked(), 1);
+}
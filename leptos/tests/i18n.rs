@@ -0,0 +1,97 @@
+leptos::load_locales!(path = "tests/locales", default = "en");
+
+use i18n::{t, Locale};
+use leptos::{i18n::*, prelude::*};
+
+#[test]
+fn negotiates_locale() {
+    assert_eq!(Locale::negotiate("de-AT,de;q=0.9,en;q=0.8"), Locale::DeAt);
+    assert_eq!(Locale::negotiate("fr-CH, de;q=0.9, en;q=0.8"), Locale::DeAt);
+    assert_eq!(Locale::negotiate("en;q=0.5, de-AT;q=0.7"), Locale::DeAt);
+    assert_eq!(Locale::negotiate("fr, ja"), Locale::En);
+    assert_eq!(Locale::negotiate(""), Locale::En);
+    assert_eq!(Locale::from_tag("DE-at"), Some(Locale::DeAt));
+    assert_eq!(Locale::DeAt.as_str(), "de-AT");
+}
+
+#[test]
+fn messages_follow_locale() {
+    let owner = Owner::new();
+    owner.set();
+
+    let i18n = provide_i18n(Locale::default());
+    let name = "Alice";
+    let greeting = t!(i18n, greeting, name = name);
+    let home = t!(use_i18n::<Locale>(), nav.home);
+    let braces = t!(i18n, braces);
+
+    assert_eq!(greeting(), "Hello, Alice!");
+    assert_eq!(home(), "Home");
+    assert_eq!(braces(), "{not a variable}");
+
+    i18n.set_locale(Locale::DeAt);
+    assert_eq!(greeting(), "Servus, Alice!");
+    assert_eq!(home(), "Startseite");
+    assert_eq!(braces(), "{not a variable}");
+}
+
+#[test]
+fn plural_messages() {
+    let owner = Owner::new();
+    owner.set();
+
+    let i18n = provide_i18n(Locale::En);
+    let (count, set_count) = signal(0);
+    let cart = t!(i18n, cart, count = count.get(), name = "Alice");
+
+    assert_eq!(cart(), "Alice has no items in the cart.");
+    set_count.set(1);
+    assert_eq!(cart(), "Alice has 1 item in the cart.");
+    set_count.set(3);
+    assert_eq!(cart(), "Alice has 3 items in the cart.");
+
+    i18n.set_locale(Locale::DeAt);
+    assert_eq!(cart(), "Alice hat 3 Artikel im Warenkorb.");
+    set_count.set(1);
+    assert_eq!(cart(), "Alice hat einen Artikel im Warenkorb.");
+    set_count.set(0);
+    assert_eq!(cart(), "Alice hat keine Artikel im Warenkorb.");
+}
+
+#[test]
+fn plural_categories() {
+    use PluralCategory::*;
+
+    assert_eq!(plural_category("en-US", 1), One);
+    assert_eq!(plural_category("en", 0), Other);
+    assert_eq!(plural_category("fr", 0), One);
+    assert_eq!(plural_category("pt-PT", 0), Other);
+    assert_eq!(plural_category("ja", 1), Other);
+    assert_eq!(
+        [1, 2, 5, 11, 21, 22, 25].map(|n| plural_category("ru", n)),
+        [One, Few, Many, Many, One, Few, Many]
+    );
+    assert_eq!(
+        [1, 2, 5, 12, 22, 101].map(|n| plural_category("pl", n)),
+        [One, Few, Many, Many, Few, Many]
+    );
+    assert_eq!(
+        [0, 1, 2, 3, 11, 100].map(|n| plural_category("ar", n)),
+        [Zero, One, Two, Few, Many, Other]
+    );
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn messages_in_view() {
+    let owner = Owner::new();
+    owner.set();
+
+    let i18n = provide_i18n(Locale::DeAt);
+    let (name, _) = signal("Bob".to_string());
+    let rendered = view! {
+        <p>{t!(i18n, greeting, name = name.get())}</p>
+    };
+
+    assert_eq!(rendered.to_html(), "<p>Servus, Bob!</p>");
+}
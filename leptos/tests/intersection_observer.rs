@@ -0,0 +1,84 @@
+#![cfg(all(
+    target_family = "wasm",
+    feature = "csr",
+    feature = "observers",
+    feature = "testing"
+))]
+
+use leptos::{
+    html::Div,
+    intersection_observer::{
+        use_intersection_observer, IntersectionObserverOptions,
+        UseIntersectionObserver,
+    },
+    prelude::*,
+    testing::browser::*,
+};
+use std::{cell::Cell, rc::Rc};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// Mounts a box at `top` pixels from the top of the page, and observes it.
+fn observe_box(
+    top: u32,
+    options: IntersectionObserverOptions,
+) -> (TestRoot, UseIntersectionObserver) {
+    let visibility = Rc::new(Cell::new(None));
+    let root = mount_to_body_for_test({
+        let visibility = Rc::clone(&visibility);
+        move || {
+            let node_ref = NodeRef::<Div>::new();
+            visibility.set(Some(use_intersection_observer(node_ref, options)));
+            view! {
+                <div
+                    node_ref=node_ref
+                    style:position="absolute"
+                    style:top=format!("{top}px")
+                    style:width="50px"
+                    style:height="50px"
+                />
+            }
+        }
+    });
+    (root, visibility.get().unwrap())
+}
+
+#[wasm_bindgen_test]
+async fn elements_in_the_viewport_are_visible() {
+    let (_root, visibility) =
+        observe_box(0, IntersectionObserverOptions::default());
+
+    wait_until(|| visibility.is_intersecting()).await;
+    assert!(visibility.is_intersecting());
+    assert!(visibility.ratio() > 0.0);
+}
+
+#[wasm_bindgen_test]
+async fn elements_outside_the_viewport_are_not_visible() {
+    let (_root, visibility) =
+        observe_box(100_000, IntersectionObserverOptions::default());
+
+    // the first observation is made even if the element is not visible
+    for _ in 0..3 {
+        next_animation_frame().await;
+    }
+    assert!(!visibility.is_intersecting());
+    assert_eq!(visibility.ratio(), 0.0);
+}
+
+#[wasm_bindgen_test]
+async fn observing_once_stops_when_the_element_becomes_visible() {
+    let (_root, visibility) = observe_box(
+        0,
+        IntersectionObserverOptions {
+            once: true,
+            ..Default::default()
+        },
+    );
+    assert!(!visibility.is_stopped());
+
+    wait_until(|| visibility.is_stopped()).await;
+    assert!(visibility.is_stopped());
+    assert!(visibility.is_intersecting());
+}
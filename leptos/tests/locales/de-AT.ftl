@@ -0,0 +1,9 @@
+# Austrian German
+greeting = Servus, { $name }!
+nav.home = Startseite
+braces = {"{"}not a variable{"}"}
+cart = { $name } hat { $count ->
+    [0] keine Artikel
+    [one] einen Artikel
+   *[other] { $count } Artikel
+} im Warenkorb.
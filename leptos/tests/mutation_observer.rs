@@ -0,0 +1,127 @@
+#![cfg(all(
+    target_family = "wasm",
+    feature = "csr",
+    feature = "observers",
+    feature = "testing"
+))]
+
+use leptos::{
+    html::Div,
+    mutation_observer::{
+        use_mutation_observer, MutationObserverOptions, UseMutationObserver,
+    },
+    prelude::*,
+    testing::browser::*,
+};
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen_test::*;
+use web_sys::Element;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// A change that was reported to the observer.
+#[derive(Debug, Clone, PartialEq)]
+struct Change {
+    kind: String,
+    attribute: Option<String>,
+    old_value: Option<String>,
+}
+
+/// Mounts an element and observes it, returning the element and the changes reported so far.
+fn observe(
+    options: MutationObserverOptions,
+) -> (Element, UseMutationObserver, Rc<RefCell<Vec<Change>>>) {
+    let changes = Rc::new(RefCell::new(Vec::new()));
+    let observer = Rc::new(RefCell::new(None));
+    let root = mount_to_body_for_test({
+        let changes = Rc::clone(&changes);
+        let observer = Rc::clone(&observer);
+        move || {
+            let node_ref = NodeRef::<Div>::new();
+            *observer.borrow_mut() = Some(use_mutation_observer(
+                node_ref,
+                options,
+                move |records| {
+                    changes.borrow_mut().extend(records.into_iter().map(
+                        |record| Change {
+                            kind: record.type_(),
+                            attribute: record.attribute_name(),
+                            old_value: record.old_value(),
+                        },
+                    ))
+                },
+            ));
+            view! { <div node_ref=node_ref id="observed"/> }
+        }
+    });
+    let observer = observer.borrow_mut().take().unwrap();
+    (root.get("#observed"), observer, changes)
+}
+
+/// Waits until the effect that starts the observer has run, and until changes have been
+/// reported, both of which happen asynchronously.
+async fn settle() {
+    next_animation_frame().await;
+}
+
+#[wasm_bindgen_test]
+async fn children_being_added_are_reported_by_default() {
+    let (el, _observer, changes) = observe(MutationObserverOptions::default());
+    settle().await;
+
+    el.append_child(&document().create_element("span").unwrap())
+        .unwrap();
+    // attributes are not observed by default
+    el.set_attribute("title", "ignored").unwrap();
+    settle().await;
+
+    assert_eq!(
+        *changes.borrow(),
+        [Change {
+            kind: "childList".into(),
+            attribute: None,
+            old_value: None,
+        }]
+    );
+}
+
+#[wasm_bindgen_test]
+async fn an_attribute_filter_only_reports_those_attributes() {
+    let (el, _observer, changes) = observe(MutationObserverOptions {
+        child_list: false,
+        attribute_filter: vec!["data-state".into()],
+        attribute_old_value: true,
+        ..Default::default()
+    });
+    settle().await;
+
+    el.set_attribute("data-state", "open").unwrap();
+    el.set_attribute("data-state", "closed").unwrap();
+    el.set_attribute("title", "ignored").unwrap();
+    settle().await;
+
+    let attribute = |old_value: Option<&str>| Change {
+        kind: "attributes".into(),
+        attribute: Some("data-state".into()),
+        old_value: old_value.map(Into::into),
+    };
+    assert_eq!(
+        *changes.borrow(),
+        [attribute(None), attribute(Some("open"))]
+    );
+}
+
+#[wasm_bindgen_test]
+async fn stopping_disconnects_the_observer() {
+    let (el, observer, changes) = observe(MutationObserverOptions::default());
+    settle().await;
+
+    observer.stop();
+    settle().await;
+    el.append_child(&document().create_element("span").unwrap())
+        .unwrap();
+    settle().await;
+
+    assert!(observer.is_stopped());
+    assert!(changes.borrow().is_empty());
+}
@@ -0,0 +1,139 @@
+#![cfg(feature = "request")]
+
+use leptos::{
+    prelude::*,
+    request::{
+        use_request_parts, ConnectionInfo, Cookies, Extension, HeaderMap,
+        HeaderValue, Method, PreloadLink, RequestParts, ResponseHeaders,
+        TraceContext,
+    },
+};
+
+#[derive(Debug, Clone, PartialEq)]
+struct UserId(u32);
+
+fn parts() -> RequestParts {
+    let mut headers = HeaderMap::new();
+    headers.insert("host", HeaderValue::from_static("example.com"));
+    headers.append(
+        "cookie",
+        HeaderValue::from_static("session=abc; theme=dark"),
+    );
+    headers.append(
+        "cookie",
+        HeaderValue::from_static("session=old; lang=\"en\""),
+    );
+    let mut parts = RequestParts {
+        method: Method::POST,
+        uri: "/api/whoami?x=1".parse().unwrap(),
+        headers,
+        remote_addr: Some("127.0.0.1:4000".parse().unwrap()),
+        ..Default::default()
+    };
+    parts.extensions.insert(UserId(7));
+    parts
+}
+
+#[test]
+fn request_parts_are_extracted() {
+    let owner = Owner::new();
+    owner.with(|| {
+        provide_context(parts());
+
+        assert_eq!(use_request_parts::<Method>().unwrap(), Method::POST);
+        assert_eq!(
+            use_request_parts::<ConnectionInfo>().unwrap(),
+            ConnectionInfo {
+                remote_addr: Some("127.0.0.1:4000".parse().unwrap()),
+                host: Some("example.com".into()),
+            }
+        );
+
+        let cookies = use_request_parts::<Cookies>().unwrap();
+        assert_eq!(cookies.get("session"), Some("abc"));
+        assert_eq!(cookies.get("theme"), Some("dark"));
+        assert_eq!(cookies.get("lang"), Some("en"));
+        assert_eq!(cookies.get("missing"), None);
+
+        let Extension(user) = use_request_parts::<Extension<UserId>>().unwrap();
+        assert_eq!(user, UserId(7));
+        assert!(use_request_parts::<Extension<String>>().is_err());
+        assert_eq!(
+            use_request_parts::<Option<Extension<String>>>().unwrap(),
+            None
+        );
+    });
+}
+
+#[test]
+fn extraction_fails_without_a_request() {
+    let owner = Owner::new();
+    owner.with(|| {
+        assert!(use_request_parts::<Method>().is_err());
+        assert!(use_request_parts::<Cookies>().is_err());
+    });
+}
+
+#[test]
+fn trace_context_is_parsed_from_traceparent() {
+    let parsed = TraceContext::parse(
+        "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+    )
+    .unwrap();
+    assert_eq!(parsed.trace_id, "4bf92f3577b34da6a3ce929d0e0e4736");
+    assert_eq!(parsed.parent_id, "00f067aa0ba902b7");
+    assert!(parsed.sampled);
+
+    for invalid in [
+        "",
+        "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",
+        "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+        "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01",
+        "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-xx",
+        "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+    ] {
+        assert_eq!(TraceContext::parse(invalid), None, "{invalid}");
+    }
+
+    let owner = Owner::new();
+    owner.with(|| {
+        provide_context(parts());
+        assert_eq!(use_request_parts::<Option<TraceContext>>().unwrap(), None);
+    });
+}
+
+#[test]
+fn preload_links_are_added_to_the_response_headers() {
+    use std::sync::{Arc, Mutex};
+
+    let wasm = PreloadLink::preload("/pkg/app.wasm", "fetch")
+        .type_("application/wasm")
+        .crossorigin("");
+    assert_eq!(
+        wasm.header_value(),
+        "</pkg/app.wasm>; rel=preload; as=fetch; type=\"application/wasm\"; \
+         crossorigin"
+    );
+
+    let owner = Owner::new();
+    owner.with(|| {
+        assert!(!wasm.send());
+
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        provide_context(ResponseHeaders::new({
+            let sent = Arc::clone(&sent);
+            move |name, value| {
+                sent.lock().unwrap().push((name, value));
+                true
+            }
+        }));
+        assert!(PreloadLink::modulepreload("/pkg/app.js").send());
+        assert_eq!(
+            *sent.lock().unwrap(),
+            [(
+                http::header::LINK,
+                HeaderValue::from_static("</pkg/app.js>; rel=modulepreload")
+            )]
+        );
+    });
+}
@@ -0,0 +1,38 @@
+#[cfg(not(feature = "ssr"))]
+mod server_fn_mock {
+    use futures::executor::block_on;
+    use leptos::{prelude::*, server_fn::mock};
+
+    #[server]
+    pub async fn get_user_name(id: u32) -> Result<String, ServerFnError> {
+        unreachable!("the server is never reached in tests: {id}")
+    }
+
+    #[server]
+    pub async fn save_user_name(name: String) -> Result<(), ServerFnError> {
+        unreachable!("the server is never reached in tests: {name}")
+    }
+
+    #[test]
+    fn mocks_replace_requests() {
+        mock::register::<GetUserName>(|args| Ok(format!("User {}", args.id)));
+        mock::register_async::<SaveUserName, _>(|args| async move {
+            if args.name.is_empty() {
+                Err(ServerFnError::new("the name is empty"))
+            } else {
+                Ok(())
+            }
+        });
+
+        assert_eq!(block_on(get_user_name(7)).unwrap(), "User 7");
+        assert!(block_on(save_user_name("Ada".into())).is_ok());
+        assert!(block_on(save_user_name(String::new())).is_err());
+
+        mock::register::<GetUserName>(|_| Ok("Grace".into()));
+        assert_eq!(block_on(get_user_name(7)).unwrap(), "Grace");
+
+        mock::unregister::<GetUserName>();
+        assert!(block_on(save_user_name("Ada".into())).is_ok());
+        mock::clear();
+    }
+}
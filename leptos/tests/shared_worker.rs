@@ -0,0 +1,126 @@
+#![cfg(all(
+    target_family = "wasm",
+    feature = "shared-worker",
+    feature = "testing"
+))]
+
+use leptos::{
+    shared_worker::{PortError, TypedPort},
+    testing::browser::*,
+};
+use serde::{Deserialize, Serialize};
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::*;
+use web_sys::{
+    js_sys::{ArrayBuffer, Object, Reflect},
+    MessageChannel,
+};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+enum Request {
+    Ping(u32),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+enum Response {
+    Pong(u32),
+}
+
+type Client = TypedPort<Request, Response>;
+type Server = TypedPort<Response, Request>;
+
+/// Wraps both ends of a `MessageChannel`, under the owner of a mounted view.
+fn connect() -> (Client, Server) {
+    let ports = Rc::new(RefCell::new(None));
+    mount_to_body_for_test({
+        let ports = Rc::clone(&ports);
+        move || {
+            let channel = MessageChannel::new().unwrap();
+            *ports.borrow_mut() = Some((
+                TypedPort::new(channel.port1()),
+                TypedPort::new(channel.port2()),
+            ));
+        }
+    });
+    let ports = ports.borrow_mut().take();
+    ports.unwrap()
+}
+
+#[wasm_bindgen_test]
+async fn typed_messages_are_sent_both_ways() {
+    let (client, server) = connect();
+    server.on_message(move |Request::Ping(n), _| {
+        _ = server.send(&Response::Pong(*n + 1));
+    });
+    assert!(client.is_open());
+
+    client.send(&Request::Ping(1)).unwrap();
+    wait_until(|| client.message().is_some()).await;
+    assert_eq!(server.message(), Some(Request::Ping(1)));
+    assert_eq!(client.message(), Some(Response::Pong(2)));
+}
+
+#[wasm_bindgen_test]
+async fn transferred_objects_are_moved_to_the_other_side() {
+    let (client, server) = connect();
+    let received = Rc::new(RefCell::new(Vec::new()));
+    server.on_message({
+        let received = Rc::clone(&received);
+        move |_, transfer| received.borrow_mut().extend_from_slice(transfer)
+    });
+
+    let buffer = ArrayBuffer::new(16);
+    client
+        .send_with_transfer(&Request::Ping(1), &[buffer.clone().into()])
+        .unwrap();
+    wait_until(|| !received.borrow().is_empty()).await;
+
+    let received = received.borrow();
+    assert_eq!(received.len(), 1);
+    assert_eq!(ArrayBuffer::from(received[0].clone()).byte_length(), 16);
+    // the buffer no longer belongs to this side
+    assert_eq!(buffer.byte_length(), 0);
+}
+
+#[wasm_bindgen_test]
+async fn messages_that_cannot_be_deserialized_are_skipped() {
+    let channel = MessageChannel::new().unwrap();
+    let server = Rc::new(RefCell::new(None::<Server>));
+    mount_to_body_for_test({
+        let port = channel.port2();
+        let server = Rc::clone(&server);
+        move || *server.borrow_mut() = Some(TypedPort::new(port))
+    });
+    let server = server.borrow_mut().take().unwrap();
+    let received = Rc::new(RefCell::new(Vec::new()));
+    server.on_message({
+        let received = Rc::clone(&received);
+        move |request, _| received.borrow_mut().push(request.clone())
+    });
+
+    // posted directly to the other end, like a script written in JavaScript would
+    let post = |json: &str| {
+        let envelope = Object::new();
+        Reflect::set(&envelope, &"json".into(), &JsValue::from_str(json))
+            .unwrap();
+        channel.port1().post_message(&envelope).unwrap();
+    };
+    post(r#""not a request""#);
+    post(r#"{"Ping":3}"#);
+
+    wait_until(|| server.message().is_some()).await;
+    assert_eq!(*received.borrow(), [Request::Ping(3)]);
+}
+
+#[wasm_bindgen_test]
+fn ports_are_closed_with_their_owner() {
+    let (client, _server) = connect();
+    assert!(client.is_open());
+
+    cleanup();
+    assert!(!client.is_open());
+    assert_eq!(client.send(&Request::Ping(1)), Err(PortError::Closed));
+}
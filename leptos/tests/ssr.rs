@@ -135,3 +135,501 @@ fn ssr_option() {
 
     assert_eq!(rendered.to_html(), "<option></option>");
 }
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_match_and_if_else() {
+    use leptos::prelude::*;
+
+    #[allow(dead_code)]
+    enum State {
+        Loading,
+        Ready(i32),
+        Failed,
+    }
+
+    let (state, _) = signal(State::Ready(42));
+    let (logged_in, _) = signal(false);
+    let rendered: View<HtmlElement<_, _, _>> = view! {
+        <div>
+            {#[either] move || match *state.read() {
+                State::Loading => view! { <p>"Loading..."</p> },
+                State::Ready(value) => view! { <span>{value}</span> },
+                State::Failed => "Failed",
+            }}
+            {#[either] move || if logged_in.get() {
+                view! { <button>"Log out"</button> }
+            } else {
+                view! { <a href="/login">"Log in"</a> }
+            }}
+            {move || #[either] if logged_in.get() {
+                view! { <button>"Log out"</button> }
+            }}
+        </div>
+    };
+
+    assert_eq!(
+        rendered.to_html(),
+        "<div><span>42</span><a href=\"/login\">Log in</a><!></div>"
+    );
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_unmarked_match_and_if_else() {
+    use leptos::prelude::*;
+
+    let (count, _) = signal(2);
+    // without `#[either]`, the branches are left as they are
+    let rendered: View<HtmlElement<_, _, _>> = view! {
+        <div>
+            {move || match count.get() {
+                0 => view! { <p>"None"</p> }.into_any(),
+                _ => view! { <span>{count.get()}</span> }.into_any(),
+            }}
+            {move || if count.get() == 1 { "item" } else { "items" }}
+        </div>
+    };
+
+    assert_eq!(rendered.to_html(), "<div><span>2</span>items</div>");
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_let_bindings() {
+    use leptos::prelude::*;
+
+    let (items, _) = signal(vec![1, 2, 3]);
+    let rendered: View<HtmlElement<_, _, _>> = view! {
+        <ul>
+            {let total = move || items.read().len();}
+            {let label = "items";}
+            <li data-total=total>{total} " " {label}</li>
+            <li>{move || total() * 2}</li>
+        </ul>
+    };
+
+    assert_eq!(
+        rendered.to_html(),
+        "<ul><li data-total=\"3\">3<!> <!>items</li><li>6</li></ul>"
+    );
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_let_bindings_run_once() {
+    use leptos::prelude::*;
+
+    let items = RwSignal::new(vec![1, 2, 3]);
+    let name = String::from("items");
+    let rendered = view! {
+        {let count = items.read().len(); let total = move || items.read().len();}
+        <li data-count=count>{total}</li>
+        // values from outside the view can still be moved into the nodes that follow
+        <li>{name}</li>
+    };
+    items.update(|items| items.push(4));
+
+    // the statements ran when the view was created, but the closure follows the signal
+    assert_eq!(
+        rendered.to_html(),
+        "<li data-count=\"3\">4</li><li>items</li>"
+    );
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_typed_slots() {
+    use leptos::prelude::*;
+
+    #[slot]
+    struct Title {
+        children: Children,
+    }
+
+    #[slot]
+    struct Tab {
+        #[prop(into)]
+        label: String,
+        #[prop(into)]
+        badge: Callback<(usize,), String>,
+    }
+
+    #[slot]
+    struct Footer {
+        children: Children,
+    }
+
+    #[component]
+    fn Tabs(
+        title: Title,
+        #[prop(optional)] tab: Vec<Tab>,
+        #[prop(optional)] footer: Option<Footer>,
+    ) -> impl IntoView {
+        view! {
+            <nav>
+                <h2>{(title.children)()}</h2>
+                {tab
+                    .into_iter()
+                    .enumerate()
+                    .map(|(idx, tab)| view! { <a title=tab.badge.run((idx,))>{tab.label}</a> })
+                    .collect::<Vec<_>>()}
+                {footer.map(|footer| (footer.children)())}
+            </nav>
+        }
+    }
+
+    let one_tab = view! {
+        <Tabs>
+            <Title slot>"One"</Title>
+            <Tab slot label="First" badge=|idx: usize| idx.to_string()/>
+        </Tabs>
+    };
+    assert_eq!(
+        one_tab.to_html(),
+        "<nav><h2>One</h2><a title=\"0\">First</a><!><!></nav>"
+    );
+
+    let many_tabs = view! {
+        <Tabs>
+            <Title slot>"Many"</Title>
+            <Tab slot label="First" badge=|_: usize| "new".to_string()/>
+            <Tab slot label="Second" badge=|idx: usize| format!("#{idx}")/>
+            <Footer slot>"Done"</Footer>
+        </Tabs>
+    };
+    assert_eq!(
+        many_tabs.to_html(),
+        "<nav><h2>Many</h2><a title=\"new\">First</a><a \
+         title=\"#1\">Second</a><!>Done</nav>"
+    );
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_render_prop_children() {
+    use leptos::prelude::*;
+
+    struct Row {
+        index: usize,
+        label: &'static str,
+    }
+
+    #[component]
+    fn Rows(
+        labels: Vec<&'static str>,
+        children: impl Fn(usize, &'static str) -> impl IntoView,
+    ) -> impl IntoView {
+        labels
+            .into_iter()
+            .enumerate()
+            .map(|(index, label)| children(index, label))
+            .collect::<Vec<_>>()
+    }
+
+    #[component]
+    fn RowsWithContext(
+        labels: Vec<&'static str>,
+        children: impl Fn(Row) -> impl IntoView,
+    ) -> impl IntoView {
+        labels
+            .into_iter()
+            .enumerate()
+            .map(|(index, label)| children(Row { index, label }))
+            .collect::<Vec<_>>()
+    }
+
+    let bindings = view! {
+        <Rows labels=vec!["a", "b"] let:index let:label>
+            <li data-index=index>{label}</li>
+        </Rows>
+    };
+    assert_eq!(
+        bindings.to_html(),
+        "<li data-index=\"0\">a</li><li data-index=\"1\">b</li><!>"
+    );
+
+    let destructured = view! {
+        <RowsWithContext labels=vec!["a", "b"] let(Row { index, label })>
+            <li data-index=index>{label}</li>
+        </RowsWithContext>
+    };
+    assert_eq!(
+        destructured.to_html(),
+        "<li data-index=\"0\">a</li><li data-index=\"1\">b</li><!>"
+    );
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_prop_groups() {
+    use leptos::prelude::*;
+
+    #[derive(PropGroup, Clone, Default)]
+    struct CommonProps {
+        disabled: bool,
+        size: &'static str,
+    }
+
+    #[component]
+    fn Button(
+        #[prop(flatten)] common: CommonProps,
+        label: &'static str,
+    ) -> impl IntoView {
+        view! {
+            <button disabled=common.disabled data-size=common.size>
+                {label}
+            </button>
+        }
+    }
+
+    let common = CommonProps {
+        disabled: true,
+        size: "small",
+    };
+    let spread = view! { <Button {..common.clone()} label="Save"/> };
+    assert_eq!(
+        spread.to_html(),
+        "<button disabled data-size=\"small\">Save</button>"
+    );
+
+    let named = view! { <Button common label="Save"/> };
+    assert_eq!(
+        named.to_html(),
+        "<button disabled data-size=\"small\">Save</button>"
+    );
+
+    let attrs = view! { <{..} class="primary"/> };
+    let default = view! { <Button label="Save" {..attrs}/> };
+    assert_eq!(
+        default.to_html(),
+        "<button data-size=\"\" class=\"primary\">Save</button>"
+    );
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_custom_element_bindings() {
+    use leptos::{custom_element_bindings, prelude::*};
+
+    custom_element_bindings! {
+        SlButton("sl-button") {
+            attributes {
+                variant: String,
+                disabled: bool,
+                help_text("help-text"): String,
+            }
+            properties {
+                value: String,
+            }
+            events {
+                SlFocus("sl-focus"): (),
+            }
+        }
+    }
+
+    let (disabled, _) = signal(true);
+    let rendered = view! {
+        <SlButton
+            variant="primary"
+            disabled=disabled
+            value="save"
+            on:typed:SlFocus=|_| {}
+        >
+            "Save"
+        </SlButton>
+    };
+    assert_eq!(
+        rendered.to_html(),
+        "<sl-button variant=\"primary\" disabled>Save</sl-button>"
+    );
+
+    let rendered = view! { <SlButton help_text="Saves the document"/> };
+    assert_eq!(
+        rendered.to_html(),
+        "<sl-button help-text=\"Saves the document\"><!></sl-button>"
+    );
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_typed_custom_events() {
+    use leptos::{ev::TypedCustomEvent, prelude::*};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Clone, Serialize, Deserialize)]
+    struct SavePayload {
+        id: u32,
+    }
+
+    leptos::define_custom_event!(SaveRequested, SavePayload);
+
+    // `on:typed:` takes an event type that is in scope
+    let typed = view! {
+        <div on:typed:SaveRequested={|ev: TypedCustomEvent<SavePayload>| {
+            _ = ev.detail().map(|payload| payload.id);
+        }}/>
+    };
+    assert_eq!(typed.to_html(), "<div></div>");
+
+    // any other name is the name of the DOM event, even if it is capitalized
+    let named = view! { <div on:DOMContentLoaded=|_| {}/> };
+    assert_eq!(named.to_html(), "<div></div>");
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn hydration_scripts_pass_the_server_fn_mount_to_the_client() {
+    use leptos::{
+        config::LeptosOptions, hydration::HydrationScripts, prelude::*,
+    };
+
+    let options = LeptosOptions::builder()
+        .output_name("app")
+        .server_fn_mount("/api/v2")
+        .build();
+    let rendered =
+        Owner::new().with(|| view! { <HydrationScripts options/> }.to_html());
+    assert!(
+        rendered.starts_with(
+            "<meta name=\"leptos-server-fn-mount\" content=\"/api /api/v2\">"
+        ),
+        "{rendered}"
+    );
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn hydration_scripts_load_the_app_below_the_base_path() {
+    use leptos::{
+        config::LeptosOptions, hydration::HydrationScripts, prelude::*,
+    };
+
+    let options = LeptosOptions::builder()
+        .output_name("app")
+        .base_path("/tools/myapp/")
+        .build();
+    let rendered =
+        Owner::new().with(|| view! { <HydrationScripts options/> }.to_html());
+    assert!(
+        rendered.starts_with(
+            "<meta name=\"leptos-base-path\" content=\"/tools/myapp\"><meta \
+             name=\"leptos-server-fn-mount\" content=\"/api \
+             /tools/myapp/api\"><link rel=\"modulepreload\" \
+             href=\"/tools/myapp/pkg/app.js\">"
+        ),
+        "{rendered}"
+    );
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn hydration_scripts_leave_nothing_behind_without_a_base_path() {
+    use leptos::{
+        config::LeptosOptions, hydration::HydrationScripts, prelude::*,
+    };
+
+    let options = LeptosOptions::builder().output_name("app").build();
+    let rendered =
+        Owner::new().with(|| view! { <HydrationScripts options/> }.to_html());
+    assert!(
+        rendered
+            .starts_with("<link rel=\"modulepreload\" href=\"/pkg/app.js\">"),
+        "{rendered}"
+    );
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn routes_are_moved_below_the_base_path() {
+    use leptos::{
+        base_path::{base_path, ensure_base_path},
+        config::LeptosOptions,
+        hydration::mount_server_fns,
+    };
+
+    let options = LeptosOptions::builder()
+        .output_name("app")
+        .base_path("/tools/myapp/")
+        .build();
+    mount_server_fns(&options);
+    assert_eq!(base_path().as_deref(), Some("/tools/myapp"));
+    assert_eq!(ensure_base_path("/posts"), "/tools/myapp/posts");
+    assert_eq!(ensure_base_path("/tools/myapp/posts"), "/tools/myapp/posts");
+    assert_eq!(
+        ensure_base_path("/tools/myappx"),
+        "/tools/myapp/tools/myappx"
+    );
+}
+
+#[cfg(all(feature = "ssr", feature = "worker"))]
+#[test]
+fn hydration_scripts_pass_the_worker_script_to_the_client() {
+    use leptos::{
+        config::LeptosOptions, hydration::HydrationScripts, prelude::*,
+    };
+
+    let options = LeptosOptions::builder().output_name("app").build();
+    let rendered =
+        Owner::new().with(|| view! { <HydrationScripts options/> }.to_html());
+    assert!(
+        rendered.ends_with(
+            "<meta name=\"leptos-worker-script\" content=\"/pkg/app.js\">"
+        ),
+        "{rendered}"
+    );
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn raw_attributes_are_not_escaped() {
+    use leptos::prelude::*;
+
+    let config = r#"{'theme':"dark"}"#.replace('"', "&quot;");
+    let rendered: View<HtmlElement<_, _, _>> = view! {
+        <div data-escaped="a&b" attr:raw:data-config=config></div>
+    };
+
+    assert_eq!(
+        rendered.to_html(),
+        "<div data-escaped=\"a&amp;b\" \
+         data-config=\"{'theme':&quot;dark&quot;}\"></div>"
+    );
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn auto_bindings_render_as_attributes() {
+    use leptos::prelude::*;
+
+    let rendered: View<HtmlElement<_, _, _>> = view! {
+        <input auto:value="hello" auto:checked=true auto:data-id=7 attr:list="suggestions"/>
+    };
+
+    assert_eq!(
+        rendered.to_html(),
+        "<input value=\"hello\" checked data-id=\"7\" list=\"suggestions\">"
+    );
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn class_list_merges_conflicting_classes() {
+    use leptos::prelude::*;
+
+    let (active, _) = signal(true);
+    let extra = Some("px-6");
+    let rendered: View<HtmlElement<_, _, _>> = view! {
+        <button class=class_list![
+            "px-4 py-2 bg-gray-100 rounded",
+            ("bg-blue-500 text-white", move || active.get()),
+            ("hidden", false),
+            extra,
+        ]>"Save"</button>
+    };
+
+    assert_eq!(
+        rendered.to_html(),
+        "<button class=\"py-2 rounded bg-blue-500 text-white px-6\">Save</button>"
+    );
+}
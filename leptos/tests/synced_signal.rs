@@ -0,0 +1,78 @@
+#![cfg(all(
+    target_family = "wasm",
+    feature = "csr",
+    feature = "synced-signal",
+    feature = "testing"
+))]
+
+use leptos::{prelude::*, synced_signal::SyncedSignal, testing::browser::*};
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// Mounts a synced signal for each key. Each `BroadcastChannel` receives the messages of the
+/// others with the same name, even in the same tab, so signals with the same key stand in for
+/// different tabs.
+fn mount_synced(keys: &'static [&'static str]) -> Vec<SyncedSignal<i32>> {
+    let signals = Rc::new(RefCell::new(Vec::new()));
+    mount_to_body_for_test({
+        let signals = Rc::clone(&signals);
+        move || {
+            signals
+                .borrow_mut()
+                .extend(keys.iter().map(|key| SyncedSignal::new(*key, 0)))
+        }
+    });
+    signals.take()
+}
+
+#[wasm_bindgen_test]
+async fn changes_are_sent_to_signals_with_the_same_key() {
+    let signals = mount_synced(&["counter", "counter", "other-counter"]);
+    let [first, second, other] = signals[..] else {
+        unreachable!()
+    };
+    // lets the effects open the channels
+    next_animation_frame().await;
+
+    first.set(5);
+    wait_until(|| second.get_untracked() == 5).await;
+    assert_eq!(second.get_untracked(), 5);
+
+    second.update(|n| *n += 1);
+    wait_until(|| first.get_untracked() == 6).await;
+    assert_eq!(first.get_untracked(), 6);
+    assert_eq!(other.get_untracked(), 0);
+}
+
+#[component]
+fn Tab(
+    key: &'static str,
+    tab: RwSignal<Option<SyncedSignal<i32>>>,
+) -> impl IntoView {
+    tab.set(Some(SyncedSignal::new(key, 0)));
+}
+
+#[wasm_bindgen_test]
+async fn signals_created_later_ask_for_the_current_value() {
+    let first = RwSignal::new(None);
+    let later = RwSignal::new(None);
+    let open_later = RwSignal::new(false);
+    mount_to_body_for_test(move || {
+        view! {
+            <Tab key="late" tab=first/>
+            <Show when=move || open_later.get()>
+                <Tab key="late" tab=later/>
+            </Show>
+        }
+    });
+    next_animation_frame().await;
+    first.get_untracked().unwrap().set(7);
+
+    open_later.set(true);
+    next_animation_frame().await;
+    let later = later.get_untracked().expect("the later tab was not opened");
+    wait_until(|| later.get_untracked() == 7).await;
+    assert_eq!(later.get_untracked(), 7);
+}
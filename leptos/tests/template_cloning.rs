@@ -0,0 +1,43 @@
+#![cfg(feature = "ssr")]
+
+use leptos::{html::HtmlElement, prelude::*};
+
+#[test]
+fn static_structure_uses_template() {
+    let (active, set_active) = signal(true);
+    let rendered: ViewTemplate<_> = view! {
+        #[template]
+        <div class="card" class:active=move || active.get()>
+            <h2>"Title"</h2>
+            <button on:click=move |_| set_active.set(false)>"Close"</button>
+        </div>
+    };
+
+    assert_eq!(
+        rendered.to_html(),
+        "<div class=\"card active\"><h2>Title</h2><button>Close</button></div>"
+    );
+}
+
+#[test]
+fn dynamic_children_fall_back() {
+    let (value, _) = signal(0);
+    let rendered: View<HtmlElement<_, _, _>> = view! {
+        #[template]
+        <p>"Value: " {move || value.get()}</p>
+    };
+
+    assert_eq!(rendered.to_html(), "<p>Value: <!>0</p>");
+}
+
+#[test]
+fn views_without_the_attribute_are_unchanged() {
+    let rendered: View<HtmlElement<_, _, _>> = view! {
+        <div class="card"><h2>"Title"</h2></div>
+    };
+
+    assert_eq!(
+        rendered.to_html(),
+        "<div class=\"card\"><h2>Title</h2></div>"
+    );
+}
@@ -0,0 +1,175 @@
+#[cfg(all(feature = "ssr", feature = "testing"))]
+mod testing {
+    use any_spawner::Executor;
+    use futures::executor::block_on;
+    use leptos::{
+        ev,
+        prelude::*,
+        testing::{normalize_html, Screen},
+    };
+
+    #[component]
+    fn Todos(items: RwSignal<Vec<&'static str>>) -> impl IntoView {
+        view! {
+            <h2>"Todos & more"</h2>
+            <ul>
+                <For each=move || items.get() key=|item| *item let:item>
+                    <li data-testid=item>{item}</li>
+                </For>
+            </ul>
+            <input type="checkbox" checked />
+            <a href="/done">"Done"</a>
+        }
+    }
+
+    #[test]
+    fn queries_by_role_text_and_test_id() {
+        let items = RwSignal::new(vec!["milk", "eggs"]);
+        let mut screen =
+            block_on(Screen::render(move || view! { <Todos items /> }));
+
+        assert_eq!(screen.get_by_role("heading").text(), "Todos & more");
+        assert_eq!(screen.all_by_role("listitem").len(), 2);
+        assert_eq!(screen.get_by_test_id("eggs").name(), "li");
+        assert_eq!(screen.get_by_role("checkbox").attr("checked"), Some(""));
+        assert_eq!(screen.get_by_text("Done").attr("href"), Some("/done"));
+        assert!(screen.query_by_text("bread").is_none());
+
+        items.update(|items| items.push("bread"));
+        block_on(screen.rerender());
+        let items = screen
+            .all_by_role("listitem")
+            .into_iter()
+            .map(|item| item.text())
+            .collect::<Vec<_>>();
+        assert_eq!(items, ["milk", "eggs", "bread"]);
+    }
+
+    #[test]
+    fn waits_for_suspense() {
+        _ = Executor::init_futures_executor();
+        let screen = block_on(Screen::render(|| {
+            let user = Resource::new(|| (), |_| async { "Ada".to_string() });
+            view! {
+                <Suspense fallback=|| view! { <p>"Loading..."</p> }>
+                    <p data-testid="user">{move || user.get()}</p>
+                </Suspense>
+            }
+        }));
+
+        assert!(screen.query_by_text("Loading...").is_none());
+        assert_eq!(screen.get_by_test_id("user").text(), "Ada");
+    }
+
+    #[test]
+    fn settle_runs_tasks_until_the_view_stops_changing() {
+        _ = Executor::init_futures_executor();
+        let count = RwSignal::new(0);
+        let mut screen =
+            block_on(Screen::render(move || view! { <p>{count}</p> }));
+
+        // each task spawns the next one, until the count has reached three
+        fn step(count: RwSignal<i32>) {
+            Executor::spawn(async move {
+                count.update(|count| *count += 1);
+                if count.get_untracked() < 3 {
+                    step(count);
+                }
+            });
+        }
+        step(count);
+        block_on(screen.settle());
+        assert_eq!(screen.text(), "3");
+    }
+
+    #[test]
+    fn fires_events_at_elements() {
+        _ = Executor::init_futures_executor();
+        let count = RwSignal::new(0);
+        let focused = RwSignal::new(false);
+        let mut screen = block_on(Screen::render(move || {
+            let user = Resource::new(|| (), |_| async { "Ada".to_string() });
+            view! {
+                <div on:click=move |_| *count.write() += 10>
+                    <button on:click=move |ev: ev::MouseEvent| {
+                        _ = ev;
+                        *count.write() += 1;
+                    }>{count}</button>
+                    <Suspense>
+                        <input
+                            aria-label=move || user.get()
+                            on:focus=move |_| focused.set(true)
+                        />
+                    </Suspense>
+                </div>
+            }
+        }));
+        assert!(!screen.html().contains("data-leptos-listener"));
+
+        // `click` bubbles up to the `<div>`
+        screen.fire(screen.get_by_role("button"), ev::click);
+        block_on(screen.settle());
+        assert_eq!(screen.get_by_role("button").text(), "11");
+
+        screen.fire(screen.get_by_role("textbox"), ev::focus);
+        assert!(focused.get_untracked());
+    }
+
+    #[test]
+    #[should_panic(expected = "no listener for `focus`")]
+    fn firing_an_event_nothing_listens_for_panics() {
+        let screen = block_on(Screen::render(|| {
+            view! { <div on:focus=|_| ()><button>"Save"</button></div> }
+        }));
+        // `focus` does not bubble
+        screen.fire(screen.get_by_role("button"), ev::focus);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "the `click` listener of <button>Save</button> panicked"
+    )]
+    fn listeners_that_read_the_event_panic_with_the_event_and_element() {
+        let screen = block_on(Screen::render(|| {
+            view! {
+                <button on:click=|ev: ev::MouseEvent| ev.prevent_default()>
+                    "Save"
+                </button>
+            }
+        }));
+        screen.fire(screen.get_by_role("button"), ev::click);
+    }
+
+    #[test]
+    fn snapshots_are_normalized() {
+        _ = Executor::init_futures_executor();
+        let screen = block_on(Screen::render(|| {
+            let user = Resource::new(|| (), |_| async { "Ada".to_string() });
+            view! {
+                <main id="app" class="page">
+                    <Suspense fallback=|| "Loading...">
+                        <p>"Hello, " {move || user.get()} "!"</p>
+                    </Suspense>
+                    <input type="checkbox" checked title="<done>" />
+                </main>
+            }
+        }));
+
+        assert_eq!(
+            screen.snapshot(),
+            "<main class=\"page\" id=\"app\">\n  <p>Hello, Ada!</p>\n  \
+             <input checked title=\"&lt;done&gt;\" type=\"checkbox\">\n</main>\n"
+        );
+    }
+
+    #[test]
+    fn normalizes_streamed_html() {
+        let html = "<div><!--s-1-o--><b>Loading</b><!--s-1-c--></div>\
+                    <template id=\"1-f\"><b>Done</b></template>\
+                    <script nonce=\"abc\">resolve()</script>";
+        assert_eq!(
+            normalize_html(html),
+            "<div>\n  <b>Loading</b>\n</div>\n<template>\n  <b>Done</b>\n</template>\n"
+        );
+    }
+}
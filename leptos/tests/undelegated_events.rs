@@ -0,0 +1,44 @@
+#![cfg(all(
+    target_family = "wasm",
+    feature = "testing",
+    feature = "delegation"
+))]
+
+use leptos::{prelude::*, testing::browser::*};
+use wasm_bindgen_test::*;
+use web_sys::{Element, Event, EventInit};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+// an event that does not bubble never reaches the delegated root handler, so only a listener
+// attached to the element itself sees it
+fn dispatch_without_bubbling(target: &Element, name: &str) {
+    let init = EventInit::new();
+    init.set_bubbles(false);
+    let event = Event::new_with_event_init_dict(name, &init)
+        .expect("could not create the event");
+    _ = target.dispatch_event(&event);
+}
+
+#[wasm_bindgen_test]
+fn undelegated_listeners_are_attached_to_the_element() {
+    let delegated = RwSignal::new(0);
+    let direct = RwSignal::new(0);
+    let root = mount_to_body_for_test(move || {
+        view! {
+            <button id="delegated" on:click=move |_| *delegated.write() += 1/>
+            <button id="direct" on:click:undelegated=move |_| *direct.write() += 1/>
+        }
+    });
+
+    dispatch_without_bubbling(&root.get("#delegated"), "click");
+    dispatch_without_bubbling(&root.get("#direct"), "click");
+    assert_eq!(delegated.get_untracked(), 0);
+    assert_eq!(direct.get_untracked(), 1);
+
+    // both still see a click that bubbles
+    click(&root.get("#delegated"));
+    click(&root.get("#direct"));
+    assert_eq!(delegated.get_untracked(), 1);
+    assert_eq!(direct.get_untracked(), 2);
+}
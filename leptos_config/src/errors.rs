@@ -13,6 +13,8 @@ pub enum LeptosConfigError {
     ConfigError(String),
     #[error("Config Error: {0}")]
     EnvVarError(String),
+    #[error("Config Error: invalid value for `{key}`: {message}")]
+    InvalidValue { key: &'static str, message: String },
 }
 impl From<config::ConfigError> for LeptosConfigError {
     fn from(e: config::ConfigError) -> Self {
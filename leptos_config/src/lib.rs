@@ -1,6 +1,8 @@
 #![forbid(unsafe_code)]
 
 pub mod errors;
+pub mod loader;
+pub mod reload;
 
 use crate::errors::LeptosConfigError;
 use config::{Case, Config, File, FileFormat};
@@ -92,6 +94,12 @@ pub struct LeptosOptions {
     #[builder(default, setter(strip_option))]
     #[serde(default)]
     pub server_fn_prefix: Option<String>,
+    /// Serves the server functions that use the default prefix at this path instead, like
+    /// `/api/v2`. Unlike the prefix, this is chosen when the server starts, and is passed on to
+    /// the client when it is hydrated, so neither needs to be recompiled to change it.
+    #[builder(default, setter(strip_option, into))]
+    #[serde(default)]
+    pub server_fn_mount: Option<String>,
     /// Whether to disable appending the server functions' hashes to the end of their API names.
     ///
     /// This is useful when an app's client side needs a stable server API. For example, shipping
@@ -118,6 +126,21 @@ pub struct LeptosOptions {
     #[builder(default)]
     #[serde(default)]
     pub server_fn_mod_path: bool,
+    /// The path the app is served under, like `/tools/myapp` for an app hosted at
+    /// `https://example.com/tools/myapp/`. Defaults to the root of the site.
+    #[builder(default, setter(strip_option, into))]
+    #[serde(default)]
+    pub base_path: Option<String>,
+    /// The public URL of the site, like `https://example.com`, used to build the absolute URLs
+    /// of the sitemap that the integrations can serve. Defaults to `http://` and the `site_addr`.
+    #[builder(default, setter(strip_option, into))]
+    #[serde(default)]
+    pub site_url: Option<String>,
+    /// Path prefixes that crawlers are asked not to visit in the `robots.txt` that the
+    /// integrations can serve. Can be set with a comma-separated list in an environment variable.
+    #[builder(default)]
+    #[serde(default, deserialize_with = "deserialize_list")]
+    pub robots_disallow: Vec<String>,
 }
 
 impl LeptosOptions {
@@ -161,13 +184,68 @@ impl LeptosOptions {
                 .into(),
             hash_files: env_w_default("LEPTOS_HASH_FILES", "false")?.parse()?,
             server_fn_prefix: env_wo_default("SERVER_FN_PREFIX")?,
+            server_fn_mount: env_wo_default("LEPTOS_SERVER_FN_MOUNT")?,
             disable_server_fn_hash: env_wo_default("DISABLE_SERVER_FN_HASH")?
                 .is_some(),
             server_fn_mod_path: env_wo_default("SERVER_FN_MOD_PATH")?.is_some(),
+            base_path: env_wo_default("LEPTOS_BASE_PATH")?,
+            site_url: env_wo_default("LEPTOS_SITE_URL")?,
+            robots_disallow: env_wo_default("LEPTOS_ROBOTS_DISALLOW")?
+                .map(|list| split_list(&list))
+                .unwrap_or_default(),
         })
     }
 }
 
+impl LeptosOptions {
+    /// Checks the values that can't be caught while parsing the options, but
+    /// that the integrations rely on.
+    ///
+    /// The error names the offending key as it is written in `Cargo.toml`.
+    pub fn validate(&self) -> Result<(), LeptosConfigError> {
+        let invalid = |key, message: &str| {
+            Err(LeptosConfigError::InvalidValue {
+                key,
+                message: message.to_string(),
+            })
+        };
+
+        if self.output_name.is_empty() {
+            return invalid("output-name", "must not be empty");
+        }
+        if self.site_pkg_dir.is_empty() {
+            return invalid("site-pkg-dir", "must not be empty");
+        }
+        let ports = [
+            ("reload-port", Some(self.reload_port)),
+            ("reload-external-port", self.reload_external_port),
+        ];
+        for (key, port) in ports {
+            if port.is_some_and(|port| port == 0 || port > u16::MAX as u32) {
+                return invalid(key, "must be a port between 1 and 65535");
+            }
+        }
+        let paths = [
+            ("server-fn-prefix", &self.server_fn_prefix),
+            ("server-fn-mount", &self.server_fn_mount),
+        ];
+        for (key, path) in paths {
+            if path.as_ref().is_some_and(|path| !path.starts_with('/')) {
+                return invalid(key, "must start with `/`");
+            }
+        }
+        if let Some(url) = &self.site_url {
+            if !url.starts_with("http://") && !url.starts_with("https://") {
+                return invalid(
+                    "site-url",
+                    "must start with `http://` or `https://`",
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
 fn default_site_root() -> Arc<str> {
     ".".into()
 }
@@ -200,6 +278,33 @@ fn default_hash_files() -> bool {
     false
 }
 
+/// Deserializes a list from either a sequence or a comma-separated string, which is how lists
+/// are set with environment variables.
+fn deserialize_list<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(serde::Deserialize)]
+    #[serde(untagged)]
+    enum List {
+        Separated(String),
+        Items(Vec<String>),
+    }
+
+    Ok(match serde::Deserialize::deserialize(deserializer)? {
+        List::Separated(list) => split_list(&list),
+        List::Items(items) => items,
+    })
+}
+
+fn split_list(list: &str) -> Vec<String> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
 fn env_wo_default(key: &str) -> Result<Option<String>, LeptosConfigError> {
     match std::env::var(key) {
         Ok(val) => Ok(Some(val)),
@@ -338,6 +443,26 @@ impl TryFrom<String> for ReloadWSProtocol {
 pub fn get_config_from_str(
     text: &str,
 ) -> Result<LeptosOptions, LeptosConfigError> {
+    let toml = metadata_toml(text)?;
+    let settings = Config::builder()
+        // Read the "default" configuration file
+        .add_source(File::from_str(&toml, FileFormat::Toml))
+        // Layer on the environment-specific values.
+        // Add in settings from environment variables (with a prefix of LEPTOS)
+        // E.g. `LEPTOS_RELOAD_PORT=5001 would set `LeptosOptions.reload_port`
+        .add_source(
+            config::Environment::with_prefix("LEPTOS")
+                .convert_case(Case::Kebab),
+        )
+        .build()?;
+
+    settings
+        .try_deserialize()
+        .map_err(|e| LeptosConfigError::ConfigError(e.to_string()))
+}
+
+/// Extracts the Leptos metadata section of a Cargo.toml as a TOML document of its own.
+fn metadata_toml(text: &str) -> Result<String, LeptosConfigError> {
     let re: Regex = Regex::new(r"(?m)^\[package.metadata.leptos\]").unwrap();
     let re_workspace: Regex =
         Regex::new(r"(?m)^\[\[workspace.metadata.leptos\]\]").unwrap();
@@ -362,22 +487,7 @@ pub fn get_config_from_str(
     let newlines = text[..start].matches('\n').count();
     let input = "\n".repeat(newlines) + &text[start..];
     // so the settings will be interpreted as root level settings
-    let toml = input.replace(metadata_name, "");
-    let settings = Config::builder()
-        // Read the "default" configuration file
-        .add_source(File::from_str(&toml, FileFormat::Toml))
-        // Layer on the environment-specific values.
-        // Add in settings from environment variables (with a prefix of LEPTOS)
-        // E.g. `LEPTOS_RELOAD_PORT=5001 would set `LeptosOptions.reload_port`
-        .add_source(
-            config::Environment::with_prefix("LEPTOS")
-                .convert_case(Case::Kebab),
-        )
-        .build()?;
-
-    settings
-        .try_deserialize()
-        .map_err(|e| LeptosConfigError::ConfigError(e.to_string()))
+    Ok(input.replace(metadata_name, ""))
 }
 
 /// Loads [LeptosOptions] from a Cargo.toml with layered overrides. If an env var is specified, like `LEPTOS_ENV`,
@@ -0,0 +1,139 @@
+//! Building [`LeptosOptions`] from several layers of configuration.
+//!
+//! [`ConfigLoader`] combines, from lowest to highest precedence,
+//!
+//! 1. the defaults of each option,
+//! 2. the `[package.metadata.leptos]` sections of one or more `Cargo.toml` files, with later files
+//!    overriding earlier ones,
+//! 3. `LEPTOS_`-prefixed environment variables, like `LEPTOS_SITE_ROOT`,
+//! 4. overrides set in code,
+//!
+//! and then [validates](LeptosOptions::validate) the result, so that a misconfigured app fails
+//! when it starts with an error naming the offending key, instead of somewhere in the
+//! integrations:
+//!
+//! ```rust,no_run
+//! use leptos_config::loader::ConfigLoader;
+//!
+//! let conf = ConfigLoader::new()
+//!     .file("Cargo.toml")
+//!     .override_with(|options| options.site_root = "dist".into())
+//!     .load()
+//!     .unwrap();
+//! ```
+
+use crate::{
+    errors::LeptosConfigError, metadata_toml, ConfFile, LeptosOptions,
+};
+use config::{Case, Config, File, FileFormat};
+use std::{fs, path::PathBuf};
+
+type Override = Box<dyn FnOnce(&mut LeptosOptions)>;
+
+enum Layer {
+    Path(PathBuf),
+    Text(String),
+}
+
+/// Builds [`LeptosOptions`] from layered sources. See the [module docs](self).
+#[must_use]
+pub struct ConfigLoader {
+    files: Vec<Layer>,
+    env: bool,
+    overrides: Vec<Override>,
+}
+
+impl Default for ConfigLoader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for ConfigLoader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConfigLoader")
+            .field("files", &self.files.len())
+            .field("env", &self.env)
+            .field("overrides", &self.overrides.len())
+            .finish()
+    }
+}
+
+impl ConfigLoader {
+    /// Starts from the defaults, overridden by the environment.
+    pub fn new() -> Self {
+        Self {
+            files: Vec::new(),
+            env: true,
+            overrides: Vec::new(),
+        }
+    }
+
+    /// Adds the Leptos metadata of the `Cargo.toml` at `path`.
+    pub fn file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.files.push(Layer::Path(path.into()));
+        self
+    }
+
+    /// Adds the Leptos metadata of a `Cargo.toml` with these contents.
+    pub fn toml(mut self, text: impl Into<String>) -> Self {
+        self.files.push(Layer::Text(text.into()));
+        self
+    }
+
+    /// Ignores the environment variables.
+    pub fn without_env(mut self) -> Self {
+        self.env = false;
+        self
+    }
+
+    /// Changes the options after all other layers have been applied.
+    pub fn override_with(
+        mut self,
+        f: impl FnOnce(&mut LeptosOptions) + 'static,
+    ) -> Self {
+        self.overrides.push(Box::new(f));
+        self
+    }
+
+    /// Combines the layers into [`LeptosOptions`] and validates them.
+    pub fn build(self) -> Result<LeptosOptions, LeptosConfigError> {
+        // lets the output name be set by an override, and then be validated
+        let mut builder = Config::builder().set_default("output-name", "")?;
+        for layer in self.files {
+            let text = match layer {
+                Layer::Path(path) => fs::read_to_string(path)
+                    .map_err(|_| LeptosConfigError::ConfigNotFound)?,
+                Layer::Text(text) => text,
+            };
+            builder = builder.add_source(File::from_str(
+                &metadata_toml(&text)?,
+                FileFormat::Toml,
+            ));
+        }
+        if self.env {
+            builder = builder.add_source(
+                config::Environment::with_prefix("LEPTOS")
+                    .convert_case(Case::Kebab),
+            );
+        }
+
+        let mut options: LeptosOptions = builder
+            .build()?
+            .try_deserialize()
+            .map_err(|e| LeptosConfigError::ConfigError(e.to_string()))?;
+        for f in self.overrides {
+            f(&mut options);
+        }
+        options.validate()?;
+        Ok(options)
+    }
+
+    /// Like [`build`](Self::build), but returns the options in a [`ConfFile`], like
+    /// [`get_configuration`](crate::get_configuration).
+    pub fn load(self) -> Result<ConfFile, LeptosConfigError> {
+        Ok(ConfFile {
+            leptos_options: self.build()?,
+        })
+    }
+}
@@ -0,0 +1,242 @@
+//! [`LeptosOptions`] that can be changed while the server is running.
+//!
+//! [`get_configuration`] reads the options once, when the server starts. Long-running servers
+//! that need to rotate some settings can keep them in [`LiveOptions`] instead, and read the
+//! file or the environment again with [`LiveOptions::reload`], for example on `SIGHUP`. The
+//! parts of the server that depend on a setting [`subscribe`](LiveOptions::subscribe) to be
+//! told when it changes:
+//!
+//! ```rust,no_run
+//! use leptos_config::reload::LiveOptions;
+//!
+//! let options = LiveOptions::from_configuration(Some("Cargo.toml")).unwrap();
+//! options
+//!     .subscribe(|change| {
+//!         if change.changed("site_root") {
+//!             println!("now serving files from {}", change.new.site_root);
+//!         }
+//!     })
+//!     .forget();
+//!
+//! // later
+//! options.reload().unwrap();
+//! ```
+//!
+//! Settings that are only used while the server starts, like the `site_addr` it listens on, are
+//! updated like any other, but only take effect once it is restarted.
+
+use crate::{errors::LeptosConfigError, get_configuration, LeptosOptions};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, Mutex, RwLock, Weak,
+};
+
+type Listener = Arc<dyn Fn(&OptionsChange) + Send + Sync>;
+
+/// Shared, reloadable [`LeptosOptions`].
+///
+/// Cloning this is cheap, and all clones see the same options.
+#[derive(Clone)]
+pub struct LiveOptions {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    // the path passed to `get_configuration`
+    source: Option<String>,
+    current: RwLock<Arc<LeptosOptions>>,
+    listeners: Mutex<Vec<(usize, Listener)>>,
+    next_id: AtomicUsize,
+}
+
+impl std::fmt::Debug for LiveOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LiveOptions")
+            .field("source", &self.inner.source)
+            .field("current", &self.get())
+            .finish_non_exhaustive()
+    }
+}
+
+impl LiveOptions {
+    /// Reads the options like [`get_configuration`], and remembers where they came from so that
+    /// [`reload`](Self::reload) reads them from the same place.
+    pub fn from_configuration(
+        path: Option<&str>,
+    ) -> Result<Self, LeptosConfigError> {
+        let options = get_configuration(path)?.leptos_options;
+        Ok(Self::new(options, path.map(str::to_string)))
+    }
+
+    /// Wraps options that were read from `source`, a `Cargo.toml` file, or from the environment if
+    /// it is `None`.
+    pub fn new(options: LeptosOptions, source: Option<String>) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                source,
+                current: RwLock::new(Arc::new(options)),
+                listeners: Mutex::new(Vec::new()),
+                next_id: AtomicUsize::new(0),
+            }),
+        }
+    }
+
+    /// Returns the current options.
+    pub fn get(&self) -> Arc<LeptosOptions> {
+        self.inner
+            .current
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Reads the options again from where they were first read, and applies them with
+    /// [`set`](Self::set).
+    ///
+    /// If they can't be read, the current options are kept and the error is returned.
+    pub fn reload(&self) -> Result<OptionsChange, LeptosConfigError> {
+        let options = get_configuration(self.inner.source.as_deref())?;
+        Ok(self.set(options.leptos_options))
+    }
+
+    /// Replaces the current options, and notifies the subscribers if any of them changed.
+    pub fn set(&self, options: LeptosOptions) -> OptionsChange {
+        let new = Arc::new(options);
+        let old = {
+            let mut current = self
+                .inner
+                .current
+                .write()
+                .unwrap_or_else(|e| e.into_inner());
+            std::mem::replace(&mut *current, Arc::clone(&new))
+        };
+        let change = OptionsChange {
+            changed: changed_fields(&old, &new),
+            old,
+            new,
+        };
+
+        if !change.changed.is_empty() {
+            // called without holding the lock, so that listeners can subscribe or unsubscribe
+            let listeners = self
+                .inner
+                .listeners
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .iter()
+                .map(|(_, listener)| Arc::clone(listener))
+                .collect::<Vec<_>>();
+            for listener in listeners {
+                listener(&change);
+            }
+        }
+        change
+    }
+
+    /// Calls `listener` whenever the options change, until the returned [`Subscription`] is
+    /// dropped.
+    pub fn subscribe(
+        &self,
+        listener: impl Fn(&OptionsChange) + Send + Sync + 'static,
+    ) -> Subscription {
+        let id = self.inner.next_id.fetch_add(1, Ordering::Relaxed);
+        self.inner
+            .listeners
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push((id, Arc::new(listener)));
+        Subscription {
+            inner: Arc::downgrade(&self.inner),
+            id,
+        }
+    }
+}
+
+/// A change of the options applied by [`LiveOptions::set`] or [`LiveOptions::reload`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct OptionsChange {
+    /// The options before the change.
+    pub old: Arc<LeptosOptions>,
+    /// The options after the change.
+    pub new: Arc<LeptosOptions>,
+    /// The names of the fields of [`LeptosOptions`] that changed, like `site_root`.
+    pub changed: Vec<&'static str>,
+}
+
+impl OptionsChange {
+    /// Whether the field of [`LeptosOptions`] with this name changed.
+    pub fn changed(&self, field: &str) -> bool {
+        self.changed.contains(&field)
+    }
+}
+
+/// Keeps a listener added with [`LiveOptions::subscribe`] subscribed until it is dropped.
+#[must_use = "Dropping a `Subscription` unsubscribes the listener. You should \
+              either call `.forget()` to keep it subscribed permanently, or \
+              store the `Subscription` somewhere and drop it when you'd like \
+              to unsubscribe."]
+#[derive(Debug)]
+pub struct Subscription {
+    inner: Weak<Inner>,
+    id: usize,
+}
+
+impl Subscription {
+    /// Keeps the listener subscribed for as long as the options exist.
+    pub fn forget(self) {
+        std::mem::forget(self);
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        if let Some(inner) = self.inner.upgrade() {
+            inner
+                .listeners
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .retain(|(id, _)| *id != self.id);
+        }
+    }
+}
+
+fn changed_fields(
+    old: &LeptosOptions,
+    new: &LeptosOptions,
+) -> Vec<&'static str> {
+    macro_rules! compare {
+        ($($field:ident),* $(,)?) => {{
+            // destructures every field, so that new ones can't be left out
+            let LeptosOptions { $($field),* } = old;
+            let mut changed = Vec::new();
+            $(
+                if *$field != new.$field {
+                    changed.push(stringify!($field));
+                }
+            )*
+            changed
+        }};
+    }
+
+    compare!(
+        output_name,
+        site_root,
+        site_pkg_dir,
+        env,
+        site_addr,
+        reload_port,
+        reload_external_port,
+        reload_ws_protocol,
+        not_found_path,
+        hash_file,
+        hash_files,
+        server_fn_prefix,
+        server_fn_mount,
+        disable_server_fn_hash,
+        server_fn_mod_path,
+        base_path,
+        site_url,
+        robots_disallow,
+    )
+}
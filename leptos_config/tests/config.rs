@@ -275,3 +275,172 @@ fn environment_variable_override() {
     assert_eq!(config.reload_port, 8082);
     assert_eq!(config.reload_external_port, Some(8082));
 }
+
+#[test]
+fn crawler_options_from_str() {
+    const CONTENT: &str = r#"\
+[package.metadata.leptos]
+output-name = "app-test"
+site-url = "https://example.com"
+robots-disallow = ["/admin", "/api"]
+"#;
+
+    let config = temp_env::with_vars_unset(
+        ["LEPTOS_SITE_URL", "LEPTOS_ROBOTS_DISALLOW"],
+        || get_config_from_str(CONTENT).unwrap(),
+    );
+    assert_eq!(config.site_url.as_deref(), Some("https://example.com"));
+    assert_eq!(config.robots_disallow, ["/admin", "/api"]);
+
+    let config = temp_env::with_var(
+        "LEPTOS_ROBOTS_DISALLOW",
+        Some("/drafts, /tmp"),
+        || get_config_from_str(CONTENT).unwrap(),
+    );
+    assert_eq!(config.robots_disallow, ["/drafts", "/tmp"]);
+}
+
+#[test]
+fn server_fn_mount_from_env() {
+    const CONTENT: &str = r#"\
+[package.metadata.leptos]
+output-name = "app-test"
+server-fn-mount = "/api/v1"
+"#;
+
+    let config = temp_env::with_var_unset("LEPTOS_SERVER_FN_MOUNT", || {
+        get_config_from_str(CONTENT).unwrap()
+    });
+    assert_eq!(config.server_fn_mount.as_deref(), Some("/api/v1"));
+
+    let config =
+        temp_env::with_var("LEPTOS_SERVER_FN_MOUNT", Some("/api/v2"), || {
+            get_config_from_str(CONTENT).unwrap()
+        });
+    assert_eq!(config.server_fn_mount.as_deref(), Some("/api/v2"));
+}
+
+#[test]
+fn live_options_reload_and_notify() {
+    use leptos_config::reload::LiveOptions;
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    let cargo_tmp = NamedTempFile::new().unwrap();
+    let path_s = cargo_tmp.path().to_string_lossy().to_string();
+    let write = |port: u32| {
+        let mut output = File::create(&cargo_tmp).unwrap();
+        write!(
+            output,
+            "[package.metadata.leptos]\noutput-name = \"app-test\"\nreload-port = {port}\n"
+        )
+        .unwrap();
+    };
+
+    temp_env::with_vars(
+        [
+            ("LEPTOS_OUTPUT_NAME", None::<&str>),
+            ("LEPTOS_RELOAD_PORT", None::<&str>),
+        ],
+        || {
+            write(8080);
+            let options =
+                LiveOptions::from_configuration(Some(&path_s)).unwrap();
+            assert_eq!(options.get().reload_port, 8080);
+
+            let calls = Arc::new(AtomicUsize::new(0));
+            let subscription = options.subscribe({
+                let calls = Arc::clone(&calls);
+                move |change| {
+                    assert!(change.changed("reload_port"));
+                    calls.fetch_add(1, Ordering::Relaxed);
+                }
+            });
+
+            write(9090);
+            let change = options.reload().unwrap();
+            assert_eq!(change.changed, ["reload_port"]);
+            assert_eq!(options.get().reload_port, 9090);
+            assert_eq!(calls.load(Ordering::Relaxed), 1);
+
+            // nothing changed
+            assert!(options.reload().unwrap().changed.is_empty());
+            assert_eq!(calls.load(Ordering::Relaxed), 1);
+
+            drop(subscription);
+            write(7070);
+            options.reload().unwrap();
+            assert_eq!(calls.load(Ordering::Relaxed), 1);
+
+            // invalid files keep the current options
+            std::fs::write(&cargo_tmp, CARGO_TOML_CONTENT_ERR).unwrap();
+            assert!(options.reload().is_err());
+            assert_eq!(options.get().reload_port, 7070);
+        },
+    );
+}
+
+#[test]
+fn config_loader_layers_and_validates() {
+    use leptos_config::{errors::LeptosConfigError, loader::ConfigLoader};
+
+    const BASE: &str = r#"
+[package.metadata.leptos]
+output-name = "app-test"
+site-root = "base/site"
+reload-port = 8080
+"#;
+    const LOCAL: &str = r#"
+[package.metadata.leptos]
+site-root = "local/site"
+"#;
+
+    temp_env::with_vars(
+        [
+            ("LEPTOS_OUTPUT_NAME", None::<&str>),
+            ("LEPTOS_SITE_ROOT", None::<&str>),
+            ("LEPTOS_RELOAD_PORT", Some("9090")),
+        ],
+        || {
+            let options =
+                ConfigLoader::new().toml(BASE).toml(LOCAL).build().unwrap();
+            assert_eq!(options.output_name.as_ref(), "app-test");
+            assert_eq!(options.site_root.as_ref(), "local/site");
+            assert_eq!(options.reload_port, 9090);
+            assert_eq!(options.site_pkg_dir.as_ref(), "pkg");
+
+            let options = ConfigLoader::new()
+                .toml(BASE)
+                .without_env()
+                .override_with(|options| options.site_root = "dist".into())
+                .build()
+                .unwrap();
+            assert_eq!(options.site_root.as_ref(), "dist");
+            assert_eq!(options.reload_port, 8080);
+
+            let err = ConfigLoader::new().without_env().build().unwrap_err();
+            assert!(matches!(
+                err,
+                LeptosConfigError::InvalidValue {
+                    key: "output-name",
+                    ..
+                }
+            ));
+
+            let err = ConfigLoader::new()
+                .toml(BASE)
+                .override_with(|options| {
+                    options.server_fn_mount = Some("api/v2".into())
+                })
+                .build()
+                .unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "Config Error: invalid value for `server-fn-mount`: must \
+                 start with `/`"
+            );
+        },
+    );
+}
@@ -271,13 +271,70 @@ pub fn queue_microtask(task: impl FnOnce() + 'static) {
 
 /// Handle that is generated by [set_timeout_with_handle] and can be used to clear the timeout.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
-pub struct TimeoutHandle(i32);
+pub struct TimeoutHandle(Timer);
 
 impl TimeoutHandle {
     /// Cancels the timeout to which this refers.
     /// See [`clearTimeout()`](https://developer.mozilla.org/en-US/docs/Web/API/clearTimeout)
     pub fn clear(&self) {
-        window().clear_timeout_with_handle(self.0);
+        match self.0 {
+            Timer::Browser(handle) => {
+                window().clear_timeout_with_handle(handle)
+            }
+            Timer::Task(id) => executor_timers::clear(id),
+        }
+    }
+}
+
+/// A timer started by [`set_timeout_with_handle`] or [`set_interval_with_handle`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+enum Timer {
+    /// A browser timer, by its handle.
+    Browser(i32),
+    /// A task on the [`any_spawner`] executor, by its id in [`executor_timers`].
+    Task(u64),
+}
+
+/// Whether timers run as tasks with [`any_spawner::time`] rather than with the browser's
+/// timers: always outside the browser, and in the browser with a virtual-time executor, so that
+/// tests can drive them by advancing the clock.
+fn uses_executor_timers() -> bool {
+    !cfg!(target_family = "wasm") || any_spawner::time::is_virtual()
+}
+
+/// Timers that run as tasks on the [`any_spawner`] executor, and can be cancelled by their id.
+mod executor_timers {
+    use any_spawner::Executor;
+    use futures::future::{abortable, AbortHandle};
+    use std::{
+        cell::{Cell, RefCell},
+        collections::HashMap,
+        future::Future,
+    };
+
+    thread_local! {
+        static RUNNING: RefCell<HashMap<u64, AbortHandle>> =
+            RefCell::new(HashMap::new());
+        static NEXT_ID: Cell<u64> = const { Cell::new(0) };
+    }
+
+    pub(super) fn spawn(timer: impl Future<Output = ()> + 'static) -> u64 {
+        let id = NEXT_ID.with(|next| next.replace(next.get() + 1));
+        let (timer, handle) = abortable(timer);
+        RUNNING.with(|running| running.borrow_mut().insert(id, handle));
+        Executor::spawn_local(async move {
+            if timer.await.is_ok() {
+                RUNNING.with(|running| running.borrow_mut().remove(&id));
+            }
+        });
+        id
+    }
+
+    pub(super) fn clear(id: u64) {
+        let handle = RUNNING.with(|running| running.borrow_mut().remove(&id));
+        if let Some(handle) = handle {
+            handle.abort();
+        }
     }
 }
 
@@ -298,6 +355,9 @@ pub fn set_timeout(cb: impl FnOnce() + 'static, duration: Duration) {
 /// Executes the given function after the given duration of time has passed, returning a cancelable handle.
 /// [`setTimeout()`](https://developer.mozilla.org/en-US/docs/Web/API/setTimeout).
 ///
+/// Outside the browser, or with [`Executor::init_virtual_time`](any_spawner::Executor::init_virtual_time),
+/// the timer runs as a task with [`any_spawner::time`] instead.
+///
 /// ### Note about Context
 ///
 /// The callback is called outside of the reactive ownership tree. This means that it does not have access to context via [`use_context`](reactive_graph::owner::use_context). If you want to use context inside the callback, you should either call `use_context` in the body of the component, and move the value into the callback, or access the current owner inside the component body using [`Owner::current`](reactive_graph::owner::Owner::current) and reestablish it in the callback with [`Owner::with`](reactive_graph::owner::Owner::with).
@@ -324,6 +384,14 @@ pub fn set_timeout_with_handle(
         cb();
     };
 
+    if uses_executor_timers() {
+        let id = executor_timers::spawn(async move {
+            any_spawner::time::sleep(duration).await;
+            cb();
+        });
+        return Ok(TimeoutHandle(Timer::Task(id)));
+    }
+
     #[inline(never)]
     fn st(cb: JsValue, duration: Duration) -> Result<TimeoutHandle, JsValue> {
         window()
@@ -331,7 +399,7 @@ pub fn set_timeout_with_handle(
                 cb.as_ref().unchecked_ref(),
                 duration.as_millis().try_into().unwrap_throw(),
             )
-            .map(TimeoutHandle)
+            .map(|handle| TimeoutHandle(Timer::Browser(handle)))
     }
 
     st(closure_once(cb), duration)
@@ -416,13 +484,18 @@ pub fn debounce<T: 'static>(
 
 /// Handle that is generated by [set_interval] and can be used to clear the interval.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
-pub struct IntervalHandle(i32);
+pub struct IntervalHandle(Timer);
 
 impl IntervalHandle {
     /// Cancels the repeating event to which this refers.
     /// See [`clearInterval()`](https://developer.mozilla.org/en-US/docs/Web/API/clearInterval)
     pub fn clear(&self) {
-        window().clear_interval_with_handle(self.0);
+        match self.0 {
+            Timer::Browser(handle) => {
+                window().clear_interval_with_handle(handle)
+            }
+            Timer::Task(id) => executor_timers::clear(id),
+        }
     }
 }
 
@@ -444,6 +517,9 @@ pub fn set_interval(cb: impl Fn() + 'static, duration: Duration) {
 /// returning a cancelable handle.
 /// See [`setInterval()`](https://developer.mozilla.org/en-US/docs/Web/API/setInterval).
 ///
+/// Outside the browser, or with [`Executor::init_virtual_time`](any_spawner::Executor::init_virtual_time),
+/// the interval runs as a task with [`any_spawner::time`] instead.
+///
 /// ### Note about Context
 ///
 /// The callback is called outside of the reactive ownership tree. This means that it does not have access to context via [`use_context`](reactive_graph::owner::use_context). If you want to use context inside the callback, you should either call `use_context` in the body of the component, and move the value into the callback, or access the current owner inside the component body using [`Owner::current`](reactive_graph::owner::Owner::current) and reestablish it in the callback with [`Owner::with`](reactive_graph::owner::Owner::with).
@@ -469,6 +545,18 @@ pub fn set_interval_with_handle(
         cb();
     };
 
+    if uses_executor_timers() {
+        let id = executor_timers::spawn(async move {
+            use futures::StreamExt;
+
+            let mut interval = any_spawner::time::interval(duration);
+            while interval.next().await.is_some() {
+                cb();
+            }
+        });
+        return Ok(IntervalHandle(Timer::Task(id)));
+    }
+
     #[inline(never)]
     fn si(
         cb: Box<dyn Fn()>,
@@ -481,7 +569,7 @@ pub fn set_interval_with_handle(
                 cb.as_ref().unchecked_ref(),
                 duration.as_millis().try_into().unwrap_throw(),
             )
-            .map(IntervalHandle)
+            .map(|handle| IntervalHandle(Timer::Browser(handle)))
     }
 
     si(Box::new(cb), duration)
@@ -501,6 +589,30 @@ pub fn set_interval_with_handle(
 pub fn window_event_listener_untyped(
     event_name: &str,
     cb: impl Fn(web_sys::Event) + 'static,
+) -> WindowListenerHandle {
+    global_event_listener(GlobalTarget::Window, event_name, None, cb)
+}
+
+#[derive(Debug, Clone, Copy)]
+enum GlobalTarget {
+    Window,
+    Document,
+}
+
+impl GlobalTarget {
+    fn get(self) -> web_sys::EventTarget {
+        match self {
+            GlobalTarget::Window => window().into(),
+            GlobalTarget::Document => document().into(),
+        }
+    }
+}
+
+fn global_event_listener(
+    target: GlobalTarget,
+    event_name: &str,
+    options: Option<web_sys::AddEventListenerOptions>,
+    cb: impl Fn(web_sys::Event) + 'static,
 ) -> WindowListenerHandle {
     #[cfg(debug_assertions)]
     let cb = move |e| {
@@ -515,33 +627,48 @@ pub fn window_event_listener_untyped(
         cb(e);
     };
 
-    if !is_server() {
-        #[inline(never)]
-        fn wel(
-            cb: Box<dyn FnMut(web_sys::Event)>,
-            event_name: &str,
-        ) -> WindowListenerHandle {
-            let cb = Closure::wrap(cb).into_js_value();
-            _ = window().add_event_listener_with_callback(
-                event_name,
-                cb.unchecked_ref(),
-            );
-            let event_name = event_name.to_string();
-            let cb = SendWrapper::new(cb);
-            WindowListenerHandle(Box::new(move || {
-                _ = window().remove_event_listener_with_callback(
-                    &event_name,
-                    cb.unchecked_ref(),
-                );
-            }))
-        }
-
-        wel(Box::new(cb), event_name)
-    } else {
+    if is_server() {
         WindowListenerHandle(Box::new(|| ()))
+    } else {
+        add_global_event_listener(target, event_name, options, Box::new(cb))
     }
 }
 
+#[inline(never)]
+fn add_global_event_listener(
+    target: GlobalTarget,
+    event_name: &str,
+    options: Option<web_sys::AddEventListenerOptions>,
+    cb: Box<dyn FnMut(web_sys::Event)>,
+) -> WindowListenerHandle {
+    let cb = Closure::wrap(cb).into_js_value();
+    // a capturing listener can only be removed by passing `capture` again
+    let capture = options
+        .as_ref()
+        .is_some_and(|options| options.get_capture().unwrap_or(false));
+    _ = match &options {
+        Some(options) => target
+            .get()
+            .add_event_listener_with_callback_and_add_event_listener_options(
+                event_name,
+                cb.unchecked_ref(),
+                options,
+            ),
+        None => target
+            .get()
+            .add_event_listener_with_callback(event_name, cb.unchecked_ref()),
+    };
+    let event_name = event_name.to_string();
+    let cb = SendWrapper::new(cb);
+    WindowListenerHandle(Box::new(move || {
+        _ = target.get().remove_event_listener_with_callback_and_bool(
+            &event_name,
+            cb.unchecked_ref(),
+            capture,
+        );
+    }))
+}
+
 /// Creates a window event listener from a typed event, returning a
 /// cancelable handle.
 /// ```
@@ -572,9 +699,79 @@ pub fn window_event_listener<E: EventDescriptor + 'static>(
 where
     E::EventType: JsCast,
 {
-    window_event_listener_untyped(&event.name(), move |e| {
-        cb(e.unchecked_into::<E::EventType>())
-    })
+    global_event_listener(
+        GlobalTarget::Window,
+        &event.name(),
+        event.listener_options(),
+        move |e| cb(e.unchecked_into::<E::EventType>()),
+    )
+}
+
+/// Creates a document event listener from a typed event, returning a cancelable handle.
+///
+/// Like [`window_event_listener`], this respects the [`capture`](tachys::html::event::capture),
+/// [`passive`](tachys::html::event::passive) and [`once`](tachys::html::event::once) wrappers.
+///
+/// ### Note about Context
+///
+/// The callback is called outside of the reactive ownership tree. This means that it does not have access to context via [`use_context`](reactive_graph::owner::use_context). If you want to use context inside the callback, you should either call `use_context` in the body of the component, and move the value into the callback, or access the current owner inside the component body using [`Owner::current`](reactive_graph::owner::Owner::current) and reestablish it in the callback with [`Owner::with`](reactive_graph::owner::Owner::with).
+pub fn document_event_listener<E: EventDescriptor + 'static>(
+    event: E,
+    cb: impl Fn(E::EventType) + 'static,
+) -> WindowListenerHandle
+where
+    E::EventType: JsCast,
+{
+    global_event_listener(
+        GlobalTarget::Document,
+        &event.name(),
+        event.listener_options(),
+        move |e| cb(e.unchecked_into::<E::EventType>()),
+    )
+}
+
+/// Adds a typed event listener to the `Window`, which is removed when the current reactive owner
+/// is cleaned up.
+///
+/// Wrap the event with [`passive`](tachys::html::event::passive),
+/// [`capture`](tachys::html::event::capture) or [`once`](tachys::html::event::once) to set the
+/// options of the listener. While rendering on the server, this does nothing.
+/// ```
+/// use leptos::{
+///     ev, leptos_dom::helpers::use_window_event_listener, prelude::*,
+/// };
+///
+/// #[component]
+/// fn ScrollPosition() -> impl IntoView {
+///     let (y, set_y) = signal(0.0);
+///     use_window_event_listener(ev::passive(ev::scroll), move |_| {
+///         set_y.set(window().scroll_y().unwrap_or_default());
+///     });
+///     view! { <p>"Scrolled " {y} "px"</p> }
+/// }
+/// ```
+pub fn use_window_event_listener<E: EventDescriptor + 'static>(
+    event: E,
+    cb: impl Fn(E::EventType) + 'static,
+) where
+    E::EventType: JsCast,
+{
+    let handle = window_event_listener(event, cb);
+    Owner::on_cleanup(move || handle.remove());
+}
+
+/// Adds a typed event listener to the `Document`, which is removed when the current reactive
+/// owner is cleaned up.
+///
+/// See [`use_window_event_listener`].
+pub fn use_document_event_listener<E: EventDescriptor + 'static>(
+    event: E,
+    cb: impl Fn(E::EventType) + 'static,
+) where
+    E::EventType: JsCast,
+{
+    let handle = document_event_listener(event, cb);
+    Owner::on_cleanup(move || handle.remove());
 }
 
 /// A handle that can be called to remove a global event listener.
@@ -594,6 +791,10 @@ impl WindowListenerHandle {
 }
 
 fn is_server() -> bool {
+    // there is no DOM to listen to outside the browser, even when nothing is being rendered
+    if !cfg!(target_arch = "wasm32") {
+        return true;
+    }
     #[cfg(feature = "hydration")]
     {
         Owner::current_shared_context()
@@ -13,6 +13,54 @@ impl LNode {
         self.diff_at(other, &[], &old_children)
     }
 
+    /// Whether this template and `other` only differ in their literal text,
+    /// attributes and classes, so that patching them in place is all it takes
+    /// to update the page.
+    #[must_use]
+    pub fn differs_only_in_literals(&self, other: &LNode) -> bool {
+        self.without_literals() == other.without_literals()
+    }
+
+    fn without_literals(&self) -> LNode {
+        match self {
+            LNode::Fragment(children) => LNode::Fragment(
+                children.iter().map(LNode::without_literals).collect(),
+            ),
+            LNode::Text(_) => LNode::Text(String::new()),
+            LNode::Element {
+                name,
+                attrs,
+                children,
+            } => LNode::Element {
+                name: name.to_owned(),
+                attrs: attrs
+                    .iter()
+                    .filter(|(_, value)| {
+                        matches!(value, LAttributeValue::Dynamic(_))
+                    })
+                    .cloned()
+                    .collect(),
+                children: children
+                    .iter()
+                    .map(LNode::without_literals)
+                    .collect(),
+            },
+            LNode::Component {
+                name,
+                props,
+                children,
+            } => LNode::Component {
+                name: name.to_owned(),
+                props: props.to_owned(),
+                children: children
+                    .iter()
+                    .map(LNode::without_literals)
+                    .collect(),
+            },
+            LNode::DynChild(_) => self.clone(),
+        }
+    }
+
     fn to_replacement_node(
         &self,
         old_children: &OldChildren,
@@ -37,18 +85,7 @@ impl LNode {
                     children,
                 } => ReplacementNode::Element {
                     name: name.to_owned(),
-                    attrs: attrs
-                        .iter()
-                        .filter_map(|(name, value)| match value {
-                            LAttributeValue::Boolean => {
-                                Some((name.to_owned(), name.to_owned()))
-                            }
-                            LAttributeValue::Static(value) => {
-                                Some((name.to_owned(), value.to_owned()))
-                            }
-                            _ => None,
-                        })
-                        .collect(),
+                    attrs: LAttributeValue::literal_attrs(attrs),
                     children: children
                         .iter()
                         .map(|node| node.to_replacement_node(old_children))
@@ -115,7 +152,7 @@ impl LNode {
                 LNode::diff_children(path, old, new, orig_children)
             }
             // text node: replace text
-            (LNode::Text(_), LNode::Text(new)) => vec![Patch {
+            (LNode::Text(old), LNode::Text(new)) if old != new => vec![Patch {
                 path: path.to_owned(),
                 action: PatchAction::SetText(new.to_owned()),
             }],
@@ -185,8 +222,32 @@ impl LNode {
         old: &'a [(String, LAttributeValue)],
         new: &'a [(String, LAttributeValue)],
     ) -> impl Iterator<Item = Patch> + 'a {
+        // classes are added and removed one by one, so that the ones toggled
+        // by the app are kept
+        let is_class =
+            |name: &str| name == "class" || name.starts_with("class:");
+        let old_classes = LAttributeValue::literal_classes(old);
+        let new_classes = LAttributeValue::literal_classes(new);
+        let added_classes = new_classes
+            .iter()
+            .filter(|class| !old_classes.contains(class))
+            .map(|class| Patch {
+                path: path.to_owned(),
+                action: PatchAction::AddClass((*class).to_owned()),
+            })
+            .collect::<Vec<_>>();
+        let removed_classes = old_classes
+            .iter()
+            .filter(|class| !new_classes.contains(class))
+            .map(|class| Patch {
+                path: path.to_owned(),
+                action: PatchAction::RemoveClass((*class).to_owned()),
+            })
+            .collect::<Vec<_>>();
+
         let additions = new
             .iter()
+            .filter(move |(name, _)| !is_class(name))
             .filter_map(|(name, new_value)| {
                 let old_attr = old.iter().find(|(o_name, _)| o_name == name);
                 let replace = match old_attr {
@@ -213,18 +274,22 @@ impl LNode {
                 action: PatchAction::SetAttribute(name, value),
             });
 
-        let removals = old.iter().filter_map(|(name, _)| {
-            if new.iter().any(|(new_name, _)| new_name == name) {
-                None
-            } else {
-                Some(Patch {
-                    path: path.to_owned(),
-                    action: PatchAction::RemoveAttribute(name.to_owned()),
-                })
-            }
+        let removals = old.iter().filter_map(move |(name, value)| {
+            let kept = is_class(name)
+                || *value == LAttributeValue::Noop
+                || new.iter().any(|(new_name, new_value)| {
+                    new_name == name && *new_value != LAttributeValue::Noop
+                });
+            (!kept).then(|| Patch {
+                path: path.to_owned(),
+                action: PatchAction::RemoveAttribute(name.to_owned()),
+            })
         });
 
-        additions.chain(removals)
+        additions
+            .chain(removals)
+            .chain(removed_classes)
+            .chain(added_classes)
     }
 
     fn diff_children(
@@ -362,6 +427,8 @@ pub enum PatchAction {
     ChangeTagName(String),
     RemoveAttribute(String),
     SetAttribute(String, String),
+    AddClass(String),
+    RemoveClass(String),
     SetText(String),
     ClearChildren,
     AppendChildren(Vec<ReplacementNode>),
@@ -397,6 +464,11 @@ mod tests {
         node::LAttributeValue,
         LNode,
     };
+    use quote::quote;
+
+    fn parse(tokens: proc_macro2::TokenStream) -> LNode {
+        LNode::parse_view(rstml::parse2(tokens).unwrap()).unwrap()
+    }
 
     #[test]
     fn patches_text() {
@@ -434,13 +506,6 @@ mod tests {
         assert_eq!(
             delta,
             vec![
-                Patch {
-                    path: vec![],
-                    action: PatchAction::SetAttribute(
-                        "class".into(),
-                        "a b".into()
-                    )
-                },
                 Patch {
                     path: vec![],
                     action: PatchAction::SetAttribute(
@@ -452,6 +517,10 @@ mod tests {
                     path: vec![],
                     action: PatchAction::RemoveAttribute("type".into())
                 },
+                Patch {
+                    path: vec![],
+                    action: PatchAction::AddClass("b".into())
+                },
             ]
         );
     }
@@ -570,4 +639,53 @@ mod tests {
             },]
         );
     }
+
+    #[test]
+    fn patches_class_directives_and_boolean_attrs() {
+        let a = parse(quote! {
+            <button class="a" class:b=true class:c=on_off disabled>"Go"</button>
+        });
+        let b = parse(quote! {
+            <button class="a" class:b=false class:c=on_off class:d>"Go"</button>
+        });
+        let delta = a.diff(&b);
+        assert_eq!(
+            delta,
+            vec![
+                Patch {
+                    path: vec![],
+                    action: PatchAction::RemoveAttribute("disabled".into())
+                },
+                Patch {
+                    path: vec![],
+                    action: PatchAction::RemoveClass("b".into())
+                },
+                Patch {
+                    path: vec![],
+                    action: PatchAction::AddClass("d".into())
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn only_literal_changes_can_be_patched() {
+        let a = parse(quote! {
+            <p class="a" title={"old"} on:click=move |_| ()>"Hello, " {name}</p>
+        });
+        let literals = parse(quote! {
+            <p class="b" title="new" on:click=move |_| ()>"Hi, " {name}</p>
+        });
+        assert!(a.differs_only_in_literals(&literals));
+
+        let handler = parse(quote! {
+            <p class="a" title={"old"} on:click=move |_| log()>"Hello, " {name}</p>
+        });
+        assert!(!a.differs_only_in_literals(&handler));
+
+        let child = parse(quote! {
+            <p class="a" title={"old"} on:click=move |_| ()>"Hello, " {name} "!"</p>
+        });
+        assert!(!a.differs_only_in_literals(&child));
+    }
 }
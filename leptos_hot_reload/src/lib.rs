@@ -5,9 +5,10 @@ use camino::Utf8PathBuf;
 use diff::Patches;
 use node::LNode;
 use parking_lot::RwLock;
+use proc_macro2::{Group, LineColumn, TokenStream, TokenTree};
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     fs::File,
     io::Read,
     path::{Path, PathBuf},
@@ -29,7 +30,29 @@ pub const HOT_RELOAD_JS: &str = include_str!("patch.js");
 #[derive(Debug, Clone, Default)]
 pub struct ViewMacros {
     // keyed by original location identifier
-    views: Arc<RwLock<HashMap<Utf8PathBuf, Vec<MacroInvocation>>>>,
+    views: Arc<RwLock<HashMap<Utf8PathBuf, FileViews>>>,
+}
+
+#[derive(Debug, Clone)]
+struct FileViews {
+    macros: Vec<MacroInvocation>,
+    // the tokens of the file outside of its view macros
+    rest: String,
+}
+
+/// What it takes to bring a running app up to date with a changed file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ViewChange {
+    /// Only the literal text, attributes or classes of `view!` macros have
+    /// changed, so applying the patches updates the page and the app does not
+    /// need to be rebuilt.
+    ///
+    /// The compiled app still renders the old markup, so a full page reload
+    /// shows it again until the app is next rebuilt.
+    Patch(Patches),
+    /// Anything else has changed, and the app needs to be rebuilt. The
+    /// patches, if any, can be applied while it builds.
+    Rebuild(Option<Patches>),
 }
 
 impl ViewMacros {
@@ -50,9 +73,8 @@ impl ViewMacros {
                     let path: PathBuf = entry.path().into();
                     let path = Utf8PathBuf::try_from(path)?;
                     if path.extension() == Some("rs") || path.ends_with(".rs") {
-                        let macros = Self::parse_file(&path)?;
-                        let entry = views.entry(path.clone()).or_default();
-                        *entry = macros;
+                        let file = Self::parse_views(&path)?;
+                        views.insert(path, file);
                     }
                 }
             }
@@ -67,6 +89,10 @@ impl ViewMacros {
     ///
     /// Will return `Err` if the contents of the file cannot be parsed.
     pub fn parse_file(path: &Utf8PathBuf) -> Result<Vec<MacroInvocation>> {
+        Ok(Self::parse_views(path)?.macros)
+    }
+
+    fn parse_views(path: &Utf8PathBuf) -> Result<FileViews> {
         let mut file = File::open(path)?;
         let mut content = String::new();
         file.read_to_string(&mut content)?;
@@ -74,6 +100,14 @@ impl ViewMacros {
 
         let mut visitor = ViewMacroVisitor::default();
         visitor.visit_file(&ast);
+        let view_starts = visitor
+            .views
+            .iter()
+            .map(|view| view.delimiter.span().open().start())
+            .collect();
+        let tokens = syn::parse_str(&content)?;
+        let rest = without_views(tokens, &view_starts).to_string();
+
         let mut views = Vec::new();
         for view in visitor.views {
             let span = view.span();
@@ -93,43 +127,66 @@ impl ViewMacros {
                 views.push(MacroInvocation { id, template });
             }
         }
-        Ok(views)
+        Ok(FileViews {
+            macros: views,
+            rest,
+        })
     }
 
     /// # Errors
     ///
     /// Will return `Err` if the contents of the file cannot be parsed.
     pub fn patch(&self, path: &Utf8PathBuf) -> Result<Option<Patches>> {
-        let new_views = Self::parse_file(path)?;
+        Ok(match self.change(path)? {
+            ViewChange::Patch(patches) => Some(patches),
+            ViewChange::Rebuild(patches) => patches,
+        })
+    }
+
+    /// Diffs the views in the file at `path` against the last known version,
+    /// and tells whether patching them is enough to update the app.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the contents of the file cannot be parsed.
+    pub fn change(&self, path: &Utf8PathBuf) -> Result<ViewChange> {
+        let new_file = Self::parse_views(path)?;
         let mut lock = self.views.write();
-        let diffs = match lock.get(path) {
-            None => return Ok(None),
-            Some(current_views) => {
-                if current_views.len() == new_views.len() {
-                    let mut diffs = Vec::new();
-                    for (current_view, new_view) in
-                        current_views.iter().zip(&new_views)
-                    {
-                        if current_view.id == new_view.id
-                            && current_view.template != new_view.template
-                        {
-                            diffs.push((
-                                current_view.id.clone(),
-                                current_view.template.diff(&new_view.template),
-                            ));
-                        }
-                    }
-                    diffs
-                } else {
-                    return Ok(None);
-                }
-            }
+        let Some(current_file) = lock.get(path) else {
+            return Ok(ViewChange::Rebuild(None));
         };
+        let (current_views, new_views) =
+            (&current_file.macros, &new_file.macros);
+        if current_views.len() != new_views.len() {
+            return Ok(ViewChange::Rebuild(None));
+        }
+
+        let mut patch_only = current_file.rest == new_file.rest;
+        let mut diffs = Vec::new();
+        for (current_view, new_view) in current_views.iter().zip(new_views) {
+            if current_view.id != new_view.id {
+                // the markers in the page no longer match
+                patch_only = false;
+            } else if current_view.template != new_view.template {
+                patch_only &= current_view
+                    .template
+                    .differs_only_in_literals(&new_view.template);
+                diffs.push((
+                    current_view.id.clone(),
+                    current_view.template.diff(&new_view.template),
+                ));
+            }
+        }
 
         // update the status to the new views
-        lock.insert(path.clone(), new_views);
+        lock.insert(path.clone(), new_file);
 
-        Ok(Some(Patches(diffs)))
+        let patches = Patches(diffs);
+        Ok(if patch_only {
+            ViewChange::Patch(patches)
+        } else {
+            ViewChange::Rebuild(Some(patches))
+        })
     }
 }
 
@@ -164,6 +221,28 @@ impl<'ast> Visit<'ast> for ViewMacroVisitor<'ast> {
     }
 }
 
+/// Empties the view macros that start at `view_starts`, leaving the tokens
+/// that their templates don't cover.
+fn without_views(
+    tokens: TokenStream,
+    view_starts: &HashSet<LineColumn>,
+) -> TokenStream {
+    tokens
+        .into_iter()
+        .map(|token| match token {
+            TokenTree::Group(group) => {
+                let stream = if view_starts.contains(&group.span().start()) {
+                    TokenStream::new()
+                } else {
+                    without_views(group.stream(), view_starts)
+                };
+                TokenTree::Group(Group::new(group.delimiter(), stream))
+            }
+            token => token,
+        })
+        .collect()
+}
+
 pub fn span_to_stable_id(path: impl AsRef<Path>, line: usize) -> String {
     let file = path
         .as_ref()
@@ -172,3 +251,38 @@ pub fn span_to_stable_id(path: impl AsRef<Path>, line: usize) -> String {
         .replace(['/', '\\'], "-");
     format!("{file}-{line}")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn literal_changes_skip_the_rebuild() {
+        let dir = std::env::temp_dir().join("leptos_hot_reload_change");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = Utf8PathBuf::try_from(dir.join("app.rs")).unwrap();
+        let write = |source: &str| std::fs::write(&path, source).unwrap();
+
+        write(r#"fn app() { let n = 1; view! { <p class="a">"Hi"</p> } }"#);
+        let macros = ViewMacros::new();
+        macros.update_from_paths(&[&dir]).unwrap();
+
+        write(r#"fn app() { let n = 1; view! { <p class="b">"Bye"</p> } }"#);
+        assert!(matches!(
+            macros.change(&path).unwrap(),
+            ViewChange::Patch(Patches(patches)) if patches.len() == 1
+        ));
+
+        write(r#"fn app() { let n = 2; view! { <p class="b">"Bye"</p> } }"#);
+        assert_eq!(
+            macros.change(&path).unwrap(),
+            ViewChange::Rebuild(Some(Patches(vec![])))
+        );
+
+        write(r#"fn app() { let n = 2; view! { <p class="b">{n}</p> } }"#);
+        assert!(matches!(
+            macros.change(&path).unwrap(),
+            ViewChange::Rebuild(Some(_))
+        ));
+    }
+}
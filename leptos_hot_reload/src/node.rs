@@ -1,4 +1,6 @@
-use crate::parsing::is_component_node;
+use crate::parsing::{
+    block_to_primitive_expression, is_component_node, value_to_string,
+};
 use anyhow::Result;
 use quote::ToTokens;
 use rstml::node::{Node, NodeAttribute};
@@ -33,11 +35,19 @@ pub enum LNode {
 pub enum LAttributeValue {
     Boolean,
     Static(String),
+    // can't be patched, but the expression is kept so that changes to it
+    // are noticed
+    Dynamic(String),
     // safely ignored
-    Dynamic,
     Noop,
 }
 
+/// Directives that are applied by the compiled app rather than as attributes.
+/// `class:` is handled separately, as literal classes can be patched.
+const DIRECTIVES: [&str; 8] = [
+    "on:", "prop:", "style:", "attr:", "use:", "bind:", "clone:", "let:",
+];
+
 impl LNode {
     /// # Errors
     ///
@@ -70,9 +80,17 @@ impl LNode {
                 views.push(LNode::Text(text.value_string()));
             }
             Node::Block(block) => {
-                views.push(LNode::DynChild(
-                    block.into_token_stream().to_string(),
-                ));
+                // `{"text"}` is rendered just like `"text"`
+                let literal = block
+                    .try_block()
+                    .and_then(block_to_primitive_expression)
+                    .and_then(value_to_string);
+                views.push(match literal {
+                    Some(text) => LNode::Text(text),
+                    None => {
+                        LNode::DynChild(block.into_token_stream().to_string())
+                    }
+                });
             }
             Node::Element(el) => {
                 if is_component_node(&el) {
@@ -102,16 +120,20 @@ impl LNode {
                     let mut attrs = Vec::new();
 
                     for attr in el.open_tag.attributes {
-                        if let NodeAttribute::Attribute(attr) = attr {
-                            let name = attr.key.to_string();
-                            if let Some(value) = attr.value_literal_string() {
-                                attrs.push((
-                                    name,
-                                    LAttributeValue::Static(value),
-                                ));
-                            } else {
-                                attrs.push((name, LAttributeValue::Dynamic));
+                        match attr {
+                            NodeAttribute::Attribute(attr) => {
+                                let name = attr.key.to_string();
+                                let value =
+                                    LAttributeValue::parse(&name, attr.value());
+                                attrs.push((name, value));
                             }
+                            // spread attributes
+                            NodeAttribute::Block(block) => attrs.push((
+                                "{..}".to_string(),
+                                LAttributeValue::Dynamic(
+                                    block.into_token_stream().to_string(),
+                                ),
+                            )),
                         }
                     }
 
@@ -153,17 +175,9 @@ impl LNode {
                 // I wouldn't do this for real code, but this is just for dev mode
                 let is_self_closing = children.is_empty();
 
-                let attrs = attrs
-                    .iter()
-                    .filter_map(|(name, value)| match value {
-                        LAttributeValue::Boolean => Some(format!("{name} ")),
-                        LAttributeValue::Static(value) => {
-                            Some(format!("{name}=\"{value}\" "))
-                        }
-                        LAttributeValue::Dynamic | LAttributeValue::Noop => {
-                            None
-                        }
-                    })
+                let attrs = LAttributeValue::literal_attrs(attrs)
+                    .into_iter()
+                    .map(|(name, value)| format!("{name}=\"{value}\" "))
                     .collect::<String>();
 
                 let children =
@@ -178,3 +192,94 @@ impl LNode {
         }
     }
 }
+
+impl LAttributeValue {
+    fn parse(name: &str, value: Option<&syn::Expr>) -> Self {
+        let Some(value) = value else {
+            return if DIRECTIVES.iter().any(|d| name.starts_with(d)) {
+                LAttributeValue::Dynamic(String::new())
+            } else {
+                LAttributeValue::Boolean
+            };
+        };
+        if DIRECTIVES.iter().any(|d| name.starts_with(d)) {
+            return LAttributeValue::Dynamic(
+                value.to_token_stream().to_string(),
+            );
+        }
+
+        // `{"literal"}` is the same as `"literal"`
+        let literal = match value {
+            syn::Expr::Block(block) => {
+                block_to_primitive_expression(&block.block)
+            }
+            value => Some(value),
+        };
+        match literal {
+            Some(syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Bool(b),
+                ..
+            })) => {
+                if b.value {
+                    LAttributeValue::Boolean
+                } else {
+                    LAttributeValue::Noop
+                }
+            }
+            Some(literal) if !name.starts_with("class:") => {
+                match value_to_string(literal) {
+                    Some(value) => LAttributeValue::Static(value),
+                    None => LAttributeValue::Dynamic(
+                        value.to_token_stream().to_string(),
+                    ),
+                }
+            }
+            _ => LAttributeValue::Dynamic(value.to_token_stream().to_string()),
+        }
+    }
+
+    /// Returns the attributes that are known without running the app, with
+    /// the literal `class:` directives merged into `class`.
+    #[must_use]
+    pub fn literal_attrs(attrs: &[(String, Self)]) -> Vec<(String, String)> {
+        let mut literal = Vec::new();
+        let classes = Self::literal_classes(attrs);
+        for (name, value) in attrs {
+            if name == "class" || name.starts_with("class:") {
+                continue;
+            }
+            match value {
+                LAttributeValue::Boolean => {
+                    literal.push((name.to_owned(), String::new()));
+                }
+                LAttributeValue::Static(value) => {
+                    literal.push((name.to_owned(), value.to_owned()));
+                }
+                LAttributeValue::Dynamic(_) | LAttributeValue::Noop => {}
+            }
+        }
+        if !classes.is_empty() {
+            literal.push(("class".to_string(), classes.join(" ")));
+        }
+        literal
+    }
+
+    /// Returns the classes set by a literal `class` attribute, or by
+    /// `class:` directives that are always on.
+    #[must_use]
+    pub fn literal_classes(attrs: &[(String, Self)]) -> Vec<&str> {
+        let mut classes = Vec::new();
+        for (name, value) in attrs {
+            match (name.strip_prefix("class:"), value) {
+                (None, LAttributeValue::Static(value)) if name == "class" => {
+                    classes.extend(value.split_whitespace());
+                }
+                (Some(class), LAttributeValue::Boolean) => {
+                    classes.push(class);
+                }
+                _ => {}
+            }
+        }
+        classes
+    }
+}
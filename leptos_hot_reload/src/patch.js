@@ -76,6 +76,16 @@ function patch(json) {
               console.log("[HOT RELOAD] > SetAttribute", child.node, action.SetAttribute);
               child.node.setAttribute(name, value);
             });
+          } else if (action.AddClass) {
+            actions.push(() => {
+              console.log("[HOT RELOAD] > AddClass", child.node, action.AddClass);
+              child.node.classList.add(action.AddClass);
+            });
+          } else if (action.RemoveClass) {
+            actions.push(() => {
+              console.log("[HOT RELOAD] > RemoveClass", child.node, action.RemoveClass);
+              child.node.classList.remove(action.RemoveClass);
+            });
           } else if (action.SetText) {
             const node = child.node;
             actions.push(() => {
@@ -5,16 +5,16 @@ use convert_case::{
 };
 use itertools::Itertools;
 use leptos_hot_reload::parsing::value_to_string;
-use proc_macro2::{Ident, Span, TokenStream};
+use proc_macro2::{Ident, Span, TokenStream, TokenTree};
 use proc_macro_error2::abort;
 use quote::{format_ident, quote, quote_spanned, ToTokens, TokenStreamExt};
-use std::hash::DefaultHasher;
+use std::{collections::HashSet, hash::DefaultHasher};
 use syn::{
     parse::Parse, parse_quote, spanned::Spanned, token::Colon,
     visit_mut::VisitMut, AngleBracketedGenericArguments, Attribute, FnArg,
-    GenericArgument, GenericParam, Item, ItemFn, LitStr, Meta, Pat, PatIdent,
-    Path, PathArguments, ReturnType, Signature, Stmt, Type, TypeImplTrait,
-    TypeParam, TypePath, Visibility,
+    GenericArgument, GenericParam, Generics, Item, ItemFn, LitStr, Meta, Pat,
+    PatIdent, Path, PathArguments, ReturnType, Signature, Stmt, Type,
+    TypeImplTrait, TypeParam, TypePath, Visibility,
 };
 
 pub struct Model {
@@ -193,6 +193,27 @@ impl ToTokens for Model {
 
         let prop_builder_fields =
             prop_builder_fields(vis, props, is_island_with_other_props);
+        let phantom_generics = phantom_generics_field(
+            vis,
+            &body.sig.generics,
+            props,
+            is_island_with_other_props,
+        );
+        let turbofish = body_turbofish(&body.sig.generics);
+        let prop_groups = props
+            .iter()
+            .filter(|prop| prop.prop_opts.flatten)
+            .map(|Prop { name, ty, .. }| {
+                let ident = &name.ident;
+                quote! {
+                    impl #impl_generics ::leptos::component::HasPropGroup<#ty> for #props_name #generics #where_clause {
+                        fn set_prop_group(&mut self, group: #ty) {
+                            self.#ident = group;
+                        }
+                    }
+                }
+            })
+            .collect::<TokenStream>();
         let props_serializer = if is_island_with_other_props {
             let fields = prop_serializer_fields(vis, props);
             quote! {
@@ -288,6 +309,15 @@ impl ToTokens for Model {
         };
 
         let component_id = name.to_string();
+        let devtools_guard_expr = cfg!(feature = "devtools").then(|| {
+            quote! {
+                let _devtools = ::leptos::reactive::devtools::enter_component(#component_id);
+            }
+        });
+        // lets the owners created in the component know its name, in debug mode
+        let component_scope_expr = quote! {
+            let _component = ::leptos::reactive::owner::enter_component(#component_id);
+        };
         let hydrate_fn_name = is_island.then(|| {
             use std::hash::{Hash, Hasher};
 
@@ -318,14 +348,14 @@ impl ToTokens for Model {
                 ::leptos::reactive::owner::Owner::new().with(|| {
                     ::leptos::reactive::owner::Owner::with_hydration(move || {
                         ::leptos::tachys::reactive_graph::OwnedView::new({
-                            #body_name(#prop_names)
+                            #body_name #turbofish (#prop_names)
                         })
                     })
                 })
             }
         } else {
             quote! {
-                #body_name(#prop_names)
+                #body_name #turbofish (#prop_names)
             }
         };
 
@@ -338,6 +368,8 @@ impl ToTokens for Model {
                         move || {
                             #tracing_guard_expr
                             #tracing_props_expr
+                            #devtools_guard_expr
+                            #component_scope_expr
                             #body_expr
                         }
                     )
@@ -349,6 +381,8 @@ impl ToTokens for Model {
                     move || {
                         #tracing_guard_expr
                         #tracing_props_expr
+                        #devtools_guard_expr
+                        #component_scope_expr
                         #body_expr
                     }
                 )
@@ -410,10 +444,16 @@ impl ToTokens for Model {
             } else {
                 quote! {}
             };
+            let rest = if phantom_generics.is_empty() {
+                quote! {}
+            } else {
+                quote! { .. }
+            };
             quote! {
                 #island_serialize_props
                 let #props_name {
                     #prop_names
+                    #rest
                 } = props;
                 #wrapped_children
             }
@@ -561,6 +601,7 @@ impl ToTokens for Model {
             #[allow(non_snake_case)]
             #vis struct #props_name #impl_generics #where_clause {
                 #prop_builder_fields
+                #phantom_generics
             }
 
             #props_serializer
@@ -576,6 +617,8 @@ impl ToTokens for Model {
                 }
             }
 
+            #prop_groups
+
             // TODO restore dyn attrs
             /*impl #impl_generics ::leptos::DynAttrs for #props_name #generics #where_clause {
                 fn dyn_attrs(mut self, v: Vec<(&'static str, ::leptos::Attribute)>) -> Self {
@@ -640,6 +683,9 @@ impl Parse for DummyModel {
         let vis: Visibility = input.parse()?;
         let mut sig: Signature = input.parse()?;
         maybe_modify_return_type(&mut sig.output);
+        // `impl Trait` is not allowed in some positions that are valid in props, such as the
+        // return type of `children: impl Fn(Row) -> impl IntoView`
+        convert_impl_trait_to_generic(&mut sig);
 
         // The body is left untouched, so it will not cause an error
         // even if the syntax is invalid.
@@ -965,6 +1011,8 @@ struct PropOpt {
     into: bool,
     attrs: bool,
     name: Option<String>,
+    #[attribute(conflicts = [optional, optional_no_strip, strip_option, default])]
+    flatten: bool,
 }
 
 struct TypedBuilderOpts {
@@ -977,7 +1025,10 @@ struct TypedBuilderOpts {
 impl TypedBuilderOpts {
     fn from_opts(opts: &PropOpt, is_ty_option: bool) -> Self {
         Self {
-            default: opts.optional || opts.optional_no_strip || opts.attrs,
+            default: opts.optional
+                || opts.optional_no_strip
+                || opts.attrs
+                || opts.flatten,
             default_with_value: opts.default.clone(),
             strip_option: opts.strip_option || opts.optional && is_ty_option,
             into: opts.into,
@@ -1090,6 +1141,95 @@ fn prop_builder_fields(
         .collect()
 }
 
+/// Generic parameters that are only used in the body of a component, and not in the type of
+/// any of its props, would be unused in the props struct. This returns a hidden
+/// [`PhantomData`](std::marker::PhantomData) field that uses them, if there are any.
+fn phantom_generics_field(
+    vis: &Visibility,
+    generics: &Generics,
+    props: &[Prop],
+    is_island_with_other_props: bool,
+) -> TokenStream {
+    fn collect_idents(tokens: TokenStream, idents: &mut HashSet<String>) {
+        for token in tokens {
+            match token {
+                TokenTree::Ident(ident) => {
+                    idents.insert(ident.to_string());
+                }
+                TokenTree::Group(group) => {
+                    collect_idents(group.stream(), idents)
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let mut used = HashSet::new();
+    for prop in props {
+        collect_idents(prop.ty.to_token_stream(), &mut used);
+    }
+
+    let unused = generics
+        .params
+        .iter()
+        .filter_map(|param| match param {
+            GenericParam::Type(ty) if !used.contains(&ty.ident.to_string()) => {
+                let ident = &ty.ident;
+                Some(quote! { #ident })
+            }
+            GenericParam::Lifetime(lt)
+                if !used.contains(&lt.lifetime.ident.to_string()) =>
+            {
+                let lifetime = &lt.lifetime;
+                Some(quote! { &#lifetime () })
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    if unused.is_empty() {
+        return quote! {};
+    }
+
+    let skip_serde = if is_island_with_other_props {
+        quote!(#[serde(skip)])
+    } else {
+        quote!()
+    };
+    quote! {
+        #[doc(hidden)]
+        #[builder(setter(skip), default)]
+        #skip_serde
+        #vis _phantom_generics: ::std::marker::PhantomData<fn() -> (#(#unused,)*)>,
+    }
+}
+
+/// Returns the generic arguments with which the original component function should be
+/// called, so that generic parameters that are only used in its body can be inferred.
+///
+/// Lifetimes are left out, and the parameters created for `impl Trait` arguments are inferred
+/// from the arguments themselves.
+fn body_turbofish(generics: &Generics) -> TokenStream {
+    let args = generics
+        .params
+        .iter()
+        .filter_map(|param| match param {
+            GenericParam::Type(ty)
+                if ty.ident.to_string().starts_with(IMPL_TRAIT_PREFIX) =>
+            {
+                Some(quote! { _ })
+            }
+            GenericParam::Type(ty) => Some(ty.ident.to_token_stream()),
+            GenericParam::Const(c) => Some(c.ident.to_token_stream()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    if args.is_empty() {
+        quote! {}
+    } else {
+        quote! { ::<#(#args),*> }
+    }
+}
+
 fn prop_serializer_fields(vis: &Visibility, props: &[Prop]) -> TokenStream {
     props
         .iter()
@@ -1314,10 +1454,13 @@ pub fn unmodified_fn_name_from_fn_name(ident: &Ident) -> Ident {
     Ident::new(&format!("__{ident}"), ident.span())
 }
 
+/// The prefix for the generic params that replace `impl Trait`s in a component's arguments.
+const IMPL_TRAIT_PREFIX: &str = "__ImplTrait";
+
 /// Converts all `impl Trait`s in a function signature to use generic params instead.
 fn convert_impl_trait_to_generic(sig: &mut Signature) {
     fn new_generic_ident(i: usize, span: Span) -> Ident {
-        Ident::new(&format!("__ImplTrait{i}"), span)
+        Ident::new(&format!("{IMPL_TRAIT_PREFIX}{i}"), span)
     }
 
     // First: visit all `impl Trait`s and replace them with new generic params.
@@ -0,0 +1,169 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{spanned::Spanned, Data, DeriveInput, Expr, Fields, Ident, LitInt};
+
+pub fn form_model_impl(ast: &DeriveInput) -> proc_macro::TokenStream {
+    form_model(ast)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+struct FieldInfo<'a> {
+    ident: &'a Ident,
+    name: String,
+    validators: Vec<Expr>,
+    async_validators: Vec<Expr>,
+    debounce_ms: Option<LitInt>,
+}
+
+fn form_model(ast: &DeriveInput) -> syn::Result<TokenStream> {
+    let name = &ast.ident;
+    let vis = &ast.vis;
+    let fields_name = format_ident!("{name}Fields");
+
+    if !ast.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            ast.generics.span(),
+            "FormModel cannot be derived for generic structs",
+        ));
+    }
+    let Data::Struct(data) = &ast.data else {
+        return Err(syn::Error::new(
+            ast.span(),
+            "FormModel can only be derived for structs",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new(
+            data.fields.span(),
+            "FormModel can only be derived for structs with named fields",
+        ));
+    };
+
+    let mut infos = Vec::new();
+    for field in &fields.named {
+        let ident = field.ident.as_ref().unwrap();
+        let mut validators = Vec::new();
+        let mut async_validators = Vec::new();
+        let mut debounce_ms = None;
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("form"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("validate") {
+                    validators.push(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("validate_async") {
+                    async_validators.push(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("debounce_ms") {
+                    debounce_ms = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else {
+                    Err(meta.error(
+                        "expected `validate = ...`, `validate_async = ...`, \
+                         or `debounce_ms = ...`",
+                    ))
+                }
+            })?;
+        }
+        infos.push(FieldInfo {
+            ident,
+            name: ident.to_string().trim_start_matches("r#").to_owned(),
+            validators,
+            async_validators,
+            debounce_ms,
+        });
+    }
+
+    let field_defs = fields.named.iter().map(|field| {
+        let vis = &field.vis;
+        let ident = &field.ident;
+        let ty = &field.ty;
+        quote! { #vis #ident: ::leptos::form::FormField<#ty> }
+    });
+    let idents = infos.iter().map(|info| info.ident).collect::<Vec<_>>();
+    let into_fields = infos.iter().map(|info| {
+        let FieldInfo {
+            ident,
+            name,
+            validators,
+            async_validators,
+            debounce_ms,
+        } = info;
+        let debounce = debounce_ms.iter().map(|ms| {
+            quote! { .debounce(::std::time::Duration::from_millis(#ms)) }
+        });
+        quote! {
+            #ident: ::leptos::form::FormField::new(#name, self.#ident)
+                #(.validator(#validators))*
+                #(.async_validator(#async_validators))*
+                #(#debounce)*
+        }
+    });
+    let validate = infos.iter().filter(|info| !info.validators.is_empty()).map(
+        |info| {
+            let FieldInfo {
+                ident,
+                name,
+                validators,
+                ..
+            } = info;
+            quote! {
+                let result: ::std::result::Result<(), ::std::string::String> =
+                    ::std::result::Result::Ok(())
+                        #(.and_then(|()| (#validators)(&self.#ident)))*;
+                if let ::std::result::Result::Err(error) = result {
+                    errors.insert(#name, error);
+                }
+            }
+        },
+    );
+    let doc = format!("The fields of a [`{name}`] form.");
+
+    Ok(quote! {
+        #[doc = #doc]
+        #[derive(Clone, Copy, Debug)]
+        #vis struct #fields_name {
+            #(#field_defs,)*
+        }
+
+        impl ::leptos::form::FormModel for #name {
+            type Fields = #fields_name;
+
+            fn into_fields(self) -> Self::Fields {
+                #fields_name {
+                    #(#into_fields,)*
+                }
+            }
+
+            fn from_fields(fields: &Self::Fields) -> Self {
+                Self {
+                    #(#idents: fields.#idents.get(),)*
+                }
+            }
+
+            fn for_each_field(
+                fields: &Self::Fields,
+                f: &mut dyn ::std::ops::FnMut(&dyn ::leptos::form::AnyField),
+            ) {
+                #(f(&fields.#idents);)*
+            }
+
+            fn validate(
+                &self,
+            ) -> ::std::result::Result<(), ::leptos::form::FieldErrors> {
+                #[allow(unused_mut)]
+                let mut errors = ::leptos::form::FieldErrors::new();
+                #(#validate)*
+                if errors.is_empty() {
+                    ::std::result::Result::Ok(())
+                } else {
+                    ::std::result::Result::Err(errors)
+                }
+            }
+        }
+    })
+}
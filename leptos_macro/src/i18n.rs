@@ -0,0 +1,799 @@
+use convert_case::{Case, Casing};
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Ident, LitStr, Token,
+};
+
+struct LoadLocalesArgs {
+    path: Option<LitStr>,
+    default: Option<LitStr>,
+}
+
+impl Parse for LoadLocalesArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut args = LoadLocalesArgs {
+            path: None,
+            default: None,
+        };
+        let pairs =
+            Punctuated::<(Ident, LitStr), Token![,]>::parse_terminated_with(
+                input,
+                |input| {
+                    let name = input.parse::<Ident>()?;
+                    input.parse::<Token![=]>()?;
+                    Ok((name, input.parse()?))
+                },
+            )?;
+        for (name, value) in pairs {
+            match name.to_string().as_str() {
+                "path" => args.path = Some(value),
+                "default" => args.default = Some(value),
+                _ => {
+                    return Err(syn::Error::new(
+                        name.span(),
+                        "expected `path` or `default`",
+                    ))
+                }
+            }
+        }
+        Ok(args)
+    }
+}
+
+/// A single piece of a message.
+#[derive(Debug, PartialEq)]
+enum Segment {
+    /// Literal text.
+    Text(String),
+    /// A variable, which is replaced by its value.
+    Var(String),
+    /// A choice between several versions of the text, depending on the value of a numeric
+    /// variable.
+    Plural {
+        var: String,
+        cases: Vec<(Selector, Vec<Segment>)>,
+        other: Vec<Segment>,
+    },
+}
+
+#[derive(Debug, PartialEq)]
+enum Selector {
+    Exact(u64),
+    Category(String),
+}
+
+/// The CLDR plural categories.
+const CATEGORIES: [&str; 6] = ["zero", "one", "two", "few", "many", "other"];
+
+type Catalog = BTreeMap<String, Vec<Segment>>;
+
+pub fn load_locales_impl(tokens: proc_macro::TokenStream) -> TokenStream {
+    let args = match syn::parse::<LoadLocalesArgs>(tokens) {
+        Ok(args) => args,
+        Err(e) => return e.to_compile_error(),
+    };
+    load_locales(args).unwrap_or_else(|e| e.to_compile_error())
+}
+
+fn load_locales(args: LoadLocalesArgs) -> syn::Result<TokenStream> {
+    let span = args
+        .path
+        .as_ref()
+        .map(|path| path.span())
+        .unwrap_or_else(Span::call_site);
+    let error = |msg: String| syn::Error::new(span, msg);
+
+    let root = std::env::var("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_default();
+    let dir = root.join(
+        args.path
+            .as_ref()
+            .map(LitStr::value)
+            .unwrap_or_else(|| "locales".to_string()),
+    );
+    let entries = std::fs::read_dir(&dir).map_err(|e| {
+        error(format!(
+            "could not read locales from {}: {e}",
+            dir.display()
+        ))
+    })?;
+
+    // locale tag => (file, catalog)
+    let mut locales = BTreeMap::new();
+    for entry in entries {
+        let path = entry.map_err(|e| error(e.to_string()))?.path();
+        let (Some(tag), Some(ext)) = (
+            path.file_stem().and_then(|stem| stem.to_str()),
+            path.extension().and_then(|ext| ext.to_str()),
+        ) else {
+            continue;
+        };
+        let catalog = match ext {
+            "json" => parse_json(&read(&path).map_err(error)?),
+            "ftl" => parse_fluent(&read(&path).map_err(error)?),
+            _ => continue,
+        }
+        .map_err(|e| error(format!("{}: {e}", path.display())))?;
+        if locales
+            .insert(tag.to_string(), (path.clone(), catalog))
+            .is_some()
+        {
+            return Err(error(format!(
+                "there is more than one catalog for the locale `{tag}`"
+            )));
+        }
+    }
+    if locales.is_empty() {
+        return Err(error(format!(
+            "no `.json` or `.ftl` catalogs found in {}",
+            dir.display()
+        )));
+    }
+
+    let default = match &args.default {
+        Some(default) => {
+            let default = default.value();
+            if !locales.contains_key(&default) {
+                return Err(syn::Error::new(
+                    args.default.as_ref().unwrap().span(),
+                    format!("there is no catalog for the locale `{default}`"),
+                ));
+            }
+            default
+        }
+        None => locales.keys().next().unwrap().clone(),
+    };
+
+    // every locale must define the same keys, and can only use the variables that are used by
+    // the default locale
+    let (_, default_catalog) = &locales[&default];
+    for (tag, (_, catalog)) in &locales {
+        for key in default_catalog.keys() {
+            if !catalog.contains_key(key) {
+                return Err(error(format!(
+                    "the key `{key}` is missing from the locale `{tag}`"
+                )));
+            }
+        }
+        for (key, message) in catalog {
+            let Some(default_message) = default_catalog.get(key) else {
+                return Err(error(format!(
+                    "the key `{key}` in the locale `{tag}` is missing from \
+                     the default locale `{default}`"
+                )));
+            };
+            let default_vars = vars(default_message);
+            if let Some(var) = vars(message)
+                .into_iter()
+                .find(|var| !default_vars.contains(var))
+            {
+                return Err(error(format!(
+                    "the message `{key}` in the locale `{tag}` uses the \
+                     variable `{var}`, which is not used by the default \
+                     locale `{default}`"
+                )));
+            }
+        }
+    }
+
+    let variants = locales
+        .keys()
+        .map(|tag| {
+            let name = tag.replace(['-', '_'], " ").to_case(Case::Pascal);
+            format_ident!("{name}")
+        })
+        .collect::<Vec<_>>();
+    let tags = locales.keys().collect::<Vec<_>>();
+    let default_variant =
+        &variants[locales.keys().position(|tag| *tag == default).unwrap()];
+    let files = locales
+        .values()
+        .map(|(path, _)| path.display().to_string())
+        .collect::<Vec<_>>();
+
+    // internal rules, which find the value passed for a variable, and check that each variable
+    // that is passed is used by the message
+    let all_vars = default_catalog
+        .values()
+        .flat_map(|message| vars(message))
+        .collect::<BTreeSet<_>>();
+    let arg_rules = all_vars.iter().map(|var| {
+        let var = format_ident!("{var}");
+        quote! {
+            (@arg #var; #var = $value:expr $(, $rest:ident = $rest_value:expr)*) => {
+                $value
+            };
+            (@arg #var; $other:ident = $other_value:expr $(, $rest:ident = $rest_value:expr)*) => {
+                $crate::i18n::t!(@arg #var; $($rest = $rest_value),*)
+            };
+        }
+    });
+    let mut check_rules = Vec::new();
+
+    let arms = default_catalog
+        .iter()
+        .map(|(key, default_message)| {
+            let key_tokens = TokenStream::from_str(key).map_err(|_| {
+                error(format!("`{key}` is not a valid message key"))
+            })?;
+            let vars = vars(default_message);
+            let messages = locales.iter().map(|(tag, (_, catalog))| {
+                message_to_tokens(tag, &catalog[key], !vars.is_empty())
+            });
+            if vars.is_empty() {
+                return Ok(quote! {
+                    ($i18n:expr, #key_tokens $(,)?) => {{
+                        let i18n = $i18n;
+                        move || match ::leptos::i18n::I18n::locale(&i18n) {
+                            #($crate::i18n::Locale::#variants => #messages,)*
+                        }
+                    }};
+                });
+            }
+
+            let var_names = vars.iter().map(|var| format_ident!("{var}"));
+            let var_idents = vars.iter().map(|var| format_ident!("__var_{var}"));
+            for var in &vars {
+                let var = format_ident!("{var}");
+                check_rules.push(quote! {
+                    (@check #var in #key_tokens) => {};
+                });
+            }
+            Ok(quote! {
+                ($i18n:expr, #key_tokens $(, $arg:ident = $value:expr)* $(,)?) => {{
+                    $($crate::i18n::t!(@check $arg in #key_tokens);)*
+                    let i18n = $i18n;
+                    move || {
+                        #(
+                            let #var_idents = &$crate::i18n::t!(
+                                @arg #var_names; $($arg = $value),*
+                            );
+                        )*
+                        match ::leptos::i18n::I18n::locale(&i18n) {
+                            #($crate::i18n::Locale::#variants => #messages,)*
+                        }
+                    }
+                }};
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    Ok(quote! {
+        /// The locales and messages loaded by `leptos::load_locales!`.
+        pub mod i18n {
+            // rebuild whenever a catalog changes
+            #(const _: &str = include_str!(#files);)*
+
+            /// The locales that this application has been translated into.
+            #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+            pub enum Locale {
+                #(
+                    #[doc = #tags]
+                    #variants,
+                )*
+            }
+
+            impl ::leptos::i18n::Locales for Locale {
+                const ALL: &'static [Self] = &[#(Locale::#variants),*];
+                const DEFAULT: Self = Locale::#default_variant;
+
+                fn as_str(self) -> &'static str {
+                    match self {
+                        #(Locale::#variants => #tags,)*
+                    }
+                }
+            }
+
+            impl ::std::default::Default for Locale {
+                fn default() -> Self {
+                    <Self as ::leptos::i18n::Locales>::DEFAULT
+                }
+            }
+
+            /// Looks up a message in the current locale.
+            ///
+            /// Takes the `I18n` context and the key of the message,
+            /// followed by a value for each of the message's variables, and returns a
+            /// function that returns the message and updates whenever the locale changes.
+            #[allow(unused_macros)]
+            macro_rules! t {
+                #(#arg_rules)*
+                (@arg $var:ident;) => {
+                    ::std::compile_error!(::std::concat!(
+                        "missing a value for the variable `",
+                        ::std::stringify!($var),
+                        "`"
+                    ))
+                };
+                #(#check_rules)*
+                (@check $arg:ident in $($key:tt)*) => {
+                    ::std::compile_error!(::std::concat!(
+                        "the message `",
+                        ::std::stringify!($($key)*),
+                        "` has no variable named `",
+                        ::std::stringify!($arg),
+                        "`"
+                    ));
+                };
+                #(#arms)*
+            }
+            #[allow(unused_imports)]
+            pub(crate) use t;
+        }
+    })
+}
+
+/// Generates an expression that builds the message, as it is displayed in the locale `tag`.
+///
+/// This is a `&'static str` if the message has no variables and `owned` is not set, and a
+/// `String` otherwise.
+fn message_to_tokens(
+    tag: &str,
+    message: &[Segment],
+    owned: bool,
+) -> TokenStream {
+    if vars(message).is_empty() {
+        let text = message
+            .iter()
+            .map(|segment| match segment {
+                Segment::Text(text) => text.as_str(),
+                _ => unreachable!(),
+            })
+            .collect::<String>();
+        return if owned {
+            quote! { ::std::string::String::from(#text) }
+        } else {
+            quote! { #text }
+        };
+    }
+
+    let mut format = String::new();
+    let mut args = Vec::new();
+    for segment in message {
+        match segment {
+            Segment::Text(text) => {
+                format.push_str(&text.replace('{', "{{").replace('}', "}}"))
+            }
+            Segment::Var(var) => {
+                format.push_str("{}");
+                let var = format_ident!("__var_{var}");
+                args.push(quote! { #var });
+            }
+            Segment::Plural { var, cases, other } => {
+                format.push_str("{}");
+                let var = format_ident!("__var_{var}");
+                let cases = cases.iter().map(|(selector, message)| {
+                    let message = message_to_tokens(tag, message, true);
+                    match selector {
+                        Selector::Exact(n) => quote! { (#n, _) => #message, },
+                        Selector::Category(category) => {
+                            let category =
+                                format_ident!("{}", category.to_case(Case::Pascal));
+                            quote! {
+                                (_, ::leptos::i18n::PluralCategory::#category) => #message,
+                            }
+                        }
+                    }
+                });
+                let other = message_to_tokens(tag, other, true);
+                args.push(quote! {{
+                    let n = ::leptos::i18n::PluralCount::plural_count(#var);
+                    match (n, ::leptos::i18n::plural_category(#tag, n)) {
+                        #(#cases)*
+                        _ => #other,
+                    }
+                }});
+            }
+        }
+    }
+    quote! { ::std::format!(#format, #(#args),*) }
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    std::fs::read_to_string(path)
+        .map_err(|e| format!("could not read {}: {e}", path.display()))
+}
+
+fn vars(message: &[Segment]) -> BTreeSet<&str> {
+    let mut vars = BTreeSet::new();
+    for segment in message {
+        match segment {
+            Segment::Text(_) => {}
+            Segment::Var(var) => {
+                vars.insert(var.as_str());
+            }
+            Segment::Plural { var, cases, other } => {
+                vars.insert(var.as_str());
+                for (_, message) in cases {
+                    vars.extend(self::vars(message));
+                }
+                vars.extend(self::vars(other));
+            }
+        }
+    }
+    vars
+}
+
+/// Parses a JSON catalog, in which each message is a string in the
+/// [ICU MessageFormat](https://unicode-org.github.io/icu/userguide/format_parse/messages/)
+/// syntax. Nested objects are flattened, so that `{"nav": {"home": "Home"}}` defines the key
+/// `nav.home`.
+fn parse_json(source: &str) -> Result<Catalog, String> {
+    fn flatten(
+        prefix: &str,
+        value: serde_json::Value,
+        catalog: &mut Catalog,
+    ) -> Result<(), String> {
+        match value {
+            serde_json::Value::Object(map) => {
+                for (key, value) in map {
+                    let key = if prefix.is_empty() {
+                        key
+                    } else {
+                        format!("{prefix}.{key}")
+                    };
+                    flatten(&key, value, catalog)?;
+                }
+                Ok(())
+            }
+            serde_json::Value::String(message) => {
+                check_key(prefix)?;
+                let message = MessageParser::new(&message)
+                    .parse_icu(None, false)
+                    .map_err(|e| format!("in the message `{prefix}`: {e}"))?;
+                catalog.insert(prefix.to_string(), message);
+                Ok(())
+            }
+            _ => Err(format!("the message `{prefix}` is not a string")),
+        }
+    }
+
+    let value = serde_json::from_str(source).map_err(|e| e.to_string())?;
+    let mut catalog = Catalog::new();
+    flatten("", value, &mut catalog)?;
+    Ok(catalog)
+}
+
+/// Parses a catalog in the [Fluent](https://projectfluent.org/) syntax. Comments, messages
+/// (including indented continuation lines), variables like `{ $name }`, string literals like
+/// `{ "{" }`, and select expressions on the plural category of a number are supported.
+fn parse_fluent(source: &str) -> Result<Catalog, String> {
+    let mut messages: Vec<(String, String)> = Vec::new();
+    for line in source.lines() {
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if line.starts_with([' ', '\t', '}', '[', '*']) {
+            let Some((_, message)) = messages.last_mut() else {
+                return Err(format!("unexpected indented line `{line}`"));
+            };
+            if !message.is_empty() {
+                message.push('\n');
+            }
+            message.push_str(line.trim());
+            continue;
+        }
+        let Some((key, message)) = line.split_once('=') else {
+            return Err(format!("expected `key = value`, found `{line}`"));
+        };
+        messages.push((key.trim().to_string(), message.trim().to_string()));
+    }
+
+    let mut catalog = Catalog::new();
+    for (key, message) in messages {
+        check_key(&key)?;
+        let message = MessageParser::new(&message)
+            .parse_fluent(false)
+            .map_err(|e| format!("in the message `{key}`: {e}"))?;
+        catalog.insert(key, message);
+    }
+    Ok(catalog)
+}
+
+struct MessageParser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl MessageParser {
+    fn new(message: &str) -> Self {
+        Self {
+            chars: message.chars().collect(),
+            pos: 0,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_next(&self) -> Option<char> {
+        self.chars.get(self.pos + 1).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), String> {
+        self.skip_whitespace();
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(format!("expected `{c}`"))
+        }
+    }
+
+    fn take_while(&mut self, f: impl Fn(char) -> bool) -> String {
+        let start = self.pos;
+        while self.peek().is_some_and(&f) {
+            self.pos += 1;
+        }
+        self.chars[start..self.pos].iter().collect()
+    }
+
+    fn ident(&mut self) -> Result<String, String> {
+        self.skip_whitespace();
+        let ident = self.take_while(|c| c.is_alphanumeric() || c == '_');
+        check_var(&ident)
+    }
+
+    fn selector(&mut self, key: &str) -> Result<Selector, String> {
+        let key = key.trim();
+        if let Ok(n) = key.trim_start_matches('=').parse() {
+            Ok(Selector::Exact(n))
+        } else if CATEGORIES.contains(&key) {
+            Ok(Selector::Category(key.to_string()))
+        } else {
+            Err(format!(
+                "`{key}` is not a plural category; expected a number or one \
+                 of {}",
+                CATEGORIES.join(", ")
+            ))
+        }
+    }
+
+    /// Parses an ICU message, in which variables are written `{name}` and plurals
+    /// `{count, plural, one {# item} other {# items}}`. An apostrophe quotes special characters,
+    /// so `'{'` is a literal brace and `''` a literal apostrophe.
+    ///
+    /// `plural` is the variable that `#` refers to, and `nested` whether this is one of the cases
+    /// of a plural, which ends with a `}`.
+    fn parse_icu(
+        &mut self,
+        plural: Option<&str>,
+        nested: bool,
+    ) -> Result<Vec<Segment>, String> {
+        let mut segments = Vec::new();
+        let mut text = String::new();
+        loop {
+            match self.peek() {
+                None if nested => return Err("unclosed `{`".to_string()),
+                None => break,
+                Some('}') if nested => {
+                    self.pos += 1;
+                    break;
+                }
+                Some('}') => return Err("unmatched `}`".to_string()),
+                Some('\'') if self.peek_next() == Some('\'') => {
+                    self.pos += 2;
+                    text.push('\'');
+                }
+                Some('\'')
+                    if matches!(self.peek_next(), Some('{' | '}' | '#')) =>
+                {
+                    self.pos += 1;
+                    text.push_str(&self.take_while(|c| c != '\''));
+                    self.pos += 1;
+                }
+                Some('#') if plural.is_some() => {
+                    self.pos += 1;
+                    push_text(&mut segments, &mut text);
+                    segments.push(Segment::Var(plural.unwrap().to_string()));
+                }
+                Some('{') => {
+                    self.pos += 1;
+                    push_text(&mut segments, &mut text);
+                    segments.push(self.icu_argument()?);
+                }
+                Some(c) => {
+                    self.pos += 1;
+                    text.push(c);
+                }
+            }
+        }
+        push_text(&mut segments, &mut text);
+        Ok(segments)
+    }
+
+    fn icu_argument(&mut self) -> Result<Segment, String> {
+        let var = self.ident()?;
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.pos += 1;
+            return Ok(Segment::Var(var));
+        }
+        self.expect(',')?;
+        let kind = self.ident()?;
+        if kind != "plural" {
+            return Err(format!(
+                "unsupported argument type `{kind}`; only `plural` is \
+                 supported"
+            ));
+        }
+        self.expect(',')?;
+
+        let mut cases = Vec::new();
+        let mut other = None;
+        loop {
+            self.skip_whitespace();
+            if self.peek() == Some('}') {
+                self.pos += 1;
+                break;
+            }
+            let key = self.take_while(|c| !c.is_whitespace() && c != '{');
+            let selector = self.selector(&key)?;
+            self.expect('{')?;
+            let message = self.parse_icu(Some(&var), true)?;
+            push_case(&mut cases, &mut other, selector, message)?;
+        }
+        let other = other.ok_or_else(|| {
+            format!("the plural of `{var}` has no `other` case")
+        })?;
+        Ok(Segment::Plural { var, cases, other })
+    }
+
+    /// Parses a Fluent pattern. If `variant` is set, this is the value of a variant in a select
+    /// expression, which ends at the end of the line or the end of the select expression.
+    fn parse_fluent(&mut self, variant: bool) -> Result<Vec<Segment>, String> {
+        let mut segments = Vec::new();
+        let mut text = String::new();
+        loop {
+            match self.peek() {
+                None => break,
+                Some('\n' | '}') if variant => break,
+                Some('}') => return Err("unmatched `}`".to_string()),
+                Some('{') => {
+                    self.pos += 1;
+                    push_text(&mut segments, &mut text);
+                    segments.push(self.fluent_placeable()?);
+                }
+                Some(c) => {
+                    self.pos += 1;
+                    text.push(c);
+                }
+            }
+        }
+        push_text(&mut segments, &mut text);
+        Ok(segments)
+    }
+
+    fn fluent_placeable(&mut self) -> Result<Segment, String> {
+        self.skip_whitespace();
+        if self.peek() == Some('"') {
+            self.pos += 1;
+            let text = self.take_while(|c| c != '"');
+            self.pos += 1;
+            self.expect('}')?;
+            return Ok(Segment::Text(text));
+        }
+        if self.peek() != Some('$') {
+            return Err(
+                "only variables like `{ $name }`, string literals, and select \
+                 expressions are supported"
+                    .to_string(),
+            );
+        }
+        self.pos += 1;
+        let var = self.ident()?;
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.pos += 1;
+            return Ok(Segment::Var(var));
+        }
+        self.expect('-')?;
+        self.expect('>')?;
+
+        let mut cases = Vec::new();
+        let mut other = None;
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some('}') => {
+                    self.pos += 1;
+                    break;
+                }
+                None => return Err("unclosed `{`".to_string()),
+                _ => {}
+            }
+            let is_default = self.peek() == Some('*');
+            if is_default {
+                self.pos += 1;
+            }
+            self.expect('[')?;
+            let key = self.take_while(|c| c != ']');
+            self.expect(']')?;
+            let selector = self.selector(&key)?;
+            self.take_while(|c| c == ' ' || c == '\t');
+            let mut message = self.parse_fluent(true)?;
+            if let Some(Segment::Text(text)) = message.last_mut() {
+                text.truncate(text.trim_end().len());
+            }
+            if is_default {
+                if other.is_some() {
+                    return Err("there is more than one default variant, \
+                                marked with `*`"
+                        .to_string());
+                }
+                other = Some(message);
+            } else {
+                push_case(&mut cases, &mut None, selector, message)?;
+            }
+        }
+        let other = other.ok_or_else(|| {
+            format!(
+                "the select expression on `{var}` has no default variant, \
+                 marked with `*`"
+            )
+        })?;
+        Ok(Segment::Plural { var, cases, other })
+    }
+}
+
+fn push_text(segments: &mut Vec<Segment>, text: &mut String) {
+    if !text.is_empty() {
+        segments.push(Segment::Text(std::mem::take(text)));
+    }
+}
+
+/// Adds a case to a plural, treating the `other` category as the fallback.
+fn push_case(
+    cases: &mut Vec<(Selector, Vec<Segment>)>,
+    other: &mut Option<Vec<Segment>>,
+    selector: Selector,
+    message: Vec<Segment>,
+) -> Result<(), String> {
+    if cases.iter().any(|(existing, _)| *existing == selector) {
+        return Err(format!("the case `{selector:?}` appears more than once"));
+    }
+    match selector {
+        Selector::Category(category) if category == "other" => {
+            *other = Some(message);
+        }
+        selector => cases.push((selector, message)),
+    }
+    Ok(())
+}
+
+fn check_key(key: &str) -> Result<(), String> {
+    if key
+        .split('.')
+        .all(|segment| syn::parse_str::<Ident>(segment).is_ok())
+    {
+        Ok(())
+    } else {
+        Err(format!(
+            "`{key}` is not a valid message key: keys must be identifiers, \
+             optionally separated by `.`"
+        ))
+    }
+}
+
+fn check_var(var: &str) -> Result<String, String> {
+    match syn::parse_str::<Ident>(var) {
+        Ok(_) => Ok(var.to_string()),
+        Err(_) => Err(format!("`{var}` is not a valid variable name")),
+    }
+}
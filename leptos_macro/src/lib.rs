@@ -23,10 +23,14 @@ mod params;
 mod view;
 use crate::component::unmodified_fn_name_from_fn_name;
 mod component;
+mod form_model;
+mod i18n;
 mod lazy;
 mod memo;
+mod prop_group;
 mod slice;
 mod slot;
+mod style;
 
 /// The `view` macro uses RSX (like JSX, but Rust!) It follows most of the
 /// same rules as HTML, with the following differences:
@@ -99,6 +103,49 @@ mod slot;
 /// # };
 /// ```
 ///
+///    A `match` or an `if`/`else` chain (or a closure that returns one) marked with `#[either]` can return a
+///    different type of view from each branch: each branch is wrapped in the appropriate
+///    [`Either`](leptos::either::Either)/`EitherOfN` variant for you. An `if` without an `else` renders
+///    nothing when none of its conditions are met. Unmarked blocks are left as they are. Note that when
+///    the branch changes, the old branch is unmounted and the new one is created from scratch, so any
+///    DOM state in it, like the contents of an input, is lost.
+/// ```rust
+/// # use leptos::prelude::*;
+/// # fn test() -> impl IntoView {
+/// let (count, set_count) = signal(0);
+///
+/// view! {
+///   {#[either] move || match count.get() {
+///     0 => view! { <p>"Zero"</p> },
+///     n if n < 0 => view! { <strong>"Negative"</strong> },
+///     _ => "Positive",
+///   }}
+///   {#[either] move || if count.get() > 10 {
+///     view! { <button on:click=move |_| set_count.set(0)>"Reset"</button> }
+///   }}
+/// }
+/// # }
+/// ```
+///
+///    A block that only contains statements, such as `{let total = move || items.read().len();}`, renders
+///    nothing, but any bindings it makes are in scope for the nodes that follow it. The statements run once,
+///    when the view is created: to have a binding follow a signal, bind a closure and call it from the
+///    nodes that use it.
+/// ```rust
+/// # use leptos::prelude::*;
+/// # fn test() -> impl IntoView {
+/// let items = RwSignal::new(vec![1, 2, 3]);
+///
+/// view! {
+///   <ul>
+///     {let total = move || items.read().len();}
+///     <li data-total=total>{total} " items"</li>
+///     <li>{move || total() * 2} " halves"</li>
+///   </ul>
+/// }
+/// # }
+/// ```
+///
 /// 5. Event handlers can be added with `on:` attributes. In most cases, the events are given the correct type
 ///    based on the event name.
 /// ```rust
@@ -114,6 +161,62 @@ mod slot;
 /// # }
 /// ```
 ///
+///    When the `delegation` feature is enabled, events that bubble are handled by a single
+///    global listener. Adding `:undelegated` attaches the listener directly to the element
+///    instead, which is necessary inside shadow roots or for widgets that stop propagation.
+/// ```rust
+/// # use leptos::prelude::*;
+/// # fn test() -> impl IntoView {
+/// view! {
+///   <button on:click:undelegated=|ev| {
+///     log::debug!("click event: {ev:#?}");
+///   }>
+///     "Click me"
+///   </button>
+/// }
+/// # }
+/// ```
+///
+///    Listener options can be set with the `:capture`, `:passive`, and `:once` modifiers, which
+///    can be combined. Listeners with options are always attached directly to the element.
+/// ```rust
+/// # use leptos::prelude::*;
+/// # fn test() -> impl IntoView {
+/// view! {
+///   <div on:scroll:passive=|_| log::debug!("scrolled")>
+///     <img on:load:once=|_| log::debug!("loaded") />
+///     <button on:click:capture:once=|_| log::debug!("clicked")>"Click me"</button>
+///   </div>
+/// }
+/// # }
+/// ```
+///
+///    `on:typed:` takes an event type in scope instead of an event name, such as a custom event
+///    with a typed payload declared with [`define_custom_event!`](leptos::define_custom_event).
+///    The payload can be read with `ev.detail()`.
+/// ```rust
+/// # use leptos::prelude::*;
+/// # use serde::{Deserialize, Serialize};
+/// #[derive(Clone, Serialize, Deserialize)]
+/// pub struct SavePayload {
+///     pub id: u32,
+/// }
+///
+/// leptos::define_custom_event!(pub SaveRequested, SavePayload);
+///
+/// # fn test() -> impl IntoView {
+/// view! {
+///   <div on:typed:SaveRequested=|ev| {
+///     if let Some(payload) = ev.detail() {
+///       log::debug!("save requested for {}", payload.id);
+///     }
+///   }>
+///     "..."
+///   </div>
+/// }
+/// # }
+/// ```
+///
 /// 6. DOM properties can be set with `prop:` attributes, which take any primitive type or `JsValue` (or a signal
 ///    that returns a primitive or JsValue). They can also take an `Option`, in which case `Some` sets the property
 ///    and `None` deletes the property.
@@ -243,6 +346,25 @@ mod slot;
 /// # }
 /// ```
 ///
+/// To render untrusted HTML, use `inner_html_sanitized` instead. This passes the HTML through
+/// the sanitizer registered with [`set_html_sanitizer`](leptos::html::set_html_sanitizer)
+/// (for example, `ammonia` on the server) both during server rendering and in the browser.
+/// ```rust
+/// # use leptos::prelude::*;
+/// # fn test() -> impl IntoView {
+/// let (comment, _) = signal("<p>User-provided <script>HTML</script></p>".to_string());
+/// view! {
+///   <div inner_html_sanitized=comment/>
+/// }
+/// # }
+/// ```
+///
+/// With the `html_validation` feature enabled, `view!` also checks for common HTML mistakes at compile
+/// time: unknown element names, wrongly-cased attribute names (such as `className` or `tabIndex`), the same
+/// `id` used twice in one view, and nesting that the browser's HTML parser would "fix" when it parses
+/// server-rendered HTML (such as a `<div>` inside a `<p>`, or a `<tr>` directly inside a `<table>`).
+/// These would otherwise only show up as hydration errors at runtime.
+///
 /// Here’s a simple example that shows off several of these features, put together
 /// ```rust
 /// # use leptos::prelude::*;
@@ -277,6 +399,25 @@ pub fn view(tokens: TokenStream) -> TokenStream {
 /// [`ViewTemplate`](https://docs.rs/leptos/0.7.0-gamma3/leptos/prelude/struct.ViewTemplate.html). This optimizes creation speed by rendering
 /// most of the view into a `<template>` tag with HTML rendered at compile time, then hydrating it.
 /// In exchange, there is a small binary size overhead.
+///
+/// A [`view`](view!) that starts with `#[template]` does this whenever it can: if the view
+/// consists of a single HTML element containing only other HTML elements and static text, and
+/// its attribute values are literals or `move` closures, it is rendered from a cloned template.
+/// Views with components or other dynamic children are created element-by-element as usual.
+///
+/// ```rust
+/// # use leptos::prelude::*;
+/// # fn test() -> impl IntoView {
+/// let (active, set_active) = signal(true);
+/// view! {
+///     #[template]
+///     <div class="card" class:active=move || active.get()>
+///         <h2>"Title"</h2>
+///         <button on:click=move |_| set_active.set(false)>"Close"</button>
+///     </div>
+/// }
+/// # }
+/// ```
 #[proc_macro_error2::proc_macro_error]
 #[proc_macro]
 #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all))]
@@ -290,7 +431,28 @@ pub fn template(tokens: TokenStream) -> TokenStream {
 
 fn view_macro_impl(tokens: TokenStream, template: bool) -> TokenStream {
     let tokens: proc_macro2::TokenStream = tokens.into();
-    let mut tokens = tokens.into_iter();
+    let mut tokens = tokens.into_iter().peekable();
+
+    // a leading `#[template]` opts this view into template cloning, where possible
+    let mut clone_template = false;
+    if matches!(
+        tokens.peek(),
+        Some(TokenTree::Punct(hash)) if hash.as_char() == '#'
+    ) {
+        let hash = tokens.next();
+        match tokens.next() {
+            Some(TokenTree::Group(group))
+                if group.delimiter() == proc_macro2::Delimiter::Bracket
+                    && group.stream().to_string() == "template" =>
+            {
+                clone_template = true;
+            }
+            _ => abort!(
+                hash, "expected `#[template]`";
+                help = "`#[template]` is the only attribute a view can start with"
+            ),
+        }
+    }
 
     let first = tokens.next();
     let second = tokens.next();
@@ -327,6 +489,13 @@ fn view_macro_impl(tokens: TokenStream, template: bool) -> TokenStream {
     let parser = rstml::Parser::new(config);
     let (mut nodes, errors) = parser.parse_recoverable(tokens).split_vec();
     let errors = errors.into_iter().map(|e| e.emit_as_expr_tokens());
+    if cfg!(feature = "html_validation") {
+        view::validate_view(&nodes);
+    }
+    let template = template
+        || (clone_template
+            && !cfg!(feature = "__internal_erase_components")
+            && view::can_clone_template(&nodes));
     let nodes_output = view::render_view(
         &mut nodes,
         global_class.as_ref(),
@@ -468,6 +637,27 @@ pub fn include_view(tokens: TokenStream) -> TokenStream {
 /// fn my_snake_case_component() -> impl IntoView {}
 /// ```
 ///
+/// * Components can be generic, including over const generics and with `where` clauses. A type
+///    parameter that is only used in the body of the component (and not in any of its props) has to
+///    be specified when the component is used, as in `<ParseAs<u32> raw="12"/>`.
+///
+/// ```
+/// # use leptos::prelude::*;
+/// # use std::{fmt::Display, str::FromStr};
+/// #[component]
+/// fn ParseAs<T>(#[prop(into)] raw: String) -> impl IntoView
+/// where
+///     T: FromStr + Display + 'static,
+/// {
+///     raw.parse::<T>().ok().map(|value| value.to_string())
+/// }
+///
+/// #[component]
+/// fn App() -> impl IntoView {
+///     view! { <ParseAs<u32> raw="12"/> }
+/// }
+/// ```
+///
 /// 5. You can access the children passed into the component with the `children` property, which takes
 ///    an argument of the type `Children`. This is an alias for `Box<dyn FnOnce() -> AnyView<_>>`.
 ///    If you need `children` to be a `Fn` or `FnMut`, you can use the `ChildrenFn` or `ChildrenFnMut`
@@ -499,6 +689,42 @@ pub fn include_view(tokens: TokenStream) -> TokenStream {
 /// }
 /// ```
 ///
+///    `children` can also be a closure that takes arguments, such as
+///    `children: impl Fn(Row) -> impl IntoView`, which the component calls with its own data. Each
+///    argument is bound with `let:name` when the component is used, or destructured with
+///    `let(pattern)`.
+///
+/// ```
+/// # use leptos::prelude::*;
+/// pub struct Row {
+///     index: usize,
+///     label: String,
+/// }
+///
+/// #[component]
+/// fn Rows(
+///     labels: Vec<String>,
+///     children: impl Fn(Row) -> impl IntoView,
+/// ) -> impl IntoView {
+///     labels
+///         .into_iter()
+///         .enumerate()
+///         .map(|(index, label)| children(Row { index, label }))
+///         .collect::<Vec<_>>()
+/// }
+///
+/// #[component]
+/// fn App() -> impl IntoView {
+///     view! {
+///         <ul>
+///             <Rows labels=vec!["first".to_string()] let(Row { index, label })>
+///                 <li>{index} ": " {label}</li>
+///             </Rows>
+///         </ul>
+///     }
+/// }
+/// ```
+///
 /// ## Customizing Properties
 /// You can use the `#[prop]` attribute on individual component properties (function arguments) to
 /// customize the types that component property can receive. You can use the following attributes:
@@ -514,6 +740,9 @@ pub fn include_view(tokens: TokenStream) -> TokenStream {
 /// * `#[prop(optional_no_strip)]`: The same as `optional`, but requires values to be passed as `None` or
 ///   `Some(T)` explicitly. This means that the optional property can be omitted (and be `None`), or explicitly
 ///   specified as either `None` or `Some(T)`.
+/// * `#[prop(flatten)]`: Accepts a struct that derives [`PropGroup`](derive@PropGroup), which can be
+///   passed by name or spread into the component with `{..group}`. If it is not passed, it will be
+///   set to its default value.
 /// ```rust
 /// # use leptos::prelude::*;
 ///
@@ -798,6 +1027,66 @@ fn component_macro(
 ///     }
 /// }
 /// ```
+///
+/// 4. The type of a component's prop for a slot determines how many times that slot can be
+///    passed: a prop of type `S` requires exactly one `<S slot>`, a `#[prop(optional)]` prop of
+///    type `Option<S>` accepts at most one, and a `#[prop(optional)]` prop of type `Vec<S>`
+///    accepts any number. Leaving out a required slot, or passing a slot more times than its
+///    prop accepts, is a compile-time error. Slots can also carry typed callbacks, which the
+///    component calls with its own data.
+///
+/// ```
+/// # use leptos::prelude::*;
+/// #[slot]
+/// struct Title {
+///     children: Children,
+/// }
+///
+/// #[slot]
+/// struct Column {
+///     #[prop(into)]
+///     header: String,
+///     #[prop(into)]
+///     cell: Callback<(usize,), String>,
+/// }
+///
+/// #[component]
+/// fn Table(
+///     title: Title,
+///     #[prop(optional)] column: Vec<Column>,
+/// ) -> impl IntoView {
+///     view! {
+///         <table>
+///             <caption>{(title.children)()}</caption>
+///             <tbody>
+///                 {(0..3)
+///                     .map(|row| {
+///                         view! {
+///                             <tr>
+///                                 {column
+///                                     .iter()
+///                                     .map(|column| view! { <td>{column.cell.run((row,))}</td> })
+///                                     .collect::<Vec<_>>()}
+///                             </tr>
+///                         }
+///                     })
+///                     .collect::<Vec<_>>()}
+///             </tbody>
+///         </table>
+///     }
+/// }
+///
+/// #[component]
+/// fn App() -> impl IntoView {
+///     view! {
+///         <Table>
+///             <Title slot>"Squares"</Title>
+///             <Column slot header="n" cell=|row: usize| row.to_string()/>
+///             <Column slot header="n²" cell=|row: usize| (row * row).to_string()/>
+///         </Table>
+///     }
+/// }
+/// ```
 #[proc_macro_error2::proc_macro_error]
 #[proc_macro_attribute]
 pub fn slot(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
@@ -948,6 +1237,126 @@ pub fn params_derive(
     }
 }
 
+/// Derives the ability to spread a struct of shared props into a component.
+///
+/// A component accepts a prop group with a `#[prop(flatten)]` prop, and callers can then pass
+/// the whole group at once with `{..group}`, alongside any other props. Because a spread can
+/// replace it, a flattened prop is always optional, and its type must implement [`Default`].
+///
+/// ```rust
+/// # use leptos::prelude::*;
+/// #[derive(PropGroup, Clone, Default)]
+/// pub struct CommonProps {
+///     pub disabled: bool,
+///     pub size: &'static str,
+/// }
+///
+/// #[component]
+/// fn Button(
+///     #[prop(flatten)] common: CommonProps,
+///     label: &'static str,
+/// ) -> impl IntoView {
+///     view! {
+///         <button disabled=common.disabled data-size=common.size>
+///             {label}
+///         </button>
+///     }
+/// }
+///
+/// #[component]
+/// fn Toolbar() -> impl IntoView {
+///     let common = CommonProps {
+///         disabled: false,
+///         size: "small",
+///     };
+///     view! {
+///         <Button {..common.clone()} label="Save"/>
+///         <Button {..common} label="Cancel"/>
+///     }
+/// }
+/// ```
+#[proc_macro_derive(PropGroup)]
+pub fn prop_group_derive(
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    match syn::parse(input) {
+        Ok(ast) => prop_group::prop_group_impl(&ast),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Derives `FormModel` for a struct with named fields, so that it can be edited with a
+/// `FormStore`.
+///
+/// This generates a struct named after the model with a `Fields` suffix, which has a `FormField`
+/// for each field of the model. Validators for a field are declared with
+/// `#[form(validate = ...)]`, which takes any function of the form `fn(&T) -> Result<(), String>`
+/// and can be repeated; they are run in order, and the first error is shown. The same validators
+/// are checked by `FormModel::validate`, so that a server function can check the submitted data
+/// with the same rules as the browser.
+///
+/// Asynchronous validators, such as a server function that checks whether an email address is
+/// already registered, are declared with `#[form(validate_async = ...)]`, which takes a function
+/// of the form `async fn(T) -> Result<(), String>`. They run once the other validators pass,
+/// after the user stops typing for the delay set by `#[form(debounce_ms = ...)]` (300ms by
+/// default), and `FormField::is_validating` is `true` while they do. They are not checked by
+/// `FormModel::validate`.
+///
+/// ```rust
+/// # use leptos::prelude::*;
+/// #[derive(FormModel, Clone, Default)]
+/// struct Contact {
+///     #[form(validate = not_empty)]
+///     #[form(validate = |email: &String| {
+///         if email.contains('@') {
+///             Ok(())
+///         } else {
+///             Err("Enter an email address.".to_string())
+///         }
+///     })]
+///     email: String,
+///     #[form(validate = not_empty)]
+///     message: String,
+///     subscribe: bool,
+/// }
+///
+/// fn not_empty(value: &impl AsRef<str>) -> Result<(), String> {
+///     if value.as_ref().is_empty() {
+///         Err("This field is required.".into())
+///     } else {
+///         Ok(())
+///     }
+/// }
+///
+/// #[component]
+/// fn ContactForm() -> impl IntoView {
+///     let form = FormStore::new(Contact::default());
+///     let ContactFields {
+///         email,
+///         message,
+///         subscribe,
+///     } = form.fields();
+///
+///     view! {
+///         <input type="email" {..email.attrs()}/>
+///         <Show when=move || email.is_touched()>
+///             <p class="error">{move || email.error()}</p>
+///         </Show>
+///         <textarea {..message.attrs()}></textarea>
+///         <input type="checkbox" {..subscribe.checkbox_attrs()}/>
+///     }
+/// }
+/// ```
+#[proc_macro_derive(FormModel, attributes(form))]
+pub fn form_model_derive(
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    match syn::parse(input) {
+        Ok(ast) => form_model::form_model_impl(&ast),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
 /// Generates a `slice` into a struct with a default getter and setter.
 ///
 /// Can be used to access deeply nested fields within a global state object.
@@ -1012,6 +1421,111 @@ pub fn memo(input: TokenStream) -> TokenStream {
     memo::memo_impl(input)
 }
 
+/// Loads translated messages from a directory of catalogs, and generates an `i18n` module that
+/// contains a `Locale` enum with a variant for each catalog, and a `t!` macro to look up
+/// messages in the current locale.
+///
+/// Each file in the directory (`locales` in the crate root by default) is the catalog for the
+/// locale named by its file stem, such as `en.json` or `de-AT.ftl`. Catalogs can be written in
+/// JSON, with messages in [ICU MessageFormat](https://unicode-org.github.io/icu/userguide/format_parse/messages/)
+/// like `"Hello, {name}!"` and nested objects flattened into keys like `nav.home`, or in a subset
+/// of [Fluent](https://projectfluent.org/), with messages like `hello = Hello, { $name }!`.
+///
+/// Messages can depend on the plural category of an integer variable, which is chosen using the
+/// rules of each locale's language (see `leptos::i18n::plural_category`):
+///
+/// ```text
+/// // en.json
+/// "cart": "{count, plural, =0 {Your cart is empty} one {# item} other {# items}}"
+///
+/// # pl.ftl
+/// cart = { $count ->
+///     [0] Koszyk jest pusty
+///     [one] { $count } produkt
+///     [few] { $count } produkty
+///    *[many] { $count } produktów
+/// }
+/// ```
+///
+/// Catalogs are checked at compile time: every locale must define the same keys, and can only use
+/// the variables used by the default locale. Using a key that doesn't exist, leaving out a
+/// variable or passing one the message doesn't use, or choosing a plural with a value that isn't
+/// an integer, is a compile error.
+///
+/// ```rust,ignore
+/// // in the crate root, with locales/en.json and locales/de.json
+/// leptos::load_locales!(default = "en");
+///
+/// use crate::i18n::{t, Locale};
+/// use leptos::{i18n::*, prelude::*};
+///
+/// #[component]
+/// fn Greeting(name: String) -> impl IntoView {
+///     let i18n = use_i18n::<Locale>();
+///
+///     view! {
+///         <h1>{t!(i18n, greeting, name = name.clone())}</h1>
+///         <p>{t!(i18n, cart, count = 3)}</p>
+///         <button on:click=move |_| i18n.set_locale(Locale::De)>
+///             {t!(i18n, nav.german)}
+///         </button>
+///     }
+/// }
+/// ```
+///
+/// The macro accepts two optional arguments: `path`, the directory containing the catalogs
+/// relative to the crate root, and `default`, the locale used when none of the user's preferred
+/// locales is available. The default locale defaults to the first catalog in alphabetical order.
+#[proc_macro]
+pub fn load_locales(input: TokenStream) -> TokenStream {
+    i18n::load_locales_impl(input).into()
+}
+
+/// Creates styles that only apply to the elements of the component that uses them, returning
+/// the class that marks those elements.
+///
+/// The CSS is given as a string literal. Every selector in it is rewritten at compile time to
+/// only match elements that have a class derived from a hash of the CSS, which is usually given
+/// to every element of the component's view with `view! { class = ..., }`:
+///
+/// ```rust
+/// # use leptos::prelude::*;
+/// #[component]
+/// fn Card(title: String, children: Children) -> impl IntoView {
+///     let class = style!(r#"
+///         .card { border: 1px solid #ccc; border-radius: 0.5em; }
+///         .card h2 { margin-top: 0; }
+///         .card:hover::after { content: "→"; }
+///         :global(.dark) .card { border-color: #444; }
+///     "#);
+///
+///     view! { class = class,
+///         <div class="card">
+///             <h2>{title}</h2>
+///             {children()}
+///         </div>
+///     }
+/// }
+/// ```
+///
+/// Each compound selector gets the class, so that `.card h2` becomes
+/// `.card.leptos-1a2b3c4d h2.leptos-1a2b3c4d`, and neither matches the elements of other
+/// components. A part of a selector wrapped in `:global(...)` is left as it is. The rules inside
+/// `@media`, `@supports`, `@container` and `@layer` blocks are scoped too, while `@keyframes`,
+/// `@font-face` and other at-rules are kept as they are.
+///
+/// The styles of the components rendered on the server are inlined in the `<head>` by
+/// `leptos_meta`, and those of components rendered later in the browser are added to it as they
+/// are first used. See [`leptos::scoped_style`](../leptos/scoped_style/index.html) for how the
+/// styles can be extracted into a stylesheet instead.
+///
+/// Requires the `scoped-style` feature of `leptos`.
+#[proc_macro_error2::proc_macro_error]
+#[proc_macro]
+pub fn style(tokens: TokenStream) -> TokenStream {
+    style::style_impl(tokens).into()
+}
+
 /// The `#[lazy]` macro marks an `async` function as a function that can be lazy-loaded from a
 /// separate (WebAssembly) binary.
 ///
@@ -0,0 +1,22 @@
+use quote::quote;
+
+pub fn prop_group_impl(ast: &syn::DeriveInput) -> proc_macro::TokenStream {
+    let name = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) =
+        ast.generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            // takes precedence over `SpreadAttributes::__spread_onto`, which
+            // spreads any other value as attributes
+            #[doc(hidden)]
+            pub fn __spread_onto<P>(self, props: &mut P)
+            where
+                P: ::leptos::component::HasPropGroup<Self>,
+            {
+                props.set_prop_group(self);
+            }
+        }
+    }
+    .into()
+}
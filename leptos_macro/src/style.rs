@@ -0,0 +1,318 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    env, fs, io,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+use syn::LitStr;
+
+/// The at-rules whose blocks hold rules with selectors, which are scoped too. The blocks of
+/// every other at-rule, like `@keyframes` or `@font-face`, are kept as they are.
+const NESTING_AT_RULES: &[&str] =
+    &["media", "supports", "container", "layer", "document"];
+
+pub fn style_impl(tokens: proc_macro::TokenStream) -> TokenStream {
+    let css = match syn::parse::<LitStr>(tokens) {
+        Ok(css) => css,
+        Err(_) => {
+            proc_macro_error2::abort!(
+                Span::call_site(),
+                "style! takes the CSS as a string literal";
+                help = r##"e.g., style!(r#".card { padding: 1em; }"#)"##
+            )
+        }
+    };
+    let class = format!("leptos-{:08x}", fnv1a(css.value().as_bytes()) as u32);
+    let scoped = match scope_css(&css.value(), &class) {
+        Ok(scoped) => scoped,
+        Err(e) => proc_macro_error2::abort!(css.span(), "invalid CSS: {}", e),
+    };
+    extract(&class, &scoped);
+
+    quote! {
+        {
+            static STYLE: ::leptos::scoped_style::ScopedStyle =
+                ::leptos::scoped_style::ScopedStyle::new(#class, #scoped);
+            ::leptos::scoped_style::use_scoped_style(&STYLE)
+        }
+    }
+}
+
+/// Writes the scoped CSS to the directory set with `LEPTOS_SCOPED_CSS_DIR`, if any, and lists
+/// its class in the manifest of the crate being compiled.
+fn extract(class: &str, css: &str) {
+    let Some(dir) = env::var_os("LEPTOS_SCOPED_CSS_DIR") else {
+        return;
+    };
+    let dir = Path::new(&dir);
+    if let Err(e) =
+        write_css(dir, class, css).and_then(|_| write_manifest(dir, class))
+    {
+        proc_macro_error2::emit_call_site_warning!(
+            "could not write the scoped CSS to {}: {}",
+            dir.display(),
+            e
+        );
+    }
+}
+
+fn write_css(dir: &Path, class: &str, css: &str) -> io::Result<()> {
+    let path = dir.join(format!("{class}.css"));
+    // the file is only written when it changes, so that file watchers are not triggered by
+    // every build
+    if fs::read_to_string(&path).is_ok_and(|existing| existing == css) {
+        return Ok(());
+    }
+    fs::create_dir_all(dir)?;
+    fs::write(&path, css)
+}
+
+/// The classes of the styles used so far while compiling the crate, by the path of its manifest.
+static MANIFESTS: Mutex<BTreeMap<PathBuf, BTreeSet<String>>> =
+    Mutex::new(BTreeMap::new());
+
+/// Rewrites the manifest of the crate being compiled with every class used so far, so that once
+/// the crate has been compiled, `leptos::scoped_style::bundle` leaves out the files of the styles
+/// that it no longer uses.
+fn write_manifest(dir: &Path, class: &str) -> io::Result<()> {
+    let package = env::var("CARGO_PKG_NAME").unwrap_or_default();
+    // the library and the binaries of a package can have the same crate name
+    let target = match env::var("CARGO_BIN_NAME") {
+        Ok(bin) => format!("bin.{bin}"),
+        Err(_) => env::var("CARGO_CRATE_NAME").unwrap_or_default(),
+    };
+    let path = dir.join(format!("{package}.{target}.manifest"));
+
+    let mut manifests = MANIFESTS.lock().unwrap_or_else(|e| e.into_inner());
+    let classes = manifests.entry(path.clone()).or_default();
+    if !classes.insert(class.to_string()) {
+        return Ok(());
+    }
+    let list = classes
+        .iter()
+        .map(|class| format!("{class}\n"))
+        .collect::<String>();
+    // other builds of the crate, like those of its tests, can write the manifest at the same
+    // time, so it is replaced in one step
+    let tmp = path.with_extension(format!("manifest.{}", std::process::id()));
+    fs::write(&tmp, list)?;
+    fs::rename(tmp, path)
+}
+
+/// A hash that is the same on every platform and with every compiler, so that the server and
+/// the client agree on the class of each style.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Adds `class` to every compound selector of the rules in `css`, except those wrapped in
+/// `:global(...)`.
+pub(crate) fn scope_css(css: &str, class: &str) -> Result<String, String> {
+    let mut out = String::with_capacity(css.len());
+    scope_block(&strip_comments(css)?, class, &mut out)?;
+    Ok(out)
+}
+
+fn strip_comments(css: &str) -> Result<String, String> {
+    let mut out = String::with_capacity(css.len());
+    let mut rest = css;
+    while let Some(start) = find_top_level(rest, |c| c == '/', true) {
+        if !rest[start..].starts_with("/*") {
+            out.push_str(&rest[..=start]);
+            rest = &rest[start + 1..];
+            continue;
+        }
+        out.push_str(&rest[..start]);
+        let end = rest[start + 2..]
+            .find("*/")
+            .ok_or("a comment is not closed")?;
+        rest = &rest[start + 2 + end + 2..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+fn scope_block(
+    mut input: &str,
+    class: &str,
+    out: &mut String,
+) -> Result<(), String> {
+    loop {
+        input = input.trim_start();
+        if input.is_empty() {
+            return Ok(());
+        }
+        let end = find_top_level(input, |c| c == '{' || c == ';', false)
+            .ok_or_else(|| format!("expected `{{` after {:?}", input.trim()))?;
+        let prelude = input[..end].trim();
+        if input[end..].starts_with(';') {
+            // a statement like `@import` or `@charset`
+            out.push_str(prelude);
+            out.push(';');
+            input = &input[end + 1..];
+            continue;
+        }
+        let close = matching_brace(input, end)
+            .ok_or_else(|| format!("the block of {prelude:?} is not closed"))?;
+        let body = input[end + 1..close].trim();
+        if let Some(at_rule) = prelude.strip_prefix('@') {
+            let name = at_rule
+                .split(|c: char| c.is_whitespace() || c == '(')
+                .next()
+                .unwrap_or_default()
+                .to_ascii_lowercase();
+            out.push_str(prelude);
+            out.push('{');
+            if NESTING_AT_RULES.contains(&name.as_str()) {
+                scope_block(body, class, out)?;
+            } else {
+                out.push_str(body);
+            }
+            out.push('}');
+        } else {
+            out.push_str(&scope_selector_list(prelude, class)?);
+            out.push('{');
+            out.push_str(body);
+            out.push('}');
+        }
+        input = &input[close + 1..];
+    }
+}
+
+fn scope_selector_list(selectors: &str, class: &str) -> Result<String, String> {
+    split_top_level(selectors, ',')
+        .into_iter()
+        .map(|selector| scope_selector(selector.trim(), class))
+        .collect::<Result<Vec<_>, _>>()
+        .map(|selectors| selectors.join(","))
+}
+
+fn scope_selector(selector: &str, class: &str) -> Result<String, String> {
+    if selector.is_empty() {
+        return Err("a selector is empty".to_string());
+    }
+    let mut out = String::new();
+    let mut compound = String::new();
+    let mut combinator = None;
+    let mut depth = 0usize;
+    let mut quote = None;
+    for c in selector.chars() {
+        if let Some(q) = quote {
+            compound.push(c);
+            if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        match c {
+            '"' | '\'' => quote = Some(c),
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+        if depth == 0 && (c.is_whitespace() || matches!(c, '>' | '+' | '~')) {
+            if !compound.is_empty() {
+                out.push_str(&scope_compound(&compound, class));
+                compound.clear();
+                combinator = Some(' ');
+            }
+            if !c.is_whitespace() {
+                combinator = Some(c);
+            }
+            continue;
+        }
+        if let Some(combinator) = combinator.take() {
+            out.push(combinator);
+        }
+        compound.push(c);
+    }
+    if compound.is_empty() {
+        return Err(format!("{selector:?} ends with a combinator"));
+    }
+    out.push_str(&scope_compound(&compound, class));
+    Ok(out)
+}
+
+fn scope_compound(compound: &str, class: &str) -> String {
+    if let Some(global) = compound
+        .strip_prefix(":global(")
+        .and_then(|global| global.strip_suffix(')'))
+    {
+        return global.to_string();
+    }
+    // the class has to come before a pseudo-element like `::before`
+    let at = compound.find("::").unwrap_or(compound.len());
+    format!("{}.{class}{}", &compound[..at], &compound[at..])
+}
+
+/// Finds the first character matching `pat` that is outside of strings, and, unless `nested`
+/// is set, outside of parentheses and brackets.
+fn find_top_level(
+    input: &str,
+    pat: impl Fn(char) -> bool,
+    nested: bool,
+) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut quote = None;
+    let mut escaped = false;
+    for (idx, c) in input.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        if let Some(q) = quote {
+            match c {
+                '\\' => escaped = true,
+                c if c == q => quote = None,
+                _ => {}
+            }
+            continue;
+        }
+        if (nested || depth == 0) && pat(c) {
+            return Some(idx);
+        }
+        match c {
+            '\\' => escaped = true,
+            '"' | '\'' => quote = Some(c),
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Finds the `}` that closes the `{` at `open`.
+fn matching_brace(input: &str, open: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut offset = open;
+    loop {
+        let idx =
+            find_top_level(&input[offset..], |c| c == '{' || c == '}', true)?;
+        offset += idx;
+        if input[offset..].starts_with('{') {
+            depth += 1;
+        } else {
+            depth -= 1;
+            if depth == 0 {
+                return Some(offset);
+            }
+        }
+        offset += 1;
+    }
+}
+
+fn split_top_level(input: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut rest = input;
+    while let Some(idx) = find_top_level(rest, |c| c == separator, false) {
+        parts.push(&rest[..idx]);
+        rest = &rest[idx + 1..];
+    }
+    parts.push(rest);
+    parts
+}
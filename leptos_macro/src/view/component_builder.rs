@@ -1,5 +1,6 @@
 use super::{
-    fragment_to_tokens, utils::is_nostrip_optional_and_update_key, TagType,
+    fragment_to_tokens, slot_helper::slot_setter,
+    utils::is_nostrip_optional_and_update_key, TagType,
 };
 use crate::view::{
     attribute_absolute, text_to_tokens, utils::filter_prefixed_attrs,
@@ -134,6 +135,9 @@ pub(crate) fn component_to_tokens(
     // 1) blocks ({..attrs} or {attrs}),
     // 2) start with attr: and can be used as actual attributes, or
     // 3) the custom attribute types (on:, class:, style:, prop:, use:)
+    // `{..value}` spreads a prop group into the props if `value` is a `PropGroup`, and is
+    // spread onto the component as attributes otherwise
+    let mut spread_bindings = Vec::new();
     let spreads = node
         .attributes()
         .iter()
@@ -154,7 +158,22 @@ pub(crate) fn component_to_tokens(
                                 ..
                             }),
                             _,
-                        )) => Some(quote! { #end }),
+                        )) => {
+                            let binding = format_ident!(
+                                "__spread_{}",
+                                spread_bindings.len(),
+                                span = end.span()
+                            );
+                            spread_bindings.push(quote_spanned! {end.span()=>
+                                #[allow(clippy::let_unit_value)]
+                                let #binding = {
+                                    #[allow(unused_imports)]
+                                    use ::leptos::component::SpreadAttributes as _;
+                                    (#end).__spread_onto(&mut props)
+                                };
+                            });
+                            Some(quote! { #binding })
+                        }
                         _ => None,
                     }
                 } else {
@@ -259,24 +278,9 @@ pub(crate) fn component_to_tokens(
         }
     };
 
-    let slots = slots.drain().map(|(slot, mut values)| {
-        let span = values
-            .last()
-            .expect("List of slots must not be empty")
-            .span();
-        let slot = Ident::new(&slot, span);
-        let value = if values.len() > 1 {
-            quote_spanned! {span=>
-                ::std::vec![
-                    #(#values)*
-                ]
-            }
-        } else {
-            values.remove(0)
-        };
-
-        quote! { .#slot(#value) }
-    });
+    let slots = slots
+        .drain()
+        .map(|(slot, values)| slot_setter(slot, values));
 
     let generics = &node.open_tag.generics;
     let generics = if generics.lt_token.is_some() {
@@ -291,19 +295,18 @@ pub(crate) fn component_to_tokens(
         {
             #[allow(unreachable_code)]
             #[allow(unused_mut)]
-            #[allow(clippy::let_and_return)]
+            let mut props = ::leptos::component::component_props_builder(&#name #generics)
+                #(#required_props)*
+                #(#slots)*
+                #children
+                .build();
+            #(#optional_props)*
+            #(#spread_bindings)*
+            #[allow(unreachable_code)]
             ::leptos::component::component_view(
                 #[allow(clippy::needless_borrows_for_generic_args)]
-                &#name,
-                {
-                    let mut props = ::leptos::component::component_props_builder(&#name #generics)
-                        #(#required_props)*
-                        #(#slots)*
-                        #children
-                        .build();
-                    #(#optional_props)*
-                    props
-                }
+                &#name #generics,
+                props
             )
             #spreads
         }
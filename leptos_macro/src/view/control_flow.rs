@@ -0,0 +1,232 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote, quote_spanned};
+use rstml::node::{CustomNode, Node, NodeBlock};
+use syn::{spanned::Spanned, Attribute, Block, Expr, ExprIf, ExprMatch, Stmt};
+
+/// The largest `EitherOfN` type available; larger `match` expressions fall back to
+/// type-erasing each branch.
+const MAX_EITHER_BRANCHES: usize = 16;
+
+/// If `block` consists of a `match` or `if`/`else` expression (or a closure returning
+/// one) marked with `#[either]`, returns a version of it in which each branch is wrapped in
+/// the appropriate `Either`/`EitherOfN` variant, so that branches returning different view
+/// types can be used without any manual conversion.
+///
+/// Returns `None` if the block is not marked, in which case it should be rendered as-is, and
+/// an error if the marked expression is not a `match` or `if`/`else`.
+pub(crate) fn control_flow_to_tokens(block: &Block) -> Option<TokenStream> {
+    let [Stmt::Expr(expr, None)] = block.stmts.as_slice() else {
+        return None;
+    };
+    // the marker goes on the expression itself, or on the body of a closure
+    let marker = either_marker(expr).or_else(|| match expr {
+        Expr::Closure(closure) => either_marker(peel_blocks(&closure.body)),
+        _ => None,
+    })?;
+    if !matches!(marker.meta, syn::Meta::Path(_)) {
+        return Some(
+            syn::Error::new_spanned(marker, "`#[either]` takes no arguments")
+                .to_compile_error(),
+        );
+    }
+    let expr =
+        branching_expr_to_tokens(expr, &attrs_of(expr)).unwrap_or_else(|| {
+            syn::Error::new_spanned(
+                marker,
+                "`#[either]` can only be used on a `match` or `if`/`else` \
+             expression, or on a closure that returns one",
+            )
+            .to_compile_error()
+        });
+    Some(quote! { { #expr } })
+}
+
+/// If `node` is a block that only contains statements, such as `{let total = items.len();}`,
+/// returns those statements. The bindings they make are in scope for the nodes that follow
+/// the block.
+pub(crate) fn statement_block(node: &Node<impl CustomNode>) -> Option<&[Stmt]> {
+    let Node::Block(NodeBlock::ValidBlock(block)) = node else {
+        return None;
+    };
+    match block.stmts.last()? {
+        Stmt::Expr(_, None)
+        | Stmt::Macro(syn::StmtMacro {
+            semi_token: None, ..
+        }) => None,
+        _ => Some(&block.stmts),
+    }
+}
+
+fn expr_attrs(expr: &Expr) -> Option<&[Attribute]> {
+    match expr {
+        Expr::Match(expr) => Some(&expr.attrs),
+        Expr::If(expr) => Some(&expr.attrs),
+        Expr::Closure(expr) => Some(&expr.attrs),
+        Expr::Paren(expr) => Some(&expr.attrs),
+        _ => None,
+    }
+}
+
+/// Wraps the branches of `expr`, giving it `attrs` in place of its own.
+fn branching_expr_to_tokens(
+    expr: &Expr,
+    attrs: &[&Attribute],
+) -> Option<TokenStream> {
+    match expr {
+        Expr::Match(expr) => Some(match_to_tokens(expr, attrs)),
+        Expr::If(expr) => Some(if_to_tokens(expr, attrs)),
+        Expr::Closure(closure) => {
+            let body = peel_blocks(&closure.body);
+            let body = branching_expr_to_tokens(body, &attrs_of(body))?;
+            let syn::ExprClosure {
+                lifetimes,
+                constness,
+                movability,
+                asyncness,
+                capture,
+                inputs,
+                output,
+                ..
+            } = closure;
+            let inputs = inputs.iter();
+            Some(quote! {
+                #(#attrs)* #lifetimes #constness #movability #asyncness #capture
+                |#(#inputs),*| #output { #body }
+            })
+        }
+        Expr::Paren(expr) => branching_expr_to_tokens(&expr.expr, attrs),
+        _ => None,
+    }
+}
+
+fn either_marker(expr: &Expr) -> Option<&Attribute> {
+    expr_attrs(expr)?
+        .iter()
+        .find(|attr| attr.path().is_ident("either"))
+}
+
+/// The attributes of `expr`, other than `#[either]`.
+fn attrs_of(expr: &Expr) -> Vec<&Attribute> {
+    expr_attrs(expr)
+        .unwrap_or_default()
+        .iter()
+        .filter(|attr| !attr.path().is_ident("either"))
+        .collect()
+}
+
+/// Looks through blocks that consist of a single expression, such as the `{ match .. }` in
+/// `move || { match .. }`.
+fn peel_blocks(expr: &Expr) -> &Expr {
+    match expr {
+        Expr::Block(block)
+            if block.attrs.is_empty() && block.label.is_none() =>
+        {
+            match block.block.stmts.as_slice() {
+                [Stmt::Expr(inner, None)] => peel_blocks(inner),
+                _ => expr,
+            }
+        }
+        _ => expr,
+    }
+}
+
+fn match_to_tokens(expr: &ExprMatch, attrs: &[&Attribute]) -> TokenStream {
+    let ExprMatch { expr, arms, .. } = expr;
+    let count = arms.len();
+    let arms = arms.iter().enumerate().map(|(idx, arm)| {
+        let syn::Arm {
+            attrs,
+            pat,
+            guard,
+            body,
+            ..
+        } = arm;
+        let guard = guard.as_ref().map(|(if_token, guard)| {
+            quote! { #if_token #guard }
+        });
+        let body = wrap_branch(idx, count, body.span(), quote! { #body });
+        quote! { #(#attrs)* #pat #guard => #body, }
+    });
+    quote! {
+        #(#attrs)*
+        match #expr {
+            #(#arms)*
+        }
+    }
+}
+
+fn if_to_tokens(expr: &ExprIf, attrs: &[&Attribute]) -> TokenStream {
+    // flatten the `if`/`else if`/`else` chain into its conditions and branches
+    let mut conditions = Vec::new();
+    let mut branches: Vec<&Block> = Vec::new();
+    let mut else_branch = None;
+    let mut current = expr;
+    loop {
+        conditions.push(&current.cond);
+        branches.push(&current.then_branch);
+        match current.else_branch.as_ref().map(|(_, expr)| &**expr) {
+            Some(Expr::If(next)) => current = next,
+            Some(Expr::Block(block)) => {
+                else_branch = Some(&block.block);
+                break;
+            }
+            // a chain with no final `else` renders nothing in that case
+            _ => break,
+        }
+    }
+
+    let count = branches.len() + 1;
+    let branches = conditions.iter().zip(branches.iter()).enumerate().map(
+        |(idx, (cond, block))| {
+            let body = wrap_branch(idx, count, block.span(), quote! { #block });
+            if idx == 0 {
+                quote! { if #cond { #body } }
+            } else {
+                quote! { else if #cond { #body } }
+            }
+        },
+    );
+    let else_body = match else_branch {
+        Some(block) => {
+            wrap_branch(count - 1, count, block.span(), quote! { #block })
+        }
+        None => wrap_branch(count - 1, count, expr.span(), quote! { () }),
+    };
+
+    quote! {
+        #(#attrs)*
+        #(#branches)* else { #else_body }
+    }
+}
+
+fn wrap_branch(
+    idx: usize,
+    count: usize,
+    span: Span,
+    body: TokenStream,
+) -> TokenStream {
+    match count {
+        0 | 1 => body,
+        2 => {
+            let variant = if idx == 0 {
+                format_ident!("Left", span = span)
+            } else {
+                format_ident!("Right", span = span)
+            };
+            quote_spanned! {span=>
+                ::leptos::either::Either::#variant(#body)
+            }
+        }
+        n if n <= MAX_EITHER_BRANCHES => {
+            let ty = format_ident!("EitherOf{n}", span = span);
+            let variant =
+                format_ident!("{}", (b'A' + idx as u8) as char, span = span);
+            quote_spanned! {span=>
+                ::leptos::either::#ty::#variant(#body)
+            }
+        }
+        _ => quote_spanned! {span=>
+            ::leptos::prelude::IntoAny::into_any(#body)
+        },
+    }
+}
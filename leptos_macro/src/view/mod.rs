@@ -1,11 +1,18 @@
 mod component_builder;
+mod control_flow;
 mod slot_helper;
+mod template_cloning;
 mod utils;
+mod validation;
 
 use self::{
     component_builder::component_to_tokens,
+    control_flow::{control_flow_to_tokens, statement_block},
     slot_helper::{get_slot, slot_to_tokens},
 };
+pub(crate) use self::{
+    template_cloning::can_clone_template, validation::validate_view,
+};
 use convert_case::{
     Case::{Snake, UpperCamel},
     Casing,
@@ -155,7 +162,7 @@ fn is_inert_element(orig_node: &Node<impl CustomNode>) -> bool {
                                             matches!(&value.value, KVAttributeValue::Expr(expr) if {
                                                 if let Expr::Lit(lit) = expr {
                                                     let key = attr.key.to_string();
-                                                    if key.starts_with("style:") || key.starts_with("prop:") || key.starts_with("on:") || key.starts_with("use:") || key.starts_with("bind") {
+                                                    if key.starts_with("style:") || key.starts_with("prop:") || key.starts_with("auto:") || key.starts_with("on:") || key.starts_with("use:") || key.starts_with("bind") || key.starts_with("attr:") {
                                                         false
                                                     } else {
                                                         matches!(&lit.lit, Lit::Str(_))
@@ -513,6 +520,9 @@ fn children_to_tokens(
     disable_inert_html: bool,
 ) -> Vec<TokenStream> {
     if nodes.len() == 1 {
+        if let Some(stmts) = statement_block(&nodes[0]) {
+            return vec![quote! { { #(#stmts)* } }];
+        }
         match node_to_tokens(
             &mut nodes[0],
             parent_type,
@@ -530,6 +540,9 @@ fn children_to_tokens(
         let nodes = nodes
             .iter_mut()
             .filter_map(|node| {
+                if let Some(stmts) = statement_block(node) {
+                    return Some(Child::Statements(quote! { #(#stmts)* }));
+                }
                 node_to_tokens(
                     node,
                     TagType::Unknown,
@@ -539,8 +552,9 @@ fn children_to_tokens(
                     top_level,
                     disable_inert_html,
                 )
+                .map(Child::View)
             })
-            .collect();
+            .collect::<Vec<_>>();
         if let Some(parent_slots) = parent_slots {
             for (slot, mut values) in slots.drain() {
                 parent_slots
@@ -549,10 +563,51 @@ fn children_to_tokens(
                     .or_insert(values);
             }
         }
-        nodes
+        scope_statements(nodes)
     }
 }
 
+/// A child of an element or fragment: either a view, or a block of statements whose
+/// bindings are in scope for the children that follow it.
+enum Child {
+    View(TokenStream),
+    Statements(TokenStream),
+}
+
+/// Nests the children that follow each block of statements inside that block, so that
+/// any `let` bindings it makes are in scope for them.
+fn scope_statements(children: Vec<Child>) -> Vec<TokenStream> {
+    let mut scoped = Vec::new();
+    for child in children.into_iter().rev() {
+        match child {
+            Child::View(view) => scoped.push(view),
+            Child::Statements(stmts) => {
+                scoped.reverse();
+                let rest = match scoped.len() {
+                    1 => scoped.remove(0),
+                    _ if scoped.len() > 16 => {
+                        let chunks = scoped.chunks(16).map(|children| {
+                            quote! {
+                                (#(#children),*)
+                            }
+                        });
+                        quote! { (#(#chunks),*) }
+                    }
+                    _ => quote! { (#(#scoped),*) },
+                };
+                scoped = vec![quote! {
+                    {
+                        #stmts
+                        #rest
+                    }
+                }];
+            }
+        }
+    }
+    scoped.reverse();
+    scoped
+}
+
 fn node_to_tokens(
     node: &mut Node<impl CustomNode>,
     parent_type: TagType,
@@ -579,6 +634,13 @@ fn node_to_tokens(
             disable_inert_html,
         ),
         Node::Block(block) => {
+            // `#[either]` blocks have each branch wrapped in an `Either`, so that
+            // branches can return different types of view
+            let block = match block {
+                NodeBlock::ValidBlock(inner) => control_flow_to_tokens(inner)
+                    .unwrap_or_else(|| block.to_token_stream()),
+                _ => block.to_token_stream(),
+            };
             Some(quote! { ::leptos::prelude::IntoRender::into_render(#block) })
         }
         Node::Text(text) => Some(text_to_tokens(&text.value)),
@@ -992,6 +1054,21 @@ fn attribute_to_tokens(
                     _ => unreachable!(),
                 };
                 style_to_tokens(node, style.into_token_stream(), None)
+            } else if let Some(name) = name.strip_prefix("attr:raw:") {
+                let value = attribute_value(node, true);
+                quote! {
+                    .attr(#name, ::leptos::tachys::html::attribute::Raw(#value))
+                }
+            } else if let Some(name) = name.strip_prefix("attr:") {
+                let value = attribute_value(node, true);
+                quote! {
+                    .attr(#name, #value)
+                }
+            } else if let Some(name) = name.strip_prefix("auto:") {
+                let value = attribute_value(node, false);
+                quote! {
+                    .add_any_attr(::leptos::tachys::html::property::prop_or_attr(#name, #value))
+                }
             } else if let Some(name) = name.strip_prefix("prop:") {
                 let prop = match &node.key {
                     NodeName::Punctuated(parts) => &parts[0],
@@ -1047,6 +1124,7 @@ pub(crate) fn attribute_absolute(
         || key.starts_with("style:")
         || key.starts_with("class:")
         || key.starts_with("prop:")
+        || key.starts_with("auto:")
         || key.starts_with("use:");
     // anything that follows the x:y pattern
     match &node.key {
@@ -1063,7 +1141,16 @@ pub(crate) fn attribute_absolute(
                             let multipart = parts.len() > 2;
                             let key = &parts[1];
                             let key_name = key.to_string();
-                            if key_name == "class" || key_name == "style" {
+                            if key_name == "raw" && multipart {
+                                // e.g., attr:raw:data-foo="bar"
+                                let key_name = parts.pairs().skip(2).map(|p| match p {
+                                    Punctuated(n, p) => format!("{n}{p}"),
+                                    End(n) => n.to_string(),
+                                }).collect::<String>();
+                                Some(
+                                    quote! { ::leptos::tachys::html::attribute::custom::custom_attribute(#key_name, ::leptos::tachys::html::attribute::Raw(#value)) },
+                                )
+                            } else if key_name == "class" || key_name == "style" {
                                 Some(
                                     quote! { ::leptos::tachys::html::#key::#key(#value) },
                                 )
@@ -1121,6 +1208,13 @@ pub(crate) fn attribute_absolute(
                             Some(
                                 quote! { ::leptos::tachys::html::property::#id(#key, #value) },
                             )
+                        } else if id == "auto" {
+                            let value = attribute_value(node, false);
+                            let key = &node.key.to_string();
+                            let key = key.replacen("auto:", "", 1);
+                            Some(
+                                quote! { ::leptos::tachys::html::property::prop_or_attr(#key, #value) },
+                            )
                         } else if id == "on" {
                             let key = &node.key.to_string();
                             let key = key.replacen("on:", "", 1);
@@ -1234,6 +1328,18 @@ pub(crate) fn event_type_and_handler(
         }
         _ => unreachable!(),
     };
+    let passive_ident = match &node.key {
+        NodeName::Punctuated(parts) => {
+            parts.iter().find(|part| part.to_string() == "passive")
+        }
+        _ => unreachable!(),
+    };
+    let once_ident = match &node.key {
+        NodeName::Punctuated(parts) => {
+            parts.iter().find(|part| part.to_string() == "once")
+        }
+        _ => unreachable!(),
+    };
     let on = match &node.key {
         NodeName::Punctuated(parts) => &parts[0],
         _ => unreachable!(),
@@ -1243,16 +1349,34 @@ pub(crate) fn event_type_and_handler(
     } else {
         on.to_token_stream()
     };
-    let event_type = if is_custom {
-        event_type
-    } else if let Some(ev_name) = event_name_ident {
+    // `on:typed:SomeEvent` refers to an event type that is in scope at the call
+    // site, such as one declared with `define_custom_event!`
+    let user_event_type = match &node.key {
+        NodeName::Punctuated(parts)
+            if parts.len() >= 3
+                && parts[1].to_string() == "typed"
+                && !EVENT_MODIFIERS
+                    .contains(&parts[2].to_string().as_str()) =>
+        {
+            Some(&parts[2])
+        }
+        _ => None,
+    };
+
+    let event_type = if let Some(ev_name) = user_event_type {
         quote! { #ev_name }
     } else {
-        event_type
-    };
+        let event_type = if is_custom {
+            event_type
+        } else if let Some(ev_name) = event_name_ident {
+            quote! { #ev_name }
+        } else {
+            event_type
+        };
 
-    let event_type = quote! {
-        ::leptos::tachys::html::event::#event_type
+        quote! {
+            ::leptos::tachys::html::event::#event_type
+        }
     };
     let event_type = if options.captured {
         let capture = if let Some(capture) = capture_ident {
@@ -1265,6 +1389,28 @@ pub(crate) fn event_type_and_handler(
         event_type
     };
 
+    let event_type = if options.passive {
+        let passive = if let Some(passive) = passive_ident {
+            quote! { #passive }
+        } else {
+            quote! { passive }
+        };
+        quote! { ::leptos::tachys::html::event::#passive(#event_type) }
+    } else {
+        event_type
+    };
+
+    let event_type = if options.once {
+        let once = if let Some(once) = once_ident {
+            quote! { #once }
+        } else {
+            quote! { once }
+        };
+        quote! { ::leptos::tachys::html::event::#once(#event_type) }
+    } else {
+        event_type
+    };
+
     let event_type = if options.undelegated {
         let undelegated = if let Some(undelegated) = undelegated_ident {
             quote! { #undelegated }
@@ -1483,16 +1629,22 @@ fn parse_event(event_name: &str) -> (String, EventNameOptions) {
     let undelegated = event_name.contains(":undelegated");
     let targeted = event_name.contains(":target");
     let captured = event_name.contains(":capture");
+    let passive = event_name.contains(":passive");
+    let once = event_name.contains(":once");
     let event_name = event_name
         .replace(":undelegated", "")
         .replace(":target", "")
-        .replace(":capture", "");
+        .replace(":capture", "")
+        .replace(":passive", "")
+        .replace(":once", "");
     (
         event_name,
         EventNameOptions {
             undelegated,
             targeted,
             captured,
+            passive,
+            once,
         },
     )
 }
@@ -1686,11 +1838,16 @@ const TYPED_EVENTS: [&str; 126] = [
 
 const CUSTOM_EVENT: &str = "Custom";
 
+const EVENT_MODIFIERS: [&str; 5] =
+    ["undelegated", "target", "capture", "passive", "once"];
+
 #[derive(Debug)]
 pub(crate) struct EventNameOptions {
     undelegated: bool,
     targeted: bool,
     captured: bool,
+    passive: bool,
+    once: bool,
 }
 
 pub(crate) fn parse_event_name(
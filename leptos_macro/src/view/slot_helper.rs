@@ -165,24 +165,9 @@ pub(crate) fn slot_to_tokens(
         }
     };
 
-    let slots = slots.drain().map(|(slot, mut values)| {
-        let span = values
-            .last()
-            .expect("List of slots must not be empty")
-            .span();
-        let slot = Ident::new(&slot, span);
-        let value = if values.len() > 1 {
-            quote! {
-                ::std::vec![
-                    #(#values)*
-                ]
-            }
-        } else {
-            values.remove(0)
-        };
-
-        quote! { .#slot(#value) }
-    });
+    let slots = slots
+        .drain()
+        .map(|(slot, values)| slot_setter(slot, values));
 
     let build = quote_spanned! {node.name().span()=>
         .build()
@@ -197,8 +182,7 @@ pub(crate) fn slot_to_tokens(
                 #build
                 #dyn_attrs;
 
-            #[allow(unreachable_code, clippy::useless_conversion)]
-            slot.into()
+            slot
         },
     };
 
@@ -211,6 +195,37 @@ pub(crate) fn slot_to_tokens(
         .or_insert(vec![slot]);
 }
 
+/// Passes the values of a slot to the setter of the parent's prop for that slot.
+///
+/// A slot that was passed once is converted into the type of the prop, which may be the slot
+/// itself or a collection of slots. A slot that was passed more than once is collected into a
+/// `Vec`, which only converts into props that accept more than one value.
+pub(crate) fn slot_setter(
+    slot: String,
+    mut values: Vec<TokenStream>,
+) -> TokenStream {
+    let span = values
+        .last()
+        .expect("List of slots must not be empty")
+        .span();
+    let slot = Ident::new(&slot, span);
+    let value = if values.len() > 1 {
+        quote_spanned! {span=>
+            ::leptos::slot::IntoSlots::into_slots(::std::vec![
+                #(#values)*
+            ])
+        }
+    } else {
+        let value = values.remove(0);
+        quote! {
+            #[allow(unreachable_code, clippy::useless_conversion)]
+            ::std::convert::Into::into(#value)
+        }
+    };
+
+    quote! { .#slot(#value) }
+}
+
 pub(crate) fn is_slot(node: &KeyedAttribute) -> bool {
     let key = node.key.to_string();
     let key = key.trim();
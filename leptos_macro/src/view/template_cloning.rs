@@ -0,0 +1,75 @@
+//! Support for `#[template]` views, in which `view!` renders a view whose
+//! structure is entirely known at compile time by cloning a single cached `<template>`,
+//! rather than creating each element individually.
+
+use super::is_spread_marker;
+use leptos_hot_reload::parsing::is_component_node;
+use rstml::node::{CustomNode, Node, NodeAttribute, NodeBlock, NodeElement};
+use syn::{Expr, Stmt};
+
+/// Returns `true` if the view can be rendered from a cloned `<template>`.
+///
+/// This is the case if the view is a single HTML element, and every node inside it is
+/// either an HTML element or static text. Attribute values must be literals or `move`
+/// closures, so that their types can be used to build the template. Any view that contains
+/// a component, a dynamic child, or a spread is rendered as usual.
+pub(crate) fn can_clone_template(nodes: &[Node<impl CustomNode>]) -> bool {
+    match nodes {
+        [Node::Element(el)] => element_can_be_cloned(el),
+        _ => false,
+    }
+}
+
+fn element_can_be_cloned(el: &NodeElement<impl CustomNode>) -> bool {
+    // custom elements don't have a tag name known at compile time, so they can't be
+    // included in a template
+    if is_component_node(el)
+        || is_spread_marker(el)
+        || el.name().to_string().contains('-')
+    {
+        return false;
+    }
+
+    el.attributes().iter().all(attribute_can_be_cloned)
+        && el.children.iter().all(node_can_be_cloned)
+}
+
+fn node_can_be_cloned(node: &Node<impl CustomNode>) -> bool {
+    match node {
+        Node::Comment(_) | Node::Text(_) | Node::RawText(_) => true,
+        Node::Element(el) => element_can_be_cloned(el),
+        Node::Fragment(fragment) => {
+            fragment.children.iter().all(node_can_be_cloned)
+        }
+        Node::Block(NodeBlock::ValidBlock(block)) => {
+            matches!(block.stmts.as_slice(), [Stmt::Expr(Expr::Lit(_), None)])
+        }
+        _ => false,
+    }
+}
+
+fn attribute_can_be_cloned(attr: &NodeAttribute) -> bool {
+    let NodeAttribute::Attribute(attr) = attr else {
+        return false;
+    };
+    let key = attr.key.to_string();
+    if key == "node_ref"
+        || key == "inner_html"
+        || key == "inner_html_sanitized"
+        || key.starts_with("let:")
+        || key.starts_with("clone:")
+    {
+        false
+    } else if key.starts_with("on:")
+        || key.starts_with("use:")
+        || key.starts_with("bind:")
+    {
+        true
+    } else {
+        match attr.value() {
+            None | Some(Expr::Lit(_)) => true,
+            Some(Expr::Closure(closure)) => closure.capture.is_some(),
+            _ => false,
+        }
+    }
+}
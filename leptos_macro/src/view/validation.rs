@@ -0,0 +1,394 @@
+//! Compile-time checks for common HTML mistakes in `view!`, enabled by the
+//! `html_validation` feature.
+//!
+//! Most of these are mistakes that the browser's HTML parser silently "fixes" when it parses
+//! server-rendered HTML (for example, by closing a `<p>` before a `<div>`, or by inserting a
+//! `<tbody>`), which leaves the DOM in a different shape from the one the view describes and
+//! causes a hydration error at runtime.
+
+use super::{is_math_ml_element, is_spread_marker, is_svg_element};
+use leptos_hot_reload::parsing::{is_component_node, value_to_string};
+use proc_macro2::Span;
+use proc_macro_error2::emit_error;
+use rstml::node::{CustomNode, Node, NodeAttribute, NodeElement, NodeName};
+use std::collections::HashSet;
+use syn::spanned::Spanned;
+
+const HTML_ELEMENTS: [&str; 114] = [
+    "a",
+    "abbr",
+    "address",
+    "area",
+    "article",
+    "aside",
+    "audio",
+    "b",
+    "base",
+    "bdi",
+    "bdo",
+    "blockquote",
+    "body",
+    "br",
+    "button",
+    "canvas",
+    "caption",
+    "cite",
+    "code",
+    "col",
+    "colgroup",
+    "data",
+    "datalist",
+    "dd",
+    "del",
+    "details",
+    "dfn",
+    "dialog",
+    "div",
+    "dl",
+    "dt",
+    "em",
+    "embed",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "head",
+    "header",
+    "hgroup",
+    "hr",
+    "html",
+    "i",
+    "iframe",
+    "img",
+    "input",
+    "ins",
+    "kbd",
+    "label",
+    "legend",
+    "li",
+    "link",
+    "main",
+    "map",
+    "mark",
+    "menu",
+    "meta",
+    "meter",
+    "nav",
+    "noscript",
+    "object",
+    "ol",
+    "optgroup",
+    "option",
+    "output",
+    "p",
+    "param",
+    "picture",
+    "portal",
+    "pre",
+    "progress",
+    "q",
+    "rp",
+    "rt",
+    "ruby",
+    "s",
+    "samp",
+    "script",
+    "search",
+    "section",
+    "select",
+    "slot",
+    "small",
+    "source",
+    "span",
+    "strong",
+    "style",
+    "sub",
+    "summary",
+    "sup",
+    "table",
+    "tbody",
+    "td",
+    "template",
+    "textarea",
+    "tfoot",
+    "th",
+    "thead",
+    "time",
+    "title",
+    "tr",
+    "track",
+    "u",
+    "ul",
+    "var",
+    "video",
+    "wbr",
+];
+
+/// Elements whose start tag implicitly closes an open `<p>`.
+const CLOSES_P: [&str; 29] = [
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "details",
+    "dialog",
+    "div",
+    "dl",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hgroup",
+    "hr",
+    "main",
+    "menu",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "section",
+];
+
+/// Elements that limit how far up the tree the parser looks for an open `<p>`, `<a>`,
+/// `<button>`, or `<form>`.
+const SCOPE_BOUNDARIES: [&str; 9] = [
+    "caption", "html", "object", "table", "td", "template", "th", "svg", "math",
+];
+
+/// Checks the nodes of a single `view!` invocation, emitting an error for each problem found.
+pub(crate) fn validate_view(nodes: &[Node<impl CustomNode>]) {
+    let mut ids = HashSet::new();
+    validate_nodes(nodes, &mut Vec::new(), &mut ids);
+}
+
+fn validate_nodes(
+    nodes: &[Node<impl CustomNode>],
+    ancestors: &mut Vec<String>,
+    ids: &mut HashSet<String>,
+) {
+    for node in nodes {
+        match node {
+            Node::Element(el) => validate_element(el, ancestors, ids),
+            Node::Fragment(fragment) => {
+                validate_nodes(&fragment.children, ancestors, ids)
+            }
+            _ => {}
+        }
+    }
+}
+
+fn validate_element(
+    el: &NodeElement<impl CustomNode>,
+    ancestors: &mut Vec<String>,
+    ids: &mut HashSet<String>,
+) {
+    if is_spread_marker(el) {
+        return;
+    }
+    // components and slots can be rendered anywhere, so their children are checked without
+    // any knowledge of their ancestors
+    if is_component_node(el) {
+        validate_nodes(&el.children, &mut Vec::new(), ids);
+        return;
+    }
+
+    let name = el.name().to_string();
+    let span = el.name().span();
+    let in_foreign_content = ancestors
+        .iter()
+        .any(|ancestor| ancestor == "svg" || ancestor == "math");
+    let is_custom = name.contains('-');
+    let is_html = !in_foreign_content
+        && !is_custom
+        && !is_svg_element(&name)
+        && !is_math_ml_element(&name);
+
+    if is_html && HTML_ELEMENTS.binary_search(&name.as_str()).is_err() {
+        let lowercase = name.to_lowercase();
+        if HTML_ELEMENTS.binary_search(&lowercase.as_str()).is_ok() {
+            emit_error!(
+                span,
+                "`<{}>` is not a valid HTML element name; did you mean `<{}>`?",
+                name,
+                lowercase
+            );
+        } else {
+            emit_error!(
+                span,
+                "`<{}>` is not a known HTML element. Custom elements must \
+                 contain a hyphen (`-`) in their names.",
+                name
+            );
+        }
+    }
+
+    if is_html {
+        validate_nesting(&name, span, ancestors);
+    }
+
+    for attr in el.attributes() {
+        let NodeAttribute::Attribute(attr) = attr else {
+            continue;
+        };
+        let NodeName::Path(path) = &attr.key else {
+            continue;
+        };
+        let Some(key) = path.path.get_ident().map(ToString::to_string) else {
+            continue;
+        };
+
+        if key == "id" {
+            if let Some(id) = attr.value().and_then(value_to_string) {
+                if !ids.insert(id.clone()) {
+                    emit_error!(
+                        attr.span(),
+                        "the id `{}` is used more than once in this view",
+                        id
+                    );
+                }
+            }
+        }
+
+        if is_html && key.chars().any(|c| c.is_ascii_uppercase()) {
+            emit_error!(
+                attr.key.span(),
+                "`{}` is not a valid HTML attribute name; did you mean `{}`?",
+                key,
+                suggest_attribute(&key)
+            );
+        }
+    }
+
+    ancestors.push(name);
+    validate_nodes(&el.children, ancestors, ids);
+    ancestors.pop();
+}
+
+fn validate_nesting(name: &str, span: Span, ancestors: &[String]) {
+    let parent = ancestors.last().map(String::as_str);
+    let in_scope = |target: &str| {
+        ancestors
+            .iter()
+            .rev()
+            .take_while(|ancestor| {
+                !SCOPE_BOUNDARIES.contains(&ancestor.as_str())
+            })
+            .any(|ancestor| ancestor == target)
+    };
+
+    if CLOSES_P.contains(&name) && in_scope("p") {
+        emit_error!(
+            span,
+            "`<{}>` cannot be placed inside `<p>`: the browser will close the \
+             `<p>` before it, causing a hydration error. Use a `<div>` instead \
+             of the `<p>`.",
+            name
+        );
+    }
+
+    for nested in ["a", "button", "form"] {
+        if name == nested && in_scope(nested) {
+            emit_error!(
+                span,
+                "`<{}>` cannot be nested inside another `<{}>`: the browser \
+                 will close or drop the outer element, causing a hydration \
+                 error.",
+                name,
+                nested
+            );
+        }
+    }
+
+    match (name, parent) {
+        ("li", Some("li")) => emit_error!(
+            span,
+            "`<li>` cannot be a direct child of another `<li>`; wrap it in a \
+             `<ul>` or `<ol>`."
+        ),
+        (
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6",
+            Some("h1" | "h2" | "h3" | "h4" | "h5" | "h6"),
+        ) => emit_error!(
+            span,
+            "headings cannot be nested: the browser will close the `<{}>` \
+             before the `<{}>`.",
+            parent.unwrap_or_default(),
+            name
+        ),
+        ("tr", Some("table")) => emit_error!(
+            span,
+            "`<tr>` cannot be a direct child of `<table>`: the browser will \
+             insert a `<tbody>`, causing a hydration error. Wrap the rows in \
+             `<tbody>`."
+        ),
+        ("tr", Some(parent))
+            if !matches!(parent, "thead" | "tbody" | "tfoot") =>
+        {
+            emit_error!(
+                span,
+                "`<tr>` must be a child of `<thead>`, `<tbody>`, or `<tfoot>`, \
+                 not `<{}>`.",
+                parent
+            )
+        }
+        ("td" | "th", Some(parent)) if parent != "tr" => emit_error!(
+            span,
+            "`<{}>` must be a child of `<tr>`, not `<{}>`.",
+            name,
+            parent
+        ),
+        (_, Some("table"))
+            if !matches!(
+                name,
+                "caption"
+                    | "colgroup"
+                    | "thead"
+                    | "tbody"
+                    | "tfoot"
+                    | "script"
+                    | "template"
+                    | "style"
+            ) =>
+        {
+            emit_error!(
+                span,
+                "`<{}>` cannot be a direct child of `<table>`: the browser \
+                 will move it outside of the table, causing a hydration error.",
+                name
+            )
+        }
+        _ => {}
+    }
+}
+
+/// Suggests the correct spelling for a wrongly-cased attribute name, such as `className` or
+/// `onClick`.
+fn suggest_attribute(key: &str) -> String {
+    match key {
+        "className" => "class".to_string(),
+        "htmlFor" => "for".to_string(),
+        _ => match key.strip_prefix("on") {
+            Some(event)
+                if event.starts_with(|c: char| c.is_ascii_uppercase()) =>
+            {
+                format!("on:{}", event.to_lowercase())
+            }
+            _ => key.to_lowercase(),
+        },
+    }
+}
@@ -119,3 +119,57 @@ fn returns_static_lifetime() {
         WithLifetime(WithLifetimeProps::builder().data(&val).build())
     }
 }
+
+#[component]
+fn WithWhereClause<T>(items: Vec<T>) -> impl IntoView
+where
+    T: std::fmt::Display + Send + 'static,
+{
+    items.into_iter().map(|i| i.to_string()).collect::<Vec<_>>()
+}
+
+#[component]
+fn WithConstGeneric<const N: usize>(cells: [u8; N]) -> impl IntoView {
+    cells.len()
+}
+
+#[component]
+fn ParsedOnlyInBody<T>(
+    #[prop(into)] raw: String,
+    fallback: impl Fn() -> String + Send + 'static,
+) -> impl IntoView
+where
+    T: std::str::FromStr + std::fmt::Display + 'static,
+{
+    raw.parse::<T>()
+        .map(|value| value.to_string())
+        .unwrap_or_else(|_| fallback())
+}
+
+#[component]
+fn GenericChildren<T, F, IV>(items: Vec<T>, children: F) -> impl IntoView
+where
+    T: Send + 'static,
+    F: Fn(T) -> IV + Send + 'static,
+    IV: IntoView + 'static,
+{
+    items.into_iter().map(children).collect::<Vec<_>>()
+}
+
+#[test]
+fn generic_components() {
+    let props = ParsedOnlyInBodyProps::<u8, _>::builder()
+        .raw("12")
+        .fallback(|| "invalid".to_string())
+        .build();
+    assert_eq!(props.raw, "12");
+
+    _ = view! {
+        <WithWhereClause items=vec![1, 2, 3]/>
+        <WithConstGeneric cells=[1, 2, 3]/>
+        <ParsedOnlyInBody<u8, _> raw="300" fallback=|| "too large".to_string()/>
+        <GenericChildren items=vec![1, 2] let:item>
+            <span>{item}</span>
+        </GenericChildren>
+    };
+}
@@ -0,0 +1,63 @@
+use leptos::{
+    prelude::*,
+    reactive::owner::Owner,
+    scoped_style::{bundle, UsedScopedStyles},
+};
+
+fn card() -> &'static str {
+    style!(
+        r#"
+        /* the card itself */
+        .card, .card > h2 { color: red; }
+        .card:hover::after { content: "a, b { }"; }
+        :global(.dark) .card p { color: white; }
+        @media (max-width: 600px) {
+            .card { padding: 0; }
+        }
+        @keyframes fade { from { opacity: 0; } to { opacity: 1; } }
+        "#
+    )
+}
+
+#[test]
+fn selectors_are_scoped_to_the_class() {
+    let owner = Owner::new();
+    owner.set();
+    let used = UsedScopedStyles::new();
+    provide_context(used.clone());
+
+    let class = card();
+    assert!(class.starts_with("leptos-"));
+    assert_eq!(card(), class);
+    assert_eq!(used.classes(), [class]);
+
+    let css = format!(
+        ".card.{class},.card.{class}>h2.{class}{{color: red;}}\
+         .card:hover.{class}::after{{content: \"a, b {{ }}\";}}\
+         .dark .card.{class} p.{class}{{color: white;}}\
+         @media (max-width: 600px){{.card.{class}{{padding: 0;}}}}\
+         @keyframes fade{{from {{ opacity: 0; }} to {{ opacity: 1; }}}}"
+    );
+    assert_eq!(
+        used.to_html(),
+        format!("<style data-leptos-scoped=\"{class}\">{css}</style>")
+    );
+}
+
+#[test]
+fn bundle_only_includes_the_styles_in_a_manifest() {
+    let dir = std::env::temp_dir()
+        .join(format!("leptos-scoped-css-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("app.app.manifest"), "leptos-b\nleptos-a\n")
+        .unwrap();
+    std::fs::write(dir.join("app.bin.app.manifest"), "leptos-a\n").unwrap();
+    std::fs::write(dir.join("leptos-a.css"), ".a{}").unwrap();
+    std::fs::write(dir.join("leptos-b.css"), ".b{}").unwrap();
+    // a style that has since been removed
+    std::fs::write(dir.join("leptos-c.css"), ".c{}").unwrap();
+
+    let css = bundle(&dir);
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(css.unwrap(), ".a{}\n.b{}\n");
+}
@@ -7,4 +7,8 @@ fn ui() {
     #[cfg(all(feature = "nightly", rustc_nightly))]
     t.compile_fail("tests/ui/component_absolute.rs");
     t.compile_fail("tests/ui/server.rs");
+    t.compile_fail("tests/ui/prop_group.rs");
+    t.compile_fail("tests/ui/slot.rs");
+    #[cfg(feature = "html_validation")]
+    t.compile_fail("tests/ui/html_validation.rs");
 }
@@ -0,0 +1,47 @@
+use leptos::prelude::*;
+
+fn block_inside_paragraph() -> impl IntoView {
+    view! {
+        <p>
+            <span><div>"closes the paragraph"</div></span>
+        </p>
+    }
+}
+
+fn nested_links() -> impl IntoView {
+    view! {
+        <a href="/outer">
+            <a href="/inner">"inner"</a>
+        </a>
+    }
+}
+
+fn row_outside_tbody() -> impl IntoView {
+    view! {
+        <table>
+            <tr><td>"cell"</td></tr>
+        </table>
+    }
+}
+
+fn duplicate_ids() -> impl IntoView {
+    view! {
+        <div id="main">
+            <span id="main"></span>
+        </div>
+    }
+}
+
+fn wrong_casing() -> impl IntoView {
+    view! {
+        <label htmlFor="name" tabIndex=0>"Name"</label>
+    }
+}
+
+fn unknown_element() -> impl IntoView {
+    view! {
+        <foo></foo>
+    }
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+use leptos::prelude::*;
+
+#[derive(PropGroup, Default)]
+struct CommonProps {
+    disabled: bool,
+}
+
+#[component]
+fn Label(text: &'static str) -> impl IntoView {
+    text
+}
+
+#[component]
+fn SpreadIntoComponentWithoutGroup() -> impl IntoView {
+    let common = CommonProps { disabled: true };
+    view! { <Label {..common} text="Save"/> }
+}
+
+fn main() {}
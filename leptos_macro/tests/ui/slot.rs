@@ -0,0 +1,42 @@
+use leptos::prelude::*;
+
+#[slot]
+struct Title {
+    children: Children,
+}
+
+#[slot]
+struct Footer {
+    children: Children,
+}
+
+#[component]
+fn Card(
+    title: Title,
+    #[prop(optional)] footer: Option<Footer>,
+) -> impl IntoView {
+    _ = (title, footer);
+}
+
+#[component]
+fn TwoTitles() -> impl IntoView {
+    view! {
+        <Card>
+            <Title slot>"First"</Title>
+            <Title slot>"Second"</Title>
+        </Card>
+    }
+}
+
+#[component]
+fn TwoFooters() -> impl IntoView {
+    view! {
+        <Card>
+            <Title slot>"Title"</Title>
+            <Footer slot>"First"</Footer>
+            <Footer slot>"Second"</Footer>
+        </Card>
+    }
+}
+
+fn main() {}
@@ -1,13 +1,13 @@
 use reactive_graph::{
     actions::{Action, ArcAction},
-    owner::use_context,
+    owner::with_context,
     traits::DefinedAt,
 };
 use server_fn::{
-    error::{FromServerFnError, ServerFnUrlError},
-    ServerFn,
+    error::{FromServerFnError, ServerFnErrorErr, ServerFnUrlError},
+    ActionInput, ServerFn,
 };
-use std::{ops::Deref, panic::Location, sync::Arc};
+use std::{future::Future, ops::Deref, panic::Location, sync::Arc};
 
 /// An error that can be caused by a server action.
 ///
@@ -52,7 +52,7 @@ where
 
 impl<S> ArcServerAction<S>
 where
-    S: ServerFn + Clone + Send + Sync + 'static,
+    S: ServerFn + ActionInput + Send + Sync + 'static,
     S::Output: Send + Sync + 'static,
     S::Error: Send + Sync + 'static,
     S::Error: FromServerFnError,
@@ -60,14 +60,14 @@ where
     /// Creates a new [`ArcAction`] that will call the server function `S` when dispatched.
     #[track_caller]
     pub fn new() -> Self {
-        let err = use_context::<ServerActionError>().and_then(|error| {
+        let err = with_context(ServerActionError::clone).and_then(|error| {
             (error.path() == S::PATH)
                 .then(|| ServerFnUrlError::<S::Error>::decode_err(error.err()))
                 .map(Err)
         });
         Self {
             inner: ArcAction::new_with_value(err, |input: &S| {
-                S::run_on_client(input.clone())
+                run_with_copy(input)
             }),
             #[cfg(any(debug_assertions, leptos_debuginfo))]
             defined_at: Location::caller(),
@@ -103,7 +103,7 @@ where
 
 impl<S> Default for ArcServerAction<S>
 where
-    S: ServerFn + Clone + Send + Sync + 'static,
+    S: ServerFn + ActionInput + Send + Sync + 'static,
     S::Output: Send + Sync + 'static,
     S::Error: Send + Sync + 'static,
 {
@@ -142,20 +142,20 @@ where
 
 impl<S> ServerAction<S>
 where
-    S: ServerFn + Send + Sync + Clone + 'static,
+    S: ServerFn + Send + Sync + ActionInput + 'static,
     S::Output: Send + Sync + 'static,
     S::Error: Send + Sync + 'static,
 {
     /// Creates a new [`Action`] that will call the server function `S` when dispatched.
     pub fn new() -> Self {
-        let err = use_context::<ServerActionError>().and_then(|error| {
+        let err = with_context(ServerActionError::clone).and_then(|error| {
             (error.path() == S::PATH)
                 .then(|| ServerFnUrlError::<S::Error>::decode_err(error.err()))
                 .map(Err)
         });
         Self {
             inner: Action::new_with_value(err, |input: &S| {
-                S::run_on_client(input.clone())
+                run_with_copy(input)
             }),
             #[cfg(any(debug_assertions, leptos_debuginfo))]
             defined_at: Location::caller(),
@@ -182,7 +182,7 @@ where
 
 impl<S> Deref for ServerAction<S>
 where
-    S: ServerFn + Clone + Send + Sync + 'static,
+    S: ServerFn + ActionInput + Send + Sync + 'static,
     S::Output: Send + Sync + 'static,
     S::Error: Send + Sync + 'static,
 {
@@ -205,7 +205,7 @@ where
 
 impl<S> Default for ServerAction<S>
 where
-    S: ServerFn + Clone + Send + Sync + 'static,
+    S: ServerFn + ActionInput + Send + Sync + 'static,
     S::Output: Send + Sync + 'static,
     S::Error: Send + Sync + 'static,
 {
@@ -230,3 +230,27 @@ where
         }
     }
 }
+
+/// Runs the server function with a copy of the input an action was dispatched with, as the
+/// action keeps the input itself.
+pub(crate) fn run_with_copy<S>(
+    input: &S,
+) -> impl Future<Output = Result<S::Output, S::Error>> + Send + 'static
+where
+    S: ServerFn + ActionInput + 'static,
+    S::Output: 'static,
+{
+    let input = input.copy_for_dispatch();
+    async move {
+        match input {
+            Some(input) => server_fn::mock::run_on_client(input).await,
+            None => {
+                Err(S::Error::from_server_fn_error(ServerFnErrorErr::Request(
+                    "the input of this action can't be sent to the server, \
+                     as it was read from a request"
+                        .to_string(),
+                )))
+            }
+        }
+    }
+}
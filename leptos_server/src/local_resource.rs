@@ -7,7 +7,7 @@ use reactive_graph::{
         AnySource, AnySubscriber, ReactiveNode, Source, Subscriber,
         ToAnySource, ToAnySubscriber,
     },
-    owner::use_context,
+    owner::with_context,
     send_wrapper_ext::SendOption,
     signal::{
         guards::{AsyncPlain, Mapped, ReadGuard},
@@ -45,7 +45,8 @@ impl<T> ArcLocalResource<T> {
     /// Creates the resource.
     ///
     /// This will only begin loading data if you are on the client (i.e., if you do not have the
-    /// `ssr` feature activated).
+    /// `ssr` feature activated). On the server, it is always pending, and can safely be read from
+    /// any thread during multithreaded server-side rendering.
     #[track_caller]
     pub fn new<Fut>(fetcher: impl Fn() -> Fut + 'static) -> Self
     where
@@ -131,7 +132,7 @@ where
     type IntoFuture = AsyncDerivedFuture<T>;
 
     fn into_future(self) -> Self::IntoFuture {
-        if let Some(mut notifier) = use_context::<LocalResourceNotifier>() {
+        if let Some(mut notifier) = with_context(LocalResourceNotifier::clone) {
             notifier.notify();
         } else if cfg!(feature = "ssr") {
             panic!(
@@ -165,7 +166,7 @@ where
         ReadGuard<Option<T>, Mapped<AsyncPlain<SendOption<T>>, Option<T>>>;
 
     fn try_read_untracked(&self) -> Option<Self::Value> {
-        if let Some(mut notifier) = use_context::<LocalResourceNotifier>() {
+        if let Some(mut notifier) = with_context(LocalResourceNotifier::clone) {
             notifier.notify();
         }
         self.data.try_read_untracked()
@@ -253,7 +254,8 @@ impl<T> LocalResource<T> {
     /// Creates the resource.
     ///
     /// This will only begin loading data if you are on the client (i.e., if you do not have the
-    /// `ssr` feature activated).
+    /// `ssr` feature activated). On the server, it is always pending, and can safely be read from
+    /// any thread during multithreaded server-side rendering.
     #[track_caller]
     pub fn new<Fut>(fetcher: impl Fn() -> Fut + 'static) -> Self
     where
@@ -338,7 +340,7 @@ where
     type IntoFuture = AsyncDerivedFuture<T>;
 
     fn into_future(self) -> Self::IntoFuture {
-        if let Some(mut notifier) = use_context::<LocalResourceNotifier>() {
+        if let Some(mut notifier) = with_context(LocalResourceNotifier::clone) {
             notifier.notify();
         } else if cfg!(feature = "ssr") {
             panic!(
@@ -372,7 +374,7 @@ where
         ReadGuard<Option<T>, Mapped<AsyncPlain<SendOption<T>>, Option<T>>>;
 
     fn try_read_untracked(&self) -> Option<Self::Value> {
-        if let Some(mut notifier) = use_context::<LocalResourceNotifier>() {
+        if let Some(mut notifier) = with_context(LocalResourceNotifier::clone) {
             notifier.notify();
         }
         self.data.try_read_untracked()
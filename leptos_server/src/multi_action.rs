@@ -1,8 +1,9 @@
+use crate::action::run_with_copy;
 use reactive_graph::{
     actions::{ArcMultiAction, MultiAction},
     traits::DefinedAt,
 };
-use server_fn::ServerFn;
+use server_fn::{ActionInput, ServerFn};
 use std::{ops::Deref, panic::Location};
 
 /// An [`ArcMultiAction`] that can be used to call a server function.
@@ -18,7 +19,7 @@ where
 
 impl<S> ArcServerMultiAction<S>
 where
-    S: ServerFn + Clone + Send + Sync + 'static,
+    S: ServerFn + ActionInput + Send + Sync + 'static,
     S::Output: Send + Sync + 'static,
     S::Error: Send + Sync + 'static,
 {
@@ -26,9 +27,7 @@ where
     #[track_caller]
     pub fn new() -> Self {
         Self {
-            inner: ArcMultiAction::new(|input: &S| {
-                S::run_on_client(input.clone())
-            }),
+            inner: ArcMultiAction::new(|input: &S| run_with_copy(input)),
             #[cfg(any(debug_assertions, leptos_debuginfo))]
             defined_at: Location::caller(),
         }
@@ -63,7 +62,7 @@ where
 
 impl<S> Default for ArcServerMultiAction<S>
 where
-    S: ServerFn + Clone + Send + Sync + 'static,
+    S: ServerFn + ActionInput + Send + Sync + 'static,
     S::Output: Send + Sync + 'static,
     S::Error: Send + Sync + 'static,
 {
@@ -113,16 +112,14 @@ where
 
 impl<S> ServerMultiAction<S>
 where
-    S: ServerFn + Send + Sync + Clone + 'static,
+    S: ServerFn + Send + Sync + ActionInput + 'static,
     S::Output: Send + Sync + 'static,
     S::Error: Send + Sync + 'static,
 {
     /// Creates a new [`MultiAction`] which, when dispatched, will call the server function `S`.
     pub fn new() -> Self {
         Self {
-            inner: MultiAction::new(|input: &S| {
-                S::run_on_client(input.clone())
-            }),
+            inner: MultiAction::new(|input: &S| run_with_copy(input)),
             #[cfg(any(debug_assertions, leptos_debuginfo))]
             defined_at: Location::caller(),
         }
@@ -161,7 +158,7 @@ where
 
 impl<S> Default for ServerMultiAction<S>
 where
-    S: ServerFn + Clone + Send + Sync + 'static,
+    S: ServerFn + ActionInput + Send + Sync + 'static,
     S::Output: Send + Sync + 'static,
     S::Error: Send + Sync + 'static,
 {
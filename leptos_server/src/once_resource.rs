@@ -23,7 +23,7 @@ use reactive_graph::{
     },
     diagnostics::{SpecialNonReactiveFuture, SpecialNonReactiveZone},
     graph::{AnySource, ToAnySource},
-    owner::{use_context, ArenaItem, Owner},
+    owner::{with_context, ArenaItem, Owner},
     prelude::*,
     signal::{
         guards::{Plain, ReadGuard},
@@ -255,7 +255,7 @@ where
     type Value = ReadGuard<Option<T>, Plain<Option<T>>>;
 
     fn try_read_untracked(&self) -> Option<Self::Value> {
-        if let Some(suspense_context) = use_context::<SuspenseContext>() {
+        if let Some(suspense_context) = with_context(SuspenseContext::clone) {
             if self.value.read().or_poisoned().is_none() {
                 let handle = suspense_context.task_id();
                 let ready = SpecialNonReactiveFuture::new(self.ready());
@@ -312,7 +312,7 @@ where
         let waker = cx.waker();
         self.source.track();
 
-        if let Some(suspense_context) = use_context::<SuspenseContext>() {
+        if let Some(suspense_context) = with_context(SuspenseContext::clone) {
             self.suspenses.write().or_poisoned().push(suspense_context);
         }
 
@@ -174,9 +174,10 @@ where
         {
             use reactive_graph::{
                 computed::suspense::SuspenseContext, effect::in_effect_scope,
-                owner::use_context,
+                owner::with_context,
             };
-            if !in_effect_scope() && use_context::<SuspenseContext>().is_none()
+            if !in_effect_scope()
+                && with_context(SuspenseContext::clone).is_none()
             {
                 let location = std::panic::Location::caller();
                 reactive_graph::log_warning(format_args!(
@@ -247,11 +248,20 @@ where
             let refetch = refetch.clone();
             move |_| (refetch.get(), source())
         });
+        #[cfg(feature = "tracing")]
+        let defined_at = Location::caller();
         let fun = {
             let source = source.clone();
             move || {
                 let (_, source) = source.get();
                 let fut = fetcher(source);
+                // attach each fetch to the span of the code that created the resource, e.g. the
+                // component or request being rendered
+                #[cfg(feature = "tracing")]
+                let fut = tracing::Instrument::instrument(
+                    fut,
+                    tracing::debug_span!("resource", %defined_at),
+                );
                 async move {
                     if IS_SUPPRESSING_RESOURCE_LOAD.load(Ordering::Relaxed) {
                         pending().await
@@ -854,9 +864,10 @@ where
         {
             use reactive_graph::{
                 computed::suspense::SuspenseContext, effect::in_effect_scope,
-                owner::use_context,
+                owner::with_context,
             };
-            if !in_effect_scope() && use_context::<SuspenseContext>().is_none()
+            if !in_effect_scope()
+                && with_context(SuspenseContext::clone).is_none()
             {
                 let location = std::panic::Location::caller();
                 reactive_graph::log_warning(format_args!(
@@ -0,0 +1,166 @@
+use crate::register;
+use leptos::{
+    component, i18n::Locales, oco::Oco, tachys::html::element::link, IntoView,
+};
+use std::sync::Arc;
+
+/// Injects `<link rel="alternate" hreflang="...">` tags for each locale that the current page is
+/// available in, so that search engines can find the translated versions of the page.
+///
+/// The `href` function is called with each locale and returns the URL of the page in that locale.
+/// It is reactive, so it can read the current location to build the URL for the current route.
+///
+/// If the `locale` of the current page is given, a `<link rel="canonical">` pointing to the page
+/// in that locale is also added. If a `default_locale` is given, it is also linked as the
+/// `x-default` alternate, for users whose language matches none of the locales.
+///
+/// The tags are keyed (see [`Meta`](crate::Meta)) by their `hreflang`, so that a nested route's
+/// `<AlternateLinks/>` replaces the links its parent set for the same locales. The canonical link
+/// uses the key `canonical`.
+///
+/// ```
+/// use leptos::prelude::*;
+/// use leptos_meta::*;
+///
+/// #[component]
+/// fn MyApp() -> impl IntoView {
+///     provide_meta_context();
+///
+///     view! {
+///       <AlternateLinks
+///         locales=["en", "de", "fr"]
+///         href=|locale: &str| format!("https://example.com/{locale}/about")
+///         locale="en"
+///         default_locale="en"
+///       />
+///       <main>
+///         // ...
+///       </main>
+///     }
+/// }
+/// ```
+///
+/// With [`load_locales!`](leptos::load_locales), [`LocaleList::all`] lists every locale of the
+/// application, and the current locale is that of its [`I18n`](leptos::i18n::I18n) context.
+/// Where a locale appears in the URL is up to the application's routes, and `leptos_meta` does
+/// not depend on the router, so `href` builds the URLs, for example from the current location:
+///
+/// ```rust,ignore
+/// leptos::load_locales!(default = "en");
+///
+/// use crate::i18n::Locale;
+/// use leptos::{i18n::*, prelude::*};
+/// use leptos_meta::*;
+/// use leptos_router::hooks::use_location;
+///
+/// #[component]
+/// fn LocalizedPage() -> impl IntoView {
+///     // changing the locale navigates to another route, which renders this again
+///     let current = untrack(|| use_i18n::<Locale>().locale());
+///     // the routes are nested under `/:locale`
+///     let pathname = use_location().pathname;
+///     let href = move |locale: &str| {
+///         let path = pathname.get();
+///         let rest = path.trim_start_matches('/').split_once('/');
+///         let rest = rest.map_or("", |(_, rest)| rest);
+///         format!("https://example.com/{locale}/{rest}")
+///     };
+///
+///     view! {
+///         <AlternateLinks
+///             locales={LocaleList::all::<Locale>()}
+///             href=href
+///             locale=current.as_str()
+///             default_locale=Locale::DEFAULT.as_str()
+///         />
+///     }
+/// }
+/// ```
+#[component]
+pub fn AlternateLinks(
+    /// The locales the page is available in, as [BCP 47](https://www.rfc-editor.org/info/bcp47)
+    /// language tags like `en` or `de-AT`.
+    #[prop(into)]
+    locales: LocaleList,
+    /// Returns the absolute URL of the current page in the given locale.
+    #[prop(into)]
+    href: LocaleHref,
+    /// The locale of the current page, used for the canonical link.
+    #[prop(optional, into)]
+    locale: Option<Oco<'static, str>>,
+    /// The locale to link as `hreflang="x-default"`.
+    #[prop(optional, into)]
+    default_locale: Option<Oco<'static, str>>,
+) -> impl IntoView {
+    let alternates = locales
+        .0
+        .into_iter()
+        .map(|locale| alternate_link(&href, locale.clone(), locale))
+        .collect::<Vec<_>>();
+    let default = default_locale
+        .map(|locale| alternate_link(&href, "x-default".into(), locale));
+    let canonical = locale.map(|locale| {
+        let href = href.clone();
+        register(link().rel("canonical").href(move || (href.0)(&locale)))
+            .with_key(Some("canonical".into()))
+    });
+
+    (alternates, default, canonical)
+}
+
+fn alternate_link(
+    href: &LocaleHref,
+    hreflang: Oco<'static, str>,
+    locale: Oco<'static, str>,
+) -> impl IntoView {
+    let href = href.clone();
+    let key = format!("alternate:{hreflang}");
+    register(
+        link()
+            .rel("alternate")
+            .hreflang(hreflang)
+            .href(move || (href.0)(&locale)),
+    )
+    .with_key(Some(key.into()))
+}
+
+/// The locales passed to [`AlternateLinks`].
+#[derive(Debug, Clone, Default)]
+pub struct LocaleList(Vec<Oco<'static, str>>);
+
+impl LocaleList {
+    /// Every locale of a set of [`Locales`], like the `Locale` enum generated by
+    /// [`load_locales!`](leptos::load_locales).
+    pub fn all<L: Locales>() -> Self {
+        LocaleList(L::ALL.iter().map(|locale| locale.as_str().into()).collect())
+    }
+}
+
+impl<T, I> From<I> for LocaleList
+where
+    I: IntoIterator<Item = T>,
+    T: Into<Oco<'static, str>>,
+{
+    fn from(locales: I) -> Self {
+        LocaleList(locales.into_iter().map(Into::into).collect())
+    }
+}
+
+/// A function that returns the URL of the current page in a given locale.
+#[derive(Clone)]
+pub struct LocaleHref(Arc<dyn Fn(&str) -> String + Send + Sync>);
+
+impl<F> From<F> for LocaleHref
+where
+    F: Fn(&str) -> String + Send + Sync + 'static,
+{
+    fn from(f: F) -> Self {
+        LocaleHref(Arc::new(f))
+    }
+}
+
+impl core::fmt::Debug for LocaleHref {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("LocaleHref").finish()
+    }
+}
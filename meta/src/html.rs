@@ -2,7 +2,7 @@ use crate::ServerMetaContext;
 use leptos::{
     attr::{any_attribute::AnyAttribute, NextAttribute},
     component, html,
-    reactive::owner::use_context,
+    reactive::owner::with_context,
     tachys::{
         dom::document,
         html::attribute::Attribute,
@@ -125,7 +125,7 @@ where
         _mark_branches: bool,
         extra_attrs: Vec<AnyAttribute>,
     ) {
-        if let Some(meta) = use_context::<ServerMetaContext>() {
+        if let Some(meta) = with_context(ServerMetaContext::clone) {
             let mut buf = String::new();
             _ = html::attributes_to_html(
                 (self.attributes, extra_attrs),
@@ -39,21 +39,27 @@
 //! # Feature Flags
 //! - `ssr` Server-side rendering: Generate an HTML string (typically on the server)
 //! - `tracing` Adds integration with the `tracing` crate.
+//! - `scoped-style` Inlines the styles of the `style!` macro of `leptos` in the `<head>`, and
+//!   adds [`ScopedStylesheet`].
 //!
 //! **Important Note:** If you’re using server-side rendering, you should enable `ssr`.
 
 use futures::{Stream, StreamExt};
+use indexmap::IndexMap;
+#[cfg(feature = "scoped-style")]
+use leptos::scoped_style::UsedScopedStyles;
 use leptos::{
     attr::{any_attribute::AnyAttribute, NextAttribute},
     component,
     logging::debug_warn,
     oco::Oco,
-    reactive::owner::{provide_context, use_context},
+    prelude::CustomAttribute,
+    reactive::owner::{provide_context, use_context, with_context},
     tachys::{
         dom::document,
         html::{
             attribute::Attribute,
-            element::{ElementType, HtmlElement},
+            element::{meta, ElementType, HtmlElement},
         },
         hydration::Cursor,
         view::{
@@ -64,33 +70,43 @@ use leptos::{
     IntoView,
 };
 use once_cell::sync::Lazy;
+use or_poisoned::OrPoisoned;
 use send_wrapper::SendWrapper;
 use std::{
+    collections::HashMap,
     fmt::Debug,
     sync::{
         mpsc::{channel, Receiver, Sender},
-        Arc,
+        Arc, Mutex,
     },
 };
 use wasm_bindgen::JsCast;
-use web_sys::HtmlHeadElement;
+use web_sys::{Element, HtmlHeadElement};
 
+mod alternate_links;
 mod body;
 mod html;
 mod link;
 mod meta_tags;
+mod open_graph;
 mod script;
+mod structured_data;
 mod style;
 mod stylesheet;
 mod title;
+mod web_app_manifest;
+pub use alternate_links::*;
 pub use body::*;
 pub use html::*;
 pub use link::*;
 pub use meta_tags::*;
+pub use open_graph::*;
 pub use script::*;
+pub use structured_data::*;
 pub use style::*;
 pub use stylesheet::*;
 pub use title::*;
+pub use web_app_manifest::*;
 
 /// Contains the current state of meta tags. To access it, you can use [`use_head`].
 ///
@@ -102,6 +118,9 @@ pub struct MetaContext {
     pub(crate) title: TitleContext,
     /// The hydration cursor for the location in the `<head>` for arbitrary tags will be rendered.
     pub(crate) cursor: Arc<Lazy<SendWrapper<Cursor>>>,
+    /// The mounted elements for each key used by a keyed tag, in the order they were mounted.
+    /// Only the last element for each key is actually in the document.
+    pub(crate) keyed: Arc<Mutex<HashMap<String, Vec<SendWrapper<Element>>>>>,
 }
 
 impl MetaContext {
@@ -109,6 +128,58 @@ impl MetaContext {
     pub fn new() -> Self {
         Default::default()
     }
+
+    /// Marks `el` as the current element for `key`, removing the previous one from the document.
+    fn push_keyed(&self, key: &str, el: Element) {
+        let mut keyed = self.keyed.lock().or_poisoned();
+        let stack = keyed.entry(key.to_string()).or_default();
+        stack.retain(|other| !other.is_same_node(Some(&el)));
+        if let Some(prev) = stack.last() {
+            prev.remove();
+        }
+        stack.push(SendWrapper::new(el));
+    }
+
+    /// Removes `el` from the elements for `key`, restoring the previous one to the document if
+    /// `el` was the current element.
+    fn remove_keyed(&self, key: &str, el: &Element) {
+        let mut keyed = self.keyed.lock().or_poisoned();
+        let Some(stack) = keyed.get_mut(key) else {
+            return;
+        };
+        let Some(pos) =
+            stack.iter().position(|other| other.is_same_node(Some(el)))
+        else {
+            return;
+        };
+        stack.remove(pos);
+        if pos == stack.len() {
+            if let Some(prev) = stack.last() {
+                _ = document_head().append_child(prev);
+            }
+        }
+        if stack.is_empty() {
+            keyed.remove(key);
+        }
+    }
+}
+
+/// The HTML of a keyed tag, as it is sent to the `<head>` during server rendering.
+#[derive(Debug)]
+#[allow(unused)] // used in SSR
+pub(crate) enum KeyedHtml {
+    /// A tag that has already been rendered.
+    Rendered(String),
+    /// A `<meta>` tag, named by the given attribute and its key, whose content is the document
+    /// title. It is only rendered once the `<head>` is sent and every `<Title/>` has been
+    /// rendered, and is left out if there is no title.
+    Title { attr: &'static str },
+}
+
+/// The text of the comment that precedes a keyed tag in server-rendered HTML.
+fn key_marker(key: &str) -> String {
+    // keys are arbitrary strings, so make sure they can't end the comment
+    format!("key:{}", key.replace("--", "- -"))
 }
 
 pub(crate) const HEAD_MARKER_COMMENT: &str = "HEAD";
@@ -147,6 +218,7 @@ impl Default for MetaContext {
         Self {
             title: Default::default(),
             cursor,
+            keyed: Default::default(),
         }
     }
 }
@@ -170,6 +242,12 @@ pub struct ServerMetaContext {
     /// Arbitrary elements to be added to the `<head>` as HTML.
     #[allow(unused)] // used in SSR
     pub(crate) elements: Sender<String>,
+    /// Keyed elements to be added to the `<head>` as HTML, along with their keys.
+    #[allow(unused)] // used in SSR
+    pub(crate) keyed_elements: Sender<(String, KeyedHtml)>,
+    /// The scoped styles used by the components that were rendered.
+    #[cfg(all(feature = "ssr", feature = "scoped-style"))]
+    pub(crate) scoped_styles: UsedScopedStyles,
 }
 
 /// Allows you to access `<head>` content that was inserted via [`ServerMetaContext`].
@@ -181,6 +259,9 @@ pub struct ServerMetaContextOutput {
     html: Receiver<String>,
     body: Receiver<String>,
     elements: Receiver<String>,
+    keyed_elements: Receiver<(String, KeyedHtml)>,
+    #[cfg(feature = "scoped-style")]
+    scoped_styles: UsedScopedStyles,
 }
 
 impl ServerMetaContext {
@@ -190,17 +271,26 @@ impl ServerMetaContext {
         let (html_tx, html_rx) = channel();
         let (body_tx, body_rx) = channel();
         let (elements_tx, elements_rx) = channel();
+        let (keyed_elements_tx, keyed_elements_rx) = channel();
+        #[cfg(feature = "scoped-style")]
+        let scoped_styles = UsedScopedStyles::new();
         let tx = ServerMetaContext {
             title: title.clone(),
             html: html_tx,
             body: body_tx,
             elements: elements_tx,
+            keyed_elements: keyed_elements_tx,
+            #[cfg(all(feature = "ssr", feature = "scoped-style"))]
+            scoped_styles: scoped_styles.clone(),
         };
         let rx = ServerMetaContextOutput {
             title,
             html: html_rx,
             body: body_rx,
             elements: elements_rx,
+            keyed_elements: keyed_elements_rx,
+            #[cfg(feature = "scoped-style")]
+            scoped_styles,
         };
         (tx, rx)
     }
@@ -217,7 +307,7 @@ impl ServerMetaContextOutput {
         mut stream: impl Stream<Item = String> + Send + Unpin,
     ) -> impl Stream<Item = String> + Send {
         // wait for the first chunk of the stream, to ensure our components hve run
-        let mut first_chunk = stream.next().await.unwrap_or_default();
+        let first_chunk = stream.next().await.unwrap_or_default();
 
         // create <title> tag
         let title = self.title.as_string();
@@ -226,40 +316,65 @@ impl ServerMetaContextOutput {
             .map(|n| "<title>".len() + n.len() + "</title>".len())
             .unwrap_or(0);
 
-        // collect all registered meta tags
+        // collect all registered meta tags, and the styles of the components that were rendered
         let meta_buf = self.elements.try_iter().collect::<String>();
+        #[cfg(feature = "scoped-style")]
+        let meta_buf = meta_buf + &self.scoped_styles.to_html();
+
+        // collect keyed meta tags, keeping only the last one rendered for each key
+        // these are placed before the marker, because they are not hydrated
+        let mut keyed = IndexMap::new();
+        for (key, html) in self.keyed_elements.try_iter() {
+            keyed.insert(key, html);
+        }
+        let keyed_buf = keyed
+            .into_iter()
+            .filter_map(|(key, html)| {
+                let html = match html {
+                    KeyedHtml::Rendered(html) => html,
+                    KeyedHtml::Title { attr } => meta()
+                        .attr(attr, key.clone())
+                        .content(title.as_deref()?.to_string())
+                        .to_html(),
+                };
+                Some(format!("<!--{}-->{html}", key_marker(&key)))
+            })
+            .collect::<String>();
 
         // get HTML strings for `<html>` and `<body>`
         let html_attrs = self.html.try_iter().collect::<String>();
         let body_attrs = self.body.try_iter().collect::<String>();
 
-        let mut modified_chunk = if title_len == 0 && meta_buf.is_empty() {
-            first_chunk
-        } else {
-            let mut buf = String::with_capacity(
-                first_chunk.len() + title_len + meta_buf.len(),
-            );
-            let head_loc = first_chunk
-                .find("</head>")
-                .expect("you are using leptos_meta without a </head> tag");
-            let marker_loc = first_chunk
-                .find("<!--HEAD-->")
-                .map(|pos| pos + "<!--HEAD-->".len())
-                .unwrap_or_else(|| {
-                    first_chunk.find("</head>").unwrap_or(head_loc)
-                });
-            let (before_marker, after_marker) =
-                first_chunk.split_at_mut(marker_loc);
-            buf.push_str(before_marker);
-            buf.push_str(&meta_buf);
-            if let Some(title) = title {
-                buf.push_str("<title>");
-                buf.push_str(&title);
-                buf.push_str("</title>");
-            }
-            buf.push_str(after_marker);
-            buf
-        };
+        let mut modified_chunk =
+            if title_len == 0 && meta_buf.is_empty() && keyed_buf.is_empty() {
+                first_chunk
+            } else {
+                let mut buf = String::with_capacity(
+                    first_chunk.len()
+                        + title_len
+                        + meta_buf.len()
+                        + keyed_buf.len(),
+                );
+                let head_loc = first_chunk
+                    .find("</head>")
+                    .expect("you are using leptos_meta without a </head> tag");
+                let (keyed_loc, marker_loc) = first_chunk
+                    .find("<!--HEAD-->")
+                    .map(|pos| (pos, pos + "<!--HEAD-->".len()))
+                    .unwrap_or((head_loc, head_loc));
+                buf.push_str(&first_chunk[..keyed_loc]);
+                buf.push_str(&keyed_buf);
+                buf.push_str(&first_chunk[keyed_loc..marker_loc]);
+                let after_marker = &first_chunk[marker_loc..];
+                buf.push_str(&meta_buf);
+                if let Some(title) = title {
+                    buf.push_str("<title>");
+                    buf.push_str(&title);
+                    buf.push_str("</title>");
+                }
+                buf.push_str(after_marker);
+                buf
+            };
 
         if !html_attrs.is_empty() {
             if let Some(index) = modified_chunk.find("<html") {
@@ -285,9 +400,14 @@ impl ServerMetaContextOutput {
 /// at the highest possible level, without overwriting a [`MetaContext`] that has already been provided
 /// (for example, by a server-rendering integration.)
 pub fn provide_meta_context() {
-    if use_context::<MetaContext>().is_none() {
+    if with_context(MetaContext::clone).is_none() {
         provide_context(MetaContext::new());
     }
+    // the scoped styles used while rendering on the server are inlined in the `<head>`
+    #[cfg(all(feature = "ssr", feature = "scoped-style"))]
+    if let Some(server) = with_context(ServerMetaContext::clone) {
+        provide_context(server.scoped_styles.clone());
+    }
 }
 
 /// Returns the current [`MetaContext`].
@@ -299,7 +419,7 @@ pub fn provide_meta_context() {
 /// call `use_head()` but a single [`MetaContext`] has not been provided at the application root.
 /// The best practice is always to call [`provide_meta_context`] early in the application.
 pub fn use_head() -> MetaContext {
-    match use_context::<MetaContext>() {
+    match with_context(MetaContext::clone) {
         None => {
             debug_warn!(
                 "use_head() is being called without a MetaContext being \
@@ -322,13 +442,45 @@ pub(crate) fn register<E, At, Ch>(
 where
     HtmlElement<E, At, Ch>: RenderHtml,
 {
-    RegisteredMetaTag { el }
+    RegisteredMetaTag { el, key: None }
 }
 
 struct RegisteredMetaTag<E, At, Ch> {
     // this is `None` if we've already taken it out to render to HTML on the server
     // we don't render it in place in RenderHtml, so it's fine
     el: HtmlElement<E, At, Ch>,
+    key: Option<TagKey>,
+}
+
+/// The key of a keyed tag: only the tag with a given key that was rendered or mounted last is
+/// included in the `<head>`.
+#[derive(Clone)]
+struct TagKey {
+    key: String,
+    meta: MetaContext,
+    // the attribute that names a `<meta>` tag whose content is the document title
+    title_attr: Option<&'static str>,
+}
+
+impl<E, At, Ch> RegisteredMetaTag<E, At, Ch> {
+    /// Sets the key of this tag, so that it replaces any other tag with the same key.
+    pub(crate) fn with_key(mut self, key: Option<Oco<'static, str>>) -> Self {
+        self.key = key.map(|key| TagKey {
+            key: key.to_string(),
+            meta: use_head(),
+            title_attr: None,
+        });
+        self
+    }
+
+    /// Marks this keyed `<meta>` tag, named by `attr`, as having the document title as its
+    /// content, so that it is rendered on the server once the title is known.
+    pub(crate) fn with_title_content(mut self, attr: &'static str) -> Self {
+        if let Some(key) = &mut self.key {
+            key.title_attr = Some(attr);
+        }
+        self
+    }
 }
 
 struct RegisteredMetaTagState<E, At, Ch>
@@ -336,6 +488,7 @@ where
     HtmlElement<E, At, Ch>: Render,
 {
     state: <HtmlElement<E, At, Ch> as Render>::State,
+    key: Option<TagKey>,
 }
 
 impl<E, At, Ch> Drop for RegisteredMetaTagState<E, At, Ch>
@@ -343,10 +496,37 @@ where
     HtmlElement<E, At, Ch>: Render,
 {
     fn drop(&mut self) {
+        if let Some(TagKey { key, meta, .. }) = &self.key {
+            for el in self.state.elements() {
+                meta.remove_keyed(key, &el);
+            }
+        }
         self.state.unmount();
     }
 }
 
+/// Removes the server-rendered tag for `key` from the `<head>`, if there is one.
+///
+/// Keyed tags are not hydrated, because only one of the tags for each key is rendered on the
+/// server; instead, each is created again and replaces the server-rendered tag.
+fn remove_server_keyed(key: &str) {
+    let head = document_head();
+    let marker = key_marker(key);
+    let mut child = head.first_child();
+    while let Some(this_child) = child {
+        if this_child.node_type() == COMMENT_NODE
+            && this_child.text_content().as_deref() == Some(marker.as_str())
+        {
+            if let Some(el) = this_child.next_sibling() {
+                _ = head.remove_child(&el);
+            }
+            _ = head.remove_child(&this_child);
+            return;
+        }
+        child = this_child.next_sibling();
+    }
+}
+
 fn document_head() -> HtmlHeadElement {
     let document = document();
     document.head().unwrap_or_else(|| {
@@ -367,7 +547,10 @@ where
 
     fn build(self) -> Self::State {
         let state = self.el.build();
-        RegisteredMetaTagState { state }
+        RegisteredMetaTagState {
+            state,
+            key: self.key,
+        }
     }
 
     fn rebuild(self, state: &mut Self::State) {
@@ -393,6 +576,7 @@ where
     {
         RegisteredMetaTag {
             el: self.el.add_any_attr(attr),
+            key: self.key,
         }
     }
 }
@@ -427,7 +611,20 @@ where
         // meta tags are rendered into the buffer stored into the context
         // the value has already been taken out, when we're on the server
         #[cfg(feature = "ssr")]
-        if let Some(cx) = use_context::<ServerMetaContext>() {
+        if let Some(cx) = with_context(ServerMetaContext::clone) {
+            // a tag with the title as its content is rendered once the title is known
+            if let Some(TagKey {
+                key,
+                title_attr: Some(attr),
+                ..
+            }) = &self.key
+            {
+                _ = cx
+                    .keyed_elements
+                    .send((key.clone(), KeyedHtml::Title { attr }));
+                return;
+            }
+
             let mut buf = String::new();
             self.el.to_html_with_buf(
                 &mut buf,
@@ -436,7 +633,13 @@ where
                 false,
                 vec![],
             );
-            _ = cx.elements.send(buf); // fails only if the receiver is already dropped
+            // these fail only if the receiver is already dropped
+            match self.key {
+                Some(TagKey { key, .. }) => {
+                    _ = cx.keyed_elements.send((key, KeyedHtml::Rendered(buf)));
+                }
+                None => _ = cx.elements.send(buf),
+            }
         } else {
             let msg = "tried to use a leptos_meta component without \
                        `ServerMetaContext` provided";
@@ -454,6 +657,13 @@ where
         _cursor: &Cursor,
         _position: &PositionState,
     ) -> Self::State {
+        if let Some(TagKey { key, .. }) = &self.key {
+            remove_server_keyed(key);
+            let mut state = self.build();
+            state.mount(&document_head(), None);
+            return state;
+        }
+
         let cursor = use_context::<MetaContext>()
             .expect(
                 "attempting to hydrate `leptos_meta` components without a \
@@ -464,12 +674,13 @@ where
             &cursor,
             &PositionState::new(Position::NextChild),
         );
-        RegisteredMetaTagState { state }
+        RegisteredMetaTagState { state, key: None }
     }
 
     fn into_owned(self) -> Self::Owned {
         RegisteredMetaTag {
             el: self.el.into_owned(),
+            key: self.key,
         }
     }
 }
@@ -481,6 +692,11 @@ where
     Ch: Render,
 {
     fn unmount(&mut self) {
+        if let Some(TagKey { key, meta, .. }) = &self.key {
+            for el in self.state.elements() {
+                meta.remove_keyed(key, &el);
+            }
+        }
         self.state.unmount();
     }
 
@@ -494,6 +710,11 @@ where
         // because it will call "mount" with the parent where it is located in the component tree,
         // but actually be mounted to the <head>
         self.state.mount(&document_head(), None);
+        if let Some(TagKey { key, meta, .. }) = &self.key {
+            for el in self.state.elements() {
+                meta.push_keyed(key, el);
+            }
+        }
     }
 
     fn insert_before_this(&self, _child: &mut dyn Mountable) -> bool {
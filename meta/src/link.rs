@@ -7,6 +7,10 @@ use leptos::{
 /// Injects an [`HTMLLinkElement`](https://developer.mozilla.org/en-US/docs/Web/API/HTMLLinkElement) into the document
 /// head, accepting any of the valid attributes for that tag.
 ///
+/// When server rendering, a link with `rel="preload"` or `rel="modulepreload"` is also sent as a
+/// `Link` header of the response, so that the browser can start to load the resource as soon as
+/// it receives the headers. (See [`PreloadLink`](leptos::request::PreloadLink).)
+///
 /// ```
 /// use leptos::prelude::*;
 /// use leptos_meta::*;
@@ -77,7 +81,22 @@ pub fn Link(
     /// The [`blocking`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/link#attr-blocking) attribute.
     #[prop(optional, into)]
     blocking: Option<Oco<'static, str>>,
+    /// A key that identifies this tag. If several tags have the same key, only the one that was
+    /// rendered last is included in the `<head>`, replacing the others.
+    #[prop(optional, into)]
+    key: Option<Oco<'static, str>>,
 ) -> impl IntoView {
+    #[cfg(feature = "ssr")]
+    if let Some(preload) = preload_link(
+        rel.as_deref(),
+        href.as_deref(),
+        as_.as_deref(),
+        type_.as_deref(),
+        crossorigin.as_deref(),
+    ) {
+        preload.send();
+    }
+
     // TODO additional attributes
     register(
         link()
@@ -98,4 +117,33 @@ pub fn Link(
             .r#type(type_)
             .blocking(blocking),
     )
+    .with_key(key)
+}
+
+/// The `Link` header that lets the browser start to load a preloaded resource before it has
+/// parsed the `<head>`.
+#[cfg(feature = "ssr")]
+fn preload_link(
+    rel: Option<&str>,
+    href: Option<&str>,
+    as_: Option<&str>,
+    type_: Option<&str>,
+    crossorigin: Option<&str>,
+) -> Option<leptos::request::PreloadLink> {
+    use leptos::request::PreloadLink;
+
+    let href = href?;
+    let preload = match rel? {
+        "preload" => PreloadLink::preload(href, as_?),
+        "modulepreload" => PreloadLink::modulepreload(href),
+        _ => return None,
+    };
+    let preload = match type_ {
+        Some(type_) => preload.type_(type_),
+        None => preload,
+    };
+    Some(match crossorigin {
+        Some(crossorigin) => preload.crossorigin(crossorigin),
+        None => preload,
+    })
 }
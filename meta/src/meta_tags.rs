@@ -1,6 +1,7 @@
 use crate::register;
 use leptos::{
     component,
+    oco::Oco,
     prelude::{CustomAttribute, GlobalAttributes},
     tachys::html::element::meta,
     text_prop::TextProp,
@@ -27,6 +28,32 @@ use leptos::{
 ///   }
 /// }
 /// ```
+///
+/// If a layout and a page both declare the same tag, give them the same `key`: the one rendered
+/// last replaces the other, both in the server-rendered HTML and in the browser, instead of the
+/// tag being included twice. [`Link`](crate::Link), [`Script`](crate::Script), and
+/// [`Style`](crate::Style) accept a `key` in the same way.
+///
+/// ```
+/// use leptos::prelude::*;
+/// use leptos_meta::*;
+///
+/// #[component]
+/// fn Layout() -> impl IntoView {
+///   view! {
+///     <Meta name="description" key="description" content="A Leptos fan site."/>
+///     // ... nested routes here
+///   }
+/// }
+///
+/// #[component]
+/// fn Page() -> impl IntoView {
+///   // replaces the layout's description while this page is mounted
+///   view! {
+///     <Meta name="description" key="description" content="All about signals."/>
+///   }
+/// }
+/// ```
 #[component]
 pub fn Meta(
     /// The [`charset`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/meta#attr-charset) attribute.
@@ -47,6 +74,10 @@ pub fn Meta(
     /// The [`content`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/meta#attr-content) attribute.
     #[prop(optional, into)]
     content: Option<TextProp>,
+    /// A key that identifies this tag. If several tags have the same key, only the one that was
+    /// rendered last is included in the `<head>`, replacing the others.
+    #[prop(optional, into)]
+    key: Option<Oco<'static, str>>,
 ) -> impl IntoView {
     register(
         meta()
@@ -57,4 +88,5 @@ pub fn Meta(
             .itemprop(itemprop.map(|v| move || v.get()))
             .content(content.map(|v| move || v.get())),
     )
+    .with_key(key)
 }
@@ -0,0 +1,186 @@
+use crate::{register, use_head};
+use leptos::{
+    component, either::Either, prelude::CustomAttribute,
+    tachys::html::element::meta, text_prop::TextProp, IntoView,
+};
+
+/// Injects the [Open Graph](https://ogp.me/) `<meta>` tags that control how a page is shown when
+/// it is shared on social media.
+///
+/// If no `title` is given, `og:title` falls back to the title set by the [`Title`](crate::Title)
+/// component, including its formatter, and is left out if there is no title. `og:type` defaults
+/// to `"website"`.
+///
+/// Each tag is keyed by its property (for example, `og:title`), so that if several components
+/// declare the same tag, the one rendered last replaces the others: an `og:description` set by a
+/// page replaces the one set by its layout. A [`Meta`](crate::Meta) with the same `key` will
+/// also replace it.
+///
+/// ```
+/// use leptos::prelude::*;
+/// use leptos_meta::*;
+///
+/// #[component]
+/// fn MyApp() -> impl IntoView {
+///     provide_meta_context();
+///
+///     view! {
+///       <Title text="Leptos Meta"/>
+///       <OpenGraph
+///         description="Tools to set HTML metadata."
+///         image="https://leptos.dev/og.png"
+///         url="https://leptos.dev"
+///         site_name="Leptos"
+///       />
+///       <main>
+///         // ...
+///       </main>
+///     }
+/// }
+/// ```
+#[component]
+pub fn OpenGraph(
+    /// The `og:title` of the page. Defaults to the current document title.
+    #[prop(optional, into)]
+    title: Option<TextProp>,
+    /// The `og:type` of the page. Defaults to `"website"`.
+    #[prop(optional, into)]
+    type_: Option<TextProp>,
+    /// The canonical URL of the page, as `og:url`.
+    #[prop(optional, into)]
+    url: Option<TextProp>,
+    /// The URL of an image that represents the page, as `og:image`.
+    #[prop(optional, into)]
+    image: Option<TextProp>,
+    /// A description of the image, as `og:image:alt`.
+    #[prop(optional, into)]
+    image_alt: Option<TextProp>,
+    /// A short description of the page, as `og:description`.
+    #[prop(optional, into)]
+    description: Option<TextProp>,
+    /// The name of the overall site, as `og:site_name`.
+    #[prop(optional, into)]
+    site_name: Option<TextProp>,
+    /// The locale of the page, such as `en_US`, as `og:locale`.
+    #[prop(optional, into)]
+    locale: Option<TextProp>,
+) -> impl IntoView {
+    let og_title = match title {
+        Some(title) => {
+            Either::Left(keyed_meta("property", "og:title", Some(title)))
+        }
+        None => Either::Right(title_meta("property", "og:title")),
+    };
+    let type_ = type_.unwrap_or_else(|| "website".into());
+
+    (
+        og_title,
+        keyed_meta("property", "og:type", Some(type_)),
+        keyed_meta("property", "og:url", url),
+        keyed_meta("property", "og:image", image),
+        keyed_meta("property", "og:image:alt", image_alt),
+        keyed_meta("property", "og:description", description),
+        keyed_meta("property", "og:site_name", site_name),
+        keyed_meta("property", "og:locale", locale),
+    )
+}
+
+/// Injects the [Twitter card](https://developer.x.com/en/docs/x-for-websites/cards/overview/markup)
+/// `<meta>` tags that control how a page is shown when it is shared on Twitter/X.
+///
+/// Twitter falls back to the corresponding Open Graph tags for the title, description, and
+/// image, so when used alongside [`OpenGraph`] these only need to be set if they should differ.
+/// `twitter:card` defaults to `"summary_large_image"` if an `image` is given, and to `"summary"`
+/// otherwise.
+///
+/// As with [`OpenGraph`], each tag is keyed by its name (for example, `twitter:card`), so that a
+/// tag declared by several components is only included once.
+///
+/// ```
+/// use leptos::prelude::*;
+/// use leptos_meta::*;
+///
+/// #[component]
+/// fn MyApp() -> impl IntoView {
+///     provide_meta_context();
+///
+///     view! {
+///       <OpenGraph title="Leptos" image="https://leptos.dev/og.png"/>
+///       <TwitterCard card="summary_large_image" site="@leptos_rs"/>
+///       <main>
+///         // ...
+///       </main>
+///     }
+/// }
+/// ```
+#[component]
+pub fn TwitterCard(
+    /// The type of card, as `twitter:card`.
+    #[prop(optional, into)]
+    card: Option<TextProp>,
+    /// The `@username` of the website, as `twitter:site`.
+    #[prop(optional, into)]
+    site: Option<TextProp>,
+    /// The `@username` of the content creator, as `twitter:creator`.
+    #[prop(optional, into)]
+    creator: Option<TextProp>,
+    /// The title of the page, as `twitter:title`.
+    #[prop(optional, into)]
+    title: Option<TextProp>,
+    /// A short description of the page, as `twitter:description`.
+    #[prop(optional, into)]
+    description: Option<TextProp>,
+    /// The URL of an image that represents the page, as `twitter:image`.
+    #[prop(optional, into)]
+    image: Option<TextProp>,
+    /// A description of the image, as `twitter:image:alt`.
+    #[prop(optional, into)]
+    image_alt: Option<TextProp>,
+) -> impl IntoView {
+    let card = card.unwrap_or_else(|| {
+        if image.is_some() {
+            "summary_large_image".into()
+        } else {
+            "summary".into()
+        }
+    });
+
+    (
+        keyed_meta("name", "twitter:card", Some(card)),
+        keyed_meta("name", "twitter:site", site),
+        keyed_meta("name", "twitter:creator", creator),
+        keyed_meta("name", "twitter:title", title),
+        keyed_meta("name", "twitter:description", description),
+        keyed_meta("name", "twitter:image", image),
+        keyed_meta("name", "twitter:image:alt", image_alt),
+    )
+}
+
+/// A `<meta>` tag that is rendered only if `content` is set, keyed by its property or name so that
+/// it replaces any other tag with the same key.
+fn keyed_meta(
+    attr: &'static str,
+    key: &'static str,
+    content: Option<TextProp>,
+) -> impl IntoView {
+    content.map(|content| {
+        register(meta().attr(attr, key).content(move || content.get()))
+            .with_key(Some(key.into()))
+    })
+}
+
+/// A keyed `<meta>` tag whose content is the document title, which updates whenever a
+/// [`Title`](crate::Title) changes.
+///
+/// On the server, it is only rendered once the `<head>` is sent, so that it has the final title
+/// wherever the `<Title/>` was rendered, and it is left out if there is no title.
+fn title_meta(attr: &'static str, key: &'static str) -> impl IntoView {
+    let title = use_head().title;
+    register(
+        meta()
+            .attr(attr, key)
+            .content(move || title.track_string().unwrap_or_default()),
+    )
+    .with_key(Some(key.into()))
+    .with_title_content(attr)
+}
@@ -64,6 +64,10 @@ pub fn Script(
     /// The content of the `<script>` tag.
     #[prop(optional)]
     children: Option<Children>,
+    /// A key that identifies this tag. If several tags have the same key, only the one that was
+    /// rendered last is included in the `<head>`, replacing the others.
+    #[prop(optional, into)]
+    key: Option<Oco<'static, str>>,
 ) -> impl IntoView {
     register(
         script()
@@ -81,4 +85,5 @@ pub fn Script(
             .blocking(blocking)
             .child(children.map(|c| c())),
     )
+    .with_key(key)
 }
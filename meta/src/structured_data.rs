@@ -0,0 +1,93 @@
+use crate::register;
+use leptos::{
+    component, logging::debug_warn, oco::Oco, prelude::*,
+    tachys::html::element::script, IntoView,
+};
+use serde::Serialize;
+
+/// Injects [JSON-LD](https://json-ld.org/) structured data, such as a [schema.org](https://schema.org/)
+/// description of the page, into the document head as a `<script type="application/ld+json">`.
+///
+/// The `data` can be any value that implements [`Serialize`]. It is serialized to JSON and
+/// escaped so that it can be safely included in the `<script>` tag, even if it contains
+/// user-provided strings like `</script>`.
+///
+/// If several `<StructuredData/>` components render the same data (for example, because both a
+/// layout and a page describe the same organization), it is only added to the head once. To have
+/// a page replace data set by its layout, give both the same `key`.
+///
+/// ```
+/// use leptos::prelude::*;
+/// use leptos_meta::*;
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct Article {
+///     #[serde(rename = "@context")]
+///     context: &'static str,
+///     #[serde(rename = "@type")]
+///     kind: &'static str,
+///     headline: String,
+///     author: String,
+/// }
+///
+/// #[component]
+/// fn BlogPost() -> impl IntoView {
+///     provide_meta_context();
+///
+///     view! {
+///       <StructuredData data=Article {
+///         context: "https://schema.org",
+///         kind: "Article",
+///         headline: "Leptos Meta and JSON-LD".to_string(),
+///         author: "Alice".to_string(),
+///       }/>
+///       <article>
+///         // ...
+///       </article>
+///     }
+/// }
+/// ```
+#[component]
+pub fn StructuredData<T>(
+    /// The value to serialize as JSON-LD.
+    data: T,
+    /// An ID for the `<script>` tag.
+    #[prop(optional, into)]
+    id: Option<Oco<'static, str>>,
+    /// A key that identifies this data. Defaults to the serialized data itself.
+    #[prop(optional, into)]
+    key: Option<Oco<'static, str>>,
+) -> impl IntoView
+where
+    T: Serialize,
+{
+    let json = match serde_json::to_string(&data) {
+        Ok(json) => escape_json(&json),
+        Err(e) => {
+            debug_warn!("could not serialize <StructuredData/>: {e}");
+            return None;
+        }
+    };
+
+    let key = key.unwrap_or_else(|| json.clone().into());
+    Some(
+        register(script().id(id).r#type("application/ld+json").child(json))
+            .with_key(Some(key)),
+    )
+}
+
+/// Escapes the characters that could close the `<script>` tag or be mistaken for HTML by the
+/// parser. The result is still valid JSON that describes the same value.
+fn escape_json(json: &str) -> String {
+    let mut escaped = String::with_capacity(json.len());
+    for c in json.chars() {
+        match c {
+            '<' => escaped.push_str("\\u003c"),
+            '>' => escaped.push_str("\\u003e"),
+            '&' => escaped.push_str("\\u0026"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -43,6 +43,10 @@ pub fn Style(
     /// The content of the `<style>` tag.
     #[prop(optional)]
     children: Option<Children>,
+    /// A key that identifies this tag. If several tags have the same key, only the one that was
+    /// rendered last is included in the `<head>`, replacing the others.
+    #[prop(optional, into)]
+    key: Option<Oco<'static, str>>,
 ) -> impl IntoView {
     register(
         style()
@@ -53,4 +57,5 @@ pub fn Style(
             .blocking(blocking)
             .child(children.map(|c| c())),
     )
+    .with_key(key)
 }
@@ -34,10 +34,49 @@ pub fn Stylesheet(
     #[prop(optional, into)]
     id: Option<String>,
 ) -> impl IntoView {
+    #[cfg(feature = "ssr")]
+    leptos::request::PreloadLink::preload(href.as_str(), "style").send();
     // TODO additional attributes
     register(link().id(id).rel("stylesheet").href(href))
 }
 
+/// Injects an [`HTMLLinkElement`](https://developer.mozilla.org/en-US/docs/Web/API/HTMLLinkElement) into the document
+/// head that loads the stylesheet that the scoped styles of the [`style!`](leptos::style) macro
+/// were extracted to, with [`bundle`](leptos::scoped_style::bundle).
+///
+/// Once it is linked, scoped styles are no longer added to the `<head>` one by one in the
+/// browser. The styles of the components rendered on the server are still inlined in the
+/// `<head>`, so that the first page does not wait for the stylesheet to be painted.
+///
+/// ```
+/// use leptos::prelude::*;
+/// use leptos_meta::*;
+///
+/// #[component]
+/// fn MyApp() -> impl IntoView {
+///     provide_meta_context();
+///
+///     view! {
+///       <ScopedStylesheet href="/pkg/scoped.css"/>
+///       <main>"..."</main>
+///     }
+/// }
+/// ```
+///
+/// Requires the `scoped-style` feature.
+#[cfg(feature = "scoped-style")]
+#[component]
+pub fn ScopedStylesheet(
+    /// The URL at which the extracted stylesheet is located.
+    #[prop(into)]
+    href: String,
+) -> impl IntoView {
+    leptos::scoped_style::set_stylesheet_linked(true);
+    #[cfg(feature = "ssr")]
+    leptos::request::PreloadLink::preload(href.as_str(), "style").send();
+    register(link().rel("stylesheet").href(href))
+}
+
 /// Injects an [`HTMLLinkElement`](https://developer.mozilla.org/en-US/docs/Web/API/HTMLLinkElement) into the document head that loads a `cargo-leptos`-hashed stylesheet.
 ///
 /// This should only be used in the application’s server-side `shell` function, as
@@ -50,7 +89,8 @@ pub fn HashedStylesheet(
     /// An ID for the stylesheet.
     #[prop(optional, into)]
     id: Option<String>,
-    /// A base url, not including a trailing slash
+    /// A base url, not including a trailing slash. Defaults to the
+    /// [`base_path`](LeptosOptions::base_path) of the app.
     #[prop(optional, into)]
     root: Option<String>,
 ) -> impl IntoView {
@@ -80,10 +120,17 @@ pub fn HashedStylesheet(
     }
     css_file_name.push_str(".css");
     let pkg_path = &options.site_pkg_dir;
-    let root = root.unwrap_or_default();
+    let root = root
+        .or_else(|| {
+            options
+                .base_path
+                .as_deref()
+                .and_then(leptos::base_path::normalize)
+        })
+        .unwrap_or_default();
+    let href = format!("{root}/{pkg_path}/{css_file_name}");
 
-    link()
-        .id(id)
-        .rel("stylesheet")
-        .href(format!("{root}/{pkg_path}/{css_file_name}"))
+    #[cfg(feature = "ssr")]
+    leptos::request::PreloadLink::preload(href.as_str(), "style").send();
+    link().id(id).rel("stylesheet").href(href)
 }
@@ -4,7 +4,10 @@ use leptos::{
     component,
     oco::Oco,
     prelude::{ArcTrigger, Notify, Track},
-    reactive::{effect::RenderEffect, owner::use_context},
+    reactive::{
+        effect::RenderEffect,
+        owner::{shadow_context, with_context},
+    },
     tachys::{
         dom::document,
         hydration::Cursor,
@@ -39,11 +42,29 @@ impl core::fmt::Debug for TitleContext {
     }
 }
 
-type TitleId = u32;
+/// Identifies a single `<Title/>`. The stacks of titles and formatters are ordered by these IDs,
+/// so that a title nested inside the layout or route of another `<Title/>` takes precedence over
+/// it, whichever of them happened to be rendered or mounted first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct TitleId {
+    depth: usize,
+    id: u32,
+}
+
+/// The number of `<Title/>` components that enclose the current component, which each provide
+/// this to the rest of the component they are used in, including its nested routes.
+#[derive(Debug, Clone, Copy)]
+struct TitleDepth(usize);
 
 impl TitleContext {
     fn next_id(&self) -> TitleId {
-        self.id.fetch_add(1, Ordering::Relaxed)
+        let depth =
+            with_context(|depth: &TitleDepth| depth.0 + 1).unwrap_or_default();
+        shadow_context(TitleDepth(depth));
+        TitleId {
+            depth,
+            id: self.id.fetch_add(1, Ordering::Relaxed),
+        }
     }
 
     fn invalidate(&self) {
@@ -74,13 +95,14 @@ impl TitleContext {
         formatter: Option<Formatter>,
     ) {
         if let Some(text) = text {
-            self.text_stack.write().or_poisoned().push((id, text));
+            insert_sorted(&mut self.text_stack.write().or_poisoned(), id, text);
         }
         if let Some(formatter) = formatter {
-            self.formatter_stack
-                .write()
-                .or_poisoned()
-                .push((id, formatter));
+            insert_sorted(
+                &mut self.formatter_stack.write().or_poisoned(),
+                id,
+                formatter,
+            );
         }
         self.invalidate();
     }
@@ -110,7 +132,7 @@ impl TitleContext {
                 self.invalidate();
             }
             (None, Some(new)) => {
-                text_stack.push((id, new));
+                insert_sorted(&mut text_stack, id, new);
                 self.invalidate();
             }
         }
@@ -125,7 +147,7 @@ impl TitleContext {
                 self.invalidate();
             }
             (None, Some(new)) => {
-                formatter_stack.push((id, new));
+                insert_sorted(&mut formatter_stack, id, new);
                 self.invalidate();
             }
         }
@@ -149,30 +171,46 @@ impl TitleContext {
         (text, formatter)
     }
 
+    /// Like [`TitleContext::as_string`], but also subscribes the current reactive observer to
+    /// any changes to the title.
+    pub(crate) fn track_string(&self) -> Option<Oco<'static, str>> {
+        self.revalidate.track();
+        self.as_string()
+    }
+
     /// Converts the title into a string that can be used as the text content of a `<title>` tag.
     pub fn as_string(&self) -> Option<Oco<'static, str>> {
-        let title = self
+        let mut segments = self
             .text_stack
             .read()
             .or_poisoned()
-            .last()
-            .map(|n| n.1.get());
-
-        title.map(|title| {
-            if let Some(formatter) =
-                self.formatter_stack.read().or_poisoned().last()
-            {
-                (formatter.1 .0)(title.into_owned()).into()
-            } else {
-                title
-            }
-        })
+            .iter()
+            .map(|n| n.1.get().into_owned())
+            .collect::<Vec<_>>();
+        if segments.is_empty() {
+            return None;
+        }
+
+        if let Some(formatter) =
+            self.formatter_stack.read().or_poisoned().last()
+        {
+            Some((formatter.1 .0)(&segments).into())
+        } else {
+            segments.pop().map(Into::into)
+        }
     }
 }
 
+type FormatterFn = Box<dyn Fn(&[String]) -> String + Send + Sync>;
+
+fn insert_sorted<T>(stack: &mut Vec<(TitleId, T)>, id: TitleId, value: T) {
+    let pos = stack.partition_point(|(item_id, _)| *item_id < id);
+    stack.insert(pos, (id, value));
+}
+
 /// A function that is applied to the text value before setting `document.title`.
 #[repr(transparent)]
-pub struct Formatter(Box<dyn Fn(String) -> String + Send + Sync>);
+pub struct Formatter(FormatterFn);
 
 impl<F> From<F> for Formatter
 where
@@ -180,7 +218,33 @@ where
 {
     #[inline(always)]
     fn from(f: F) -> Formatter {
-        Formatter(Box::new(f))
+        Formatter(Box::new(move |segments| {
+            f(segments.last().cloned().unwrap_or_default())
+        }))
+    }
+}
+
+impl From<StackFormatter> for Formatter {
+    #[inline(always)]
+    fn from(f: StackFormatter) -> Formatter {
+        Formatter(f.0)
+    }
+}
+
+/// A function that builds `document.title` from the text of every `<Title/>` that is currently
+/// mounted, ordered from the outermost to the innermost.
+///
+/// This allows nested layouts to each contribute a segment to the title, rather than replacing it.
+#[repr(transparent)]
+pub struct StackFormatter(FormatterFn);
+
+impl<F> From<F> for StackFormatter
+where
+    F: Fn(&[String]) -> String + Send + Sync + 'static,
+{
+    #[inline(always)]
+    fn from(f: F) -> StackFormatter {
+        StackFormatter(Box::new(f))
     }
 }
 
@@ -189,6 +253,10 @@ where
 /// The `title` and `formatter` can be set independently of one another. For example, you can create a root-level
 /// `<Title formatter=.../>` that will wrap each of the text values of `<Title/>` components created lower in the tree.
 ///
+/// If several `<Title/>` components are mounted at once, the one nested most deeply in the tree
+/// determines the title, no matter which order they were rendered in. This makes the title
+/// deterministic during server-side rendering and client-side navigation.
+///
 /// ```
 /// use leptos::prelude::*;
 /// use leptos_meta::*;
@@ -224,17 +292,60 @@ where
 ///     }
 /// }
 /// ```
+///
+/// To compose the title from nested layouts instead, use a `stack_formatter`, which receives the
+/// text of every mounted `<Title/>`, from the outermost to the innermost.
+///
+/// ```
+/// use leptos::prelude::*;
+/// use leptos_meta::*;
+///
+/// #[component]
+/// fn MyApp() -> impl IntoView {
+///     provide_meta_context();
+///     let stack_formatter = |segments: &[String]| {
+///         segments.iter().rev().cloned().collect::<Vec<_>>().join(" – ")
+///     };
+///
+///     view! {
+///       <Title text="MyApp" stack_formatter/>
+///       // ... routing logic here
+///     }
+/// }
+///
+/// #[component]
+/// fn ProfileLayout() -> impl IntoView {
+///     view! {
+///       <Title text="Profile"/>
+///       // ... nested routes here
+///     }
+/// }
+///
+/// #[component]
+/// fn Settings() -> impl IntoView {
+///     view! {
+///       <Title text="Settings"/> // sets title to "Settings – Profile – MyApp"
+///     }
+/// }
+/// ```
 #[component]
 pub fn Title(
     /// A function that will be applied to any text value before it’s set as the title.
     #[prop(optional, into)]
     mut formatter: Option<Formatter>,
+    /// A function that will be applied to the text values of every mounted `<Title/>` to build
+    /// the title. Takes precedence over `formatter`.
+    #[prop(optional, into)]
+    stack_formatter: Option<StackFormatter>,
     /// Sets the current `document.title`.
     #[prop(optional, into)]
     mut text: Option<TextProp>,
 ) -> impl IntoView {
+    if let Some(stack_formatter) = stack_formatter {
+        formatter = Some(stack_formatter.into());
+    }
     let meta = use_head();
-    let server_ctx = use_context::<ServerMetaContext>();
+    let server_ctx = with_context(ServerMetaContext::clone);
     let id = meta.title.next_id();
     if let Some(cx) = server_ctx {
         // if we are server rendering, we will not actually use these values via RenderHtml
@@ -253,7 +364,7 @@ pub fn Title(
 }
 
 struct TitleView {
-    id: u32,
+    id: TitleId,
     meta: MetaContext,
     formatter: Option<Formatter>,
     text: Option<TextProp>,
@@ -0,0 +1,277 @@
+use crate::register;
+use leptos::{
+    component,
+    oco::Oco,
+    tachys::html::element::{link, meta},
+    IntoView,
+};
+use serde::{Deserialize, Serialize};
+
+/// A [web app manifest](https://developer.mozilla.org/en-US/docs/Web/Manifest), which tells the
+/// browser how to install the app and how to show it once installed.
+///
+/// Build it once, and use the same value for the [`WebAppManifest`] component and for the route
+/// that serves it (`leptos_axum::web_app_manifest` or `leptos_actix::web_app_manifest`), so that
+/// the tags in the `<head>` always match the manifest.
+///
+/// Fields that are `None` or empty are left out of the JSON.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct AppManifest {
+    /// The name of the app.
+    pub name: String,
+    /// A shorter name, used where there is not enough room for [`name`](Self::name).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub short_name: Option<String>,
+    /// What the app does.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// A unique identifier for the app. Defaults to the [`start_url`](Self::start_url).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// The page that opens when the app is launched.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_url: Option<String>,
+    /// The URLs that belong to the app once installed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    /// How much of the browser UI is shown around the app.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display: Option<DisplayMode>,
+    /// The orientation the app prefers, like `portrait` or `landscape`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub orientation: Option<String>,
+    /// The color of the browser UI around the app, as a CSS color.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme_color: Option<String>,
+    /// The color of the splash screen while the app loads, as a CSS color.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub background_color: Option<String>,
+    /// The language of the name and the description, like `en-US`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lang: Option<String>,
+    /// The icons of the app, in several sizes.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub icons: Vec<ManifestIcon>,
+    /// The categories the app belongs to in app stores, like `productivity`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub categories: Vec<String>,
+}
+
+/// How much of the browser UI is shown around an installed app.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DisplayMode {
+    /// The app takes up the whole screen.
+    Fullscreen,
+    /// The app looks like a native app, without browser UI.
+    Standalone,
+    /// Like [`Standalone`](Self::Standalone), with minimal navigation controls.
+    MinimalUi,
+    /// The app opens in a regular browser tab.
+    Browser,
+}
+
+/// An icon of an [`AppManifest`].
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct ManifestIcon {
+    /// The URL of the image.
+    pub src: String,
+    /// The sizes of the image, like `192x192`, or `any` for vector images.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sizes: Option<String>,
+    /// The MIME type of the image, like `image/png`.
+    #[serde(default, rename = "type", skip_serializing_if = "Option::is_none")]
+    pub type_: Option<String>,
+    /// How the image can be used, like `maskable` or `monochrome`. Defaults to `any`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub purpose: Option<String>,
+}
+
+impl ManifestIcon {
+    /// An icon with the image at `src`, of the given `sizes`.
+    pub fn new(src: impl Into<String>, sizes: impl Into<String>) -> Self {
+        Self {
+            src: src.into(),
+            sizes: Some(sizes.into()),
+            ..Default::default()
+        }
+    }
+
+    /// The width of the largest size, or `None` for icons without a size or of `any` size.
+    fn largest_size(&self) -> Option<u32> {
+        self.sizes
+            .as_deref()?
+            .split_whitespace()
+            .filter_map(|size| size.split_once(['x', 'X'])?.0.parse().ok())
+            .max()
+    }
+
+    fn is_for_any_purpose(&self) -> bool {
+        match self.purpose.as_deref() {
+            Some(purpose) => {
+                purpose.split_whitespace().any(|purpose| purpose == "any")
+            }
+            None => true,
+        }
+    }
+}
+
+impl AppManifest {
+    /// The path the manifest is served at, unless another one is given.
+    pub const DEFAULT_PATH: &'static str = "/manifest.webmanifest";
+
+    /// The MIME type to serve the manifest with.
+    pub const CONTENT_TYPE: &'static str = "application/manifest+json";
+
+    /// A manifest for the app called `name`.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// The manifest as JSON, to be served with [`CONTENT_TYPE`](Self::CONTENT_TYPE).
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("a manifest is valid JSON")
+    }
+
+    /// The icon that iOS shows on the home screen, which ignores the manifest: the largest
+    /// icon that is meant for any purpose, since iOS masks icons itself.
+    pub fn apple_touch_icon(&self) -> Option<&ManifestIcon> {
+        self.icons
+            .iter()
+            .filter(|icon| icon.is_for_any_purpose())
+            .max_by_key(|icon| icon.largest_size().unwrap_or(0))
+    }
+}
+
+/// Links to a [web app manifest](https://developer.mozilla.org/en-US/docs/Web/Manifest), and
+/// injects the tags that browsers read instead of the manifest, so that both describe the app the
+/// same way.
+///
+/// Along with the `<link rel="manifest">`, this renders a `theme-color` `<meta>` from
+/// [`theme_color`](AppManifest::theme_color), an `apple-touch-icon` `<link>` from
+/// [`apple_touch_icon`](AppManifest::apple_touch_icon), and an `apple-mobile-web-app-title`
+/// `<meta>` from the short name or the name. Each tag is keyed, so a page can render another
+/// `<WebAppManifest/>` to replace the one set by its layout.
+///
+/// The manifest itself is served by the integration, at the same `href`:
+///
+/// ```
+/// use leptos::prelude::*;
+/// use leptos_meta::*;
+///
+/// pub fn manifest() -> AppManifest {
+///     AppManifest {
+///         short_name: Some("Todos".into()),
+///         display: Some(DisplayMode::Standalone),
+///         start_url: Some("/".into()),
+///         theme_color: Some("#1e40af".into()),
+///         icons: vec![
+///             ManifestIcon::new("/icons/192.png", "192x192"),
+///             ManifestIcon::new("/icons/512.png", "512x512"),
+///         ],
+///         ..AppManifest::new("Todo List")
+///     }
+/// }
+///
+/// #[component]
+/// fn MyApp() -> impl IntoView {
+///     provide_meta_context();
+///
+///     view! {
+///       <WebAppManifest manifest=manifest()/>
+///       <main>
+///         // ...
+///       </main>
+///     }
+/// }
+///
+/// // on the server, with `leptos_axum`:
+/// // let app = Router::new()
+/// //     .route(AppManifest::DEFAULT_PATH, leptos_axum::web_app_manifest(manifest()));
+/// ```
+#[component]
+pub fn WebAppManifest(
+    /// The manifest that is served at `href`.
+    manifest: AppManifest,
+    /// The URL of the manifest. Defaults to [`AppManifest::DEFAULT_PATH`].
+    #[prop(optional, into)]
+    href: Option<Oco<'static, str>>,
+) -> impl IntoView {
+    let href = href.unwrap_or(Oco::Borrowed(AppManifest::DEFAULT_PATH));
+    let title = manifest.short_name.clone().unwrap_or(manifest.name.clone());
+    let apple_touch_icon = manifest.apple_touch_icon().cloned();
+
+    (
+        register(link().rel("manifest").href(href))
+            .with_key(Some("manifest".into())),
+        manifest.theme_color.map(|color| {
+            register(meta().name("theme-color").content(color))
+                .with_key(Some("theme-color".into()))
+        }),
+        apple_touch_icon.map(|icon| {
+            register(
+                link()
+                    .rel("apple-touch-icon")
+                    .href(icon.src)
+                    .sizes(icon.sizes),
+            )
+            .with_key(Some("apple-touch-icon".into()))
+        }),
+        register(meta().name("apple-mobile-web-app-title").content(title))
+            .with_key(Some("apple-mobile-web-app-title".into())),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_fields_are_left_out_of_the_json() {
+        let manifest = AppManifest {
+            short_name: Some("Todos".into()),
+            display: Some(DisplayMode::MinimalUi),
+            icons: vec![ManifestIcon {
+                type_: Some("image/svg+xml".into()),
+                ..ManifestIcon::new("/icon.svg", "any")
+            }],
+            ..AppManifest::new("Todo List")
+        };
+        assert_eq!(
+            manifest.to_json(),
+            r#"{"name":"Todo List","short_name":"Todos","display":"minimal-ui","icons":[{"src":"/icon.svg","sizes":"any","type":"image/svg+xml"}]}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<AppManifest>(&manifest.to_json()).unwrap(),
+            manifest
+        );
+    }
+
+    #[test]
+    fn the_apple_touch_icon_is_the_largest_icon_for_any_purpose() {
+        let maskable = ManifestIcon {
+            purpose: Some("maskable".into()),
+            ..ManifestIcon::new("/maskable.png", "1024x1024")
+        };
+        let manifest = AppManifest {
+            icons: vec![
+                ManifestIcon::new("/192.png", "192x192"),
+                maskable,
+                ManifestIcon::new("/multi.ico", "16x16 512x512 32x32"),
+                ManifestIcon {
+                    purpose: Some("maskable any".into()),
+                    ..ManifestIcon::new("/256.png", "256x256")
+                },
+            ],
+            ..AppManifest::new("App")
+        };
+        assert_eq!(
+            manifest.apple_touch_icon().map(|icon| icon.src.as_str()),
+            Some("/multi.ico")
+        );
+        assert_eq!(AppManifest::new("App").apple_touch_icon(), None);
+    }
+}
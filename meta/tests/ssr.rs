@@ -0,0 +1,288 @@
+#![cfg(feature = "ssr")]
+
+use futures::{executor::block_on, stream, StreamExt};
+use leptos::prelude::*;
+use leptos_meta::*;
+use serde::Serialize;
+
+/// Renders `app` on the server, and returns the `<head>` after the meta tags have been injected.
+fn render_head<V: IntoView + 'static>(app: impl FnOnce() -> V) -> String {
+    let owner = Owner::new();
+    owner.with(|| {
+        let (meta_context, meta_output) = ServerMetaContext::new();
+        provide_context(meta_context);
+        provide_meta_context();
+        let body = app().to_html();
+        let html = format!("<html><head></head><body>{body}</body></html>");
+        let html = block_on(async move {
+            meta_output
+                .inject_meta_context(stream::iter([html]))
+                .await
+                .collect::<String>()
+                .await
+        });
+        let end = html.find("</head>").expect("no </head>");
+        html[..end + "</head>".len()].to_string()
+    })
+}
+
+#[derive(Serialize)]
+struct Organization {
+    name: &'static str,
+}
+
+#[test]
+fn structured_data_is_included_once() {
+    let head = render_head(|| {
+        view! {
+            <StructuredData data=Organization { name: "Leptos" }/>
+            <StructuredData data=Organization { name: "Leptos" }/>
+        }
+    });
+
+    assert_eq!(head.matches("application/ld+json").count(), 1);
+    assert!(head.contains(r#">{"name":"Leptos"}</script>"#));
+}
+
+#[test]
+fn structured_data_with_the_same_key_is_replaced() {
+    let head = render_head(|| {
+        view! {
+            <StructuredData key="org" data=Organization { name: "Layout" }/>
+            <StructuredData key="org" data=Organization { name: "Page" }/>
+            <StructuredData key="other" data=Organization { name: "Other" }/>
+        }
+    });
+
+    assert_eq!(
+        head,
+        "<html><head><!--key:org--><script \
+         type=\"application/ld+json\">{\"name\":\"Page\"}</script><!--key:other--><script \
+         type=\"application/ld+json\">{\"name\":\"Other\"}</script></head>"
+    );
+}
+
+#[test]
+fn open_graph_tags_set_by_a_page_replace_those_of_its_layout() {
+    let head = render_head(|| {
+        view! {
+            <OpenGraph site_name="Leptos" description="A layout."/>
+            <OpenGraph description="A page."/>
+        }
+    });
+
+    assert_eq!(head.matches("property=\"og:description\"").count(), 1);
+    assert!(head.contains("content=\"A page.\""));
+    assert!(!head.contains("A layout."));
+    assert!(head.contains("content=\"Leptos\""));
+}
+
+#[test]
+fn tags_with_the_same_key_are_replaced() {
+    let head = render_head(|| {
+        view! {
+            <Meta name="description" key="description" content="A layout."/>
+            <Link rel="canonical" key="canonical" href="https://leptos.dev/"/>
+            <Meta name="description" key="description" content="A page."/>
+            <Link rel="canonical" key="canonical" href="https://leptos.dev/page"/>
+            <Meta name="author" content="Leptos"/>
+        }
+    });
+
+    assert_eq!(
+        head,
+        "<html><head><!--key:description--><meta name=\"description\" content=\"A \
+         page.\"><!--key:canonical--><link \
+         href=\"https://leptos.dev/page\" rel=\"canonical\"><meta name=\"author\" \
+         content=\"Leptos\"></head>"
+    );
+}
+
+#[test]
+fn titles_are_stacked_by_their_nesting() {
+    #[component]
+    fn Profile() -> impl IntoView {
+        view! {
+            <Title text="Profile"/>
+            <Settings/>
+        }
+    }
+
+    #[component]
+    fn Settings() -> impl IntoView {
+        // however many owners the page is nested in
+        Owner::new()
+            .with(|| Owner::new().with(|| view! { <Title text="Settings"/> }))
+    }
+
+    let head = render_head(|| {
+        let stack_formatter = |segments: &[String]| {
+            segments
+                .iter()
+                .rev()
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(" – ")
+        };
+        view! {
+            <Title text="MyApp" stack_formatter/>
+            <Profile/>
+        }
+    });
+
+    assert!(head.contains("<title>Settings – Profile – MyApp</title>"));
+}
+
+#[test]
+fn open_graph_title_uses_a_title_rendered_after_it() {
+    let head = render_head(|| {
+        view! {
+            <OpenGraph site_name="Leptos"/>
+            <Title text="A page"/>
+        }
+    });
+
+    assert!(head.contains("<meta property=\"og:title\" content=\"A page\">"));
+}
+
+#[test]
+fn open_graph_title_is_left_out_without_a_title() {
+    let head = render_head(|| {
+        view! { <OpenGraph site_name="Leptos"/> }
+    });
+
+    assert!(!head.contains("og:title"));
+    assert!(head.contains("content=\"Leptos\""));
+}
+
+#[test]
+fn web_app_manifest_links_the_manifest_and_mirrors_it_in_the_head() {
+    let manifest = AppManifest {
+        short_name: Some("Todos".into()),
+        theme_color: Some("#1e40af".into()),
+        icons: vec![
+            ManifestIcon::new("/icons/192.png", "192x192"),
+            ManifestIcon::new("/icons/512.png", "512x512"),
+        ],
+        ..AppManifest::new("Todo List")
+    };
+    let head = render_head(|| view! { <WebAppManifest manifest/> });
+
+    assert!(
+        head.contains(r#"<link rel="manifest" href="/manifest.webmanifest">"#)
+    );
+    assert!(head.contains(r##"<meta name="theme-color" content="#1e40af">"##));
+    assert!(head.contains(
+        r#"<link rel="apple-touch-icon" href="/icons/512.png" sizes="512x512">"#
+    ));
+    assert!(head.contains(
+        r#"<meta name="apple-mobile-web-app-title" content="Todos">"#
+    ));
+}
+
+#[test]
+fn web_app_manifest_set_by_a_page_replaces_that_of_its_layout() {
+    let head = render_head(|| {
+        view! {
+            <WebAppManifest manifest=AppManifest::new("Layout")/>
+            <WebAppManifest manifest=AppManifest::new("Page") href="/page.webmanifest"/>
+        }
+    });
+
+    assert_eq!(head.matches(r#"rel="manifest""#).count(), 1);
+    assert!(head.contains(r#"href="/page.webmanifest""#));
+    assert!(head.contains(r#"content="Page""#));
+    assert!(!head.contains("Layout"));
+}
+
+fn example_href(locale: &str) -> String {
+    format!("https://example.com/{locale}/about")
+}
+
+#[test]
+fn alternate_links_link_each_locale_and_the_canonical_page() {
+    let head = render_head(|| {
+        view! {
+            <AlternateLinks
+                locales=["en", "de-AT"]
+                href=example_href
+                locale="de-AT"
+                default_locale="en"
+            />
+        }
+    });
+
+    assert_eq!(
+        head,
+        "<html><head><!--key:alternate:en--><link rel=\"alternate\" hreflang=\"en\" \
+         href=\"https://example.com/en/about\"><!--key:alternate:de-AT--><link \
+         rel=\"alternate\" hreflang=\"de-AT\" \
+         href=\"https://example.com/de-AT/about\"><!--key:alternate:x-default--><link \
+         rel=\"alternate\" hreflang=\"x-default\" \
+         href=\"https://example.com/en/about\"><!--key:canonical--><link \
+         rel=\"canonical\" href=\"https://example.com/de-AT/about\"></head>"
+    );
+}
+
+#[test]
+fn alternate_links_of_a_nested_route_replace_those_of_its_parent() {
+    let head = render_head(|| {
+        view! {
+            <AlternateLinks
+                locales=["en", "de"]
+                href=|locale: &str| format!("https://example.com/{locale}")
+                locale="en"
+                default_locale="en"
+            />
+            <AlternateLinks
+                locales=["en", "de"]
+                href=example_href
+                locale="de"
+                default_locale="en"
+            />
+        }
+    });
+
+    assert_eq!(head.matches(r#"hreflang="en""#).count(), 1);
+    assert_eq!(head.matches(r#"hreflang="de""#).count(), 1);
+    assert_eq!(head.matches(r#"hreflang="x-default""#).count(), 1);
+    assert_eq!(head.matches(r#"rel="canonical""#).count(), 1);
+    assert!(head.contains(
+        r#"<link rel="canonical" href="https://example.com/de/about">"#
+    ));
+    assert!(!head.contains(r#"href="https://example.com/en""#));
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Locale {
+    En,
+    Fr,
+}
+
+impl leptos::i18n::Locales for Locale {
+    const ALL: &'static [Self] = &[Locale::En, Locale::Fr];
+    const DEFAULT: Self = Locale::En;
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Fr => "fr",
+        }
+    }
+}
+
+#[test]
+fn alternate_links_can_list_every_locale_of_the_application() {
+    let head = render_head(|| {
+        view! { <AlternateLinks locales={LocaleList::all::<Locale>()} href=example_href/> }
+    });
+
+    assert!(head.contains(
+        r#"<link rel="alternate" hreflang="en" href="https://example.com/en/about">"#
+    ));
+    assert!(head.contains(
+        r#"<link rel="alternate" hreflang="fr" href="https://example.com/fr/about">"#
+    ));
+    assert!(!head.contains("x-default"));
+    assert!(!head.contains("canonical"));
+}
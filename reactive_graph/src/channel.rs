@@ -1,5 +1,7 @@
 use core::sync::atomic::Ordering::Relaxed;
 use futures::{task::AtomicWaker, Stream};
+#[cfg(feature = "tracing")]
+use or_poisoned::OrPoisoned;
 use std::{
     fmt::Debug,
     hash::Hash,
@@ -11,13 +13,16 @@ use std::{
 #[derive(Debug)]
 pub(crate) struct Sender(Arc<Inner>);
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) struct Receiver(Weak<Inner>);
 
 #[derive(Debug, Default)]
 struct Inner {
     waker: AtomicWaker,
     set: AtomicBool,
+    // the signal whose change sent the pending notification
+    #[cfg(feature = "tracing")]
+    cause: std::sync::Mutex<crate::trace::Cause>,
 }
 
 impl Drop for Inner {
@@ -40,21 +45,53 @@ impl Drop for Inner {
 }
 
 pub fn channel() -> (Sender, Receiver) {
-    let inner = Arc::new(Inner {
-        waker: AtomicWaker::new(),
-        set: AtomicBool::new(false),
-    });
+    let inner = Arc::new(Inner::default());
     let rx = Arc::downgrade(&inner);
     (Sender(inner), Receiver(rx))
 }
 
 impl Sender {
     pub fn notify(&mut self) {
+        #[cfg(feature = "tracing")]
+        if let Some(cause) = crate::trace::current_cause() {
+            *self.0.cause.lock().or_poisoned() = Some(cause);
+        }
         self.0.set.store(true, Relaxed);
         self.0.waker.wake();
     }
 }
 
+impl Receiver {
+    /// Wakes the task waiting on this receiver, as if it had been notified.
+    pub fn wake(&self) {
+        if let Some(inner) = self.0.upgrade() {
+            inner.set.store(true, Relaxed);
+            inner.waker.wake();
+        }
+    }
+
+    /// Consumes a pending notification without waiting for one.
+    pub fn take_notification(&self) -> bool {
+        self.0
+            .upgrade()
+            .is_some_and(|inner| inner.set.swap(false, Relaxed))
+    }
+
+    /// Takes the cause of the last notification.
+    pub fn take_cause(&self) -> crate::trace::Cause {
+        #[cfg(feature = "tracing")]
+        {
+            self.0
+                .upgrade()
+                .and_then(|inner| inner.cause.lock().or_poisoned().take())
+        }
+        #[cfg(not(feature = "tracing"))]
+        {
+            None
+        }
+    }
+}
+
 impl Stream for Receiver {
     type Item = ();
 
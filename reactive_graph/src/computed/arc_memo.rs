@@ -9,6 +9,7 @@ use crate::{
         guards::{Mapped, Plain, ReadGuard},
         ArcReadSignal, ArcRwSignal,
     },
+    trace::{Kind, Origin},
     traits::{DefinedAt, Get, IsDisposed, ReadUntracked},
 };
 use core::fmt::Debug;
@@ -103,6 +104,8 @@ where
 {
     /// Creates a new memo by passing a function that computes the value.
     ///
+    /// The function receives the previous value, which is `None` for the initial calculation.
+    ///
     /// This is lazy: the function will not be called until the memo's value is read for the first
     /// time.
     #[track_caller]
@@ -154,13 +157,14 @@ where
     pub fn new_owning(
         fun: impl Fn(Option<T>) -> (T, bool) + Send + Sync + 'static,
     ) -> Self {
+        let origin = Origin::new(Kind::Memo);
         let inner = Arc::new_cyclic(|weak| {
             let subscriber = AnySubscriber(
                 weak.as_ptr() as usize,
                 Weak::clone(weak) as Weak<dyn Subscriber + Send + Sync>,
             );
 
-            MemoInner::new(Arc::new(fun), subscriber)
+            MemoInner::new(Arc::new(fun), subscriber, origin)
         });
         Self {
             #[cfg(any(debug_assertions, leptos_debuginfo))]
@@ -12,7 +12,7 @@ use crate::{
         AnySource, AnySubscriber, ReactiveNode, Source, SourceSet, Subscriber,
         SubscriberSet, ToAnySource, ToAnySubscriber, WithObserver,
     },
-    owner::{use_context, Owner},
+    owner::{use_context_silent, Owner},
     send_wrapper_ext::SendOption,
     signal::{
         guards::{AsyncPlain, Mapped, MappedMut, ReadGuard, WriteGuard},
@@ -227,6 +227,10 @@ macro_rules! spawn_derived {
         let is_ready = $initial.is_some() && !$force_spawn;
 
         let owner = Owner::new();
+        owner.on_resume({
+            let rx = rx.clone();
+            move || rx.wake()
+        });
         let inner = Arc::new(RwLock::new(ArcAsyncDerivedInner {
             owner: owner.clone(),
             notifier,
@@ -612,7 +616,8 @@ impl<T: 'static> ReadUntracked for ArcAsyncDerived<T> {
         ReadGuard<Option<T>, Mapped<AsyncPlain<SendOption<T>>, Option<T>>>;
 
     fn try_read_untracked(&self) -> Option<Self::Value> {
-        if let Some(suspense_context) = use_context::<SuspenseContext>() {
+        if let Some(suspense_context) = use_context_silent::<SuspenseContext>()
+        {
             let handle = suspense_context.task_id();
             let ready = SpecialNonReactiveFuture::new(self.ready());
             crate::spawn(async move {
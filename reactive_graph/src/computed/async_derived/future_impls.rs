@@ -3,7 +3,7 @@ use crate::{
     computed::suspense::SuspenseContext,
     diagnostics::SpecialNonReactiveZone,
     graph::{AnySource, ToAnySource},
-    owner::{use_context, Storage},
+    owner::{use_context_silent, Storage},
     send_wrapper_ext::SendOption,
     signal::guards::{AsyncPlain, Mapped, ReadGuard},
     traits::{DefinedAt, Track},
@@ -128,7 +128,8 @@ where
         self.source.track();
         let value = self.value.read_arc();
 
-        if let Some(suspense_context) = use_context::<SuspenseContext>() {
+        if let Some(suspense_context) = use_context_silent::<SuspenseContext>()
+        {
             self.inner
                 .write()
                 .or_poisoned()
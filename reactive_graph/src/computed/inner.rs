@@ -4,6 +4,7 @@ use crate::{
         Source, SourceSet, Subscriber, SubscriberSet, WithObserver,
     },
     owner::{Owner, Storage, StorageAccess},
+    trace::Origin,
 };
 use or_poisoned::OrPoisoned;
 use std::{
@@ -20,6 +21,7 @@ where
     #[allow(clippy::type_complexity)]
     pub(crate) fun: Arc<dyn Fn(Option<T>) -> (T, bool) + Send + Sync>,
     pub(crate) owner: Owner,
+    pub(crate) origin: Origin,
     pub(crate) reactivity: RwLock<MemoInnerReactivity>,
 }
 
@@ -28,6 +30,9 @@ pub(crate) struct MemoInnerReactivity {
     pub(crate) sources: SourceSet,
     pub(crate) subscribers: SubscriberSet,
     pub(crate) any_subscriber: AnySubscriber,
+    // the signal whose change made this dirty
+    #[cfg(feature = "tracing")]
+    pub(crate) cause: crate::trace::Cause,
 }
 
 impl<T, S> Debug for MemoInner<T, S>
@@ -47,16 +52,20 @@ where
     pub fn new(
         fun: Arc<dyn Fn(Option<T>) -> (T, bool) + Send + Sync>,
         any_subscriber: AnySubscriber,
+        origin: Origin,
     ) -> Self {
         Self {
             value: Arc::new(RwLock::new(None)),
             fun,
             owner: Owner::new(),
+            origin,
             reactivity: RwLock::new(MemoInnerReactivity {
                 state: ReactiveNodeState::Dirty,
                 sources: Default::default(),
                 subscribers: SubscriberSet::new(),
                 any_subscriber,
+                #[cfg(feature = "tracing")]
+                cause: None,
             }),
         }
     }
@@ -67,7 +76,14 @@ where
     S: Storage<T>,
 {
     fn mark_dirty(&self) {
-        self.reactivity.write().or_poisoned().state = ReactiveNodeState::Dirty;
+        {
+            let mut lock = self.reactivity.write().or_poisoned();
+            lock.state = ReactiveNodeState::Dirty;
+            #[cfg(feature = "tracing")]
+            {
+                lock.cause = crate::trace::current_cause().or(lock.cause);
+            }
+        }
         self.mark_subscribers_check();
     }
 
@@ -79,6 +95,10 @@ where
                 if lock.state != ReactiveNodeState::Dirty {
                     lock.state = ReactiveNodeState::Check;
                 }
+                #[cfg(feature = "tracing")]
+                {
+                    lock.cause = crate::trace::current_cause().or(lock.cause);
+                }
             }
             for sub in
                 (&reactivity.read().or_poisoned().subscribers).into_iter()
@@ -131,9 +151,15 @@ where
             }
             let any_subscriber = inner_1(&self.reactivity);
 
-            let (new_value, changed) = self.owner.with_cleanup(|| {
-                any_subscriber.with_observer(|| {
-                    (self.fun)(value.map(StorageAccess::into_taken))
+            #[cfg(feature = "tracing")]
+            let cause = self.reactivity.write().or_poisoned().cause.take();
+            #[cfg(not(feature = "tracing"))]
+            let cause = None;
+            let (new_value, changed) = self.origin.run(cause, || {
+                self.owner.with_cleanup(|| {
+                    any_subscriber.with_observer(|| {
+                        (self.fun)(value.map(StorageAccess::into_taken))
+                    })
                 })
             });
 
@@ -168,6 +168,15 @@ where
     /// // the memo will reactively update whenever `value` changes
     /// let memoized =
     ///     Memo::new(move |_| really_expensive_computation(value.get()));
+    ///
+    /// // the previous value can be used to compute the next one incrementally
+    /// let running_max = Memo::new(move |prev: Option<&i32>| {
+    ///     prev.copied().unwrap_or(i32::MIN).max(value.get())
+    /// });
+    /// set_value.set(5);
+    /// assert_eq!(running_max.get(), 5);
+    /// set_value.set(3);
+    /// assert_eq!(running_max.get(), 5);
     /// # });
     /// ```
     pub fn new(fun: impl Fn(Option<&T>) -> T + Send + Sync + 'static) -> Self
@@ -210,6 +219,26 @@ where
     /// Unlike [`Memo::new`](), this receives ownership of the previous value. As a result, it
     /// must return both the new value and a `bool` that is `true` if the value has changed.
     ///
+    /// This is useful to update a value that is expensive to clone in place, like a list that is
+    /// appended to:
+    /// ```
+    /// # use reactive_graph::prelude::*;
+    /// # use reactive_graph::computed::Memo;
+    /// # use reactive_graph::signal::signal;
+    /// # let owner = reactive_graph::owner::Owner::new(); owner.set();
+    /// let (message, set_message) = signal("hello".to_string());
+    ///
+    /// let history = Memo::new_owning(move |prev: Option<Vec<String>>| {
+    ///     let mut history = prev.unwrap_or_default();
+    ///     history.push(message.get());
+    ///     (history, true)
+    /// });
+    /// assert_eq!(history.get(), ["hello"]);
+    ///
+    /// set_message.set("world".to_string());
+    /// assert_eq!(history.get(), ["hello", "world"]);
+    /// ```
+    ///
     /// This is lazy: the function will not be called until the memo's value is read for the first
     /// time.
     #[track_caller]
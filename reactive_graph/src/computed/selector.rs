@@ -4,9 +4,10 @@ use crate::{
     traits::{Track, Update},
 };
 use or_poisoned::OrPoisoned;
-use rustc_hash::FxHashMap;
+use rustc_hash::FxBuildHasher;
 use std::{
-    hash::Hash,
+    collections::HashMap,
+    hash::{BuildHasher, Hash},
     sync::{Arc, RwLock},
 };
 
@@ -58,12 +59,16 @@ use std::{
 /// # }).await;
 /// # });
 /// ```
+///
+/// The source can also be mapped to a key with [`Selector::new_with_key`], so that values that
+/// are not hashable, like floats, or that are only selected by part of their value, can be used,
+/// and the map of keys can use another hasher with [`Selector::new_with_hasher`].
 #[derive(Clone)]
-pub struct Selector<T>
+pub struct Selector<T, S = FxBuildHasher>
 where
     T: PartialEq + Eq + Clone + Hash + 'static,
 {
-    subs: Arc<RwLock<FxHashMap<T, ArcRwSignal<bool>>>>,
+    subs: Arc<RwLock<HashMap<T, ArcRwSignal<bool>, S>>>,
     v: Arc<RwLock<Option<T>>>,
     #[allow(clippy::type_complexity)]
     f: Arc<dyn Fn(&T, &T) -> bool + Send + Sync>,
@@ -87,8 +92,62 @@ where
         source: impl Fn() -> T + Clone + Send + Sync + 'static,
         f: impl Fn(&T, &T) -> bool + Send + Sync + Clone + 'static,
     ) -> Self {
-        let subs: Arc<RwLock<FxHashMap<T, ArcRwSignal<bool>>>> =
-            Default::default();
+        Self::new_with_hasher(source, f, FxBuildHasher)
+    }
+
+    /// Creates a new selector whose keys are derived from the value of the source by `key`.
+    ///
+    /// The source does not need to be hashable, and its key can be anything that is, like a
+    /// composite key or a bucket of values.
+    ///
+    /// ```
+    /// # use reactive_graph::computed::*;
+    /// # use reactive_graph::signal::*;
+    /// # use reactive_graph::prelude::*;
+    /// # tokio_test::block_on(async move {
+    /// # any_spawner::Executor::init_tokio(); let owner = reactive_graph::owner::Owner::new(); owner.set();
+    /// # let _guard = reactive_graph::diagnostics::SpecialNonReactiveZone::enter();
+    /// // the temperature is selected in buckets of 10 degrees
+    /// let temperature = RwSignal::new(21.5_f64);
+    /// let bucket = Selector::new_with_key(
+    ///     move || temperature.get(),
+    ///     |temperature| (temperature / 10.0).floor() as i64,
+    /// );
+    /// assert!(bucket.selected(&2));
+    ///
+    /// // cells are selected by their (table, row) key
+    /// let cell = RwSignal::new(("users", 3, "name"));
+    /// let row = Selector::new_with_key(
+    ///     move || cell.get(),
+    ///     |(table, row, _column)| (*table, *row),
+    /// );
+    /// assert!(row.selected(&("users", 3)));
+    /// assert!(!row.selected(&("users", 4)));
+    /// # });
+    /// ```
+    pub fn new_with_key<U>(
+        source: impl Fn() -> U + Clone + Send + Sync + 'static,
+        key: impl Fn(&U) -> T + Send + Sync + 'static,
+    ) -> Self {
+        let key = Arc::new(key);
+        Self::new(move || key(&source()))
+    }
+}
+
+impl<T, S> Selector<T, S>
+where
+    T: PartialEq + Send + Sync + Eq + Clone + Hash + 'static,
+    S: BuildHasher + Send + Sync + 'static,
+{
+    /// Creates a new selector that compares values with a comparator function, like
+    /// [`Selector::new_with_fn`], and that uses the given hasher for its keys.
+    pub fn new_with_hasher(
+        source: impl Fn() -> T + Clone + Send + Sync + 'static,
+        f: impl Fn(&T, &T) -> bool + Send + Sync + Clone + 'static,
+        hasher: S,
+    ) -> Self {
+        let subs: Arc<RwLock<HashMap<T, ArcRwSignal<bool>, S>>> =
+            Arc::new(RwLock::new(HashMap::with_hasher(hasher)));
         let v: Arc<RwLock<Option<T>>> = Default::default();
         let f = Arc::new(f) as Arc<dyn Fn(&T, &T) -> bool + Send + Sync>;
 
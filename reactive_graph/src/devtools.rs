@@ -0,0 +1,548 @@
+//! Live statistics about the reactive graph, for development tools like a profiler overlay, when
+//! the `devtools` feature is enabled.
+//!
+//! This keeps count of the reactive sources, memos and effects that are alive, and of how often
+//! and for how long effects and memos rerun. A component that calls [`enter_component`] while it
+//! is being created (which the `#[component]` macro does with its `devtools` feature) is credited
+//! with its own creation, and with the reruns of the effects and memos created inside it.
+//!
+//! ```rust
+//! # any_spawner::Executor::init_futures_executor();
+//! # let owner = reactive_graph::owner::Owner::new(); owner.set();
+//! use reactive_graph::{devtools, prelude::*, signal::RwSignal};
+//!
+//! let count = RwSignal::new(0);
+//! assert!(devtools::stats().sources >= 1);
+//! ```
+//!
+//! Durations are inclusive: the time of a component includes that of the components it creates,
+//! and the time of an effect includes that of the memos it recomputes.
+//!
+//! ## Finding leaks
+//!
+//! The live counts include the items in the arena and the subscriptions between sources and the
+//! effects and memos that depend on them. A [`checkpoint`] remembers them, and its
+//! [`delta`](Checkpoint::delta) shows how they have changed since. Counts that keep growing over
+//! a long session or a load test, after the same work has been done and undone, point to a leak:
+//!
+//! ```rust
+//! # any_spawner::Executor::init_futures_executor();
+//! use reactive_graph::{devtools, owner::Owner, signal::RwSignal};
+//!
+//! let checkpoint = devtools::checkpoint();
+//! let owner = Owner::new();
+//! owner.with(|| RwSignal::new(0));
+//! owner.cleanup();
+//! // nothing outlives the owner
+//! assert!(checkpoint.delta().is_empty(), "{}", checkpoint.delta());
+//! ```
+//!
+//! The counts are global (although the arena is that of the current thread with the
+//! `sandboxed-arenas` feature), so values created elsewhere in the meantime show up too.
+//!
+//! ## Finding hot nodes
+//!
+//! After [`record_nodes(true)`](record_nodes), every notification of a signal and every rerun of
+//! a memo or an effect is also counted by where that signal, memo or effect was defined.
+//! [`hot_nodes`] then lists the busiest ones since the last [`reset`], which makes it possible to
+//! spot a signal that changes far more often than expected, or an effect that reruns on every
+//! frame, before the UI visibly stutters:
+//!
+//! ```rust
+//! # any_spawner::Executor::init_futures_executor();
+//! # let owner = reactive_graph::owner::Owner::new(); owner.set();
+//! use reactive_graph::{devtools, prelude::*, signal::RwSignal};
+//!
+//! devtools::record_nodes(true);
+//! devtools::reset();
+//! let count = RwSignal::new(0);
+//! for i in 0..100 {
+//!     count.set(i);
+//! }
+//! for node in devtools::hot_nodes(5) {
+//!     println!("{node}");
+//! }
+//! ```
+//!
+//! Nodes are only known by where they were defined in debug mode (or with
+//! `--cfg leptos_debuginfo`), so nothing is counted by node in release mode.
+
+use crate::{owner::Arena, trace::Kind};
+use or_poisoned::OrPoisoned;
+use std::{
+    cell::Cell,
+    collections::BTreeMap,
+    fmt::{self, Display},
+    panic::Location,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Mutex,
+    },
+    time::Duration,
+};
+use web_time::Instant;
+
+pub(crate) const SOURCES: usize = 0;
+pub(crate) const MEMOS: usize = 1;
+pub(crate) const EFFECTS: usize = 2;
+const SUBSCRIPTIONS: usize = 3;
+
+static LIVE: [AtomicUsize; 4] = [
+    AtomicUsize::new(0),
+    AtomicUsize::new(0),
+    AtomicUsize::new(0),
+    AtomicUsize::new(0),
+];
+
+static TOTALS: Mutex<Totals> = Mutex::new(Totals {
+    updates: 0,
+    update_time: Duration::ZERO,
+});
+
+static COMPONENTS: Mutex<BTreeMap<&'static str, ComponentStats>> =
+    Mutex::new(BTreeMap::new());
+
+static RECORD_NODES: AtomicBool = AtomicBool::new(false);
+
+static NODES: Mutex<
+    BTreeMap<(NodeKind, &'static Location<'static>), NodeStats>,
+> = Mutex::new(BTreeMap::new());
+
+thread_local! {
+    static COMPONENT: Cell<Option<&'static str>> = const { Cell::new(None) };
+    static IGNORED: Cell<bool> = const { Cell::new(false) };
+}
+
+struct Totals {
+    updates: u64,
+    update_time: Duration,
+}
+
+/// A snapshot of the reactive graph, returned by [`stats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Stats {
+    /// The number of items in the arena, which holds `Copy` signals, stored values and other
+    /// arena-allocated values.
+    pub arena_items: usize,
+    /// The number of live signals, memos, triggers and other values that can be subscribed to.
+    pub sources: usize,
+    /// The number of live memos.
+    pub memos: usize,
+    /// The number of live effects and render effects.
+    pub effects: usize,
+    /// The number of subscriptions of effects and memos to the sources they depend on.
+    pub subscriptions: usize,
+    /// How many times effects and memos have rerun since the last [`reset`].
+    pub updates: u64,
+    /// How long those reruns took.
+    pub update_time: Duration,
+}
+
+/// What a component has done since the last [`reset`], returned by [`components`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ComponentStats {
+    /// How many times the component has been created.
+    pub renders: u64,
+    /// How long creating it took.
+    pub render_time: Duration,
+    /// How many times the effects and memos created inside it have rerun.
+    pub updates: u64,
+    /// How long those reruns took.
+    pub update_time: Duration,
+}
+
+/// The current state of the reactive graph.
+pub fn stats() -> Stats {
+    let totals = TOTALS.lock().or_poisoned();
+    Stats {
+        arena_items: Arena::try_with(|arena| arena.len()).unwrap_or_default(),
+        sources: LIVE[SOURCES].load(Ordering::Relaxed),
+        memos: LIVE[MEMOS].load(Ordering::Relaxed),
+        effects: LIVE[EFFECTS].load(Ordering::Relaxed),
+        subscriptions: LIVE[SUBSCRIPTIONS].load(Ordering::Relaxed),
+        updates: totals.updates,
+        update_time: totals.update_time,
+    }
+}
+
+impl Stats {
+    /// How the live counts changed from these statistics to `later` ones.
+    pub fn delta(&self, later: &Stats) -> Delta {
+        let diff =
+            |before: usize, after: usize| after as isize - before as isize;
+        Delta {
+            arena_items: diff(self.arena_items, later.arena_items),
+            sources: diff(self.sources, later.sources),
+            memos: diff(self.memos, later.memos),
+            effects: diff(self.effects, later.effects),
+            subscriptions: diff(self.subscriptions, later.subscriptions),
+        }
+    }
+}
+
+/// The live counts at some point, to compare with later ones. Returned by [`checkpoint`].
+#[derive(Debug, Clone, Copy)]
+pub struct Checkpoint(Stats);
+
+/// Remembers the current live counts.
+pub fn checkpoint() -> Checkpoint {
+    Checkpoint(stats())
+}
+
+impl Checkpoint {
+    /// The statistics when the checkpoint was taken.
+    pub fn stats(&self) -> &Stats {
+        &self.0
+    }
+
+    /// How the live counts have changed since the checkpoint was taken.
+    pub fn delta(&self) -> Delta {
+        self.0.delta(&stats())
+    }
+}
+
+/// The change in the live counts between two [`Stats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Delta {
+    /// The change in the number of items in the arena.
+    pub arena_items: isize,
+    /// The change in the number of live sources.
+    pub sources: isize,
+    /// The change in the number of live memos.
+    pub memos: isize,
+    /// The change in the number of live effects.
+    pub effects: isize,
+    /// The change in the number of subscriptions.
+    pub subscriptions: isize,
+}
+
+impl Delta {
+    /// Whether none of the counts changed.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl Display for Delta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let counts = [
+            (self.arena_items, "arena items"),
+            (self.sources, "sources"),
+            (self.memos, "memos"),
+            (self.effects, "effects"),
+            (self.subscriptions, "subscriptions"),
+        ];
+        let mut changed = counts.iter().filter(|(n, _)| *n != 0).peekable();
+        if changed.peek().is_none() {
+            return f.write_str("no change");
+        }
+        for (i, (n, what)) in changed.enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{n:+} {what}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The statistics of every component that has been created since the last [`reset`], by name.
+pub fn components() -> Vec<(&'static str, ComponentStats)> {
+    COMPONENTS
+        .lock()
+        .or_poisoned()
+        .iter()
+        .map(|(name, stats)| (*name, *stats))
+        .collect()
+}
+
+/// Clears the update counts and the statistics of components. The counts of live values are kept.
+pub fn reset() {
+    *TOTALS.lock().or_poisoned() = Totals {
+        updates: 0,
+        update_time: Duration::ZERO,
+    };
+    COMPONENTS.lock().or_poisoned().clear();
+    NODES.lock().or_poisoned().clear();
+}
+
+/// What kind of reactive node a [`NodeStats`] is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum NodeKind {
+    /// A signal, trigger or other source that is notified of changes directly.
+    Signal,
+    /// A memo.
+    Memo,
+    /// An effect or render effect.
+    Effect,
+}
+
+/// How busy the nodes defined at one location have been since the last [`reset`], returned by
+/// [`hot_nodes`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct NodeStats {
+    /// What kind of node this is.
+    pub kind: NodeKind,
+    /// Where the nodes were defined.
+    pub defined_at: &'static Location<'static>,
+    /// How many times a signal notified its subscribers of a change.
+    pub notifications: u64,
+    /// How many times a memo or effect reran.
+    pub runs: u64,
+    /// How long those reruns took.
+    pub run_time: Duration,
+}
+
+impl NodeStats {
+    fn new(kind: NodeKind, defined_at: &'static Location<'static>) -> Self {
+        Self {
+            kind,
+            defined_at,
+            notifications: 0,
+            runs: 0,
+            run_time: Duration::ZERO,
+        }
+    }
+
+    /// How many times the nodes were notified or reran, by which the hottest nodes are ranked.
+    pub fn activity(&self) -> u64 {
+        self.notifications + self.runs
+    }
+}
+
+impl Display for NodeStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            NodeKind::Signal => write!(
+                f,
+                "signal at {}: {} notifications",
+                self.defined_at, self.notifications
+            ),
+            NodeKind::Memo | NodeKind::Effect => write!(
+                f,
+                "{} at {}: {} runs in {:?}",
+                if self.kind == NodeKind::Memo {
+                    "memo"
+                } else {
+                    "effect"
+                },
+                self.defined_at,
+                self.runs,
+                self.run_time
+            ),
+        }
+    }
+}
+
+/// Turns counting notifications and reruns by node on or off. It is off by default, because it
+/// slows every update down.
+pub fn record_nodes(enabled: bool) {
+    RECORD_NODES.store(enabled, Ordering::Relaxed);
+}
+
+/// The `n` nodes that have been the busiest since the last [`reset`], busiest first.
+pub fn hot_nodes(n: usize) -> Vec<NodeStats> {
+    let mut nodes = NODES
+        .lock()
+        .or_poisoned()
+        .values()
+        .copied()
+        .collect::<Vec<_>>();
+    nodes.sort_by_key(|node| std::cmp::Reverse(node.activity()));
+    nodes.truncate(n);
+    nodes
+}
+
+/// Records that the signal defined at `defined_at` notified its subscribers.
+pub(crate) fn notified(defined_at: Option<&'static Location<'static>>) {
+    if !RECORD_NODES.load(Ordering::Relaxed) {
+        return;
+    }
+    if let Some(defined_at) = defined_at {
+        NODES
+            .lock()
+            .or_poisoned()
+            .entry((NodeKind::Signal, defined_at))
+            .or_insert_with(|| NodeStats::new(NodeKind::Signal, defined_at))
+            .notifications += 1;
+    }
+}
+
+/// Runs `fun` without recording anything about the components it creates, or about the reruns of
+/// the effects and memos it creates, so that development tools don't measure themselves.
+pub fn ignore<T>(fun: impl FnOnce() -> T) -> T {
+    let prev = IGNORED.replace(true);
+    let value = fun();
+    IGNORED.set(prev);
+    value
+}
+
+/// Records the creation of the component `name`, which lasts until the guard is dropped.
+pub fn enter_component(name: &'static str) -> ComponentGuard {
+    let recorded = !IGNORED.get();
+    if recorded {
+        COMPONENTS
+            .lock()
+            .or_poisoned()
+            .entry(name)
+            .or_default()
+            .renders += 1;
+    }
+    ComponentGuard {
+        name,
+        prev: COMPONENT.replace(Some(name)),
+        started: recorded.then(Instant::now),
+    }
+}
+
+/// Ends the creation of a component when dropped. Returned by [`enter_component`].
+#[must_use = "the component is only entered until the guard is dropped"]
+pub struct ComponentGuard {
+    name: &'static str,
+    prev: Option<&'static str>,
+    started: Option<Instant>,
+}
+
+impl fmt::Debug for ComponentGuard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ComponentGuard")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for ComponentGuard {
+    fn drop(&mut self) {
+        COMPONENT.set(self.prev);
+        if let Some(started) = self.started {
+            COMPONENTS
+                .lock()
+                .or_poisoned()
+                .entry(self.name)
+                .or_default()
+                .render_time += started.elapsed();
+        }
+    }
+}
+
+/// Keeps one of the live counts up while it exists.
+#[derive(Debug)]
+pub(crate) struct Live<const COUNT: usize>(());
+
+impl<const COUNT: usize> Default for Live<COUNT> {
+    fn default() -> Self {
+        LIVE[COUNT].fetch_add(1, Ordering::Relaxed);
+        Self(())
+    }
+}
+
+impl<const COUNT: usize> Clone for Live<COUNT> {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl<const COUNT: usize> Drop for Live<COUNT> {
+    fn drop(&mut self) {
+        LIVE[COUNT].fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Counts a source, and the subscriptions to it, while it exists.
+#[derive(Debug, Default)]
+pub(crate) struct SourceTracker {
+    _live: Live<SOURCES>,
+    subscriptions: usize,
+}
+
+impl SourceTracker {
+    pub(crate) fn subscribed(&mut self) {
+        self.subscriptions += 1;
+        LIVE[SUBSCRIPTIONS].fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn unsubscribed(&mut self, count: usize) {
+        self.subscriptions -= count;
+        LIVE[SUBSCRIPTIONS].fetch_sub(count, Ordering::Relaxed);
+    }
+}
+
+impl Clone for SourceTracker {
+    fn clone(&self) -> Self {
+        LIVE[SUBSCRIPTIONS].fetch_add(self.subscriptions, Ordering::Relaxed);
+        Self {
+            _live: Live::default(),
+            subscriptions: self.subscriptions,
+        }
+    }
+}
+
+impl Drop for SourceTracker {
+    fn drop(&mut self) {
+        LIVE[SUBSCRIPTIONS].fetch_sub(self.subscriptions, Ordering::Relaxed);
+    }
+}
+
+/// Counts an effect or memo while it exists, and records its reruns.
+#[derive(Debug, Clone)]
+pub(crate) struct Tracker {
+    _memo: Option<Live<MEMOS>>,
+    _effect: Option<Live<EFFECTS>>,
+    kind: NodeKind,
+    defined_at: &'static Location<'static>,
+    // `None` if it was created while ignored
+    component: Option<Option<&'static str>>,
+}
+
+impl Tracker {
+    #[track_caller]
+    pub(crate) fn new(kind: Kind) -> Self {
+        let memo = matches!(kind, Kind::Memo);
+        Self {
+            _memo: memo.then(Live::default),
+            _effect: (!memo).then(Live::default),
+            kind: if memo {
+                NodeKind::Memo
+            } else {
+                NodeKind::Effect
+            },
+            defined_at: Location::caller(),
+            component: (!IGNORED.get()).then(|| COMPONENT.get()),
+        }
+    }
+
+    /// Runs a rerun, and records how long it took.
+    pub(crate) fn time<T>(&self, fun: impl FnOnce() -> T) -> T {
+        let Some(component) = self.component else {
+            return fun();
+        };
+        let started = Instant::now();
+        let value = fun();
+        let elapsed = started.elapsed();
+
+        {
+            let mut totals = TOTALS.lock().or_poisoned();
+            totals.updates += 1;
+            totals.update_time += elapsed;
+        }
+        if let Some(component) = component {
+            let mut components = COMPONENTS.lock().or_poisoned();
+            let stats = components.entry(component).or_default();
+            stats.updates += 1;
+            stats.update_time += elapsed;
+        }
+        if RECORD_NODES.load(Ordering::Relaxed) {
+            let mut nodes = NODES.lock().or_poisoned();
+            let stats = nodes
+                .entry((self.kind, self.defined_at))
+                .or_insert_with(|| NodeStats::new(self.kind, self.defined_at));
+            stats.runs += 1;
+            stats.run_time += elapsed;
+        }
+        value
+    }
+}
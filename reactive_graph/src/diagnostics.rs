@@ -19,8 +19,11 @@ pub struct SpecialNonReactiveZoneGuard;
 use pin_project_lite::pin_project;
 use std::{
     cell::Cell,
+    fmt::{self, Display},
     future::Future,
+    panic::Location,
     pin::Pin,
+    sync::atomic::{AtomicBool, Ordering},
     task::{Context, Poll},
 };
 
@@ -93,3 +96,77 @@ pub fn suppress_resource_load(suppress: bool) {
 pub fn is_suppressing_resource_load() -> bool {
     SUPPRESS_RESOURCE_LOAD.with(|w| w.get())
 }
+
+static CONTEXT_WARNINGS: AtomicBool = AtomicBool::new(false);
+
+/// Enables or disables warnings about context, in debug mode (or with `--cfg leptos_debuginfo`).
+///
+/// Once enabled, a warning is logged when [`use_context`](crate::owner::use_context) finds no
+/// value, which lists the contexts that can be used there and where a value of the same type was
+/// provided elsewhere, and when [`provide_context`](crate::owner::provide_context) shadows a
+/// value of the same type that an ancestor provided. Shadowing a value on purpose with
+/// [`shadow_context`](crate::owner::shadow_context) does not warn.
+///
+/// These are disabled by default, because libraries often look for optional contexts, and
+/// shadow their own contexts in nested components.
+pub fn context_warnings(enabled: bool) {
+    CONTEXT_WARNINGS.store(enabled, Ordering::Relaxed);
+}
+
+#[cfg(any(debug_assertions, leptos_debuginfo))]
+pub(crate) fn context_warnings_enabled() -> bool {
+    CONTEXT_WARNINGS.load(Ordering::Relaxed)
+}
+
+/// What kind of reactive node a [`RunningNode`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NodeKind {
+    /// An [`Effect`](crate::effect::Effect).
+    Effect,
+    /// A [`RenderEffect`](crate::effect::RenderEffect).
+    RenderEffect,
+    /// A memo.
+    Memo,
+}
+
+impl Display for NodeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            NodeKind::Effect => "effect",
+            NodeKind::RenderEffect => "render effect",
+            NodeKind::Memo => "memo",
+        })
+    }
+}
+
+/// An effect or memo that is running, returned by [`running_node`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RunningNode {
+    /// What kind of node it is.
+    pub kind: NodeKind,
+    /// Where it was defined.
+    pub defined_at: &'static Location<'static>,
+}
+
+impl Display for RunningNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the {} defined at {}", self.kind, self.defined_at)
+    }
+}
+
+/// Returns the effect or memo that is running on the current thread, if any: the innermost one,
+/// if a memo is recomputed while an effect runs.
+///
+/// Nodes are only known in debug mode (or with `--cfg leptos_debuginfo`), so this is always
+/// `None` in release mode. This is meant for error reporting, like in a panic hook.
+pub fn running_node() -> Option<RunningNode> {
+    #[cfg(any(debug_assertions, leptos_debuginfo))]
+    {
+        crate::trace::running()
+    }
+    #[cfg(not(any(debug_assertions, leptos_debuginfo)))]
+    {
+        None
+    }
+}
@@ -3,12 +3,14 @@
 #[allow(clippy::module_inception)]
 mod effect;
 mod effect_function;
+pub(crate) mod flush;
 mod immediate;
 mod inner;
 mod render_effect;
 
 pub use effect::*;
 pub use effect_function::*;
+pub use flush::{flush_effects, tick};
 pub use immediate::*;
 pub use render_effect::*;
 
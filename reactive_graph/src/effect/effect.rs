@@ -1,15 +1,19 @@
 use crate::{
     channel::{channel, Receiver},
-    effect::{inner::EffectInner, EffectFunction},
+    effect::{
+        flush::{effect_task, local_effect_task},
+        inner::EffectInner,
+        EffectFunction,
+    },
     graph::{
         AnySubscriber, ReactiveNode, SourceSet, Subscriber, ToAnySubscriber,
         WithObserver,
     },
     owner::{ArenaItem, LocalStorage, Owner, Storage, SyncStorage},
+    trace::{Kind, Origin},
     traits::Dispose,
 };
 use any_spawner::Executor;
-use futures::StreamExt;
 use or_poisoned::OrPoisoned;
 use std::{
     mem,
@@ -101,6 +105,10 @@ fn effect_base() -> (Receiver, Owner, Arc<RwLock<EffectInner>>) {
     observer.notify();
 
     let owner = Owner::new();
+    owner.on_resume({
+        let rx = rx.clone();
+        move || rx.wake()
+    });
     let inner = Arc::new(RwLock::new(EffectInner {
         dirty: true,
         observer,
@@ -155,41 +163,41 @@ impl Effect<LocalStorage> {
     /// This spawns a task on the local thread using
     /// [`spawn_local`](any_spawner::Executor::spawn_local). For an effect that can be spawned on
     /// any thread, use [`new_sync`](Effect::new_sync).
+    #[track_caller]
     pub fn new<T, M>(mut fun: impl EffectFunction<T, M> + 'static) -> Self
     where
         T: 'static,
     {
+        let origin = Origin::new(Kind::Effect);
         let inner = cfg!(feature = "effects").then(|| {
-            let (mut rx, owner, inner) = effect_base();
+            let (rx, owner, inner) = effect_base();
             let value = Arc::new(RwLock::new(None::<T>));
             let mut first_run = true;
 
-            Executor::spawn_local({
+            Executor::spawn_local(local_effect_task(rx, origin, {
                 let value = Arc::clone(&value);
                 let subscriber = inner.to_any_subscriber();
 
-                async move {
-                    while rx.next().await.is_some() {
-                        if !owner.paused()
-                            && (subscriber.with_observer(|| {
-                                subscriber.update_if_necessary()
-                            }) || first_run)
-                        {
-                            first_run = false;
-                            subscriber.clear_sources(&subscriber);
-
-                            let old_value =
-                                mem::take(&mut *value.write().or_poisoned());
-                            let new_value = owner.with_cleanup(|| {
-                                subscriber.with_observer(|| {
-                                    run_in_effect_scope(|| fun.run(old_value))
-                                })
-                            });
-                            *value.write().or_poisoned() = Some(new_value);
-                        }
+                move || {
+                    if !owner.paused()
+                        && (subscriber
+                            .with_observer(|| subscriber.update_if_necessary())
+                            || first_run)
+                    {
+                        first_run = false;
+                        subscriber.clear_sources(&subscriber);
+
+                        let old_value =
+                            mem::take(&mut *value.write().or_poisoned());
+                        let new_value = owner.with_cleanup(|| {
+                            subscriber.with_observer(|| {
+                                run_in_effect_scope(|| fun.run(old_value))
+                            })
+                        });
+                        *value.write().or_poisoned() = Some(new_value);
                     }
                 }
-            });
+            }));
 
             ArenaItem::new_with_storage(Some(inner))
         });
@@ -300,6 +308,7 @@ impl Effect<LocalStorage> {
     /// # }).await;
     /// # });
     /// ```
+    #[track_caller]
     pub fn watch<D, T>(
         mut dependency_fn: impl FnMut() -> D + 'static,
         mut handler: impl FnMut(&D, Option<&D>, Option<T>) -> T + 'static,
@@ -309,56 +318,52 @@ impl Effect<LocalStorage> {
         D: 'static,
         T: 'static,
     {
+        let origin = Origin::new(Kind::Effect);
         let inner = cfg!(feature = "effects").then(|| {
-            let (mut rx, owner, inner) = effect_base();
+            let (rx, owner, inner) = effect_base();
             let mut first_run = true;
             let dep_value = Arc::new(RwLock::new(None::<D>));
             let watch_value = Arc::new(RwLock::new(None::<T>));
 
-            Executor::spawn_local({
+            Executor::spawn_local(local_effect_task(rx, origin, {
                 let dep_value = Arc::clone(&dep_value);
                 let watch_value = Arc::clone(&watch_value);
                 let subscriber = inner.to_any_subscriber();
 
-                async move {
-                    while rx.next().await.is_some() {
-                        if !owner.paused()
-                            && (subscriber.with_observer(|| {
-                                subscriber.update_if_necessary()
-                            }) || first_run)
-                        {
-                            subscriber.clear_sources(&subscriber);
-
-                            let old_dep_value = mem::take(
-                                &mut *dep_value.write().or_poisoned(),
-                            );
-                            let new_dep_value = owner.with_cleanup(|| {
-                                subscriber.with_observer(&mut dependency_fn)
-                            });
-
-                            let old_watch_value = mem::take(
-                                &mut *watch_value.write().or_poisoned(),
-                            );
+                move || {
+                    if !owner.paused()
+                        && (subscriber
+                            .with_observer(|| subscriber.update_if_necessary())
+                            || first_run)
+                    {
+                        subscriber.clear_sources(&subscriber);
 
-                            if immediate || !first_run {
-                                let new_watch_value = handler(
-                                    &new_dep_value,
-                                    old_dep_value.as_ref(),
-                                    old_watch_value,
-                                );
+                        let old_dep_value =
+                            mem::take(&mut *dep_value.write().or_poisoned());
+                        let new_dep_value = owner.with_cleanup(|| {
+                            subscriber.with_observer(&mut dependency_fn)
+                        });
 
-                                *watch_value.write().or_poisoned() =
-                                    Some(new_watch_value);
-                            }
+                        let old_watch_value =
+                            mem::take(&mut *watch_value.write().or_poisoned());
 
-                            *dep_value.write().or_poisoned() =
-                                Some(new_dep_value);
+                        if immediate || !first_run {
+                            let new_watch_value = handler(
+                                &new_dep_value,
+                                old_dep_value.as_ref(),
+                                old_watch_value,
+                            );
 
-                            first_run = false;
+                            *watch_value.write().or_poisoned() =
+                                Some(new_watch_value);
                         }
+
+                        *dep_value.write().or_poisoned() = Some(new_dep_value);
+
+                        first_run = false;
                     }
                 }
-            });
+            }));
 
             ArenaItem::new_with_storage(Some(inner))
         });
@@ -373,6 +378,7 @@ impl Effect<SyncStorage> {
     ///
     /// This spawns a task that can be run on any thread. For an effect that will be spawned on
     /// the current thread, use [`new`](Effect::new).
+    #[track_caller]
     pub fn new_sync<T, M>(
         fun: impl EffectFunction<T, M> + Send + Sync + 'static,
     ) -> Self
@@ -390,42 +396,42 @@ impl Effect<SyncStorage> {
     /// that are read inside it change.
     ///
     /// This will run whether the `effects` feature is enabled or not.
+    #[track_caller]
     pub fn new_isomorphic<T, M>(
         mut fun: impl EffectFunction<T, M> + Send + Sync + 'static,
     ) -> Self
     where
         T: Send + Sync + 'static,
     {
-        let (mut rx, owner, inner) = effect_base();
+        let origin = Origin::new(Kind::Effect);
+        let (rx, owner, inner) = effect_base();
         let mut first_run = true;
         let value = Arc::new(RwLock::new(None::<T>));
 
-        let task = {
+        let task = effect_task(rx, origin, {
             let value = Arc::clone(&value);
             let subscriber = inner.to_any_subscriber();
 
-            async move {
-                while rx.next().await.is_some() {
-                    if !owner.paused()
-                        && (subscriber
-                            .with_observer(|| subscriber.update_if_necessary())
-                            || first_run)
-                    {
-                        first_run = false;
-                        subscriber.clear_sources(&subscriber);
-
-                        let old_value =
-                            mem::take(&mut *value.write().or_poisoned());
-                        let new_value = owner.with_cleanup(|| {
-                            subscriber.with_observer(|| {
-                                run_in_effect_scope(|| fun.run(old_value))
-                            })
-                        });
-                        *value.write().or_poisoned() = Some(new_value);
-                    }
+            move || {
+                if !owner.paused()
+                    && (subscriber
+                        .with_observer(|| subscriber.update_if_necessary())
+                        || first_run)
+                {
+                    first_run = false;
+                    subscriber.clear_sources(&subscriber);
+
+                    let old_value =
+                        mem::take(&mut *value.write().or_poisoned());
+                    let new_value = owner.with_cleanup(|| {
+                        subscriber.with_observer(|| {
+                            run_in_effect_scope(|| fun.run(old_value))
+                        })
+                    });
+                    *value.write().or_poisoned() = Some(new_value);
                 }
             }
-        };
+        });
 
         crate::spawn(task);
 
@@ -435,6 +441,7 @@ impl Effect<SyncStorage> {
     }
 
     /// This is to [`Effect::watch`] what [`Effect::new_sync`] is to [`Effect::new`].
+    #[track_caller]
     pub fn watch_sync<D, T>(
         mut dependency_fn: impl FnMut() -> D + Send + Sync + 'static,
         mut handler: impl FnMut(&D, Option<&D>, Option<T>) -> T
@@ -447,56 +454,52 @@ impl Effect<SyncStorage> {
         D: Send + Sync + 'static,
         T: Send + Sync + 'static,
     {
-        let (mut rx, owner, inner) = effect_base();
+        let origin = Origin::new(Kind::Effect);
+        let (rx, owner, inner) = effect_base();
         let mut first_run = true;
         let dep_value = Arc::new(RwLock::new(None::<D>));
         let watch_value = Arc::new(RwLock::new(None::<T>));
 
         let inner = cfg!(feature = "effects").then(|| {
-            crate::spawn({
+            crate::spawn(effect_task(rx, origin, {
                 let dep_value = Arc::clone(&dep_value);
                 let watch_value = Arc::clone(&watch_value);
                 let subscriber = inner.to_any_subscriber();
 
-                async move {
-                    while rx.next().await.is_some() {
-                        if !owner.paused()
-                            && (subscriber.with_observer(|| {
-                                subscriber.update_if_necessary()
-                            }) || first_run)
-                        {
-                            subscriber.clear_sources(&subscriber);
-
-                            let old_dep_value = mem::take(
-                                &mut *dep_value.write().or_poisoned(),
-                            );
-                            let new_dep_value = owner.with_cleanup(|| {
-                                subscriber.with_observer(&mut dependency_fn)
-                            });
-
-                            let old_watch_value = mem::take(
-                                &mut *watch_value.write().or_poisoned(),
-                            );
+                move || {
+                    if !owner.paused()
+                        && (subscriber
+                            .with_observer(|| subscriber.update_if_necessary())
+                            || first_run)
+                    {
+                        subscriber.clear_sources(&subscriber);
 
-                            if immediate || !first_run {
-                                let new_watch_value = handler(
-                                    &new_dep_value,
-                                    old_dep_value.as_ref(),
-                                    old_watch_value,
-                                );
+                        let old_dep_value =
+                            mem::take(&mut *dep_value.write().or_poisoned());
+                        let new_dep_value = owner.with_cleanup(|| {
+                            subscriber.with_observer(&mut dependency_fn)
+                        });
 
-                                *watch_value.write().or_poisoned() =
-                                    Some(new_watch_value);
-                            }
+                        let old_watch_value =
+                            mem::take(&mut *watch_value.write().or_poisoned());
 
-                            *dep_value.write().or_poisoned() =
-                                Some(new_dep_value);
+                        if immediate || !first_run {
+                            let new_watch_value = handler(
+                                &new_dep_value,
+                                old_dep_value.as_ref(),
+                                old_watch_value,
+                            );
 
-                            first_run = false;
+                            *watch_value.write().or_poisoned() =
+                                Some(new_watch_value);
                         }
+
+                        *dep_value.write().or_poisoned() = Some(new_dep_value);
+
+                        first_run = false;
                     }
                 }
-            });
+            }));
 
             ArenaItem::new_with_storage(Some(inner))
         });
@@ -0,0 +1,244 @@
+use crate::{channel::Receiver, owner::Owner, trace::Origin};
+use futures::StreamExt;
+use or_poisoned::OrPoisoned;
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    future::Future,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, TryLockError, Weak,
+    },
+};
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// The effects that [`flush_effects`] can run for one reactive graph: those created under the
+/// same root [`Owner`], or on the same thread without an owner.
+pub(crate) struct EffectRegistry {
+    id: u64,
+    // the effects that can be run synchronously, in the order they were created
+    sync: Mutex<BTreeMap<u64, Weak<dyn Flush + Send + Sync>>>,
+}
+
+impl Default for EffectRegistry {
+    fn default() -> Self {
+        Self {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            sync: Default::default(),
+        }
+    }
+}
+
+impl EffectRegistry {
+    pub(crate) fn new() -> Arc<Self> {
+        Arc::default()
+    }
+
+    /// The registry of the current owner's graph.
+    fn current() -> Arc<Self> {
+        Owner::current()
+            .map(|owner| owner.effect_registry())
+            .unwrap_or_else(|| UNOWNED_EFFECTS.with(Arc::clone))
+    }
+}
+
+impl std::fmt::Debug for EffectRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EffectRegistry")
+            .field("id", &self.id)
+            .finish()
+    }
+}
+
+// effects that can only run on this thread, with the id of the registry they belong to
+type LocalEffects = BTreeMap<u64, (u64, Weak<dyn Flush>)>;
+
+thread_local! {
+    static LOCAL_EFFECTS: RefCell<LocalEffects> =
+        const { RefCell::new(BTreeMap::new()) };
+    static UNOWNED_EFFECTS: Arc<EffectRegistry> = EffectRegistry::new();
+}
+
+/// Synchronously runs every effect of the current reactive graph that has been notified of a change,
+/// including effects that have not had their first run yet, until none is left.
+///
+/// Effects normally run on the next “tick” of the async executor. This lets tests and imperative
+/// code observe the state after the effects that depend on a change have run, without having to
+/// wait for the executor:
+///
+/// ```
+/// # use reactive_graph::{effect::{flush_effects, Effect}, prelude::*, signal::RwSignal};
+/// # use std::sync::{Arc, RwLock};
+/// # any_spawner::Executor::init_futures_executor();
+/// # let owner = reactive_graph::owner::Owner::new(); owner.set();
+/// let count = RwSignal::new(0);
+/// let log = Arc::new(RwLock::new(Vec::new()));
+///
+/// Effect::new({
+///     let log = Arc::clone(&log);
+///     move || log.write().unwrap().push(count.get())
+/// });
+/// flush_effects();
+/// # if cfg!(feature = "effects") {
+/// assert_eq!(*log.read().unwrap(), [0]);
+/// # }
+///
+/// count.set(1);
+/// flush_effects();
+/// # if cfg!(feature = "effects") {
+/// assert_eq!(*log.read().unwrap(), [0, 1]);
+/// # }
+/// ```
+///
+/// The graph is the tree of the current [`Owner`]: only effects created under the same root owner
+/// are run, so that flushing doesn't touch the effects of other tests or requests. Effects that are
+/// created without an owner belong to a graph of their own on each thread.
+///
+/// An effect that is already running, on this thread (because it called this function) or on
+/// another one, is skipped, and runs again when its current run is done if it needs to.
+pub fn flush_effects() {
+    let registry = EffectRegistry::current();
+    loop {
+        let local = LOCAL_EFFECTS.with(|effects| {
+            effects
+                .borrow()
+                .values()
+                .filter(|(registry_id, _)| *registry_id == registry.id)
+                .filter_map(|(_, effect)| effect.upgrade())
+                .collect::<Vec<_>>()
+        });
+        let sync = registry
+            .sync
+            .lock()
+            .or_poisoned()
+            .values()
+            .filter_map(Weak::upgrade)
+            .collect::<Vec<_>>();
+
+        let mut ran = false;
+        for effect in local {
+            ran |= effect.flush();
+        }
+        for effect in sync {
+            ran |= effect.flush();
+        }
+        // effects that ran may have notified other effects
+        if !ran {
+            break;
+        }
+    }
+}
+
+/// Waits for the next “tick” of the async executor, and then runs the pending effects with
+/// [`flush_effects`].
+///
+/// This also lets the tasks that effects depend on, like those of resources, make progress.
+pub async fn tick() {
+    any_spawner::Executor::tick().await;
+    flush_effects();
+}
+
+trait Flush {
+    /// Runs the effect if it has been notified, and returns whether it ran.
+    fn flush(&self) -> bool;
+}
+
+struct Runner<F> {
+    id: u64,
+    local: bool,
+    registry: Arc<EffectRegistry>,
+    origin: Origin,
+    rx: Receiver,
+    run: Mutex<F>,
+}
+
+impl<F: FnMut()> Runner<F> {
+    fn new(rx: Receiver, origin: Origin, run: F, local: bool) -> Self {
+        Self {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            local,
+            registry: EffectRegistry::current(),
+            origin,
+            rx,
+            run: Mutex::new(run),
+        }
+    }
+
+    fn run(&self) {
+        let mut run = self.run.lock().or_poisoned();
+        self.origin.run(self.rx.take_cause(), &mut *run);
+    }
+}
+
+impl<F: FnMut()> Flush for Runner<F> {
+    fn flush(&self) -> bool {
+        let mut run = match self.run.try_lock() {
+            Ok(run) => run,
+            Err(TryLockError::Poisoned(e)) => e.into_inner(),
+            // it's running, and has the notification, if any
+            Err(TryLockError::WouldBlock) => return false,
+        };
+        let notified = self.rx.take_notification();
+        if notified {
+            self.origin.run(self.rx.take_cause(), &mut *run);
+        }
+        notified
+    }
+}
+
+impl<F> Drop for Runner<F> {
+    fn drop(&mut self) {
+        if self.local {
+            // the thread-local may already have been destroyed if the thread is exiting
+            _ = LOCAL_EFFECTS
+                .try_with(|effects| effects.borrow_mut().remove(&self.id));
+        } else {
+            self.registry.sync.lock().or_poisoned().remove(&self.id);
+        }
+    }
+}
+
+/// Creates the task of an effect that runs on this thread, which calls `run` whenever `rx` is
+/// notified, unless [`flush_effects`] has already done so.
+pub(crate) fn local_effect_task(
+    mut rx: Receiver,
+    origin: Origin,
+    run: impl FnMut() + 'static,
+) -> impl Future<Output = ()> {
+    let runner = Arc::new(Runner::new(rx.clone(), origin, run, true));
+    let weak: Weak<dyn Flush> = Arc::downgrade(&runner) as _;
+    LOCAL_EFFECTS.with(|effects| {
+        effects
+            .borrow_mut()
+            .insert(runner.id, (runner.registry.id, weak))
+    });
+
+    async move {
+        while rx.next().await.is_some() {
+            runner.run();
+        }
+    }
+}
+
+/// Like [`local_effect_task`], for an effect that can run on any thread.
+pub(crate) fn effect_task(
+    mut rx: Receiver,
+    origin: Origin,
+    run: impl FnMut() + Send + 'static,
+) -> impl Future<Output = ()> + Send {
+    let runner = Arc::new(Runner::new(rx.clone(), origin, run, false));
+    let weak: Weak<dyn Flush + Send + Sync> = Arc::downgrade(&runner) as _;
+    runner
+        .registry
+        .sync
+        .lock()
+        .or_poisoned()
+        .insert(runner.id, weak);
+
+    async move {
+        while rx.next().await.is_some() {
+            runner.run();
+        }
+    }
+}
@@ -203,7 +203,15 @@ mod inner {
         ) -> Arc<RwLock<EffectInner>> {
             let owner = Owner::new();
 
-            Arc::new_cyclic(|weak| {
+            Arc::new_cyclic(|weak: &Weak<RwLock<EffectInner>>| {
+                owner.on_resume({
+                    let weak = Weak::clone(weak);
+                    move || {
+                        if let Some(inner) = weak.upgrade() {
+                            inner.update_if_necessary();
+                        }
+                    }
+                });
                 let any_subscriber = AnySubscriber(
                     weak.as_ptr() as usize,
                     Weak::clone(weak) as Weak<dyn Subscriber + Send + Sync>,
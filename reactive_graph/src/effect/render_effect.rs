@@ -1,13 +1,13 @@
 use crate::{
     channel::channel,
-    effect::inner::EffectInner,
+    effect::{flush::effect_task, inner::EffectInner},
     graph::{
         AnySubscriber, ReactiveNode, SourceSet, Subscriber, ToAnySubscriber,
         WithObserver,
     },
     owner::Owner,
+    trace::{Kind, Origin},
 };
-use futures::StreamExt;
 use or_poisoned::OrPoisoned;
 use std::{
     fmt::Debug,
@@ -52,11 +52,13 @@ where
     T: 'static,
 {
     /// Creates a new render effect, which immediately runs `fun`.
+    #[track_caller]
     pub fn new(fun: impl FnMut(Option<T>) -> T + 'static) -> Self {
         Self::new_with_value_erased(Box::new(fun), None)
     }
 
     /// Creates a new render effect with an initial value.
+    #[track_caller]
     pub fn new_with_value(
         fun: impl FnMut(Option<T>) -> T + 'static,
         initial_value: Option<T>,
@@ -64,6 +66,7 @@ where
         Self::new_with_value_erased(Box::new(fun), initial_value)
     }
 
+    #[track_caller]
     fn new_with_value_erased(
         mut fun: Box<dyn FnMut(Option<T>) -> T + 'static>,
         initial_value: Option<T>,
@@ -73,6 +76,10 @@ where
         {
             let (observer, rx) = channel();
             let owner = Owner::new();
+            owner.on_resume({
+                let rx = rx.clone();
+                move || rx.wake()
+            });
             let inner = Arc::new(RwLock::new(EffectInner {
                 dirty: false,
                 observer,
@@ -81,7 +88,8 @@ where
             (owner, inner, rx)
         }
 
-        let (owner, inner, mut rx) = prep();
+        let (owner, inner, rx) = prep();
+        let origin = Origin::new(Kind::RenderEffect);
 
         let value = Arc::new(RwLock::new(None::<T>));
 
@@ -89,7 +97,8 @@ where
         {
             let _ = initial_value;
             let _ = owner;
-            let _ = &mut rx;
+            let _ = rx;
+            let _ = origin;
             let _ = &mut fun;
         }
 
@@ -100,11 +109,11 @@ where
                 owner.with(|| subscriber.with_observer(|| fun(initial_value))),
             );
 
-            any_spawner::Executor::spawn_local({
-                let value = Arc::clone(&value);
+            any_spawner::Executor::spawn_local(
+                super::flush::local_effect_task(rx, origin, {
+                    let value = Arc::clone(&value);
 
-                async move {
-                    while rx.next().await.is_some() {
+                    move || {
                         if !owner.paused()
                             && subscriber.with_observer(|| {
                                 subscriber.update_if_necessary()
@@ -120,8 +129,8 @@ where
                             *value.write().or_poisoned() = Some(new_value);
                         }
                     }
-                }
-            });
+                }),
+            );
         }
 
         RenderEffect { value, inner }
@@ -146,15 +155,22 @@ where
     T: Send + Sync + 'static,
 {
     /// Creates a render effect that will run whether the `effects` feature is enabled or not.
+    #[track_caller]
     pub fn new_isomorphic(
         fun: impl FnMut(Option<T>) -> T + Send + Sync + 'static,
     ) -> Self {
+        #[track_caller]
         fn erased<T: Send + Sync + 'static>(
             mut fun: Box<dyn FnMut(Option<T>) -> T + Send + Sync + 'static>,
         ) -> RenderEffect<T> {
-            let (observer, mut rx) = channel();
+            let origin = Origin::new(Kind::RenderEffect);
+            let (observer, rx) = channel();
             let value = Arc::new(RwLock::new(None::<T>));
             let owner = Owner::new();
+            owner.on_resume({
+                let rx = rx.clone();
+                move || rx.wake()
+            });
             let inner = Arc::new(RwLock::new(EffectInner {
                 dirty: false,
                 observer,
@@ -165,29 +181,26 @@ where
                 .with(|| inner.to_any_subscriber().with_observer(|| fun(None)));
             *value.write().or_poisoned() = Some(initial_value);
 
-            crate::spawn({
+            crate::spawn(effect_task(rx, origin, {
                 let value = Arc::clone(&value);
                 let subscriber = inner.to_any_subscriber();
 
-                async move {
-                    while rx.next().await.is_some() {
-                        if !owner.paused()
-                            && subscriber.with_observer(|| {
-                                subscriber.update_if_necessary()
-                            })
-                        {
-                            subscriber.clear_sources(&subscriber);
-
-                            let old_value =
-                                mem::take(&mut *value.write().or_poisoned());
-                            let new_value = owner.with_cleanup(|| {
-                                subscriber.with_observer(|| fun(old_value))
-                            });
-                            *value.write().or_poisoned() = Some(new_value);
-                        }
+                move || {
+                    if !owner.paused()
+                        && subscriber
+                            .with_observer(|| subscriber.update_if_necessary())
+                    {
+                        subscriber.clear_sources(&subscriber);
+
+                        let old_value =
+                            mem::take(&mut *value.write().or_poisoned());
+                        let new_value = owner.with_cleanup(|| {
+                            subscriber.with_observer(|| fun(old_value))
+                        });
+                        *value.write().or_poisoned() = Some(new_value);
                     }
                 }
-            });
+            }));
 
             RenderEffect { value, inner }
         }
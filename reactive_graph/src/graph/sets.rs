@@ -60,16 +60,25 @@ impl<'a> IntoIterator for &'a SourceSet {
     }
 }
 #[derive(Debug, Default, Clone)]
-pub struct SubscriberSet(Vec<AnySubscriber>);
+pub struct SubscriberSet(
+    Vec<AnySubscriber>,
+    #[cfg(feature = "devtools")] crate::devtools::SourceTracker,
+);
 
 impl SubscriberSet {
     pub fn new() -> Self {
-        Self(Vec::with_capacity(2))
+        Self(
+            Vec::with_capacity(2),
+            #[cfg(feature = "devtools")]
+            Default::default(),
+        )
     }
 
     pub fn subscribe(&mut self, subscriber: AnySubscriber) {
         if !self.0.contains(&subscriber) {
             self.0.push(subscriber);
+            #[cfg(feature = "devtools")]
+            self.1.subscribed();
         }
     }
 
@@ -83,10 +92,14 @@ impl SubscriberSet {
             // has already run (for example, an outer effect that checks .is_some(),
             // and an inner effect that unwraps)
             self.0.remove(pos);
+            #[cfg(feature = "devtools")]
+            self.1.unsubscribed(1);
         }
     }
 
     pub fn take(&mut self) -> Vec<AnySubscriber> {
+        #[cfg(feature = "devtools")]
+        self.1.unsubscribed(self.0.len());
         mem::take(&mut self.0)
     }
 
@@ -0,0 +1,27 @@
+//! A hook that is told about every signal created with [`signal`](crate::signal::signal) or
+//! [`RwSignal::new`](crate::signal::RwSignal::new), when the `hot-state` feature is enabled, so
+//! that the values of signals can be kept across hot reloads without registering each one.
+
+use crate::signal::ArcRwSignal;
+use std::{any::Any, panic::Location, sync::OnceLock};
+
+/// Called with the place a signal was created at, and the signal itself as an
+/// [`ArcRwSignal<T>`].
+pub type SignalHook = fn(&'static Location<'static>, &dyn Any);
+
+static HOOK: OnceLock<SignalHook> = OnceLock::new();
+
+/// Sets the hook that is called for every signal created from now on. Only the first hook is
+/// kept.
+pub fn on_signal_created(hook: SignalHook) {
+    _ = HOOK.set(hook);
+}
+
+pub(crate) fn created<T: 'static>(
+    location: &'static Location<'static>,
+    signal: &ArcRwSignal<T>,
+) {
+    if let Some(hook) = HOOK.get() {
+        hook(location, signal);
+    }
+}
@@ -77,20 +77,26 @@ use std::{fmt::Arguments, future::Future};
 pub mod actions;
 pub(crate) mod channel;
 pub mod computed;
+#[cfg(feature = "devtools")]
+pub mod devtools;
 pub mod diagnostics;
 pub mod effect;
 pub mod graph;
+#[cfg(feature = "hot-state")]
+pub mod hot_state;
 pub mod owner;
 pub mod send_wrapper_ext;
 #[cfg(feature = "serde")]
 mod serde;
 pub mod signal;
+mod trace;
 mod trait_options;
 pub mod traits;
 pub mod transition;
 pub mod wrappers;
 
 use computed::ScopedFuture;
+pub use effect::{flush_effects, tick};
 
 #[cfg(all(feature = "nightly", rustc_nightly))]
 mod nightly;
@@ -127,7 +133,14 @@ pub fn log_warning(text: Arguments) {
 
 /// Calls [`Executor::spawn`](any_spawner::Executor::spawn) on non-wasm targets and [`Executor::spawn_local`](any_spawner::Executor::spawn_local) on wasm targets, but ensures that the task also runs in the current arena, if
 /// multithreaded arena sandboxing is enabled.
+///
+/// With the `tracing` feature, the task runs in the tracing span that was current when it was
+/// spawned.
 pub fn spawn(task: impl Future<Output = ()> + Send + 'static) {
+    let task = owner::TaskCancellation::register(task);
+    #[cfg(feature = "tracing")]
+    let task = tracing::Instrument::in_current_span(task);
+
     #[cfg(feature = "sandboxed-arenas")]
     let task = owner::Sandboxed::new(task);
 
@@ -141,6 +154,10 @@ pub fn spawn(task: impl Future<Output = ()> + Send + 'static) {
 /// Calls [`Executor::spawn_local`](any_spawner::Executor::spawn_local), but ensures that the task also runs in the current arena, if
 /// multithreaded arena sandboxing is enabled.
 pub fn spawn_local(task: impl Future<Output = ()> + 'static) {
+    let task = owner::TaskCancellation::register(task);
+    #[cfg(feature = "tracing")]
+    let task = tracing::Instrument::in_current_span(task);
+
     #[cfg(feature = "sandboxed-arenas")]
     let task = owner::Sandboxed::new(task);
 
@@ -151,7 +168,9 @@ pub fn spawn_local(task: impl Future<Output = ()> + 'static) {
 ///
 /// Does not cancel the task if the owner is cleaned up.
 pub fn spawn_local_scoped(task: impl Future<Output = ()> + 'static) {
-    let task = ScopedFuture::new(task);
+    let task = ScopedFuture::new(owner::TaskCancellation::register(task));
+    #[cfg(feature = "tracing")]
+    let task = tracing::Instrument::in_current_span(task);
 
     #[cfg(feature = "sandboxed-arenas")]
     let task = owner::Sandboxed::new(task);
@@ -172,12 +191,15 @@ pub fn spawn_local_scoped_with_cancellation(
     on_cleanup(move || abort_handle.abort());
 
     let task = Abortable::new(task, abort_registration);
-    let task = ScopedFuture::new(task);
+    let task =
+        ScopedFuture::new(owner::TaskCancellation::register(async move {
+            _ = task.await;
+        }));
+    #[cfg(feature = "tracing")]
+    let task = tracing::Instrument::in_current_span(task);
 
     #[cfg(feature = "sandboxed-arenas")]
     let task = owner::Sandboxed::new(task);
 
-    any_spawner::Executor::spawn_local(async move {
-        _ = task.await;
-    });
+    any_spawner::Executor::spawn_local(task);
 }
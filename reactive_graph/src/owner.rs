@@ -1,5 +1,6 @@
 //! The reactive ownership model, which manages effect cancelation, cleanups, and arena allocation.
 
+use crate::effect::flush::EffectRegistry;
 #[cfg(feature = "hydration")]
 use hydration_context::SharedContext;
 use or_poisoned::OrPoisoned;
@@ -15,10 +16,11 @@ use std::{
 mod arc_stored_value;
 mod arena;
 mod arena_item;
+mod cancellation;
 mod context;
 mod storage;
 mod stored_value;
-use self::arena::Arena;
+pub(crate) use self::arena::Arena;
 pub use arc_stored_value::ArcStoredValue;
 #[cfg(feature = "sandboxed-arenas")]
 pub use arena::sandboxed::Sandboxed;
@@ -26,6 +28,7 @@ pub use arena::sandboxed::Sandboxed;
 use arena::ArenaMap;
 use arena::NodeId;
 pub use arena_item::*;
+pub use cancellation::TaskCancellation;
 pub use context::*;
 pub use storage::*;
 #[allow(deprecated)] // allow exporting deprecated fn
@@ -100,6 +103,9 @@ impl PartialEq for Owner {
 
 thread_local! {
     static OWNER: RefCell<Option<WeakOwner>> = Default::default();
+    #[cfg(any(debug_assertions, leptos_debuginfo))]
+    static COMPONENT: std::cell::Cell<Option<&'static str>> =
+        const { std::cell::Cell::new(None) };
 }
 
 impl Owner {
@@ -136,6 +142,23 @@ impl Owner {
         ancestors
     }
 
+    /// Returns the names of the components that this owner and its ancestors were created in,
+    /// from the innermost one to the outermost one.
+    ///
+    /// Owners only know the component they were created in if it was being created at the time,
+    /// and only in debug mode (or with `--cfg leptos_debuginfo`), so this is empty in release
+    /// mode.
+    pub fn component_stack(&self) -> Vec<&'static str> {
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        {
+            component_stack(&self.inner)
+        }
+        #[cfg(not(any(debug_assertions, leptos_debuginfo)))]
+        {
+            Vec::new()
+        }
+    }
+
     /// Creates a new `Owner` and registers it as a child of the current `Owner`, if there is one.
     pub fn new() -> Self {
         #[cfg(not(feature = "hydration"))]
@@ -158,6 +181,8 @@ impl Owner {
                 parent: parent.clone(),
                 nodes: Default::default(),
                 contexts: Default::default(),
+                #[cfg(any(debug_assertions, leptos_debuginfo))]
+                provided: Default::default(),
                 cleanups: Default::default(),
                 children: Default::default(),
                 #[cfg(feature = "sandboxed-arenas")]
@@ -167,6 +192,14 @@ impl Owner {
                     .map(|parent| parent.read().or_poisoned().arena.clone())
                     .unwrap_or_default(),
                 paused: false,
+                on_resume: Default::default(),
+                effects: parent
+                    .as_ref()
+                    .and_then(|parent| parent.upgrade())
+                    .map(|parent| parent.read().or_poisoned().effects.clone())
+                    .unwrap_or_default(),
+                #[cfg(any(debug_assertions, leptos_debuginfo))]
+                component: COMPONENT.get(),
             })),
             #[cfg(feature = "hydration")]
             shared_context,
@@ -196,11 +229,17 @@ impl Owner {
                 parent: None,
                 nodes: Default::default(),
                 contexts: Default::default(),
+                #[cfg(any(debug_assertions, leptos_debuginfo))]
+                provided: Default::default(),
                 cleanups: Default::default(),
                 children: Default::default(),
                 #[cfg(feature = "sandboxed-arenas")]
                 arena: Default::default(),
                 paused: false,
+                on_resume: Default::default(),
+                effects: EffectRegistry::new(),
+                #[cfg(any(debug_assertions, leptos_debuginfo))]
+                component: COMPONENT.get(),
             })),
             #[cfg(feature = "hydration")]
             shared_context,
@@ -221,11 +260,17 @@ impl Owner {
                 parent,
                 nodes: Default::default(),
                 contexts: Default::default(),
+                #[cfg(any(debug_assertions, leptos_debuginfo))]
+                provided: Default::default(),
                 cleanups: Default::default(),
                 children: Default::default(),
                 #[cfg(feature = "sandboxed-arenas")]
                 arena,
                 paused,
+                on_resume: Default::default(),
+                effects: inner.effects.clone(),
+                #[cfg(any(debug_assertions, leptos_debuginfo))]
+                component: COMPONENT.get(),
             })),
             #[cfg(feature = "hydration")]
             shared_context: self.shared_context.clone(),
@@ -297,6 +342,10 @@ impl Owner {
         }
     }
 
+    pub(crate) fn effect_registry(&self) -> Arc<EffectRegistry> {
+        self.inner.read().or_poisoned().effects.clone()
+    }
+
     fn register(&self, node: NodeId) {
         self.inner.write().or_poisoned().nodes.push(node);
     }
@@ -340,7 +389,7 @@ impl Owner {
     #[cfg(feature = "hydration")]
     pub fn with_hydration<T>(fun: impl FnOnce() -> T + 'static) -> T {
         fn inner<T>(fun: Box<dyn FnOnce() -> T>) -> T {
-            provide_context(IsHydrating(true));
+            shadow_context(IsHydrating(true));
 
             let sc = OWNER.with_borrow(|o| {
                 o.as_ref()
@@ -366,7 +415,7 @@ impl Owner {
     #[cfg(feature = "hydration")]
     pub fn with_no_hydration<T>(fun: impl FnOnce() -> T + 'static) -> T {
         fn inner<T>(fun: Box<dyn FnOnce() -> T>) -> T {
-            provide_context(IsHydrating(false));
+            shadow_context(IsHydrating(false));
 
             let sc = OWNER.with_borrow(|o| {
                 o.as_ref()
@@ -390,10 +439,16 @@ impl Owner {
 
     /// Pauses the execution of side effects for this owner, and any of its descendants.
     ///
-    /// If this owner is the owner for an [`Effect`](crate::effect::Effect) or [`RenderEffect`](crate::effect::RenderEffect), this effect will not run until [`Owner::resume`] is called. All children of this effects are also paused.
+    /// This can be used to suspend a subtree that is not visible, like a hidden panel or a
+    /// background tab, so that its effects, render effects and async derived signals do not run
+    /// while its signals keep changing. Signals and memos can still be read and written.
     ///
-    /// Any notifications will be ignored; effects that are notified will paused will not run when
-    /// resumed, until they are notified again by a source after being resumed.
+    /// If this owner is the owner for an [`Effect`](crate::effect::Effect) or
+    /// [`RenderEffect`](crate::effect::RenderEffect), this effect will not run until
+    /// [`Owner::resume`] is called. Owners created under a paused owner are paused too.
+    ///
+    /// Notifications are not lost while paused: the effects that were notified are re-checked
+    /// when the owner is resumed.
     pub fn pause(&self) {
         let mut stack = Vec::with_capacity(16);
         stack.push(Arc::downgrade(&self.inner));
@@ -415,25 +470,98 @@ impl Owner {
     ///
     /// All children will also be resumed.
     ///
-    /// This will *not* cause side effects that were notified while paused to run, until they are
-    /// notified again by a source after being resumed.
+    /// Every effect, render effect and async derived signal in the subtree then re-checks its
+    /// sources, as it would have if it had been notified, and runs again only if one of them has
+    /// actually changed while it was paused. Effects run at most once for any number of changes
+    /// made while paused, with the latest values. Effects are scheduled as usual, while
+    /// [`ImmediateEffect`](crate::effect::ImmediateEffect)s run before this returns.
     pub fn resume(&self) {
+        let mut on_resume = Vec::new();
         let mut stack = Vec::with_capacity(16);
         stack.push(Arc::downgrade(&self.inner));
         while let Some(curr) = stack.pop() {
             if let Some(curr) = curr.upgrade() {
                 let mut curr = curr.write().or_poisoned();
                 curr.paused = false;
+                on_resume.extend(curr.on_resume.iter().cloned());
                 stack.extend(curr.children.iter().map(Weak::clone));
             }
         }
+        // the whole subtree is resumed before anything runs, and no lock is held while it does
+        for resume in on_resume {
+            resume();
+        }
+    }
+
+    /// Registers a function that re-checks a node owned by this owner when it is resumed.
+    pub(crate) fn on_resume(&self, fun: impl Fn() + Send + Sync + 'static) {
+        self.inner
+            .write()
+            .or_poisoned()
+            .on_resume
+            .push(Arc::new(fun));
+    }
+}
+
+#[cfg(any(debug_assertions, leptos_debuginfo))]
+pub(crate) fn component_stack(
+    inner: &Arc<RwLock<OwnerInner>>,
+) -> Vec<&'static str> {
+    let mut stack: Vec<&'static str> = Vec::new();
+    let mut curr = Some(Arc::clone(inner));
+    while let Some(owner) = curr {
+        let owner = owner.read().or_poisoned();
+        // the effects and memos created in a component each have their own owner
+        if let Some(name) = owner.component {
+            if stack.last() != Some(&name) {
+                stack.push(name);
+            }
+        }
+        curr = owner.parent.as_ref().and_then(|n| n.upgrade());
     }
+    stack
 }
 
 #[doc(hidden)]
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct IsHydrating(pub bool);
 
+/// Marks the component `name` as being created until the guard is dropped, so that the owners
+/// created in the meantime know which component they belong to.
+///
+/// This is called by the `#[component]` macro, and does nothing in release mode. See
+/// [`Owner::component_stack`].
+#[inline(always)]
+pub fn enter_component(name: &'static str) -> ComponentScope {
+    #[cfg(any(debug_assertions, leptos_debuginfo))]
+    {
+        ComponentScope {
+            prev: COMPONENT.replace(Some(name)),
+        }
+    }
+    #[cfg(not(any(debug_assertions, leptos_debuginfo)))]
+    {
+        _ = name;
+        ComponentScope {}
+    }
+}
+
+/// Ends the creation of a component when dropped. Returned by [`enter_component`].
+#[derive(Debug)]
+#[must_use = "the component is only entered until the guard is dropped"]
+pub struct ComponentScope {
+    #[cfg(any(debug_assertions, leptos_debuginfo))]
+    prev: Option<&'static str>,
+}
+
+impl Drop for ComponentScope {
+    #[inline(always)]
+    fn drop(&mut self) {
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        COMPONENT.set(self.prev);
+    }
+}
+
 /// Registers a function to be run the next time the current owner is cleaned up.
 ///
 /// Because the ownership model is associated with reactive nodes, each "decision point" in an
@@ -450,11 +578,18 @@ pub(crate) struct OwnerInner {
     pub parent: Option<Weak<RwLock<OwnerInner>>>,
     nodes: Vec<NodeId>,
     pub contexts: FxHashMap<TypeId, Box<dyn Any + Send + Sync>>,
+    #[cfg(any(debug_assertions, leptos_debuginfo))]
+    pub provided: FxHashMap<TypeId, context::Provided>,
     pub cleanups: Vec<Box<dyn FnOnce() + Send + Sync>>,
     pub children: Vec<Weak<RwLock<OwnerInner>>>,
     #[cfg(feature = "sandboxed-arenas")]
     arena: Arc<RwLock<ArenaMap>>,
     paused: bool,
+    on_resume: Vec<Arc<dyn Fn() + Send + Sync>>,
+    // shared by every owner under the same root
+    effects: Arc<EffectRegistry>,
+    #[cfg(any(debug_assertions, leptos_debuginfo))]
+    component: Option<&'static str>,
 }
 
 impl Debug for OwnerInner {
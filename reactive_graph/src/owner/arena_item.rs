@@ -2,7 +2,10 @@ use super::{
     arena::{Arena, NodeId},
     LocalStorage, Storage, SyncStorage, OWNER,
 };
-use crate::traits::{Dispose, IntoInner, IsDisposed};
+use crate::{
+    send_wrapper_ext::WrongThreadError,
+    traits::{Dispose, IntoInner, IsDisposed},
+};
 use send_wrapper::SendWrapper;
 use std::{any::Any, hash::Hash, marker::PhantomData};
 
@@ -96,6 +99,21 @@ where
     pub fn new_local(value: T) -> Self {
         ArenaItem::new_with_storage(value)
     }
+
+    /// Returns an error if the value belongs to a thread other than the current one, in which case
+    /// it cannot be accessed. A disposed value is not an error.
+    pub fn check_thread(&self) -> Result<(), WrongThreadError> {
+        LocalStorage::check_thread::<T>(self.node)
+    }
+
+    /// Moves the value to the current thread, so that it can be accessed from this thread from now
+    /// on, rather than from the one that created it. Returns `false` if it has been disposed.
+    pub fn transfer(&self) -> bool
+    where
+        T: Send,
+    {
+        LocalStorage::transfer::<T>(self.node)
+    }
 }
 
 impl<T, S: Storage<T>> ArenaItem<T, S> {
@@ -0,0 +1,122 @@
+use super::use_context_silent;
+use futures::future::{AbortHandle, Abortable, Either};
+use or_poisoned::OrPoisoned;
+use rustc_hash::FxHashMap;
+use std::{
+    future::Future,
+    mem,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+/// Cancels the tasks that are spawned below the owner that provides it via context.
+///
+/// Tasks spawned with [`spawn`](crate::spawn), [`spawn_local`](crate::spawn_local), and their
+/// scoped variants, including the tasks that drive async derived values and resources, are
+/// registered with the nearest `TaskCancellation` in context when they are spawned. Calling
+/// [`cancel`](TaskCancellation::cancel) drops all of them, along with whatever futures they were
+/// awaiting.
+///
+/// The server integrations provide one for each request, and cancel it if the client disconnects
+/// before the response is complete, so that an abandoned page stops loading its data. Until a
+/// `TaskCancellation` has been created, as in the browser, spawning a task doesn't look for one.
+#[derive(Debug, Clone)]
+pub struct TaskCancellation(Arc<CancellationInner>);
+
+/// Whether a `TaskCancellation` has ever been created, so that tasks need to look for one.
+static CREATED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Default)]
+struct CancellationInner {
+    cancelled: AtomicBool,
+    next_task: AtomicU64,
+    // the tasks that are still running, removed as they finish
+    tasks: Mutex<FxHashMap<u64, AbortHandle>>,
+}
+
+impl Default for TaskCancellation {
+    fn default() -> Self {
+        CREATED.store(true, Ordering::Relaxed);
+        Self(Default::default())
+    }
+}
+
+impl TaskCancellation {
+    /// Creates a new cancellation, which has no effect until it is provided via context.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels every task that has been registered with this cancellation, and any task that
+    /// is registered with it from now on.
+    pub fn cancel(&self) {
+        self.0.cancelled.store(true, Ordering::Release);
+        let tasks = mem::take(&mut *self.0.tasks.lock().or_poisoned());
+        for task in tasks.into_values() {
+            task.abort();
+        }
+    }
+
+    /// Whether [`cancel`](TaskCancellation::cancel) has been called.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.cancelled.load(Ordering::Acquire)
+    }
+
+    /// The number of registered tasks that have not finished yet.
+    pub fn running_tasks(&self) -> usize {
+        self.0.tasks.lock().or_poisoned().len()
+    }
+
+    /// Registers a task with the `TaskCancellation` in context, if any.
+    pub(crate) fn register<T>(task: T) -> impl Future<Output = ()>
+    where
+        T: Future<Output = ()>,
+    {
+        let cancellation = CREATED
+            .load(Ordering::Relaxed)
+            .then(use_context_silent::<TaskCancellation>)
+            .flatten();
+        let Some(cancellation) = cancellation else {
+            return Either::Left(task);
+        };
+
+        let (handle, registration) = AbortHandle::new_pair();
+        let id = cancellation.0.next_task.fetch_add(1, Ordering::Relaxed);
+        {
+            let mut tasks = cancellation.0.tasks.lock().or_poisoned();
+            if cancellation.is_cancelled() {
+                handle.abort();
+            } else {
+                tasks.insert(id, handle);
+            }
+        }
+        let task = Abortable::new(task, registration);
+        // removes the handle once the task completes, or is dropped
+        let registered = Registered {
+            cancellation: cancellation.0,
+            id,
+        };
+        Either::Right(async move {
+            let _registered = registered;
+            _ = task.await;
+        })
+    }
+}
+
+/// A task's place in the registry of a [`TaskCancellation`].
+struct Registered {
+    cancellation: Arc<CancellationInner>,
+    id: u64,
+}
+
+impl Drop for Registered {
+    fn drop(&mut self) {
+        self.cancellation
+            .tasks
+            .lock()
+            .or_poisoned()
+            .remove(&self.id);
+    }
+}
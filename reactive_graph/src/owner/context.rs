@@ -1,17 +1,150 @@
 use crate::owner::Owner;
 use or_poisoned::OrPoisoned;
+use rustc_hash::FxHashMap;
 use std::{
     any::{Any, TypeId},
     collections::VecDeque,
+    sync::Arc,
 };
 
+/// The values of type `T` that an owner provides with [`provide_context_keyed`], by key.
+struct Keyed<T>(FxHashMap<String, T>);
+
+/// Where a context value was provided, kept in debug mode for diagnostics.
+#[cfg(any(debug_assertions, leptos_debuginfo))]
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Provided {
+    type_name: &'static str,
+    at: &'static std::panic::Location<'static>,
+}
+
 impl Owner {
+    #[track_caller]
     fn provide_context<T: Send + Sync + 'static>(&self, value: T) {
-        self.inner
-            .write()
+        self.provide_context_inner(value, false)
+    }
+
+    #[track_caller]
+    fn provide_context_inner<T: Send + Sync + 'static>(
+        &self,
+        value: T,
+        shadows: bool,
+    ) {
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        let provided = Provided {
+            type_name: std::any::type_name::<T>(),
+            at: std::panic::Location::caller(),
+        };
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        if !shadows && crate::diagnostics::context_warnings_enabled() {
+            if let Some(shadowed) = self.provided_by_ancestor::<T>() {
+                crate::log_warning(format_args!(
+                    "provide_context::<{}>() at {} shadows the value of the \
+                     same type provided at {} by an ancestor. Use \
+                     shadow_context() if this is intended.",
+                    provided.type_name, provided.at, shadowed.at
+                ));
+            }
+        }
+        #[cfg(not(any(debug_assertions, leptos_debuginfo)))]
+        {
+            _ = shadows;
+        }
+
+        let mut inner = self.inner.write().or_poisoned();
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        inner.provided.insert(TypeId::of::<T>(), provided);
+        inner.contexts.insert(value.type_id(), Box::new(value));
+    }
+
+    /// Where the closest ancestor that provides a value of type `T` provided it.
+    #[cfg(any(debug_assertions, leptos_debuginfo))]
+    fn provided_by_ancestor<T: 'static>(&self) -> Option<Provided> {
+        let ty = TypeId::of::<T>();
+        let mut parent = self
+            .inner
+            .read()
             .or_poisoned()
-            .contexts
-            .insert(value.type_id(), Box::new(value));
+            .parent
+            .as_ref()
+            .and_then(|p| p.upgrade());
+        while let Some(this_parent) = parent {
+            let this_parent = this_parent.read().or_poisoned();
+            if let Some(provided) = this_parent.provided.get(&ty) {
+                return Some(*provided);
+            }
+            parent = this_parent.parent.as_ref().and_then(|p| p.upgrade());
+        }
+        None
+    }
+
+    /// Explains why no value of type `T` can be found in context from this owner: which contexts
+    /// can be used here, and where values of type `T` were provided elsewhere.
+    #[cfg(any(debug_assertions, leptos_debuginfo))]
+    fn describe_missing_context<T: 'static>(&self) -> String {
+        use std::fmt::Write;
+
+        let ty = TypeId::of::<T>();
+        let mut message = format!(
+            "No context of type {} was found.",
+            std::any::type_name::<T>()
+        );
+
+        // the contexts that can be used here, closest first, without those they shadow
+        let mut available: Vec<(TypeId, Provided)> = Vec::new();
+        let mut root = Arc::clone(&self.inner);
+        let mut curr = Some(Arc::clone(&self.inner));
+        while let Some(owner) = curr {
+            let lock = owner.read().or_poisoned();
+            for (ty, provided) in &lock.provided {
+                if !available.iter().any(|(other, _)| other == ty) {
+                    available.push((*ty, *provided));
+                }
+            }
+            curr = lock.parent.as_ref().and_then(|p| p.upgrade());
+            drop(lock);
+            root = owner;
+        }
+
+        // values of this type elsewhere in the tree, which can't be used from here
+        let mut elsewhere = Vec::new();
+        let mut to_search = VecDeque::from([root]);
+        while let Some(owner) = to_search.pop_front() {
+            let lock = owner.read().or_poisoned();
+            if let Some(provided) = lock.provided.get(&ty) {
+                elsewhere.push((*provided, super::component_stack(&owner)));
+            }
+            to_search.extend(lock.children.iter().filter_map(|c| c.upgrade()));
+        }
+
+        for (provided, components) in elsewhere {
+            _ = write!(
+                message,
+                "\nA value of this type was provided at {}",
+                provided.at
+            );
+            if !components.is_empty() {
+                _ = write!(message, " in <{}/>", components.join("/> < <"));
+            }
+            _ = write!(
+                message,
+                ", which is not an ancestor of this owner: context can \
+                 only be used below where it was provided."
+            );
+        }
+        if available.is_empty() {
+            message.push_str("\nNo contexts are available here.");
+        } else {
+            message.push_str("\nThe contexts available here are:");
+            for (_, provided) in available {
+                _ = write!(
+                    message,
+                    "\n  {} (provided at {})",
+                    provided.type_name, provided.at
+                );
+            }
+        }
+        message
     }
 
     fn use_context<T: Clone + 'static>(&self) -> Option<T> {
@@ -22,6 +155,8 @@ impl Owner {
         let ty = TypeId::of::<T>();
         let mut inner = self.inner.write().or_poisoned();
         let mut parent = inner.parent.as_ref().and_then(|p| p.upgrade());
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        inner.provided.remove(&ty);
         let contexts = &mut inner.contexts;
         if let Some(context) = contexts.remove(&ty) {
             context.downcast::<T>().ok().map(|n| *n)
@@ -33,6 +168,8 @@ impl Owner {
                 let downcast =
                     value.and_then(|context| context.downcast::<T>().ok());
                 if let Some(value) = downcast {
+                    #[cfg(any(debug_assertions, leptos_debuginfo))]
+                    this_parent.provided.remove(&ty);
                     return Some(*value);
                 } else {
                     parent =
@@ -101,6 +238,45 @@ impl Owner {
         reference.map(cb)
     }
 
+    fn provide_context_keyed<T: Send + Sync + 'static>(
+        &self,
+        key: String,
+        value: T,
+    ) {
+        self.inner
+            .write()
+            .or_poisoned()
+            .contexts
+            .entry(TypeId::of::<Keyed<T>>())
+            .or_insert_with(|| Box::new(Keyed::<T>(FxHashMap::default())))
+            .downcast_mut::<Keyed<T>>()
+            .expect("keyed contexts are stored by their type")
+            .0
+            .insert(key, value);
+    }
+
+    fn with_context_keyed<T: 'static, R>(
+        &self,
+        key: &str,
+        cb: impl FnOnce(&T) -> R,
+    ) -> Option<R> {
+        let ty = TypeId::of::<Keyed<T>>();
+        let mut curr = Some(Arc::clone(&self.inner));
+        while let Some(owner) = curr {
+            let owner = owner.read().or_poisoned();
+            let value = owner
+                .contexts
+                .get(&ty)
+                .and_then(|keyed| keyed.downcast_ref::<Keyed<T>>())
+                .and_then(|keyed| keyed.0.get(key));
+            if let Some(value) = value {
+                return Some(cb(value));
+            }
+            curr = owner.parent.as_ref().and_then(|p| p.upgrade());
+        }
+        None
+    }
+
     /// Searches for items stored in context in either direction, either among parents or among
     /// descendants.
     pub fn use_context_bidirectional<T: Clone + 'static>(&self) -> Option<T> {
@@ -199,12 +375,28 @@ impl Owner {
 /// });
 /// # });
 /// ```
+///
+/// With [`context_warnings`](crate::diagnostics::context_warnings) enabled, shadowing a value
+/// this way logs a warning in debug mode. Use [`shadow_context`] to shadow it on purpose.
+#[track_caller]
 pub fn provide_context<T: Send + Sync + 'static>(value: T) {
     if let Some(owner) = Owner::current() {
         owner.provide_context(value);
     }
 }
 
+/// Provides a context value of type `T` to the current reactive [`Owner`] and all of its
+/// descendants, shadowing on purpose a value of the same type that an ancestor provided.
+///
+/// This is the same as [`provide_context`], except that it never warns about shadowing, even
+/// with [`context_warnings`](crate::diagnostics::context_warnings) enabled.
+#[track_caller]
+pub fn shadow_context<T: Send + Sync + 'static>(value: T) {
+    if let Some(owner) = Owner::current() {
+        owner.provide_context_inner(value, true);
+    }
+}
+
 /// Extracts a context value of type `T` from the reactive system.
 ///
 /// This traverses the reactive ownership graph, beginning from the current reactive
@@ -246,7 +438,28 @@ pub fn provide_context<T: Send + Sync + 'static>(value: T) {
 /// });
 /// # });
 /// ```
+///
+/// With [`context_warnings`](crate::diagnostics::context_warnings) enabled, not finding a value
+/// logs a warning in debug mode, which lists the contexts that can be used here, and where values
+/// of type `T` were provided elsewhere.
+#[track_caller]
 pub fn use_context<T: Clone + 'static>() -> Option<T> {
+    let owner = Owner::current()?;
+    let value = owner.use_context();
+    #[cfg(any(debug_assertions, leptos_debuginfo))]
+    if value.is_none() && crate::diagnostics::context_warnings_enabled() {
+        crate::log_warning(format_args!(
+            "use_context() at {}: {}",
+            std::panic::Location::caller(),
+            owner.describe_missing_context::<T>()
+        ));
+    }
+    value
+}
+
+/// Like [`use_context`], but never warns when the value is missing, for the framework's own
+/// lookups of contexts that are often absent.
+pub(crate) fn use_context_silent<T: Clone + 'static>() -> Option<T> {
     Owner::current().and_then(|owner| owner.use_context())
 }
 
@@ -297,19 +510,104 @@ pub fn use_context<T: Clone + 'static>() -> Option<T> {
 /// ## Panics
 /// Panics if a context of this type is not found in the current reactive
 /// owner or its ancestors.
+///
+/// In debug mode, the panic message lists the contexts that can be used here, and where values
+/// of type `T` were provided elsewhere.
 #[track_caller]
 pub fn expect_context<T: Clone + 'static>() -> T {
     let location = std::panic::Location::caller();
 
-    use_context().unwrap_or_else(|| {
+    let owner = Owner::current();
+    owner
+        .as_ref()
+        .and_then(Owner::use_context)
+        .unwrap_or_else(|| {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            if let Some(owner) = owner {
+                panic!(
+                    "{:?} expected context of type {:?} to be present\n{}",
+                    location,
+                    std::any::type_name::<T>(),
+                    owner.describe_missing_context::<T>()
+                )
+            }
+            panic!(
+                "{:?} expected context of type {:?} to be present",
+                location,
+                std::any::type_name::<T>()
+            )
+        })
+}
+
+/// Provides a context value of type `T` under the given `key` to the current reactive [`Owner`]
+/// and all of its descendants. This can be accessed using [`use_context_keyed`] with the same
+/// key.
+///
+/// Keyed values let several values of the same type coexist, like the themes of two regions of
+/// a page, without a newtype wrapper for each one. They are separate from the value provided
+/// with [`provide_context`]: each key shadows only the value provided by an ancestor under the
+/// same key.
+///
+/// ```rust
+/// # use reactive_graph::owner::*;
+/// # let owner = Owner::new(); owner.set();
+/// provide_context_keyed("sidebar", String::from("dark"));
+/// provide_context_keyed("main", String::from("light"));
+///
+/// Owner::new().with(|| {
+///     assert_eq!(use_context_keyed::<String>("sidebar").as_deref(), Some("dark"));
+///     assert_eq!(use_context_keyed::<String>("main").as_deref(), Some("light"));
+///     assert_eq!(use_context::<String>(), None);
+/// });
+/// ```
+pub fn provide_context_keyed<T: Send + Sync + 'static>(
+    key: impl Into<String>,
+    value: T,
+) {
+    if let Some(owner) = Owner::current() {
+        owner.provide_context_keyed(key.into(), value);
+    }
+}
+
+/// Extracts a context value of type `T` that was provided under the given `key` with
+/// [`provide_context_keyed`], if any. When the value is found, it is cloned.
+///
+/// This traverses the reactive ownership graph, beginning from the current reactive [`Owner`]
+/// and iterating through its parents, if any.
+pub fn use_context_keyed<T: Clone + 'static>(key: &str) -> Option<T> {
+    with_context_keyed(key, T::clone)
+}
+
+/// Extracts a context value of type `T` that was provided under the given `key` with
+/// [`provide_context_keyed`], and panics if it can't be found.
+///
+/// ## Panics
+/// Panics if a context of this type is not found under this key in the current reactive owner
+/// or its ancestors.
+#[track_caller]
+pub fn expect_context_keyed<T: Clone + 'static>(key: &str) -> T {
+    let location = std::panic::Location::caller();
+
+    use_context_keyed(key).unwrap_or_else(|| {
         panic!(
-            "{:?} expected context of type {:?} to be present",
+            "{:?} expected context of type {:?} to be present under the key \
+             {:?}",
             location,
-            std::any::type_name::<T>()
+            std::any::type_name::<T>(),
+            key
         )
     })
 }
 
+/// Accesses a reference to a context value of type `T` that was provided under the given `key`
+/// with [`provide_context_keyed`], if any.
+pub fn with_context_keyed<T: 'static, R>(
+    key: &str,
+    cb: impl FnOnce(&T) -> R,
+) -> Option<R> {
+    Owner::current().and_then(|owner| owner.with_context_keyed(key, cb))
+}
+
 /// Extracts a context value of type `T` from the reactive system, and takes ownership,
 /// removing it from the context system.
 ///
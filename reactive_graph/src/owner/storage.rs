@@ -1,5 +1,5 @@
 use super::arena::{Arena, NodeId};
-use send_wrapper::SendWrapper;
+use crate::send_wrapper_ext::{ThreadBound, WrongThreadError};
 
 /// A trait for borrowing and taking data.
 pub trait StorageAccess<T> {
@@ -20,13 +20,15 @@ impl<T> StorageAccess<T> for T {
     }
 }
 
-impl<T> StorageAccess<T> for SendWrapper<T> {
+impl<T> StorageAccess<T> for ThreadBound<T> {
+    #[track_caller]
     fn as_borrowed(&self) -> &T {
-        self
+        self.try_get().unwrap_or_else(|e| panic!("{e}"))
     }
 
+    #[track_caller]
     fn into_taken(self) -> T {
-        self.take()
+        self.try_take().unwrap_or_else(|e| panic!("{e}"))
     }
 }
 
@@ -121,24 +123,78 @@ where
 
 /// A form of [`Storage`] that stores the type with a wrapper that makes it `Send + Sync`, but only
 /// allows it to be accessed from the thread on which it was created.
+///
+/// If the value is accessed from another thread, which can happen by accident during
+/// multithreaded server-side rendering, it is treated as if it had been disposed: the `try_`
+/// methods of the types that use this storage return `None`, and a warning explaining why is
+/// logged in debug mode. If it is dropped on another thread, it is leaked. Values that are `Send`
+/// can be moved to another thread explicitly, with
+/// [`StoredValue::transfer`](super::StoredValue::transfer) or [`ArenaItem::transfer`](super::ArenaItem::transfer).
 #[derive(Debug, Copy, Clone)]
 pub struct LocalStorage;
 
+impl LocalStorage {
+    /// Checks whether the value stored for `node` can be accessed from the current thread.
+    pub(crate) fn check_thread<T>(node: NodeId) -> Result<(), WrongThreadError>
+    where
+        T: 'static,
+    {
+        Arena::with(|arena| {
+            match arena
+                .get(node)
+                .and_then(|n| n.downcast_ref::<ThreadBound<T>>())
+            {
+                Some(inner) => inner.try_get().map(|_| ()),
+                None => Ok(()),
+            }
+        })
+    }
+
+    /// Moves the value stored for `node` to the current thread, so that it can be used there.
+    ///
+    /// Returns `false` if the value has been disposed.
+    pub(crate) fn transfer<T>(node: NodeId) -> bool
+    where
+        T: Send + 'static,
+    {
+        Arena::with_mut(|arena| {
+            arena
+                .get_mut(node)
+                .and_then(|n| n.downcast_mut::<ThreadBound<T>>())
+                .map(ThreadBound::transfer)
+                .is_some()
+        })
+    }
+}
+
+/// Warns that a local value was accessed from the wrong thread.
+fn wrong_thread<U>(e: WrongThreadError) -> Option<U> {
+    #[cfg(any(debug_assertions, leptos_debuginfo))]
+    crate::log_warning(format_args!("{e}"));
+    _ = e;
+    None
+}
+
 impl<T> Storage<T> for LocalStorage
 where
     T: 'static,
 {
-    type Wrapped = SendWrapper<T>;
+    type Wrapped = ThreadBound<T>;
 
     fn wrap(value: T) -> Self::Wrapped {
-        SendWrapper::new(value)
+        ThreadBound::new(value)
     }
 
     fn try_with<U>(node: NodeId, fun: impl FnOnce(&T) -> U) -> Option<U> {
         Arena::with(|arena| {
             let m = arena.get(node);
-            m.and_then(|n| n.downcast_ref::<SendWrapper<T>>())
-                .map(|inner| fun(inner))
+            match m
+                .and_then(|n| n.downcast_ref::<ThreadBound<T>>())?
+                .try_get()
+            {
+                Ok(inner) => Some(fun(inner)),
+                Err(e) => wrong_thread(e),
+            }
         })
     }
 
@@ -148,17 +204,23 @@ where
     ) -> Option<U> {
         Arena::with_mut(|arena| {
             let m = arena.get_mut(node);
-            m.and_then(|n| n.downcast_mut::<SendWrapper<T>>())
-                .map(|inner| fun(&mut *inner))
+            match m
+                .and_then(|n| n.downcast_mut::<ThreadBound<T>>())?
+                .try_get_mut()
+            {
+                Ok(inner) => Some(fun(inner)),
+                Err(e) => wrong_thread(e),
+            }
         })
     }
 
     fn try_set(node: NodeId, value: T) -> Option<T> {
         Arena::with_mut(|arena| {
             let m = arena.get_mut(node);
-            match m.and_then(|n| n.downcast_mut::<SendWrapper<T>>()) {
+            match m.and_then(|n| n.downcast_mut::<ThreadBound<T>>()) {
+                // a value that belongs to another thread is replaced, and leaked
                 Some(inner) => {
-                    *inner = SendWrapper::new(value);
+                    *inner = ThreadBound::new(value);
                     None
                 }
                 None => Some(value),
@@ -169,8 +231,11 @@ where
     fn take(node: NodeId) -> Option<T> {
         Arena::with_mut(|arena| {
             let m = arena.remove(node)?;
-            match m.downcast::<SendWrapper<T>>() {
-                Ok(inner) => Some(inner.take()),
+            match m.downcast::<ThreadBound<T>>() {
+                Ok(inner) => match inner.try_take() {
+                    Ok(inner) => Some(inner),
+                    Err(e) => wrong_thread(e),
+                },
                 Err(_) => None,
             }
         })
@@ -3,6 +3,7 @@ use super::{
     SyncStorage,
 };
 use crate::{
+    send_wrapper_ext::WrongThreadError,
     signal::guards::{Plain, ReadGuard, UntrackedWriteGuard},
     traits::{
         DefinedAt, Dispose, IntoInner, IsDisposed, ReadValue, WriteValue,
@@ -122,6 +123,45 @@ where
     pub fn new_local(value: T) -> Self {
         StoredValue::new_with_storage(value)
     }
+
+    /// Returns an error if the value belongs to a thread other than the current one, in which case
+    /// it cannot be accessed. A disposed value is not an error.
+    ///
+    /// Accessing a local value from another thread, which can happen by accident during
+    /// multithreaded server-side rendering, does not panic: the `try_` methods return `None`, as
+    /// if the value had been disposed. This tells the two cases apart.
+    pub fn check_thread(&self) -> Result<(), WrongThreadError> {
+        self.value.check_thread()
+    }
+
+    /// Moves the value to the current thread, so that it can be accessed from this thread from now
+    /// on, rather than from the one that created it. Returns `false` if it has been disposed.
+    ///
+    /// ```
+    /// # use reactive_graph::{owner::StoredValue, traits::*};
+    /// # let owner = reactive_graph::owner::Owner::new(); owner.set();
+    /// // created on the thread that rendered the page
+    /// let visits = StoredValue::new_local(1);
+    ///
+    /// std::thread::spawn(move || {
+    ///     # owner.with(|| {
+    ///     // used on the thread that serves the response
+    ///     assert!(visits.check_thread().is_err());
+    ///     assert_eq!(visits.try_get_value(), None);
+    ///
+    ///     visits.transfer();
+    ///     assert_eq!(visits.try_get_value(), Some(1));
+    ///     # });
+    /// })
+    /// .join()
+    /// .unwrap();
+    /// ```
+    pub fn transfer(&self) -> bool
+    where
+        T: Send + Sync,
+    {
+        self.value.transfer()
+    }
 }
 
 impl<T, S> ReadValue for StoredValue<T, S>
@@ -1,21 +1,155 @@
 //! Additional wrapper utilities for [`send_wrapper::SendWrapper`].
+//!
+//! [`ThreadBound`] is used instead of a `SendWrapper` for values that are stored with
+//! [`LocalStorage`](crate::owner::LocalStorage) and for the local values of [`SendOption`]. Unlike a
+//! `SendWrapper`, it does not panic when it is touched from another thread, which can happen by
+//! accident during multithreaded server-side rendering: reading it returns a
+//! [`WrongThreadError`], and dropping it leaks the value. A value that is `Send` can also be
+//! moved to another thread explicitly with [`ThreadBound::transfer`]; one that is not can only
+//! ever be used on the thread that created it.
 
-use send_wrapper::SendWrapper;
 use std::{
     fmt::{Debug, Formatter},
     hash,
+    mem::ManuallyDrop,
     ops::{Deref, DerefMut},
+    thread::{self, ThreadId},
 };
+use thiserror::Error;
+
+/// The error returned when a [`ThreadBound`] value is accessed from a thread other than the one
+/// it belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error(
+    "a local value that belongs to thread {owner:?} was accessed from thread \
+     {current:?}; local values can only be used on the thread that created \
+     them, unless they are moved to another thread with `transfer()`"
+)]
+pub struct WrongThreadError {
+    /// The thread the value belongs to.
+    pub owner: ThreadId,
+    /// The thread it was accessed from.
+    pub current: ThreadId,
+}
+
+/// A value that can be sent between threads, but only accessed from the thread it belongs to,
+/// which is the one that created it unless it has been [transferred](ThreadBound::transfer).
+///
+/// Accessing it from any other thread returns a [`WrongThreadError`]. If it is dropped on another
+/// thread, the value is leaked instead of being dropped, and a warning is logged in debug mode.
+///
+/// Only values that are `Send` can be transferred. The values that are `!Send`, like the JS
+/// objects held by a `LocalResource`, always belong to the thread that created them: on any
+/// other thread they can't be read, and they are leaked when they are dropped there. This keeps
+/// a request from crashing, but the value is lost, so a `!Send` value still has to be created on
+/// the thread that uses it.
+pub struct ThreadBound<T> {
+    value: ManuallyDrop<T>,
+    thread: ThreadId,
+}
+
+// SAFETY: the value can only be accessed, taken or dropped on the thread it belongs to, and it
+// only changes threads in `transfer`, which requires `T: Send` and exclusive access
+unsafe impl<T> Send for ThreadBound<T> {}
+unsafe impl<T> Sync for ThreadBound<T> {}
+
+impl<T> ThreadBound<T> {
+    /// Binds the value to the current thread.
+    pub fn new(value: T) -> Self {
+        Self {
+            value: ManuallyDrop::new(value),
+            thread: thread::current().id(),
+        }
+    }
+
+    /// Whether the value can be accessed from the current thread.
+    pub fn is_accessible(&self) -> bool {
+        self.thread == thread::current().id()
+    }
+
+    fn check(&self) -> Result<(), WrongThreadError> {
+        let current = thread::current().id();
+        if self.thread == current {
+            Ok(())
+        } else {
+            Err(WrongThreadError {
+                owner: self.thread,
+                current,
+            })
+        }
+    }
+
+    /// Borrows the value, if it belongs to the current thread.
+    pub fn try_get(&self) -> Result<&T, WrongThreadError> {
+        self.check()?;
+        Ok(&self.value)
+    }
+
+    /// Mutably borrows the value, if it belongs to the current thread.
+    pub fn try_get_mut(&mut self) -> Result<&mut T, WrongThreadError> {
+        self.check()?;
+        Ok(&mut self.value)
+    }
+
+    /// Takes the value, if it belongs to the current thread. Otherwise, it is leaked.
+    pub fn try_take(self) -> Result<T, WrongThreadError> {
+        self.check()?;
+        let mut this = ManuallyDrop::new(self);
+        // SAFETY: `this` is never used or dropped again
+        Ok(unsafe { ManuallyDrop::take(&mut this.value) })
+    }
+
+    /// Moves the value to the current thread, which it will belong to from now on.
+    ///
+    /// This is how a value created on another thread, like one created while rendering on the
+    /// server and then used on the thread that serves the response, can be used safely. It
+    /// requires `T: Send`, as moving a `!Send` value to another thread is never safe.
+    pub fn transfer(&mut self)
+    where
+        T: Send,
+    {
+        self.thread = thread::current().id();
+    }
+}
+
+impl<T> Drop for ThreadBound<T> {
+    fn drop(&mut self) {
+        match self.check() {
+            // SAFETY: the value is never used again
+            Ok(()) => unsafe { ManuallyDrop::drop(&mut self.value) },
+            Err(e) => {
+                #[cfg(any(debug_assertions, leptos_debuginfo))]
+                crate::log_warning(format_args!(
+                    "{e}. The value will be leaked instead of being dropped."
+                ));
+                _ = e;
+            }
+        }
+    }
+}
+
+impl<T: Debug> Debug for ThreadBound<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.try_get() {
+            Ok(value) => f.debug_tuple("ThreadBound").field(value).finish(),
+            Err(_) => f
+                .debug_struct("ThreadBound")
+                .field("thread", &self.thread)
+                .finish_non_exhaustive(),
+        }
+    }
+}
+
 /// An optional value that can always be sent between threads, even if its inner value
 /// in the `Some(_)` case would not be threadsafe.
 ///
 /// This struct can be derefenced to `Option<T>`.
 ///
-/// If it has been given a local (`!Send`) value, that value is wrapped in a [`SendWrapper`], which
-/// allows sending it between threads but will panic if it is accessed or updated from a  
-/// thread other than the one on which it was created.
+/// If it has been given a local (`!Send`) value, that value is wrapped in a [`ThreadBound`], which
+/// allows sending it between threads but can only be accessed from the thread on which it was
+/// created.
 ///
-/// If it is created with `None` for a local (`!Send`) type, no `SendWrapper` is created until a
+/// If it is created with `None` for a local (`!Send`) type, no `ThreadBound` is created until a
 /// value is provided via [`DerefMut`] or [`update`](SendOption::update).
 ///
 /// ### Use Case
@@ -24,21 +158,21 @@ use std::{
 /// `SendOption` can be created with its `None` variant and sent between threads without causing issues
 /// when it is dropped.
 ///
-/// ### Panics
-/// Dereferencing or dropping `SendOption` panics under the following conditions:
-/// 1) It is created via [`new_local`](SendOption::new_local) (signifying a `!Send` inner type),
-/// 2) It has `Some(_)` value, and
-/// 3) It has been sent to a thread other than the one on which it was created.
+/// ### Other threads
+/// If a local `SendOption` that has a `Some(_)` value is used on a thread other than the one on
+/// which it was created, it behaves as if it were `None`: it dereferences to `None`, and updating
+/// it replaces the value. The value is leaked rather than dropped on the wrong thread. A value
+/// that is `Send` can be moved to the current thread with [`transfer`](SendOption::transfer).
 pub struct SendOption<T> {
     inner: Inner<T>,
 }
 
 // SAFETY: `SendOption` can *only* be given a T in four ways
 // 1) via new(), which requires T: Send + Sync
-// 2) via new_local(), which wraps T in a SendWrapper if given Some(T)
-// 3) via deref_mut(), which creates a SendWrapper<Option<T>> as needed
-// 4) via update(), which either dereferences an existing SendWrapper
-//    or creates a new SendWrapper as needed
+// 2) via new_local(), which wraps T in a ThreadBound if given Some(T)
+// 3) via deref_mut(), which creates a ThreadBound<Option<T>> as needed
+// 4) via update(), which either dereferences an existing ThreadBound
+//    or creates a new ThreadBound as needed
 unsafe impl<T> Send for SendOption<T> {}
 unsafe impl<T> Sync for SendOption<T> {}
 
@@ -74,8 +208,9 @@ where
 enum Inner<T> {
     /// A threadsafe value.
     Threadsafe(Option<T>),
-    /// A non-threadsafe value. If accessed/dropped from a different thread in the Some() variant, it will panic.
-    Local(Option<SendWrapper<Option<T>>>),
+    /// A non-threadsafe value. If accessed from a different thread in the Some() variant, it is
+    /// treated as `None`.
+    Local(Option<ThreadBound<Option<T>>>),
 }
 
 impl<T> SendOption<T>
@@ -104,7 +239,7 @@ impl<T> SendOption<T> {
     pub fn new_local(value: Option<T>) -> Self {
         Self {
             inner: if let Some(value) = value {
-                Inner::Local(Some(SendWrapper::new(Some(value))))
+                Inner::Local(Some(ThreadBound::new(Some(value))))
             } else {
                 Inner::Local(None)
             },
@@ -113,37 +248,60 @@ impl<T> SendOption<T> {
 
     /// Update a value in place with a callback.
     ///
-    /// # Panics
-    /// If the value is [`Inner::Local`] and it is called from a different thread than the one the instance has been created with, it will panic.
+    /// If the value is [`Inner::Local`] and it is called from a different thread than the one the
+    /// instance has been created with, the callback is given `None`.
     pub fn update(&mut self, cb: impl FnOnce(&mut Option<T>)) {
         match &mut self.inner {
             Inner::Threadsafe(value) => cb(value),
-            Inner::Local(value) => match value {
-                Some(sw) => {
-                    cb(sw.deref_mut());
-                    if sw.is_none() {
-                        *value = None;
+            Inner::Local(value) => {
+                match value.as_mut().map(ThreadBound::try_get_mut) {
+                    Some(Ok(inner)) => {
+                        cb(inner);
+                        if inner.is_none() {
+                            *value = None;
+                        }
                     }
-                }
-                None => {
-                    let mut inner = None;
-                    cb(&mut inner);
-                    if let Some(inner) = inner {
-                        *value = Some(SendWrapper::new(Some(inner)));
+                    // a value that belongs to another thread is replaced
+                    Some(Err(_)) | None => {
+                        let mut inner = None;
+                        cb(&mut inner);
+                        *value =
+                            inner.map(|inner| ThreadBound::new(Some(inner)));
                     }
                 }
-            },
+            }
         }
     }
 
     /// Consume the value.
     ///
-    /// # Panics
-    /// Panics if the [`Inner::Local`] variant and it is called from a different thread than the one the instance has been created with.
+    /// If the value is [`Inner::Local`] and it is called from a different thread than the one the
+    /// instance has been created with, this returns `None`.
     pub fn take(self) -> Option<T> {
         match self.inner {
             Inner::Threadsafe(value) => value,
-            Inner::Local(value) => value.and_then(|value| value.take()),
+            Inner::Local(value) => {
+                value.and_then(|value| value.try_take().ok().flatten())
+            }
+        }
+    }
+
+    /// Whether the value can be accessed from the current thread, which is always the case unless
+    /// a local value was created on another thread.
+    pub fn is_accessible(&self) -> bool {
+        match &self.inner {
+            Inner::Threadsafe(_) | Inner::Local(None) => true,
+            Inner::Local(Some(value)) => value.is_accessible(),
+        }
+    }
+
+    /// Moves a local value to the current thread. See [`ThreadBound::transfer`].
+    pub fn transfer(&mut self)
+    where
+        T: Send,
+    {
+        if let Inner::Local(Some(value)) = &mut self.inner {
+            value.transfer();
         }
     }
 }
@@ -154,10 +312,20 @@ impl<T> Deref for SendOption<T> {
     fn deref(&self) -> &Self::Target {
         match &self.inner {
             Inner::Threadsafe(value) => value,
-            Inner::Local(value) => match value {
-                Some(value) => value.deref(),
-                None => &None,
-            },
+            Inner::Local(value) => {
+                match value.as_ref().map(ThreadBound::try_get) {
+                    Some(Ok(value)) => value,
+                    Some(Err(e)) => {
+                        #[cfg(any(debug_assertions, leptos_debuginfo))]
+                        crate::log_warning(format_args!(
+                            "{e}. It will be treated as `None`."
+                        ));
+                        _ = e;
+                        &None
+                    }
+                    None => &None,
+                }
+            }
         }
     }
 }
@@ -166,13 +334,16 @@ impl<T> DerefMut for SendOption<T> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         match &mut self.inner {
             Inner::Threadsafe(value) => value,
-            Inner::Local(value) => match value {
-                Some(value) => value.deref_mut(),
-                None => {
-                    *value = Some(SendWrapper::new(None));
-                    value.as_mut().unwrap().deref_mut()
+            Inner::Local(value) => {
+                // a value that belongs to another thread is replaced
+                if !value.as_ref().is_some_and(ThreadBound::is_accessible) {
+                    *value = Some(ThreadBound::new(None));
                 }
-            },
+                value
+                    .as_mut()
+                    .and_then(|value| value.try_get_mut().ok())
+                    .expect("the value belongs to the current thread")
+            }
         }
     }
 }
@@ -195,7 +366,12 @@ impl<T: Clone> Clone for SendOption<T> {
         Self {
             inner: match &self.inner {
                 Inner::Threadsafe(value) => Inner::Threadsafe(value.clone()),
-                Inner::Local(value) => Inner::Local(value.clone()),
+                Inner::Local(value) => Inner::Local(
+                    value
+                        .as_ref()
+                        .and_then(|value| value.try_get().ok())
+                        .map(|value| ThreadBound::new(value.clone())),
+                ),
             },
         }
     }
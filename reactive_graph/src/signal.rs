@@ -115,7 +115,10 @@ pub fn arc_signal<T>(value: T) -> (ArcReadSignal<T>, ArcWriteSignal<T>) {
 pub fn signal<T: Send + Sync + 'static>(
     value: T,
 ) -> (ReadSignal<T>, WriteSignal<T>) {
-    let (r, w) = arc_signal(value);
+    let signal = ArcRwSignal::new(value);
+    #[cfg(feature = "hot-state")]
+    crate::hot_state::created(std::panic::Location::caller(), &signal);
+    let (r, w) = signal.split();
     (r.into(), w.into())
 }
 
@@ -138,10 +138,13 @@ where
     )]
     #[track_caller]
     pub fn new_with_storage(value: T) -> Self {
+        let signal = ArcRwSignal::new(value);
+        #[cfg(feature = "hot-state")]
+        crate::hot_state::created(Location::caller(), &signal);
         Self {
             #[cfg(any(debug_assertions, leptos_debuginfo))]
             defined_at: Location::caller(),
-            inner: ArenaItem::new_with_storage(ArcRwSignal::new(value)),
+            inner: ArenaItem::new_with_storage(signal),
         }
     }
 }
@@ -55,6 +55,9 @@ impl<T: AsSubscriberSet + DefinedAt> ReactiveNode for T {
 
     fn mark_subscribers_check(&self) {
         if let Some(inner) = self.as_subscriber_set() {
+            let _cause = crate::trace::propagate(self.defined_at());
+            #[cfg(feature = "devtools")]
+            crate::devtools::notified(self.defined_at());
             let subs = inner.borrow().read().unwrap().clone();
             for sub in subs {
                 sub.mark_dirty();
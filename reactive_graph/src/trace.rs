@@ -0,0 +1,167 @@
+//! Spans for the reruns of effects and memos, with the signal whose change caused them, when the
+//! `tracing` feature is enabled.
+//!
+//! Each effect or memo remembers where it was created and the span that was current at the time,
+//! like that of the component that created it, and reruns in a child of that span. A signal that
+//! changes is recorded as the cause of the reruns that its change leads to.
+//!
+//! Without the `tracing` feature, this all compiles away.
+
+#[cfg(feature = "tracing")]
+use std::cell::Cell;
+use std::panic::Location;
+
+/// The signal whose change caused a rerun, in debug mode.
+pub(crate) type Cause = Option<&'static Location<'static>>;
+
+pub(crate) use crate::diagnostics::NodeKind as Kind;
+#[cfg(any(debug_assertions, leptos_debuginfo))]
+use crate::diagnostics::RunningNode;
+
+#[cfg(feature = "tracing")]
+thread_local! {
+    static CAUSE: Cell<Cause> = const { Cell::new(None) };
+}
+
+#[cfg(any(debug_assertions, leptos_debuginfo))]
+thread_local! {
+    static RUNNING: std::cell::Cell<Option<RunningNode>> =
+        const { std::cell::Cell::new(None) };
+}
+
+/// The effect or memo that is running on this thread, if any.
+#[cfg(any(debug_assertions, leptos_debuginfo))]
+pub(crate) fn running() -> Option<RunningNode> {
+    RUNNING.get()
+}
+
+/// Restores the node that was running before when dropped.
+#[cfg(any(debug_assertions, leptos_debuginfo))]
+struct RunningGuard(Option<RunningNode>);
+
+#[cfg(any(debug_assertions, leptos_debuginfo))]
+impl Drop for RunningGuard {
+    fn drop(&mut self) {
+        RUNNING.set(self.0);
+    }
+}
+
+/// The signal whose change is being propagated on this thread, if any.
+#[cfg(feature = "tracing")]
+pub(crate) fn current_cause() -> Cause {
+    CAUSE.get()
+}
+
+/// Records `source` as the cause of the changes propagated until the guard is dropped, unless an
+/// earlier change is already being propagated.
+#[inline(always)]
+pub(crate) fn propagate(source: Cause) -> PropagateGuard {
+    #[cfg(feature = "tracing")]
+    {
+        let previous = CAUSE.get();
+        if previous.is_none() {
+            CAUSE.set(source);
+        }
+        PropagateGuard { previous }
+    }
+    #[cfg(not(feature = "tracing"))]
+    {
+        _ = source;
+        PropagateGuard {}
+    }
+}
+
+/// Restores the previous cause when dropped.
+pub(crate) struct PropagateGuard {
+    #[cfg(feature = "tracing")]
+    previous: Cause,
+}
+
+impl Drop for PropagateGuard {
+    #[inline(always)]
+    fn drop(&mut self) {
+        #[cfg(feature = "tracing")]
+        CAUSE.set(self.previous);
+    }
+}
+
+/// Where an effect or memo was created.
+#[derive(Debug, Clone)]
+pub(crate) struct Origin {
+    #[cfg(any(feature = "tracing", debug_assertions, leptos_debuginfo))]
+    kind: Kind,
+    #[cfg(any(feature = "tracing", debug_assertions, leptos_debuginfo))]
+    defined_at: &'static Location<'static>,
+    #[cfg(feature = "tracing")]
+    parent: tracing::Span,
+    #[cfg(feature = "devtools")]
+    tracker: crate::devtools::Tracker,
+}
+
+impl Origin {
+    #[track_caller]
+    #[inline(always)]
+    pub(crate) fn new(kind: Kind) -> Self {
+        _ = kind;
+        Self {
+            #[cfg(any(
+                feature = "tracing",
+                debug_assertions,
+                leptos_debuginfo
+            ))]
+            kind,
+            #[cfg(any(
+                feature = "tracing",
+                debug_assertions,
+                leptos_debuginfo
+            ))]
+            defined_at: Location::caller(),
+            #[cfg(feature = "tracing")]
+            parent: tracing::Span::current(),
+            #[cfg(feature = "devtools")]
+            tracker: crate::devtools::Tracker::new(kind),
+        }
+    }
+
+    /// Runs `fun` in a span for this rerun.
+    #[inline(always)]
+    pub(crate) fn run<T>(&self, cause: Cause, fun: impl FnOnce() -> T) -> T {
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        let _running = RunningGuard(RUNNING.replace(Some(RunningNode {
+            kind: self.kind,
+            defined_at: self.defined_at,
+        })));
+
+        #[cfg(feature = "devtools")]
+        let fun = || self.tracker.time(fun);
+
+        #[cfg(feature = "tracing")]
+        {
+            macro_rules! span {
+                ($name:literal) => {
+                    tracing::trace_span!(
+                        parent: &self.parent,
+                        $name,
+                        defined_at = %self.defined_at,
+                        cause = tracing::field::Empty,
+                    )
+                };
+            }
+
+            let span = match self.kind {
+                Kind::Effect => span!("effect"),
+                Kind::RenderEffect => span!("render_effect"),
+                Kind::Memo => span!("memo"),
+            };
+            if let Some(cause) = cause {
+                span.record("cause", tracing::field::display(cause));
+            }
+            span.in_scope(fun)
+        }
+        #[cfg(not(feature = "tracing"))]
+        {
+            _ = cause;
+            fun()
+        }
+    }
+}
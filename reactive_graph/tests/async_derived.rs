@@ -137,3 +137,77 @@ async fn async_derived_with_initial() {
     signal2.set(1);
     assert_eq!(derived.await, 2);
 }
+
+#[tokio::test]
+async fn cancelling_tasks_drops_pending_futures() {
+    use reactive_graph::owner::{provide_context, TaskCancellation};
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    };
+
+    struct SetOnDrop(Arc<AtomicBool>);
+
+    impl Drop for SetOnDrop {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+    let cancellation = TaskCancellation::new();
+    provide_context(cancellation.clone());
+
+    let dropped = Arc::new(AtomicBool::new(false));
+    let _value = ArcAsyncDerived::new({
+        let dropped = Arc::clone(&dropped);
+        move || {
+            let on_drop = SetOnDrop(Arc::clone(&dropped));
+            async move {
+                pending::<()>().await;
+                drop(on_drop);
+                42
+            }
+        }
+    });
+
+    Executor::tick().await;
+    assert!(!dropped.load(Ordering::SeqCst));
+
+    cancellation.cancel();
+    Executor::tick().await;
+    assert!(cancellation.is_cancelled());
+    assert!(dropped.load(Ordering::SeqCst));
+}
+
+#[tokio::test]
+async fn finished_tasks_leave_the_cancellation_registry() {
+    use reactive_graph::{
+        owner::{provide_context, TaskCancellation},
+        spawn,
+    };
+
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+    let cancellation = TaskCancellation::new();
+    provide_context(cancellation.clone());
+
+    for _ in 0..1000 {
+        spawn(async {});
+    }
+    Executor::tick().await;
+    assert_eq!(cancellation.running_tasks(), 0);
+
+    let (tx, rx) = futures::channel::oneshot::channel::<()>();
+    spawn(async move {
+        _ = rx.await;
+    });
+    Executor::tick().await;
+    assert_eq!(cancellation.running_tasks(), 1);
+    drop(tx);
+    Executor::tick().await;
+    assert_eq!(cancellation.running_tasks(), 0);
+}
@@ -0,0 +1,98 @@
+use reactive_graph::owner::{
+    expect_context, provide_context, provide_context_keyed, use_context_keyed,
+    Owner,
+};
+use std::panic::{self, AssertUnwindSafe};
+
+#[derive(Debug, Clone)]
+struct Theme;
+
+#[derive(Debug, Clone)]
+struct Locale;
+
+#[test]
+fn missing_context_lists_available_and_unreachable_providers() {
+    let owner = Owner::new();
+    owner.set();
+    provide_context(Locale);
+
+    let sidebar = Owner::new();
+    sidebar.with(|| provide_context(Theme));
+    let main = Owner::new();
+
+    let message = panic::catch_unwind(AssertUnwindSafe(|| {
+        main.with(expect_context::<Theme>);
+    }))
+    .unwrap_err()
+    .downcast::<String>()
+    .unwrap();
+
+    assert!(message.contains("expected context of type \"context::Theme\""));
+    assert!(message.contains(
+        "A value of this type was provided at reactive_graph/tests/context.rs"
+    ));
+    assert!(message.contains("which is not an ancestor of this owner"));
+    assert!(message.contains("\n  context::Locale (provided at "));
+}
+
+#[test]
+fn keyed_values_of_the_same_type_coexist() {
+    let owner = Owner::new();
+    owner.set();
+    provide_context_keyed("sidebar", 1);
+    provide_context_keyed("main", 2);
+
+    Owner::new().with(|| {
+        provide_context_keyed("main", 3);
+        assert_eq!(use_context_keyed::<i32>("sidebar"), Some(1));
+        assert_eq!(use_context_keyed::<i32>("main"), Some(3));
+        assert_eq!(use_context_keyed::<i32>("footer"), None);
+        assert_eq!(use_context_keyed::<u32>("main"), None);
+    });
+    assert_eq!(use_context_keyed::<i32>("main"), Some(2));
+}
+
+// warnings are printed to stderr, which the test harness captures, so the
+// spawn runs again in a child process whose output can be read
+#[cfg(debug_assertions)]
+#[test]
+fn spawning_outside_a_cancellation_does_not_warn() {
+    use any_spawner::Executor;
+    use reactive_graph::{
+        diagnostics::context_warnings, owner::TaskCancellation, spawn,
+    };
+    use std::{env, process::Command};
+
+    const CHILD: &str = "REACTIVE_GRAPH_SPAWN_WARNING_CHILD";
+    const MARKER: &str = "-- spawned --";
+
+    if env::var_os(CHILD).is_some() {
+        _ = Executor::init_futures_executor();
+        context_warnings(true);
+        // tasks only look for a cancellation once one exists
+        _ = TaskCancellation::new();
+        let owner = Owner::new();
+        owner.set();
+
+        spawn(async {});
+        eprintln!("{MARKER}");
+        // shows that warnings do reach stderr
+        _ = reactive_graph::owner::use_context::<Theme>();
+        return;
+    }
+
+    let output = Command::new(env::current_exe().unwrap())
+        .args([
+            "spawning_outside_a_cancellation_does_not_warn",
+            "--exact",
+            "--nocapture",
+        ])
+        .env(CHILD, "1")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let (spawning, after) = stderr.split_once(MARKER).unwrap();
+    assert!(!spawning.contains("use_context()"), "{spawning}");
+    assert!(after.contains("use_context()"), "{after}");
+}
@@ -0,0 +1,81 @@
+#![cfg(all(feature = "effects", feature = "devtools"))]
+
+use any_spawner::Executor;
+use reactive_graph::{
+    computed::Memo,
+    devtools::{self, NodeKind},
+    effect::Effect,
+    flush_effects,
+    owner::Owner,
+    prelude::*,
+    signal::RwSignal,
+};
+
+// the statistics are global, so this is the only test in this binary
+#[test]
+fn counts_live_values_and_updates() {
+    _ = Executor::init_futures_executor();
+    let owner = Owner::new();
+    owner.set();
+    let checkpoint = devtools::checkpoint();
+    let before = *checkpoint.stats();
+
+    let count = RwSignal::new(0);
+    let doubled = {
+        let _component = devtools::enter_component("Counter");
+        let doubled = Memo::new(move |_| count.get() * 2);
+        Effect::new(move |_| {
+            doubled.get();
+        });
+        doubled
+    };
+    devtools::ignore(|| {
+        Effect::new(move |_| {
+            doubled.get();
+        })
+    });
+    flush_effects();
+
+    let stats = devtools::stats();
+    // the memo is a source too
+    assert_eq!(stats.sources, before.sources + 2);
+    assert_eq!(stats.memos, before.memos + 1);
+    assert_eq!(stats.effects, before.effects + 2);
+    // the memo on the signal, and both effects on the memo
+    assert_eq!(stats.subscriptions, before.subscriptions + 3);
+    assert!(stats.arena_items > before.arena_items);
+
+    assert_eq!(devtools::components()[0].1.renders, 1);
+
+    devtools::reset();
+    count.set(1);
+    flush_effects();
+    let stats = devtools::stats();
+    // the memo, and the effect that isn't ignored
+    assert_eq!(stats.updates, 2);
+    let components = devtools::components();
+    assert_eq!(components.len(), 1);
+    let (name, counter) = components[0];
+    assert_eq!(name, "Counter");
+    assert_eq!(counter.renders, 0);
+    assert_eq!(counter.updates, 2);
+
+    devtools::record_nodes(true);
+    devtools::reset();
+    count.set(2);
+    flush_effects();
+    let hot = devtools::hot_nodes(10);
+    for kind in [NodeKind::Signal, NodeKind::Memo, NodeKind::Effect] {
+        let node = hot
+            .iter()
+            .find(|node| node.kind == kind)
+            .unwrap_or_else(|| panic!("no {kind:?} in {hot:?}"));
+        assert_eq!(node.defined_at.file(), file!());
+        assert_eq!(node.activity(), 1);
+    }
+
+    owner.cleanup();
+    // lets the effects' tasks finish
+    Executor::poll_local();
+    assert!(checkpoint.delta().is_empty(), "{}", checkpoint.delta());
+}
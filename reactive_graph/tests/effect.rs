@@ -246,12 +246,147 @@ async fn paused_effect_pauses() {
             println!("resuming");
             owner.get_value().unwrap().resume();
 
+            // it was notified while paused, so it runs again when resumed
+            Executor::tick().await;
+            assert_eq!(runs.get_value(), 3);
+
             println!("setting to 3");
             a.set(3);
 
             Executor::tick().await;
             println!("checking value");
-            assert_eq!(runs.get_value(), 3);
+            assert_eq!(runs.get_value(), 4);
+        })
+        .await
+}
+
+#[cfg(feature = "effects")]
+#[tokio::test]
+async fn resumed_effect_rechecks_its_sources() {
+    use imports::*;
+    use reactive_graph::{computed::Memo, owner::StoredValue};
+
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+
+    task::LocalSet::new()
+        .run_until(async {
+            let a = RwSignal::new(0);
+            let is_even = Memo::new(move |_| a.get() % 2 == 0);
+            let panel = Owner::new();
+
+            // the values the effects have seen
+            let seen = StoredValue::new(Vec::new());
+            let parity = StoredValue::new(Vec::new());
+
+            panel.with(|| {
+                Effect::new(move || seen.write_value().push(a.get()));
+                Effect::new(move || parity.write_value().push(is_even.get()));
+            });
+
+            Executor::tick().await;
+            assert_eq!(seen.get_value(), vec![0]);
+            assert_eq!(parity.get_value(), vec![true]);
+
+            panel.pause();
+            a.set(1);
+            a.set(2);
+            Executor::tick().await;
+            assert_eq!(seen.get_value(), vec![0]);
+
+            // the effects run once with the latest values, and only if they have changed
+            panel.resume();
+            Executor::tick().await;
+            assert_eq!(seen.get_value(), vec![0, 2]);
+            assert_eq!(parity.get_value(), vec![true]);
+
+            // resuming again does not run anything
+            panel.pause();
+            panel.resume();
+            Executor::tick().await;
+            assert_eq!(seen.get_value(), vec![0, 2]);
+
+            a.set(3);
+            Executor::tick().await;
+            assert_eq!(seen.get_value(), vec![0, 2, 3]);
+            assert_eq!(parity.get_value(), vec![true, false]);
+        })
+        .await
+}
+
+#[cfg(feature = "effects")]
+#[tokio::test]
+async fn flush_effects_runs_pending_effects() {
+    use imports::*;
+    use reactive_graph::effect::flush_effects;
+
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+
+    task::LocalSet::new()
+        .run_until(async {
+            let a = RwSignal::new(0);
+            let doubled = RwSignal::new(0);
+            let log = Arc::new(RwLock::new(Vec::new()));
+
+            Effect::new(move || doubled.set(a.get() * 2));
+            Effect::new_sync({
+                let log = Arc::clone(&log);
+                move || log.write().unwrap().push(doubled.get())
+            });
+            let render_effect = RenderEffect::new({
+                let log = Arc::clone(&log);
+                move |_| log.write().unwrap().push(-a.get())
+            });
+
+            // nothing has awaited the executor, so only the render effect has run
+            assert_eq!(*log.read().unwrap(), [0]);
+            flush_effects();
+            assert_eq!(*log.read().unwrap(), [0, 0]);
+
+            a.set(1);
+            flush_effects();
+            // the second effect runs once the first one has changed `doubled`
+            assert_eq!(*log.read().unwrap(), [0, 0, -1, 2]);
+
+            // the tasks find nothing left to do
+            reactive_graph::tick().await;
+            Executor::tick().await;
+            assert_eq!(*log.read().unwrap(), [0, 0, -1, 2]);
+            drop(render_effect);
+        })
+        .await
+}
+
+#[cfg(feature = "effects")]
+#[tokio::test]
+async fn flush_effects_only_runs_effects_of_the_current_root() {
+    use imports::*;
+    use reactive_graph::effect::flush_effects;
+
+    _ = Executor::init_tokio();
+
+    task::LocalSet::new()
+        .run_until(async {
+            let log = Arc::new(RwLock::new(Vec::new()));
+            let effect = |owner: &Owner, name: &'static str| {
+                let log = Arc::clone(&log);
+                owner.with(|| {
+                    Effect::new_sync(move || log.write().unwrap().push(name))
+                })
+            };
+            let first = Owner::new();
+            let second = Owner::new();
+            let child = first.child();
+            let _first_effect = effect(&child, "first");
+            let _second_effect = effect(&second, "second");
+
+            first.with(flush_effects);
+            assert_eq!(*log.read().unwrap(), ["first"]);
+            second.with(flush_effects);
+            assert_eq!(*log.read().unwrap(), ["first", "second"]);
         })
         .await
 }
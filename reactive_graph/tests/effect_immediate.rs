@@ -168,11 +168,14 @@ fn paused_effect_pauses() {
     println!("resuming");
     owner.get_value().unwrap().resume();
 
+    // it was notified while paused, so it runs again when resumed
+    assert_eq!(runs.get_value(), 3);
+
     println!("setting to 3");
     a.set(3);
 
     println!("checking value");
-    assert_eq!(runs.get_value(), 3);
+    assert_eq!(runs.get_value(), 4);
 }
 
 #[cfg(feature = "effects")]
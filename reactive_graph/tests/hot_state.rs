@@ -0,0 +1,41 @@
+#![cfg(feature = "hot-state")]
+
+use reactive_graph::{
+    hot_state::on_signal_created,
+    owner::Owner,
+    signal::{signal, ArcRwSignal, RwSignal},
+    traits::{GetUntracked, Set},
+};
+use std::{any::Any, panic::Location, sync::Mutex};
+
+static CREATED: Mutex<Vec<(u32, bool)>> = Mutex::new(Vec::new());
+
+fn created(location: &'static Location<'static>, signal: &dyn Any) {
+    assert_eq!(location.file(), file!());
+    // the hook gets the signal itself, and can change its value
+    let signal = signal.downcast_ref::<ArcRwSignal<i32>>();
+    if let Some(signal) = signal {
+        signal.set(42);
+    }
+    CREATED
+        .lock()
+        .unwrap()
+        .push((location.line(), signal.is_some()));
+}
+
+#[test]
+fn hook_is_told_about_new_signals() {
+    let owner = Owner::new();
+    owner.set();
+    on_signal_created(created);
+
+    let line = line!() + 1;
+    let (count, _) = signal(0);
+    let name = RwSignal::new(String::new());
+    // `ArcRwSignal`s are not registered
+    let _ = ArcRwSignal::new(0);
+
+    assert_eq!(*CREATED.lock().unwrap(), [(line, true), (line + 1, false)]);
+    assert_eq!(count.get_untracked(), 42);
+    assert_eq!(name.get_untracked(), "");
+}
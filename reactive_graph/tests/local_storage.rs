@@ -0,0 +1,100 @@
+use reactive_graph::{
+    owner::{Owner, StoredValue},
+    send_wrapper_ext::{SendOption, ThreadBound},
+    signal::RwSignal,
+    traits::{GetUntracked, GetValue, Set},
+};
+use std::{
+    rc::Rc,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+};
+
+#[test]
+fn local_values_are_not_accessible_from_other_threads() {
+    let owner = Owner::new();
+    owner.set();
+
+    let signal = RwSignal::new_local(Rc::new(1));
+    let stored = StoredValue::new_local(Rc::new(2));
+    let option = SendOption::new_local(Some(Rc::new(3)));
+
+    let owner = owner.clone();
+    thread::spawn(move || {
+        owner.with(|| {
+            assert_eq!(signal.try_get_untracked(), None);
+            assert_eq!(stored.try_get_value(), None);
+            assert!(stored.check_thread().is_err());
+            assert!(!option.is_accessible());
+            assert_eq!(*option, None);
+
+            // writing from another thread does nothing
+            signal.set(Rc::new(4));
+            assert_eq!(signal.try_get_untracked(), None);
+
+            // the values are leaked rather than dropped on this thread
+            drop(option);
+        });
+        owner.cleanup();
+    })
+    .join()
+    .unwrap();
+}
+
+#[test]
+fn local_values_can_be_transferred() {
+    let owner = Owner::new();
+    owner.set();
+
+    let stored = StoredValue::new_local(vec![1, 2, 3]);
+    let mut option = SendOption::new_local(Some(String::from("ready")));
+
+    let owner = owner.clone();
+    let stored = thread::spawn(move || {
+        owner.with(|| {
+            assert!(stored.transfer());
+            assert_eq!(stored.try_get_value(), Some(vec![1, 2, 3]));
+
+            option.transfer();
+            assert_eq!(option.take().as_deref(), Some("ready"));
+        });
+        stored
+    })
+    .join()
+    .unwrap();
+
+    // it now belongs to the other thread
+    assert_eq!(stored.try_get_value(), None);
+}
+
+struct CountDrops(Arc<AtomicUsize>);
+
+impl Drop for CountDrops {
+    fn drop(&mut self) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn local_values_dropped_on_another_thread_are_leaked() {
+    let drops = Arc::new(AtomicUsize::new(0));
+    // `Rc` makes the value `!Send`, so it can't be transferred
+    let leaked = ThreadBound::new(Rc::new(CountDrops(Arc::clone(&drops))));
+    let dropped = ThreadBound::new(Rc::new(CountDrops(Arc::clone(&drops))));
+
+    let dropped = thread::spawn(move || {
+        assert!(leaked.try_get().is_err());
+        drop(leaked);
+        dropped
+    })
+    .join()
+    .expect("dropping a value on another thread should not panic");
+    assert_eq!(drops.load(Ordering::SeqCst), 0);
+
+    // on its own thread, it is dropped as usual
+    drop(dropped);
+    assert_eq!(drops.load(Ordering::SeqCst), 1);
+}
@@ -164,6 +164,42 @@ fn memo_runs_only_when_inputs_change() {
     assert_eq!(*call_count.read().unwrap(), 2);
 }
 
+#[test]
+fn memo_receives_previous_value() {
+    let owner = Owner::new();
+    owner.set();
+
+    let a = RwSignal::new(1);
+    let prevs = Arc::new(RwLock::new(Vec::new()));
+
+    // a running total, computed from the previous total
+    let total = Memo::new({
+        let prevs = Arc::clone(&prevs);
+        move |prev: Option<&i32>| {
+            prevs.write().unwrap().push(prev.copied());
+            prev.copied().unwrap_or(0) + a.get()
+        }
+    });
+
+    assert_eq!(total.get_untracked(), 1);
+    a.set(2);
+    assert_eq!(total.get_untracked(), 3);
+    a.set(3);
+    assert_eq!(total.get_untracked(), 6);
+    assert_eq!(*prevs.read().unwrap(), [None, Some(1), Some(3)]);
+
+    // an owning memo appends to its previous value without cloning it
+    let history = ArcMemo::new_owning(move |prev: Option<Vec<i32>>| {
+        let mut history = prev.unwrap_or_default();
+        history.push(a.get());
+        (history, true)
+    });
+    assert_eq!(history.get_untracked(), [3]);
+    a.set(4);
+    a.set(5);
+    assert_eq!(history.get_untracked(), [3, 5]);
+}
+
 #[test]
 fn diamond_problem() {
     let owner = Owner::new();
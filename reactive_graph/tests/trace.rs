@@ -0,0 +1,104 @@
+#![cfg(all(feature = "effects", feature = "tracing"))]
+
+use any_spawner::Executor;
+use reactive_graph::{
+    computed::Memo, effect::Effect, flush_effects, owner::Owner, prelude::*,
+    signal::RwSignal,
+};
+use std::{
+    fmt::Debug,
+    panic::Location,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+use tracing::{
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+    Event, Metadata, Subscriber,
+};
+
+type Fields = Vec<(String, String)>;
+
+/// The name and fields of each span that was created.
+#[derive(Default)]
+struct Spans {
+    next_id: AtomicU64,
+    spans: Mutex<Vec<(&'static str, Fields)>>,
+}
+
+struct Recorder<'a>(&'a mut Fields);
+
+impl Visit for Recorder<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0
+            .push((field.name().to_string(), format!("{value:?}")));
+    }
+}
+
+impl Subscriber for Spans {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut fields = Vec::new();
+        span.record(&mut Recorder(&mut fields));
+        let mut spans = self.spans.lock().unwrap();
+        spans.push((span.metadata().name(), fields));
+        Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+    }
+
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        let mut spans = self.spans.lock().unwrap();
+        let (_, fields) = &mut spans[span.into_u64() as usize - 1];
+        values.record(&mut Recorder(fields));
+    }
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, _: &Event<'_>) {}
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+#[test]
+fn reruns_are_traced_with_their_cause() {
+    _ = Executor::init_futures_executor();
+    let owner = Owner::new();
+    owner.set();
+
+    let spans = Arc::new(Spans::default());
+    tracing::subscriber::with_default(Arc::clone(&spans), || {
+        let defined_at = Location::caller();
+        let count = RwSignal::new(0);
+        let doubled = Memo::new(move |_| count.get() * 2);
+        Effect::new(move |_| {
+            doubled.get();
+        });
+        flush_effects();
+        let before = spans.spans.lock().unwrap().len();
+
+        count.set(1);
+        flush_effects();
+        let spans = &spans.spans.lock().unwrap()[before..];
+        let cause = format!("{}:{}", defined_at.file(), defined_at.line() + 1);
+        for kind in ["effect", "memo"] {
+            let (_, fields) = spans
+                .iter()
+                .find(|(name, _)| *name == kind)
+                .unwrap_or_else(|| panic!("no {kind} span"));
+            let field = |name| {
+                fields
+                    .iter()
+                    .find(|(n, _)| n == name)
+                    .map(|(_, value)| value.as_str())
+            };
+            assert!(field("cause").unwrap().starts_with(&cause));
+            assert!(field("defined_at").unwrap().contains("trace.rs"));
+        }
+    });
+}
@@ -17,7 +17,7 @@ use crate::{
 use either_of::EitherOf3;
 use leptos::{children, prelude::*};
 use reactive_graph::{
-    owner::{provide_context, use_context, Owner},
+    owner::{provide_context, use_context, with_context, Owner},
     signal::ArcRwSignal,
     traits::{GetUntracked, ReadUntracked, Set},
     wrappers::write::SignalSetter,
@@ -53,7 +53,8 @@ where
 
 #[component(transparent)]
 pub fn Router<Chil>(
-    /// The base URL for the router. Defaults to `""`.
+    /// The base URL for the router. Defaults to the
+    /// [`base_path`](leptos::base_path::base_path) of the app, or `""`.
     #[prop(optional, into)]
     base: Option<Cow<'static, str>>,
     /// A signal that will be set while the navigation process is underway.
@@ -71,23 +72,30 @@ pub fn Router<Chil>(
 where
     Chil: IntoView,
 {
+    let base = base.or_else(|| {
+        leptos::base_path::base_path().map(|base| Cow::Owned(base.to_string()))
+    });
+
     #[cfg(feature = "ssr")]
-    let (location_provider, current_url, redirect_hook) = {
+    let (location_provider, current_url, state, redirect_hook) = {
         let req = use_context::<RequestUrl>().expect("no RequestUrl provided");
         let parsed = req.parse().expect("could not parse RequestUrl");
         let current_url = ArcRwSignal::new(parsed);
+        let state = ArcRwSignal::new(State::new(None));
 
-        (None, current_url, Box::new(move |_: &str| {}))
+        (None, current_url, state, Box::new(move |_: &str| {}))
     };
 
     #[cfg(not(feature = "ssr"))]
-    let (location_provider, current_url, redirect_hook) = {
+    let (location_provider, current_url, state, redirect_hook) = {
         let owner = Owner::current();
         let location =
             BrowserUrl::new().expect("could not access browser navigation"); // TODO options here
         location.init(base.clone());
         provide_context(location.clone());
         let current_url = location.as_url().clone();
+        // the state of the entry being visited, which changes on back/forward navigations
+        let state = location.state.clone();
 
         let redirect_hook = Box::new(move |loc: &str| {
             if let Some(owner) = &owner {
@@ -95,10 +103,9 @@ where
             }
         });
 
-        (Some(location), current_url, redirect_hook)
+        (Some(location), current_url, state, redirect_hook)
     };
     // provide router context
-    let state = ArcRwSignal::new(State::new(None));
     let location = Location::new(current_url.read_only(), state.read_only());
 
     // set server function redirect hook
@@ -235,7 +242,7 @@ where
     FallbackFn: FnOnce() -> Fallback + Clone + Send + 'static,
     Fallback: IntoView + 'static,
 {
-    let location = use_context::<BrowserUrl>();
+    let location = with_context(BrowserUrl::clone);
     let RouterContext {
         current_url,
         base,
@@ -288,7 +295,7 @@ where
     FallbackFn: FnOnce() -> Fallback + Clone + Send + 'static,
     Fallback: IntoView + 'static,
 {
-    let location = use_context::<BrowserUrl>();
+    let location = with_context(BrowserUrl::clone);
     let RouterContext {
         current_url,
         base,
@@ -579,7 +586,7 @@ pub fn Redirect<P>(
     let path = path.to_string();
 
     // redirect on the server
-    if let Some(redirect_fn) = use_context::<ServerRedirectFunction>() {
+    if let Some(redirect_fn) = with_context(ServerRedirectFunction::clone) {
         (redirect_fn.f)(&path);
     }
     // redirect on the client
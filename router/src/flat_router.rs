@@ -13,7 +13,7 @@ use futures::FutureExt;
 use leptos::attr::{any_attribute::AnyAttribute, Attribute};
 use reactive_graph::{
     computed::{ArcMemo, ScopedFuture},
-    owner::{provide_context, Owner},
+    owner::{shadow_context, Owner},
     signal::ArcRwSignal,
     traits::{GetUntracked, ReadUntracked, Set},
     transition::AsyncTransition,
@@ -145,9 +145,9 @@ where
                         let url = url.clone();
                         let matched = matched.clone();
                         async move {
-                            provide_context(params_memo);
-                            provide_context(url);
-                            provide_context(Matched(ArcMemo::from(matched)));
+                            shadow_context(params_memo);
+                            shadow_context(url);
+                            shadow_context(Matched(ArcMemo::from(matched)));
                             OwnedView::new(view.choose().await)
                         }
                     })
@@ -266,9 +266,9 @@ where
             // render fallback
             None => {
                 owner.with(|| {
-                    provide_context(url);
-                    provide_context(params_memo);
-                    provide_context(Matched(ArcMemo::from(new_matched)));
+                    shadow_context(url);
+                    shadow_context(params_memo);
+                    shadow_context(Matched(ArcMemo::from(new_matched)));
                     fallback().into_any().rebuild(&mut state.borrow_mut().view)
                 });
                 if let Some(location) = location {
@@ -295,11 +295,9 @@ where
                     ScopedFuture::new({
                         let state = Rc::clone(state);
                         async move {
-                            provide_context(url);
-                            provide_context(params_memo);
-                            provide_context(Matched(ArcMemo::from(
-                                new_matched,
-                            )));
+                            shadow_context(url);
+                            shadow_context(params_memo);
+                            shadow_context(Matched(ArcMemo::from(new_matched)));
                             let view = OwnedView::new(
                                 if let Some(set_is_routing) = set_is_routing {
                                     set_is_routing.set(true);
@@ -514,9 +512,9 @@ where
                 let view = owner
                     .with(|| {
                         ScopedFuture::new(async move {
-                            provide_context(url);
-                            provide_context(params_memo);
-                            provide_context(Matched(ArcMemo::from(matched)));
+                            shadow_context(url);
+                            shadow_context(params_memo);
+                            shadow_context(Matched(ArcMemo::from(matched)));
                             view.choose().await
                         })
                     })
@@ -705,9 +703,9 @@ where
                         let url = url.clone();
                         let matched = matched.clone();
                         async move {
-                            provide_context(params_memo);
-                            provide_context(url);
-                            provide_context(Matched(ArcMemo::from(matched)));
+                            shadow_context(params_memo);
+                            shadow_context(url);
+                            shadow_context(Matched(ArcMemo::from(matched)));
                             OwnedView::new(view.choose().await)
                         }
                     })
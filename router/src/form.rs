@@ -4,17 +4,62 @@ use crate::{
     location::{BrowserUrl, LocationProvider},
     NavigateOptions,
 };
-use leptos::{ev, html::form, logging::*, prelude::*, task::spawn_local};
+use leptos::{
+    ev, form::url_params_from_form_data, html::form, logging::*, prelude::*,
+    task::spawn_local,
+};
 use std::{error::Error, sync::Arc};
-use wasm_bindgen::{JsCast, UnwrapThrowExt};
-use web_sys::{FormData, RequestRedirect, Response};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue, UnwrapThrowExt};
+use web_sys::{
+    FormData, ProgressEvent, RequestRedirect, Response, XmlHttpRequest,
+};
 
 type OnFormData = Arc<dyn Fn(&FormData)>;
 type OnResponse = Arc<dyn Fn(&Response)>;
 type OnError = Arc<dyn Fn(&gloo_net::Error)>;
+type OnUploadProgress = Arc<dyn Fn(&ProgressEvent)>;
 
 /// An HTML [`form`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/form) progressively
 /// enhanced to use client-side routing.
+///
+/// ## File Uploads
+/// To upload files with `<input type="file">`, set `enctype="multipart/form-data"` and
+/// `method="post"`, so that the browser uploads the files when it submits the form before WASM
+/// has loaded. After it has loaded, the whole form, including its files, is posted as
+/// `multipart/form-data`, so the same `action` (for example, the URL of a server function that
+/// uses the `MultipartFormData` encoding) handles both. Without `multipart/form-data`, each file
+/// is sent as its name, as the browser does.
+///
+/// To show the progress of an upload, pass an `on_upload_progress` callback. The form is then
+/// posted with [`XMLHttpRequest`](https://developer.mozilla.org/en-US/docs/Web/API/XMLHttpRequest),
+/// as `fetch` can't report how much of a request has been sent.
+///
+/// ```rust
+/// # use leptos::prelude::*;
+/// # use leptos_router::components::Form;
+/// # use std::sync::Arc;
+/// #[component]
+/// fn Upload() -> impl IntoView {
+///     let (progress, set_progress) = signal(0.0);
+///
+///     view! {
+///         <Form
+///             action="/api/upload"
+///             method="post"
+///             enctype="multipart/form-data".to_string()
+///             on_upload_progress=Arc::new(move |ev: &web_sys::ProgressEvent| {
+///                 if ev.length_computable() {
+///                     set_progress.set(ev.loaded() / ev.total());
+///                 }
+///             })
+///         >
+///             <input type="file" name="file"/>
+///             <input type="submit"/>
+///         </Form>
+///         <progress value=progress></progress>
+///     }
+/// }
+/// ```
 #[component]
 pub fn Form<A>(
     /// [`method`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/form#attr-method)
@@ -46,6 +91,10 @@ pub fn Form<A>(
     /// A callback will be called if the attempt to submit the form results in an error.
     #[prop(optional)]
     on_error: Option<OnError>,
+    /// A callback that will be called as a `multipart/form-data` form is uploaded, with the
+    /// [`ProgressEvent`](web_sys::ProgressEvent) that reports how much has been sent.
+    #[prop(optional)]
+    on_upload_progress: Option<OnUploadProgress>,
     /// Sets whether the page should be scrolled to the top when the form is submitted.
     #[prop(optional)]
     noscroll: bool,
@@ -84,6 +133,13 @@ where
             .await
     }
 
+    /// Returns the response, and the URL it was redirected to, if any.
+    fn fetched(resp: gloo_net::http::Response) -> (Response, Option<String>) {
+        let resp = Response::from(resp);
+        let redirected_to = resp.redirected().then(|| resp.url());
+        (resp, redirected_to)
+    }
+
     fn inner(
         has_router: bool,
         method: Option<&'static str>,
@@ -94,6 +150,7 @@ where
         on_form_data: Option<OnFormData>,
         on_response: Option<OnResponse>,
         on_error: Option<OnError>,
+        on_upload_progress: Option<OnUploadProgress>,
         children: Children,
         noscroll: bool,
         replace: bool,
@@ -120,96 +177,36 @@ where
                 if let Some(on_form_data) = on_form_data.clone() {
                     on_form_data(&form_data);
                 }
-                let params =
-                    web_sys::UrlSearchParams::new_with_str_sequence_sequence(
-                        &form_data,
-                    )
-                    .unwrap_throw();
-                // multipart POST (setting Context-Type breaks the request)
-                if method == "post" && enctype == "multipart/form-data" {
+                let params = url_params_from_form_data(&form_data);
+                // POST
+                if method == "post" {
                     ev.prevent_default();
                     ev.stop_propagation();
 
                     let on_response = on_response.clone();
                     let on_error = on_error.clone();
+                    let on_upload_progress = on_upload_progress.clone();
                     spawn_local(async move {
-                        let res = post_form_data(&action, form_data).await;
-                        match res {
-                            Err(e) => {
-                                error!("<Form/> error while POSTing: {e:#?}");
-                                if let Some(on_error) = on_error {
-                                    on_error(&e);
-                                }
-                                if let Some(error) = error {
-                                    error.try_set(Some(Box::new(e)));
-                                }
-                            }
-                            Ok(resp) => {
-                                let resp = web_sys::Response::from(resp);
-                                if let Some(version) = action_version {
-                                    version.update(|n| *n += 1);
-                                }
-                                if let Some(error) = error {
-                                    error.try_set(None);
-                                }
-                                if let Some(on_response) = on_response.clone() {
-                                    on_response(&resp);
-                                }
-                                // Check all the logical 3xx responses that might
-                                // get returned from a server function
-                                if resp.redirected() {
-                                    let resp_url = &resp.url();
-                                    match BrowserUrl::parse(resp_url.as_str()) {
-                                        Ok(url) => {
-                                            if url.origin()
-                                                != current_window_origin()
-                                                || navigate.is_none()
-                                            {
-                                                _ = window()
-                                                    .location()
-                                                    .set_href(
-                                                        resp_url.as_str(),
-                                                    );
-                                            } else {
-                                                #[allow(
-                                                    clippy::unnecessary_unwrap
-                                                )]
-                                                let navigate =
-                                                    navigate.unwrap();
-                                                navigate(
-                                                    &format!(
-                                                        "{}{}{}",
-                                                        url.path(),
-                                                        if url
-                                                            .search()
-                                                            .is_empty()
-                                                        {
-                                                            ""
-                                                        } else {
-                                                            "?"
-                                                        },
-                                                        url.search(),
-                                                    ),
-                                                    navigate_options,
-                                                )
-                                            }
-                                        }
-                                        Err(e) => warn!("{:?}", e),
-                                    }
+                        // multipart POST (setting Context-Type breaks the request)
+                        let res = if enctype == "multipart/form-data" {
+                            match on_upload_progress {
+                                Some(on_upload_progress) => {
+                                    post_form_data_with_progress(
+                                        &action,
+                                        form_data,
+                                        on_upload_progress,
+                                    )
+                                    .await
                                 }
+                                None => post_form_data(&action, form_data)
+                                    .await
+                                    .map(fetched),
                             }
-                        }
-                    });
-                }
-                // POST
-                else if method == "post" {
-                    ev.prevent_default();
-                    ev.stop_propagation();
-
-                    let on_response = on_response.clone();
-                    let on_error = on_error.clone();
-                    spawn_local(async move {
-                        let res = post_params(&action, &enctype, params).await;
+                        } else {
+                            post_params(&action, &enctype, params)
+                                .await
+                                .map(fetched)
+                        };
                         match res {
                             Err(e) => {
                                 error!("<Form/> error while POSTing: {e:#?}");
@@ -220,8 +217,7 @@ where
                                     error.try_set(Some(Box::new(e)));
                                 }
                             }
-                            Ok(resp) => {
-                                let resp = web_sys::Response::from(resp);
+                            Ok((resp, redirected_to)) => {
                                 if let Some(version) = action_version {
                                     version.update(|n| *n += 1);
                                 }
@@ -233,8 +229,7 @@ where
                                 }
                                 // Check all the logical 3xx responses that might
                                 // get returned from a server function
-                                if resp.redirected() {
-                                    let resp_url = &resp.url();
+                                if let Some(resp_url) = redirected_to {
                                     match BrowserUrl::parse(resp_url.as_str()) {
                                         Ok(url) => {
                                             if url.origin()
@@ -323,12 +318,85 @@ where
         on_form_data,
         on_response,
         on_error,
+        on_upload_progress,
         children,
         noscroll,
         replace,
     )
 }
 
+/// Posts multipart form data with an `XMLHttpRequest`, which (unlike `fetch`) reports the progress
+/// of the upload. Returns the response, and the URL it was redirected to, if any.
+async fn post_form_data_with_progress(
+    action: &str,
+    form_data: FormData,
+    on_upload_progress: OnUploadProgress,
+) -> Result<(Response, Option<String>), gloo_net::Error> {
+    fn js_error(e: JsValue) -> gloo_net::Error {
+        gloo_net::Error::GlooError(format!("{e:?}"))
+    }
+
+    let xhr = XmlHttpRequest::new().map_err(js_error)?;
+    xhr.open("POST", action).map_err(js_error)?;
+    xhr.set_request_header("Accept", "application/json")
+        .map_err(js_error)?;
+
+    let on_progress =
+        Closure::<dyn Fn(ProgressEvent)>::new(move |ev: ProgressEvent| {
+            on_upload_progress(&ev)
+        });
+    xhr.upload()
+        .map_err(js_error)?
+        .set_onprogress(Some(on_progress.as_ref().unchecked_ref()));
+    let (tx, rx) = futures::channel::oneshot::channel();
+    let on_load_end = Closure::once(move |_: ProgressEvent| {
+        _ = tx.send(());
+    });
+    xhr.set_onloadend(Some(on_load_end.as_ref().unchecked_ref()));
+    xhr.send_with_opt_form_data(Some(&form_data))
+        .map_err(js_error)?;
+    _ = rx.await;
+    drop((on_progress, on_load_end));
+
+    let status = xhr.status().map_err(js_error)?;
+    if status == 0 {
+        return Err(gloo_net::Error::GlooError(
+            "the upload failed before the server responded".to_string(),
+        ));
+    }
+    let headers = web_sys::Headers::new().map_err(js_error)?;
+    for line in xhr.get_all_response_headers().map_err(js_error)?.lines() {
+        if let Some((name, value)) = line.split_once(':') {
+            headers
+                .append(name.trim(), value.trim())
+                .map_err(js_error)?;
+        }
+    }
+    let init = web_sys::ResponseInit::new();
+    init.set_status(status);
+    init.set_headers(&headers);
+    // these statuses can't have a body
+    let body = if matches!(status, 101 | 204 | 205 | 304) {
+        None
+    } else {
+        xhr.response_text().map_err(js_error)?
+    };
+    let resp = Response::new_with_opt_str_and_init(body.as_deref(), &init)
+        .map_err(js_error)?;
+
+    // the request follows redirects, so it was redirected if it ended up somewhere else
+    let requested = web_sys::Url::new_with_base(
+        action,
+        &window().location().href().map_err(js_error)?,
+    )
+    .map_err(js_error)?
+    .href();
+    let resp_url = xhr.response_url();
+    let redirected_to =
+        (!resp_url.is_empty() && resp_url != requested).then_some(resp_url);
+    Ok((resp, redirected_to))
+}
+
 fn current_window_origin() -> String {
     let location = window().location();
     let protocol = location.protocol().unwrap_or_default();
@@ -1,20 +1,33 @@
 use crate::{
     components::RouterContext,
-    location::{Location, Url},
+    location::{Location, State, Url},
     navigate::NavigateOptions,
     params::{Params, ParamsError, ParamsMap},
 };
-use leptos::{leptos_dom::helpers::request_animation_frame, oco::Oco};
+use leptos::{
+    leptos_dom::helpers::{
+        request_animation_frame, set_timeout_with_handle, TimeoutHandle,
+    },
+    oco::Oco,
+};
 use reactive_graph::{
     computed::{ArcMemo, Memo},
-    owner::{expect_context, use_context},
-    signal::{ArcRwSignal, ReadSignal},
-    traits::{Get, GetUntracked, ReadUntracked, With, WriteValue},
+    owner::{
+        expect_context, use_context, with_context, LocalStorage, StoredValue,
+    },
+    signal::{ArcRwSignal, ReadSignal, RwSignal},
+    traits::{
+        DefinedAt, Get, GetUntracked, IsDisposed, ReadUntracked, Set, Track,
+        UpdateValue, With, WithValue, WriteValue,
+    },
     wrappers::write::SignalSetter,
 };
+use serde::de::DeserializeOwned;
 use std::{
+    fmt::Debug,
     str::FromStr,
     sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
 };
 
 /// See [`query_signal`].
@@ -145,16 +158,240 @@ where
     (get, set)
 }
 
+/// Options that configure a [`QuerySignal`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuerySignalOptions {
+    /// Whether a write replaces the current entry in the history stack, instead of adding a new
+    /// one that the “back” button returns from. Defaults to `true`.
+    pub replace: bool,
+    /// Whether the page scrolls to the top after a write. Defaults to `false`.
+    pub scroll: bool,
+    /// How long to wait after a write before updating the URL, so that writes in quick
+    /// succession, like typing into a search box, update it once. The signal itself changes
+    /// immediately. Defaults to no delay.
+    pub debounce: Duration,
+}
+
+impl Default for QuerySignalOptions {
+    fn default() -> Self {
+        Self {
+            replace: true,
+            scroll: false,
+            debounce: Duration::ZERO,
+        }
+    }
+}
+
+/// A signal bound to a URL query parameter, which can be read and written like an
+/// [`RwSignal`](reactive_graph::signal::RwSignal).
+///
+/// Reading it parses the parameter from the current URL, which is the URL of the request while
+/// rendering on the server, and returns `None` if it is missing or cannot be parsed. Writing it
+/// updates the parameter, or removes it when set to `None`, and navigates to the new URL.
+///
+/// ```rust
+/// use leptos::prelude::*;
+/// use leptos_router::hooks::QuerySignal;
+///
+/// #[component]
+/// pub fn Pagination() -> impl IntoView {
+///     let page = QuerySignal::<u32>::new("page");
+///
+///     view! {
+///         <button on:click=move |_| page.set(Some(page.get().unwrap_or(1) + 1))>
+///             "Next page"
+///         </button>
+///     }
+/// }
+/// ```
+pub struct QuerySignal<T>
+where
+    T: Send + Sync + 'static,
+{
+    value: Memo<Option<T>>,
+    // a value that has been written, but is not in the URL yet
+    pending: RwSignal<Option<Option<T>>>,
+    inner: StoredValue<QueryBinding, LocalStorage>,
+}
+
+struct QueryBinding {
+    key: Oco<'static, str>,
+    options: QuerySignalOptions,
+    router: RouterContext,
+    timeout: Option<TimeoutHandle>,
+}
+
+impl<T> Clone for QuerySignal<T>
+where
+    T: Send + Sync + 'static,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for QuerySignal<T> where T: Send + Sync + 'static {}
+
+impl<T> Debug for QuerySignal<T>
+where
+    T: Send + Sync + 'static,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("QuerySignal")
+            .field("value", &self.value)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> QuerySignal<T>
+where
+    T: FromStr + ToString + Clone + PartialEq + Send + Sync + 'static,
+{
+    /// Binds a signal to the query parameter `key`, with the default [`QuerySignalOptions`].
+    #[track_caller]
+    pub fn new(key: impl Into<Oco<'static, str>>) -> Self {
+        Self::new_with_options(key, QuerySignalOptions::default())
+    }
+
+    /// Binds a signal to the query parameter `key`.
+    #[track_caller]
+    pub fn new_with_options(
+        key: impl Into<Oco<'static, str>>,
+        options: QuerySignalOptions,
+    ) -> Self {
+        let mut key: Oco<'static, str> = key.into();
+        let router = expect_context::<RouterContext>();
+        let query_map = use_query_map();
+        let pending = RwSignal::new(None::<Option<T>>);
+        let value = Memo::new({
+            let key = key.clone_inplace();
+            move |_| {
+                pending.get().unwrap_or_else(|| {
+                    query_map.with(|map| {
+                        map.get_str(&key).and_then(|value| value.parse().ok())
+                    })
+                })
+            }
+        });
+        Self {
+            value,
+            pending,
+            inner: StoredValue::new_local(QueryBinding {
+                key,
+                options,
+                router,
+                timeout: None,
+            }),
+        }
+    }
+
+    /// Moves the written value into the URL.
+    fn flush(&self) {
+        let Some(Some(value)) = self.pending.try_get_untracked() else {
+            return;
+        };
+        self.inner.with_value(|binding| {
+            let router = &binding.router;
+            let url = router.current_url.read_untracked().to_full_path();
+            router.query_mutations.write_value().push((
+                binding.key.clone(),
+                value.as_ref().map(ToString::to_string),
+            ));
+            router.navigate(
+                &url,
+                NavigateOptions {
+                    resolve: false,
+                    replace: binding.options.replace,
+                    scroll: binding.options.scroll,
+                    ..Default::default()
+                },
+            );
+        });
+        self.pending.set(None);
+    }
+}
+
+impl<T> DefinedAt for QuerySignal<T>
+where
+    T: Send + Sync + 'static,
+{
+    fn defined_at(&self) -> Option<&'static std::panic::Location<'static>> {
+        self.value.defined_at()
+    }
+}
+
+impl<T> Track for QuerySignal<T>
+where
+    T: Send + Sync + PartialEq + 'static,
+{
+    fn track(&self) {
+        self.value.track();
+    }
+}
+
+impl<T> ReadUntracked for QuerySignal<T>
+where
+    T: Send + Sync + PartialEq + 'static,
+{
+    type Value = <Memo<Option<T>> as ReadUntracked>::Value;
+
+    fn try_read_untracked(&self) -> Option<Self::Value> {
+        self.value.try_read_untracked()
+    }
+}
+
+impl<T> IsDisposed for QuerySignal<T>
+where
+    T: Send + Sync + 'static,
+{
+    fn is_disposed(&self) -> bool {
+        self.pending.is_disposed()
+    }
+}
+
+impl<T> Set for QuerySignal<T>
+where
+    T: FromStr + ToString + Clone + PartialEq + Send + Sync + 'static,
+{
+    type Value = Option<T>;
+
+    fn set(&self, value: Self::Value) {
+        self.try_set(value);
+    }
+
+    fn try_set(&self, value: Self::Value) -> Option<Self::Value> {
+        if self.inner.is_disposed() {
+            return Some(value);
+        }
+        self.pending.set(Some(value));
+        // the URL of the request cannot be changed while rendering on the server
+        if cfg!(not(feature = "ssr")) {
+            let this = *self;
+            self.inner.update_value(|binding| {
+                if let Some(timeout) = binding.timeout.take() {
+                    timeout.clear();
+                }
+                binding.timeout = set_timeout_with_handle(
+                    move || this.flush(),
+                    binding.options.debounce,
+                )
+                .ok();
+            });
+        }
+        None
+    }
+}
+
 #[track_caller]
 pub(crate) fn has_router() -> bool {
-    use_context::<RouterContext>().is_some()
+    with_context(RouterContext::clone).is_some()
 }
 
 /*
 /// Returns the current [`RouterContext`], containing information about the router's state.
 #[track_caller]
 pub(crate) fn use_router() -> RouterContext {
-    if let Some(router) = use_context::<RouterContext>() {
+    if let Some(router) = with_context(RouterContext::clone) {
         router
     } else {
         leptos::leptos_dom::debug_warn!(
@@ -174,6 +411,41 @@ pub fn use_location() -> Location {
     location
 }
 
+/// Returns the [`state`](State) of the current history entry, as a value that was pushed with
+/// [`State::serialize`], or `None` if there is no such value or it is not a `T`.
+///
+/// ```rust
+/// # use leptos::prelude::*;
+/// # use leptos_router::{hooks::{use_location_state, use_navigate}, location::State, NavigateOptions};
+/// # use serde::{Deserialize, Serialize};
+/// #[derive(Clone, PartialEq, Serialize, Deserialize)]
+/// struct Highlight {
+///     todo: usize,
+/// }
+///
+/// # fn example() {
+/// let navigate = use_navigate();
+/// navigate(
+///     "/todos",
+///     NavigateOptions {
+///         state: State::serialize(&Highlight { todo: 3 }).unwrap(),
+///         ..Default::default()
+///     },
+/// );
+///
+/// // in the component rendered at `/todos`, even after going back and forward again
+/// let highlight = use_location_state::<Highlight>();
+/// # }
+/// ```
+#[track_caller]
+pub fn use_location_state<T>() -> Memo<Option<T>>
+where
+    T: DeserializeOwned + PartialEq + Send + Sync + 'static,
+{
+    let state = use_location().state;
+    Memo::new(move |_| state.with(State::deserialize))
+}
+
 pub(crate) type RawParamsMap = ArcMemo<ParamsMap>;
 
 #[track_caller]
@@ -202,7 +474,7 @@ where
 
 #[track_caller]
 fn use_url_raw() -> ArcRwSignal<Url> {
-    use_context().unwrap_or_else(|| {
+    with_context(ArcRwSignal::<Url>::clone).unwrap_or_else(|| {
         let RouterContext { current_url, .. } = use_context().expect(
             "Tried to access reactive URL outside a <Router> component.",
         );
@@ -244,7 +516,7 @@ pub(crate) fn use_resolved_path(
     let router = use_context::<RouterContext>()
         .expect("called use_resolved_path outside a <Router>");
     // TODO make this work with flat routes too?
-    let matched = use_context::<Matched>().map(|n| n.0);
+    let matched = with_context(|n: &Matched| n.0.clone());
     ArcMemo::new(move |_| {
         let path = path();
         if path.starts_with('/') {
@@ -287,3 +559,60 @@ pub fn use_matched() -> Memo<String> {
         .0
         .into()
 }
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::*;
+    use crate::location::RequestUrl;
+    use reactive_graph::owner::{provide_context, Owner};
+
+    /// Provides the router for a request to `url`, like `<Router/>` does on the server.
+    fn route_request(url: &str) -> Owner {
+        let owner = Owner::new();
+        owner.set();
+        let current_url =
+            ArcRwSignal::new(RequestUrl::new(url).parse().unwrap());
+        let state = ArcRwSignal::new(State::new(None));
+        provide_context(RouterContext {
+            base: None,
+            location: Location::new(current_url.read_only(), state.read_only()),
+            current_url,
+            state,
+            set_is_routing: None,
+            query_mutations: Default::default(),
+            location_provider: None,
+        });
+        owner
+    }
+
+    #[test]
+    fn query_signals_parse_the_parameter_of_the_request() {
+        let _owner = route_request("/todos?page=3&sort=name");
+
+        assert_eq!(QuerySignal::<u32>::new("page").get_untracked(), Some(3));
+        assert_eq!(
+            QuerySignal::<String>::new("sort")
+                .get_untracked()
+                .as_deref(),
+            Some("name")
+        );
+        // missing, or not a number
+        assert_eq!(QuerySignal::<u32>::new("filter").get_untracked(), None);
+        assert_eq!(QuerySignal::<u32>::new("sort").get_untracked(), None);
+    }
+
+    #[test]
+    fn query_signals_keep_written_values_without_changing_the_request() {
+        let _owner = route_request("/todos?page=3");
+        let page = QuerySignal::<u32>::new("page");
+
+        page.set(Some(4));
+        assert_eq!(page.get_untracked(), Some(4));
+        page.set(None);
+        assert_eq!(page.get_untracked(), None);
+        assert_eq!(
+            use_query_map().get_untracked().get("page").as_deref(),
+            Some("3")
+        );
+    }
+}
@@ -1,4 +1,6 @@
-use super::{handle_anchor_click, LocationChange, LocationProvider, Url};
+use super::{
+    handle_anchor_click, LocationChange, LocationProvider, State, Url,
+};
 use crate::{hooks::use_navigate, params::ParamsMap};
 use core::fmt;
 use futures::channel::oneshot;
@@ -25,6 +27,7 @@ pub struct BrowserUrl {
     pub(crate) pending_navigation: Arc<Mutex<Option<oneshot::Sender<()>>>>,
     pub(crate) path_stack: ArcStoredValue<Vec<Url>>,
     pub(crate) is_back: ArcRwSignal<bool>,
+    pub(crate) state: ArcRwSignal<State>,
 }
 
 impl fmt::Debug for BrowserUrl {
@@ -34,6 +37,22 @@ impl fmt::Debug for BrowserUrl {
 }
 
 impl BrowserUrl {
+    /// The state of the current entry of the history stack.
+    fn current_state() -> State {
+        let state = window().history().and_then(|history| history.state());
+        State::new(
+            state
+                .ok()
+                .filter(|state| !state.is_undefined() && !state.is_null()),
+        )
+    }
+
+    fn set_state(&self, state: State) {
+        if self.state.get_untracked() != state {
+            self.state.set(state);
+        }
+    }
+
     fn scroll_to_el(loc_scroll: bool) {
         if let Ok(hash) = window().location().hash() {
             if !hash.is_empty() {
@@ -69,6 +88,7 @@ impl LocationProvider for BrowserUrl {
             pending_navigation: Default::default(),
             path_stack,
             is_back: Default::default(),
+            state: ArcRwSignal::new(Self::current_state()),
         })
     }
 
@@ -183,6 +203,7 @@ impl LocationProvider for BrowserUrl {
             let url = self.url.clone();
             let path_stack = self.path_stack.clone();
             let is_back = self.is_back.clone();
+            let this = self.clone();
             move || match Self::current() {
                 Ok(new_url) => {
                     let stack = path_stack.read_value();
@@ -192,6 +213,7 @@ impl LocationProvider for BrowserUrl {
 
                     is_back.set(is_navigating_back);
 
+                    this.set_state(Self::current_state());
                     url.set(new_url);
                 }
                 Err(e) => {
@@ -220,6 +242,7 @@ impl LocationProvider for BrowserUrl {
 
     fn complete_navigation(&self, loc: &LocationChange) {
         let history = window().history().unwrap();
+        self.set_state(loc.state.clone());
 
         if loc.replace {
             history
@@ -11,6 +11,7 @@ use reactive_graph::{
     traits::With,
 };
 use send_wrapper::SendWrapper;
+use serde::{de::DeserializeOwned, Serialize};
 use std::{borrow::Cow, future::Future};
 use tachys::dom::window;
 use wasm_bindgen::{JsCast, JsValue};
@@ -242,17 +243,55 @@ pub trait LocationProvider: Clone + 'static {
     fn is_back(&self) -> ReadSignal<bool>;
 }
 
+/// The [`state`](https://developer.mozilla.org/en-US/docs/Web/API/History/state) of an entry in the
+/// history stack.
+///
+/// This can be any JavaScript value, or a Rust value that is stored as JSON with
+/// [`State::serialize`], and read back with [`State::deserialize`] or
+/// [`use_location_state`](crate::hooks::use_location_state).
 #[derive(Debug, Clone, Default)]
-pub struct State(Option<SendWrapper<JsValue>>);
+pub struct State(Option<StateValue>);
+
+#[derive(Debug, Clone)]
+enum StateValue {
+    Js(SendWrapper<JsValue>),
+    Json(String),
+}
 
 impl State {
     pub fn new(state: Option<JsValue>) -> Self {
-        Self(state.map(SendWrapper::new))
+        Self(state.map(|state| StateValue::Js(SendWrapper::new(state))))
+    }
+
+    /// Stores `value` as JSON, so that it can be pushed onto the history stack and read back
+    /// after the user navigates back or forward to it.
+    pub fn serialize<T>(value: &T) -> Result<Self, serde_json::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        serde_json::to_string(value)
+            .map(|json| Self(Some(StateValue::Json(json))))
+    }
+
+    /// Reads a value stored with [`State::serialize`], or returns `None` if there is no state or
+    /// it is not a `T`.
+    pub fn deserialize<T>(&self) -> Option<T>
+    where
+        T: DeserializeOwned,
+    {
+        match self.0.as_ref()? {
+            StateValue::Json(json) => serde_json::from_str(json).ok(),
+            // state read back from the history stack is a string
+            StateValue::Js(value) => {
+                serde_json::from_str(&value.as_string()?).ok()
+            }
+        }
     }
 
     pub fn to_js_value(&self) -> JsValue {
         match &self.0 {
-            Some(v) => v.clone().take(),
+            Some(StateValue::Js(v)) => v.clone().take(),
+            Some(StateValue::Json(json)) => JsValue::from_str(json),
             None => JsValue::UNDEFINED,
         }
     }
@@ -260,8 +299,14 @@ impl State {
 
 impl PartialEq for State {
     fn eq(&self, other: &Self) -> bool {
-        self.0.as_ref().map(|n| n.as_ref())
-            == other.0.as_ref().map(|n| n.as_ref())
+        match (&self.0, &other.0) {
+            (None, None) => true,
+            (Some(StateValue::Js(a)), Some(StateValue::Js(b))) => {
+                a.as_ref() == b.as_ref()
+            }
+            (Some(StateValue::Json(a)), Some(StateValue::Json(b))) => a == b,
+            _ => false,
+        }
     }
 }
 
@@ -378,3 +423,20 @@ where
         Ok(())
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::State;
+
+    #[test]
+    fn serialized_state_can_be_read_back() {
+        let state = State::serialize(&(1, "two")).unwrap();
+        assert_eq!(
+            state.deserialize::<(i32, String)>(),
+            Some((1, "two".into()))
+        );
+        assert_eq!(state.deserialize::<bool>(), None);
+        assert_eq!(State::default().deserialize::<bool>(), None);
+        assert_eq!(state, State::serialize(&(1, "two")).unwrap());
+    }
+}
@@ -15,7 +15,7 @@ use leptos::{attr::any_attribute::AnyAttribute, component, oco::Oco};
 use or_poisoned::OrPoisoned;
 use reactive_graph::{
     computed::{ArcMemo, ScopedFuture},
-    owner::{provide_context, use_context, Owner},
+    owner::{shadow_context, use_context, Owner},
     signal::{ArcRwSignal, ArcTrigger},
     traits::{Get, GetUntracked, Notify, ReadUntracked, Set, Track},
     transition::AsyncTransition,
@@ -502,7 +502,7 @@ impl Debug for RouteContext {
 
 impl RouteContext {
     fn provide_contexts(&self) {
-        provide_context(self.clone());
+        shadow_context(self.clone());
     }
 }
 
@@ -641,9 +641,9 @@ where
                 let matched = Matched(matched_including_parents);
                 let view_fn = Arc::clone(&outlet.view_fn);
                 async move {
-                    provide_context(params_including_parents);
-                    provide_context(url);
-                    provide_context(matched.clone());
+                    shadow_context(params_including_parents);
+                    shadow_context(url);
+                    shadow_context(matched.clone());
                     view.preload().await;
                     *view_fn.lock().or_poisoned() = Box::new(move || {
                         let view = view.clone();
@@ -794,9 +794,9 @@ where
                             let matched = Matched(matched_including_parents);
                             let view_fn = Arc::clone(&current.view_fn);
                             async move {
-                                provide_context(params_including_parents);
-                                provide_context(url);
-                                provide_context(matched);
+                                shadow_context(params_including_parents);
+                                shadow_context(url);
+                                shadow_context(matched);
                                 view.preload().await;
                                 *view_fn.lock().or_poisoned() =
                                     Box::new(move || {
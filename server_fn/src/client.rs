@@ -121,8 +121,9 @@ pub mod browser {
             >,
         > + Send {
             SendWrapper::new(async move {
+                let url = crate::prefix::mounted_path(url);
                 let websocket =
-                    gloo_net::websocket::futures::WebSocket::open(url)
+                    gloo_net::websocket::futures::WebSocket::open(&url)
                         .map_err(|err| {
                             web_sys::console::error_1(&err.to_string().into());
                             Error::from_server_fn_error(
@@ -265,7 +266,10 @@ pub mod reqwest {
             {
                 websocket_server_url = format!("wss://{postfix}");
             }
-            let url = format!("{websocket_server_url}{path}");
+            let url = format!(
+                "{websocket_server_url}{}",
+                crate::prefix::mounted_path(path)
+            );
             let (ws_stream, _) =
                 tokio_tungstenite::connect_async(url).await.map_err(|e| {
                     Error::from_server_fn_error(ServerFnErrorErr::Request(
@@ -42,6 +42,20 @@ impl MultipartData {
         }
     }
 
+    /// Returns another handle to the same `FormData` on the client side, which is how server
+    /// functions that take multipart data are dispatched by actions.
+    ///
+    /// On the server side, this always returns `None`, as the data is read from the request and
+    /// can only be read once.
+    pub fn client_copy(&self) -> Option<Self> {
+        match self {
+            MultipartData::Client(data) => {
+                Some(MultipartData::Client(data.clone()))
+            }
+            MultipartData::Server(_) => None,
+        }
+    }
+
     /// Extracts the inner form data on the client side.
     ///
     /// On the server side, this always returns `None`. On the client side, always returns `Some(_)`.
@@ -94,3 +108,17 @@ where
         Ok(MultipartData::Server(data).into())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::MultipartData;
+    use bytes::Bytes;
+
+    #[test]
+    fn data_read_from_a_request_has_no_client_copy() {
+        let body = futures::stream::empty::<Result<Bytes, std::io::Error>>();
+        let data =
+            MultipartData::Server(multer::Multipart::new(body, "boundary"));
+        assert!(data.client_copy().is_none());
+    }
+}
@@ -7,6 +7,9 @@ use crate::{
 use http::Method;
 use serde::{de::DeserializeOwned, Serialize};
 
+mod form;
+pub use form::from_form_str;
+
 /// Pass arguments as a URL-encoded query string of a `GET` request.
 pub struct GetUrl;
 
@@ -58,11 +61,9 @@ where
 {
     async fn from_req(req: Request) -> Result<Self, E> {
         let string_data = req.as_query().unwrap_or_default();
-        let args = serde_qs::Config::new(5, false)
-            .deserialize_str::<Self>(string_data)
-            .map_err(|e| {
-                ServerFnErrorErr::Args(e.to_string()).into_app_error()
-            })?;
+        let args = from_form_str::<Self>(string_data).map_err(|e| {
+            ServerFnErrorErr::Args(e.to_string()).into_app_error()
+        })?;
         Ok(args)
     }
 }
@@ -97,11 +98,9 @@ where
 {
     async fn from_req(req: Request) -> Result<Self, E> {
         let string_data = req.try_into_string().await?;
-        let args = serde_qs::Config::new(5, false)
-            .deserialize_str::<Self>(&string_data)
-            .map_err(|e| {
-                ServerFnErrorErr::Args(e.to_string()).into_app_error()
-            })?;
+        let args = from_form_str::<Self>(&string_data).map_err(|e| {
+            ServerFnErrorErr::Args(e.to_string()).into_app_error()
+        })?;
         Ok(args)
     }
 }
@@ -136,11 +135,9 @@ where
 {
     async fn from_req(req: Request) -> Result<Self, E> {
         let string_data = req.as_query().unwrap_or_default();
-        let args = serde_qs::Config::new(5, false)
-            .deserialize_str::<Self>(string_data)
-            .map_err(|e| {
-                ServerFnErrorErr::Args(e.to_string()).into_app_error()
-            })?;
+        let args = from_form_str::<Self>(string_data).map_err(|e| {
+            ServerFnErrorErr::Args(e.to_string()).into_app_error()
+        })?;
         Ok(args)
     }
 }
@@ -175,11 +172,9 @@ where
 {
     async fn from_req(req: Request) -> Result<Self, E> {
         let string_data = req.as_query().unwrap_or_default();
-        let args = serde_qs::Config::new(5, false)
-            .deserialize_str::<Self>(string_data)
-            .map_err(|e| {
-                ServerFnErrorErr::Args(e.to_string()).into_app_error()
-            })?;
+        let args = from_form_str::<Self>(string_data).map_err(|e| {
+            ServerFnErrorErr::Args(e.to_string()).into_app_error()
+        })?;
         Ok(args)
     }
 }
@@ -214,11 +209,9 @@ where
 {
     async fn from_req(req: Request) -> Result<Self, E> {
         let string_data = req.as_query().unwrap_or_default();
-        let args = serde_qs::Config::new(5, false)
-            .deserialize_str::<Self>(string_data)
-            .map_err(|e| {
-                ServerFnErrorErr::Args(e.to_string()).into_app_error()
-            })?;
+        let args = from_form_str::<Self>(string_data).map_err(|e| {
+            ServerFnErrorErr::Args(e.to_string()).into_app_error()
+        })?;
         Ok(args)
     }
 }
@@ -0,0 +1,691 @@
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, Error as _, IntoDeserializer,
+    Visitor,
+};
+use std::{collections::HashMap, mem, vec};
+
+// the deepest nesting of brackets that is accepted in a field name
+const MAX_DEPTH: usize = 16;
+
+/// Deserializes a URL-encoded form or query string, as submitted by an HTML `<form>`.
+///
+/// The input is deserialized with [`serde_qs`], as it is for every URL-encoded server function
+/// argument. Only if `serde_qs` rejects it is it read again the way browsers submit forms, so
+/// that a form which `serde_qs` can read always gives the same result.
+///
+/// When it is read again, field names use the bracketed notation that HTML forms
+/// conventionally use for structured data:
+/// - `user[name]=...` is the `name` field of the `user` struct (or map);
+/// - `tags[]=a&tags[]=b` and `tags[0]=a&tags[1]=b` are the items of the `tags` sequence;
+/// - `items[][name]=...&items[][qty]=...` adds a struct to the `items` sequence, starting a new
+///   one whenever a field is repeated.
+///
+/// Beyond that, it follows the way browsers submit forms without JS:
+/// - a name that is repeated without brackets, such as a `<select multiple>` or a group of
+///   checkboxes, deserializes as a sequence, and a single value deserializes as a sequence of
+///   one item (when a scalar is expected, the last value is used);
+/// - an empty value deserializes as `None` for an `Option`, as it does with `serde_qs`;
+/// - `on` (the value of a checked checkbox without a `value` attribute) deserializes as `true`.
+///
+/// If neither succeeds, the error from `serde_qs` is returned.
+///
+/// Browsers leave unchecked checkboxes and empty multiple selects out of the form entirely, so
+/// `bool` and `Vec` fields that they fill in should be marked with `#[serde(default)]`.
+pub fn from_form_str<T: DeserializeOwned>(
+    input: &str,
+) -> Result<T, serde_qs::Error> {
+    serde_qs::Config::new(5, false)
+        .deserialize_str(input)
+        .or_else(|error| from_submitted_form(input).map_err(|_| error))
+}
+
+fn from_submitted_form<T: DeserializeOwned>(
+    input: &str,
+) -> Result<T, serde_qs::Error> {
+    let mut root = Node::Map(Fields::default());
+    for (key, value) in url::form_urlencoded::parse(input.as_bytes()) {
+        let path = parse_key(&key);
+        if path.len() > MAX_DEPTH {
+            return Err(serde_qs::Error::custom(format!(
+                "the field name `{key}` is nested too deeply"
+            )));
+        }
+        root.insert(&path, value.into_owned())?;
+    }
+    T::deserialize(NodeDeserializer(root))
+}
+
+// splits `a[b][]` into `["a", "b", ""]`
+fn parse_key(key: &str) -> Vec<&str> {
+    let Some(start) = key.find('[').filter(|&start| start > 0) else {
+        return vec![key];
+    };
+    let mut path = vec![&key[..start]];
+    let mut rest = &key[start..];
+    while let Some(inner) = rest.strip_prefix('[') {
+        let Some(end) = inner.find(']') else {
+            return vec![key];
+        };
+        path.push(&inner[..end]);
+        rest = &inner[end + 1..];
+    }
+    if rest.is_empty() {
+        path
+    } else {
+        vec![key]
+    }
+}
+
+#[derive(Debug)]
+enum Node {
+    // every value that was given for the same name
+    Values(Vec<String>),
+    Map(Fields),
+    Seq(Vec<Node>),
+}
+
+// the fields of a map, in the order in which they were first given
+#[derive(Debug, Default)]
+struct Fields {
+    entries: Vec<(String, Node)>,
+    index: HashMap<String, usize>,
+}
+
+impl Fields {
+    fn contains(&self, key: &str) -> bool {
+        self.index.contains_key(key)
+    }
+
+    // the field with the given name, which is added if there is none yet
+    fn entry(&mut self, key: &str, rest: &[&str]) -> &mut Node {
+        let index = match self.index.get(key) {
+            Some(&index) => index,
+            None => {
+                self.entries.push((key.to_owned(), Node::empty(rest)));
+                self.index.insert(key.to_owned(), self.entries.len() - 1);
+                self.entries.len() - 1
+            }
+        };
+        &mut self.entries[index].1
+    }
+}
+
+impl Node {
+    fn empty(path: &[&str]) -> Node {
+        match path.first() {
+            None => Node::Values(Vec::new()),
+            Some(&"") => Node::Seq(Vec::new()),
+            Some(_) => Node::Map(Fields::default()),
+        }
+    }
+
+    fn insert(
+        &mut self,
+        path: &[&str],
+        value: String,
+    ) -> Result<(), serde_qs::Error> {
+        let Some((&key, rest)) = path.split_first() else {
+            return match self {
+                Node::Values(values) => {
+                    values.push(value);
+                    Ok(())
+                }
+                Node::Seq(items) => {
+                    items.push(Node::Values(vec![value]));
+                    Ok(())
+                }
+                Node::Map(_) => Err(conflict()),
+            };
+        };
+
+        if key.is_empty() {
+            // `tags=a&tags[]=b` is the same as `tags[]=a&tags[]=b`
+            if let Node::Values(values) = self {
+                let items = mem::take(values)
+                    .into_iter()
+                    .map(|value| Node::Values(vec![value]))
+                    .collect();
+                *self = Node::Seq(items);
+            }
+            let Node::Seq(items) = self else {
+                return Err(conflict());
+            };
+            // `items[][name]` fills in the last item, unless it already has a `name`
+            let needs_new = match (items.last(), rest.first()) {
+                (Some(Node::Map(fields)), Some(&field))
+                    if !field.is_empty() =>
+                {
+                    fields.contains(field)
+                }
+                _ => true,
+            };
+            if needs_new {
+                items.push(Node::empty(rest));
+            }
+            items.last_mut().unwrap().insert(rest, value)
+        } else {
+            let Node::Map(fields) = self else {
+                return Err(conflict());
+            };
+            fields.entry(key, rest).insert(rest, value)
+        }
+    }
+
+    // the value of a scalar: the last one, if a name was repeated
+    fn into_value(self) -> Result<String, serde_qs::Error> {
+        match self {
+            Node::Values(mut values) => values
+                .pop()
+                .ok_or_else(|| serde_qs::Error::custom("expected a value")),
+            Node::Map(_) => Err(serde_qs::Error::custom(
+                "expected a value, found fields in brackets",
+            )),
+            Node::Seq(_) => {
+                Err(serde_qs::Error::custom("expected a value, found a list"))
+            }
+        }
+    }
+
+    fn into_items(self) -> Result<Vec<Node>, serde_qs::Error> {
+        match self {
+            Node::Values(values) => Ok(values
+                .into_iter()
+                .map(|value| Node::Values(vec![value]))
+                .collect()),
+            Node::Seq(items) => Ok(items),
+            // `items[1]=b&items[0]=a`
+            Node::Map(fields) => {
+                let mut items = fields
+                    .entries
+                    .into_iter()
+                    .map(|(key, node)| {
+                        key.parse::<usize>().map(|index| (index, node)).map_err(
+                            |_| {
+                                serde_qs::Error::custom(format!(
+                                    "expected a list, found the field `{key}`"
+                                ))
+                            },
+                        )
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                items.sort_by_key(|(index, _)| *index);
+                Ok(items.into_iter().map(|(_, node)| node).collect())
+            }
+        }
+    }
+}
+
+fn conflict() -> serde_qs::Error {
+    serde_qs::Error::custom(
+        "a field is given both as a single value and with brackets",
+    )
+}
+
+struct NodeDeserializer(Node);
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(
+                self,
+                visitor: V,
+            ) -> Result<V::Value, Self::Error> {
+                let value = self.0.into_value()?;
+                let parsed = value.trim().parse().map_err(|_| {
+                    serde_qs::Error::custom(format!(
+                        "invalid value `{value}`, expected {}",
+                        stringify!($visit).trim_start_matches("visit_")
+                    ))
+                })?;
+                visitor.$visit(parsed)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for NodeDeserializer {
+    type Error = serde_qs::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match self.0 {
+            Node::Values(mut values) if values.len() == 1 => {
+                visitor.visit_string(values.pop().unwrap())
+            }
+            Node::Map(fields) => visitor.visit_map(MapAccess {
+                fields: fields.entries.into_iter(),
+                value: None,
+            }),
+            node => {
+                visitor.visit_seq(SeqAccess(node.into_items()?.into_iter()))
+            }
+        }
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match self.0.into_value()?.as_str() {
+            "true" | "on" => visitor.visit_bool(true),
+            "false" | "off" | "" => visitor.visit_bool(false),
+            other => Err(serde_qs::Error::custom(format!(
+                "invalid value `{other}`, expected a boolean"
+            ))),
+        }
+    }
+
+    deserialize_parsed! {
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char,
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_string(self.0.into_value()?)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_string(self.0.into_value()?)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_byte_buf(self.0.into_value()?.into_bytes())
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_byte_buf(self.0.into_value()?.into_bytes())
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match &self.0 {
+            // an input that was left empty
+            Node::Values(values)
+                if values.last().map_or(true, String::is_empty) =>
+            {
+                visitor.visit_none()
+            }
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_seq(SeqAccess(self.0.into_items()?.into_iter()))
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        let fields = match self.0 {
+            Node::Map(fields) => fields.entries,
+            // an empty form, or an empty value
+            Node::Values(values) if values.iter().all(String::is_empty) => {
+                Vec::new()
+            }
+            _ => {
+                return Err(serde_qs::Error::custom(
+                    "expected fields in brackets",
+                ))
+            }
+        };
+        visitor.visit_map(MapAccess {
+            fields: fields.into_iter(),
+            value: None,
+        })
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match self.0 {
+            // `size[Large]=...`
+            Node::Map(mut fields) if fields.entries.len() == 1 => {
+                let (variant, value) = fields.entries.pop().unwrap();
+                visitor.visit_enum(EnumAccess {
+                    variant,
+                    value: Some(value),
+                })
+            }
+            // `size=Large`
+            node => visitor.visit_enum(EnumAccess {
+                variant: node.into_value()?,
+                value: None,
+            }),
+        }
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+}
+
+struct MapAccess {
+    fields: vec::IntoIter<(String, Node)>,
+    value: Option<Node>,
+}
+
+impl<'de> de::MapAccess<'de> for MapAccess {
+    type Error = serde_qs::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        let Some((key, value)) = self.fields.next() else {
+            return Ok(None);
+        };
+        self.value = Some(value);
+        seed.deserialize(NodeDeserializer(Node::Values(vec![key])))
+            .map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        let value = self
+            .value
+            .take()
+            .ok_or_else(|| serde_qs::Error::custom("expected a value"))?;
+        seed.deserialize(NodeDeserializer(value))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.fields.len())
+    }
+}
+
+struct SeqAccess(vec::IntoIter<Node>);
+
+impl<'de> de::SeqAccess<'de> for SeqAccess {
+    type Error = serde_qs::Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        self.0
+            .next()
+            .map(|node| seed.deserialize(NodeDeserializer(node)))
+            .transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.0.len())
+    }
+}
+
+struct EnumAccess {
+    variant: String,
+    value: Option<Node>,
+}
+
+impl<'de> de::EnumAccess<'de> for EnumAccess {
+    type Error = serde_qs::Error;
+    type Variant = VariantAccess;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self::Variant), Self::Error> {
+        let variant = seed.deserialize(
+            IntoDeserializer::<serde_qs::Error>::into_deserializer(
+                self.variant,
+            ),
+        )?;
+        Ok((variant, VariantAccess(self.value)))
+    }
+}
+
+struct VariantAccess(Option<Node>);
+
+impl VariantAccess {
+    fn into_node(self) -> Result<NodeDeserializer, serde_qs::Error> {
+        self.0
+            .map(NodeDeserializer)
+            .ok_or_else(|| serde_qs::Error::custom("expected a value"))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for VariantAccess {
+    type Error = serde_qs::Error;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, Self::Error> {
+        seed.deserialize(self.into_node()?)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        de::Deserializer::deserialize_seq(self.into_node()?, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        de::Deserializer::deserialize_map(self.into_node()?, visitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::from_form_str;
+    use serde::{Deserialize, Serialize};
+    use std::collections::BTreeMap;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Address {
+        city: String,
+        zip: Option<u32>,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum Size {
+        Small,
+        Large,
+        Custom { width: u32 },
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Order {
+        name: String,
+        #[serde(default)]
+        toppings: Vec<String>,
+        #[serde(default)]
+        gift: bool,
+        note: Option<String>,
+        address: Address,
+        items: Vec<Address>,
+        size: Size,
+        extra: BTreeMap<String, u8>,
+    }
+
+    fn order() -> Order {
+        Order {
+            name: "Ana & Bo".to_string(),
+            toppings: vec!["cheese".to_string(), "olives".to_string()],
+            gift: true,
+            note: None,
+            address: Address {
+                city: "Lyon".to_string(),
+                zip: Some(69001),
+            },
+            items: vec![
+                Address {
+                    city: "Paris".to_string(),
+                    zip: None,
+                },
+                Address {
+                    city: "Nice".to_string(),
+                    zip: Some(6000),
+                },
+            ],
+            size: Size::Custom { width: 30 },
+            extra: BTreeMap::from([("salt".to_string(), 2)]),
+        }
+    }
+
+    #[test]
+    fn reads_what_serde_qs_writes() {
+        let encoded = serde_qs::to_string(&order()).unwrap();
+        assert_eq!(from_form_str::<Order>(&encoded).unwrap(), order());
+    }
+
+    #[test]
+    fn reads_what_browsers_submit() {
+        let submitted = "name=Ana+%26+Bo&toppings=cheese&toppings=olives\
+                         &gift=on&note=&address%5Bcity%5D=Lyon\
+                         &address%5Bzip%5D=69001&items%5B%5D%5Bcity%5D=Paris\
+                         &items%5B%5D%5Bzip%5D=&items%5B%5D%5Bcity%5D=Nice\
+                         &items%5B%5D%5Bzip%5D=6000&size%5BCustom%5D%5Bwidth%5D=30\
+                         &extra%5Bsalt%5D=2";
+        assert_eq!(from_form_str::<Order>(submitted).unwrap(), order());
+    }
+
+    #[test]
+    fn fills_in_missing_and_single_values() {
+        let order = from_form_str::<Order>(
+            "name=a&toppings=cheese&address[city]=Lyon&items[1][city]=B\
+             &items[0][city]=A&size=Large&extra[pepper]=1",
+        )
+        .unwrap();
+        assert_eq!(order.toppings, ["cheese"]);
+        assert!(!order.gift);
+        assert_eq!(order.note, None);
+        assert_eq!(order.items[0].city, "A");
+        assert_eq!(order.items[1].city, "B");
+        assert_eq!(order.size, Size::Large);
+
+        // a hidden input before a checkbox gives the value when it is unchecked
+        #[derive(Deserialize)]
+        struct Flag {
+            on: bool,
+        }
+        assert!(!from_form_str::<Flag>("on=false").unwrap().on);
+        assert!(from_form_str::<Flag>("on=false&on=true").unwrap().on);
+    }
+
+    #[test]
+    fn rejects_invalid_forms() {
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Count {
+            count: u32,
+        }
+        // when neither can read the form, the error is the one from `serde_qs`
+        let error = from_form_str::<Count>("count=many").unwrap_err();
+        let serde_qs_error = serde_qs::Config::new(5, false)
+            .deserialize_str::<Count>("count=many")
+            .unwrap_err();
+        assert_eq!(error.to_string(), serde_qs_error.to_string());
+        assert!(from_form_str::<Count>("").is_err());
+        assert!(from_form_str::<Count>("count=1&count[a]=2").is_err());
+
+        let deep = format!("count{}=1", "[a]".repeat(20));
+        assert!(from_form_str::<Count>(&deep).is_err());
+    }
+}
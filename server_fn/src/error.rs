@@ -11,6 +11,9 @@ use std::{
 use throw_error::Error;
 use url::Url;
 
+mod chain;
+pub use chain::{register_error, ChainLink, ErrorChain};
+
 /// A custom header that can be used to indicate a server function returned an error.
 pub const SERVER_FN_ERROR_HEADER: &str = "serverfnerror";
 
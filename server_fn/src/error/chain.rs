@@ -0,0 +1,326 @@
+//! An error type for server functions that keeps the whole chain of
+//! [`source`](std::error::Error::source)s of an error, instead of flattening it into a single
+//! message.
+//!
+//! Any error can be turned into an [`ErrorChain`] with `?`. Each link of the chain keeps the
+//! message of one error, and errors whose type has been registered with [`register_error`] are
+//! also kept as data, so that the client can get them back with [`ErrorChain::find`]:
+//!
+//! ```rust
+//! use serde::{Deserialize, Serialize};
+//! use server_fn::error::{register_error, ErrorChain};
+//!
+//! #[derive(Debug, thiserror::Error, Serialize, Deserialize)]
+//! #[error("user {0} not found")]
+//! struct UserNotFound(u32);
+//!
+//! #[derive(Debug, thiserror::Error)]
+//! #[error("could not load the profile")]
+//! struct ProfileError(#[source] UserNotFound);
+//!
+//! // on both the server and the client
+//! register_error::<UserNotFound>("UserNotFound");
+//!
+//! fn load_profile() -> Result<(), ErrorChain> {
+//!     Err(ProfileError(UserNotFound(42)))?
+//! }
+//!
+//! let err = load_profile().unwrap_err();
+//! assert_eq!(
+//!     format!("{err:#}"),
+//!     "could not load the profile\ncaused by: user 42 not found"
+//! );
+//! assert_eq!(err.find::<UserNotFound>().map(|e| e.0), Some(42));
+//! ```
+//!
+//! In debug builds, the backtrace of the place where the error was converted is kept too, if
+//! backtraces are enabled with `RUST_BACKTRACE`.
+
+use super::{FromServerFnError, ServerFnErrorErr};
+use crate::codec::JsonEncoding;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    any::TypeId,
+    error::Error,
+    fmt::{self, Display},
+    sync::RwLock,
+};
+
+/// The error types whose values are kept in an [`ErrorChain`].
+static REGISTRY: RwLock<Vec<Registered>> = RwLock::new(Vec::new());
+
+struct Registered {
+    name: &'static str,
+    type_id: TypeId,
+    serialize: fn(&(dyn Error + 'static)) -> Option<serde_json::Value>,
+}
+
+/// Registers the error type `E` under `name`, so that its values are kept in an [`ErrorChain`]
+/// and can be found with [`ErrorChain::find`].
+///
+/// The same types should be registered with the same names on the server and on the client.
+/// Registering a type again replaces its earlier name.
+pub fn register_error<E>(name: &'static str)
+where
+    E: Error + Serialize + DeserializeOwned + 'static,
+{
+    fn serialize<E: Error + Serialize + 'static>(
+        err: &(dyn Error + 'static),
+    ) -> Option<serde_json::Value> {
+        serde_json::to_value(err.downcast_ref::<E>()?).ok()
+    }
+
+    let mut registry = REGISTRY.write().unwrap_or_else(|e| e.into_inner());
+    registry.retain(|entry| entry.type_id != TypeId::of::<E>());
+    registry.push(Registered {
+        name,
+        type_id: TypeId::of::<E>(),
+        serialize: serialize::<E>,
+    });
+}
+
+/// An error along with the chain of errors that caused it, which can be sent from the server to
+/// the client.
+///
+/// The [`Display`] implementation shows the outermost message, and the alternate one (`{:#}`)
+/// adds a `caused by:` line for each source.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ErrorChain {
+    errors: Vec<ChainLink>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    backtrace: Option<String>,
+}
+
+/// One error in an [`ErrorChain`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChainLink {
+    /// The message of the error.
+    pub message: String,
+    /// The name the type of the error was registered with, if it was.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub type_name: Option<String>,
+    /// The error itself, if its type was registered.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+}
+
+impl ChainLink {
+    fn from_error(err: &(dyn Error + 'static)) -> Self {
+        let registry = REGISTRY.read().unwrap_or_else(|e| e.into_inner());
+        let registered = registry.iter().find_map(|entry| {
+            (entry.serialize)(err).map(|data| (entry.name, data))
+        });
+        let (type_name, data) = registered.unzip();
+        Self {
+            message: err.to_string(),
+            type_name: type_name.map(str::to_string),
+            data,
+        }
+    }
+}
+
+impl ErrorChain {
+    /// An error with the given message and no source.
+    pub fn new(message: impl Display) -> Self {
+        Self {
+            errors: vec![ChainLink {
+                message: message.to_string(),
+                type_name: None,
+                data: None,
+            }],
+            backtrace: None,
+        }
+    }
+
+    /// Wraps the error in another one with the given message, which becomes the outermost one.
+    pub fn context(mut self, message: impl Display) -> Self {
+        self.errors.insert(
+            0,
+            ChainLink {
+                message: message.to_string(),
+                type_name: None,
+                data: None,
+            },
+        );
+        self
+    }
+
+    /// The message of the outermost error.
+    pub fn message(&self) -> &str {
+        self.errors
+            .first()
+            .map(|link| link.message.as_str())
+            .unwrap_or_default()
+    }
+
+    /// The errors in the chain, from the outermost one to its deepest source.
+    pub fn errors(&self) -> &[ChainLink] {
+        &self.errors
+    }
+
+    /// The backtrace of the place where the error was converted into an [`ErrorChain`], if it
+    /// was captured.
+    ///
+    /// Backtraces are only captured in debug builds, when they are enabled with `RUST_BACKTRACE`.
+    pub fn backtrace(&self) -> Option<&str> {
+        self.backtrace.as_deref()
+    }
+
+    /// Finds the first error of type `E` in the chain, if `E` was registered with
+    /// [`register_error`].
+    pub fn find<E>(&self) -> Option<E>
+    where
+        E: DeserializeOwned + 'static,
+    {
+        let name = REGISTRY
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .find(|entry| entry.type_id == TypeId::of::<E>())?
+            .name;
+        self.errors
+            .iter()
+            .filter(|link| link.type_name.as_deref() == Some(name))
+            .find_map(|link| serde_json::from_value(link.data.clone()?).ok())
+    }
+}
+
+impl<E> From<E> for ErrorChain
+where
+    E: Error + 'static,
+{
+    fn from(err: E) -> Self {
+        let mut errors = Vec::new();
+        let mut next: Option<&(dyn Error + 'static)> = Some(&err);
+        while let Some(err) = next {
+            errors.push(ChainLink::from_error(err));
+            next = err.source();
+        }
+        Self {
+            errors,
+            backtrace: capture_backtrace(),
+        }
+    }
+}
+
+#[cfg(debug_assertions)]
+fn capture_backtrace() -> Option<String> {
+    use std::backtrace::{Backtrace, BacktraceStatus};
+
+    let backtrace = Backtrace::capture();
+    (backtrace.status() == BacktraceStatus::Captured)
+        .then(|| backtrace.to_string())
+}
+
+#[cfg(not(debug_assertions))]
+fn capture_backtrace() -> Option<String> {
+    None
+}
+
+impl Display for ErrorChain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())?;
+        if f.alternate() {
+            for link in self.errors.iter().skip(1) {
+                write!(f, "\ncaused by: {}", link.message)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromServerFnError for ErrorChain {
+    type Encoder = JsonEncoding;
+
+    fn from_server_fn_error(value: ServerFnErrorErr) -> Self {
+        value.into()
+    }
+}
+
+/// A link of an [`ErrorChain`] as a [`std::error::Error`], whose source is the next link.
+#[derive(Debug)]
+struct LinkError {
+    link: ChainLink,
+    source: Option<Box<LinkError>>,
+}
+
+impl Display for LinkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.link.message)
+    }
+}
+
+impl Error for LinkError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source.as_deref().map(|source| source as _)
+    }
+}
+
+impl From<ErrorChain> for throw_error::Error {
+    fn from(chain: ErrorChain) -> Self {
+        let mut links = chain.errors.into_iter().rev();
+        let deepest = links.next().unwrap_or_else(|| ChainLink {
+            message: String::new(),
+            type_name: None,
+            data: None,
+        });
+        let error = links.fold(
+            LinkError {
+                link: deepest,
+                source: None,
+            },
+            |source, link| LinkError {
+                link,
+                source: Some(Box::new(source)),
+            },
+        );
+        error.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(
+        Debug, Clone, PartialEq, thiserror::Error, Serialize, Deserialize,
+    )]
+    #[error("quota of {0} exceeded")]
+    struct QuotaExceeded(u32);
+
+    #[derive(Debug, thiserror::Error)]
+    #[error("could not save")]
+    struct SaveError(#[source] QuotaExceeded);
+
+    #[test]
+    fn chains_survive_the_round_trip() {
+        register_error::<QuotaExceeded>("QuotaExceeded");
+
+        let chain = ErrorChain::from(SaveError(QuotaExceeded(10)))
+            .context("request failed");
+        let chain = ErrorChain::de(chain.ser());
+
+        assert_eq!(chain.message(), "request failed");
+        assert_eq!(
+            format!("{chain:#}"),
+            "request failed\ncaused by: could not save\ncaused by: quota of \
+             10 exceeded"
+        );
+        assert_eq!(
+            chain.errors()[2].type_name.as_deref(),
+            Some("QuotaExceeded")
+        );
+        assert_eq!(chain.find::<QuotaExceeded>(), Some(QuotaExceeded(10)));
+        assert_eq!(chain.find::<ServerFnErrorErr>(), None);
+
+        let error = throw_error::Error::from(chain);
+        assert_eq!(error.to_string(), "request failed");
+        assert_eq!(
+            error
+                .source()
+                .and_then(|e| e.source())
+                .map(|e| e.to_string()),
+            Some("quota of 10 exceeded".to_string())
+        );
+    }
+}
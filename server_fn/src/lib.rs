@@ -113,6 +113,8 @@ pub mod codec;
 pub mod error;
 /// Types to add server middleware to a server function.
 pub mod middleware;
+/// Mounting server functions at paths chosen at runtime.
+pub mod prefix;
 /// Utilities to allow client-side redirects.
 pub mod redirect;
 /// Types and traits for  for HTTP requests.
@@ -147,7 +149,7 @@ use dashmap::DashMap;
 pub use error::ServerFnError;
 #[cfg(feature = "form-redirects")]
 use error::ServerFnUrlError;
-use error::{FromServerFnError, ServerFnErrorErr};
+use error::{FromServerFnError, ServerFnErrorErr, ServerFnErrorWrapper};
 use futures::{pin_mut, SinkExt, Stream, StreamExt};
 use http::Method;
 use middleware::{BoxedService, Layer, Service};
@@ -164,6 +166,7 @@ pub use serde;
 pub use serde_lite;
 use server::Server;
 use std::{
+    any::TypeId,
     fmt::{Debug, Display},
     future::Future,
     marker::PhantomData,
@@ -349,7 +352,35 @@ pub trait ServerFn: Send + Sized {
     fn run_on_client(
         self,
     ) -> impl Future<Output = Result<Self::Output, Self::Error>> + Send {
-        async move { Self::Protocol::run_client(Self::PATH, self).await }
+        async move {
+            let res = Self::Protocol::run_client(Self::PATH, self).await;
+            if let Err(e) = &res {
+                throw_error::report(
+                    throw_error::ErrorSource::ServerFn(Self::PATH),
+                    // the error is not `Clone`, so the reporter gets a copy of it
+                    || ServerFnErrorWrapper(Self::Error::de(e.ser())).into(),
+                );
+            }
+            res
+        }
+    }
+}
+
+/// The arguments of a server function that an action can be dispatched with.
+///
+/// An action keeps the input it was dispatched with, so that it can be shown while the server
+/// function runs, and sends a copy of it to the server. This is implemented for every type that
+/// implements [`Clone`]. The `#[server]` macro also implements it for the arguments of server
+/// functions that only take [`MultipartData`](codec::MultipartData), which can be copied on the
+/// client, but not on the server, where it is read from the request.
+pub trait ActionInput: Sized {
+    /// Returns a copy of the input to send to the server, or `None` if it can't be copied.
+    fn copy_for_dispatch(&self) -> Option<Self>;
+}
+
+impl<T: Clone> ActionInput for T {
+    fn copy_for_dispatch(&self) -> Option<Self> {
+        Some(self.clone())
     }
 }
 
@@ -865,6 +896,7 @@ macro_rules! initialize_server_fn_map {
         once_cell::sync::Lazy::new(|| {
             $crate::inventory::iter::<ServerFnTraitObj<$req, $res>>
                 .into_iter()
+                .filter(|obj| obj.has_types())
                 .map(|obj| {
                     ((obj.path().to_string(), obj.method()), obj.clone())
                 })
@@ -879,7 +911,11 @@ pub type MiddlewareSet<Req, Res> = Vec<Arc<dyn Layer<Req, Res>>>;
 /// A trait object that allows multiple server functions that take the same
 /// request type and return the same response type to be gathered into a single
 /// collection.
+// the layout doesn't depend on `Req` and `Res`, so that `types` can be read from the
+// server functions of other backends, which are collected into the same registry
+#[repr(C)]
 pub struct ServerFnTraitObj<Req, Res> {
+    types: fn() -> TypeId,
     path: &'static str,
     method: Method,
     handler: fn(Req) -> Pin<Box<dyn Future<Output = Res> + Send>>,
@@ -913,6 +949,7 @@ impl<Req, Res> ServerFnTraitObj<Req, Res> {
         Res: crate::TryRes<S::Error> + Send + 'static,
     {
         Self {
+            types: TypeId::of::<(Req, Res)>,
             path: S::PATH,
             method: S::Protocol::METHOD,
             handler,
@@ -921,6 +958,20 @@ impl<Req, Res> ServerFnTraitObj<Req, Res> {
         }
     }
 
+    /// Whether the server function was registered with the request and response types `Req`
+    /// and `Res`.
+    ///
+    /// Server functions of every backend are submitted to the same `inventory` registry, so
+    /// iterating over the `ServerFnTraitObj`s of one backend also yields those of any other
+    /// backend in the same build. Only those for which this returns `true` can be used.
+    pub fn has_types(&self) -> bool
+    where
+        Req: 'static,
+        Res: 'static,
+    {
+        (self.types)() == TypeId::of::<(Req, Res)>()
+    }
+
     /// The path of the server function.
     pub fn path(&self) -> &'static str {
         self.path
@@ -970,6 +1021,7 @@ where
 impl<Req, Res> Clone for ServerFnTraitObj<Req, Res> {
     fn clone(&self) -> Self {
         Self {
+            types: self.types,
             path: self.path,
             method: self.method.clone(),
             handler: self.handler,
@@ -1106,7 +1158,101 @@ pub mod axum {
         path: &str,
         method: Method,
     ) -> Option<BoxedService<Request<Body>, Response<Body>>> {
-        let key = (path.into(), method);
+        let key = (crate::prefix::registered_path(path)?.into(), method);
+        REGISTERED_SERVER_FUNCTIONS.get(&key).map(|server_fn| {
+            let middleware = (server_fn.middleware)();
+            let mut service = server_fn.clone().boxed();
+            for middleware in middleware {
+                service = middleware.layer(service);
+            }
+            service
+        })
+    }
+}
+
+/// A server function backend that only depends on `http`, for servers that are built on neither
+/// axum nor actix.
+///
+/// Requests carry their whole body as [`Bytes`](bytes::Bytes), so the server has to read it
+/// before running the server function. Responses are either written at once or streamed, see
+/// [`Body`](crate::response::generic::Body).
+#[cfg(all(feature = "generic", feature = "ssr"))]
+pub mod generic {
+    use crate::{
+        error::FromServerFnError, middleware::BoxedService,
+        response::generic::Body, LazyServerFnMap, Protocol, Server, ServerFn,
+        ServerFnTraitObj,
+    };
+    use bytes::Bytes;
+    use http::{Method, Request, Response};
+    use std::future::Future;
+
+    static REGISTERED_SERVER_FUNCTIONS: LazyServerFnMap<
+        Request<Bytes>,
+        Response<Body>,
+    > = initialize_server_fn_map!(Request<Bytes>, Response<Body>);
+
+    /// The generic server function backend
+    pub struct GenericServerFnBackend;
+
+    impl<Error, InputStreamError, OutputStreamError>
+        Server<Error, InputStreamError, OutputStreamError>
+        for GenericServerFnBackend
+    where
+        Error: FromServerFnError + Send + Sync,
+        InputStreamError: FromServerFnError + Send + Sync,
+        OutputStreamError: FromServerFnError + Send + Sync,
+    {
+        type Request = Request<Bytes>;
+        type Response = Response<Body>;
+
+        fn spawn(
+            _future: impl Future<Output = ()> + Send + 'static,
+        ) -> Result<(), Error> {
+            Err(Error::from_server_fn_error(
+                crate::error::ServerFnErrorErr::Request(
+                    "No async runtime available. Implement the `Server` \
+                     trait for your async runtime manually."
+                        .into(),
+                ),
+            ))
+        }
+    }
+
+    /// Explicitly register a server function. This is only necessary if you are
+    /// running the server in a WASM environment (or a rare environment that the
+    /// `inventory` crate won't work in.).
+    pub fn register_explicit<T>()
+    where
+        T: ServerFn<
+                Server: crate::Server<
+                    T::Error,
+                    T::InputStreamError,
+                    T::OutputStreamError,
+                    Request = Request<Bytes>,
+                    Response = Response<Body>,
+                >,
+            > + 'static,
+    {
+        REGISTERED_SERVER_FUNCTIONS.insert(
+            (T::PATH.into(), T::Protocol::METHOD),
+            ServerFnTraitObj::new::<T>(|req| Box::pin(T::run_on_server(req))),
+        );
+    }
+
+    /// The set of all registered server function paths.
+    pub fn server_fn_paths() -> impl Iterator<Item = (&'static str, Method)> {
+        REGISTERED_SERVER_FUNCTIONS
+            .iter()
+            .map(|item| (item.path(), item.method()))
+    }
+
+    /// Returns the server function at the given path as a service that can be modified.
+    pub fn get_server_fn_service(
+        path: &str,
+        method: Method,
+    ) -> Option<BoxedService<Request<Bytes>, Response<Body>>> {
+        let key = (crate::prefix::registered_path(path)?.into(), method);
         REGISTERED_SERVER_FUNCTIONS.get(&key).map(|server_fn| {
             let middleware = (server_fn.middleware)();
             let mut service = server_fn.clone().boxed();
@@ -1233,6 +1379,7 @@ pub mod actix {
             ActixMethod::CONNECT => Method::CONNECT,
             _ => unreachable!(),
         };
+        let path = crate::prefix::registered_path(path)?;
         REGISTERED_SERVER_FUNCTIONS.get(&(path.into(), method)).map(
             |server_fn| {
                 let middleware = (server_fn.middleware)();
@@ -1246,10 +1393,149 @@ pub mod actix {
     }
 }
 
-/// Mocks for the server function backend types when compiling for the client.
+/// Mocks for the server function backend types when compiling for the client, and for server
+/// functions themselves in tests.
+///
+/// A server function called on the client normally sends a request to the server. With the
+/// `mock` feature, which tests can enable as a dev-dependency, calls run the mock that has been
+/// [registered](register) for the server function instead, so that components that call server
+/// functions can be tested without a backend:
+///
+/// ```rust
+/// # use server_fn_macro_default::server;
+/// # #[cfg(all(feature = "browser", feature = "mock"))] {
+/// use server_fn::{mock, ServerFnError};
+///
+/// #[server]
+/// pub async fn get_user_name(id: u32) -> Result<String, ServerFnError> {
+///     todo!("look the user up in the database")
+/// }
+///
+/// # async fn test() {
+/// mock::register::<GetUserName>(|args| Ok(format!("User {}", args.id)));
+/// assert_eq!(get_user_name(7).await.unwrap(), "User 7");
+/// # }
+/// # }
+/// ```
+///
+/// Mocks are global, so tests that run in parallel should not register different mocks for the
+/// same server function.
 pub mod mock {
+    use crate::ServerFn;
     use std::future::Future;
 
+    #[cfg(feature = "mock")]
+    pub use registry::{clear, register, register_async, unregister};
+
+    /// Calls the server function `F` from the client, or runs its mock if one has been
+    /// registered. This is how the functions generated by `#[server]` and server actions call
+    /// server functions.
+    #[doc(hidden)]
+    pub async fn run_on_client<F>(args: F) -> Result<F::Output, F::Error>
+    where
+        F: ServerFn + 'static,
+        F::Output: 'static,
+    {
+        #[cfg(feature = "mock")]
+        let args = match registry::call(args) {
+            Ok(mocked) => return mocked.await,
+            Err(args) => args,
+        };
+        args.run_on_client().await
+    }
+
+    #[cfg(feature = "mock")]
+    mod registry {
+        use crate::ServerFn;
+        use dashmap::DashMap;
+        use once_cell::sync::Lazy;
+        use std::{
+            any::{Any, TypeId},
+            future::Future,
+            pin::Pin,
+            sync::{
+                atomic::{AtomicBool, Ordering},
+                Arc,
+            },
+        };
+
+        type MockFuture<F> = Pin<
+            Box<
+                dyn Future<
+                        Output = Result<
+                            <F as ServerFn>::Output,
+                            <F as ServerFn>::Error,
+                        >,
+                    > + Send,
+            >,
+        >;
+        type MockFn<F> = Arc<dyn Fn(F) -> MockFuture<F> + Send + Sync>;
+
+        // the `MockFn<F>` of each mocked server function, by the `TypeId` of `F`
+        static MOCKS: Lazy<DashMap<TypeId, Box<dyn Any + Send + Sync>>> =
+            Lazy::new(DashMap::new);
+        // skips the lookup when nothing is mocked
+        static ANY_MOCKS: AtomicBool = AtomicBool::new(false);
+
+        /// Runs `mock` instead of sending a request whenever the server function `F` is called
+        /// on the client, replacing any mock registered for it before.
+        pub fn register<F>(
+            mock: impl Fn(F) -> Result<F::Output, F::Error> + Send + Sync + 'static,
+        ) where
+            F: ServerFn + 'static,
+            F::Output: 'static,
+        {
+            register_async::<F, _>(move |args| std::future::ready(mock(args)))
+        }
+
+        /// Like [`register`], for a mock that needs to wait for something, like a timer.
+        pub fn register_async<F, Fut>(
+            mock: impl Fn(F) -> Fut + Send + Sync + 'static,
+        ) where
+            F: ServerFn + 'static,
+            F::Output: 'static,
+            Fut: Future<Output = Result<F::Output, F::Error>> + Send + 'static,
+        {
+            let mock: MockFn<F> =
+                Arc::new(move |args| Box::pin(mock(args)) as MockFuture<F>);
+            MOCKS.insert(TypeId::of::<F>(), Box::new(mock));
+            ANY_MOCKS.store(true, Ordering::Release);
+        }
+
+        /// Removes the mock registered for the server function `F`, so that calls send
+        /// requests again.
+        pub fn unregister<F>()
+        where
+            F: ServerFn + 'static,
+        {
+            MOCKS.remove(&TypeId::of::<F>());
+        }
+
+        /// Removes all mocks.
+        pub fn clear() {
+            MOCKS.clear();
+        }
+
+        /// Runs the mock registered for `F`, or gives the arguments back if there is none.
+        pub(super) fn call<F>(args: F) -> Result<MockFuture<F>, F>
+        where
+            F: ServerFn + 'static,
+            F::Output: 'static,
+        {
+            if !ANY_MOCKS.load(Ordering::Acquire) {
+                return Err(args);
+            }
+            // cloned out, so that the mock can register or remove mocks
+            let mock = MOCKS.get(&TypeId::of::<F>()).and_then(|mock| {
+                mock.value().downcast_ref::<MockFn<F>>().map(Arc::clone)
+            });
+            match mock {
+                Some(mock) => Ok(mock(args)),
+                None => Err(args),
+            }
+        }
+    }
+
     /// A mocked server type that can be used in place of the actual server,
     /// when compiling for the browser.
     ///
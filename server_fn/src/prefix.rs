@@ -0,0 +1,150 @@
+//! By default, a server function is served at the path it was compiled with, which is its
+//! `prefix` followed by its `endpoint`. [`mount`] moves every server function with a given
+//! prefix to another path chosen at runtime, like a versioned `/api/v2`, without recompiling
+//! either the server or the client.
+//!
+//! The same mounts should be set up on both sides: clients send their requests to the
+//! [`mounted_path`], and servers map requests back to the server function with
+//! [`registered_path`].
+
+use std::{borrow::Cow, sync::RwLock};
+
+/// The mounts of the server functions.
+static MOUNTS: RwLock<Mounts> = RwLock::new(Mounts::new());
+
+/// Mounts the server functions whose path starts with `prefix` at `at` instead.
+///
+/// Mounting the same prefix again replaces the earlier mount. Once a prefix is mounted, its
+/// server functions are no longer served at their compiled paths.
+pub fn mount(prefix: &str, at: &str) {
+    MOUNTS
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .mount(prefix, at);
+}
+
+/// Returns the path that the server function compiled with `path` is served at.
+pub fn mounted_path(path: &str) -> Cow<'_, str> {
+    MOUNTS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .mounted_path(path)
+}
+
+/// Returns the compiled path of the server function that a request for `path` is meant for, or
+/// `None` if `path` is the compiled path of a server function that has been mounted elsewhere.
+pub fn registered_path(path: &str) -> Option<Cow<'_, str>> {
+    MOUNTS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .registered_path(path)
+}
+
+/// Pairs of compiled prefixes and the paths they are mounted at.
+///
+/// In both directions, the longest matching prefix wins, as a prefix can be mounted below
+/// another one.
+struct Mounts(Vec<(String, String)>);
+
+impl Mounts {
+    const fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    fn mount(&mut self, prefix: &str, at: &str) {
+        let prefix = prefix.trim_end_matches('/').to_string();
+        let at = at.trim_end_matches('/').to_string();
+        self.0.retain(|(existing, _)| *existing != prefix);
+        if prefix != at {
+            self.0.push((prefix, at));
+        }
+    }
+
+    fn mounted_path<'a>(&self, path: &'a str) -> Cow<'a, str> {
+        match longest_match(path, self.0.iter().map(|(from, to)| (from, to))) {
+            Some((at, rest)) => Cow::Owned(format!("{at}{rest}")),
+            None => Cow::Borrowed(path),
+        }
+    }
+
+    fn registered_path<'a>(&self, path: &'a str) -> Option<Cow<'a, str>> {
+        if let Some((prefix, rest)) =
+            longest_match(path, self.0.iter().map(|(from, to)| (to, from)))
+        {
+            return Some(Cow::Owned(format!("{prefix}{rest}")));
+        }
+        if self
+            .0
+            .iter()
+            .any(|(prefix, _)| strip_segments(path, prefix).is_some())
+        {
+            return None;
+        }
+        Some(Cow::Borrowed(path))
+    }
+}
+
+/// Finds the longest `from` that `path` starts with, returning its `to` and the rest of `path`.
+fn longest_match<'a, 'm>(
+    path: &'a str,
+    mounts: impl Iterator<Item = (&'m String, &'m String)>,
+) -> Option<(&'m str, &'a str)> {
+    mounts
+        .filter_map(|(from, to)| {
+            strip_segments(path, from).map(|rest| (from.len(), to, rest))
+        })
+        .max_by_key(|(len, ..)| *len)
+        .map(|(_, to, rest)| (to.as_str(), rest))
+}
+
+/// Strips `prefix` from `path` if it is made of whole path segments of it.
+fn strip_segments<'a>(path: &'a str, prefix: &str) -> Option<&'a str> {
+    path.strip_prefix(prefix)
+        .filter(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mounted_prefixes_are_rewritten_both_ways() {
+        let mut mounts = Mounts::new();
+        mounts.mount("/api", "/api/v2/");
+        assert_eq!(mounts.mounted_path("/api/get_posts"), "/api/v2/get_posts");
+        assert_eq!(mounts.mounted_path("/apix/get_posts"), "/apix/get_posts");
+        assert_eq!(
+            mounts.registered_path("/api/v2/get_posts").as_deref(),
+            Some("/api/get_posts")
+        );
+        assert_eq!(mounts.registered_path("/api/get_posts"), None);
+        assert_eq!(
+            mounts.registered_path("/other/get_posts").as_deref(),
+            Some("/other/get_posts")
+        );
+
+        mounts.mount("/api", "/api");
+        assert_eq!(mounts.mounted_path("/api/get_posts"), "/api/get_posts");
+        assert_eq!(
+            mounts.registered_path("/api/get_posts").as_deref(),
+            Some("/api/get_posts")
+        );
+    }
+
+    #[test]
+    fn nested_prefixes_use_the_longest_match() {
+        let mut mounts = Mounts::new();
+        mounts.mount("/api", "/v1");
+        mounts.mount("/api/admin", "/admin");
+        for (compiled, served) in [
+            ("/api/get_posts", "/v1/get_posts"),
+            ("/api/admin/ban", "/admin/ban"),
+        ] {
+            assert_eq!(mounts.mounted_path(compiled), served);
+            assert_eq!(
+                mounts.registered_path(served).as_deref(),
+                Some(compiled)
+            );
+        }
+    }
+}
@@ -2,6 +2,7 @@ use super::ClientReq;
 use crate::{
     client::get_server_url,
     error::{FromServerFnError, ServerFnErrorErr},
+    prefix::mounted_path,
 };
 use bytes::Bytes;
 use futures::{Stream, StreamExt};
@@ -72,7 +73,7 @@ impl DerefMut for BrowserRequest {
 }
 
 /// The `FormData` type available in the browser.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct BrowserFormData(pub(crate) SendWrapper<FormData>);
 
 impl From<FormData> for BrowserFormData {
@@ -102,11 +103,12 @@ where
     ) -> Result<Self, E> {
         let (abort_ctrl, abort_signal) = abort_signal();
         let server_url = get_server_url();
+        let path = mounted_path(path);
         let mut url = String::with_capacity(
             server_url.len() + path.len() + 1 + query.len(),
         );
         url.push_str(server_url);
-        url.push_str(path);
+        url.push_str(&path);
         url.push('?');
         url.push_str(query);
         Ok(Self(SendWrapper::new(RequestInner {
@@ -146,9 +148,10 @@ where
     ) -> Result<Self, E> {
         let (abort_ctrl, abort_signal) = abort_signal();
         let server_url = get_server_url();
+        let path = mounted_path(path);
         let mut url = String::with_capacity(server_url.len() + path.len());
         url.push_str(server_url);
-        url.push_str(path);
+        url.push_str(&path);
         Ok(Self(SendWrapper::new(RequestInner {
             request: match method {
                 Method::POST => Request::post(&url),
@@ -184,9 +187,10 @@ where
     ) -> Result<Self, E> {
         let (abort_ctrl, abort_signal) = abort_signal();
         let server_url = get_server_url();
+        let path = mounted_path(path);
         let mut url = String::with_capacity(server_url.len() + path.len());
         url.push_str(server_url);
-        url.push_str(path);
+        url.push_str(&path);
         let body: &[u8] = &body;
         let body = Uint8Array::from(body).buffer();
         Ok(Self(SendWrapper::new(RequestInner {
@@ -223,9 +227,10 @@ where
     ) -> Result<Self, E> {
         let (abort_ctrl, abort_signal) = abort_signal();
         let server_url = get_server_url();
+        let path = mounted_path(path);
         let mut url = String::with_capacity(server_url.len() + path.len());
         url.push_str(server_url);
-        url.push_str(path);
+        url.push_str(&path);
         Ok(Self(SendWrapper::new(RequestInner {
             request: match method {
                 Method::POST => Request::post(&url),
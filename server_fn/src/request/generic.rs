@@ -31,7 +31,7 @@ where
     InputStreamError: FromServerFnError + Send,
     OutputStreamError: FromServerFnError + Send,
 {
-    type WebsocketResponse = Response<Bytes>;
+    type WebsocketResponse = Response<crate::response::generic::Body>;
 
     async fn try_into_bytes(self) -> Result<Bytes, Error> {
         Ok(self.into_body())
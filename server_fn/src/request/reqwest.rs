@@ -2,6 +2,7 @@ use super::ClientReq;
 use crate::{
     client::get_server_url,
     error::{FromServerFnError, IntoAppError, ServerFnErrorErr},
+    prefix::mounted_path,
 };
 use bytes::Bytes;
 use futures::{Stream, StreamExt};
@@ -27,7 +28,7 @@ where
         query: &str,
         method: Method,
     ) -> Result<Self, E> {
-        let url = format!("{}{}", get_server_url(), path);
+        let url = format!("{}{}", get_server_url(), mounted_path(path));
         let mut url = Url::try_from(url.as_str()).map_err(|e| {
             E::from_server_fn_error(ServerFnErrorErr::Request(e.to_string()))
         })?;
@@ -61,7 +62,7 @@ where
         body: String,
         method: Method,
     ) -> Result<Self, E> {
-        let url = format!("{}{}", get_server_url(), path);
+        let url = format!("{}{}", get_server_url(), mounted_path(path));
         match method {
             Method::POST => CLIENT.post(url),
             Method::PUT => CLIENT.put(url),
@@ -86,7 +87,7 @@ where
         body: Bytes,
         method: Method,
     ) -> Result<Self, E> {
-        let url = format!("{}{}", get_server_url(), path);
+        let url = format!("{}{}", get_server_url(), mounted_path(path));
         match method {
             Method::POST => CLIENT.post(url),
             Method::PATCH => CLIENT.patch(url),
@@ -157,7 +158,7 @@ where
         body: impl Stream<Item = Bytes> + Send + 'static,
         method: Method,
     ) -> Result<Self, E> {
-        let url = format!("{}{}", get_server_url(), path);
+        let url = format!("{}{}", get_server_url(), mounted_path(path));
         let body = Body::wrap_stream(
             body.map(|chunk| Ok(chunk) as Result<Bytes, ServerFnErrorErr>),
         );
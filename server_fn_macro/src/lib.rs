@@ -160,6 +160,10 @@ impl ServerFnCall {
             parse_quote! {
                 #server_fn_path::mock::BrowserMockServer
             }
+        } else if let Some(server) = &self.args.server {
+            // a backend that is named explicitly wins over those enabled by features, which
+            // may have been enabled by another crate in the same build
+            server.clone()
         } else if cfg!(feature = "axum") {
             parse_quote! {
                 #server_fn_path::axum::AxumServerFnBackend
@@ -170,10 +174,8 @@ impl ServerFnCall {
             }
         } else if cfg!(feature = "generic") {
             parse_quote! {
-                #server_fn_path::axum::AxumServerFnBackend
+                #server_fn_path::generic::GenericServerFnBackend
             }
-        } else if let Some(server) = &self.args.server {
-            server.clone()
         } else if let Some(server) = &self.preset_server {
             server.clone()
         } else {
@@ -684,6 +686,18 @@ impl ServerFnCall {
             .map(|field| (&*field.arg.pat, &*field.arg.ty))
     }
 
+    /// Whether the type of every argument is a path ending in `ty`.
+    fn all_args_are(&self, ty: &str) -> bool {
+        self.body.inputs.iter().all(|field| match &*field.arg.ty {
+            Type::Path(path) => path
+                .path
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == ty),
+            _ => false,
+        })
+    }
+
     fn deref_impl(&self) -> TokenStream2 {
         let impl_deref = self
             .args
@@ -744,6 +758,29 @@ impl ServerFnCall {
         }
     }
 
+    fn action_input_impl(&self) -> TokenStream2 {
+        // the arguments of other server functions are `Clone`, which implements `ActionInput`;
+        // multipart data can't be cloned, but it can be copied on the client, where actions
+        // are dispatched
+        if self.input_ident().as_deref() != Some("MultipartFormData")
+            || !self.all_args_are("MultipartData")
+        {
+            return quote! {};
+        }
+        let server_fn_path = self.server_fn_path();
+        let struct_name = self.struct_name();
+        let field_names = self.field_names();
+        quote! {
+            impl #server_fn_path::ActionInput for #struct_name {
+                fn copy_for_dispatch(&self) -> Option<Self> {
+                    Some(#struct_name {
+                        #(#field_names: self.#field_names.client_copy()?,)*
+                    })
+                }
+            }
+        }
+    }
+
     fn func_tokens(&self) -> TokenStream2 {
         let body = &self.body;
         // default values for args
@@ -793,9 +830,8 @@ impl ServerFnCall {
                 #(#attrs)*
                 #[allow(unused_variables)]
                 #vis async fn #fn_name(#(#fn_args),*) #output_arrow #return_ty {
-                    use #server_fn_path::ServerFn;
                     #restructure
-                    data.run_on_client().await
+                    #server_fn_path::mock::run_on_client(data).await
                 }
             }
         }
@@ -813,6 +849,8 @@ impl ToTokens for ServerFnCall {
 
         let deref_impl = self.deref_impl();
 
+        let action_input_impl = self.action_input_impl();
+
         let inventory = self.submit_to_inventory();
 
         let func = self.func_tokens();
@@ -828,6 +866,8 @@ impl ToTokens for ServerFnCall {
 
             #deref_impl
 
+            #action_input_impl
+
             #server_fn_impl
 
             #inventory
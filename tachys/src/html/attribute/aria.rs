@@ -6,8 +6,15 @@ use crate::{
     renderer::Rndr,
     view::{add_attr::AddAnyAttr, RenderHtml},
 };
+use std::{
+    borrow::Cow,
+    fmt::{self, Display, Formatter},
+};
 
 /// Applies ARIA attributes to an HTML element.
+///
+/// Values can be strings, or the typed [`AriaRole`], [`Tristate`] and [`IdRefs`], which catch
+/// invalid values at compile time.
 pub trait AriaAttributes<Rndr, V>
 where
     Self: Sized + AddAnyAttr,
@@ -406,3 +413,470 @@ where
     V: AttributeValue,
 {
 }
+
+/// Implements [`AttributeValue`] for a typed ARIA value by converting it into a plain value.
+macro_rules! aria_value {
+    ($ty:ty, $inner:ty, |$value:ident| $into:expr) => {
+        impl AttributeValue for $ty {
+            type State = <$inner as AttributeValue>::State;
+            type AsyncOutput = Self;
+            type Cloneable = Self;
+            type CloneableOwned = Self;
+
+            fn html_len(&self) -> usize {
+                let $value = self.clone();
+                <$inner as AttributeValue>::html_len(&$into)
+            }
+
+            fn to_html(self, key: &str, buf: &mut String) {
+                let $value = self;
+                <$inner as AttributeValue>::to_html($into, key, buf);
+            }
+
+            fn to_template(key: &str, buf: &mut String) {
+                <$inner as AttributeValue>::to_template(key, buf);
+            }
+
+            fn hydrate<const FROM_SERVER: bool>(
+                self,
+                key: &str,
+                el: &crate::renderer::types::Element,
+            ) -> Self::State {
+                let $value = self;
+                <$inner as AttributeValue>::hydrate::<FROM_SERVER>(
+                    $into, key, el,
+                )
+            }
+
+            fn build(
+                self,
+                el: &crate::renderer::types::Element,
+                key: &str,
+            ) -> Self::State {
+                let $value = self;
+                <$inner as AttributeValue>::build($into, el, key)
+            }
+
+            fn rebuild(self, key: &str, state: &mut Self::State) {
+                let $value = self;
+                <$inner as AttributeValue>::rebuild($into, key, state);
+            }
+
+            fn into_cloneable(self) -> Self::Cloneable {
+                self
+            }
+
+            fn into_cloneable_owned(self) -> Self::CloneableOwned {
+                self
+            }
+
+            fn dry_resolve(&mut self) {}
+
+            async fn resolve(self) -> Self::AsyncOutput {
+                self
+            }
+        }
+    };
+}
+
+macro_rules! roles {
+    ($($(#[$meta:meta])* $variant:ident $role:literal),* $(,)?) => {
+        /// A WAI-ARIA role, for the `role` attribute.
+        ///
+        /// Using this instead of a string means that a misspelled role is a compile error, rather
+        /// than an element that assistive technologies silently treat as having no role.
+        ///
+        /// ```
+        /// # use tachys::{html::{attribute::aria::{AriaRole, Tristate}, element::button}, prelude::*};
+        /// let tab = button()
+        ///     .role(AriaRole::Tab)
+        ///     .aria_selected(Tristate::True)
+        ///     .child("Settings");
+        /// # let _ = tab;
+        /// ```
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #[non_exhaustive]
+        pub enum AriaRole {
+            $(
+                $(#[$meta])*
+                $variant,
+            )*
+        }
+
+        impl AriaRole {
+            /// The role as it appears in the `role` attribute.
+            pub const fn as_str(&self) -> &'static str {
+                match self {
+                    $(Self::$variant => $role,)*
+                }
+            }
+        }
+    };
+}
+
+roles! {
+    /// A message with important, and usually time-sensitive, information.
+    Alert "alert",
+    /// A modal alert dialog that interrupts the user's workflow.
+    Alertdialog "alertdialog",
+    /// A region with its own keyboard interaction, like a desktop application.
+    Application "application",
+    /// A self-contained composition, like a blog post or a comment.
+    Article "article",
+    /// The site-oriented content at the beginning of the page.
+    Banner "banner",
+    /// A section quoted from another source.
+    Blockquote "blockquote",
+    /// An element that triggers an action when activated.
+    Button "button",
+    /// The caption of a table, grid, treegrid or figure.
+    Caption "caption",
+    /// A cell in a tabular container.
+    Cell "cell",
+    /// A checkable input with three possible values: `true`, `false` or `mixed`.
+    Checkbox "checkbox",
+    /// A fragment of computer code.
+    Code "code",
+    /// A cell containing header information for a column.
+    Columnheader "columnheader",
+    /// An input that controls another element, like a listbox, that can pop up to help set its value.
+    Combobox "combobox",
+    /// A section that supports the main content, while remaining meaningful on its own.
+    Complementary "complementary",
+    /// Information about the parent document, like copyrights and links to privacy statements.
+    Contentinfo "contentinfo",
+    /// A definition of a term or concept.
+    Definition "definition",
+    /// Content that has been removed, or that is marked for removal.
+    Deletion "deletion",
+    /// A window separate from the rest of the page, like a modal.
+    Dialog "dialog",
+    /// Content that is meant to be read, rather than interacted with.
+    Document "document",
+    /// Text that is stressed.
+    Emphasis "emphasis",
+    /// A scrollable list of articles, where articles may be added or removed as the user scrolls.
+    Feed "feed",
+    /// Content grouped with an optional caption, like an image or a code listing.
+    Figure "figure",
+    /// A landmark containing items that together make up a form.
+    Form "form",
+    /// An element without semantic meaning of its own.
+    Generic "generic",
+    /// A composite widget of cells in rows and columns.
+    Grid "grid",
+    /// A cell in a grid or treegrid.
+    Gridcell "gridcell",
+    /// A set of objects that are not meant to appear in a page summary or table of contents.
+    Group "group",
+    /// A heading for a section of the page.
+    Heading "heading",
+    /// A container for a collection of elements that form an image.
+    Img "img",
+    /// Content that has been added, or that is marked for addition.
+    Insertion "insertion",
+    /// A reference to a resource that navigates to it when activated.
+    Link "link",
+    /// A section containing list items.
+    List "list",
+    /// A widget that allows the user to select one or more options from a list.
+    Listbox "listbox",
+    /// A single item in a list.
+    Listitem "listitem",
+    /// A live region where new information is added in a meaningful order, like a chat log.
+    Log "log",
+    /// The main content of the document.
+    Main "main",
+    /// A live region with non-essential information that changes often, like a ticker.
+    Marquee "marquee",
+    /// A mathematical expression.
+    Math "math",
+    /// A widget that offers a list of choices to the user.
+    Menu "menu",
+    /// A menu that is usually always visible and laid out horizontally.
+    Menubar "menubar",
+    /// An option in a menu or menubar.
+    Menuitem "menuitem",
+    /// A checkable item in a menu.
+    Menuitemcheckbox "menuitemcheckbox",
+    /// A checkable item in a menu, of which only one in a group can be checked at a time.
+    Menuitemradio "menuitemradio",
+    /// A scalar measurement within a known range.
+    Meter "meter",
+    /// A collection of links for navigating the document or related documents.
+    Navigation "navigation",
+    /// An element whose implicit role should not be exposed to assistive technologies.
+    None "none",
+    /// A section whose content is parenthetic or ancillary to the main content.
+    Note "note",
+    /// A selectable item in a listbox.
+    Option "option",
+    /// A paragraph of content.
+    Paragraph "paragraph",
+    /// A synonym of [`AriaRole::None`].
+    Presentation "presentation",
+    /// An element that displays the progress of a long-running task.
+    Progressbar "progressbar",
+    /// A checkable input in a group, of which only one can be checked at a time.
+    Radio "radio",
+    /// A group of radio buttons.
+    Radiogroup "radiogroup",
+    /// A section that is important enough to be included in a page summary.
+    Region "region",
+    /// A row of cells in a tabular container.
+    Row "row",
+    /// A structure containing one or more rows in a tabular container.
+    Rowgroup "rowgroup",
+    /// A cell containing header information for a row.
+    Rowheader "rowheader",
+    /// A graphical object that controls the scrolling of content within a viewing area.
+    Scrollbar "scrollbar",
+    /// A landmark containing the items that together make up a search facility.
+    Search "search",
+    /// A textbox for entering search criteria.
+    Searchbox "searchbox",
+    /// A divider that separates sections of content or groups of menu items.
+    Separator "separator",
+    /// An input where the user selects a value from within a given range.
+    Slider "slider",
+    /// A range of discrete values that the user can step through.
+    Spinbutton "spinbutton",
+    /// A live region with advisory information that is not important enough to be an alert.
+    Status "status",
+    /// Text that is important, serious or urgent.
+    Strong "strong",
+    /// Text that is rendered below the baseline.
+    Subscript "subscript",
+    /// Text that is rendered above the baseline.
+    Superscript "superscript",
+    /// A checkbox that represents on and off values rather than checked and unchecked ones.
+    Switch "switch",
+    /// A tab in a tablist, which selects the content shown in a tabpanel.
+    Tab "tab",
+    /// A section containing data arranged in rows and columns.
+    Table "table",
+    /// A list of tabs.
+    Tablist "tablist",
+    /// The content associated with a tab.
+    Tabpanel "tabpanel",
+    /// A word or phrase with a corresponding definition.
+    Term "term",
+    /// An input for free-form text.
+    Textbox "textbox",
+    /// A specific point in time.
+    Time "time",
+    /// A live region with a numerical counter of elapsed or remaining time.
+    Timer "timer",
+    /// A collection of commonly used buttons or other controls.
+    Toolbar "toolbar",
+    /// A popup that displays a description for an element.
+    Tooltip "tooltip",
+    /// A hierarchical list of items that may be expanded and collapsed.
+    Tree "tree",
+    /// A grid whose rows can be expanded and collapsed like the items of a tree.
+    Treegrid "treegrid",
+    /// An item in a tree.
+    Treeitem "treeitem",
+}
+
+impl Display for AriaRole {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+aria_value!(AriaRole, &'static str, |role| role.as_str());
+
+/// The value of an ARIA state that can be `true`, `false` or `mixed`, like `aria-checked` or
+/// `aria-pressed`.
+///
+/// It also converts from a `bool`, for ARIA states that are only `true` or `false`, like
+/// `aria-expanded` or `aria-hidden`: a plain `bool` is rendered as an HTML boolean attribute,
+/// which is either present and empty or missing entirely, and is not a valid ARIA value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Tristate {
+    /// `false`.
+    #[default]
+    False,
+    /// `true`.
+    True,
+    /// `mixed`, for a checkbox that controls other checkboxes, some of which are checked.
+    Mixed,
+}
+
+impl Tristate {
+    /// The value as it appears in the attribute.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Tristate::False => "false",
+            Tristate::True => "true",
+            Tristate::Mixed => "mixed",
+        }
+    }
+}
+
+impl From<bool> for Tristate {
+    fn from(value: bool) -> Self {
+        if value {
+            Tristate::True
+        } else {
+            Tristate::False
+        }
+    }
+}
+
+impl Display for Tristate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+aria_value!(Tristate, &'static str, |state| state.as_str());
+
+/// A list of element ids, for the ARIA attributes that refer to other elements, like
+/// `aria-labelledby`, `aria-describedby`, `aria-controls` or `aria-owns`.
+///
+/// The attribute is left out while the list is empty.
+///
+/// ```
+/// # use tachys::html::attribute::aria::IdRefs;
+/// let ids = IdRefs::new().id("title").id("subtitle");
+/// assert_eq!(ids.to_string(), "title subtitle");
+/// ```
+///
+/// With the `reactive_graph` feature, ids can also be taken from a
+/// [`NodeRef`](crate::reactive_graph::node_ref::NodeRef). Node references are only loaded once
+/// the element is mounted in the browser, so the list should be built in a closure, which reruns
+/// when they are loaded; ids that are known ahead of time are also rendered on the server.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct IdRefs(Vec<Cow<'static, str>>);
+
+impl IdRefs {
+    /// Creates an empty list.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an id to the list.
+    pub fn id(mut self, id: impl Into<Cow<'static, str>>) -> Self {
+        self.push(id);
+        self
+    }
+
+    /// Adds an id to the list.
+    pub fn push(&mut self, id: impl Into<Cow<'static, str>>) {
+        let id = id.into();
+        if !id.is_empty() {
+            self.0.push(id);
+        }
+    }
+
+    /// Adds the id of the element that `node_ref` refers to, if it has been loaded.
+    ///
+    /// An element without an id is given a unique one. This tracks `node_ref`, so it should be
+    /// called in a reactive closure.
+    #[cfg(feature = "reactive_graph")]
+    pub fn node_ref<E>(
+        mut self,
+        node_ref: crate::reactive_graph::node_ref::NodeRef<E>,
+    ) -> Self
+    where
+        E: ElementType,
+        E::Output: wasm_bindgen::JsCast + Clone + 'static,
+    {
+        use reactive_graph::traits::Get;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use wasm_bindgen::JsCast;
+
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+        if let Some(el) = node_ref.get() {
+            let el = el.unchecked_ref::<web_sys::Element>();
+            let mut id = el.id();
+            if id.is_empty() {
+                id = format!(
+                    "aria-ref-{}",
+                    NEXT_ID.fetch_add(1, Ordering::Relaxed)
+                );
+                el.set_id(&id);
+            }
+            self.push(id);
+        }
+        self
+    }
+
+    /// Whether the list is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The ids in the list.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(|id| id.as_ref())
+    }
+}
+
+impl<T> FromIterator<T> for IdRefs
+where
+    T: Into<Cow<'static, str>>,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut ids = Self::new();
+        for id in iter {
+            ids.push(id);
+        }
+        ids
+    }
+}
+
+impl Display for IdRefs {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (idx, id) in self.0.iter().enumerate() {
+            if idx > 0 {
+                f.write_str(" ")?;
+            }
+            f.write_str(id)?;
+        }
+        Ok(())
+    }
+}
+
+aria_value!(IdRefs, Option<String>, |ids| (!ids.is_empty())
+    .then(|| ids.to_string()));
+
+#[cfg(test)]
+mod tests {
+    use super::{AriaRole, IdRefs, Tristate};
+    use crate::html::attribute::AttributeValue;
+
+    fn render(key: &str, value: impl AttributeValue) -> String {
+        let mut buf = String::new();
+        value.to_html(key, &mut buf);
+        buf
+    }
+
+    #[test]
+    fn renders_typed_aria_values() {
+        assert_eq!(render("role", AriaRole::Tablist), r#" role="tablist""#);
+        assert_eq!(
+            render("aria-checked", Tristate::Mixed),
+            r#" aria-checked="mixed""#
+        );
+        assert_eq!(
+            render("aria-expanded", Tristate::from(false)),
+            r#" aria-expanded="false""#
+        );
+        assert_eq!(
+            render("aria-labelledby", IdRefs::new().id("title").id("sub")),
+            r#" aria-labelledby="title sub""#
+        );
+        assert_eq!(
+            render(
+                "aria-describedby",
+                ["", "hint"].into_iter().collect::<IdRefs>()
+            ),
+            r#" aria-describedby="hint""#
+        );
+        assert_eq!(render("aria-controls", IdRefs::new()), "");
+    }
+}
@@ -1,6 +1,7 @@
 use crate::renderer::Rndr;
 use std::{
     borrow::Cow,
+    cell::Cell,
     future::Future,
     net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
     num::{
@@ -8,7 +9,7 @@ use std::{
         NonZeroIsize, NonZeroU128, NonZeroU16, NonZeroU32, NonZeroU64,
         NonZeroU8, NonZeroUsize,
     },
-    sync::Arc,
+    sync::{Arc, OnceLock},
 };
 
 /// Declares that this type can be converted into some other type, which is a valid attribute value.
@@ -151,7 +152,7 @@ impl<'a> AttributeValue for &'a str {
         buf.push(' ');
         buf.push_str(key);
         buf.push_str("=\"");
-        buf.push_str(&escape_attr(self));
+        buf.push_str(&escape_attr(key, self));
         buf.push('"');
     }
 
@@ -595,10 +596,161 @@ where
     }
 }
 
-pub(crate) fn escape_attr(value: &str) -> Cow<'_, str> {
+/// A function that escapes the value of an attribute, given its name, before it is rendered to
+/// HTML between double quotes.
+pub type AttributeEscaper = for<'a> fn(&str, &'a str) -> Cow<'a, str>;
+
+static ATTRIBUTE_ESCAPER: OnceLock<AttributeEscaper> = OnceLock::new();
+
+thread_local! {
+    // set while a `Raw` value is rendered
+    static RAW: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Marks the values rendered on this thread as [`Raw`] until it is dropped, even if rendering
+/// panics.
+struct RawGuard(bool);
+
+impl RawGuard {
+    fn enter() -> Self {
+        Self(RAW.with(|raw| raw.replace(true)))
+    }
+}
+
+impl Drop for RawGuard {
+    fn drop(&mut self) {
+        RAW.with(|raw| raw.set(self.0));
+    }
+}
+
+/// Sets the function used to escape attribute values when they are rendered to HTML on the
+/// server, replacing [`escape_attribute_value`]. This is meant to be called once, when the
+/// server starts, before anything is rendered.
+///
+/// This can be used to escape more than the default does, like the delimiters of a templating
+/// system that will process the HTML, or to escape some attributes differently. It applies to
+/// every attribute value rendered at runtime, including `class` and `style`, but not to values
+/// wrapped in [`Raw`], nor to the string literals of static views, which the `view!` macro
+/// escapes when it compiles them.
+///
+/// An escaper must make sure that the value does not contain a double quote (`"`), which would
+/// end the attribute.
+///
+/// # Errors
+/// Returns the escaper back if one has already been set.
+///
+/// ```rust
+/// use std::borrow::Cow;
+/// use tachys::html::attribute::{escape_attribute_value, set_attribute_escaper};
+///
+/// // keep `{{ }}` from being interpreted by a template engine
+/// set_attribute_escaper(|key, value| {
+///     let value = escape_attribute_value(key, value);
+///     if value.contains("{{") {
+///         Cow::Owned(value.replace("{{", "{&#123;"))
+///     } else {
+///         value
+///     }
+/// })
+/// .expect("no escaper has been set yet");
+/// ```
+pub fn set_attribute_escaper(
+    escaper: AttributeEscaper,
+) -> Result<(), AttributeEscaper> {
+    ATTRIBUTE_ESCAPER.set(escaper)
+}
+
+/// Escapes the value of an attribute so that it can be rendered between double quotes.
+///
+/// This is how attribute values are escaped unless another function is set with
+/// [`set_attribute_escaper`].
+pub fn escape_attribute_value<'a>(_key: &str, value: &'a str) -> Cow<'a, str> {
     html_escape::encode_double_quoted_attribute(value)
 }
 
+pub(crate) fn escape_attr<'a>(key: &str, value: &'a str) -> Cow<'a, str> {
+    if RAW.with(Cell::get) {
+        return Cow::Borrowed(value);
+    }
+    let escaper = ATTRIBUTE_ESCAPER
+        .get()
+        .copied()
+        .unwrap_or(escape_attribute_value);
+    escaper(key, value)
+}
+
+/// An attribute value that is rendered to HTML exactly as it is, without being escaped.
+///
+/// This is an escape hatch for attribute values that must be rendered precisely, like JSON that a
+/// third-party script reads from an attribute. In the `view!` macro, `attr:raw:name={value}` sets
+/// the attribute `name` to a `Raw` value.
+///
+/// The value must not contain a double quote (`"`), which would end the attribute, nor anything
+/// that comes from an untrusted source: it would allow injecting HTML into the page. Values that
+/// are set in the browser are never escaped, so this only changes how the value is rendered on
+/// the server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Raw<V>(pub V);
+
+impl<V> AttributeValue for Raw<V>
+where
+    V: AttributeValue,
+{
+    type State = V::State;
+    type AsyncOutput = Raw<V::AsyncOutput>;
+    type Cloneable = Raw<V::Cloneable>;
+    type CloneableOwned = Raw<V::CloneableOwned>;
+
+    fn html_len(&self) -> usize {
+        self.0.html_len()
+    }
+
+    fn to_html(self, key: &str, buf: &mut String) {
+        let _raw = RawGuard::enter();
+        self.0.to_html(key, buf);
+    }
+
+    fn to_template(key: &str, buf: &mut String) {
+        V::to_template(key, buf);
+    }
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        key: &str,
+        el: &crate::renderer::types::Element,
+    ) -> Self::State {
+        self.0.hydrate::<FROM_SERVER>(key, el)
+    }
+
+    fn build(
+        self,
+        el: &crate::renderer::types::Element,
+        key: &str,
+    ) -> Self::State {
+        self.0.build(el, key)
+    }
+
+    fn rebuild(self, key: &str, state: &mut Self::State) {
+        self.0.rebuild(key, state);
+    }
+
+    fn into_cloneable(self) -> Self::Cloneable {
+        Raw(self.0.into_cloneable())
+    }
+
+    fn into_cloneable_owned(self) -> Self::CloneableOwned {
+        Raw(self.0.into_cloneable_owned())
+    }
+
+    fn dry_resolve(&mut self) {
+        self.0.dry_resolve();
+    }
+
+    async fn resolve(self) -> Self::AsyncOutput {
+        Raw(self.0.resolve().await)
+    }
+}
+
 macro_rules! render_primitive {
   ($($child_type:ty),* $(,)?) => {
       $(
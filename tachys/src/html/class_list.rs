@@ -0,0 +1,929 @@
+use super::class::IntoClass;
+use crate::renderer::Rndr;
+use std::{borrow::Cow, collections::HashSet, fmt, sync::Arc};
+
+/// Builds a [`ClassList`] from a list of segments, each of which is anything that implements
+/// [`ClassSegment`].
+///
+/// The classes of the segments are merged so that, when two utility classes set the same CSS
+/// property, only the last one is kept, the way `tailwind-merge` does. This makes it possible
+/// to override the classes of a component from its props without duplicating them.
+///
+/// ```rust,ignore
+/// let (active, set_active) = signal(false);
+///
+/// view! {
+///     <button class=class_list![
+///         "px-4 py-2 bg-gray-100 rounded",
+///         ("bg-blue-500 text-white", move || active.get()),
+///         extra_class,
+///     ]>
+///         "Toggle"
+///     </button>
+/// }
+/// ```
+///
+/// When the list is reactive, only the classes that changed are added to or removed from the
+/// element, so that classes added with `class:name=` or by other code are left alone.
+#[macro_export]
+macro_rules! class_list {
+    ($($segment:expr),* $(,)?) => {
+        $crate::html::class_list::ClassList::new()
+            $(.push($segment))*
+    };
+}
+
+/// Something that adds classes to a [`ClassList`].
+///
+/// This is implemented for strings, for `Option`s of segments, for `(segment, condition)`
+/// pairs, where the condition is a `bool` or a function that returns one, and for functions
+/// that return a segment, which are run again whenever the signals they read change.
+pub trait ClassSegment: Send + Sync + 'static {
+    /// Appends the classes of this segment to `classes`, separated by whitespace.
+    fn push_classes(&self, classes: &mut String);
+}
+
+impl ClassSegment for &'static str {
+    fn push_classes(&self, classes: &mut String) {
+        classes.push(' ');
+        classes.push_str(self);
+    }
+}
+
+impl ClassSegment for String {
+    fn push_classes(&self, classes: &mut String) {
+        classes.push(' ');
+        classes.push_str(self);
+    }
+}
+
+impl ClassSegment for Arc<str> {
+    fn push_classes(&self, classes: &mut String) {
+        classes.push(' ');
+        classes.push_str(self);
+    }
+}
+
+impl ClassSegment for Cow<'static, str> {
+    fn push_classes(&self, classes: &mut String) {
+        classes.push(' ');
+        classes.push_str(self);
+    }
+}
+
+impl<T: ClassSegment> ClassSegment for Option<T> {
+    fn push_classes(&self, classes: &mut String) {
+        if let Some(segment) = self {
+            segment.push_classes(classes);
+        }
+    }
+}
+
+impl<T: ClassSegment> ClassSegment for (T, bool) {
+    fn push_classes(&self, classes: &mut String) {
+        if self.1 {
+            self.0.push_classes(classes);
+        }
+    }
+}
+
+impl<T, F> ClassSegment for (T, F)
+where
+    T: ClassSegment,
+    F: Fn() -> bool + Send + Sync + 'static,
+{
+    fn push_classes(&self, classes: &mut String) {
+        if (self.1)() {
+            self.0.push_classes(classes);
+        }
+    }
+}
+
+impl<F, T> ClassSegment for F
+where
+    F: Fn() -> T + Send + Sync + 'static,
+    T: ClassSegment,
+{
+    fn push_classes(&self, classes: &mut String) {
+        self().push_classes(classes);
+    }
+}
+
+impl ClassSegment for ClassList {
+    fn push_classes(&self, classes: &mut String) {
+        for segment in self.segments.iter() {
+            segment.push_classes(classes);
+        }
+    }
+}
+
+/// A list of class segments, whose classes are merged with last-wins conflict resolution for
+/// utility classes. Usually created with [`class_list!`](crate::class_list).
+#[derive(Clone, Default)]
+pub struct ClassList {
+    segments: Vec<Arc<dyn ClassSegment>>,
+}
+
+impl fmt::Debug for ClassList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ClassList").field(&self.to_merged()).finish()
+    }
+}
+
+impl ClassList {
+    /// Creates an empty list.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a segment to the end of the list. Its classes win over those of earlier segments.
+    pub fn push(mut self, segment: impl ClassSegment) -> Self {
+        self.segments.push(Arc::new(segment));
+        self
+    }
+
+    /// Runs every segment, and merges their classes.
+    pub fn to_merged(&self) -> MergedClasses {
+        let mut classes = String::new();
+        self.push_classes(&mut classes);
+        MergedClasses::new(&classes)
+    }
+}
+
+/// A set of classes from which conflicting utility classes have been removed.
+///
+/// Unlike a `String`, it updates the `classList` of the element rather than its `class`
+/// attribute, adding and removing only the classes that changed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MergedClasses(Vec<String>);
+
+impl MergedClasses {
+    /// Merges the whitespace-separated `classes`; see [`merge_classes`].
+    pub fn new(classes: &str) -> Self {
+        Self(merge_tokens(classes))
+    }
+
+    /// The classes, in the order they are rendered.
+    pub fn classes(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(String::as_str)
+    }
+}
+
+impl fmt::Display for MergedClasses {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0.join(" "))
+    }
+}
+
+impl IntoClass for MergedClasses {
+    type AsyncOutput = Self;
+    type State = (crate::renderer::types::ClassList, Vec<String>);
+    type Cloneable = Self;
+    type CloneableOwned = Self;
+
+    fn html_len(&self) -> usize {
+        self.0.iter().map(|class| class.len() + 1).sum()
+    }
+
+    fn to_html(self, class: &mut String) {
+        class.push_str(&self.0.join(" "));
+    }
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        el: &crate::renderer::types::Element,
+    ) -> Self::State {
+        let list = Rndr::class_list(el);
+        if !FROM_SERVER {
+            for class in &self.0 {
+                Rndr::add_class(&list, class);
+            }
+        }
+        (list, self.0)
+    }
+
+    fn build(self, el: &crate::renderer::types::Element) -> Self::State {
+        let list = Rndr::class_list(el);
+        for class in &self.0 {
+            Rndr::add_class(&list, class);
+        }
+        (list, self.0)
+    }
+
+    fn rebuild(self, state: &mut Self::State) {
+        let (list, prev) = state;
+        if *prev == self.0 {
+            return;
+        }
+        for class in prev.iter() {
+            if !self.0.contains(class) {
+                Rndr::remove_class(list, class);
+            }
+        }
+        for class in &self.0 {
+            if !prev.contains(class) {
+                Rndr::add_class(list, class);
+            }
+        }
+        *prev = self.0;
+    }
+
+    fn into_cloneable(self) -> Self::Cloneable {
+        self
+    }
+
+    fn into_cloneable_owned(self) -> Self::CloneableOwned {
+        self
+    }
+
+    fn dry_resolve(&mut self) {}
+
+    async fn resolve(self) -> Self::AsyncOutput {
+        self
+    }
+
+    fn reset(state: &mut Self::State) {
+        let (list, prev) = state;
+        for class in prev.drain(..) {
+            Rndr::remove_class(list, &class);
+        }
+    }
+}
+
+/// Merges the whitespace-separated `classes`, keeping only the last of any utility classes
+/// that set the same CSS property with the same variants, as well as the last of any
+/// duplicates.
+///
+/// Utility classes are recognized by the names of the default Tailwind CSS utilities. A class
+/// also overrides the classes of more specific utilities that it covers, so `p-4` removes an
+/// earlier `px-2`, but `px-2` is kept after `p-4`. Classes that are not recognized are kept.
+///
+/// ```
+/// # use tachys::html::class_list::merge_classes;
+/// assert_eq!(
+///     merge_classes("px-2 py-1 bg-red-500 hover:bg-red-600 p-3 bg-blue-500"),
+///     "hover:bg-red-600 p-3 bg-blue-500"
+/// );
+/// ```
+pub fn merge_classes(classes: &str) -> String {
+    merge_tokens(classes).join(" ")
+}
+
+fn merge_tokens(classes: &str) -> Vec<String> {
+    let mut seen = HashSet::new();
+    let mut merged = Vec::new();
+    // the classes are read from last to first, so that a class can be dropped as soon as a
+    // later one has taken its place
+    for class in classes.split_whitespace().rev() {
+        let Some(utility) = Utility::parse(class) else {
+            if seen.insert(format!("={class}")) {
+                merged.push(class.to_string());
+            }
+            continue;
+        };
+        if !seen.insert(utility.key(&utility.group)) {
+            continue;
+        }
+        for group in conflicting_groups(&utility.group) {
+            seen.insert(utility.key(group));
+        }
+        merged.push(class.to_string());
+    }
+    merged.reverse();
+    merged
+}
+
+/// A utility class split into its variants, like `md:hover:`, and the property it sets.
+struct Utility {
+    variants: String,
+    important: bool,
+    group: Cow<'static, str>,
+}
+
+impl Utility {
+    fn parse(class: &str) -> Option<Self> {
+        let mut variants = split_variants(class);
+        let base = variants.pop()?;
+        let (base, important) = match base.strip_prefix('!') {
+            Some(base) => (base, true),
+            None => match base.strip_suffix('!') {
+                Some(base) => (base, true),
+                None => (base, false),
+            },
+        };
+        let base = base.strip_prefix('-').unwrap_or(base);
+        let group = utility_group(base)?;
+        // the order of most variants does not matter, except for arbitrary ones like `[&>*]:`
+        let mut sorted = Vec::with_capacity(variants.len());
+        let mut run = Vec::new();
+        for variant in variants {
+            if variant.starts_with('[') {
+                run.sort_unstable();
+                sorted.append(&mut run);
+                sorted.push(variant);
+            } else {
+                run.push(variant);
+            }
+        }
+        run.sort_unstable();
+        sorted.append(&mut run);
+        Some(Self {
+            variants: sorted.join(":"),
+            important,
+            group,
+        })
+    }
+
+    fn key(&self, group: &str) -> String {
+        format!(
+            "{}:{}{}",
+            self.variants,
+            if self.important { "!" } else { "" },
+            group
+        )
+    }
+}
+
+/// Splits a class at the colons that are not inside brackets.
+fn split_variants(class: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (idx, c) in class.char_indices() {
+        match c {
+            '[' | '(' => depth += 1,
+            ']' | ')' => depth = depth.saturating_sub(1),
+            ':' if depth == 0 => {
+                parts.push(&class[start..idx]);
+                start = idx + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&class[start..]);
+    parts
+}
+
+/// Utilities that take no value, and the property they set.
+const KEYWORDS: &[(&str, &str)] = &[
+    ("block", "display"),
+    ("inline-block", "display"),
+    ("inline", "display"),
+    ("flex", "display"),
+    ("inline-flex", "display"),
+    ("table", "display"),
+    ("inline-table", "display"),
+    ("table-caption", "display"),
+    ("table-cell", "display"),
+    ("table-column", "display"),
+    ("table-column-group", "display"),
+    ("table-footer-group", "display"),
+    ("table-header-group", "display"),
+    ("table-row-group", "display"),
+    ("table-row", "display"),
+    ("flow-root", "display"),
+    ("grid", "display"),
+    ("inline-grid", "display"),
+    ("contents", "display"),
+    ("list-item", "display"),
+    ("hidden", "display"),
+    ("static", "position"),
+    ("fixed", "position"),
+    ("absolute", "position"),
+    ("relative", "position"),
+    ("sticky", "position"),
+    ("visible", "visibility"),
+    ("invisible", "visibility"),
+    ("collapse", "visibility"),
+    ("italic", "font-style"),
+    ("not-italic", "font-style"),
+    ("uppercase", "text-transform"),
+    ("lowercase", "text-transform"),
+    ("capitalize", "text-transform"),
+    ("normal-case", "text-transform"),
+    ("underline", "text-decoration"),
+    ("overline", "text-decoration"),
+    ("line-through", "text-decoration"),
+    ("no-underline", "text-decoration"),
+    ("truncate", "text-overflow"),
+    ("text-ellipsis", "text-overflow"),
+    ("text-clip", "text-overflow"),
+    ("antialiased", "font-smoothing"),
+    ("subpixel-antialiased", "font-smoothing"),
+    ("isolate", "isolation"),
+    ("isolation-auto", "isolation"),
+    ("sr-only", "sr"),
+    ("not-sr-only", "sr"),
+    ("grow", "grow"),
+    ("shrink", "shrink"),
+    ("border", "border-w"),
+    ("rounded", "rounded"),
+    ("shadow", "shadow"),
+    ("ring", "ring-w"),
+    ("outline", "outline-style"),
+    ("transition", "transition"),
+    ("blur", "blur"),
+    ("grayscale", "grayscale"),
+    ("invert", "invert"),
+    ("sepia", "sepia"),
+];
+
+/// Utilities whose property does not depend on their value.
+const PREFIXES: &[&str] = &[
+    "p",
+    "px",
+    "py",
+    "pt",
+    "pr",
+    "pb",
+    "pl",
+    "ps",
+    "pe",
+    "m",
+    "mx",
+    "my",
+    "mt",
+    "mr",
+    "mb",
+    "ml",
+    "ms",
+    "me",
+    "gap",
+    "gap-x",
+    "gap-y",
+    "space-x",
+    "space-y",
+    "inset",
+    "inset-x",
+    "inset-y",
+    "top",
+    "right",
+    "bottom",
+    "left",
+    "start",
+    "end",
+    "z",
+    "w",
+    "h",
+    "size",
+    "min-w",
+    "min-h",
+    "max-w",
+    "max-h",
+    "basis",
+    "grow",
+    "shrink",
+    "order",
+    "opacity",
+    "leading",
+    "tracking",
+    "cursor",
+    "overflow",
+    "overflow-x",
+    "overflow-y",
+    "overscroll",
+    "grid-cols",
+    "grid-rows",
+    "col-span",
+    "col-start",
+    "col-end",
+    "row-span",
+    "row-start",
+    "row-end",
+    "grid-flow",
+    "auto-cols",
+    "auto-rows",
+    "aspect",
+    "columns",
+    "duration",
+    "delay",
+    "ease",
+    "animate",
+    "transition",
+    "items",
+    "self",
+    "content",
+    "justify-items",
+    "justify-self",
+    "place-content",
+    "place-items",
+    "place-self",
+    "float",
+    "clear",
+    "whitespace",
+    "break",
+    "select",
+    "resize",
+    "pointer-events",
+    "list",
+    "align",
+    "line-clamp",
+    "scale",
+    "scale-x",
+    "scale-y",
+    "rotate",
+    "translate-x",
+    "translate-y",
+    "skew-x",
+    "skew-y",
+    "origin",
+    "blur",
+    "brightness",
+    "contrast",
+    "grayscale",
+    "invert",
+    "saturate",
+    "sepia",
+    "hue-rotate",
+    "drop-shadow",
+    "backdrop-blur",
+    "underline-offset",
+    "indent",
+    "accent",
+    "caret",
+    "fill",
+    "mix-blend",
+    "bg-blend",
+];
+
+/// The sides of `border-*` and `rounded-*` utilities.
+const SIDES: &[&str] = &[
+    "x", "y", "t", "r", "b", "l", "s", "e", "tl", "tr", "br", "bl", "ss", "se",
+    "ee", "es",
+];
+
+/// Finds the property that the utility class `base` sets, if it is one.
+fn utility_group(base: &str) -> Option<Cow<'static, str>> {
+    if let Some((_, group)) = KEYWORDS.iter().find(|(name, _)| *name == base) {
+        return Some(Cow::Borrowed(group));
+    }
+    // an arbitrary property, like `[mask-type:luminance]`
+    if let Some(property) = base
+        .strip_prefix('[')
+        .and_then(|base| base.strip_suffix(']'))
+        .and_then(|base| base.split_once(':'))
+        .map(|(property, _)| property)
+    {
+        return Some(Cow::Owned(format!("[{property}]")));
+    }
+    // the longest prefix wins, so that `gap-x-2` is not read as `gap` with a value of `x-2`
+    for (idx, _) in base.rmatch_indices('-') {
+        let (prefix, value) = (&base[..idx], &base[idx + 1..]);
+        if let Some(group) = value_group(prefix, value) {
+            return Some(group);
+        }
+    }
+    None
+}
+
+/// Finds the property set by a utility with the given prefix and value.
+fn value_group(prefix: &str, value: &str) -> Option<Cow<'static, str>> {
+    let group = match prefix {
+        "text" => match value {
+            "left" | "center" | "right" | "justify" | "start" | "end" => {
+                "text-align"
+            }
+            "wrap" | "nowrap" | "balance" | "pretty" => "text-wrap",
+            _ if is_size(value) || is_arbitrary_length(value) => "text-size",
+            _ => "text-color",
+        },
+        "font" => match value {
+            "thin" | "extralight" | "light" | "normal" | "medium"
+            | "semibold" | "bold" | "extrabold" | "black" => "font-weight",
+            _ if is_number(value) => "font-weight",
+            _ => "font-family",
+        },
+        "bg" => match value {
+            "fixed" | "local" | "scroll" => "bg-attachment",
+            "auto" | "cover" | "contain" => "bg-size",
+            "repeat" | "no-repeat" | "repeat-x" | "repeat-y"
+            | "repeat-round" | "repeat-space" => "bg-repeat",
+            "bottom" | "center" | "left" | "left-bottom" | "left-top"
+            | "right" | "right-bottom" | "right-top" | "top" => "bg-position",
+            "none" => "bg-image",
+            _ if value.starts_with("gradient-")
+                || value.starts_with("[url(")
+                || value.starts_with("[image:") =>
+            {
+                "bg-image"
+            }
+            _ if value.starts_with("clip-") => "bg-clip",
+            _ if value.starts_with("origin-") => "bg-origin",
+            _ => "bg-color",
+        },
+        "border" => {
+            if let Some(style) = border_style(value) {
+                style
+            } else if matches!(value, "collapse" | "separate") {
+                "border-collapse"
+            } else if let Some(side) = SIDES.iter().find(|side| **side == value)
+            {
+                return Some(Cow::Owned(format!("border-w-{side}")));
+            } else if let Some((side, value)) = value
+                .split_once('-')
+                .filter(|(side, _)| SIDES.contains(side))
+            {
+                let kind = if is_length(value) { "w" } else { "color" };
+                return Some(Cow::Owned(format!("border-{kind}-{side}")));
+            } else if is_length(value) {
+                "border-w"
+            } else {
+                "border-color"
+            }
+        }
+        "rounded" => {
+            if let Some(side) = SIDES.iter().find(|side| **side == value) {
+                return Some(Cow::Owned(format!("rounded-{side}")));
+            }
+            match value.split_once('-') {
+                Some((side, _)) if SIDES.contains(&side) => {
+                    return Some(Cow::Owned(format!("rounded-{side}")));
+                }
+                _ => "rounded",
+            }
+        }
+        "shadow" => {
+            if is_size(value) || matches!(value, "inner" | "none") {
+                "shadow"
+            } else {
+                "shadow-color"
+            }
+        }
+        "ring" => {
+            if value == "inset" {
+                "ring-inset"
+            } else if is_length(value) {
+                "ring-w"
+            } else {
+                "ring-color"
+            }
+        }
+        "ring-offset" => {
+            if is_length(value) {
+                "ring-offset-w"
+            } else {
+                "ring-offset-color"
+            }
+        }
+        "outline" => {
+            if let Some(style) = border_style(value) {
+                if style == "border-style" {
+                    "outline-style"
+                } else {
+                    "outline-collapse"
+                }
+            } else if is_length(value) {
+                "outline-w"
+            } else {
+                "outline-color"
+            }
+        }
+        "outline-offset" => "outline-offset",
+        "decoration" => match value {
+            "solid" | "double" | "dotted" | "dashed" | "wavy" => {
+                "decoration-style"
+            }
+            _ if is_length(value)
+                || value == "auto"
+                || value == "from-font" =>
+            {
+                "decoration-thickness"
+            }
+            _ => "decoration-color",
+        },
+        "stroke" => {
+            if is_length(value) {
+                "stroke-w"
+            } else {
+                "stroke"
+            }
+        }
+        "divide" => {
+            if let Some(style) = border_style(value) {
+                if style == "border-style" {
+                    "divide-style"
+                } else {
+                    return None;
+                }
+            } else {
+                "divide-color"
+            }
+        }
+        "divide-x" | "divide-y" => {
+            return Some(Cow::Owned(format!("{prefix}-w")));
+        }
+        "flex" => match value {
+            "row" | "row-reverse" | "col" | "col-reverse" => "flex-direction",
+            "wrap" | "nowrap" | "wrap-reverse" => "flex-wrap",
+            _ => "flex",
+        },
+        "flex-grow" => "grow",
+        "flex-shrink" => "shrink",
+        "justify" => "justify-content",
+        "object" => match value {
+            "contain" | "cover" | "fill" | "none" | "scale-down" => {
+                "object-fit"
+            }
+            _ => "object-position",
+        },
+        "box" => match value {
+            "border" | "content" => "box-sizing",
+            "decoration-clone" | "decoration-slice" => "box-decoration",
+            _ => return None,
+        },
+        _ => {
+            let prefix = PREFIXES.iter().find(|known| **known == prefix)?;
+            return Some(Cow::Borrowed(prefix));
+        }
+    };
+    Some(Cow::Borrowed(group))
+}
+
+fn border_style(value: &str) -> Option<&'static str> {
+    match value {
+        "solid" | "dashed" | "dotted" | "double" | "hidden" | "none" => {
+            Some("border-style")
+        }
+        _ => None,
+    }
+}
+
+/// The classes of more specific utilities that setting `group` overrides.
+fn conflicting_groups(group: &str) -> &'static [&'static str] {
+    match group {
+        "p" => &["px", "py", "pt", "pr", "pb", "pl", "ps", "pe"],
+        "px" => &["pr", "pl"],
+        "py" => &["pt", "pb"],
+        "m" => &["mx", "my", "mt", "mr", "mb", "ml", "ms", "me"],
+        "mx" => &["mr", "ml"],
+        "my" => &["mt", "mb"],
+        "gap" => &["gap-x", "gap-y"],
+        "inset" => &[
+            "inset-x", "inset-y", "top", "right", "bottom", "left", "start",
+            "end",
+        ],
+        "inset-x" => &["right", "left"],
+        "inset-y" => &["top", "bottom"],
+        "size" => &["w", "h"],
+        "overflow" => &["overflow-x", "overflow-y"],
+        "flex" => &["basis", "grow", "shrink"],
+        "text-size" => &["leading"],
+        "scale" => &["scale-x", "scale-y"],
+        "border-w" => &[
+            "border-w-x",
+            "border-w-y",
+            "border-w-t",
+            "border-w-r",
+            "border-w-b",
+            "border-w-l",
+            "border-w-s",
+            "border-w-e",
+        ],
+        "border-w-x" => &["border-w-r", "border-w-l"],
+        "border-w-y" => &["border-w-t", "border-w-b"],
+        "border-color" => &[
+            "border-color-x",
+            "border-color-y",
+            "border-color-t",
+            "border-color-r",
+            "border-color-b",
+            "border-color-l",
+            "border-color-s",
+            "border-color-e",
+        ],
+        "border-color-x" => &["border-color-r", "border-color-l"],
+        "border-color-y" => &["border-color-t", "border-color-b"],
+        "rounded" => &[
+            "rounded-s",
+            "rounded-e",
+            "rounded-t",
+            "rounded-r",
+            "rounded-b",
+            "rounded-l",
+            "rounded-ss",
+            "rounded-se",
+            "rounded-ee",
+            "rounded-es",
+            "rounded-tl",
+            "rounded-tr",
+            "rounded-br",
+            "rounded-bl",
+        ],
+        "rounded-t" => &["rounded-tl", "rounded-tr"],
+        "rounded-r" => &["rounded-tr", "rounded-br"],
+        "rounded-b" => &["rounded-br", "rounded-bl"],
+        "rounded-l" => &["rounded-tl", "rounded-bl"],
+        "rounded-s" => &["rounded-ss", "rounded-es"],
+        "rounded-e" => &["rounded-se", "rounded-ee"],
+        _ => &[],
+    }
+}
+
+fn is_arbitrary(value: &str) -> bool {
+    value.starts_with('[') && value.ends_with(']')
+}
+
+fn is_arbitrary_length(value: &str) -> bool {
+    is_arbitrary(value) && {
+        let inner = &value[1..value.len() - 1];
+        inner.starts_with("length:")
+            || inner.starts_with("calc(")
+            || inner.starts_with(|c: char| c.is_ascii_digit() || c == '.')
+    }
+}
+
+fn is_number(value: &str) -> bool {
+    !value.is_empty() && value.parse::<f64>().is_ok()
+}
+
+fn is_length(value: &str) -> bool {
+    is_number(value) || value == "px" || is_arbitrary_length(value)
+}
+
+/// Whether `value` is a size like `sm`, `base` or `2xl`.
+fn is_size(value: &str) -> bool {
+    matches!(value, "xs" | "sm" | "base" | "md" | "lg" | "xl")
+        || value
+            .strip_suffix("xl")
+            .is_some_and(|count| count.parse::<u8>().is_ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{merge_classes, ClassList, MergedClasses};
+
+    #[test]
+    fn keeps_the_last_of_conflicting_utilities() {
+        assert_eq!(merge_classes("p-2 p-4"), "p-4");
+        assert_eq!(
+            merge_classes("bg-red-500 text-sm bg-blue-500"),
+            "text-sm bg-blue-500"
+        );
+        assert_eq!(merge_classes("block flex"), "flex");
+        assert_eq!(merge_classes("w-[calc(100%-2rem)] w-full"), "w-full");
+        assert_eq!(merge_classes("-mt-2 mt-4"), "mt-4");
+    }
+
+    #[test]
+    fn keeps_utilities_that_set_different_properties() {
+        assert_eq!(
+            merge_classes("text-lg text-red-500 text-center"),
+            "text-lg text-red-500 text-center"
+        );
+        assert_eq!(
+            merge_classes("border border-red-500 border-dashed"),
+            "border border-red-500 border-dashed"
+        );
+        assert_eq!(merge_classes("font-bold font-mono"), "font-bold font-mono");
+        assert_eq!(
+            merge_classes("shadow-lg shadow-red-500"),
+            "shadow-lg shadow-red-500"
+        );
+        assert_eq!(merge_classes("gap-2 gap-x-4"), "gap-2 gap-x-4");
+    }
+
+    #[test]
+    fn shorthands_override_earlier_longhands_only() {
+        assert_eq!(merge_classes("px-2 pt-1 p-4"), "p-4");
+        assert_eq!(merge_classes("p-4 px-2"), "p-4 px-2");
+        assert_eq!(merge_classes("rounded-tl-lg rounded-none"), "rounded-none");
+        assert_eq!(merge_classes("border-t-2 border-0"), "border-0");
+        assert_eq!(merge_classes("leading-6 text-lg"), "text-lg");
+    }
+
+    #[test]
+    fn variants_and_important_are_separate() {
+        assert_eq!(
+            merge_classes("hover:bg-red-500 bg-blue-500 hover:bg-green-500"),
+            "bg-blue-500 hover:bg-green-500"
+        );
+        assert_eq!(merge_classes("md:hover:p-2 hover:md:p-4"), "hover:md:p-4");
+        assert_eq!(merge_classes("!p-2 p-4"), "!p-2 p-4");
+        assert_eq!(merge_classes("[&>*]:p-2 [&>*]:p-4"), "[&>*]:p-4");
+        assert_eq!(
+            merge_classes("[mask-type:luminance] [mask-type:alpha]"),
+            "[mask-type:alpha]"
+        );
+    }
+
+    #[test]
+    fn keeps_unknown_classes_once() {
+        assert_eq!(merge_classes("card foo-bar card p-2"), "foo-bar card p-2");
+        assert_eq!(merge_classes("  "), "");
+    }
+
+    #[test]
+    fn evaluates_conditional_segments() {
+        let list = ClassList::new()
+            .push("px-2 bg-gray-100")
+            .push(("bg-blue-500", true))
+            .push(("hidden", || false))
+            .push(Some(String::from("p-4")))
+            .push(None::<&'static str>)
+            .push(|| "rounded");
+        assert_eq!(
+            list.to_merged(),
+            MergedClasses::new("bg-blue-500 p-4 rounded")
+        );
+    }
+}
@@ -0,0 +1,567 @@
+//! Typed values for individual CSS properties.
+//!
+//! These can be used anywhere an [`IntoStyleValue`] is expected, for example as the value of a
+//! `style:` binding in the `view` macro. Rather than formatting strings by hand, you can write
+//! `style:width=px(120)`, `style:color=rgb(255, 0, 0)`, or
+//! `style:transform=Transform::new().translate_x(px(10)).rotate(deg(45))`.
+//!
+//! In `leptos`, these are available from `leptos::css`, rather than from the prelude, because
+//! their short names would otherwise clash with those in user code.
+//!
+//! Each value keeps its typed form in the view state, so rebuilding only touches the DOM when
+//! the value has actually changed, and server rendering writes the value directly into the
+//! `style` attribute without an intermediate allocation.
+
+use super::style::IntoStyleValue;
+use crate::renderer::{dom::CssStyleDeclaration, Rndr};
+use std::{
+    fmt::{self, Display, Write},
+    ops::{Div, Mul, Neg},
+};
+
+/// A unit of length in CSS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LengthUnit {
+    /// Pixels (`px`).
+    Px,
+    /// Relative to the font size of the element (`em`).
+    Em,
+    /// Relative to the font size of the root element (`rem`).
+    Rem,
+    /// Percentage of the containing block (`%`).
+    Percent,
+    /// Percentage of the viewport width (`vw`).
+    Vw,
+    /// Percentage of the viewport height (`vh`).
+    Vh,
+    /// Percentage of the smaller viewport dimension (`vmin`).
+    Vmin,
+    /// Percentage of the larger viewport dimension (`vmax`).
+    Vmax,
+    /// Width of the `0` glyph (`ch`).
+    Ch,
+    /// Points (`pt`).
+    Pt,
+}
+
+impl LengthUnit {
+    /// The suffix used for this unit in CSS.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            LengthUnit::Px => "px",
+            LengthUnit::Em => "em",
+            LengthUnit::Rem => "rem",
+            LengthUnit::Percent => "%",
+            LengthUnit::Vw => "vw",
+            LengthUnit::Vh => "vh",
+            LengthUnit::Vmin => "vmin",
+            LengthUnit::Vmax => "vmax",
+            LengthUnit::Ch => "ch",
+            LengthUnit::Pt => "pt",
+        }
+    }
+}
+
+/// A CSS length, consisting of a number and a [`LengthUnit`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Length {
+    value: f64,
+    unit: LengthUnit,
+}
+
+impl Length {
+    /// Creates a new length.
+    pub fn new(value: impl Into<f64>, unit: LengthUnit) -> Self {
+        Self {
+            value: value.into(),
+            unit,
+        }
+    }
+
+    /// The numeric part of the length.
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// The unit of the length.
+    pub fn unit(&self) -> LengthUnit {
+        self.unit
+    }
+}
+
+impl Display for Length {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.value, self.unit.as_str())
+    }
+}
+
+impl Mul<f64> for Length {
+    type Output = Length;
+
+    fn mul(self, rhs: f64) -> Self::Output {
+        Length {
+            value: self.value * rhs,
+            unit: self.unit,
+        }
+    }
+}
+
+impl Div<f64> for Length {
+    type Output = Length;
+
+    fn div(self, rhs: f64) -> Self::Output {
+        Length {
+            value: self.value / rhs,
+            unit: self.unit,
+        }
+    }
+}
+
+impl Neg for Length {
+    type Output = Length;
+
+    fn neg(self) -> Self::Output {
+        Length {
+            value: -self.value,
+            unit: self.unit,
+        }
+    }
+}
+
+/// A length in pixels.
+pub fn px(value: impl Into<f64>) -> Length {
+    Length::new(value, LengthUnit::Px)
+}
+
+/// A length relative to the element's font size.
+pub fn em(value: impl Into<f64>) -> Length {
+    Length::new(value, LengthUnit::Em)
+}
+
+/// A length relative to the root element's font size.
+pub fn rem(value: impl Into<f64>) -> Length {
+    Length::new(value, LengthUnit::Rem)
+}
+
+/// A percentage of the containing block.
+pub fn pct(value: impl Into<f64>) -> Length {
+    Length::new(value, LengthUnit::Percent)
+}
+
+/// A percentage of the viewport width.
+pub fn vw(value: impl Into<f64>) -> Length {
+    Length::new(value, LengthUnit::Vw)
+}
+
+/// A percentage of the viewport height.
+pub fn vh(value: impl Into<f64>) -> Length {
+    Length::new(value, LengthUnit::Vh)
+}
+
+/// A unit of angle in CSS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AngleUnit {
+    /// Degrees (`deg`).
+    Deg,
+    /// Radians (`rad`).
+    Rad,
+    /// Full turns (`turn`).
+    Turn,
+}
+
+impl AngleUnit {
+    /// The suffix used for this unit in CSS.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            AngleUnit::Deg => "deg",
+            AngleUnit::Rad => "rad",
+            AngleUnit::Turn => "turn",
+        }
+    }
+}
+
+/// A CSS angle, used in transforms and gradients.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Angle {
+    value: f64,
+    unit: AngleUnit,
+}
+
+impl Angle {
+    /// Creates a new angle.
+    pub fn new(value: impl Into<f64>, unit: AngleUnit) -> Self {
+        Self {
+            value: value.into(),
+            unit,
+        }
+    }
+}
+
+impl Display for Angle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.value, self.unit.as_str())
+    }
+}
+
+/// An angle in degrees.
+pub fn deg(value: impl Into<f64>) -> Angle {
+    Angle::new(value, AngleUnit::Deg)
+}
+
+/// An angle in radians.
+pub fn rad(value: impl Into<f64>) -> Angle {
+    Angle::new(value, AngleUnit::Rad)
+}
+
+/// An angle in full turns.
+pub fn turn(value: impl Into<f64>) -> Angle {
+    Angle::new(value, AngleUnit::Turn)
+}
+
+/// A CSS color.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Color {
+    /// An opaque color given by its red, green, and blue channels.
+    Rgb(u8, u8, u8),
+    /// A color given by its red, green, and blue channels, and an alpha value between `0.0` and
+    /// `1.0`.
+    Rgba(u8, u8, u8, f32),
+    /// A color given by its hue (in degrees), saturation and lightness (as percentages).
+    Hsl(f32, f32, f32),
+    /// The value of the element's `color` property (`currentcolor`).
+    CurrentColor,
+    /// A fully-transparent color (`transparent`).
+    Transparent,
+}
+
+impl Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Color::Rgb(r, g, b) => write!(f, "#{r:02x}{g:02x}{b:02x}"),
+            Color::Rgba(r, g, b, a) => write!(f, "rgba({r}, {g}, {b}, {a})"),
+            Color::Hsl(h, s, l) => write!(f, "hsl({h}, {s}%, {l}%)"),
+            Color::CurrentColor => f.write_str("currentcolor"),
+            Color::Transparent => f.write_str("transparent"),
+        }
+    }
+}
+
+/// An opaque color from its red, green, and blue channels.
+pub fn rgb(r: u8, g: u8, b: u8) -> Color {
+    Color::Rgb(r, g, b)
+}
+
+/// A color from its red, green, and blue channels and an alpha value between `0.0` and `1.0`.
+pub fn rgba(r: u8, g: u8, b: u8, a: f32) -> Color {
+    Color::Rgba(r, g, b, a.clamp(0.0, 1.0))
+}
+
+/// A color from its hue (in degrees), saturation and lightness (as percentages).
+pub fn hsl(h: f32, s: f32, l: f32) -> Color {
+    Color::Hsl(h, s.clamp(0.0, 100.0), l.clamp(0.0, 100.0))
+}
+
+/// An opaque color from a hexadecimal `0xRRGGBB` value.
+pub fn hex(value: u32) -> Color {
+    Color::Rgb(
+        ((value >> 16) & 0xff) as u8,
+        ((value >> 8) & 0xff) as u8,
+        (value & 0xff) as u8,
+    )
+}
+
+/// A single function in a CSS [`Transform`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TransformFunction {
+    /// `translate(x, y)`
+    Translate(Length, Length),
+    /// `translateX(x)`
+    TranslateX(Length),
+    /// `translateY(y)`
+    TranslateY(Length),
+    /// `rotate(angle)`
+    Rotate(Angle),
+    /// `scale(x, y)`
+    Scale(f64, f64),
+    /// `skewX(angle)`
+    SkewX(Angle),
+    /// `skewY(angle)`
+    SkewY(Angle),
+}
+
+impl Display for TransformFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransformFunction::Translate(x, y) => {
+                write!(f, "translate({x}, {y})")
+            }
+            TransformFunction::TranslateX(x) => write!(f, "translateX({x})"),
+            TransformFunction::TranslateY(y) => write!(f, "translateY({y})"),
+            TransformFunction::Rotate(a) => write!(f, "rotate({a})"),
+            TransformFunction::Scale(x, y) if x == y => {
+                write!(f, "scale({x})")
+            }
+            TransformFunction::Scale(x, y) => write!(f, "scale({x}, {y})"),
+            TransformFunction::SkewX(a) => write!(f, "skewX({a})"),
+            TransformFunction::SkewY(a) => write!(f, "skewY({a})"),
+        }
+    }
+}
+
+/// A list of CSS transform functions, applied in order.
+///
+/// An empty transform renders as `none`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Transform(Vec<TransformFunction>);
+
+impl Transform {
+    /// Creates an empty transform.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a transform function.
+    pub fn then(mut self, function: TransformFunction) -> Self {
+        self.0.push(function);
+        self
+    }
+
+    /// Appends `translate(x, y)`.
+    pub fn translate(self, x: Length, y: Length) -> Self {
+        self.then(TransformFunction::Translate(x, y))
+    }
+
+    /// Appends `translateX(x)`.
+    pub fn translate_x(self, x: Length) -> Self {
+        self.then(TransformFunction::TranslateX(x))
+    }
+
+    /// Appends `translateY(y)`.
+    pub fn translate_y(self, y: Length) -> Self {
+        self.then(TransformFunction::TranslateY(y))
+    }
+
+    /// Appends `rotate(angle)`.
+    pub fn rotate(self, angle: Angle) -> Self {
+        self.then(TransformFunction::Rotate(angle))
+    }
+
+    /// Appends a uniform `scale(factor)`.
+    pub fn scale(self, factor: impl Into<f64>) -> Self {
+        let factor = factor.into();
+        self.then(TransformFunction::Scale(factor, factor))
+    }
+
+    /// Appends `scale(x, y)`.
+    pub fn scale_xy(self, x: impl Into<f64>, y: impl Into<f64>) -> Self {
+        self.then(TransformFunction::Scale(x.into(), y.into()))
+    }
+
+    /// Appends `skewX(angle)`.
+    pub fn skew_x(self, angle: Angle) -> Self {
+        self.then(TransformFunction::SkewX(angle))
+    }
+
+    /// Appends `skewY(angle)`.
+    pub fn skew_y(self, angle: Angle) -> Self {
+        self.then(TransformFunction::SkewY(angle))
+    }
+}
+
+impl Display for Transform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.is_empty() {
+            return f.write_str("none");
+        }
+        for (idx, function) in self.0.iter().enumerate() {
+            if idx > 0 {
+                f.write_char(' ')?;
+            }
+            Display::fmt(function, f)?;
+        }
+        Ok(())
+    }
+}
+
+macro_rules! impl_typed_style_value {
+    ($ty:ty) => {
+        impl IntoStyleValue for $ty {
+            type AsyncOutput = Self;
+            type State = Self;
+            type Cloneable = Self;
+            type CloneableOwned = Self;
+
+            fn to_html(self, name: &str, style: &mut String) {
+                style.push_str(name);
+                style.push(':');
+                // writing to a String cannot fail
+                _ = write!(style, "{self}");
+                style.push(';');
+            }
+
+            fn build(
+                self,
+                style: &CssStyleDeclaration,
+                name: &str,
+            ) -> Self::State {
+                Rndr::set_css_property(style, name, &self.to_string());
+                self
+            }
+
+            fn rebuild(
+                self,
+                style: &CssStyleDeclaration,
+                name: &str,
+                state: &mut Self::State,
+            ) {
+                if self != *state {
+                    Rndr::set_css_property(style, name, &self.to_string());
+                }
+                *state = self;
+            }
+
+            fn hydrate(
+                self,
+                _style: &CssStyleDeclaration,
+                _name: &str,
+            ) -> Self::State {
+                self
+            }
+
+            fn into_cloneable(self) -> Self::Cloneable {
+                self
+            }
+
+            fn into_cloneable_owned(self) -> Self::CloneableOwned {
+                self
+            }
+
+            fn dry_resolve(&mut self) {}
+
+            async fn resolve(self) -> Self::AsyncOutput {
+                self
+            }
+        }
+
+        impl IntoStyleValue for Option<$ty> {
+            type AsyncOutput = Self;
+            type State = Self;
+            type Cloneable = Self;
+            type CloneableOwned = Self;
+
+            fn to_html(self, name: &str, style: &mut String) {
+                if let Some(value) = self {
+                    value.to_html(name, style);
+                }
+            }
+
+            fn build(
+                self,
+                style: &CssStyleDeclaration,
+                name: &str,
+            ) -> Self::State {
+                if let Some(value) = &self {
+                    Rndr::set_css_property(style, name, &value.to_string());
+                }
+                self
+            }
+
+            fn rebuild(
+                self,
+                style: &CssStyleDeclaration,
+                name: &str,
+                state: &mut Self::State,
+            ) {
+                match (&state, &self) {
+                    (None, None) => {}
+                    (Some(_), None) => Rndr::remove_css_property(style, name),
+                    (None, Some(value)) => {
+                        Rndr::set_css_property(style, name, &value.to_string())
+                    }
+                    (Some(old), Some(new)) => {
+                        if new != old {
+                            Rndr::set_css_property(
+                                style,
+                                name,
+                                &new.to_string(),
+                            );
+                        }
+                    }
+                }
+                *state = self;
+            }
+
+            fn hydrate(
+                self,
+                _style: &CssStyleDeclaration,
+                _name: &str,
+            ) -> Self::State {
+                self
+            }
+
+            fn into_cloneable(self) -> Self::Cloneable {
+                self
+            }
+
+            fn into_cloneable_owned(self) -> Self::CloneableOwned {
+                self
+            }
+
+            fn dry_resolve(&mut self) {}
+
+            async fn resolve(self) -> Self::AsyncOutput {
+                self
+            }
+        }
+    };
+}
+
+impl_typed_style_value!(Length);
+impl_typed_style_value!(Angle);
+impl_typed_style_value!(Color);
+impl_typed_style_value!(Transform);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(name: &str, value: impl IntoStyleValue) -> String {
+        let mut style = String::new();
+        value.to_html(name, &mut style);
+        style
+    }
+
+    #[test]
+    fn renders_lengths_with_units() {
+        assert_eq!(render("width", px(120)), "width:120px;");
+        assert_eq!(render("width", pct(33.5)), "width:33.5%;");
+        assert_eq!(render("margin-left", -rem(2) * 1.5), "margin-left:-3rem;");
+        assert_eq!(render("height", None::<Length>), "");
+    }
+
+    #[test]
+    fn renders_colors() {
+        assert_eq!(render("color", hex(0xff8800)), "color:#ff8800;");
+        assert_eq!(
+            render("color", rgba(0, 0, 0, 2.0)),
+            "color:rgba(0, 0, 0, 1);"
+        );
+        assert_eq!(
+            render("color", hsl(120.0, 50.0, 25.0)),
+            "color:hsl(120, 50%, 25%);"
+        );
+    }
+
+    #[test]
+    fn renders_transforms_in_order() {
+        let transform = Transform::new()
+            .translate(px(10), em(-1))
+            .rotate(deg(45))
+            .scale(2);
+        assert_eq!(
+            render("transform", transform),
+            "transform:translate(10px, -1em) rotate(45deg) scale(2);"
+        );
+        assert_eq!(render("transform", Transform::new()), "transform:none;");
+    }
+}
@@ -9,7 +9,12 @@ use crate::{
     renderer::Rndr,
     view::add_attr::AddAnyAttr,
 };
-use std::{future::Future, sync::Arc};
+use or_poisoned::OrPoisoned;
+use std::{
+    borrow::Cow,
+    future::Future,
+    sync::{Arc, RwLock},
+};
 
 /// Returns an [`Attribute`] that sets the inner HTML of an element.
 ///
@@ -27,6 +32,24 @@ where
     InnerHtml { value }
 }
 
+/// Returns an [`Attribute`] that sets the inner HTML of an element, after passing it through
+/// the HTML sanitizer registered with [`set_html_sanitizer`].
+///
+/// The sanitizer runs both when rendering to HTML on the server and when setting the inner
+/// HTML in the browser, so the same function (or an equivalent one) should be registered on
+/// both sides.
+///
+/// No children should be given to this element, as this HTML will be used instead.
+#[inline(always)]
+pub fn inner_html_sanitized<T>(value: T) -> InnerHtml<Sanitized<T>>
+where
+    Sanitized<T>: InnerHtmlValue,
+{
+    InnerHtml {
+        value: Sanitized(value),
+    }
+}
+
 /// Sets the inner HTML of an element.
 #[derive(Debug)]
 pub struct InnerHtml<T> {
@@ -142,6 +165,20 @@ where
     ) -> <Self as AddAnyAttr>::Output<InnerHtml<T>> {
         self.add_any_attr(inner_html(value))
     }
+
+    /// Sets the inner HTML of this element, after passing it through the HTML sanitizer
+    /// registered with [`set_html_sanitizer`].
+    ///
+    /// No children should be given to this element, as this HTML will be used instead.
+    fn inner_html_sanitized(
+        self,
+        value: T,
+    ) -> <Self as AddAnyAttr>::Output<InnerHtml<Sanitized<T>>>
+    where
+        Sanitized<T>: InnerHtmlValue,
+    {
+        self.add_any_attr(inner_html_sanitized(value))
+    }
 }
 
 impl<T, E, At> InnerHtmlAttribute<T> for HtmlElement<E, At, ()>
@@ -157,6 +194,16 @@ where
     ) -> <Self as AddAnyAttr>::Output<InnerHtml<T>> {
         self.add_any_attr(inner_html(value))
     }
+
+    fn inner_html_sanitized(
+        self,
+        value: T,
+    ) -> <Self as AddAnyAttr>::Output<InnerHtml<Sanitized<T>>>
+    where
+        Sanitized<T>: InnerHtmlValue,
+    {
+        self.add_any_attr(inner_html_sanitized(value))
+    }
 }
 
 /// A possible value for [`InnerHtml`].
@@ -433,3 +480,242 @@ where
         self
     }
 }
+
+type HtmlSanitizer = dyn Fn(&str) -> String + Send + Sync;
+
+static HTML_SANITIZER: RwLock<Option<Arc<HtmlSanitizer>>> = RwLock::new(None);
+
+/// Registers the function used to sanitize HTML set with [`inner_html_sanitized`].
+///
+/// This is typically a wrapper around a library like `ammonia` on the server, or around a
+/// binding to a sanitizer like DOMPurify in the browser. Registering a new sanitizer replaces
+/// any previous one.
+///
+/// ```rust,ignore
+/// set_html_sanitizer(|html| ammonia::clean(html));
+/// ```
+pub fn set_html_sanitizer(
+    sanitizer: impl Fn(&str) -> String + Send + Sync + 'static,
+) {
+    *HTML_SANITIZER.write().or_poisoned() = Some(Arc::new(sanitizer));
+}
+
+/// Passes the HTML through the sanitizer registered with [`set_html_sanitizer`].
+///
+/// If no sanitizer has been registered, the HTML is escaped entirely, so that it will be
+/// displayed as text rather than interpreted as markup.
+pub fn sanitize_html(html: &str) -> Cow<'_, str> {
+    let sanitizer = HTML_SANITIZER.read().or_poisoned().clone();
+    match sanitizer {
+        Some(sanitizer) => Cow::Owned(sanitizer(html)),
+        None => html_escape::encode_text(html),
+    }
+}
+
+/// An [`InnerHtmlValue`] that will be passed through [`sanitize_html`] before being inserted.
+///
+/// This is usually created with [`inner_html_sanitized`].
+#[derive(Debug, Clone)]
+pub struct Sanitized<T>(pub T);
+
+/// A plain HTML value that can be passed through [`sanitize_html`].
+///
+/// This allows reactive values to be wrapped in [`Sanitized`].
+pub trait SanitizableHtml: Send {
+    /// Returns the sanitized HTML, or `None` if there is nothing to render.
+    fn sanitized(&self) -> Option<String>;
+}
+
+impl SanitizableHtml for String {
+    fn sanitized(&self) -> Option<String> {
+        Some(sanitize_html(self).into_owned())
+    }
+}
+
+impl SanitizableHtml for Arc<str> {
+    fn sanitized(&self) -> Option<String> {
+        Some(sanitize_html(self).into_owned())
+    }
+}
+
+impl SanitizableHtml for &str {
+    fn sanitized(&self) -> Option<String> {
+        Some(sanitize_html(self).into_owned())
+    }
+}
+
+impl<T> SanitizableHtml for Option<T>
+where
+    T: SanitizableHtml,
+{
+    fn sanitized(&self) -> Option<String> {
+        self.as_ref().and_then(SanitizableHtml::sanitized)
+    }
+}
+
+macro_rules! sanitized_inner_html {
+    ([$($gen:tt)*] $ty:ty, $cloneable:ty, $cloneable_owned:ty) => {
+        impl<$($gen)*> InnerHtmlValue for Sanitized<$ty> {
+            type AsyncOutput = Self;
+            type State = (crate::renderer::types::Element, $ty);
+            type Cloneable = Sanitized<$cloneable>;
+            type CloneableOwned = Sanitized<$cloneable_owned>;
+
+            fn html_len(&self) -> usize {
+                self.0.len()
+            }
+
+            fn to_html(self, buf: &mut String) {
+                buf.push_str(&sanitize_html(&self.0));
+            }
+
+            fn to_template(_buf: &mut String) {}
+
+            fn hydrate<const FROM_SERVER: bool>(
+                self,
+                el: &crate::renderer::types::Element,
+            ) -> Self::State {
+                if !FROM_SERVER {
+                    Rndr::set_inner_html(el, &sanitize_html(&self.0));
+                }
+                (el.clone(), self.0)
+            }
+
+            fn build(
+                self,
+                el: &crate::renderer::types::Element,
+            ) -> Self::State {
+                Rndr::set_inner_html(el, &sanitize_html(&self.0));
+                (el.clone(), self.0)
+            }
+
+            fn rebuild(self, state: &mut Self::State) {
+                if self.0 != state.1 {
+                    Rndr::set_inner_html(&state.0, &sanitize_html(&self.0));
+                    state.1 = self.0;
+                }
+            }
+
+            fn into_cloneable(self) -> Self::Cloneable {
+                Sanitized(self.0.into())
+            }
+
+            fn into_cloneable_owned(self) -> Self::CloneableOwned {
+                Sanitized(self.0.into())
+            }
+
+            fn dry_resolve(&mut self) {}
+
+            async fn resolve(self) -> Self::AsyncOutput {
+                self
+            }
+        }
+    };
+}
+
+sanitized_inner_html!([] String, Arc<str>, Arc<str>);
+sanitized_inner_html!([] Arc<str>, Arc<str>, Arc<str>);
+sanitized_inner_html!(['a] &'a str, &'a str, Arc<str>);
+
+macro_rules! sanitized_inner_html_option {
+    ([$($gen:tt)*] $ty:ty, $cloneable:ty, $cloneable_owned:ty) => {
+        impl<$($gen)*> InnerHtmlValue for Sanitized<Option<$ty>> {
+            type AsyncOutput = Self;
+            type State = (crate::renderer::types::Element, Option<$ty>);
+            type Cloneable = Sanitized<Option<$cloneable>>;
+            type CloneableOwned = Sanitized<Option<$cloneable_owned>>;
+
+            fn html_len(&self) -> usize {
+                self.0.as_ref().map(|value| value.len()).unwrap_or(0)
+            }
+
+            fn to_html(self, buf: &mut String) {
+                if let Some(value) = &self.0 {
+                    buf.push_str(&sanitize_html(value));
+                }
+            }
+
+            fn to_template(_buf: &mut String) {}
+
+            fn hydrate<const FROM_SERVER: bool>(
+                self,
+                el: &crate::renderer::types::Element,
+            ) -> Self::State {
+                if !FROM_SERVER {
+                    if let Some(value) = &self.0 {
+                        Rndr::set_inner_html(el, &sanitize_html(value));
+                    }
+                }
+                (el.clone(), self.0)
+            }
+
+            fn build(
+                self,
+                el: &crate::renderer::types::Element,
+            ) -> Self::State {
+                if let Some(value) = &self.0 {
+                    Rndr::set_inner_html(el, &sanitize_html(value));
+                }
+                (el.clone(), self.0)
+            }
+
+            fn rebuild(self, state: &mut Self::State) {
+                if self.0 != state.1 {
+                    match &self.0 {
+                        Some(value) => Rndr::set_inner_html(
+                            &state.0,
+                            &sanitize_html(value),
+                        ),
+                        None => Rndr::set_inner_html(&state.0, ""),
+                    }
+                    state.1 = self.0;
+                }
+            }
+
+            fn into_cloneable(self) -> Self::Cloneable {
+                Sanitized(self.0.map(Into::into))
+            }
+
+            fn into_cloneable_owned(self) -> Self::CloneableOwned {
+                Sanitized(self.0.map(Into::into))
+            }
+
+            fn dry_resolve(&mut self) {}
+
+            async fn resolve(self) -> Self::AsyncOutput {
+                self
+            }
+        }
+    };
+}
+
+sanitized_inner_html_option!([] String, Arc<str>, Arc<str>);
+sanitized_inner_html_option!([] Arc<str>, Arc<str>, Arc<str>);
+sanitized_inner_html_option!(['a] &'a str, &'a str, Arc<str>);
+
+#[cfg(test)]
+mod tests {
+    use super::{set_html_sanitizer, InnerHtmlValue, Sanitized};
+
+    #[test]
+    fn sanitizes_inner_html_when_rendering() {
+        let render = |value: Sanitized<Option<&str>>| {
+            let mut buf = String::new();
+            value.to_html(&mut buf);
+            buf
+        };
+
+        // without a sanitizer, the HTML is escaped as text
+        assert_eq!(
+            render(Sanitized(Some("<b>bold</b>"))),
+            "&lt;b&gt;bold&lt;/b&gt;"
+        );
+
+        set_html_sanitizer(|html| html.replace("<script>", ""));
+        assert_eq!(
+            render(Sanitized(Some("<b>bold</b><script>"))),
+            "<b>bold</b>"
+        );
+        assert_eq!(render(Sanitized(None)), "");
+    }
+}
@@ -597,13 +597,13 @@ where
     if !class.is_empty() {
         buf.push(' ');
         buf.push_str("class=\"");
-        buf.push_str(&escape_attr(class.trim_start().trim_end()));
+        buf.push_str(&escape_attr("class", class.trim_start().trim_end()));
         buf.push('"');
     }
     if !style.is_empty() {
         buf.push(' ');
         buf.push_str("style=\"");
-        buf.push_str(&escape_attr(style.trim_start().trim_end()));
+        buf.push_str(&escape_attr("style", style.trim_start().trim_end()));
         buf.push('"');
     }
 
@@ -14,7 +14,7 @@ use std::{
     ops::{Deref, DerefMut},
     rc::Rc,
 };
-use wasm_bindgen::convert::FromWasmAbi;
+use wasm_bindgen::{convert::FromWasmAbi, JsCast};
 
 /// A cloneable event callback.
 pub type SharedEventCallback<E> = Rc<RefCell<dyn FnMut(E)>>;
@@ -158,7 +158,30 @@ where
     E::EventType: 'static,
     E::EventType: From<crate::renderer::types::Event>,
 {
+    /// Wraps the callback in a listener that converts the event to its typed form.
+    fn handler(mut cb: F) -> Box<dyn FnMut(crate::renderer::types::Event)> {
+        #[cfg(feature = "tracing")]
+        let span = tracing::Span::current();
+
+        Box::new(move |ev: crate::renderer::types::Event| {
+            #[cfg(all(debug_assertions, feature = "reactive_graph"))]
+            let _rx_guard =
+                reactive_graph::diagnostics::SpecialNonReactiveZone::enter();
+            #[cfg(feature = "tracing")]
+            let _tracing_guard = span.enter();
+
+            let ev = E::EventType::from(ev);
+            cb.invoke(ev);
+        })
+    }
+
     /// Attaches the event listener to the element.
+    ///
+    /// The listener is delegated if the event can be [delegated](EventDescriptor::DELEGATED) and
+    /// the `delegation` feature is enabled, in which case the [`capture`], [`passive`], and
+    /// [`once`] wrappers are honored by the global listener that dispatches it. Otherwise, or if the event has custom
+    /// [`options`](EventDescriptor::options), which cannot be delegated, the listener is attached
+    /// directly to the element.
     pub fn attach(
         self,
         el: &crate::renderer::types::Element,
@@ -171,37 +194,47 @@ where
             // (rather than, say, having a const DELEGATED: bool)
             // add to binary size?
             delegation_key: Option<Cow<'static, str>>,
+            #[cfg_attr(not(feature = "delegation"), allow(unused_variables))]
+            flags: ListenerOptions,
+            options: Option<web_sys::AddEventListenerOptions>,
         ) -> RemoveEventHandler<crate::renderer::types::Element> {
-            match delegation_key {
-                None => Rndr::add_event_listener(el, &name, cb),
-                Some(key) => {
-                    Rndr::add_event_listener_delegated(el, name, key, cb)
+            match (options, delegation_key) {
+                // without the `delegation` feature, the delegation runtime is not compiled at all
+                #[cfg(feature = "delegation")]
+                (_, Some(key)) => {
+                    let cb = if flags.once { call_once(cb) } else { cb };
+                    Rndr::add_event_listener_delegated_with_options(
+                        el,
+                        name,
+                        flags.delegation_key(key),
+                        cb,
+                        flags.capture,
+                        flags.passive,
+                    )
                 }
+                (Some(options), _) => Rndr::add_event_listener_with_options(
+                    el, &name, cb, &options,
+                ),
+                (None, _) => Rndr::add_event_listener(el, &name, cb),
             }
         }
 
-        let mut cb = self.cb.expect("callback removed before attaching").take();
-
-        #[cfg(feature = "tracing")]
-        let span = tracing::Span::current();
-
-        let cb = Box::new(move |ev: crate::renderer::types::Event| {
-            #[cfg(all(debug_assertions, feature = "reactive_graph"))]
-            let _rx_guard =
-                reactive_graph::diagnostics::SpecialNonReactiveZone::enter();
-            #[cfg(feature = "tracing")]
-            let _tracing_guard = span.enter();
-
-            let ev = E::EventType::from(ev);
-            cb.invoke(ev);
-        }) as Box<dyn FnMut(crate::renderer::types::Event)>;
+        let flags = ListenerOptions::of::<E>();
+        let options = self.event.listener_options();
+        let delegated = E::DELEGATED
+            && cfg!(feature = "delegation")
+            && self.event.options().is_none();
+        let cb = Self::handler(
+            self.cb.expect("callback removed before attaching").take(),
+        );
 
         attach_inner(
             el,
             cb,
             self.event.name(),
-            (E::BUBBLES && cfg!(feature = "delegation"))
-                .then(|| self.event.event_delegation_key()),
+            delegated.then(|| self.event.event_delegation_key()),
+            flags,
+            options,
         )
     }
 
@@ -219,21 +252,9 @@ where
             Rndr::add_event_listener_use_capture(el, &name, cb)
         }
 
-        let mut cb = self.cb.expect("callback removed before attaching").take();
-
-        #[cfg(feature = "tracing")]
-        let span = tracing::Span::current();
-
-        let cb = Box::new(move |ev: crate::renderer::types::Event| {
-            #[cfg(all(debug_assertions, feature = "reactive_graph"))]
-            let _rx_guard =
-                reactive_graph::diagnostics::SpecialNonReactiveZone::enter();
-            #[cfg(feature = "tracing")]
-            let _tracing_guard = span.enter();
-
-            let ev = E::EventType::from(ev);
-            cb.invoke(ev);
-        }) as Box<dyn FnMut(crate::renderer::types::Event)>;
+        let cb = Self::handler(
+            self.cb.expect("callback removed before attaching").take(),
+        );
 
         attach_inner(el, cb, self.event.name())
     }
@@ -271,7 +292,10 @@ where
         0
     }
 
-    #[inline(always)]
+    #[cfg_attr(
+        not(all(feature = "ssr", feature = "listener-recorder")),
+        inline(always)
+    )]
     fn to_html(
         self,
         _buf: &mut String,
@@ -279,6 +303,15 @@ where
         _style: &mut String,
         _inner_html: &mut String,
     ) {
+        #[cfg(all(feature = "ssr", feature = "listener-recorder"))]
+        if let Some(cb) = self.cb {
+            if recorder::is_recording() {
+                let cb = Self::handler(cb.take());
+                let cb = if E::ONCE { call_once(cb) } else { cb };
+                let id = recorder::record(self.event.name(), cb);
+                _buf.push_str(&format!(" {}=\"{id}\"", recorder::ATTR));
+            }
+        }
     }
 
     #[inline(always)]
@@ -286,21 +319,13 @@ where
         self,
         el: &crate::renderer::types::Element,
     ) -> Self::State {
-        let cleanup = if E::CAPTURE {
-            self.attach_capture(el)
-        } else {
-            self.attach(el)
-        };
+        let cleanup = self.attach(el);
         (el.clone(), Some(cleanup))
     }
 
     #[inline(always)]
     fn build(self, el: &crate::renderer::types::Element) -> Self::State {
-        let cleanup = if E::CAPTURE {
-            self.attach_capture(el)
-        } else {
-            self.attach(el)
-        };
+        let cleanup = self.attach(el);
         (el.clone(), Some(cleanup))
     }
 
@@ -310,11 +335,7 @@ where
         if let Some(prev) = prev_cleanup.take() {
             (prev.into_inner())(el);
         }
-        *prev_cleanup = Some(if E::CAPTURE {
-            self.attach_capture(el)
-        } else {
-            self.attach(el)
-        });
+        *prev_cleanup = Some(self.attach(el));
     }
 
     fn into_cloneable(self) -> Self::Cloneable {
@@ -336,6 +357,11 @@ where
         // synchronously remove and drop the SendWrapper value
         // we don't need this value during SSR and leaving it here could drop it
         // from a different thread
+        // unless the listeners are being recorded on this thread, for a test
+        #[cfg(all(feature = "ssr", feature = "listener-recorder"))]
+        if recorder::is_recording() {
+            return;
+        }
         self.cb.take();
     }
 
@@ -381,14 +407,25 @@ pub trait EventDescriptor: Clone {
 
     /// Indicates if this event bubbles. For example, `click` bubbles,
     /// but `focus` does not.
+    const BUBBLES: bool;
+
+    /// Indicates if the listener can be delegated to a single global listener. Defaults to
+    /// [`BUBBLES`](EventDescriptor::BUBBLES).
     ///
     /// If this is true, then the event will be delegated globally if the `delegation`
     /// feature is enabled. Otherwise, event listeners will be directly attached to the element.
-    const BUBBLES: bool;
+    const DELEGATED: bool = Self::BUBBLES;
 
     /// Indicates if this event should be handled during the capture phase.
     const CAPTURE: bool = false;
 
+    /// Indicates if this event listener is passive, i.e., that it will never call
+    /// `preventDefault()`. This allows the browser to scroll without waiting for the listener.
+    const PASSIVE: bool = false;
+
+    /// Indicates if this event listener should be removed after it is invoked once.
+    const ONCE: bool = false;
+
     /// The name of the event, such as `click` or `mouseover`.
     fn name(&self) -> Cow<'static, str>;
 
@@ -401,6 +438,94 @@ pub trait EventDescriptor: Clone {
     fn options(&self) -> Option<&web_sys::AddEventListenerOptions> {
         None
     }
+
+    /// Returns the [`AddEventListenerOptions`](web_sys::AddEventListenerOptions) that should be
+    /// used when attaching the listener, combining [`options`](EventDescriptor::options) with
+    /// the `CAPTURE`, `PASSIVE`, and `ONCE` flags.
+    ///
+    /// Returns `None` if the listener can be attached without any options.
+    fn listener_options(&self) -> Option<web_sys::AddEventListenerOptions> {
+        if !Self::CAPTURE
+            && !Self::PASSIVE
+            && !Self::ONCE
+            && self.options().is_none()
+        {
+            return None;
+        }
+        // the options of a `Custom` event are copied, so that they are not changed for every
+        // other listener that uses them
+        let options = match self.options() {
+            Some(options) => {
+                js_sys::Object::assign(&js_sys::Object::new(), options)
+                    .unchecked_into::<web_sys::AddEventListenerOptions>()
+            }
+            None => web_sys::AddEventListenerOptions::new(),
+        };
+        if Self::CAPTURE {
+            options.set_capture(true);
+        }
+        if Self::PASSIVE {
+            options.set_passive(true);
+        }
+        if Self::ONCE {
+            options.set_once(true);
+        }
+        Some(options)
+    }
+}
+
+/// The `CAPTURE`, `PASSIVE`, and `ONCE` flags of an [`EventDescriptor`], which are honored both
+/// by listeners attached directly to an element and by delegated listeners.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ListenerOptions {
+    /// Whether the listener runs during the capture phase.
+    pub capture: bool,
+    /// Whether the listener is passive.
+    pub passive: bool,
+    /// Whether the listener only runs once.
+    pub once: bool,
+}
+
+impl ListenerOptions {
+    /// The flags of the event type `E`.
+    pub fn of<E: EventDescriptor>() -> Self {
+        Self {
+            capture: E::CAPTURE,
+            passive: E::PASSIVE,
+            once: E::ONCE,
+        }
+    }
+
+    /// The key under which a delegated listener with these options is stored on its element.
+    ///
+    /// Captured and passive listeners are dispatched by their own global listener, which is
+    /// itself captured or passive, so they are stored under a different key than other listeners
+    /// for the same event.
+    pub fn delegation_key(&self, key: Cow<'static, str>) -> Cow<'static, str> {
+        match (self.capture, self.passive) {
+            (false, false) => key,
+            (true, false) => format!("{key}$capture").into(),
+            (false, true) => format!("{key}$passive").into(),
+            (true, true) => format!("{key}$capture$passive").into(),
+        }
+    }
+}
+
+/// Wraps `cb` so that it only runs the first time it is called, as a delegated or recorded
+/// listener can't be removed by the browser after it runs.
+#[cfg(any(
+    feature = "delegation",
+    all(feature = "ssr", feature = "listener-recorder"),
+    test
+))]
+fn call_once<T: 'static>(mut cb: Box<dyn FnMut(T)>) -> Box<dyn FnMut(T)> {
+    let mut called = false;
+    Box::new(move |ev| {
+        if !called {
+            called = true;
+            cb(ev);
+        }
+    })
 }
 
 /// A wrapper that tells the framework to handle an event during the capture phase.
@@ -419,6 +544,120 @@ impl<E: EventDescriptor> EventDescriptor for Capture<E> {
 
     const CAPTURE: bool = true;
     const BUBBLES: bool = E::BUBBLES;
+    const DELEGATED: bool = E::DELEGATED;
+    const PASSIVE: bool = E::PASSIVE;
+    const ONCE: bool = E::ONCE;
+
+    fn name(&self) -> Cow<'static, str> {
+        self.inner.name()
+    }
+
+    fn event_delegation_key(&self) -> Cow<'static, str> {
+        self.inner.event_delegation_key()
+    }
+
+    #[inline(always)]
+    fn options(&self) -> Option<&web_sys::AddEventListenerOptions> {
+        self.inner.options()
+    }
+}
+
+/// A wrapper that marks an event listener as passive, meaning it will never call
+/// `preventDefault()`.
+///
+/// Passive listeners for events like `scroll`, `wheel`, and `touchmove` allow the browser to
+/// scroll immediately, without waiting for the listener to run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Passive<E> {
+    inner: E,
+}
+
+/// Wraps an event to indicate that its listener is passive.
+pub fn passive<E>(event: E) -> Passive<E> {
+    Passive { inner: event }
+}
+
+impl<E: EventDescriptor> EventDescriptor for Passive<E> {
+    type EventType = E::EventType;
+
+    const CAPTURE: bool = E::CAPTURE;
+    const BUBBLES: bool = E::BUBBLES;
+    const DELEGATED: bool = E::DELEGATED;
+    const PASSIVE: bool = true;
+    const ONCE: bool = E::ONCE;
+
+    fn name(&self) -> Cow<'static, str> {
+        self.inner.name()
+    }
+
+    fn event_delegation_key(&self) -> Cow<'static, str> {
+        self.inner.event_delegation_key()
+    }
+
+    #[inline(always)]
+    fn options(&self) -> Option<&web_sys::AddEventListenerOptions> {
+        self.inner.options()
+    }
+}
+
+/// A wrapper that removes an event listener after it has been invoked once.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Once<E> {
+    inner: E,
+}
+
+/// Wraps an event to indicate that its listener should only be invoked once.
+pub fn once<E>(event: E) -> Once<E> {
+    Once { inner: event }
+}
+
+impl<E: EventDescriptor> EventDescriptor for Once<E> {
+    type EventType = E::EventType;
+
+    const CAPTURE: bool = E::CAPTURE;
+    const BUBBLES: bool = E::BUBBLES;
+    const DELEGATED: bool = E::DELEGATED;
+    const PASSIVE: bool = E::PASSIVE;
+    const ONCE: bool = true;
+
+    fn name(&self) -> Cow<'static, str> {
+        self.inner.name()
+    }
+
+    fn event_delegation_key(&self) -> Cow<'static, str> {
+        self.inner.event_delegation_key()
+    }
+
+    #[inline(always)]
+    fn options(&self) -> Option<&web_sys::AddEventListenerOptions> {
+        self.inner.options()
+    }
+}
+
+/// A wrapper that tells the framework to attach the event listener directly to the element,
+/// rather than delegating it to a single global listener.
+///
+/// This is useful when an event needs to be handled inside a shadow root or a portal, or on
+/// elements controlled by third-party code that stops propagation before the event reaches
+/// the global handler.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Undelegated<E> {
+    inner: E,
+}
+
+/// Wraps an event to indicate that its listener should not be delegated.
+pub fn undelegated<E>(event: E) -> Undelegated<E> {
+    Undelegated { inner: event }
+}
+
+impl<E: EventDescriptor> EventDescriptor for Undelegated<E> {
+    type EventType = E::EventType;
+
+    const CAPTURE: bool = E::CAPTURE;
+    const BUBBLES: bool = E::BUBBLES;
+    const DELEGATED: bool = false;
+    const PASSIVE: bool = E::PASSIVE;
+    const ONCE: bool = E::ONCE;
 
     fn name(&self) -> Cow<'static, str> {
         self.inner.name()
@@ -427,6 +666,11 @@ impl<E: EventDescriptor> EventDescriptor for Capture<E> {
     fn event_delegation_key(&self) -> Cow<'static, str> {
         self.inner.event_delegation_key()
     }
+
+    #[inline(always)]
+    fn options(&self) -> Option<&web_sys::AddEventListenerOptions> {
+        self.inner.options()
+    }
 }
 
 /// A custom event.
@@ -506,6 +750,221 @@ impl<E: FromWasmAbi> Custom<E> {
     }
 }
 
+/// A payload that can be carried in the `detail` field of a [`TypedCustomEvent`].
+///
+/// With the `serde` feature enabled, this is implemented for every type that implements
+/// [`Serialize`](serde::Serialize) and [`DeserializeOwned`](serde::de::DeserializeOwned). The
+/// payload is stored as a plain JavaScript object, so it can also be read by non-Rust listeners
+/// (for example, code outside a web component).
+pub trait CustomEventDetail: Sized {
+    /// Converts the payload into the value that will be stored as `event.detail`.
+    fn to_detail(&self) -> wasm_bindgen::JsValue;
+
+    /// Reads the payload back out of `event.detail`, returning `None` if it is missing or
+    /// does not have the expected shape.
+    fn from_detail(detail: wasm_bindgen::JsValue) -> Option<Self>;
+}
+
+#[cfg(feature = "serde")]
+impl<T> CustomEventDetail for T
+where
+    T: serde::Serialize + serde::de::DeserializeOwned,
+{
+    fn to_detail(&self) -> wasm_bindgen::JsValue {
+        serde_json::to_string(self)
+            .ok()
+            .and_then(|json| js_sys::JSON::parse(&json).ok())
+            .unwrap_or(wasm_bindgen::JsValue::UNDEFINED)
+    }
+
+    fn from_detail(detail: wasm_bindgen::JsValue) -> Option<Self> {
+        if detail.is_undefined() {
+            return None;
+        }
+        let json = js_sys::JSON::stringify(&detail).ok()?;
+        serde_json::from_str(&String::from(json)).ok()
+    }
+}
+
+/// A [`CustomEvent`](web_sys::CustomEvent) whose `detail` is a payload of type `T`.
+///
+/// This is the event type received by listeners for events declared with
+/// [`define_custom_event!`](crate::define_custom_event). It dereferences to the underlying
+/// [`web_sys::CustomEvent`], so all of the usual event methods are available.
+pub struct TypedCustomEvent<T> {
+    event: web_sys::CustomEvent,
+    _detail: PhantomData<fn() -> T>,
+}
+
+impl<T> TypedCustomEvent<T> {
+    /// Consumes the wrapper, returning the underlying [`web_sys::CustomEvent`].
+    pub fn into_inner(self) -> web_sys::CustomEvent {
+        self.event
+    }
+}
+
+impl<T: CustomEventDetail> TypedCustomEvent<T> {
+    /// Returns the payload carried by this event.
+    ///
+    /// Returns `None` if the event was dispatched without a `detail`, or with a `detail`
+    /// that cannot be read as a `T`.
+    pub fn detail(&self) -> Option<T> {
+        T::from_detail(self.event.detail())
+    }
+}
+
+impl<T> Debug for TypedCustomEvent<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("TypedCustomEvent")
+            .field(&self.event)
+            .finish()
+    }
+}
+
+impl<T> Clone for TypedCustomEvent<T> {
+    fn clone(&self) -> Self {
+        Self {
+            event: self.event.clone(),
+            _detail: PhantomData,
+        }
+    }
+}
+
+impl<T> Deref for TypedCustomEvent<T> {
+    type Target = web_sys::CustomEvent;
+
+    fn deref(&self) -> &Self::Target {
+        &self.event
+    }
+}
+
+impl<T> From<wasm_bindgen::JsValue> for TypedCustomEvent<T> {
+    fn from(event: wasm_bindgen::JsValue) -> Self {
+        Self {
+            event: event.unchecked_into(),
+            _detail: PhantomData,
+        }
+    }
+}
+
+impl<T> wasm_bindgen::describe::WasmDescribe for TypedCustomEvent<T> {
+    fn describe() {
+        <web_sys::CustomEvent as wasm_bindgen::describe::WasmDescribe>::describe(
+        )
+    }
+}
+
+impl<T> FromWasmAbi for TypedCustomEvent<T> {
+    type Abi = <web_sys::CustomEvent as FromWasmAbi>::Abi;
+
+    unsafe fn from_abi(js: Self::Abi) -> Self {
+        Self {
+            event: web_sys::CustomEvent::from_abi(js),
+            _detail: PhantomData,
+        }
+    }
+}
+
+/// An [`EventDescriptor`] for a custom event that carries a typed payload.
+///
+/// This is usually implemented with [`define_custom_event!`](crate::define_custom_event).
+pub trait TypedCustomEventDescriptor:
+    EventDescriptor<EventType = TypedCustomEvent<Self::Detail>>
+{
+    /// The payload carried in the event's `detail`.
+    type Detail: CustomEventDetail;
+
+    /// Dispatches this event on the given target, with `detail` as its payload.
+    ///
+    /// The event bubbles and is `composed`, so it crosses shadow DOM boundaries and can be
+    /// listened for on a custom element's host. Returns `false` if a listener called
+    /// `preventDefault()`.
+    fn dispatch(
+        &self,
+        target: &web_sys::EventTarget,
+        detail: &Self::Detail,
+    ) -> bool {
+        let init = web_sys::CustomEventInit::new();
+        init.set_bubbles(true);
+        init.set_composed(true);
+        init.set_cancelable(true);
+        init.set_detail(&detail.to_detail());
+        web_sys::CustomEvent::new_with_event_init_dict(&self.name(), &init)
+            .and_then(|event| target.dispatch_event(&event))
+            .unwrap_or(true)
+    }
+}
+
+/// Declares a custom event type with a strongly-typed payload.
+///
+/// The generated type implements [`EventDescriptor`], so it can be used with
+/// [`on`] or with `on:typed:` in the `view!` macro, and [`TypedCustomEventDescriptor`], which
+/// provides [`dispatch`](TypedCustomEventDescriptor::dispatch). The payload is passed to
+/// listeners as a [`TypedCustomEvent`].
+///
+/// By default, the DOM event name is the name of the type. An explicit event name can be
+/// given as a third argument, which is useful for listening to events emitted by
+/// third-party web components.
+///
+/// ```rust,ignore
+/// #[derive(Clone, Serialize, Deserialize)]
+/// pub struct SavePayload {
+///     pub id: u32,
+/// }
+///
+/// define_custom_event!(pub SaveRequested, SavePayload);
+/// define_custom_event!(pub SlChange, ChangePayload, "sl-change");
+///
+/// // dispatch from one component...
+/// SaveRequested.dispatch(&el, &SavePayload { id: 42 });
+///
+/// // ...and listen for it in another
+/// view! {
+///     <div on:typed:SaveRequested=|ev| {
+///         if let Some(payload) = ev.detail() {
+///             save(payload.id);
+///         }
+///     }>
+///         <Editor/>
+///     </div>
+/// }
+/// ```
+#[macro_export]
+macro_rules! define_custom_event {
+    ($(#[$meta:meta])* $vis:vis $name:ident, $detail:ty) => {
+        $crate::define_custom_event!(
+            $(#[$meta])* $vis $name, $detail, stringify!($name)
+        );
+    };
+    ($(#[$meta:meta])* $vis:vis $name:ident, $detail:ty, $event_name:expr) => {
+        $(#[$meta])*
+        #[derive(Copy, Clone, Debug)]
+        $vis struct $name;
+
+        impl $crate::html::event::EventDescriptor for $name {
+            type EventType = $crate::html::event::TypedCustomEvent<$detail>;
+
+            // custom events may be dispatched without bubbling (for example, by
+            // third-party web components), so they are never delegated
+            const BUBBLES: bool = false;
+
+            #[inline(always)]
+            fn name(&self) -> ::std::borrow::Cow<'static, str> {
+                ::std::borrow::Cow::Borrowed($event_name)
+            }
+
+            #[inline(always)]
+            fn event_delegation_key(&self) -> ::std::borrow::Cow<'static, str> {
+                ::std::borrow::Cow::Owned(::std::format!("$$${}", $event_name))
+            }
+        }
+
+        impl $crate::html::event::TypedCustomEventDescriptor for $name {
+            type Detail = $detail;
+        }
+    };
+}
+
 macro_rules! generate_event_types {
   {$(
     $( #[$does_not_bubble:ident] )?
@@ -766,3 +1225,192 @@ pub use web_sys::{
     SecurityPolicyViolationEvent, StorageEvent, SubmitEvent, TouchEvent,
     TransitionEvent, UiEvent, WheelEvent,
 };
+
+/// Records the event listeners of a view while it is rendered to HTML, so that tests can run
+/// them without a browser.
+///
+/// While recording, each listener is kept instead of being dropped, and its element is marked
+/// with an [`ATTR`](recorder::ATTR) attribute holding the listener's index, which callers strip
+/// from the HTML before showing it.
+///
+/// Only available with the `listener-recorder` feature, so that other server rendering does not
+/// check for a recording.
+#[cfg(all(feature = "ssr", feature = "listener-recorder"))]
+pub mod recorder {
+    use std::{borrow::Cow, cell::RefCell};
+
+    /// The attribute added to an element for each of its recorded listeners.
+    pub const ATTR: &str = "data-leptos-listener";
+
+    thread_local! {
+        static RECORDED: RefCell<Option<Vec<Listener>>> =
+            const { RefCell::new(None) };
+    }
+
+    /// An event listener recorded while rendering to HTML.
+    pub struct Listener {
+        name: Cow<'static, str>,
+        cb: Box<dyn FnMut(crate::renderer::types::Event)>,
+    }
+
+    impl Listener {
+        /// The name of the event, such as `click`.
+        pub fn name(&self) -> &str {
+            &self.name
+        }
+
+        /// Runs the listener.
+        pub fn call(&mut self, event: crate::renderer::types::Event) {
+            (self.cb)(event)
+        }
+    }
+
+    impl std::fmt::Debug for Listener {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("Listener")
+                .field("name", &self.name)
+                .finish_non_exhaustive()
+        }
+    }
+
+    /// Records the listeners rendered on this thread until it is dropped or
+    /// [`finish`](Recording::finish)ed.
+    #[derive(Debug)]
+    #[must_use = "listeners are only recorded while the recording is alive"]
+    pub struct Recording(());
+
+    impl Recording {
+        /// Starts recording listeners.
+        ///
+        /// # Panics
+        /// Panics if listeners are already being recorded on this thread.
+        pub fn start() -> Self {
+            RECORDED.with(|recorded| {
+                let mut recorded = recorded.borrow_mut();
+                assert!(
+                    recorded.is_none(),
+                    "event listeners are already being recorded on this \
+                     thread"
+                );
+                *recorded = Some(Vec::new());
+            });
+            Recording(())
+        }
+
+        /// Stops recording, returning the listeners in the order of their indices.
+        pub fn finish(self) -> Vec<Listener> {
+            RECORDED
+                .with(|recorded| recorded.borrow_mut().take())
+                .unwrap_or_default()
+        }
+    }
+
+    impl Drop for Recording {
+        fn drop(&mut self) {
+            // empty if `finish` has already taken the listeners
+            let listeners =
+                RECORDED.with(|recorded| recorded.borrow_mut().take());
+            drop(listeners);
+        }
+    }
+
+    pub(crate) fn is_recording() -> bool {
+        RECORDED.with(|recorded| recorded.borrow().is_some())
+    }
+
+    pub(crate) fn record(
+        name: Cow<'static, str>,
+        cb: Box<dyn FnMut(crate::renderer::types::Event)>,
+    ) -> usize {
+        RECORDED.with(|recorded| {
+            let mut recorded = recorded.borrow_mut();
+            let recorded = recorded.get_or_insert_with(Vec::new);
+            recorded.push(Listener { name, cb });
+            recorded.len() - 1
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        call_once, capture, click, focus, once, passive, scroll, undelegated,
+        EventDescriptor, ListenerOptions,
+    };
+    use std::{cell::Cell, rc::Rc};
+
+    fn options_of<E: super::EventDescriptor>(_event: E) -> ListenerOptions {
+        ListenerOptions::of::<E>()
+    }
+
+    #[test]
+    fn listener_options_are_read_from_wrappers() {
+        assert_eq!(options_of(click), ListenerOptions::default());
+        assert_eq!(
+            options_of(capture(passive(once(scroll)))),
+            ListenerOptions {
+                capture: true,
+                passive: true,
+                once: true,
+            }
+        );
+    }
+
+    #[test]
+    fn each_combination_of_options_has_its_own_delegation_key() {
+        let keys = [(false, false), (true, false), (false, true), (true, true)]
+            .map(|(capture, passive)| {
+                ListenerOptions {
+                    capture,
+                    passive,
+                    once: false,
+                }
+                .delegation_key("$$$click".into())
+            });
+        assert_eq!(keys[0], "$$$click");
+        for (i, key) in keys.iter().enumerate() {
+            assert!(!keys[i + 1..].contains(key));
+        }
+
+        let once = ListenerOptions {
+            once: true,
+            ..Default::default()
+        };
+        assert_eq!(once.delegation_key("$$$click".into()), "$$$click");
+    }
+
+    #[test]
+    fn call_once_only_runs_the_first_time() {
+        let calls = Rc::new(Cell::new(0));
+        let mut cb = call_once(Box::new({
+            let calls = Rc::clone(&calls);
+            move |n: usize| calls.set(calls.get() + n)
+        }));
+        cb(1);
+        cb(10);
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    fn undelegated_events_forward_to_the_inner_event() {
+        fn flags<E: EventDescriptor>(_event: &E) -> (bool, bool) {
+            (E::BUBBLES, E::DELEGATED)
+        }
+
+        assert_eq!(flags(&click), (true, true));
+        let event = undelegated(click);
+        assert_eq!(event.name(), "click");
+        assert_eq!(event.event_delegation_key(), click.event_delegation_key());
+        assert_eq!(flags(&event), (true, false));
+        assert_eq!(flags(&undelegated(focus)), (false, false));
+        // the other wrappers keep it undelegated
+        assert_eq!(flags(&once(undelegated(click))), (true, false));
+        assert_eq!(
+            options_of(capture(undelegated(click))),
+            ListenerOptions {
+                capture: true,
+                ..Default::default()
+            }
+        );
+    }
+}
@@ -59,9 +59,9 @@ impl<View> Island<View> {
     fn should_have_element_representation() -> bool {
         #[cfg(feature = "reactive_graph")]
         {
-            use reactive_graph::owner::{use_context, IsHydrating};
+            use reactive_graph::owner::{with_context, IsHydrating};
             let already_hydrating =
-                use_context::<IsHydrating>().map(|h| h.0).unwrap_or(false);
+                with_context(|h: &IsHydrating| h.0).unwrap_or(false);
             !already_hydrating
         }
         #[cfg(not(feature = "reactive_graph"))]
@@ -16,6 +16,10 @@ use std::borrow::Cow;
 pub mod attribute;
 /// Types for manipulating the `class` attribute and `classList`.
 pub mod class;
+/// Merging conditional classes, with conflict resolution for utility classes.
+pub mod class_list;
+/// Typed values for individual CSS properties.
+pub mod css;
 /// Types for creating user-defined attributes with custom behavior (directives).
 pub mod directive;
 /// Types for HTML elements.
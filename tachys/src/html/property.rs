@@ -1,12 +1,13 @@
 use super::attribute::{
     maybe_next_attr_erasure_macros::next_attr_output_type, Attribute,
-    NextAttribute,
+    AttributeValue, NextAttribute,
 };
 use crate::{
     html::attribute::maybe_next_attr_erasure_macros::next_attr_combine,
     renderer::Rndr,
     view::{Position, ToTemplate},
 };
+use either_of::Either;
 use send_wrapper::SendWrapper;
 use std::{borrow::Cow, sync::Arc};
 use wasm_bindgen::JsValue;
@@ -156,6 +157,154 @@ where
     }
 }
 
+/// Creates an [`Attribute`] that sets the DOM property `key` on an element if the element has
+/// one that can be set, and the attribute `key` otherwise.
+///
+/// This avoids having to know whether a value should be set with `prop:` or as an attribute:
+/// `value`, `checked` and `indeterminate` are set as properties on an `<input>`, as are the
+/// properties of a custom element, while `data-*` and `aria-*` attributes, read-only properties
+/// like `list` and `form`, or any other name that is not a property of the element, are set as
+/// attributes. The choice is made once, when the
+/// element is created or hydrated, so custom elements should be defined before then. `prop:` and
+/// `attr:` can still be used to choose explicitly.
+///
+/// On the server, the value is always rendered as an attribute, and it is set again as a
+/// property when it is hydrated.
+#[inline(always)]
+pub fn prop_or_attr<K, V>(key: K, value: V) -> PropOrAttr<K, V>
+where
+    K: AsRef<str> + Send,
+    V: IntoProperty + AttributeValue + Clone + 'static,
+{
+    PropOrAttr { key, value }
+}
+
+/// An [`Attribute`] that sets a DOM property if the element has one that can be set, and an
+/// attribute otherwise.
+/// See [`prop_or_attr`].
+#[derive(Debug, Clone)]
+pub struct PropOrAttr<K, V> {
+    key: K,
+    value: V,
+}
+
+impl<K, V> Attribute for PropOrAttr<K, V>
+where
+    K: AsRef<str> + Send,
+    V: IntoProperty + AttributeValue + Clone + 'static,
+{
+    const MIN_LENGTH: usize = 0;
+
+    type AsyncOutput = Self;
+    type State =
+        Either<<V as IntoProperty>::State, <V as AttributeValue>::State>;
+    type Cloneable = PropOrAttr<Arc<str>, V>;
+    type CloneableOwned = PropOrAttr<Arc<str>, V>;
+
+    fn html_len(&self) -> usize {
+        self.key.as_ref().len() + 3 + self.value.html_len()
+    }
+
+    fn to_html(
+        self,
+        buf: &mut String,
+        _class: &mut String,
+        _style: &mut String,
+        _inner_html: &mut String,
+    ) {
+        AttributeValue::to_html(self.value, self.key.as_ref(), buf);
+    }
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        el: &crate::renderer::types::Element,
+    ) -> Self::State {
+        let key = self.key.as_ref();
+        if Rndr::has_writable_property(el, key) {
+            Either::Left(IntoProperty::hydrate::<FROM_SERVER>(
+                self.value, el, key,
+            ))
+        } else {
+            Either::Right(AttributeValue::hydrate::<FROM_SERVER>(
+                self.value, key, el,
+            ))
+        }
+    }
+
+    fn build(self, el: &crate::renderer::types::Element) -> Self::State {
+        let key = self.key.as_ref();
+        if Rndr::has_writable_property(el, key) {
+            Either::Left(IntoProperty::build(self.value, el, key))
+        } else {
+            Either::Right(AttributeValue::build(self.value, el, key))
+        }
+    }
+
+    fn rebuild(self, state: &mut Self::State) {
+        let key = self.key.as_ref();
+        match state {
+            Either::Left(state) => {
+                IntoProperty::rebuild(self.value, state, key)
+            }
+            Either::Right(state) => {
+                AttributeValue::rebuild(self.value, key, state)
+            }
+        }
+    }
+
+    fn into_cloneable(self) -> Self::Cloneable {
+        PropOrAttr {
+            key: self.key.as_ref().into(),
+            value: self.value,
+        }
+    }
+
+    fn into_cloneable_owned(self) -> Self::CloneableOwned {
+        PropOrAttr {
+            key: self.key.as_ref().into(),
+            value: self.value,
+        }
+    }
+
+    fn dry_resolve(&mut self) {
+        AttributeValue::dry_resolve(&mut self.value);
+    }
+
+    async fn resolve(self) -> Self::AsyncOutput {
+        self
+    }
+}
+
+impl<K, V> NextAttribute for PropOrAttr<K, V>
+where
+    K: AsRef<str> + Send,
+    V: IntoProperty + AttributeValue + Clone + 'static,
+{
+    next_attr_output_type!(Self, NewAttr);
+
+    fn add_any_attr<NewAttr: Attribute>(
+        self,
+        new_attr: NewAttr,
+    ) -> Self::Output<NewAttr> {
+        next_attr_combine!(self, new_attr)
+    }
+}
+
+impl<K, V> ToTemplate for PropOrAttr<K, V>
+where
+    K: AsRef<str>,
+    V: IntoProperty + AttributeValue,
+{
+    fn to_template(
+        _buf: &mut String,
+        _class: &mut String,
+        _style: &mut String,
+        _inner_html: &mut String,
+        _position: &mut Position,
+    ) {
+    }
+}
+
 /// A possible value for a DOM property.
 pub trait IntoProperty {
     /// The view state retained between building and rebuilding.
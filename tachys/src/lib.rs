@@ -29,6 +29,7 @@ pub mod prelude {
             },
             directive::DirectiveAttribute,
             element::{ElementChild, ElementExt, InnerHtmlAttribute},
+            event::TypedCustomEventDescriptor,
             node_ref::NodeRefAttribute,
         },
         renderer::{dom::Dom, Renderer},
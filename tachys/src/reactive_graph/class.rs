@@ -1,5 +1,11 @@
 use super::{ReactiveFunction, SharedReactiveFunction};
-use crate::{html::class::IntoClass, renderer::Rndr};
+use crate::{
+    html::{
+        class::IntoClass,
+        class_list::{ClassList, MergedClasses},
+    },
+    renderer::Rndr,
+};
 use reactive_graph::effect::RenderEffect;
 use std::borrow::Borrow;
 
@@ -117,6 +123,66 @@ where
     }
 }
 
+impl IntoClass for ClassList {
+    type AsyncOutput = MergedClasses;
+    type State = RenderEffect<<MergedClasses as IntoClass>::State>;
+    type Cloneable = Self;
+    type CloneableOwned = Self;
+
+    fn html_len(&self) -> usize {
+        0
+    }
+
+    fn to_html(self, class: &mut String) {
+        self.to_merged().to_html(class);
+    }
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        el: &crate::renderer::types::Element,
+    ) -> Self::State {
+        (move || self.to_merged()).hydrate::<FROM_SERVER>(el)
+    }
+
+    fn build(self, el: &crate::renderer::types::Element) -> Self::State {
+        (move || self.to_merged()).build(el)
+    }
+
+    fn rebuild(self, state: &mut Self::State) {
+        (move || self.to_merged()).rebuild(state)
+    }
+
+    fn into_cloneable(self) -> Self::Cloneable {
+        self
+    }
+
+    fn into_cloneable_owned(self) -> Self::CloneableOwned {
+        self
+    }
+
+    fn dry_resolve(&mut self) {
+        self.to_merged();
+    }
+
+    async fn resolve(self) -> Self::AsyncOutput {
+        self.to_merged()
+    }
+
+    fn reset(state: &mut Self::State) {
+        *state = RenderEffect::new_with_value(
+            move |prev| {
+                if let Some(mut state) = prev {
+                    MergedClasses::reset(&mut state);
+                    state
+                } else {
+                    unreachable!()
+                }
+            },
+            state.take_value(),
+        );
+    }
+}
+
 impl<F, T> IntoClass for (&'static str, F)
 where
     F: ReactiveFunction<Output = T>,
@@ -1,5 +1,5 @@
 use super::{ReactiveFunction, SharedReactiveFunction};
-use crate::html::element::InnerHtmlValue;
+use crate::html::element::{InnerHtmlValue, SanitizableHtml, Sanitized};
 use reactive_graph::effect::RenderEffect;
 
 impl<F, V> InnerHtmlValue for F
@@ -86,6 +86,61 @@ where
     }
 }
 
+impl<F, V> InnerHtmlValue for Sanitized<F>
+where
+    F: ReactiveFunction<Output = V>,
+    V: SanitizableHtml + 'static,
+{
+    type AsyncOutput = Option<String>;
+    type State = RenderEffect<<Option<String> as InnerHtmlValue>::State>;
+    type Cloneable = Sanitized<SharedReactiveFunction<V>>;
+    type CloneableOwned = Sanitized<SharedReactiveFunction<V>>;
+
+    fn html_len(&self) -> usize {
+        0
+    }
+
+    fn to_html(mut self, buf: &mut String) {
+        self.0.invoke().sanitized().to_html(buf);
+    }
+
+    fn to_template(_buf: &mut String) {}
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        el: &crate::renderer::types::Element,
+    ) -> Self::State {
+        let mut f = self.0;
+        (move || f.invoke().sanitized()).hydrate::<FROM_SERVER>(el)
+    }
+
+    fn build(self, el: &crate::renderer::types::Element) -> Self::State {
+        let mut f = self.0;
+        (move || f.invoke().sanitized()).build(el)
+    }
+
+    fn rebuild(self, state: &mut Self::State) {
+        let mut f = self.0;
+        (move || f.invoke().sanitized()).rebuild(state)
+    }
+
+    fn into_cloneable(self) -> Self::Cloneable {
+        Sanitized(self.0.into_shared())
+    }
+
+    fn into_cloneable_owned(self) -> Self::CloneableOwned {
+        Sanitized(self.0.into_shared())
+    }
+
+    fn dry_resolve(&mut self) {
+        self.0.invoke();
+    }
+
+    async fn resolve(mut self) -> Self::AsyncOutput {
+        self.0.invoke().sanitized()
+    }
+}
+
 macro_rules! inner_html_reactive {
     ($name:ident, <$($gen:ident),*>, $v:ty, $( $where_clause:tt )*) =>
     {
@@ -144,6 +199,61 @@ macro_rules! inner_html_reactive {
                 self
             }
         }
+
+        #[allow(deprecated)]
+        impl<$($gen),*> InnerHtmlValue
+            for crate::html::element::Sanitized<$name<$($gen),*>>
+        where
+            $v: crate::html::element::SanitizableHtml + Clone + Send + Sync + 'static,
+            $($where_clause)*
+        {
+            type AsyncOutput = Self;
+            type State = RenderEffect<<Option<String> as InnerHtmlValue>::State>;
+            type Cloneable = Self;
+            type CloneableOwned = Self;
+
+            fn html_len(&self) -> usize {
+                0
+            }
+
+            fn to_html(self, buf: &mut String) {
+                crate::html::element::SanitizableHtml::sanitized(&self.0.get()).to_html(buf);
+            }
+
+            fn to_template(_buf: &mut String) {}
+
+            fn hydrate<const FROM_SERVER: bool>(
+                self,
+                el: &crate::renderer::types::Element,
+            ) -> Self::State {
+                (move || crate::html::element::SanitizableHtml::sanitized(&self.0.get())).hydrate::<FROM_SERVER>(el)
+            }
+
+            fn build(
+                self,
+                el: &crate::renderer::types::Element,
+            ) -> Self::State {
+                (move || crate::html::element::SanitizableHtml::sanitized(&self.0.get())).build(el)
+            }
+
+            fn rebuild(self, state: &mut Self::State) {
+                (move || crate::html::element::SanitizableHtml::sanitized(&self.0.get())).rebuild(state)
+            }
+
+            fn into_cloneable(self) -> Self::Cloneable {
+                self
+            }
+
+            fn into_cloneable_owned(self) -> Self::CloneableOwned {
+                self
+            }
+
+            fn dry_resolve(&mut self) {}
+
+            async fn resolve(self) -> Self::AsyncOutput {
+                self
+            }
+        }
     };
 }
 
@@ -22,7 +22,7 @@ use reactive_graph::{
         AnySource, AnySubscriber, Observer, ReactiveNode, Source, Subscriber,
         ToAnySubscriber, WithObserver,
     },
-    owner::{on_cleanup, provide_context, use_context},
+    owner::{on_cleanup, shadow_context, with_context},
 };
 use std::{
     cell::RefCell,
@@ -191,8 +191,8 @@ where
         let inner = Rc::new(RefCell::new(initial.build()));
 
         // get a unique ID if there's a SuspenseContext
-        let id = use_context::<SuspenseContext>().map(|sc| sc.task_id());
-        let error_hook = use_context::<Arc<dyn ErrorHook>>();
+        let id = with_context(SuspenseContext::clone).map(|sc| sc.task_id());
+        let error_hook = with_context(Arc::<dyn ErrorHook>::clone);
 
         // if the initial state was pending, spawn a future to wait for it
         // spawning immediately means that our now_or_never poll result isn't lost
@@ -233,8 +233,8 @@ where
         on_cleanup(move || abort_handle.abort());
 
         // get a unique ID if there's a SuspenseContext
-        let id = use_context::<SuspenseContext>().map(|sc| sc.task_id());
-        let error_hook = use_context::<Arc<dyn ErrorHook>>();
+        let id = with_context(SuspenseContext::clone).map(|sc| sc.task_id());
+        let error_hook = with_context(Arc::<dyn ErrorHook>::clone);
 
         // spawn the future, and rebuild the state when it resolves
         reactive_graph::spawn_local_scoped({
@@ -334,11 +334,11 @@ where
                 extra_attrs,
             ),
             None => {
-                if use_context::<SuspenseContext>().is_none() {
+                if with_context(SuspenseContext::clone).is_none() {
                     buf.next_id();
                     let (local_tx, mut local_rx) =
                         futures::channel::oneshot::channel::<()>();
-                    provide_context(LocalResourceNotifier::from(local_tx));
+                    shadow_context(LocalResourceNotifier::from(local_tx));
                     let mut fut = fut.fuse();
                     let fut = async move {
                         select! {
@@ -418,8 +418,8 @@ where
         ));
 
         // get a unique ID if there's a SuspenseContext
-        let id = use_context::<SuspenseContext>().map(|sc| sc.task_id());
-        let error_hook = use_context::<Arc<dyn ErrorHook>>();
+        let id = with_context(SuspenseContext::clone).map(|sc| sc.task_id());
+        let error_hook = with_context(Arc::<dyn ErrorHook>::clone);
 
         // if the initial state was pending, spawn a future to wait for it
         // spawning immediately means that our now_or_never poll result isn't lost
@@ -4,21 +4,27 @@
 
 use super::{CastFrom, RemoveEventHandler};
 use crate::{
-    dom::{document, window},
+    dom::document,
     ok_or_debug, or_debug,
     view::{Mountable, ToTemplate},
 };
 use linear_map::LinearMap;
 use once_cell::unsync::Lazy;
-use rustc_hash::FxHashSet;
-use std::{any::TypeId, borrow::Cow, cell::RefCell};
-use wasm_bindgen::{intern, prelude::Closure, JsCast, JsValue};
+use std::{any::TypeId, cell::RefCell};
+use wasm_bindgen::{intern, JsCast, JsValue};
 use web_sys::{AddEventListenerOptions, Comment, HtmlTemplateElement};
+// only used by event delegation
+#[cfg(feature = "delegation")]
+use {
+    crate::dom::window, rustc_hash::FxHashSet, std::borrow::Cow,
+    wasm_bindgen::prelude::Closure,
+};
 
 /// A [`Renderer`](crate::renderer::Renderer) that uses `web-sys` to manipulate DOM elements in the browser.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Dom;
 
+#[cfg(feature = "delegation")]
 thread_local! {
     pub(crate) static GLOBAL_EVENTS: RefCell<FxHashSet<Cow<'static, str>>> = Default::default();
 }
@@ -198,6 +204,29 @@ impl Dom {
         }
     }
 
+    /// Whether the element has a property with this name that can be set, on itself or on its
+    /// prototype chain. Read-only properties, like `list` or `form` on an `<input>`, can only be
+    /// changed through their attributes, so they are not included.
+    pub fn has_writable_property(el: &Element, key: &str) -> bool {
+        let key = JsValue::from_str(key);
+        let mut object = el.unchecked_ref::<js_sys::Object>().clone();
+        while !object.is_null() {
+            let descriptor =
+                js_sys::Object::get_own_property_descriptor(&object, &key);
+            if !descriptor.is_undefined() {
+                let field = |name: &str| {
+                    js_sys::Reflect::get(&descriptor, &JsValue::from_str(name))
+                        .unwrap_or(JsValue::UNDEFINED)
+                };
+                // a data property with `writable`, or an accessor with a setter
+                return field("writable").is_truthy()
+                    || field("set").is_function();
+            }
+            object = js_sys::Object::get_prototype_of(&object);
+        }
+        false
+    }
+
     pub fn set_property(el: &Element, key: &str, value: &JsValue) {
         or_debug!(
             js_sys::Reflect::set(
@@ -250,19 +279,30 @@ impl Dom {
         name: &str,
         cb: Box<dyn FnMut(Event)>,
     ) -> RemoveEventHandler<Element> {
-        let cb = wasm_bindgen::closure::Closure::wrap(cb);
-        let name = intern(name);
         let options = AddEventListenerOptions::new();
         options.set_capture(true);
+        Self::add_event_listener_with_options(el, name, cb, &options)
+    }
+
+    pub fn add_event_listener_with_options(
+        el: &Element,
+        name: &str,
+        cb: Box<dyn FnMut(Event)>,
+        options: &AddEventListenerOptions,
+    ) -> RemoveEventHandler<Element> {
+        let cb = wasm_bindgen::closure::Closure::wrap(cb);
+        let name = intern(name);
         or_debug!(
             el.add_event_listener_with_callback_and_add_event_listener_options(
                 name,
                 cb.as_ref().unchecked_ref(),
-                &options
+                options
             ),
             el,
-            "addEventListenerUseCapture"
+            "addEventListenerWithOptions"
         );
+        // the listener can only be removed if `capture` matches the value it was added with
+        let capture = options.get_capture().unwrap_or(false);
 
         // return the remover
         RemoveEventHandler::new({
@@ -272,9 +312,10 @@ impl Dom {
             let cb = send_wrapper::SendWrapper::new(cb);
             move |el: &Element| {
                 or_debug!(
-                    el.remove_event_listener_with_callback(
+                    el.remove_event_listener_with_callback_and_bool(
                         intern(&name),
-                        cb.as_ref().unchecked_ref()
+                        cb.as_ref().unchecked_ref(),
+                        capture
                     ),
                     el,
                     "removeEventListener"
@@ -295,11 +336,40 @@ impl Dom {
         T::cast_from(el).expect("incorrect element type")
     }
 
+    /// Adds an event listener to an element, delegated to a single listener on the window for
+    /// each event type. Only available with the `delegation` feature.
+    #[cfg(feature = "delegation")]
     pub fn add_event_listener_delegated(
         el: &Element,
         name: Cow<'static, str>,
         delegation_key: Cow<'static, str>,
         cb: Box<dyn FnMut(Event)>,
+    ) -> RemoveEventHandler<Element> {
+        Self::add_event_listener_delegated_with_options(
+            el,
+            name,
+            delegation_key,
+            cb,
+            false,
+            false,
+        )
+    }
+
+    /// Adds a delegated event listener to an element, like
+    /// [`add_event_listener_delegated`](Self::add_event_listener_delegated), which runs during
+    /// the capture phase if `capture` is set, and is dispatched by a passive listener on the
+    /// window if `passive` is set.
+    ///
+    /// Each combination of options is dispatched by its own listener on the window, so the
+    /// `delegation_key` must be different for each combination.
+    #[cfg(feature = "delegation")]
+    pub fn add_event_listener_delegated_with_options(
+        el: &Element,
+        name: Cow<'static, str>,
+        delegation_key: Cow<'static, str>,
+        cb: Box<dyn FnMut(Event)>,
+        capture: bool,
+        passive: bool,
     ) -> RemoveEventHandler<Element> {
         let cb = Closure::wrap(cb);
         let key = intern(&delegation_key);
@@ -311,13 +381,13 @@ impl Dom {
 
         GLOBAL_EVENTS.with(|global_events| {
             let mut events = global_events.borrow_mut();
-            if !events.contains(&name) {
+            if !events.contains(&delegation_key) {
                 // create global handler
                 let key = JsValue::from_str(key);
                 let handler = move |ev: web_sys::Event| {
                     let target = ev.target();
                     let node = ev.composed_path().get(0);
-                    let mut node = if node.is_undefined() || node.is_null() {
+                    let node = if node.is_undefined() || node.is_null() {
                         JsValue::from(target)
                     } else {
                         node
@@ -326,38 +396,66 @@ impl Dom {
                     // TODO reverse Shadow DOM retargetting
                     // TODO simulate currentTarget
 
-                    while !node.is_null() {
+                    // returns `false` if the event should not propagate any further
+                    let dispatch = |node: &JsValue| {
                         let node_is_disabled = js_sys::Reflect::get(
-                            &node,
+                            node,
                             &JsValue::from_str("disabled"),
                         )
                         .unwrap()
                         .is_truthy();
                         if !node_is_disabled {
                             let maybe_handler =
-                                js_sys::Reflect::get(&node, &key).unwrap();
+                                js_sys::Reflect::get(node, &key).unwrap();
                             if !maybe_handler.is_undefined() {
                                 let f = maybe_handler
                                     .unchecked_ref::<js_sys::Function>();
-                                let _ = f.call1(&node, &ev);
+                                let _ = f.call1(node, &ev);
 
                                 if ev.cancel_bubble() {
-                                    return;
+                                    return false;
                                 }
                             }
                         }
+                        true
+                    };
 
-                        // navigate up tree
+                    // navigate up tree
+                    let parent = |node: &JsValue| {
                         if let Some(parent) =
                             node.unchecked_ref::<web_sys::Node>().parent_node()
                         {
-                            node = parent.into()
+                            parent.into()
                         } else if let Some(root) =
                             node.dyn_ref::<web_sys::ShadowRoot>()
                         {
-                            node = root.host().unchecked_into();
+                            root.host().unchecked_into()
                         } else {
-                            node = JsValue::null()
+                            JsValue::null()
+                        }
+                    };
+
+                    if capture {
+                        // captured listeners run from the outermost node inwards
+                        let mut path = Vec::new();
+                        let mut node = node;
+                        while !node.is_null() {
+                            let next = parent(&node);
+                            path.push(node);
+                            node = next;
+                        }
+                        for node in path.iter().rev() {
+                            if !dispatch(node) {
+                                return;
+                            }
+                        }
+                    } else {
+                        let mut node = node;
+                        while !node.is_null() {
+                            if !dispatch(&node) {
+                                return;
+                            }
+                            node = parent(&node);
                         }
                     }
                 };
@@ -365,15 +463,19 @@ impl Dom {
                 let handler =
                     Box::new(handler) as Box<dyn FnMut(web_sys::Event)>;
                 let handler = Closure::wrap(handler).into_js_value();
+                let options = AddEventListenerOptions::new();
+                options.set_capture(capture);
+                options.set_passive(passive);
                 window()
-                    .add_event_listener_with_callback(
+                    .add_event_listener_with_callback_and_add_event_listener_options(
                         &name,
                         handler.unchecked_ref(),
+                        &options,
                     )
                     .unwrap();
 
                 // register that we've created handler
-                events.insert(name);
+                events.insert(delegation_key.clone());
             }
         });
 
@@ -1,5 +1,7 @@
 use crate::view::{Mountable, ToTemplate};
-use std::{borrow::Cow, fmt::Debug};
+#[cfg(feature = "delegation")]
+use std::borrow::Cow;
+use std::fmt::Debug;
 use wasm_bindgen::JsValue;
 
 /// A DOM renderer.
@@ -159,6 +161,7 @@ pub trait DomRenderer: Renderer {
     /// Adds an event listener to an element, delegated to the window if possible.
     ///
     /// Returns a function to remove the listener.
+    #[cfg(feature = "delegation")]
     fn add_event_listener_delegated(
         el: &Self::Element,
         name: Cow<'static, str>,
@@ -54,19 +54,21 @@ where
 
 impl<V> AddAnyAttr for ViewTemplate<V>
 where
-    V: RenderHtml + ToTemplate + 'static,
+    V: RenderHtml + ToTemplate + AddAnyAttr + 'static,
     V::State: Mountable,
 {
-    type Output<SomeNewAttr: Attribute> = ViewTemplate<V>;
+    // the template is cached by type, so a view with additional attributes can't share it:
+    // it is rendered as an ordinary view instead
+    type Output<SomeNewAttr: Attribute> = V::Output<SomeNewAttr>;
 
     fn add_any_attr<NewAttr: Attribute>(
         self,
-        _attr: NewAttr,
+        attr: NewAttr,
     ) -> Self::Output<NewAttr>
     where
         Self::Output<NewAttr>: RenderHtml,
     {
-        panic!("AddAnyAttr not supported on ViewTemplate");
+        self.view.add_any_attr(attr)
     }
 }
 